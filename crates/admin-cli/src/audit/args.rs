@@ -0,0 +1,68 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use chrono::{DateTime, Utc};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(after_long_help = "\
+EXAMPLES:
+
+Show the 100 most recent mutating API calls:
+    $ nico-admin-cli audit
+
+Everything that touched a machine, as JSON:
+    $ nico-admin-cli -f json audit --target fm100htjtiaehv1n5vh67tbmqq4eabcjdng40f7jupsadbedhruh6rag1l0
+
+Force-deletes by one user during an incident window:
+    $ nico-admin-cli audit --method AdminForceDeleteMachine --principal alice \\
+        --since 2026-10-01T00:00:00Z --until 2026-10-02T00:00:00Z
+
+Page further back, from the smallest id the previous page showed:
+    $ nico-admin-cli audit --before-id 18342
+
+")]
+pub(crate) struct Opts {
+    #[clap(
+        long,
+        help = "Only calls to this method, e.g. SetMaintenance or 'POST /machine/{machine_id}/maintenance'"
+    )]
+    pub(super) method: Option<String>,
+
+    #[clap(long, help = "Only calls by a principal containing this text")]
+    pub(super) principal: Option<String>,
+
+    #[clap(long, help = "Only calls that named this object id")]
+    pub(super) target: Option<String>,
+
+    #[clap(long, help = "Only calls made at or after this RFC 3339 time")]
+    pub(super) since: Option<DateTime<Utc>>,
+
+    #[clap(long, help = "Only calls made before this RFC 3339 time")]
+    pub(super) until: Option<DateTime<Utc>>,
+
+    #[clap(long, help = "Only events older than this id, to page backwards")]
+    pub(super) before_id: Option<i64>,
+
+    #[clap(
+        long,
+        default_value_t = 100,
+        value_parser = clap::value_parser!(u32).range(1..=1000),
+        help = "Maximum events to show"
+    )]
+    pub(super) limit: u32,
+}
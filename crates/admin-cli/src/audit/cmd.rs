@@ -0,0 +1,116 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::OutputFormat;
+use ::rpc::forge::{AuditEvent, FindAuditEventsRequest};
+use prettytable::{Cell, Row, Table};
+use serde::Serialize;
+
+use super::Opts;
+use crate::errors::CarbideCliResult;
+use crate::rpc::ApiClient;
+
+#[derive(Serialize)]
+struct AuditEventOutput {
+    id: i64,
+    time: String,
+    source: String,
+    method: String,
+    principals: String,
+    targets: String,
+    outcome: String,
+    message: String,
+    client: String,
+    request: String,
+}
+
+impl From<AuditEvent> for AuditEventOutput {
+    fn from(event: AuditEvent) -> Self {
+        Self {
+            id: event.id,
+            time: event.occurred_at.unwrap_or_default().to_string(),
+            source: event.source().as_str_name().to_string(),
+            method: event.method,
+            principals: event.principals.join(", "),
+            targets: event.target_ids.join(", "),
+            outcome: event.outcome,
+            message: event.outcome_message.unwrap_or_default(),
+            client: event.client_address.unwrap_or_default(),
+            request: event.request_digest,
+        }
+    }
+}
+
+fn build_events_table(events: &[AuditEventOutput]) -> Table {
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Id"),
+        Cell::new("Time"),
+        Cell::new("Method"),
+        Cell::new("Principals"),
+        Cell::new("Targets"),
+        Cell::new("Outcome"),
+    ]));
+    for event in events {
+        let outcome = if event.message.is_empty() {
+            event.outcome.clone()
+        } else {
+            format!("{}: {}", event.outcome, event.message)
+        };
+        table.add_row(prettytable::row![
+            event.id,
+            event.time,
+            event.method,
+            event.principals,
+            event.targets,
+            outcome
+        ]);
+    }
+    table
+}
+
+pub(super) async fn find_audit_events(
+    opts: Opts,
+    api_client: &ApiClient,
+    format: OutputFormat,
+) -> CarbideCliResult<()> {
+    let request = FindAuditEventsRequest {
+        method: opts.method,
+        principal: opts.principal,
+        target_id: opts.target,
+        since: opts.since.map(Into::into),
+        until: opts.until.map(Into::into),
+        before_id: opts.before_id,
+        limit: Some(opts.limit),
+    };
+    let events = api_client.0.find_audit_events(request).await?.events;
+    if events.is_empty() && format == OutputFormat::AsciiTable {
+        println!("No audit events found");
+        return Ok(());
+    }
+
+    let output: Vec<AuditEventOutput> = events.into_iter().map(Into::into).collect();
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&output)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&output)?),
+        OutputFormat::Csv => {
+            build_events_table(&output).to_csv(std::io::stdout()).ok();
+        }
+        OutputFormat::AsciiTable => build_events_table(&output).printstd(),
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_test_support::Outcome::*;
+use carbide_test_support::scenarios;
+use clap::{CommandFactory, Parser};
+
+use super::args::*;
+
+#[test]
+fn verify_cmd_structure() {
+    Opts::command().debug_assert();
+}
+
+// Every filter is optional; --limit defaults to the server's default page and
+// is bounded by the server's maximum.
+#[test]
+fn parse_filters() {
+    scenarios!(
+        run = |argv| {
+            Opts::try_parse_from(argv.iter().copied())
+                .map(|opts| {
+                    (
+                        opts.method,
+                        opts.target,
+                        opts.since.map(|since| since.to_rfc3339()),
+                        opts.limit,
+                    )
+                })
+                .map_err(drop)
+        };
+        "no filters" {
+            &["audit"][..] => Yields((None, None, None, 100)),
+        }
+        "method and target" {
+            &["audit", "--method", "SetMaintenance", "--target", "m-1"][..] => Yields((
+                Some("SetMaintenance".to_string()),
+                Some("m-1".to_string()),
+                None,
+                100,
+            )),
+        }
+        "time window" {
+            &["audit", "--since", "2026-10-01T00:00:00Z", "--limit", "5"][..] => Yields((
+                None,
+                None,
+                Some("2026-10-01T00:00:00+00:00".to_string()),
+                5,
+            )),
+        }
+        "unparseable time" {
+            &["audit", "--since", "yesterday"][..] => Fails,
+        }
+        "limit out of range" {
+            &["audit", "--limit", "0"][..] => Fails,
+            &["audit", "--limit", "1001"][..] => Fails,
+        }
+    );
+}
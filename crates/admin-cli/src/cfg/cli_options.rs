@@ -18,7 +18,7 @@ use clap::{Parser, ValueEnum, ValueHint};
 use rpc::admin_cli::OutputFormat;
 
 use crate::{
-    attestation, bmc_machine, boot_interface, boot_override, browse, component_manager,
+    attestation, audit, bmc_machine, boot_interface, boot_override, browse, component_manager,
     compute_allocation, credential, devenv, domain, dpa, dpu, dpu_remediation, expected_machines,
     expected_power_shelf, expected_rack, expected_switch, extension_service, firmware,
     generate_docs, generate_man, generate_shell_complete, host, ib_partition, instance,
@@ -26,8 +26,9 @@ use crate::{
     machine_validation, managed_host, managed_switch, mlx, network_devices, network_security_group,
     network_segment, nvl_domain, nvl_logical_partition, nvl_partition, nvlink_nmxc_endpoints,
     operating_system, os_image, ping, power_shelf, rack, redfish, resource_pool, rms, route_server,
-    scout_stream, secrets, set, site_explorer, site_prefix, sku, spx_partition, ssh, switch,
-    tenant, tenant_keyset, tpm_ca, trim_table, version, vpc, vpc_peering, vpc_prefix,
+    scout_stream, secrets, set, site_explorer, site_prefix, sku, spx_partition, ssh,
+    state_analytics, switch, tenant, tenant_keyset, tpm_ca, trim_table, version, vpc, vpc_peering,
+    vpc_prefix,
 };
 
 const MAX_INTERNAL_PAGE_SIZE: usize = 100;
@@ -163,6 +164,8 @@ pub(crate) enum CliCommand {
         visible_alias = "att"
     )]
     Attestation(attestation::Cmd),
+    #[clap(about = "Search the audit trail of mutating API calls")]
+    Audit(audit::Opts),
     #[clap(
         about = "BMC Machine related handling",
         subcommand,
@@ -373,6 +376,8 @@ pub(crate) enum CliCommand {
     SpxPartition(spx_partition::Cmd),
     #[clap(about = "SSH Util functions", subcommand)]
     Ssh(ssh::Cmd),
+    #[clap(about = "Time in state, transitions and stuck objects across the fleet")]
+    StateAnalytics(state_analytics::Opts),
     #[clap(about = "Switch management", subcommand, visible_alias = "sw")]
     Switch(switch::Cmd),
     #[clap(about = "Tenant management", subcommand, visible_alias = "tm")]
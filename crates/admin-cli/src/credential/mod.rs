@@ -31,6 +31,7 @@ mod force_bmc;
 mod force_uefi;
 mod generate_ufm_cert;
 mod registry;
+mod revoke_certificate;
 mod rotate;
 mod rotation_status;
 
@@ -53,6 +54,8 @@ pub(crate) enum Cmd {
     DeleteUFM(delete_ufm::Args),
     #[clap(about = "Generate UFM credential")]
     GenerateUFMCert(generate_ufm_cert::Args),
+    #[clap(about = "Revoke a certificate issued by the local certificate authority")]
+    RevokeCertificate(revoke_certificate::Args),
     #[clap(about = "Add BMC credentials")]
     AddBMC(add_bmc::Args),
     #[clap(about = "Delete BMC credentials")]
@@ -0,0 +1,43 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use clap::Parser;
+use rpc::forge::RevokeCertificateRequest;
+
+#[derive(Parser, Debug, Clone)]
+#[command(after_long_help = "\
+EXAMPLES:
+
+Revoke a certificate issued by the local certificate authority:
+    $ nico-admin-cli credential revoke-certificate --serial-number 1f:2e:3d:4c
+
+")]
+pub(crate) struct Args {
+    #[clap(
+        long,
+        help = "Hex serial number of the certificate, optionally `:`-separated"
+    )]
+    pub(super) serial_number: String,
+}
+
+impl From<Args> for RevokeCertificateRequest {
+    fn from(args: Args) -> Self {
+        Self {
+            serial_number: args.serial_number,
+        }
+    }
+}
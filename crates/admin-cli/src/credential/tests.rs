@@ -239,6 +239,28 @@ fn parse_add_nic_lockdown_ikm() {
     );
 }
 
+// parse_revoke_certificate ensures revoke-certificate parses with the
+// required serial number.
+#[test]
+fn parse_revoke_certificate() {
+    let (cmd, matches) = parse_with_leaf_matches::<Cmd>(
+        &[
+            "credential",
+            "revoke-certificate",
+            "--serial-number",
+            "1f:2e:3d:4c",
+        ],
+        &["revoke-certificate"],
+    )
+    .expect("should parse revoke-certificate");
+
+    assert!(matches!(cmd, Cmd::RevokeCertificate(_)));
+    assert_eq!(
+        raw_value(&matches, "serial_number").as_deref(),
+        Some("1f:2e:3d:4c")
+    );
+}
+
 // Every malformed invocation is rejected at parse time -- a subcommand missing
 // its required flag, or a --kind value passed without the required `=` separator.
 #[test]
@@ -267,6 +289,10 @@ fn invalid_invocations_are_rejected() {
         "add-nic-lockdown-ikm without required --password" {
             &["credential", "add-nic-lockdown-ikm"][..] => Fails,
         }
+
+        "revoke-certificate without required --serial-number" {
+            &["credential", "revoke-certificate"][..] => Fails,
+        }
     );
 }
 
@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+mod record;
 mod show;
 
 // Cross-module re-exports for jump module
@@ -32,4 +33,10 @@ use crate::cfg::dispatch::Dispatch;
 pub(crate) enum Cmd {
     #[clap(about = "Display Domain information")]
     Show(show::Args),
+    #[dispatch]
+    #[clap(
+        subcommand,
+        about = "Manage the custom CNAME, TXT, SRV, MX and NS records of a domain"
+    )]
+    Record(record::Cmd),
 }
@@ -0,0 +1,83 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::domain::DomainId;
+use clap::{Parser, ValueEnum};
+
+#[derive(Parser, Debug)]
+#[command(after_long_help = "\
+EXAMPLES:
+
+Point www at another name:
+    $ nico-admin-cli domain record create 12345678-1234-5678-90ab-cdef01234567 www.example.com cname web-1.example.com.
+
+Publish an SRV record with a one minute TTL:
+    $ nico-admin-cli domain record create 12345678-1234-5678-90ab-cdef01234567 _sip._tcp.example.com srv '10 5 5060 sip.example.com.' --ttl 60
+
+")]
+pub(crate) struct Args {
+    #[clap(help = "The domain the record is served from")]
+    pub(crate) domain: DomainId,
+
+    #[clap(help = "The record's owner name, the domain itself or a name below it")]
+    pub(crate) qname: String,
+
+    #[clap(value_enum, ignore_case = true, help = "The record type")]
+    pub(crate) qtype: RecordType,
+
+    #[clap(help = "The record data in presentation format, e.g. `10 mail.example.com.` for MX")]
+    pub(crate) content: String,
+
+    #[clap(long, help = "TTL in seconds, the server default if not set")]
+    pub(crate) ttl: Option<u32>,
+}
+
+/// The record types that can be stored; address records come from machine
+/// and instance interfaces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "kebab_case")]
+pub(crate) enum RecordType {
+    Cname,
+    Txt,
+    Srv,
+    Mx,
+    Ns,
+}
+
+impl RecordType {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            RecordType::Cname => "CNAME",
+            RecordType::Txt => "TXT",
+            RecordType::Srv => "SRV",
+            RecordType::Mx => "MX",
+            RecordType::Ns => "NS",
+        }
+    }
+}
+
+impl From<Args> for ::rpc::protos::dns::CreateDnsCustomRecordRequest {
+    fn from(args: Args) -> Self {
+        Self {
+            domain_id: Some(args.domain),
+            qname: args.qname,
+            qtype: args.qtype.as_str().to_string(),
+            ttl: args.ttl,
+            content: args.content,
+        }
+    }
+}
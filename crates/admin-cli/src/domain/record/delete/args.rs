@@ -0,0 +1,42 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use clap::Parser;
+use uuid::Uuid;
+
+#[derive(Parser, Debug)]
+#[command(after_long_help = "\
+EXAMPLES:
+
+Delete a custom record:
+    $ nico-admin-cli domain record delete 12345678-1234-5678-90ab-cdef01234567
+
+")]
+pub(crate) struct Args {
+    #[clap(help = "ID of the record to delete, as shown by `domain record show`")]
+    pub(crate) id: Uuid,
+}
+
+impl From<Args> for ::rpc::protos::dns::DeleteDnsCustomRecordRequest {
+    fn from(args: Args) -> Self {
+        Self {
+            id: Some(::rpc::common::Uuid {
+                value: args.id.to_string(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod create;
+mod delete;
+mod show;
+mod update;
+
+use clap::Parser;
+
+use crate::cfg::dispatch::Dispatch;
+
+#[derive(Parser, Debug, Dispatch)]
+pub(crate) enum Cmd {
+    #[clap(about = "Store a custom DNS record for a domain")]
+    Create(create::Args),
+    #[clap(about = "Change the TTL and content of a custom DNS record")]
+    Update(update::Args),
+    #[clap(about = "Delete a custom DNS record")]
+    Delete(delete::Args),
+    #[clap(about = "Display the custom DNS records of one or all domains")]
+    Show(show::Args),
+}
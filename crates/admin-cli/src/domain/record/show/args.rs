@@ -0,0 +1,43 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::domain::DomainId;
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(after_long_help = "\
+EXAMPLES:
+
+List the custom records of all domains:
+    $ nico-admin-cli domain record show
+
+List the custom records of one domain:
+    $ nico-admin-cli domain record show --domain 12345678-1234-5678-90ab-cdef01234567
+
+")]
+pub(crate) struct Args {
+    #[clap(long, help = "Only show the records of this domain")]
+    pub(crate) domain: Option<DomainId>,
+}
+
+impl From<Args> for ::rpc::protos::dns::FindDnsCustomRecordsRequest {
+    fn from(args: Args) -> Self {
+        Self {
+            domain_id: args.domain,
+        }
+    }
+}
@@ -0,0 +1,70 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::OutputFormat;
+use ::rpc::protos::dns::DnsCustomRecord;
+use prettytable::{Table, row};
+
+use super::args::Args;
+use crate::errors::CarbideCliResult;
+use crate::rpc::ApiClient;
+
+/// Prints custom records as JSON or as a table; shared by the commands that
+/// return records.
+pub(in crate::domain::record) fn print_records(
+    records: &[DnsCustomRecord],
+    output_format: OutputFormat,
+) -> CarbideCliResult<()> {
+    if output_format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(records)?);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_titles(row!["Id", "Domain", "Name", "Type", "TTL", "Content"]);
+    for record in records {
+        table.add_row(row![
+            record
+                .id
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            record.domain_id.unwrap_or_default(),
+            record.qname,
+            record.qtype,
+            record
+                .ttl
+                .map(|ttl| ttl.to_string())
+                .unwrap_or_else(|| "default".to_string()),
+            record.content,
+        ]);
+    }
+    table.printstd();
+    Ok(())
+}
+
+pub(crate) async fn handle_show(
+    args: Args,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let records = api_client
+        .0
+        .find_dns_custom_records(::rpc::protos::dns::FindDnsCustomRecordsRequest::from(args))
+        .await?;
+    print_records(&records.records, output_format)
+}
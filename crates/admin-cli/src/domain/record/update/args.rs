@@ -0,0 +1,50 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use clap::Parser;
+use uuid::Uuid;
+
+#[derive(Parser, Debug)]
+#[command(after_long_help = "\
+EXAMPLES:
+
+Point a CNAME record elsewhere and lower its TTL:
+    $ nico-admin-cli domain record update 12345678-1234-5678-90ab-cdef01234567 web-2.example.com. --ttl 60
+
+")]
+pub(crate) struct Args {
+    #[clap(help = "ID of the record to update, as shown by `domain record show`")]
+    pub(crate) id: Uuid,
+
+    #[clap(help = "The new record data in presentation format")]
+    pub(crate) content: String,
+
+    #[clap(long, help = "TTL in seconds, the server default if not set")]
+    pub(crate) ttl: Option<u32>,
+}
+
+impl From<Args> for ::rpc::protos::dns::UpdateDnsCustomRecordRequest {
+    fn from(args: Args) -> Self {
+        Self {
+            id: Some(::rpc::common::Uuid {
+                value: args.id.to_string(),
+            }),
+            ttl: args.ttl,
+            content: args.content,
+        }
+    }
+}
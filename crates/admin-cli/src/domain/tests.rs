@@ -56,6 +56,7 @@ fn parse_show_variants() {
             Cmd::try_parse_from(argv.iter().copied())
                 .map(|cmd| match cmd {
                     Cmd::Show(args) => (args.all, args.domain.is_some()),
+                    Cmd::Record(_) => panic!("expected a show command"),
                 })
                 .map_err(drop)
         };
@@ -68,3 +69,77 @@ fn parse_show_variants() {
         }
     );
 }
+
+// record create takes the record type case-insensitively and sends it in
+// upper case; the yielded pair is the request's qtype and ttl. Types that are
+// derived from addresses (A, AAAA) cannot be stored.
+#[test]
+fn parse_record_create() {
+    const DOMAIN: &str = "12345678-1234-5678-90ab-cdef01234567";
+    scenarios!(
+        run = |argv| {
+            Cmd::try_parse_from(argv.iter().copied())
+                .map(|cmd| match cmd {
+                    Cmd::Record(record::Cmd::Create(args)) => {
+                        let request = ::rpc::protos::dns::CreateDnsCustomRecordRequest::from(args);
+                        (request.qtype, request.ttl)
+                    }
+                    _ => panic!("expected a record create command"),
+                })
+                .map_err(drop)
+        };
+        "CNAME with the default TTL" {
+            &["domain", "record", "create", DOMAIN, "www.example.com", "cname", "web.example.com."][..]
+                => Yields(("CNAME".to_string(), None)),
+        }
+
+        "SRV with a TTL" {
+            &[
+                "domain", "record", "create", DOMAIN, "_sip._tcp.example.com", "SRV",
+                "10 5 5060 sip.example.com.", "--ttl", "60",
+            ][..] => Yields(("SRV".to_string(), Some(60))),
+        }
+
+        "address records are not stored" {
+            &["domain", "record", "create", DOMAIN, "www.example.com", "a", "10.0.0.1"][..] => Fails,
+        }
+    );
+}
+
+// update, delete and show route to their own record subcommand; update and
+// delete require a record ID.
+#[test]
+fn parse_record_subcommands() {
+    scenarios!(
+        run = |argv| {
+            Cmd::try_parse_from(argv.iter().copied())
+                .map(|cmd| match cmd {
+                    Cmd::Record(record::Cmd::Update(_)) => "update",
+                    Cmd::Record(record::Cmd::Delete(_)) => "delete",
+                    Cmd::Record(record::Cmd::Show(_)) => "show",
+                    _ => panic!("expected a record command"),
+                })
+                .map_err(drop)
+        };
+        "update with a TTL" {
+            &[
+                "domain", "record", "update", "12345678-1234-5678-90ab-cdef01234567",
+                "web-2.example.com.", "--ttl", "60",
+            ][..] => Yields("update"),
+        }
+
+        "delete by ID" {
+            &["domain", "record", "delete", "12345678-1234-5678-90ab-cdef01234567"][..]
+                => Yields("delete"),
+        }
+
+        "delete without an ID" {
+            &["domain", "record", "delete"][..] => Fails,
+        }
+
+        "show one domain" {
+            &["domain", "record", "show", "--domain", "12345678-1234-5678-90ab-cdef01234567"][..]
+                => Yields("show"),
+        }
+    );
+}
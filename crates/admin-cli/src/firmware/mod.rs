@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+mod rollout;
 mod show;
 mod start_updates;
 
@@ -32,4 +33,6 @@ use crate::cfg::dispatch::Dispatch;
 pub(crate) enum Cmd {
     #[clap(about = "Show available firmware")]
     Show(show::Args),
+    #[clap(about = "Manage staged firmware rollouts", subcommand)]
+    Rollout(rollout::Cmd),
 }
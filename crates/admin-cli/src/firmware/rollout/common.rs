@@ -0,0 +1,192 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::OutputFormat;
+use ::rpc::forge::firmware_rollout_wave::Size;
+use ::rpc::forge::{FirmwareRollout, FirmwareRolloutWave};
+use prettytable::{Table, row};
+use serde::Serialize;
+
+use crate::errors::CarbideCliResult;
+
+/// Parses a wave size: a host count such as `1`, or a share of the fleet
+/// such as `10%`.
+pub(crate) fn parse_wave(value: &str) -> Result<FirmwareRolloutWave, String> {
+    let size = match value.strip_suffix('%') {
+        Some(percent) => match percent.parse::<u32>() {
+            Ok(percent @ 1..=100) => Size::Percent(percent),
+            _ => return Err(format!("{value} is not a percentage between 1% and 100%")),
+        },
+        None => match value.parse::<u32>() {
+            Ok(count @ 1..) => Size::Count(count),
+            _ => return Err(format!("{value} is not a host count or a percentage")),
+        },
+    };
+    Ok(FirmwareRolloutWave { size: Some(size) })
+}
+
+fn format_wave(wave: &FirmwareRolloutWave) -> String {
+    match wave.size {
+        Some(Size::Count(count)) => count.to_string(),
+        Some(Size::Percent(percent)) => format!("{percent}%"),
+        None => "?".to_string(),
+    }
+}
+
+#[derive(Serialize)]
+struct RolloutOutput {
+    name: String,
+    target: String,
+    state: String,
+    waves: String,
+    current_wave: String,
+    max_per_rack: Option<u32>,
+    max_per_nvlink_domain: Option<u32>,
+    soak_time: String,
+    max_failed_percent: u32,
+    hosts_started: u32,
+    hosts_succeeded: u32,
+    hosts_failed: u32,
+    wave_started_at: String,
+    wave_completed_at: String,
+    paused_reason: String,
+}
+
+impl From<FirmwareRollout> for RolloutOutput {
+    fn from(rollout: FirmwareRollout) -> Self {
+        let spec = rollout.spec.clone().unwrap_or_default();
+        Self {
+            target: rollout.target().as_str_name().to_string(),
+            state: rollout.state().as_str_name().to_string(),
+            waves: spec
+                .waves
+                .iter()
+                .map(format_wave)
+                .collect::<Vec<_>>()
+                .join(", "),
+            current_wave: format!("{} of {}", rollout.current_wave + 1, spec.waves.len()),
+            max_per_rack: spec.max_per_rack,
+            max_per_nvlink_domain: spec.max_per_nvlink_domain,
+            soak_time: spec
+                .soak_time
+                .map(|soak_time| soak_time.to_string())
+                .unwrap_or_default(),
+            max_failed_percent: spec.max_failed_percent,
+            hosts_started: rollout.hosts_started,
+            hosts_succeeded: rollout.hosts_succeeded,
+            hosts_failed: rollout.hosts_failed,
+            wave_started_at: rollout
+                .wave_started_at
+                .map(|t| t.to_string())
+                .unwrap_or_default(),
+            wave_completed_at: rollout
+                .wave_completed_at
+                .map(|t| t.to_string())
+                .unwrap_or_default(),
+            paused_reason: rollout.paused_reason.unwrap_or_default(),
+            name: rollout.name,
+        }
+    }
+}
+
+fn build_rollouts_table(rollouts: &[RolloutOutput], extended: bool) -> Table {
+    let mut table = Table::new();
+    if extended {
+        table.set_titles(row![
+            "Name",
+            "Target",
+            "State",
+            "Waves",
+            "Wave",
+            "Per Rack",
+            "Per NVLink Domain",
+            "Soak",
+            "Max Failed",
+            "Started",
+            "Succeeded",
+            "Failed",
+            "Wave Started",
+            "Wave Completed",
+            "Paused Reason",
+        ]);
+    } else {
+        table.set_titles(row![
+            "Name",
+            "Target",
+            "State",
+            "Wave",
+            "Started",
+            "Succeeded",
+            "Failed",
+            "Paused Reason",
+        ]);
+    }
+    let cap = |cap: Option<u32>| cap.map(|c| c.to_string()).unwrap_or_default();
+    for rollout in rollouts {
+        if extended {
+            table.add_row(row![
+                rollout.name,
+                rollout.target,
+                rollout.state,
+                rollout.waves,
+                rollout.current_wave,
+                cap(rollout.max_per_rack),
+                cap(rollout.max_per_nvlink_domain),
+                rollout.soak_time,
+                format!("{}%", rollout.max_failed_percent),
+                rollout.hosts_started,
+                rollout.hosts_succeeded,
+                rollout.hosts_failed,
+                rollout.wave_started_at,
+                rollout.wave_completed_at,
+                rollout.paused_reason,
+            ]);
+        } else {
+            table.add_row(row![
+                rollout.name,
+                rollout.target,
+                rollout.state,
+                rollout.current_wave,
+                rollout.hosts_started,
+                rollout.hosts_succeeded,
+                rollout.hosts_failed,
+                rollout.paused_reason,
+            ]);
+        }
+    }
+    table
+}
+
+/// Prints rollouts in the requested format.
+pub(super) fn print_rollouts(
+    rollouts: Vec<FirmwareRollout>,
+    format: OutputFormat,
+    extended: bool,
+) -> CarbideCliResult<()> {
+    let output: Vec<RolloutOutput> = rollouts.into_iter().map(Into::into).collect();
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&output)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&output)?),
+        OutputFormat::Csv => {
+            build_rollouts_table(&output, true)
+                .to_csv(std::io::stdout())
+                .ok();
+        }
+        OutputFormat::AsciiTable => build_rollouts_table(&output, extended).printstd(),
+    }
+    Ok(())
+}
@@ -0,0 +1,107 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::forge::{
+    CreateFirmwareRolloutRequest, FirmwareRolloutSpec, FirmwareRolloutTarget, FirmwareRolloutWave,
+};
+use clap::{Parser, ValueEnum};
+
+use super::super::common::parse_wave;
+
+#[derive(Parser, Debug, Clone)]
+#[command(after_long_help = "\
+EXAMPLES:
+
+Update one canary host, then 10% of hosts, then the rest, at most two hosts
+per rack at a time, soaking each wave for an hour:
+    $ nico-admin-cli firmware rollout create --name bios-2026-10 --target host-firmware \\
+        --waves 1,10%,100% --max-per-rack 2 --soak-minutes 60
+
+Roll out DPU NIC firmware, pausing as soon as any host in a wave fails:
+    $ nico-admin-cli firmware rollout create --name nic-32.43 --target dpu-nic-firmware \\
+        --waves 5,50%,100% --max-failed-percent 0
+
+")]
+pub(crate) struct Args {
+    #[clap(long, help = "Unique name of the rollout")]
+    pub(super) name: String,
+
+    #[clap(long, value_enum, help = "The firmware update the rollout gates")]
+    pub(super) target: RolloutTarget,
+
+    #[clap(
+        long,
+        required = true,
+        value_delimiter = ',',
+        value_parser = parse_wave,
+        help = "Cumulative wave sizes, each a host count or a percentage, e.g. 1,10%,100%"
+    )]
+    pub(super) waves: Vec<FirmwareRolloutWave>,
+
+    #[clap(long, help = "Most hosts of one rack updating at once")]
+    pub(super) max_per_rack: Option<u32>,
+
+    #[clap(long, help = "Most hosts of one NVLink domain updating at once")]
+    pub(super) max_per_nvlink_domain: Option<u32>,
+
+    #[clap(
+        long,
+        default_value_t = 60,
+        help = "Minutes a finished wave must soak before the next one starts"
+    )]
+    pub(super) soak_minutes: u64,
+
+    #[clap(
+        long,
+        default_value_t = 0,
+        value_parser = clap::value_parser!(u32).range(0..=100),
+        help = "Pause once more than this percentage of a wave's hosts failed"
+    )]
+    pub(super) max_failed_percent: u32,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+#[clap(rename_all = "kebab_case")]
+pub(crate) enum RolloutTarget {
+    HostFirmware,
+    DpuNicFirmware,
+}
+
+impl From<RolloutTarget> for FirmwareRolloutTarget {
+    fn from(target: RolloutTarget) -> Self {
+        match target {
+            RolloutTarget::HostFirmware => FirmwareRolloutTarget::HostFirmware,
+            RolloutTarget::DpuNicFirmware => FirmwareRolloutTarget::DpuNicFirmware,
+        }
+    }
+}
+
+impl From<Args> for CreateFirmwareRolloutRequest {
+    fn from(args: Args) -> Self {
+        Self {
+            name: args.name,
+            target: FirmwareRolloutTarget::from(args.target).into(),
+            spec: Some(FirmwareRolloutSpec {
+                waves: args.waves,
+                max_per_rack: args.max_per_rack,
+                max_per_nvlink_domain: args.max_per_nvlink_domain,
+                soak_time: Some(std::time::Duration::from_secs(args.soak_minutes * 60).into()),
+                max_failed_percent: args.max_failed_percent,
+            }),
+        }
+    }
+}
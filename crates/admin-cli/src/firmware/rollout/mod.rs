@@ -0,0 +1,59 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+mod create;
+mod delete;
+mod pause;
+mod resume;
+mod show;
+
+use clap::Parser;
+#[cfg(test)]
+pub(super) use common::parse_wave;
+
+use crate::cfg::dispatch::Dispatch;
+use crate::cfg::run::Run;
+use crate::cfg::runtime::RuntimeContext;
+use crate::errors::CarbideCliResult;
+
+#[derive(Parser, Debug, Clone, Dispatch)]
+#[clap(rename_all = "kebab_case")]
+pub(crate) enum Cmd {
+    #[clap(about = "Start a staged rollout for a firmware update target")]
+    Create(create::Args),
+    #[clap(about = "Show firmware rollouts and their progress")]
+    Show(show::Args),
+    #[clap(about = "Stop a rollout from starting more updates")]
+    Pause(pause::Args),
+    #[clap(about = "Resume a paused rollout")]
+    Resume(resume::Args),
+    #[clap(about = "Delete a rollout, releasing its target to update freely")]
+    Delete(delete::Args),
+}
+
+impl Run for Cmd {
+    async fn run(self, ctx: &mut RuntimeContext) -> CarbideCliResult<()> {
+        match self {
+            Cmd::Create(args) => args.run(ctx).await,
+            Cmd::Show(args) => args.run(ctx).await,
+            Cmd::Pause(args) => args.run(ctx).await,
+            Cmd::Resume(args) => args.run(ctx).await,
+            Cmd::Delete(args) => args.run(ctx).await,
+        }
+    }
+}
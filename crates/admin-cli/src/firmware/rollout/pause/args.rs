@@ -0,0 +1,44 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::forge::PauseFirmwareRolloutRequest;
+use clap::Parser;
+
+#[derive(Parser, Debug, Clone)]
+#[command(after_long_help = "\
+EXAMPLES:
+
+Hold a rollout while a vendor issue is investigated:
+    $ nico-admin-cli firmware rollout pause bios-2026-10 --reason 'vendor advisory 1234'
+
+")]
+pub(crate) struct Args {
+    #[clap(help = "Name of the rollout")]
+    pub(super) name: String,
+
+    #[clap(long, help = "Why the rollout is paused, shown with the rollout")]
+    pub(super) reason: Option<String>,
+}
+
+impl From<Args> for PauseFirmwareRolloutRequest {
+    fn from(args: Args) -> Self {
+        Self {
+            name: args.name,
+            reason: args.reason.unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::OutputFormat;
+use ::rpc::forge::FindFirmwareRolloutsRequest;
+
+use super::super::common::print_rollouts;
+use super::args::Args;
+use crate::errors::CarbideCliResult;
+use crate::rpc::ApiClient;
+
+pub(super) async fn show(
+    args: Args,
+    api_client: &ApiClient,
+    format: OutputFormat,
+    extended: bool,
+) -> CarbideCliResult<()> {
+    let rollouts = api_client
+        .0
+        .find_firmware_rollouts(FindFirmwareRolloutsRequest { name: args.name })
+        .await?
+        .rollouts;
+    if rollouts.is_empty() && format == OutputFormat::AsciiTable {
+        println!("No firmware rollouts found");
+        return Ok(());
+    }
+    print_rollouts(rollouts, format, extended)
+}
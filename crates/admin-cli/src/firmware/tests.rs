@@ -22,8 +22,13 @@
 //
 // Command Structure - Baseline debug_assert() of the entire command.
 // Argument Parsing  - Ensure required/optional arg combinations parse correctly.
+// Custom Validators - Test external input validation functions.
 
-use clap::CommandFactory;
+use ::rpc::forge::firmware_rollout_wave::Size;
+use ::rpc::forge::{CreateFirmwareRolloutRequest, FirmwareRolloutTarget};
+use carbide_test_support::Outcome::*;
+use carbide_test_support::scenarios;
+use clap::{CommandFactory, Parser};
 
 use super::*;
 
@@ -36,3 +41,93 @@ use super::*;
 fn verify_cmd_structure() {
     Cmd::command().debug_assert();
 }
+
+/////////////////////////////////////////////////////////////////////////////
+// Argument Parsing
+
+// rollout create carries its schedule and limits into the create request,
+// with the soak given in minutes.
+#[test]
+fn parse_rollout_create() {
+    let cmd = Cmd::try_parse_from([
+        "firmware",
+        "rollout",
+        "create",
+        "--name",
+        "bios-2026-10",
+        "--target",
+        "host-firmware",
+        "--waves",
+        "1,10%,100%",
+        "--max-per-rack",
+        "2",
+        "--soak-minutes",
+        "30",
+    ])
+    .expect("valid create arguments");
+    let Cmd::Rollout(rollout::Cmd::Create(args)) = cmd else {
+        panic!("expected rollout create, got {cmd:?}");
+    };
+    let request = CreateFirmwareRolloutRequest::from(args);
+    assert_eq!(request.name, "bios-2026-10");
+    assert_eq!(request.target(), FirmwareRolloutTarget::HostFirmware);
+    let spec = request.spec.expect("create always sends a spec");
+    assert_eq!(
+        spec.waves.iter().map(|w| w.size).collect::<Vec<_>>(),
+        vec![
+            Some(Size::Count(1)),
+            Some(Size::Percent(10)),
+            Some(Size::Percent(100))
+        ]
+    );
+    assert_eq!(spec.max_per_rack, Some(2));
+    assert_eq!(spec.max_per_nvlink_domain, None);
+    assert_eq!(spec.soak_time.map(|d| d.seconds), Some(1800));
+    assert_eq!(spec.max_failed_percent, 0);
+}
+
+// rollout create needs a name, a target and at least one wave.
+#[test]
+fn parse_rollout_create_required_args() {
+    scenarios!(
+        run = |argv: &[&str]| Cmd::try_parse_from(argv).map(drop).map_err(drop);
+        "all required args" {
+            &["firmware", "rollout", "create", "--name", "n", "--target", "dpu-nic-firmware", "--waves", "5"][..] => Yields(()),
+        }
+        "missing waves" {
+            &["firmware", "rollout", "create", "--name", "n", "--target", "dpu-nic-firmware"][..] => Fails,
+        }
+        "unknown target" {
+            &["firmware", "rollout", "create", "--name", "n", "--target", "bmc", "--waves", "5"][..] => Fails,
+        }
+        "threshold above 100" {
+            &["firmware", "rollout", "create", "--name", "n", "--target", "host-firmware", "--waves", "5", "--max-failed-percent", "101"][..] => Fails,
+        }
+    );
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Custom Validators
+
+#[test]
+fn rollout_wave_sizes() {
+    scenarios!(
+        run = |value: &str| rollout::parse_wave(value).map(|wave| wave.size.unwrap()).map_err(drop);
+        "host counts" {
+            "1" => Yields(Size::Count(1)),
+            "250" => Yields(Size::Count(250)),
+            "0" => Fails,
+        }
+        "percentages" {
+            "10%" => Yields(Size::Percent(10)),
+            "100%" => Yields(Size::Percent(100)),
+            "0%" => Fails,
+            "101%" => Fails,
+        }
+        "garbage" {
+            "" => Fails,
+            "ten" => Fails,
+            "-1" => Fails,
+        }
+    );
+}
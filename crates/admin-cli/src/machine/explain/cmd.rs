@@ -0,0 +1,181 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use ::rpc::admin_cli::OutputFormat;
+use ::rpc::forge::{
+    ControllerStateOutcome, ExplainMachineStateHandlingRequest,
+    ExplainMachineStateHandlingResponse, StateHandlerSideEffect,
+};
+use prettytable::{Row, Table, row};
+use serde::Serialize;
+
+use super::args::Args;
+use crate::errors::CarbideCliResult;
+use crate::rpc::ApiClient;
+
+#[derive(Serialize)]
+struct ExplanationOutput {
+    machine_id: String,
+    state: serde_json::Value,
+    state_version: String,
+    time_in_state_above_sla: bool,
+    outcome: &'static str,
+    message: Option<String>,
+    source: Option<String>,
+    next_state: Option<serde_json::Value>,
+    recheck_at: Option<String>,
+    side_effects: Vec<SideEffectOutput>,
+}
+
+#[derive(Serialize)]
+struct SideEffectOutput {
+    service: String,
+    operation: String,
+    target: String,
+    details: Option<String>,
+}
+
+impl From<ExplainMachineStateHandlingResponse> for ExplanationOutput {
+    fn from(explanation: ExplainMachineStateHandlingResponse) -> Self {
+        let reason = explanation.reason.unwrap_or_default();
+        Self {
+            machine_id: explanation.machine_id.unwrap_or_default().to_string(),
+            state: state_value(explanation.state),
+            state_version: explanation.state_version,
+            time_in_state_above_sla: explanation.time_in_state_above_sla,
+            outcome: outcome_name(reason.outcome()),
+            message: reason.outcome_msg,
+            source: reason
+                .source_ref
+                .map(|source_ref| format!("{}:{}", source_ref.file, source_ref.line)),
+            next_state: explanation.next_state.map(state_value),
+            recheck_at: explanation.recheck_at.map(|at| at.to_string()),
+            side_effects: explanation
+                .side_effects
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+impl From<StateHandlerSideEffect> for SideEffectOutput {
+    fn from(side_effect: StateHandlerSideEffect) -> Self {
+        Self {
+            service: side_effect.service,
+            operation: side_effect.operation,
+            target: side_effect.target,
+            details: side_effect.details,
+        }
+    }
+}
+
+/// States are sent as JSON; keep them structured in JSON and YAML output.
+fn state_value(state: String) -> serde_json::Value {
+    serde_json::from_str(&state).unwrap_or(serde_json::Value::String(state))
+}
+
+fn outcome_name(outcome: ControllerStateOutcome) -> &'static str {
+    match outcome {
+        ControllerStateOutcome::Wait => "Wait",
+        ControllerStateOutcome::Error => "Error",
+        ControllerStateOutcome::Transition => "Transition",
+        ControllerStateOutcome::DoNothing => "DoNothing",
+        ControllerStateOutcome::Todo => "Todo",
+    }
+}
+
+fn build_summary_table(explanation: &ExplanationOutput) -> Table {
+    let mut table = Table::new();
+    table.add_row(row!["Machine ID", explanation.machine_id]);
+    table.add_row(row!["State", explanation.state]);
+    table.add_row(row!["State version", explanation.state_version]);
+    table.add_row(row![
+        "Above SLA",
+        if explanation.time_in_state_above_sla {
+            "yes (Wait and DoNothing are reported as errors)"
+        } else {
+            "no"
+        }
+    ]);
+    table.add_row(row!["Outcome", explanation.outcome]);
+    if let Some(message) = &explanation.message {
+        table.add_row(row!["Reason", message]);
+    }
+    if let Some(next_state) = &explanation.next_state {
+        table.add_row(row!["Next state", next_state]);
+    }
+    if let Some(recheck_at) = &explanation.recheck_at {
+        table.add_row(row!["Recheck at", recheck_at]);
+    }
+    if let Some(source) = &explanation.source {
+        table.add_row(row!["Source", source]);
+    }
+    table
+}
+
+fn build_side_effects_table(side_effects: &[SideEffectOutput]) -> Table {
+    let mut table = Table::new();
+    table.set_titles(Row::from(vec!["Service", "Operation", "Target", "Details"]));
+    for side_effect in side_effects {
+        table.add_row(row![
+            side_effect.service,
+            side_effect.operation,
+            if side_effect.target.is_empty() {
+                "---"
+            } else {
+                side_effect.target.as_str()
+            },
+            side_effect.details.as_deref().unwrap_or("---")
+        ]);
+    }
+    table
+}
+
+pub(super) async fn explain(
+    args: Args,
+    api_client: &ApiClient,
+    format: OutputFormat,
+) -> CarbideCliResult<()> {
+    let request = ExplainMachineStateHandlingRequest {
+        machine_id: Some(args.machine),
+    };
+    let output: ExplanationOutput = api_client
+        .0
+        .explain_machine_state_handling(request)
+        .await?
+        .into();
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&output)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&output)?),
+        OutputFormat::Csv => {
+            build_side_effects_table(&output.side_effects)
+                .to_csv(std::io::stdout())
+                .ok();
+        }
+        OutputFormat::AsciiTable => {
+            build_summary_table(&output).printstd();
+            if output.side_effects.is_empty() {
+                println!("No side effects");
+            } else {
+                println!("Side effects, in order (none were carried out):");
+                build_side_effects_table(&output.side_effects).printstd();
+            }
+        }
+    }
+    Ok(())
+}
@@ -17,6 +17,7 @@
 
 mod auto_update;
 mod common;
+mod explain;
 mod force_delete;
 mod hardware_info;
 mod health_report;
@@ -25,6 +26,7 @@ pub(crate) mod network;
 mod nvlink_info;
 mod positions;
 mod reboot;
+mod sanitization_certificates;
 mod show;
 
 #[cfg(test)]
@@ -84,6 +86,29 @@ pub(crate) enum Cmd {
             - Power shelf ID: Associated power shelf"
     )]
     Positions(positions::Args),
+    #[clap(
+        about = "Show disk sanitization certificates recorded on deprovisioning",
+        long_about = "Show disk sanitization certificates recorded on deprovisioning.\n\n\
+            Scout records how it sanitized each storage device when a machine is\n\
+            cleaned up, following NIST SP 800-88, and signs the record with the\n\
+            machine's client certificate. Each certificate lists, per device:\n\
+            - Serial number, model and firmware revision\n\
+            - Method (Clear or Purge) and the command used\n\
+            - Result, and the sampled read-back verification verdict"
+    )]
+    SanitizationCertificates(sanitization_certificates::Args),
+    #[clap(
+        about = "Explain what the state machine would do next for a host",
+        long_about = "Explain what the state machine would do next for a host.\n\n\
+            Runs the machine state handler once against the host's current state\n\
+            without letting it change anything. Redfish, IPMI, DPF, credential and\n\
+            database writes are recorded instead of carried out; reads still go to\n\
+            the real systems. Shows:\n\
+            - Outcome (Wait, Transition, DoNothing or Error) and its reason\n\
+            - The source location that produced the outcome\n\
+            - The side effects the handler would have had, in order"
+    )]
+    Explain(explain::Args),
     #[clap(subcommand, about = "Update/show NVLink info for an MNNVL machine")]
     NvlinkInfo(nvlink_info::Args),
 }
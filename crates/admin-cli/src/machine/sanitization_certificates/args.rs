@@ -0,0 +1,53 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use clap::{ArgGroup, Parser};
+
+#[derive(Parser, Debug)]
+#[clap(group(ArgGroup::new("certificate_filter").required(true).multiple(true).args(&["machine", "instance"])))]
+#[command(after_long_help = "\
+EXAMPLES:
+
+Show the disk sanitization certificates recorded for a machine:
+    $ nico-admin-cli machine sanitization-certificates --machine fm100ht038bg3qsho433vkg684heguv282qaggmrsh2ugn1qk096n2c6hcg
+
+Show the certificate for a released instance:
+    $ nico-admin-cli machine sanitization-certificates --instance 9a3c7f2e-5b1d-4e8a-9c0f-2d6b8e4a1c73
+
+Print the signed certificates, to hand to a tenant or verify independently:
+    $ nico-admin-cli machine sanitization-certificates --instance 9a3c7f2e-5b1d-4e8a-9c0f-2d6b8e4a1c73 --signed
+
+")]
+pub(crate) struct Args {
+    #[clap(short = 'm', long, help = "The machine whose certificates to show")]
+    pub(super) machine: Option<MachineId>,
+
+    #[clap(
+        short = 'i',
+        long,
+        help = "The released instance whose certificate to show"
+    )]
+    pub(super) instance: Option<InstanceId>,
+
+    #[clap(
+        long,
+        help = "Print each certificate as the machine signed it (a compact JWS), one per line"
+    )]
+    pub(super) signed: bool,
+}
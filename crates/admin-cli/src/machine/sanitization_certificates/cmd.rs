@@ -0,0 +1,231 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::OutputFormat;
+use ::rpc::forge::{
+    DiskSanitizationCertificate, DiskSanitizationCertificateQuery, DiskSanitizationDevice,
+    DiskSanitizationMedia, DiskSanitizationMethod, DiskSanitizationVerdict,
+    DiskSanitizationVerification,
+};
+use prettytable::{Row, Table, row};
+use serde::Serialize;
+
+use super::args::Args;
+use crate::errors::CarbideCliResult;
+use crate::rpc::ApiClient;
+
+#[derive(Serialize)]
+struct CertificateOutput {
+    id: String,
+    machine_id: String,
+    instance_id: Option<String>,
+    tenant_organization_id: Option<String>,
+    released_at: Option<String>,
+    sanitized_at: Option<String>,
+    signer: String,
+    standard: String,
+    devices: Vec<DeviceOutput>,
+}
+
+#[derive(Serialize)]
+struct DeviceOutput {
+    device: String,
+    media: &'static str,
+    serial_number: String,
+    model: String,
+    firmware_revision: String,
+    capacity_bytes: u64,
+    method: &'static str,
+    technique: String,
+    started_at: String,
+    finished_at: String,
+    succeeded: bool,
+    error: Option<String>,
+    verification: String,
+}
+
+impl From<DiskSanitizationCertificate> for CertificateOutput {
+    fn from(certificate: DiskSanitizationCertificate) -> Self {
+        Self {
+            id: certificate.id,
+            machine_id: certificate.machine_id.unwrap_or_default().to_string(),
+            instance_id: certificate.instance_id.map(|id| id.to_string()),
+            tenant_organization_id: certificate.tenant_organization_id,
+            released_at: certificate.released_at.map(|at| at.to_string()),
+            sanitized_at: certificate.sanitized_at.map(|at| at.to_string()),
+            signer: certificate.signer,
+            standard: certificate.standard,
+            devices: certificate.devices.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<DiskSanitizationDevice> for DeviceOutput {
+    fn from(device: DiskSanitizationDevice) -> Self {
+        Self {
+            media: media_name(device.media()),
+            method: method_name(device.method()),
+            verification: device
+                .verification
+                .as_ref()
+                .map(verification_summary)
+                .unwrap_or_default(),
+            device: device.device,
+            serial_number: device.serial_number,
+            model: device.model,
+            firmware_revision: device.firmware_revision,
+            capacity_bytes: device.capacity_bytes,
+            technique: device.technique,
+            started_at: device.started_at.unwrap_or_default().to_string(),
+            finished_at: device.finished_at.unwrap_or_default().to_string(),
+            succeeded: device.succeeded,
+            error: device.error,
+        }
+    }
+}
+
+fn media_name(media: DiskSanitizationMedia) -> &'static str {
+    match media {
+        DiskSanitizationMedia::Nvme => "NVMe",
+        DiskSanitizationMedia::Sata => "SATA",
+        DiskSanitizationMedia::Sas => "SAS",
+    }
+}
+
+fn method_name(method: DiskSanitizationMethod) -> &'static str {
+    match method {
+        DiskSanitizationMethod::Clear => "Clear",
+        DiskSanitizationMethod::Purge => "Purge",
+    }
+}
+
+fn verification_summary(verification: &DiskSanitizationVerification) -> String {
+    let verdict = match verification.verdict() {
+        DiskSanitizationVerdict::NotPerformed => "not performed",
+        DiskSanitizationVerdict::Passed => "passed",
+        DiskSanitizationVerdict::Failed => "failed",
+    };
+    match &verification.note {
+        Some(note) => format!("{verdict}: {note}"),
+        None => format!(
+            "{verdict} ({} changed, {} unchanged, {} inconclusive of {} samples)",
+            verification.changed,
+            verification.unchanged,
+            verification.inconclusive,
+            verification.samples
+        ),
+    }
+}
+
+/// One row per device. A certificate scout has not reported yet gets a
+/// single row saying so.
+fn build_certificates_table(certificates: &[CertificateOutput]) -> Table {
+    let mut table = Table::new();
+    table.set_titles(Row::from(vec![
+        "Machine ID",
+        "Instance",
+        "Tenant",
+        "Sanitized",
+        "Device",
+        "Serial",
+        "Method",
+        "Result",
+        "Verification",
+    ]));
+    for certificate in certificates {
+        let instance = certificate.instance_id.as_deref().unwrap_or("---");
+        let tenant = certificate
+            .tenant_organization_id
+            .as_deref()
+            .unwrap_or("---");
+        let Some(sanitized_at) = &certificate.sanitized_at else {
+            table.add_row(row![
+                certificate.machine_id,
+                instance,
+                tenant,
+                "pending",
+                "---",
+                "---",
+                "---",
+                "---",
+                "---"
+            ]);
+            continue;
+        };
+        for device in &certificate.devices {
+            let result = match &device.error {
+                Some(error) => format!("failed: {error}"),
+                None if device.succeeded => "ok".to_string(),
+                None => "failed".to_string(),
+            };
+            table.add_row(row![
+                certificate.machine_id,
+                instance,
+                tenant,
+                sanitized_at,
+                format!("{} ({})", device.device, device.media),
+                device.serial_number,
+                device.method,
+                result,
+                device.verification
+            ]);
+        }
+    }
+    table
+}
+
+pub(super) async fn sanitization_certificates(
+    args: Args,
+    api_client: &ApiClient,
+    format: OutputFormat,
+) -> CarbideCliResult<()> {
+    let request = DiskSanitizationCertificateQuery {
+        machine_id: args.machine,
+        instance_id: args.instance,
+    };
+    let certificates = api_client
+        .0
+        .find_disk_sanitization_certificates(request)
+        .await?
+        .certificates;
+
+    if args.signed {
+        for certificate in certificates {
+            if !certificate.signed_certificate.is_empty() {
+                println!("{}", certificate.signed_certificate);
+            }
+        }
+        return Ok(());
+    }
+    if certificates.is_empty() && format == OutputFormat::AsciiTable {
+        println!("No disk sanitization certificates found");
+        return Ok(());
+    }
+
+    let output: Vec<CertificateOutput> = certificates.into_iter().map(Into::into).collect();
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&output)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&output)?),
+        OutputFormat::Csv => {
+            build_certificates_table(&output)
+                .to_csv(std::io::stdout())
+                .ok();
+        }
+        OutputFormat::AsciiTable => build_certificates_table(&output).printstd(),
+    }
+    Ok(())
+}
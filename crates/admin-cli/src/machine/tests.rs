@@ -261,6 +261,52 @@ fn parse_positions() {
     assert!(matches.get_many::<MachineId>("machine").is_none());
 }
 
+// sanitization-certificates needs a machine or an instance to look up.
+#[test]
+fn parse_sanitization_certificates() {
+    scenarios!(
+        run = |argv| {
+            Cmd::try_parse_from(argv.iter().copied())
+                .map(|_| ())
+                .map_err(drop)
+        };
+        "filters" {
+            &["machine", "sanitization-certificates", "--machine", TEST_MACHINE_ID][..] => Yields(()),
+            &[
+                "machine",
+                "sanitization-certificates",
+                "--instance",
+                "9a3c7f2e-5b1d-4e8a-9c0f-2d6b8e4a1c73",
+                "--signed",
+            ][..] => Yields(()),
+        }
+        "no filter" {
+            &["machine", "sanitization-certificates"][..] => Fails,
+        }
+    );
+}
+
+// explain takes exactly one machine ID.
+#[test]
+fn parse_explain() {
+    scenarios!(
+        run = |argv| {
+            Cmd::try_parse_from(argv.iter().copied())
+                .map(|cmd| match cmd {
+                    Cmd::Explain(args) => args.machine.to_string(),
+                    _ => panic!("expected Explain variant"),
+                })
+                .map_err(drop)
+        };
+        "machine ID" {
+            &["machine", "explain", TEST_MACHINE_ID][..] => Yields(TEST_MACHINE_ID.to_string()),
+        }
+        "no machine ID" {
+            &["machine", "explain"][..] => Fails,
+        }
+    );
+}
+
 /////////////////////////////////////////////////////////////////////////////
 // ValueEnum Parsing
 //
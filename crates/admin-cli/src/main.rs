@@ -45,6 +45,7 @@ use crate::rpc::ApiClient;
 
 mod async_write;
 mod attestation;
+mod audit;
 mod bmc_machine;
 mod bmc_role;
 mod boot_interface;
@@ -112,6 +113,7 @@ mod site_prefix;
 mod sku;
 mod spx_partition;
 mod ssh;
+mod state_analytics;
 mod switch;
 mod tenant;
 mod tenant_keyset;
@@ -225,6 +227,7 @@ async fn main() -> color_eyre::Result<()> {
     // Command to talk to Carbide API.
     match command {
         CliCommand::Attestation(cmd) => cmd.dispatch(ctx).await?,
+        CliCommand::Audit(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::BmcMachine(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::BootInterface(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::BootOverride(cmd) => cmd.dispatch(ctx).await?,
@@ -278,6 +281,7 @@ async fn main() -> color_eyre::Result<()> {
         CliCommand::Secrets(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Set(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Ssh(cmd) => cmd.dispatch(ctx).await?,
+        CliCommand::StateAnalytics(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::SiteExplorer(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::SitePrefix(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Sku(cmd) => cmd.dispatch(ctx).await?,
@@ -146,6 +146,14 @@ fn power_options_show_one(
             .unwrap_or_default(),
     )?;
 
+    if let Some(idle_parked_at) = power_option.idle_parked_at {
+        writeln!(
+            &mut lines,
+            "{:<width$}: {} (Powered off by the idle host power policy)",
+            "Idle Parked At", idle_parked_at
+        )?;
+    }
+
     print!("{lines}");
     Ok(())
 }
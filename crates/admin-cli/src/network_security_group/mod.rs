@@ -22,6 +22,7 @@ mod delete;
 mod detach;
 mod show;
 mod show_attachments;
+mod simulate;
 mod update;
 
 #[cfg(test)]
@@ -66,4 +67,10 @@ pub(crate) enum Cmd {
         visible_alias = "r"
     )]
     Detach(detach::Args),
+
+    #[clap(
+        about = "Simulate whether a flow to or from an instance would be permitted",
+        visible_alias = "sim"
+    )]
+    Simulate(simulate::Args),
 }
@@ -0,0 +1,112 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use clap::{ArgGroup, Parser, ValueEnum};
+use rpc::forge::{
+    NetworkSecurityGroupFlowEndpoint, NetworkSecurityGroupRuleProtocol,
+    SimulateNetworkSecurityGroupFlowRequest,
+};
+
+#[derive(Parser, Debug, Clone)]
+#[clap(group(ArgGroup::new("source").required(true).multiple(true).args(&["src_instance", "src_ip"])))]
+#[clap(group(ArgGroup::new("destination").required(true).multiple(true).args(&["dst_instance", "dst_ip"])))]
+#[command(after_long_help = "\
+EXAMPLES:
+
+Would HTTPS from the internet reach an instance?
+    $ nico-admin-cli network-security-group simulate --src-ip 203.0.113.7 \\
+    --dst-instance 12345678-1234-5678-90ab-cdef01234567 --protocol tcp --dst-port 443
+
+Can one instance reach another over SSH?
+    $ nico-admin-cli network-security-group simulate \\
+    --src-instance 12345678-1234-5678-90ab-cdef01234567 \\
+    --dst-instance 89abcdef-1234-5678-90ab-cdef01234567 --protocol tcp --dst-port 22
+
+")]
+pub(crate) struct Args {
+    #[clap(long, help = "Instance sending the traffic")]
+    pub(super) src_instance: Option<String>,
+
+    #[clap(
+        long,
+        help = "Source IP; with --src-instance, picks which address of the instance is used"
+    )]
+    pub(super) src_ip: Option<String>,
+
+    #[clap(long, help = "Instance receiving the traffic")]
+    pub(super) dst_instance: Option<String>,
+
+    #[clap(
+        long,
+        help = "Destination IP; with --dst-instance, picks which address of the instance is used"
+    )]
+    pub(super) dst_ip: Option<String>,
+
+    #[clap(short = 'p', long, value_enum, help = "Protocol of the flow")]
+    pub(super) protocol: Protocol,
+
+    #[clap(long, help = "Source port of the flow")]
+    pub(super) src_port: Option<u32>,
+
+    #[clap(long, help = "Destination port of the flow")]
+    pub(super) dst_port: Option<u32>,
+
+    #[clap(
+        long,
+        help = "Use the IPv6 addresses of the instances when no IP is given"
+    )]
+    pub(super) ipv6: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "kebab_case")]
+pub(crate) enum Protocol {
+    Tcp,
+    Udp,
+    Icmp,
+    Icmp6,
+}
+
+impl From<Protocol> for NetworkSecurityGroupRuleProtocol {
+    fn from(protocol: Protocol) -> Self {
+        match protocol {
+            Protocol::Tcp => NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp,
+            Protocol::Udp => NetworkSecurityGroupRuleProtocol::NsgRuleProtoUdp,
+            Protocol::Icmp => NetworkSecurityGroupRuleProtocol::NsgRuleProtoIcmp,
+            Protocol::Icmp6 => NetworkSecurityGroupRuleProtocol::NsgRuleProtoIcmp6,
+        }
+    }
+}
+
+impl From<Args> for SimulateNetworkSecurityGroupFlowRequest {
+    fn from(args: Args) -> Self {
+        Self {
+            source: Some(NetworkSecurityGroupFlowEndpoint {
+                instance_id: args.src_instance,
+                ip: args.src_ip,
+            }),
+            destination: Some(NetworkSecurityGroupFlowEndpoint {
+                instance_id: args.dst_instance,
+                ip: args.dst_ip,
+            }),
+            protocol: NetworkSecurityGroupRuleProtocol::from(args.protocol).into(),
+            src_port: args.src_port,
+            dst_port: args.dst_port,
+            ipv6: args.ipv6,
+        }
+    }
+}
@@ -0,0 +1,125 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::OutputFormat;
+use ::rpc::forge::{self as forgerpc, SimulateNetworkSecurityGroupFlowRequest};
+use prettytable::{Table, row};
+
+use super::args::Args;
+use crate::errors::{CarbideCliError, CarbideCliResult};
+use crate::rpc::ApiClient;
+
+/// Evaluate a flow against the rules the DPUs of the
+/// instances involved would enforce, and display the
+/// decision together with the chain of rules that
+/// were walked to reach it.
+pub(super) async fn simulate(
+    args: Args,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let is_json = output_format == OutputFormat::Json;
+
+    let protocol = args.protocol;
+    let (src_port, dst_port) = (args.src_port, args.dst_port);
+
+    let simulation = api_client
+        .0
+        .simulate_network_security_group_flow(SimulateNetworkSecurityGroupFlowRequest::from(args))
+        .await?;
+
+    if is_json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&simulation).map_err(CarbideCliError::JsonError)?
+        );
+
+        return Ok(());
+    }
+
+    let endpoint = |ip: &str, port: Option<u32>| match port {
+        Some(port) => format!("{ip}:{port}"),
+        None => ip.to_string(),
+    };
+
+    println!(
+        "{protocol:?} {} -> {}: {}",
+        endpoint(&simulation.src_ip, src_port),
+        endpoint(&simulation.dst_ip, dst_port),
+        simulation.decision().as_str_name()
+    );
+
+    for evaluation in &simulation.evaluations {
+        let nsg = evaluation
+            .network_security_group
+            .clone()
+            .unwrap_or_default();
+
+        println!(
+            "\n{} on instance {} (network security group: {} {} {}): {}",
+            evaluation.direction().as_str_name(),
+            evaluation.instance_id,
+            nsg.source().as_str_name(),
+            nsg.id,
+            nsg.version,
+            evaluation.decision().as_str_name()
+        );
+
+        convert_steps_to_table(&evaluation.steps).printstd();
+    }
+
+    Ok(())
+}
+
+fn convert_steps_to_table(steps: &[forgerpc::NetworkSecurityGroupFlowStep]) -> Box<Table> {
+    let mut table = Box::new(Table::new());
+
+    table.set_titles(row![
+        "Stage", "Matched", "Action", "Rule", "Priority", "Match", "Stateful", "Details",
+    ]);
+
+    for step in steps {
+        let rule = step.rule.as_ref().and_then(|r| r.rule.as_ref());
+
+        let matched_on = match (&step.prefix, &step.rule) {
+            (Some(prefix), _) => prefix.clone(),
+            (None, Some(r)) => format!(
+                "{} -> {}",
+                r.src_prefixes.join(","),
+                r.dst_prefixes.join(",")
+            ),
+            (None, None) => String::new(),
+        };
+
+        table.add_row(row![
+            step.stage().as_str_name(),
+            step.matched,
+            if step.matched {
+                step.action().as_str_name()
+            } else {
+                ""
+            },
+            rule.and_then(|r| r.id.clone()).unwrap_or_default(),
+            rule.map(|r| r.priority.to_string()).unwrap_or_default(),
+            matched_on,
+            step.stateful,
+            step.details,
+        ]);
+    }
+
+    table
+}
@@ -228,8 +228,45 @@ fn parse_detach_routes_to_detach_variant() {
     );
 }
 
+// simulate routes to the Simulate variant, threading through the endpoints,
+// protocol and ports of the flow.
+#[test]
+fn parse_simulate_routes_to_simulate_variant() {
+    scenarios!(
+        run = |argv| {
+            let matches = parse_leaf::<Cmd>(argv, &["simulate"]).map_err(drop)?;
+            Ok::<_, ()>((
+                raw_value(&matches, "src_ip"),
+                raw_value(&matches, "dst_instance"),
+                raw_value(&matches, "protocol").expect("protocol is required"),
+                matches.get_one::<u32>("dst_port").copied(),
+            ))
+        };
+        "simulate from an IP to an instance" {
+            &[
+                "network-security-group",
+                "simulate",
+                "--src-ip",
+                "203.0.113.7",
+                "--dst-instance",
+                "instance-123",
+                "--protocol",
+                "tcp",
+                "--dst-port",
+                "443",
+            ][..] => Yields((
+                Some("203.0.113.7".to_string()),
+                Some("instance-123".to_string()),
+                "tcp".to_string(),
+                Some(443),
+            )),
+        }
+    );
+}
+
 // Every malformed invocation is rejected at parse time -- here, create without
-// its required --tenant-organization-id.
+// its required --tenant-organization-id, and simulate without a destination or
+// with a protocol a flow can't have.
 #[test]
 fn invalid_invocations_are_rejected() {
     scenarios!(
@@ -242,5 +279,29 @@ fn invalid_invocations_are_rejected() {
         "create without --tenant-organization-id" {
             &["network-security-group", "create"][..] => Fails,
         }
+
+        "simulate without a destination" {
+            &[
+                "network-security-group",
+                "simulate",
+                "--src-ip",
+                "203.0.113.7",
+                "--protocol",
+                "tcp",
+            ][..] => Fails,
+        }
+
+        "simulate with protocol any" {
+            &[
+                "network-security-group",
+                "simulate",
+                "--src-ip",
+                "203.0.113.7",
+                "--dst-ip",
+                "192.0.2.1",
+                "--protocol",
+                "any",
+            ][..] => Fails,
+        }
     );
 }
@@ -18,6 +18,7 @@
 use ::rpc::forge::{
     self as forgerpc, IpxeTemplateArtifact, IpxeTemplateParameter, OperatingSystem,
 };
+use clap::ValueEnum;
 use serde::Serialize;
 
 use crate::errors::{CarbideCliError, CarbideCliResult};
@@ -51,6 +52,28 @@ pub(super) fn parse_param(s: &str) -> Result<IpxeTemplateParameter, String> {
     })
 }
 
+/// How the OS definition's `--user-data` is interpreted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "kebab_case")]
+pub(super) enum UserDataFormat {
+    /// cloud-init YAML or script
+    CloudInit,
+    /// an Ignition spec 3 JSON config
+    Ignition,
+    /// a Butane YAML config, translated to Ignition when served
+    Butane,
+}
+
+impl From<UserDataFormat> for forgerpc::UserDataFormat {
+    fn from(format: UserDataFormat) -> Self {
+        match format {
+            UserDataFormat::CloudInit => forgerpc::UserDataFormat::CloudInit,
+            UserDataFormat::Ignition => forgerpc::UserDataFormat::Ignition,
+            UserDataFormat::Butane => forgerpc::UserDataFormat::Butane,
+        }
+    }
+}
+
 /// Local serializable mirror of `OperatingSystem` for JSON output.
 #[derive(Serialize)]
 pub(super) struct SerializableOs {
@@ -65,6 +88,7 @@ pub(super) struct SerializableOs {
     allow_override: bool,
     phone_home_enabled: bool,
     user_data: Option<String>,
+    user_data_format: String,
     created: String,
     updated: String,
     ipxe_script: Option<String>,
@@ -137,6 +161,9 @@ impl From<OperatingSystem> for SerializableOs {
             allow_override: os.allow_override,
             phone_home_enabled: os.phone_home_enabled,
             user_data: os.user_data,
+            user_data_format: forgerpc::UserDataFormat::try_from(os.user_data_format)
+                .map(|f| f.as_str_name().to_string())
+                .unwrap_or_else(|_| os.user_data_format.to_string()),
             created: os.created,
             updated: os.updated,
             ipxe_script: os.ipxe_script,
@@ -18,7 +18,7 @@
 use ::rpc::forge::IpxeTemplateParameter;
 use clap::Parser;
 
-use crate::operating_system::common::parse_param;
+use crate::operating_system::common::{UserDataFormat, parse_param};
 
 #[derive(Parser, Debug, Clone)]
 #[command(after_long_help = "\
@@ -31,6 +31,10 @@ Create one with a description, inactive, allowing parameter overrides:
     $ nico-admin-cli operating-system create --name ubuntu-22.04 --org fds34511233a \
     --description \"Ubuntu 22.04 base\" --is-active false --allow-override
 
+Create a Flatcar definition whose user data is a Butane config:
+    $ nico-admin-cli operating-system create --name flatcar --org fds34511233a \
+    --ipxe-script \"$(cat flatcar.ipxe)\" --user-data \"$(cat flatcar.bu)\" --user-data-format butane
+
 ")]
 pub(crate) struct Args {
     #[clap(short, long, help = "Name of the operating system definition.")]
@@ -72,6 +76,14 @@ pub(crate) struct Args {
     #[clap(long, help = "Optional cloud-init / user-data script.")]
     pub(super) user_data: Option<String>,
 
+    #[clap(
+        long,
+        value_enum,
+        default_value = "cloud-init",
+        help = "Format of --user-data. Flatcar and Fedora CoreOS images use ignition or butane."
+    )]
+    pub(super) user_data_format: UserDataFormat,
+
     #[clap(
         long,
         conflicts_with_all = ["ipxe_template_id"],
@@ -15,7 +15,7 @@
  * limitations under the License.
  */
 
-use ::rpc::forge::CreateOperatingSystemRequest;
+use ::rpc::forge::{self as forgerpc, CreateOperatingSystemRequest};
 
 use super::args::Args;
 use crate::errors::{CarbideCliError, CarbideCliResult};
@@ -40,6 +40,7 @@ pub(super) async fn create(opts: Args, api_client: &ApiClient) -> CarbideCliResu
             allow_override: opts.allow_override,
             phone_home_enabled: opts.phone_home_enabled,
             user_data: opts.user_data,
+            user_data_format: forgerpc::UserDataFormat::from(opts.user_data_format) as i32,
             id,
             ipxe_script: opts.ipxe_script,
             ipxe_template_id,
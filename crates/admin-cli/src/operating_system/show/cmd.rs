@@ -18,6 +18,7 @@
 use ::rpc::admin_cli::OutputFormat;
 use ::rpc::forge::{
     IpxeTemplateArtifactCacheStrategy, OperatingSystemSearchFilter, OperatingSystemType,
+    UserDataFormat,
 };
 use prettytable::{Cell, Row, Table};
 
@@ -190,6 +191,12 @@ async fn show_one(
         println!("Description:         {desc}");
     }
     if let Some(user_data) = &os.user_data {
+        println!(
+            "User Data Format:    {}",
+            UserDataFormat::try_from(os.user_data_format)
+                .map(|f| f.as_str_name().to_string())
+                .unwrap_or_else(|_| os.user_data_format.to_string())
+        );
         println!("User Data:           {user_data}");
     }
 
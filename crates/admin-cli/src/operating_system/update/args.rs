@@ -18,7 +18,7 @@
 use ::rpc::forge::IpxeTemplateParameter;
 use clap::Parser;
 
-use crate::operating_system::common::parse_param;
+use crate::operating_system::common::{UserDataFormat, parse_param};
 
 #[derive(Parser, Debug, Clone)]
 #[command(after_long_help = "\
@@ -58,6 +58,9 @@ pub(crate) struct Args {
     #[clap(long, help = "Update the cloud-init / user-data script.")]
     pub(super) user_data: Option<String>,
 
+    #[clap(long, value_enum, help = "Set the format of the user data.")]
+    pub(super) user_data_format: Option<UserDataFormat>,
+
     #[clap(
         long,
         conflicts_with = "ipxe_template_id",
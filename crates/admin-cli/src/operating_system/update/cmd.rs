@@ -15,7 +15,7 @@
  * limitations under the License.
  */
 
-use ::rpc::forge::{IpxeTemplateParameters, UpdateOperatingSystemRequest};
+use ::rpc::forge::{self as forgerpc, IpxeTemplateParameters, UpdateOperatingSystemRequest};
 
 use super::args::Args;
 use crate::errors::{CarbideCliError, CarbideCliResult};
@@ -42,6 +42,9 @@ pub(super) async fn update(opts: Args, api_client: &ApiClient) -> CarbideCliResu
             allow_override: opts.allow_override,
             phone_home_enabled: opts.phone_home_enabled,
             user_data: opts.user_data,
+            user_data_format: opts
+                .user_data_format
+                .map(|format| forgerpc::UserDataFormat::from(format) as i32),
             ipxe_script: opts.ipxe_script,
             ipxe_template_id,
             ipxe_template_parameters,
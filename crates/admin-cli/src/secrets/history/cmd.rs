@@ -0,0 +1,60 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::OutputFormat;
+use prettytable::{Table, row};
+
+use crate::errors::{CarbideCliError, CarbideCliResult};
+use crate::rpc::ApiClient;
+
+pub(super) async fn history(
+    api_client: &ApiClient,
+    path: String,
+    output_format: &OutputFormat,
+) -> CarbideCliResult<()> {
+    let request = ::rpc::forge::GetSecretHistoryRequest { path };
+    let versions = api_client.0.get_secret_history(request).await?.versions;
+
+    match output_format {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&versions).map_err(CarbideCliError::JsonError)?
+        ),
+        OutputFormat::Yaml => println!(
+            "{}",
+            serde_yaml::to_string(&versions).map_err(CarbideCliError::YamlError)?
+        ),
+        _ => {
+            let mut table = Table::new();
+            table.set_titles(row!["Seq", "Secret ID", "KEK", "Written", "Tombstone"]);
+            for version in versions {
+                table.add_row(row![
+                    version.seq,
+                    version.secret_id,
+                    version.kek_id,
+                    version
+                        .created_at
+                        .map(|created_at| created_at.to_string())
+                        .unwrap_or_default(),
+                    if version.tombstone { "yes" } else { "" },
+                ]);
+            }
+            table.printstd();
+        }
+    }
+    Ok(())
+}
@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+mod history;
 mod re_wrap;
 
 use clap::Parser;
@@ -28,4 +29,7 @@ pub(crate) enum Cmd {
                  currently active KEK per routing \
                  config")]
     ReWrap(re_wrap::Args),
+    #[clap(about = "List every stored version of a \
+                 credential, newest first")]
+    History(history::Args),
 }
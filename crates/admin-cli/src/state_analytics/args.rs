@@ -0,0 +1,105 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::forge::{StateAnalyticsObjectType, StateDwellAnalyticsRequest};
+use chrono::{DateTime, Utc};
+use clap::{Parser, ValueEnum};
+
+#[derive(Parser, Debug)]
+#[command(after_long_help = "\
+EXAMPLES:
+
+Time in state, transitions and stuck hosts over the last 24 hours:
+    $ nico-admin-cli state-analytics
+
+Racks that sat outside ready for more than two hours during a bring-up week:
+    $ nico-admin-cli state-analytics --object-type rack --stuck-after-minutes 120 \\
+        --since 2026-10-05T00:00:00Z --until 2026-10-12T00:00:00Z
+
+One SKU and instance type in one rack, as JSON:
+    $ nico-admin-cli -f json state-analytics --sku gb200-nvl72 --instance-type it-gb200 \\
+        --rack-id rack-17
+
+")]
+pub(crate) struct Opts {
+    #[clap(
+        long,
+        value_enum,
+        default_value_t = ObjectType::Machine,
+        help = "Whose state history to analyze"
+    )]
+    pub(super) object_type: ObjectType,
+
+    #[clap(
+        long,
+        help = "Start of the window, as RFC 3339 [default: 24 hours before --until]"
+    )]
+    pub(super) since: Option<DateTime<Utc>>,
+
+    #[clap(long, help = "End of the window, as RFC 3339 [default: now]")]
+    pub(super) until: Option<DateTime<Utc>>,
+
+    #[clap(long, help = "Only machines with this hardware SKU")]
+    pub(super) sku: Option<String>,
+
+    #[clap(long, help = "Only objects in this rack")]
+    pub(super) rack_id: Option<String>,
+
+    #[clap(long = "instance-type", help = "Only machines of this instance type")]
+    pub(super) instance_type_id: Option<String>,
+
+    #[clap(
+        long,
+        default_value_t = 30,
+        help = "Minutes in a state other than ready (or assigned) before an object counts as stuck"
+    )]
+    pub(super) stuck_after_minutes: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "kebab_case")]
+pub(crate) enum ObjectType {
+    Machine,
+    Rack,
+    Switch,
+    PowerShelf,
+}
+
+impl From<ObjectType> for StateAnalyticsObjectType {
+    fn from(object_type: ObjectType) -> Self {
+        match object_type {
+            ObjectType::Machine => StateAnalyticsObjectType::Machine,
+            ObjectType::Rack => StateAnalyticsObjectType::Rack,
+            ObjectType::Switch => StateAnalyticsObjectType::Switch,
+            ObjectType::PowerShelf => StateAnalyticsObjectType::PowerShelf,
+        }
+    }
+}
+
+impl From<Opts> for StateDwellAnalyticsRequest {
+    fn from(opts: Opts) -> Self {
+        Self {
+            object_type: StateAnalyticsObjectType::from(opts.object_type).into(),
+            since: opts.since.map(Into::into),
+            until: opts.until.map(Into::into),
+            sku: opts.sku,
+            rack_id: opts.rack_id,
+            instance_type_id: opts.instance_type_id,
+            stuck_after: Some(std::time::Duration::from_secs(opts.stuck_after_minutes * 60).into()),
+        }
+    }
+}
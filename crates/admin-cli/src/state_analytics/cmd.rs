@@ -0,0 +1,224 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::OutputFormat;
+use ::rpc::forge::{StateDwellAnalytics, StateDwellAnalyticsRequest};
+use prettytable::{Cell, Row, Table};
+use serde::Serialize;
+
+use super::Opts;
+use crate::errors::CarbideCliResult;
+use crate::rpc::ApiClient;
+
+#[derive(Serialize)]
+struct StateDwellOutput {
+    state: String,
+    completed: u64,
+    p50_seconds: i64,
+    p95_seconds: i64,
+    p99_seconds: i64,
+    max_seconds: i64,
+    in_progress: u64,
+    stuck: u64,
+}
+
+#[derive(Serialize)]
+struct TransitionOutput {
+    from: String,
+    to: String,
+    count: u64,
+}
+
+#[derive(Serialize)]
+struct StuckObjectOutput {
+    object_id: String,
+    state: String,
+    since: String,
+}
+
+#[derive(Serialize)]
+struct StateAnalyticsOutput {
+    since: String,
+    until: String,
+    object_count: u64,
+    states: Vec<StateDwellOutput>,
+    transitions: Vec<TransitionOutput>,
+    stuck_objects: Vec<StuckObjectOutput>,
+    stuck_objects_truncated: bool,
+}
+
+impl From<StateDwellAnalytics> for StateAnalyticsOutput {
+    fn from(analytics: StateDwellAnalytics) -> Self {
+        let seconds = |d: Option<::rpc::Duration>| d.map(|d| d.seconds).unwrap_or_default();
+        Self {
+            since: analytics.since.unwrap_or_default().to_string(),
+            until: analytics.until.unwrap_or_default().to_string(),
+            object_count: analytics.object_count,
+            states: analytics
+                .states
+                .into_iter()
+                .map(|stats| StateDwellOutput {
+                    state: stats.state,
+                    completed: stats.completed,
+                    p50_seconds: seconds(stats.p50),
+                    p95_seconds: seconds(stats.p95),
+                    p99_seconds: seconds(stats.p99),
+                    max_seconds: seconds(stats.max),
+                    in_progress: stats.in_progress,
+                    stuck: stats.stuck,
+                })
+                .collect(),
+            transitions: analytics
+                .transitions
+                .into_iter()
+                .map(|transition| TransitionOutput {
+                    from: transition.from_state,
+                    to: transition.to_state,
+                    count: transition.count,
+                })
+                .collect(),
+            stuck_objects: analytics
+                .stuck_objects
+                .into_iter()
+                .map(|object| StuckObjectOutput {
+                    object_id: object.object_id,
+                    state: object.state,
+                    since: object.since.unwrap_or_default().to_string(),
+                })
+                .collect(),
+            stuck_objects_truncated: analytics.stuck_objects_truncated,
+        }
+    }
+}
+
+/// Renders whole seconds as e.g. `2h05m` or `45s`.
+pub(super) fn format_seconds(seconds: i64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    match (hours, minutes) {
+        (0, 0) => format!("{seconds}s"),
+        (0, _) => format!("{minutes}m{seconds:02}s"),
+        _ => format!("{hours}h{minutes:02}m"),
+    }
+}
+
+fn build_states_table(states: &[StateDwellOutput]) -> Table {
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("State"),
+        Cell::new("Completed"),
+        Cell::new("P50"),
+        Cell::new("P95"),
+        Cell::new("P99"),
+        Cell::new("Max"),
+        Cell::new("In Progress"),
+        Cell::new("Stuck"),
+    ]));
+    for stats in states {
+        table.add_row(prettytable::row![
+            stats.state,
+            stats.completed,
+            format_seconds(stats.p50_seconds),
+            format_seconds(stats.p95_seconds),
+            format_seconds(stats.p99_seconds),
+            format_seconds(stats.max_seconds),
+            stats.in_progress,
+            stats.stuck
+        ]);
+    }
+    table
+}
+
+fn build_transitions_table(transitions: &[TransitionOutput]) -> Table {
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("From"),
+        Cell::new("To"),
+        Cell::new("Count"),
+    ]));
+    for transition in transitions {
+        table.add_row(prettytable::row![
+            transition.from,
+            transition.to,
+            transition.count
+        ]);
+    }
+    table
+}
+
+fn build_stuck_table(stuck_objects: &[StuckObjectOutput]) -> Table {
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Object"),
+        Cell::new("State"),
+        Cell::new("Since"),
+    ]));
+    for object in stuck_objects {
+        table.add_row(prettytable::row![
+            object.object_id,
+            object.state,
+            object.since
+        ]);
+    }
+    table
+}
+
+pub(super) async fn show_state_analytics(
+    opts: Opts,
+    api_client: &ApiClient,
+    format: OutputFormat,
+) -> CarbideCliResult<()> {
+    let analytics = api_client
+        .0
+        .get_state_dwell_analytics(StateDwellAnalyticsRequest::from(opts))
+        .await?;
+    let output = StateAnalyticsOutput::from(analytics);
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&output)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&output)?),
+        OutputFormat::Csv => {
+            build_states_table(&output.states)
+                .to_csv(std::io::stdout())
+                .ok();
+        }
+        OutputFormat::AsciiTable => {
+            println!(
+                "{} objects between {} and {}",
+                output.object_count, output.since, output.until
+            );
+            if output.states.is_empty() {
+                return Ok(());
+            }
+            println!("\nTime in state:");
+            build_states_table(&output.states).printstd();
+            if !output.transitions.is_empty() {
+                println!("\nTransitions:");
+                build_transitions_table(&output.transitions).printstd();
+            }
+            if !output.stuck_objects.is_empty() {
+                let truncated = if output.stuck_objects_truncated {
+                    " (longest stuck only)"
+                } else {
+                    ""
+                };
+                println!("\nStuck objects{truncated}:");
+                build_stuck_table(&output.stuck_objects).printstd();
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,107 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::forge::{StateAnalyticsObjectType, StateDwellAnalyticsRequest};
+use carbide_test_support::Outcome::*;
+use carbide_test_support::{scenarios, value_scenarios};
+use clap::{CommandFactory, Parser};
+
+use super::args::*;
+use super::cmd::format_seconds;
+
+#[test]
+fn verify_cmd_structure() {
+    Opts::command().debug_assert();
+}
+
+// Machines over the server's default window are the default; SKU and instance
+// type filters pass through unchanged and the stuck threshold becomes a
+// Duration.
+#[test]
+fn parse_request() {
+    scenarios!(
+        run = |argv| {
+            Opts::try_parse_from(argv.iter().copied())
+                .map(|opts| {
+                    let request = StateDwellAnalyticsRequest::from(opts);
+                    (
+                        request.object_type(),
+                        request.since.map(|since| since.seconds),
+                        request.sku,
+                        request.instance_type_id,
+                        request.stuck_after.map(|d| d.seconds),
+                    )
+                })
+                .map_err(drop)
+        };
+        "defaults" {
+            &["state-analytics"][..] => Yields((
+                StateAnalyticsObjectType::Machine,
+                None,
+                None,
+                None,
+                Some(1800),
+            )),
+        }
+        "filters" {
+            &[
+                "state-analytics",
+                "--sku",
+                "gb200",
+                "--instance-type",
+                "it-1",
+                "--since",
+                "1970-01-01T01:00:00Z",
+            ][..] => Yields((
+                StateAnalyticsObjectType::Machine,
+                Some(3600),
+                Some("gb200".to_string()),
+                Some("it-1".to_string()),
+                Some(1800),
+            )),
+        }
+        "power shelves" {
+            &[
+                "state-analytics",
+                "--object-type",
+                "power-shelf",
+                "--stuck-after-minutes",
+                "5",
+            ][..] => Yields((
+                StateAnalyticsObjectType::PowerShelf,
+                None,
+                None,
+                None,
+                Some(300),
+            )),
+        }
+        "unknown object type" {
+            &["state-analytics", "--object-type", "vpc"][..] => Fails,
+        }
+    );
+}
+
+#[test]
+fn formats_durations() {
+    value_scenarios!(format_seconds:
+        "compact" {
+            45 => "45s".to_string(),
+            125 => "2m05s".to_string(),
+            7500 => "2h05m".to_string(),
+        }
+    );
+}
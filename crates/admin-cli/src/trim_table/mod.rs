@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+mod audit_events;
 mod measured_boot;
 
 #[cfg(test)]
@@ -28,4 +29,5 @@ use crate::cfg::dispatch::Dispatch;
 #[clap(rename_all = "kebab_case")]
 pub(crate) enum Cmd {
     MeasuredBoot(measured_boot::Args),
+    AuditEvents(audit_events::Args),
 }
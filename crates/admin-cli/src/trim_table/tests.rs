@@ -75,6 +75,28 @@ fn parse_measured_boot_keep_entries() {
     );
 }
 
+// audit-events routes to the AuditEvents variant with the same --keep-entries.
+#[test]
+fn parse_audit_events_keep_entries() {
+    scenarios!(
+        run = |argv| {
+            parse_leaf::<Cmd>(argv, &["audit-events"])
+                .map(|matches| {
+                    *matches
+                        .get_one::<u32>("keep_entries")
+                        .expect("keep entries is required")
+                })
+                .map_err(drop)
+        };
+        "typical count" {
+            &["trim-table", "audit-events", "--keep-entries", "100000"][..] => Yields(100000),
+        }
+        "missing --keep-entries" {
+            &["trim-table", "audit-events"][..] => Fails,
+        }
+    );
+}
+
 // Malformed measured-boot invocations are rejected at parse time: the missing
 // required --keep-entries, a non-numeric value, and a negative value.
 #[test]
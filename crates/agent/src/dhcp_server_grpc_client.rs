@@ -74,6 +74,7 @@ impl From<ModelInterfaceInfoV6> for proto::InterfaceInfoV6 {
         proto::InterfaceInfoV6 {
             address: i.address.map(|ip| ip.to_string()),
             prefix: i.prefix,
+            delegated_prefix: i.delegated_prefix,
         }
     }
 }
@@ -24,14 +24,18 @@ use std::sync::{Arc, OnceLock};
 pub(crate) use ::rpc::forge as rpc;
 use ::rpc::forge::{RemoveSkuRequest, SkuIdList};
 use ::rpc::protos::dns::{
-    CreateDomainRequest, DnsResourceRecordLookupRequest, DnsResourceRecordLookupResponse, Domain,
-    DomainDeletionRequest, DomainDeletionResult, DomainList, DomainMetadataRequest,
-    DomainMetadataResponse, DomainSearchQuery, GetAllDomainsRequest, GetAllDomainsResponse,
+    CreateDnsCustomRecordRequest, CreateDomainRequest, DeleteDnsCustomRecordRequest,
+    DeleteDnsCustomRecordResponse, DnsCustomRecord, DnsCustomRecordList,
+    DnsResourceRecordLookupRequest, DnsResourceRecordLookupResponse, Domain, DomainDeletionRequest,
+    DomainDeletionResult, DomainList, DomainMetadataRequest, DomainMetadataResponse,
+    DomainSearchQuery, FindDnsCustomRecordsRequest, GetAllDomainsRequest, GetAllDomainsResponse,
+    GetAllRecordsForDomainRequest, GetAllRecordsForDomainResponse, UpdateDnsCustomRecordRequest,
     UpdateDomainRequest,
 };
 use ::rpc::protos::{measured_boot as measured_boot_pb, mlx_device as mlx_device_pb};
 use carbide_ib_fabric::ib::IBFabricManager;
 use carbide_machine_controller::dpf::DpfOperations;
+use carbide_machine_controller::dry_run::MachineStateExplainer;
 use carbide_machine_controller::io::MachineStateControllerIO;
 use carbide_rack::bms_client::BmsDsxExchangeHandle;
 use carbide_redfish::libredfish::RedfishClientPool;
@@ -56,11 +60,14 @@ use tonic::{Request, Response, Status, Streaming};
 
 use self::metrics::ApiMetricsEmitter;
 use self::rpc::forge_server::Forge;
+use crate::audit::AuditLog;
+use crate::certificate_revocations::CertificateRevocations;
 use crate::cfg::file::CarbideConfig;
 use crate::dynamic_settings::DynamicSettings;
 use crate::ethernet_virtualization::EthVirtData;
 use crate::logging::log_limiter::LogLimiter;
 use crate::scout_stream::ConnectionRegistry;
+use crate::state_watch::{StateWatchHub, StateWatchStreamType};
 use crate::{CarbideError, CarbideResult};
 
 pub struct Api {
@@ -68,6 +75,9 @@ pub struct Api {
     pub database_connection: sqlx::PgPool,
     pub(crate) credential_manager: Arc<dyn CredentialManager>,
     pub(crate) certificate_provider: Arc<dyn CertificateProvider>,
+    /// Revoked client certificates, refused by the listener's authentication
+    /// layer.
+    pub(crate) certificate_revocations: Arc<CertificateRevocations>,
     pub(crate) redfish_pool: Arc<dyn RedfishClientPool>,
     pub(crate) bmc_session_manager: Arc<crate::credentials::BmcSessionManager>,
     pub(crate) eth_data: EthVirtData,
@@ -89,11 +99,23 @@ pub struct Api {
     pub(crate) metric_emitter: ApiMetricsEmitter,
     pub(crate) component_manager: Option<component_manager::component_manager::ComponentManager>,
     pub(crate) bms_client: OnceLock<Arc<BmsDsxExchangeHandle>>,
+    /// Set once the machine state controller is built, from the same handler
+    /// and services it runs with.
+    pub(crate) machine_state_explainer: OnceLock<Arc<MachineStateExplainer>>,
     pub(crate) secrets_context: Option<crate::secrets::SecretsContext>,
     /// Validator for node-auth bearer JWTs (issue #355). `Some` only when
     /// `[node_auth] enabled`; installed into the authn middleware by the
     /// listener.
     pub(crate) node_jwt_validator: Option<Arc<crate::node_auth::NodeJwtValidator>>,
+    /// Trust anchors of the client-certificate PKI, for the certificate
+    /// chains of documents nodes sign (disk sanitization certificates). `Some`
+    /// whenever `[tls]` names a readable client CA bundle, whether or not node
+    /// auth is enabled; the same validator as `node_jwt_validator` when it is.
+    pub(crate) client_ca_verifier: Option<Arc<crate::node_auth::NodeJwtValidator>>,
+    /// Fans out local state transitions to the `Watch*States` streams.
+    pub(crate) state_watch_hub: StateWatchHub,
+    /// Stores mutating calls in the `audit_events` table.
+    pub(crate) audit_log: AuditLog,
 }
 
 pub(crate) type ScoutStreamType =
@@ -102,6 +124,11 @@ pub(crate) type ScoutStreamType =
 #[tonic::async_trait]
 impl Forge for Api {
     type ScoutStreamStream = ScoutStreamType;
+    type WatchMachineStatesStream = StateWatchStreamType;
+    type WatchInstanceStatesStream = StateWatchStreamType;
+    type WatchRackStatesStream = StateWatchStreamType;
+    type WatchSwitchStatesStream = StateWatchStreamType;
+    type WatchPowerShelfStatesStream = StateWatchStreamType;
 
     async fn version(
         &self,
@@ -138,6 +165,34 @@ impl Forge for Api {
         crate::handlers::domain::find(self, request).await
     }
 
+    async fn create_dns_custom_record(
+        &self,
+        request: Request<CreateDnsCustomRecordRequest>,
+    ) -> Result<Response<DnsCustomRecord>, Status> {
+        crate::handlers::dns::create_custom_record(self, request).await
+    }
+
+    async fn update_dns_custom_record(
+        &self,
+        request: Request<UpdateDnsCustomRecordRequest>,
+    ) -> Result<Response<DnsCustomRecord>, Status> {
+        crate::handlers::dns::update_custom_record(self, request).await
+    }
+
+    async fn delete_dns_custom_record(
+        &self,
+        request: Request<DeleteDnsCustomRecordRequest>,
+    ) -> Result<Response<DeleteDnsCustomRecordResponse>, Status> {
+        crate::handlers::dns::delete_custom_record(self, request).await
+    }
+
+    async fn find_dns_custom_records(
+        &self,
+        request: Request<FindDnsCustomRecordsRequest>,
+    ) -> Result<Response<DnsCustomRecordList>, Status> {
+        crate::handlers::dns::find_custom_records(self, request).await
+    }
+
     // Legacy domain methods for backward compatibility
     // TODO: Remove this after clients have migrated
     async fn create_domain_legacy(
@@ -538,6 +593,13 @@ impl Forge for Api {
         crate::handlers::instance::batch_allocate(self, request).await
     }
 
+    async fn allocate_instances_by_type(
+        &self,
+        request: Request<rpc::InstanceTypeAllocationRequest>,
+    ) -> Result<Response<rpc::BatchInstanceAllocationResponse>, Status> {
+        crate::handlers::instance::allocate_by_type(self, request).await
+    }
+
     async fn find_instance_ids(
         &self,
         request: Request<rpc::InstanceSearchFilter>,
@@ -759,6 +821,13 @@ impl Forge for Api {
         crate::handlers::dns::lookup_record(self, request).await
     }
 
+    async fn get_all_records_for_domain(
+        &self,
+        request: Request<GetAllRecordsForDomainRequest>,
+    ) -> Result<Response<GetAllRecordsForDomainResponse>, Status> {
+        crate::handlers::dns::get_all_records_for_domain(self, request).await
+    }
+
     async fn invoke_instance_power(
         &self,
         request: Request<rpc::InstancePowerRequest>,
@@ -881,6 +950,13 @@ impl Forge for Api {
         crate::handlers::machine_scout::cleanup_machine_completed(self, request).await
     }
 
+    async fn find_disk_sanitization_certificates(
+        &self,
+        request: Request<rpc::DiskSanitizationCertificateQuery>,
+    ) -> Result<Response<rpc::DiskSanitizationCertificateList>, Status> {
+        crate::handlers::disk_sanitization::find_disk_sanitization_certificates(self, request).await
+    }
+
     // Invoked by forge-scout whenever a certain Machine can not be properly acted on
     async fn report_forge_scout_error(
         &self,
@@ -927,6 +1003,48 @@ impl Forge for Api {
         crate::handlers::machine::find_machine_state_histories(self, request).await
     }
 
+    async fn explain_machine_state_handling(
+        &self,
+        request: Request<rpc::ExplainMachineStateHandlingRequest>,
+    ) -> Result<Response<rpc::ExplainMachineStateHandlingResponse>, Status> {
+        crate::handlers::machine_explain::explain_machine_state_handling(self, request).await
+    }
+
+    async fn watch_machine_states(
+        &self,
+        request: Request<rpc::StateWatchRequest>,
+    ) -> Result<Response<Self::WatchMachineStatesStream>, Status> {
+        crate::handlers::state_watch::watch_machine_states(self, request).await
+    }
+
+    async fn watch_instance_states(
+        &self,
+        request: Request<rpc::StateWatchRequest>,
+    ) -> Result<Response<Self::WatchInstanceStatesStream>, Status> {
+        crate::handlers::state_watch::watch_instance_states(self, request).await
+    }
+
+    async fn watch_rack_states(
+        &self,
+        request: Request<rpc::StateWatchRequest>,
+    ) -> Result<Response<Self::WatchRackStatesStream>, Status> {
+        crate::handlers::state_watch::watch_rack_states(self, request).await
+    }
+
+    async fn watch_switch_states(
+        &self,
+        request: Request<rpc::StateWatchRequest>,
+    ) -> Result<Response<Self::WatchSwitchStatesStream>, Status> {
+        crate::handlers::state_watch::watch_switch_states(self, request).await
+    }
+
+    async fn watch_power_shelf_states(
+        &self,
+        request: Request<rpc::StateWatchRequest>,
+    ) -> Result<Response<Self::WatchPowerShelfStatesStream>, Status> {
+        crate::handlers::state_watch::watch_power_shelf_states(self, request).await
+    }
+
     async fn find_power_shelf_state_histories(
         &self,
         request: Request<rpc::PowerShelfStateHistoriesRequest>,
@@ -1028,6 +1146,13 @@ impl Forge for Api {
         crate::handlers::credential::set_container_registry_credential(self, request).await
     }
 
+    async fn revoke_certificate(
+        &self,
+        request: Request<rpc::RevokeCertificateRequest>,
+    ) -> Result<Response<()>, Status> {
+        crate::handlers::credential::revoke_certificate(self, request).await
+    }
+
     /// Network status of each managed host, as reported by forge-dpu-agent.
     /// For use by forge-admin-cli
     ///
@@ -1578,6 +1703,13 @@ impl Forge for Api {
         crate::handlers::secrets::re_wrap_secrets(self, request).await
     }
 
+    async fn get_secret_history(
+        &self,
+        request: Request<rpc::GetSecretHistoryRequest>,
+    ) -> Result<Response<rpc::GetSecretHistoryResponse>, Status> {
+        crate::handlers::secrets::get_secret_history(self, request).await
+    }
+
     /// get_route_servers returns a list of all configured route server
     /// entries for all source types.
     async fn get_route_servers(
@@ -2687,6 +2819,13 @@ impl Forge for Api {
     ) -> Result<Response<rpc::GetNetworkSecurityGroupAttachmentsResponse>, Status> {
         crate::handlers::network_security_group::get_attachments(self, request).await
     }
+
+    async fn simulate_network_security_group_flow(
+        &self,
+        request: Request<rpc::SimulateNetworkSecurityGroupFlowRequest>,
+    ) -> Result<Response<rpc::SimulateNetworkSecurityGroupFlowResponse>, Status> {
+        crate::handlers::network_security_group::simulate_flow(self, request).await
+    }
     async fn create_compute_allocation(
         &self,
         request: tonic::Request<rpc::CreateComputeAllocationRequest>,
@@ -2992,6 +3131,41 @@ impl Forge for Api {
         crate::handlers::firmware::list_host_firmware(self, request).await
     }
 
+    async fn create_firmware_rollout(
+        &self,
+        request: Request<rpc::CreateFirmwareRolloutRequest>,
+    ) -> Result<Response<rpc::FirmwareRollout>, Status> {
+        crate::handlers::firmware_rollout::create(self, request).await
+    }
+
+    async fn find_firmware_rollouts(
+        &self,
+        request: Request<rpc::FindFirmwareRolloutsRequest>,
+    ) -> Result<Response<rpc::FirmwareRolloutList>, Status> {
+        crate::handlers::firmware_rollout::find(self, request).await
+    }
+
+    async fn pause_firmware_rollout(
+        &self,
+        request: Request<rpc::PauseFirmwareRolloutRequest>,
+    ) -> Result<Response<rpc::FirmwareRollout>, Status> {
+        crate::handlers::firmware_rollout::pause(self, request).await
+    }
+
+    async fn resume_firmware_rollout(
+        &self,
+        request: Request<rpc::FirmwareRolloutName>,
+    ) -> Result<Response<rpc::FirmwareRollout>, Status> {
+        crate::handlers::firmware_rollout::resume(self, request).await
+    }
+
+    async fn delete_firmware_rollout(
+        &self,
+        request: Request<rpc::FirmwareRolloutName>,
+    ) -> Result<Response<()>, Status> {
+        crate::handlers::firmware_rollout::delete(self, request).await
+    }
+
     // Scout is telling Carbide the mlx device configuration in its machine
     async fn publish_mlx_device_report(
         &self,
@@ -3016,6 +3190,20 @@ impl Forge for Api {
         crate::handlers::db::trim_table(self, request).await
     }
 
+    async fn find_audit_events(
+        &self,
+        request: Request<rpc::FindAuditEventsRequest>,
+    ) -> Result<Response<rpc::AuditEventList>, Status> {
+        crate::handlers::audit::find_audit_events(self, request).await
+    }
+
+    async fn get_state_dwell_analytics(
+        &self,
+        request: Request<rpc::StateDwellAnalyticsRequest>,
+    ) -> Result<Response<rpc::StateDwellAnalytics>, Status> {
+        crate::handlers::state_analytics::get_state_dwell_analytics(self, request).await
+    }
+
     async fn list_nvlink_nmxc_endpoints(
         &self,
         request: Request<()>,
@@ -3645,25 +3833,24 @@ fn ipxe_template_visibility_to_proto(
 }
 
 pub(crate) fn log_request_data<T: std::fmt::Debug>(request: &Request<T>) {
-    tracing::Span::current().record(
-        "request",
-        truncate(
-            format!("{:?}", request.get_ref()),
-            ::rpc::MAX_ERR_MSG_SIZE as usize,
-        ),
+    let rendering = truncate(
+        format!("{:?}", request.get_ref()),
+        ::rpc::MAX_ERR_MSG_SIZE as usize,
     );
+    crate::audit::record_request_digest(&rendering);
+    tracing::Span::current().record("request", rendering);
 }
 
 /// Logs a pre-redacted request string (e.g. for requests containing secrets).
 pub(crate) fn log_request_data_redacted(s: impl AsRef<str>) {
-    tracing::Span::current().record(
-        "request",
-        truncate(s.as_ref().to_string(), ::rpc::MAX_ERR_MSG_SIZE as usize),
-    );
+    let rendering = truncate(s.as_ref().to_string(), ::rpc::MAX_ERR_MSG_SIZE as usize);
+    crate::audit::record_request_digest(&rendering);
+    tracing::Span::current().record("request", rendering);
 }
 
 /// Logs the Machine ID in the current tracing span
 pub(crate) fn log_machine_id(machine_id: &MachineId) {
+    crate::audit::record_target_id(machine_id);
     tracing::Span::current().record("forge.machine_id", tracing::field::display(machine_id));
 }
 
@@ -3719,6 +3906,21 @@ fn default_credential_display_name(key: &CredentialKey) -> &'static str {
 }
 
 impl Api {
+    /// Runs `handler` for a state-changing admin web request and records it
+    /// in the audit trail.
+    ///
+    /// The gRPC listener audits `Forge` calls itself; the admin web UI calls
+    /// [`Api`] in-process, past that listener, so it wraps its handlers here.
+    pub async fn audit_admin_web_action<B>(
+        &self,
+        action: crate::audit::AdminWebAction,
+        handler: impl Future<Output = hyper::http::Response<B>>,
+    ) -> hyper::http::Response<B> {
+        self.audit_log
+            .record_admin_web_action(action, handler)
+            .await
+    }
+
     /// Returns the site-wide default credentials that gate endpoint exploration
     /// (the same set validated by the site explorer's `check_preconditions`) but
     /// are currently unset.
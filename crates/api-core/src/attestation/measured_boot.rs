@@ -26,6 +26,8 @@ use carbide_uuid::machine::MachineId;
 use carbide_uuid::measured_boot::MeasurementReportId;
 use chrono::Utc;
 use db::db_read::DbReader;
+use measured_boot::event_log::EventLog;
+use measured_boot::report::MeasurementReport;
 use model::machine::MeasuringState;
 use pkcs1::LineEnding;
 use rsa::pkcs1::EncodeRsaPublicKey;
@@ -82,6 +84,52 @@ pub(crate) struct MeasuredBootVerificationFailed {
     pub(crate) error: String,
 }
 
+/// The TPM event log that came with a quote does not replay to the
+/// quoted PCR values, so it can't be used to explain this machine's
+/// measurements. The quote itself is still judged on its own.
+#[derive(carbide_instrument::Event)]
+#[event(
+    event_name = "measured_boot_event_log_replay_mismatch",
+    metric_name = "carbide_measured_boot_event_log_replay_mismatches_total",
+    component = "nico-api",
+    log = warn,
+    metric = counter,
+    message = "TPM event log does not replay to the quoted PCR values",
+    describe = "Number of attestations whose TPM event log did not replay to the quoted PCR values"
+)]
+pub(crate) struct MeasuredBootEventLogReplayMismatch {
+    #[context]
+    pub(crate) machine_id: String,
+    #[context]
+    pub(crate) mismatched_pcrs: String,
+}
+
+/// A measurement report matched no bundle, and its event log was used
+/// to work out which measurements differ from the closest bundle.
+/// `explanation` has one line per mismatched PCR, followed by the
+/// event-level changes (or the contributing events, when no reference
+/// log was available).
+#[derive(carbide_instrument::Event)]
+#[event(
+    event_name = "measured_boot_mismatch_explained",
+    component = "nico-api",
+    log = warn,
+    metric = none,
+    message = "measured boot report does not match the closest bundle"
+)]
+pub(crate) struct MeasuredBootMismatchExplained {
+    #[context]
+    pub(crate) machine_id: String,
+    #[context]
+    pub(crate) report_id: String,
+    #[context]
+    pub(crate) bundle_id: String,
+    #[context(value)]
+    pub(crate) has_reference_log: bool,
+    #[context]
+    pub(crate) explanation: String,
+}
+
 impl VerifyQuoteState {
     fn from_results(signature_valid: bool, pcr_hash_matches: bool) -> Self {
         match (signature_valid, pcr_hash_matches) {
@@ -316,18 +364,140 @@ fn extract_cred_secret(creds: &[u8]) -> CarbideResult<(Vec<u8>, Vec<u8>)> {
 /// comes to us via the proto as an Option<Vec<u8>) into a String,
 /// for passing to tracing/logging.
 ///
+/// A binary TCG2 log is rendered one event per line. Older scouts
+/// send the text output of tpm2_eventlog instead, which is passed
+/// through as-is.
+///
 /// since the event log is currently "best effort", we'll log a
 /// little "error" in <>'s if we notice there's no event log.
 pub(crate) fn event_log_to_string(event_log: &Option<Vec<u8>>) -> String {
     event_log
         .as_ref()
-        .map(|log_utf8| {
-            String::from_utf8(log_utf8.to_vec())
-                .unwrap_or(String::from("<event log failed utf8 conversion>"))
+        .map(|log_bytes| match EventLog::parse(log_bytes) {
+            Ok(parsed) => parsed.to_string(),
+            Err(_) => String::from_utf8(log_bytes.to_vec())
+                .unwrap_or(String::from("<event log failed utf8 conversion>")),
         })
         .unwrap_or(String::from("<event log empty>"))
 }
 
+/// record_event_log parses the event log that came with a quote,
+/// replays it against the PCR values that went into `report`, and
+/// stores it alongside the report if it reproduces them.
+///
+/// The event log isn't covered by the quote signature, so a log that
+/// doesn't replay to the quoted values tells us nothing; that gets
+/// flagged, and the log is neither stored nor returned. This never
+/// fails attestation on its own -- the quote is what gets judged.
+pub(crate) async fn record_event_log(
+    txn: &mut PgConnection,
+    report: &MeasurementReport,
+    event_log: &Option<Vec<u8>>,
+) -> CarbideResult<Option<EventLog>> {
+    let Some(log_bytes) = event_log else {
+        return Ok(None);
+    };
+    let parsed = match EventLog::parse(log_bytes) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            tracing::debug!(
+                machine_id = %report.machine_id,
+                error = %e,
+                "TPM event log is not a binary TCG2 log, skipping replay",
+            );
+            return Ok(None);
+        }
+    };
+
+    let verification = match parsed.verify_replay(&report.pcr_values()) {
+        Ok(verification) => verification,
+        Err(e) => {
+            tracing::warn!(
+                machine_id = %report.machine_id,
+                error = %e,
+                "Could not replay TPM event log",
+            );
+            return Ok(None);
+        }
+    };
+    if !verification.is_consistent() {
+        carbide_instrument::emit(MeasuredBootEventLogReplayMismatch {
+            mismatched_pcrs: verification
+                .mismatched
+                .iter()
+                .map(|mismatch| mismatch.to_string())
+                .collect::<Vec<_>>()
+                .join("; "),
+            machine_id: report.machine_id.to_string(),
+        });
+        return Ok(None);
+    }
+
+    db::measured_boot::report::set_event_log(txn, report.report_id, log_bytes).await?;
+    Ok(Some(parsed))
+}
+
+/// explain_report_mismatch works out, for a report that didn't match
+/// any bundle, which measurements differ from the closest bundle, and
+/// emits that as an event so operators can see *why* a machine failed
+/// attestation. If a report that matched that bundle left its event log
+/// behind, the explanation is a per-event diff against it; otherwise it
+/// lists the events that went into each mismatched PCR.
+///
+/// This is diagnostics only: any error is logged and swallowed.
+pub(crate) async fn explain_report_mismatch(
+    txn: &mut PgConnection,
+    report: &MeasurementReport,
+    event_log: &EventLog,
+) {
+    if let Err(e) = try_explain_report_mismatch(txn, report, event_log).await {
+        tracing::warn!(
+            machine_id = %report.machine_id,
+            report_id = %report.report_id,
+            error = %e,
+            "Could not explain measured boot mismatch",
+        );
+    }
+}
+
+async fn try_explain_report_mismatch(
+    txn: &mut PgConnection,
+    report: &MeasurementReport,
+    event_log: &EventLog,
+) -> CarbideResult<()> {
+    let journal =
+        db::measured_boot::journal::get_journal_for_report_id(txn, report.report_id).await?;
+    let Some(profile_id) = journal.profile_id else {
+        return Ok(());
+    };
+    let report_values = report.pcr_values();
+    let Some(bundle) =
+        db::measured_boot::bundle::find_closest_match(txn, profile_id, &report_values).await?
+    else {
+        return Ok(());
+    };
+
+    let reference =
+        db::measured_boot::report::reference_event_log_for_bundle_id(txn, bundle.bundle_id)
+            .await?
+            .and_then(|log_bytes| EventLog::parse(&log_bytes).ok());
+    let mismatches =
+        event_log.explain_mismatch(&bundle.pcr_values(), &report_values, reference.as_ref())?;
+
+    carbide_instrument::emit(MeasuredBootMismatchExplained {
+        machine_id: report.machine_id.to_string(),
+        report_id: report.report_id.to_string(),
+        bundle_id: bundle.bundle_id.to_string(),
+        has_reference_log: reference.is_some(),
+        explanation: mismatches
+            .iter()
+            .map(|mismatch| mismatch.to_string())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    });
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "linux-build"), allow(unused_variables))]
 pub(crate) async fn compare_pub_key_against_cert(
     txn: &mut PgConnection,
@@ -0,0 +1,142 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! What a handler reports about the audited call it is serving.
+//!
+//! Handlers already render their request for the tracing span, redacting
+//! secrets where needed. [`crate::api::log_request_data`] and friends pass
+//! that rendering here as well; while a call runs inside [`in_call_scope`] it
+//! becomes the call's request digest, and the object ids in it its targets.
+//! Outside a scope -- reads, background work -- reporting is a no-op.
+
+use std::sync::{Arc, Mutex};
+
+use carbide_uuid::machine::MachineId;
+
+/// Upper bound on target ids kept per event. Bulk calls can name thousands of
+/// objects; past this many the digest still shows what was asked for.
+const MAX_TARGET_IDS: usize = 32;
+
+/// What the handler reported about the call it served.
+#[derive(Debug, Default)]
+pub(super) struct CallDetails {
+    pub(super) request_digest: String,
+    pub(super) target_ids: Vec<String>,
+}
+
+impl CallDetails {
+    pub(super) fn add_target_id(&mut self, id: String) {
+        if self.target_ids.len() < MAX_TARGET_IDS && !self.target_ids.contains(&id) {
+            self.target_ids.push(id);
+        }
+    }
+}
+
+tokio::task_local! {
+    /// Set while an audited call's handler runs.
+    static CURRENT_CALL: Arc<Mutex<CallDetails>>;
+}
+
+/// Records the redacted rendering of the request being served, and the object
+/// ids it names. A no-op outside an audited call.
+pub(crate) fn record_request_digest(rendering: &str) {
+    CURRENT_CALL
+        .try_with(|call| {
+            let mut call = call.lock().expect("audit call details lock poisoned");
+            call.request_digest = rendering.to_string();
+            for id in object_ids(rendering) {
+                call.add_target_id(id.to_string());
+            }
+        })
+        .ok();
+}
+
+/// Adds `id` to the targets of the call being served. A no-op outside an
+/// audited call.
+pub(crate) fn record_target_id(id: impl ToString) {
+    CURRENT_CALL
+        .try_with(|call| {
+            call.lock()
+                .expect("audit call details lock poisoned")
+                .add_target_id(id.to_string())
+        })
+        .ok();
+}
+
+/// The machine ids and hyphenated UUIDs in a request's Debug rendering -- the
+/// two forms every object id in the API takes.
+fn object_ids(rendering: &str) -> impl Iterator<Item = &str> {
+    rendering
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+        .filter(|token| {
+            (token.len() == 36 && uuid::Uuid::try_parse(token).is_ok())
+                || token.parse::<MachineId>().is_ok()
+        })
+}
+
+/// Runs `call` with reporting enabled, returning what it reported.
+pub(super) async fn in_call_scope<F: Future>(call: F) -> (F::Output, CallDetails) {
+    let details = Arc::new(Mutex::new(CallDetails::default()));
+    let output = CURRENT_CALL.scope(details.clone(), call).await;
+    let details = std::mem::take(&mut *details.lock().expect("audit call details lock poisoned"));
+    (output, details)
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_uuid::machine::{MachineIdSource, MachineType};
+
+    use super::*;
+
+    #[test]
+    fn extracts_object_ids_from_renderings() {
+        let machine_id = MachineId::new(
+            MachineIdSource::ProductBoardChassisSerial,
+            [7u8; 32],
+            MachineType::Host,
+        )
+        .to_string();
+        let rendering = format!(
+            "SetMaintenance {{ host_id: Some(MachineId({machine_id})), \
+             instance_id: InstanceId(67e55044-10b1-426f-9247-bb680e5fe0c8), \
+             reference: \"ticket-1234\", digest: 0123456789abcdef0123456789abcdef }}"
+        );
+        assert_eq!(
+            object_ids(&rendering).collect::<Vec<_>>(),
+            vec![machine_id.as_str(), "67e55044-10b1-426f-9247-bb680e5fe0c8"]
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_only_count_inside_a_scope() {
+        record_target_id("outside");
+        let ((), details) = in_call_scope(async {
+            record_request_digest("Request { id: 67e55044-10b1-426f-9247-bb680e5fe0c8 }");
+            record_target_id("67e55044-10b1-426f-9247-bb680e5fe0c8");
+            record_target_id("rack-7");
+        })
+        .await;
+        assert_eq!(
+            details.target_ids,
+            vec!["67e55044-10b1-426f-9247-bb680e5fe0c8", "rack-7"]
+        );
+        assert_eq!(
+            details.request_digest,
+            "Request { id: 67e55044-10b1-426f-9247-bb680e5fe0c8 }"
+        );
+    }
+}
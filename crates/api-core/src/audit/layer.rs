@@ -0,0 +1,376 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The tower layer that records audited `Forge` calls.
+//!
+//! The layer sits inside authentication, so the caller's principals are known,
+//! and outside authorization, so denied calls are recorded too. It only sees
+//! the raw HTTP request, though: the request digest and target ids come from
+//! the handler, through [`super::call`].
+
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use carbide_authn::middleware::ConnectionAttributes;
+use model::audit::{AuditEventSource, NewAuditEvent};
+
+use super::call::{CallDetails, in_call_scope};
+use super::{AuditLog, audit_principals};
+use crate::api::truncate;
+use crate::auth::AuthContext;
+
+/// Method-name prefixes that only read state.
+const READ_ONLY_PREFIXES: &[&str] = &["Find", "Get", "List", "Show", "Watch", "Identify"];
+
+/// Read-only methods whose names don't start with one of
+/// [`READ_ONLY_PREFIXES`].
+const READ_ONLY_METHODS: &[&str] = &[
+    "AdminListResourcePools",
+    "BmcCredentialStatus",
+    "DetermineMachineIngestionState",
+    "DpuAgentUpgradeCheck",
+    "Echo",
+    "ExportSiteMeasurements",
+    "FindAuditEvents",
+    "IBPartitionsForTenant",
+    "IsBmcInManagedHost",
+    "IsInfiniteBootEnabled",
+    "LockdownStatus",
+    "LookupRecord",
+    "MlxAdminConfigCompare",
+    "MlxAdminConfigQuery",
+    "MlxAdminLockdownStatus",
+    "MlxAdminProfileCompare",
+    "MlxAdminProfileList",
+    "MlxAdminProfileShow",
+    "MlxAdminRegistryList",
+    "MlxAdminRegistryShow",
+    "MlxAdminShowDevice",
+    "MlxAdminShowMachine",
+    "NVLinkLogicalPartitionsForTenant",
+    "NVLinkPartitionsForTenant",
+    "NetworkSegmentsForVpc",
+    "NmxcBrowse",
+    "RedfishBrowse",
+    "RedfishListActions",
+    "ScoutStreamShowConnections",
+    "SearchVpcPrefixes",
+    "TpmShowCaCerts",
+    "TpmShowUnmatchedEkCerts",
+    "UfmBrowse",
+    "ValidateTenantPublicKey",
+    "Version",
+    "VerifySkuForMachine",
+];
+
+/// Mutating methods that agents call on a timer to report what they observe.
+/// They change no intent, and at fleet scale would bury every operator action
+/// under heartbeats.
+const TELEMETRY_METHODS: &[&str] = &[
+    "DiscoverDhcp",
+    "ExpireDhcpLease",
+    "ForgeAgentControl",
+    "HeartbeatMachineValidationRun",
+    "InsertMachineHealthReport",
+    "InsertNVLinkDomainHealthReport",
+    "InsertPowerShelfHealthReport",
+    "InsertRackHealthReport",
+    "InsertSwitchHealthReport",
+    "PublishMlxDeviceReport",
+    "PublishMlxObservationReport",
+    "RecordDpuNetworkStatus",
+    "ReportScoutFirmwareUpgradeStatus",
+    "ScoutStream",
+    "ScoutStreamPing",
+    "UpdateAgentReportedInventory",
+    "UpdateInstancePhoneHomeLastContact",
+];
+
+/// Whether calls to the `Forge` method `method` belong in the audit trail.
+fn is_audited(method: &str) -> bool {
+    !(READ_ONLY_PREFIXES
+        .iter()
+        .any(|prefix| method.starts_with(prefix))
+        || READ_ONLY_METHODS.contains(&method)
+        || TELEMETRY_METHODS.contains(&method))
+}
+
+/// The audited `Forge` method a request path names, if any.
+fn audited_method(path: &str) -> Option<&str> {
+    let (service, method) = path.strip_prefix('/')?.split_once('/')?;
+    (service == ::rpc::forge::forge_server::SERVICE_NAME && is_audited(method)).then_some(method)
+}
+
+/// The outcome columns for a finished call: the gRPC code and message, or the
+/// HTTP status when the call never reached gRPC (e.g. an authorization denial).
+fn call_outcome<B, E>(result: &Result<hyper::http::Response<B>, E>) -> (String, Option<String>) {
+    match result {
+        Ok(response) if response.status() == hyper::http::StatusCode::OK => {
+            match response.extensions().get::<tonic::Status>() {
+                Some(status) => (
+                    format!("{:?}", status.code()),
+                    Some(status.message())
+                        .filter(|message| !message.is_empty())
+                        .map(|message| {
+                            truncate(message.to_string(), ::rpc::MAX_ERR_MSG_SIZE as usize)
+                        }),
+                ),
+                None => (format!("{:?}", tonic::Code::Ok), None),
+            }
+        }
+        Ok(response) => (response.status().as_u16().to_string(), None),
+        Err(_) => ("HttpError".to_string(), None),
+    }
+}
+
+/// A tower Layer which creates an `AuditService` for every request
+#[derive(Debug, Clone)]
+pub(crate) struct AuditLayer {
+    audit_log: AuditLog,
+}
+
+impl AuditLayer {
+    pub(crate) fn new(audit_log: AuditLog) -> Self {
+        Self { audit_log }
+    }
+}
+
+impl<S> tower::Layer<S> for AuditLayer {
+    type Service = AuditService<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        AuditService {
+            service,
+            audit_log: self.audit_log.clone(),
+        }
+    }
+}
+
+// This service records audited Forge calls once their outcome is known
+#[derive(Clone, Debug)]
+pub(crate) struct AuditService<S> {
+    service: S,
+    audit_log: AuditLog,
+}
+
+impl<S, RequestBody, ResponseBody> tower::Service<hyper::http::Request<RequestBody>>
+    for AuditService<S>
+where
+    S: tower::Service<
+            hyper::http::Request<RequestBody>,
+            Response = hyper::http::Response<ResponseBody>,
+        > + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+    RequestBody: Send + 'static,
+    ResponseBody: Send + 'static,
+{
+    type Response = hyper::http::Response<ResponseBody>;
+    type Error = S::Error;
+    type Future = tonic::codegen::BoxFuture<Self::Response, S::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: hyper::http::Request<RequestBody>) -> Self::Future {
+        let mut service = self.service.clone();
+        let Some(method) = audited_method(request.uri().path()).map(str::to_string) else {
+            return Box::pin(service.call(request));
+        };
+        let audit_log = self.audit_log.clone();
+
+        Box::pin(async move {
+            let occurred_at = chrono::Utc::now();
+            let principals = request
+                .extensions()
+                .get::<AuthContext>()
+                .map(|auth_context| audit_principals(&auth_context.principals))
+                .unwrap_or_default();
+            let client_address = request
+                .extensions()
+                .get::<Arc<ConnectionAttributes>>()
+                .map(|conn_attrs| conn_attrs.peer_address.to_string());
+
+            let (
+                result,
+                CallDetails {
+                    request_digest,
+                    target_ids,
+                },
+            ) = in_call_scope(service.call(request)).await;
+
+            let (outcome, outcome_message) = call_outcome(&result);
+            audit_log.record(NewAuditEvent {
+                occurred_at,
+                source: AuditEventSource::Grpc,
+                method,
+                principals,
+                target_ids,
+                request_digest,
+                outcome,
+                outcome_message,
+                client_address,
+            });
+
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_authn::middleware::Principal;
+    use carbide_test_support::value_scenarios;
+
+    use super::*;
+    use crate::audit::{record_request_digest, record_target_id};
+
+    #[test]
+    fn classifies_forge_methods() {
+        value_scenarios!(
+            run = |path: &str| audited_method(path).is_some();
+            "mutating call" {
+                "/forge.Forge/AdminForceDeleteMachine" => true,
+                "/forge.Forge/SetMaintenance" => true,
+                "/forge.Forge/AllocateInstance" => true,
+            }
+            "read by prefix" {
+                "/forge.Forge/FindMachineIds" => false,
+                "/forge.Forge/GetMachine" => false,
+                "/forge.Forge/WatchMachineStates" => false,
+            }
+            "read by name" {
+                "/forge.Forge/Version" => false,
+                "/forge.Forge/RedfishBrowse" => false,
+                "/forge.Forge/FindAuditEvents" => false,
+            }
+            "agent telemetry" {
+                "/forge.Forge/RecordDpuNetworkStatus" => false,
+                "/forge.Forge/InsertMachineHealthReport" => false,
+            }
+            "not a Forge method" {
+                "/grpc.reflection.v1alpha.ServerReflection/ServerReflectionInfo" => false,
+                "/admin/machine" => false,
+                "/" => false,
+            }
+        );
+    }
+
+    /// Answers every call like a failed unary gRPC handler that named a
+    /// target, or with a bare 403 as the authorization layer would.
+    #[derive(Clone)]
+    struct FakeHandler {
+        denied: bool,
+    }
+
+    impl tower::Service<hyper::http::Request<()>> for FakeHandler {
+        type Response = hyper::http::Response<()>;
+        type Error = std::convert::Infallible;
+        type Future = tonic::codegen::BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: hyper::http::Request<()>) -> Self::Future {
+            let denied = self.denied;
+            Box::pin(async move {
+                if denied {
+                    return Ok(hyper::http::Response::builder()
+                        .status(hyper::http::StatusCode::FORBIDDEN)
+                        .body(())
+                        .unwrap());
+                }
+                record_request_digest(
+                    "ReleaseInstanceRequest { id: 67e55044-10b1-426f-9247-bb680e5fe0c8 }",
+                );
+                record_target_id("67e55044-10b1-426f-9247-bb680e5fe0c8");
+                let mut response = hyper::http::Response::new(());
+                response
+                    .extensions_mut()
+                    .insert(tonic::Status::not_found("instance not found"));
+                Ok(response)
+            })
+        }
+    }
+
+    fn request(path: &str) -> hyper::http::Request<()> {
+        let mut request = hyper::http::Request::builder()
+            .method(hyper::http::Method::POST)
+            .uri(path)
+            .body(())
+            .unwrap();
+        request.extensions_mut().insert(AuthContext {
+            principals: vec![Principal::SpiffeServiceIdentifier("admin-cli".to_string())],
+            authorization: None,
+        });
+        request
+            .extensions_mut()
+            .insert(Arc::new(ConnectionAttributes {
+                peer_address: "203.0.113.15:41000".parse().unwrap(),
+                peer_certificates: Vec::new(),
+            }));
+        request
+    }
+
+    async fn serve(denied: bool, path: &str) -> Option<NewAuditEvent> {
+        use tower::{Layer, Service};
+
+        let (audit_log, mut events) = AuditLog::channel();
+        let mut service = AuditLayer::new(audit_log).layer(FakeHandler { denied });
+        service.call(request(path)).await.unwrap();
+        drop(service);
+        events.recv().await
+    }
+
+    #[tokio::test]
+    async fn records_handler_details_and_outcome() {
+        let event = serve(false, "/forge.Forge/ReleaseInstance")
+            .await
+            .expect("an audit event");
+        assert_eq!(event.source, AuditEventSource::Grpc);
+        assert_eq!(event.method, "ReleaseInstance");
+        assert_eq!(event.principals, vec!["spiffe-service-id/admin-cli"]);
+        assert_eq!(
+            event.target_ids,
+            vec!["67e55044-10b1-426f-9247-bb680e5fe0c8"]
+        );
+        assert_eq!(
+            event.request_digest,
+            "ReleaseInstanceRequest { id: 67e55044-10b1-426f-9247-bb680e5fe0c8 }"
+        );
+        assert_eq!(event.outcome, "NotFound");
+        assert_eq!(event.outcome_message.as_deref(), Some("instance not found"));
+        assert_eq!(event.client_address.as_deref(), Some("203.0.113.15:41000"));
+    }
+
+    #[tokio::test]
+    async fn records_denials() {
+        let event = serve(true, "/forge.Forge/ReleaseInstance")
+            .await
+            .expect("an audit event");
+        assert_eq!(event.outcome, "403");
+        assert!(event.request_digest.is_empty());
+    }
+
+    #[tokio::test]
+    async fn skips_reads() {
+        assert_eq!(serve(false, "/forge.Forge/FindInstanceIds").await, None);
+    }
+}
@@ -0,0 +1,293 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Durable audit trail of mutating API calls.
+//!
+//! [`AuditLayer`] records every audited `Forge` call once its outcome is known,
+//! and the admin web UI records its own state-changing requests through
+//! [`crate::Api::audit_admin_web_action`]. Both hand events to an [`AuditLog`],
+//! whose writer task stores them in the `audit_events` table in batches, so a
+//! slow database delays the audit trail rather than the calls being audited.
+
+mod call;
+mod layer;
+
+use std::time::Duration;
+
+use carbide_authn::middleware::Principal;
+use model::audit::{AuditEventSource, NewAuditEvent};
+use sqlx::PgPool;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+
+pub(crate) use self::call::{record_request_digest, record_target_id};
+pub(crate) use self::layer::AuditLayer;
+
+/// Events waiting for the writer. Operator traffic is a few calls per second
+/// and a batch insert takes milliseconds, so this only fills when the database
+/// is unreachable -- and then recording must not apply backpressure to the
+/// calls being audited, so overflow is dropped and counted instead.
+const QUEUE_CAPACITY: usize = 4096;
+
+/// Deadline for one batched insert, connection acquisition included.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Why an audit event never reached the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, carbide_instrument::LabelValue)]
+enum DropReason {
+    /// The writer fell [`QUEUE_CAPACITY`] events behind.
+    QueueFull,
+    /// The writer task has exited.
+    WriterStopped,
+    /// The batch insert failed or missed its deadline.
+    WriteFailed,
+}
+
+/// An audited call left no durable record. Every drop is a gap in the audit
+/// trail, so this warns per event and the counter is worth alerting on.
+#[derive(carbide_instrument::Event)]
+#[event(
+    event_name = "audit_event_dropped",
+    metric_name = "carbide_audit_events_dropped_total",
+    component = "nico-api",
+    log = warn,
+    metric = counter,
+    message = "Dropped an audit event before it was stored",
+    describe = "Number of audit events that were never stored, by reason"
+)]
+struct AuditEventDropped {
+    #[label]
+    reason: DropReason,
+    #[context]
+    method: String,
+    #[context]
+    principals: String,
+    #[context]
+    error: String,
+}
+
+impl AuditEventDropped {
+    fn report(reason: DropReason, event: &NewAuditEvent, error: impl ToString) {
+        carbide_instrument::emit(AuditEventDropped {
+            reason,
+            method: event.method.clone(),
+            principals: event.principals.join(","),
+            error: error.to_string(),
+        });
+    }
+}
+
+/// Handle for recording audit events. Clones share one writer task, which
+/// drains the queue and exits once the last handle is dropped.
+#[derive(Clone, Debug)]
+pub(crate) struct AuditLog {
+    sender: mpsc::Sender<NewAuditEvent>,
+}
+
+impl AuditLog {
+    /// Spawns the writer task on the current runtime.
+    pub(crate) fn start(db_pool: PgPool) -> Self {
+        let (sender, receiver) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(write_events(db_pool, receiver));
+        Self { sender }
+    }
+
+    /// Runs `handler` for the admin web request `action` and records it,
+    /// with the HTTP status as the outcome.
+    ///
+    /// `Forge` calls the handler makes in-process report their request digest
+    /// and target ids just as they would over gRPC. Without one, the request
+    /// path stands in as the digest.
+    pub(crate) async fn record_admin_web_action<B>(
+        &self,
+        action: AdminWebAction,
+        handler: impl Future<Output = hyper::http::Response<B>>,
+    ) -> hyper::http::Response<B> {
+        let occurred_at = chrono::Utc::now();
+        let (response, call) = call::in_call_scope(handler).await;
+
+        let mut details = call::CallDetails::default();
+        for id in action.path_params.into_iter().chain(call.target_ids) {
+            details.add_target_id(id);
+        }
+        self.record(NewAuditEvent {
+            occurred_at,
+            source: AuditEventSource::AdminWeb,
+            method: action.method,
+            principals: audit_principals(&action.principals),
+            target_ids: details.target_ids,
+            request_digest: if call.request_digest.is_empty() {
+                action.path
+            } else {
+                call.request_digest
+            },
+            outcome: response.status().as_u16().to_string(),
+            outcome_message: None,
+            client_address: action.client_address,
+        });
+        response
+    }
+
+    /// Queues `event` for storage without waiting for it to be written.
+    pub(crate) fn record(&self, event: NewAuditEvent) {
+        match self.sender.try_send(event) {
+            Ok(()) => {}
+            Err(TrySendError::Full(event)) => {
+                AuditEventDropped::report(DropReason::QueueFull, &event, "audit queue is full")
+            }
+            Err(TrySendError::Closed(event)) => AuditEventDropped::report(
+                DropReason::WriterStopped,
+                &event,
+                "audit writer has stopped",
+            ),
+        }
+    }
+
+    /// A handle whose events land on the returned receiver instead of the
+    /// database.
+    #[cfg(test)]
+    pub(crate) fn channel() -> (Self, mpsc::Receiver<NewAuditEvent>) {
+        let (sender, receiver) = mpsc::channel(QUEUE_CAPACITY);
+        (Self { sender }, receiver)
+    }
+}
+
+async fn write_events(db_pool: PgPool, mut receiver: mpsc::Receiver<NewAuditEvent>) {
+    let mut batch = Vec::with_capacity(db::audit::MAX_INSERT_BATCH);
+    while receiver
+        .recv_many(&mut batch, db::audit::MAX_INSERT_BATCH)
+        .await
+        > 0
+    {
+        let written = tokio::time::timeout(WRITE_TIMEOUT, async {
+            let mut conn = db_pool
+                .acquire()
+                .await
+                .map_err(|e| db::DatabaseError::acquire(e).to_string())?;
+            db::audit::insert(&mut conn, &batch)
+                .await
+                .map_err(|e| e.to_string())
+        })
+        .await
+        .unwrap_or_else(|_| Err(format!("insert missed its {WRITE_TIMEOUT:?} deadline")));
+        if let Err(error) = written {
+            for event in &batch {
+                AuditEventDropped::report(DropReason::WriteFailed, event, &error);
+            }
+        }
+        batch.clear();
+    }
+}
+
+/// A state-changing admin web UI request, as the web router sees it.
+#[derive(Clone, Debug)]
+pub struct AdminWebAction {
+    /// `"<HTTP method> <route>"`, e.g. `POST /machine/{machine_id}/maintenance`.
+    pub method: String,
+    /// The request path, the digest of last resort.
+    pub path: String,
+    /// The values of the route's path parameters, which name its targets.
+    pub path_params: Vec<String>,
+    pub principals: Vec<Principal>,
+    pub client_address: Option<String>,
+}
+
+/// Renders `principals` for the audit trail: [`Principal::audit_identity`],
+/// with the user name appended for external users, since "someone in this
+/// group" does not answer who made a change.
+pub(crate) fn audit_principals(principals: &[Principal]) -> Vec<String> {
+    principals
+        .iter()
+        .map(|principal| match principal {
+            Principal::ExternalUser(info) => match &info.user {
+                Some(user) => format!("{}:{user}", principal.audit_identity()),
+                None => principal.audit_identity(),
+            },
+            other => other.audit_identity(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_authn::middleware::ExternalUserInfo;
+
+    use super::*;
+
+    #[test]
+    fn external_users_are_named() {
+        let principals = [
+            Principal::ExternalUser(ExternalUserInfo::new(
+                None,
+                "ops".to_string(),
+                Some("alice".to_string()),
+            )),
+            Principal::ExternalUser(ExternalUserInfo::new(None, "ops".to_string(), None)),
+            Principal::TrustedCertificate,
+        ];
+        assert_eq!(
+            audit_principals(&principals),
+            vec![
+                "external-role/ops:alice",
+                "external-role/ops",
+                "trusted-certificate"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn admin_web_actions_merge_path_and_handler_targets() {
+        let (audit_log, mut events) = AuditLog::channel();
+        let action = AdminWebAction {
+            method: "POST /machine/{machine_id}/maintenance".to_string(),
+            path: "/machine/m-1/maintenance".to_string(),
+            path_params: vec!["m-1".to_string()],
+            principals: vec![Principal::from_web_cookie(
+                "alice".to_string(),
+                "ops".to_string(),
+            )],
+            client_address: None,
+        };
+        let response = audit_log
+            .record_admin_web_action(action.clone(), async {
+                record_target_id("m-1");
+                record_target_id("m-2");
+                hyper::http::Response::builder()
+                    .status(hyper::http::StatusCode::SEE_OTHER)
+                    .body(())
+                    .unwrap()
+            })
+            .await;
+        assert_eq!(response.status(), hyper::http::StatusCode::SEE_OTHER);
+
+        let event = events.recv().await.expect("an audit event");
+        assert_eq!(event.source, AuditEventSource::AdminWeb);
+        assert_eq!(event.principals, vec!["external-role/ops:alice"]);
+        assert_eq!(event.target_ids, vec!["m-1", "m-2"]);
+        assert_eq!(event.request_digest, "/machine/m-1/maintenance");
+        assert_eq!(event.outcome, "303");
+
+        audit_log
+            .record_admin_web_action(action, async {
+                record_request_digest("MaintenanceRequest { host_id: m-1 }");
+                hyper::http::Response::new(())
+            })
+            .await;
+        let event = events.recv().await.expect("an audit event");
+        assert_eq!(event.request_digest, "MaintenanceRequest { host_id: m-1 }");
+    }
+}
@@ -68,6 +68,10 @@ impl InternalRBACRules {
         x.perm("UpdateDomain", vec![]);
         x.perm("DeleteDomain", vec![]);
         x.perm("FindDomain", vec![ForgeAdminCLI]);
+        x.perm("CreateDnsCustomRecord", vec![ForgeAdminCLI]);
+        x.perm("UpdateDnsCustomRecord", vec![ForgeAdminCLI]);
+        x.perm("DeleteDnsCustomRecord", vec![ForgeAdminCLI]);
+        x.perm("FindDnsCustomRecords", vec![ForgeAdminCLI]);
         x.perm("CreateVpc", vec![SiteAgent, Machineatron]);
         x.perm("UpdateVpc", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("UpdateVpcVirtualization", vec![ForgeAdminCLI, SiteAgent]);
@@ -135,6 +139,10 @@ impl InternalRBACRules {
             "AllocateInstances",
             vec![ForgeAdminCLI, Machineatron, SiteAgent],
         );
+        x.perm(
+            "AllocateInstancesByType",
+            vec![ForgeAdminCLI, Machineatron, SiteAgent],
+        );
         x.perm("ReleaseInstance", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("UpdateInstanceOperatingSystem", vec![SiteAgent]);
         x.perm("UpdateInstanceConfig", vec![ForgeAdminCLI, SiteAgent]);
@@ -213,12 +221,17 @@ impl InternalRBACRules {
         x.perm("LookupRecordLegacy", vec![Dns]);
         x.perm("GetAllDomainMetadata", vec![Dns]);
         x.perm("GetAllDomains", vec![Dns]);
+        x.perm("GetAllRecordsForDomain", vec![Dns]);
         x.perm("InvokeInstancePower", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("ForgeAgentControl", vec![Machineatron, Scout]);
         x.perm("DiscoverMachine", vec![Anonymous]);
         x.perm("RenewMachineCertificate", vec![Agent]);
         x.perm("DiscoveryCompleted", vec![Machineatron, Scout]);
         x.perm("CleanupMachineCompleted", vec![Machineatron, Scout]);
+        x.perm(
+            "FindDiskSanitizationCertificates",
+            vec![ForgeAdminCLI, SiteAgent],
+        );
         x.perm("ReportForgeScoutError", vec![Scout]);
         x.perm("ReportScoutFirmwareUpgradeStatus", vec![Scout]);
         x.perm("DiscoverDhcp", vec![Dhcp, Machineatron]);
@@ -257,6 +270,12 @@ impl InternalRBACRules {
         x.perm("FindMachineIdsByBmcIps", vec![ForgeAdminCLI, Flow]);
         x.perm("FindMachineHealthHistories", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("FindMachineStateHistories", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("ExplainMachineStateHandling", vec![ForgeAdminCLI]);
+        x.perm("WatchMachineStates", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("WatchInstanceStates", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("WatchRackStates", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("WatchSwitchStates", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("WatchPowerShelfStates", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("IdentifyUuid", vec![ForgeAdminCLI]);
         x.perm("IdentifyMac", vec![ForgeAdminCLI]);
         x.perm("IdentifySerial", vec![ForgeAdminCLI, Machineatron, Flow]);
@@ -483,17 +502,21 @@ impl InternalRBACRules {
         x.perm("GetOperatingSystem", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("UpdateOperatingSystem", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("DeleteOperatingSystem", vec![ForgeAdminCLI, SiteAgent]);
-        x.perm("FindOperatingSystemIds", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm(
+            "FindOperatingSystemIds",
+            vec![ForgeAdminCLI, SiteAgent, Pxe],
+        );
         x.perm("FindOperatingSystemsByIds", vec![ForgeAdminCLI, SiteAgent]);
         x.perm(
             "GetOperatingSystemCachableIpxeTemplateArtifacts",
-            vec![ForgeAdminCLI],
+            vec![ForgeAdminCLI, Pxe],
         );
         x.perm(
             "UpdateOperatingSystemCachableIpxeTemplateArtifacts",
-            vec![ForgeAdminCLI],
+            vec![ForgeAdminCLI, Pxe],
         );
         x.perm("ReWrapSecrets", vec![ForgeAdminCLI]);
+        x.perm("GetSecretHistory", vec![ForgeAdminCLI]);
         x.perm("GetIpxeTemplate", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("ListIpxeTemplates", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("FindRackStateHistories", vec![ForgeAdminCLI, Machineatron]);
@@ -511,6 +534,7 @@ impl InternalRBACRules {
         );
         x.perm("GetContainerRegistryCredential", vec![ForgeAdminCLI, Scout]);
         x.perm("SetContainerRegistryCredential", vec![ForgeAdminCLI]);
+        x.perm("RevokeCertificate", vec![ForgeAdminCLI]);
         x.perm(
             "AddUpdateMachineValidationExternalConfig",
             vec![ForgeAdminCLI, SiteAgent],
@@ -623,6 +647,10 @@ impl InternalRBACRules {
             "GetNetworkSecurityGroupAttachments",
             vec![ForgeAdminCLI, SiteAgent],
         );
+        x.perm(
+            "SimulateNetworkSecurityGroupFlow",
+            vec![ForgeAdminCLI, SiteAgent],
+        );
         x.perm(
             "GetDesiredFirmwareVersions",
             vec![ForgeAdminCLI, Machineatron, Flow],
@@ -669,6 +697,11 @@ impl InternalRBACRules {
         x.perm("ProbeBmcVendor", vec![ForgeAdminCLI]);
         x.perm("SetFirmwareUpdateTimeWindow", vec![ForgeAdminCLI, Flow]);
         x.perm("ListHostFirmware", vec![ForgeAdminCLI, Flow]);
+        x.perm("CreateFirmwareRollout", vec![ForgeAdminCLI]);
+        x.perm("FindFirmwareRollouts", vec![ForgeAdminCLI]);
+        x.perm("PauseFirmwareRollout", vec![ForgeAdminCLI]);
+        x.perm("ResumeFirmwareRollout", vec![ForgeAdminCLI]);
+        x.perm("DeleteFirmwareRollout", vec![ForgeAdminCLI]);
         x.perm("EnableInfiniteBoot", vec![ForgeAdminCLI]);
         x.perm("IsInfiniteBootEnabled", vec![ForgeAdminCLI]);
         x.perm("Lockdown", vec![ForgeAdminCLI]);
@@ -682,6 +715,8 @@ impl InternalRBACRules {
             vec![Agent, Scout, Machineatron, ForgeAdminCLI],
         );
         x.perm("TrimTable", vec![ForgeAdminCLI, MaintenanceJobs]);
+        x.perm("FindAuditEvents", vec![ForgeAdminCLI]);
+        x.perm("GetStateDwellAnalytics", vec![ForgeAdminCLI]);
         x.perm("ListNvlinkNmxcEndpoints", vec![ForgeAdminCLI]);
         x.perm("CreateNvlinkNmxcEndpoint", vec![ForgeAdminCLI]);
         x.perm("UpdateNvlinkNmxcEndpoint", vec![ForgeAdminCLI]);
@@ -1155,6 +1190,11 @@ mod rbac_rule_tests {
                 None
             ))]
         ));
+        // carbide-pxe runs the boot artifact cache and reports cached_url itself.
+        assert!(InternalRBACRules::allowed_from_static(
+            "UpdateOperatingSystemCachableIpxeTemplateArtifacts",
+            &[Principal::SpiffeServiceIdentifier("nico-pxe".to_string())]
+        ));
         assert!(InternalRBACRules::allowed_from_static(
             "CreateVpc",
             &[Principal::SpiffeServiceIdentifier(
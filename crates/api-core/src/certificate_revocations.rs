@@ -0,0 +1,114 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Client-certificate revocations, stored in the `certificate_revocations`
+//! table and handed to the authentication layer as a CRL that the certificate
+//! provider signs. Every replica builds its [`RevocationList`] from the same
+//! table, so a certificate revoked through one replica is refused by all of
+//! them once they reload.
+
+use std::sync::Arc;
+
+use carbide_authn::revocation::RevocationList;
+use carbide_secrets::certificates::{CertificateProvider, Revocation};
+use db::Transaction;
+use model::certificate_revocation::CertificateRevocation;
+use sqlx::PgPool;
+use tokio::sync::Mutex;
+
+use crate::{CarbideError, CarbideResult};
+
+pub(crate) struct CertificateRevocations {
+    provider: Arc<dyn CertificateProvider>,
+    database_connection: PgPool,
+    list: Arc<RevocationList>,
+    /// Serializes signing and installing CRLs, so that a CRL signed from an
+    /// older read of the table never replaces a newer one.
+    update_lock: Mutex<()>,
+}
+
+impl CertificateRevocations {
+    pub(crate) fn new(provider: Arc<dyn CertificateProvider>, database_connection: PgPool) -> Self {
+        Self {
+            provider,
+            database_connection,
+            list: Arc::new(RevocationList::new()),
+            update_lock: Mutex::new(()),
+        }
+    }
+
+    /// The list [`Self::reload`] and [`Self::revoke`] keep up to date.
+    pub(crate) fn list(&self) -> Arc<RevocationList> {
+        self.list.clone()
+    }
+
+    /// Signs a CRL of every stored revocation and installs it into
+    /// [`Self::list`]. Returns `false`, installing nothing, if the certificate
+    /// provider does not sign CRLs.
+    pub(crate) async fn reload(&self) -> CarbideResult<bool> {
+        let _guard = self.update_lock.lock().await;
+        let revocations = db::certificate_revocation::find_all(&self.database_connection).await?;
+        let Some(crl) = self.sign(&revocations).await? else {
+            return Ok(false);
+        };
+        self.install(&crl)?;
+        Ok(true)
+    }
+
+    /// Revokes `serial_number`, given in the form
+    /// [`carbide_secrets::local_ca::normalize_serial`] returns, and installs
+    /// the resulting CRL before returning, so this replica refuses the
+    /// certificate at once. Returns `false` if it was already revoked.
+    pub(crate) async fn revoke(&self, serial_number: &str) -> CarbideResult<bool> {
+        let _guard = self.update_lock.lock().await;
+        let mut txn = Transaction::begin(&self.database_connection).await?;
+        let newly_revoked = db::certificate_revocation::insert(&mut txn, serial_number).await?;
+        let revocations = db::certificate_revocation::find_all(&mut txn).await?;
+        // Signed before committing, so a provider that cannot publish the
+        // revocation leaves none behind.
+        let Some(crl) = self.sign(&revocations).await? else {
+            return Err(CarbideError::FailedPrecondition(
+                "certificate provider signs no revocation list; revoke through Vault's PKI"
+                    .to_string(),
+            ));
+        };
+        txn.commit().await?;
+        self.install(&crl)?;
+        Ok(newly_revoked)
+    }
+
+    async fn sign(&self, revocations: &[CertificateRevocation]) -> CarbideResult<Option<Vec<u8>>> {
+        let revocations: Vec<Revocation> = revocations
+            .iter()
+            .map(|revocation| Revocation {
+                serial_number: revocation.serial_number.clone(),
+                revoked_at: revocation.revoked_at.timestamp(),
+            })
+            .collect();
+        self.provider
+            .revocation_list(&revocations)
+            .await
+            .map_err(|e| CarbideError::internal(format!("sign revocation list: {e:?}")))
+    }
+
+    fn install(&self, crl: &[u8]) -> CarbideResult<()> {
+        self.list
+            .update_from_crl(crl)
+            .map(|_| ())
+            .map_err(|e| CarbideError::internal(format!("load revocation list: {e}")))
+    }
+}
@@ -479,6 +479,12 @@ pub struct CarbideConfig {
     #[serde(default = "default_max_find_by_ids")]
     pub max_find_by_ids: u32,
 
+    /// Maximum number of instances a single batch or
+    /// instance-type allocation request may allocate.
+    /// Default is 100.
+    #[serde(default = "default_max_instance_allocation_batch_size")]
+    pub max_instance_allocation_batch_size: u32,
+
     /// Network security group settings: max expanded rule
     /// count, stateful ACL enforcement, and policy overrides
     /// injected before user-defined rules.
@@ -520,6 +526,12 @@ pub struct CarbideConfig {
     #[serde(default)]
     pub measured_boot_collector: MeasuredBootMetricsCollectorConfig,
 
+    /// Rack power budget manager configuration. Derives
+    /// each rack's power capacity from its power shelves
+    /// and rack profile, and caps hosts to stay within it.
+    #[serde(default)]
+    pub rack_power_budget: RackPowerBudgetConfig,
+
     /// Machine validation test configuration. Runs
     /// hardware tests (memory latency, SSD I/O, etc.)
     /// after ingestion to verify machine health.
@@ -1000,13 +1012,15 @@ pub struct CertificatesConfig {
     /// `backend = "dedicated_vault"`, ignored otherwise.
     #[serde(default)]
     pub dedicated_vault: Option<DedicatedVaultSettings>,
+
+    /// Settings for the built-in CA. Required when `backend = "local_ca"`,
+    /// ignored otherwise.
+    #[serde(default)]
+    pub local_ca: Option<LocalCaSettings>,
 }
 
 /// Tag selecting the certificate backend. The matching settings (if any) live
 /// in their own sub-table, so the choice is explicit rather than inferred.
-// The shared `Vault` suffix is intentional: both current backends are Vault
-// backends. The lint resolves once a non-Vault backend is added.
-#[allow(clippy::enum_variant_names)]
 #[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CertBackendKind {
@@ -1015,6 +1029,36 @@ pub enum CertBackendKind {
     SharedVault,
     /// Use a dedicated Vault configured under `[certificates.dedicated_vault]`.
     DedicatedVault,
+    /// Issue certificates in-process from the CA configured under
+    /// `[certificates.local_ca]`. Needs a `[secrets]` KMS to unseal its key.
+    LocalCa,
+}
+
+/// `[certificates.local_ca]` settings.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LocalCaSettings {
+    /// PEM intermediate CA certificate, optionally followed by its chain.
+    pub ca_cert_path: String,
+    /// The CA private key, sealed through the KMS.
+    pub sealed_key_path: String,
+    /// A plaintext PKCS#8 key sealed into `sealed_key_path` on first start.
+    /// Remove it (and this setting) once the sealed key exists.
+    #[serde(default)]
+    pub import_key_path: Option<String>,
+    /// KEK that seals an imported key.
+    pub kek_id: String,
+    /// How long each generated CRL is valid. Default is 1h.
+    #[serde(
+        default = "default_local_ca_crl_validity",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub crl_validity: std::time::Duration,
+}
+
+pub const fn default_local_ca_crl_validity() -> std::time::Duration {
+    std::time::Duration::from_secs(60 * 60)
 }
 
 /// `[certificates.dedicated_vault]` settings.
@@ -1080,6 +1124,21 @@ impl CertificatesConfig {
                     },
                 )
             }
+            CertBackendKind::LocalCa => {
+                let local_ca = self.local_ca.as_ref().ok_or_else(|| {
+                    eyre::eyre!(
+                        "[certificates] backend = \"local_ca\" requires a \
+                         [certificates.local_ca] section"
+                    )
+                })?;
+                carbide_secrets::CertBackend::LocalCa(carbide_secrets::LocalCaConfig {
+                    ca_cert_path: local_ca.ca_cert_path.clone().into(),
+                    sealed_key_path: local_ca.sealed_key_path.clone().into(),
+                    import_key_path: local_ca.import_key_path.clone().map(Into::into),
+                    kek_id: local_ca.kek_id.clone(),
+                    crl_validity: local_ca.crl_validity,
+                })
+            }
         };
         Ok(carbide_secrets::CertificateConfig { backend })
     }
@@ -3483,8 +3542,21 @@ fn deserialize_network_security_group_policy_overrides<'de, D>(
 where
     D: Deserializer<'de>,
 {
-    Vec::<NetworkSecurityGroupRuleConfig>::deserialize(deserializer)
-        .map(|rules| rules.into_iter().map(Into::into).collect())
+    let rules = Vec::<NetworkSecurityGroupRuleConfig>::deserialize(deserializer)?;
+
+    // Overrides apply to every tenant, so there is no tenant
+    // to resolve object references against.
+    if let Some(rule) = rules
+        .iter()
+        .find(|r| r.src_net.is_reference() || r.dst_net.is_reference())
+    {
+        return Err(serde::de::Error::custom(format!(
+            "policy override rules may only use prefixes, found {} -> {}",
+            rule.src_net, rule.dst_net
+        )));
+    }
+
+    Ok(rules.into_iter().map(Into::into).collect())
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -3508,6 +3580,18 @@ pub struct NetworkSecurityGroupConfig {
         deserialize_with = "deserialize_network_security_group_policy_overrides"
     )]
     pub policy_overrides: Vec<NetworkSecurityGroupRule>,
+
+    /// Interval at which security-group, VPC and label-selector
+    /// references in NSG rules are re-resolved.  NSGs whose
+    /// references now resolve to different prefixes have their
+    /// version bumped so the change propagates to DPUs.
+    /// Default is 30 seconds.
+    #[serde(
+        default = "NetworkSecurityGroupConfig::default_reference_refresh_interval",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub reference_refresh_interval: std::time::Duration,
 }
 
 impl Default for NetworkSecurityGroupConfig {
@@ -3516,10 +3600,17 @@ impl Default for NetworkSecurityGroupConfig {
             max_network_security_group_size: default_max_network_security_group_size(),
             stateful_acls_enabled: default_to_true(),
             policy_overrides: vec![],
+            reference_refresh_interval: Self::default_reference_refresh_interval(),
         }
     }
 }
 
+impl NetworkSecurityGroupConfig {
+    const fn default_reference_refresh_interval() -> std::time::Duration {
+        std::time::Duration::from_secs(30)
+    }
+}
+
 /// Configuration for rolling machine updates and
 /// maintenance windows.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
@@ -3541,6 +3632,10 @@ pub fn default_max_find_by_ids() -> u32 {
     100
 }
 
+pub fn default_max_instance_allocation_batch_size() -> u32 {
+    100
+}
+
 pub fn default_max_site_prefixes_per_tenant() -> u32 {
     8
 }
@@ -3617,6 +3712,67 @@ impl MeasuredBootMetricsCollectorConfig {
     }
 }
 
+/// Configuration for the rack power budget manager, which
+/// assigns Redfish power limits to the hosts of racks whose
+/// profile defines a `power_budget`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RackPowerBudgetConfig {
+    /// Enables the rack power budget manager. When disabled,
+    /// host power limits are left as they are.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Interval at which rack budgets are recomputed from
+    /// power shelf telemetry. PSU losses are reacted to
+    /// within one interval.
+    /// Default is 30 seconds.
+    #[serde(
+        default = "RackPowerBudgetConfig::default_run_interval",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub run_interval: std::time::Duration,
+    /// Number of BMCs talked to concurrently within a rack.
+    /// Default is 8.
+    #[serde(default = "RackPowerBudgetConfig::default_concurrency")]
+    pub concurrency: usize,
+    /// Interval after which an unchanged host limit is written
+    /// again, so limits lost to a BMC reset or changed out of
+    /// band are restored.
+    /// Default is 10 minutes.
+    #[serde(
+        default = "RackPowerBudgetConfig::default_reassert_interval",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub reassert_interval: std::time::Duration,
+}
+
+impl Default for RackPowerBudgetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            run_interval: Self::default_run_interval(),
+            concurrency: Self::default_concurrency(),
+            reassert_interval: Self::default_reassert_interval(),
+        }
+    }
+}
+
+impl RackPowerBudgetConfig {
+    const fn default_run_interval() -> std::time::Duration {
+        std::time::Duration::from_secs(30)
+    }
+
+    const fn default_concurrency() -> usize {
+        8
+    }
+
+    const fn default_reassert_interval() -> std::time::Duration {
+        std::time::Duration::from_secs(10 * 60)
+    }
+}
+
 /// The VPC isolation behavior enforced within a site.
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -4557,8 +4713,9 @@ mod tests {
         enum Expect {
             /// Figment/serde extraction fails (missing required field, unknown key).
             ParseErr,
-            /// Extraction succeeds but `to_certificate_config` rejects it.
-            ConvertErr,
+            /// Extraction succeeds but `to_certificate_config` rejects it,
+            /// naming the missing section.
+            ConvertErr(&'static str),
             Shared,
             Dedicated {
                 address: &'static str,
@@ -4567,6 +4724,10 @@ mod tests {
                 token: Option<&'static str>,
                 vault_cacert: Option<&'static str>,
             },
+            LocalCa {
+                import_key_path: Option<&'static str>,
+                crl_validity: std::time::Duration,
+            },
         }
 
         // The fragments extract into `CertificatesConfig` directly, so the root
@@ -4605,7 +4766,42 @@ mod tests {
             (
                 "dedicated_vault selected without its section fails conversion",
                 r#"backend = "dedicated_vault""#,
-                Expect::ConvertErr,
+                Expect::ConvertErr("dedicated_vault"),
+            ),
+            (
+                "local_ca maps all fields",
+                r#"
+                    backend = "local_ca"
+                    [local_ca]
+                    ca_cert_path = "/etc/carbide/ca/intermediate.pem"
+                    sealed_key_path = "/var/lib/carbide/ca/intermediate.key.sealed"
+                    import_key_path = "/etc/carbide/ca/intermediate.key"
+                    kek_id = "ca-kek"
+                    crl_validity = "30m"
+                "#,
+                Expect::LocalCa {
+                    import_key_path: Some("/etc/carbide/ca/intermediate.key"),
+                    crl_validity: std::time::Duration::from_secs(30 * 60),
+                },
+            ),
+            (
+                "local_ca defaults crl_validity and needs no import key",
+                r#"
+                    backend = "local_ca"
+                    [local_ca]
+                    ca_cert_path = "/etc/carbide/ca/intermediate.pem"
+                    sealed_key_path = "/var/lib/carbide/ca/intermediate.key.sealed"
+                    kek_id = "ca-kek"
+                "#,
+                Expect::LocalCa {
+                    import_key_path: None,
+                    crl_validity: std::time::Duration::from_secs(60 * 60),
+                },
+            ),
+            (
+                "local_ca selected without its section fails conversion",
+                r#"backend = "local_ca""#,
+                Expect::ConvertErr("local_ca"),
             ),
             (
                 "dedicated_vault missing required address fails parse",
@@ -4632,7 +4828,7 @@ mod tests {
                 Expect::ParseErr => {
                     assert!(parsed.is_err(), "{name}: expected a parse error");
                 }
-                Expect::ConvertErr => {
+                Expect::ConvertErr(section) => {
                     let cfg = parsed
                         .unwrap_or_else(|e| panic!("{name}: expected parse to succeed, got {e}"));
                     let err = match cfg.to_certificate_config() {
@@ -4640,7 +4836,7 @@ mod tests {
                         Err(err) => err,
                     };
                     assert!(
-                        err.to_string().contains("dedicated_vault"),
+                        err.to_string().contains(section),
                         "{name}: unexpected error: {err}"
                     );
                 }
@@ -4682,6 +4878,28 @@ mod tests {
                         other => panic!("{name}: expected dedicated vault backend, got {other:?}"),
                     }
                 }
+                Expect::LocalCa {
+                    import_key_path,
+                    crl_validity,
+                } => {
+                    let cfg = parsed
+                        .unwrap_or_else(|e| panic!("{name}: expected parse to succeed, got {e}"));
+                    match cfg.to_certificate_config().unwrap().backend {
+                        CertBackend::LocalCa(local_ca) => {
+                            assert_eq!(local_ca.kek_id, "ca-kek", "{name}: kek_id");
+                            assert_eq!(
+                                local_ca.import_key_path,
+                                import_key_path.map(std::path::PathBuf::from),
+                                "{name}: import_key_path"
+                            );
+                            assert_eq!(
+                                local_ca.crl_validity, *crl_validity,
+                                "{name}: crl_validity"
+                            );
+                        }
+                        other => panic!("{name}: expected local CA backend, got {other:?}"),
+                    }
+                }
             }
         }
     }
@@ -5168,6 +5386,10 @@ mod tests {
             IbPartitionStateControllerConfig::default()
         );
         assert_eq!(config.max_find_by_ids, default_max_find_by_ids());
+        assert_eq!(
+            config.max_instance_allocation_batch_size,
+            default_max_instance_allocation_batch_size()
+        );
         assert_eq!(
             config.max_site_prefixes_per_tenant,
             default_max_site_prefixes_per_tenant()
@@ -5179,6 +5401,15 @@ mod tests {
                 run_interval: MeasuredBootMetricsCollectorConfig::default_run_interval(),
             }
         });
+        assert_eq!(
+            config.rack_power_budget,
+            RackPowerBudgetConfig {
+                enabled: false,
+                run_interval: std::time::Duration::from_secs(30),
+                concurrency: 8,
+                reassert_interval: std::time::Duration::from_secs(600),
+            }
+        );
         // And make sure lack of [mlx-config-profiles] doesn't blow up
         // for sites not configured with any.
         assert!(config.mlxconfig_profiles.is_none());
@@ -6507,6 +6738,27 @@ priority = 1
         );
     }
 
+    #[test]
+    fn network_security_policy_override_rejects_references() {
+        const REFERENCE_POLICY: &str = r#"
+[[network_security_group.policy_overrides]]
+src_net = { VpcId = "1ec6e5fa-98c7-4c5a-a9a3-4a1b7c4b0f2d" }
+dst_net = { Prefix = "0.0.0.0/0" }
+direction = "Ingress"
+ipv6 = false
+protocol = "Any"
+action = "Deny"
+priority = 1
+"#;
+
+        let error = Figment::new()
+            .merge(Toml::file(format!("{TEST_DATA_DIR}/min_config.toml")))
+            .merge(Toml::string(REFERENCE_POLICY))
+            .extract::<CarbideConfig>()
+            .unwrap_err();
+        assert!(error.to_string().contains("may only use prefixes"));
+    }
+
     #[test]
     fn unknown_field_error_identifies_key_and_section() {
         let error = Figment::new()
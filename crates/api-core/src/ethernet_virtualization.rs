@@ -35,6 +35,7 @@ use model::machine::ManagedHostStateSnapshot;
 use model::network_prefix::NetworkPrefix;
 use model::network_security_group::{
     NetworkSecurityGroup, NetworkSecurityGroupRule, NetworkSecurityGroupRuleNet,
+    ResolvedNetworkSecurityGroupReferences,
 };
 use model::network_segment::NetworkSegment;
 use model::resource_pool::common::CommonPools;
@@ -775,6 +776,11 @@ pub(crate) async fn tenant_network(
         _ => None,
     };
 
+    let resolved_references = match network_security_group_details.as_ref() {
+        Some((_, nsg)) => network_security_group::find_resolved_references(txn, nsg).await?,
+        None => ResolvedNetworkSecurityGroupReferences::new(),
+    };
+
     let mut config = rpc::FlatInterfaceConfig {
         function_type: rpc_ft.into(),
         virtual_function_id: match iface.function_id {
@@ -816,7 +822,7 @@ pub(crate) async fn tenant_network(
                             rules:
                                 nsg.rules
                                     .into_iter()
-                                    .map(resolve_security_group_rule)
+                                    .map(|r| resolve_security_group_rule(r, &resolved_references))
                                     .collect::<Result<
                                         Vec<rpc::ResolvedNetworkSecurityGroupRule>,
                                         CarbideError,
@@ -849,23 +855,35 @@ pub(crate) async fn tenant_network(
     Ok(config)
 }
 
+/// Converts a rule into the form sent to the DPU, with any security-group,
+/// VPC or label-selector reference replaced by the host prefixes it resolved
+/// to.  Only prefixes of the rule's IP version are kept, so a reference that
+/// currently has no matching addresses yields an empty list and the rule
+/// matches nothing.
 pub(crate) fn resolve_security_group_rule(
     rule: NetworkSecurityGroupRule,
+    references: &ResolvedNetworkSecurityGroupReferences,
 ) -> Result<rpc::ResolvedNetworkSecurityGroupRule, CarbideError> {
+    let resolve = |net: &NetworkSecurityGroupRuleNet| -> Result<Vec<String>, CarbideError> {
+        match net {
+            NetworkSecurityGroupRuleNet::Prefix(p) => Ok(vec![p.to_string()]),
+            reference => Ok(references
+                .get(reference)
+                .ok_or_else(|| CarbideError::Internal {
+                    message: format!(
+                        "network security group reference {reference} was not resolved"
+                    ),
+                })?
+                .iter()
+                .filter(|p| p.is_ipv6() == rule.ipv6)
+                .map(|p| p.to_string())
+                .collect()),
+        }
+    };
+
     Ok(rpc::ResolvedNetworkSecurityGroupRule {
-        // When we decide to allow object references,
-        // they would be resolved to their actual prefix
-        // lists and stored here.
-        src_prefixes: match rule.src_net {
-            NetworkSecurityGroupRuleNet::Prefix(ref p) => {
-                vec![p.to_string()]
-            }
-        },
-        dst_prefixes: match rule.dst_net {
-            NetworkSecurityGroupRuleNet::Prefix(ref p) => {
-                vec![p.to_string()]
-            }
-        },
+        src_prefixes: resolve(&rule.src_net)?,
+        dst_prefixes: resolve(&rule.dst_net)?,
         rule: Some(rule.try_into()?),
     })
 }
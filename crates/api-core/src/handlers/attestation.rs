@@ -97,6 +97,7 @@ pub(crate) async fn trigger_machine_attestation(
         bmc_info,
         &machine_id,
         redfish_timeout_duration,
+        None,
     )
     .await
     .map_err(|e| CarbideError::AttestationError(format!("trigger error: {e}")))?;
@@ -329,6 +330,12 @@ pub(crate) async fn attest_quote(
             ),
         })?;
 
+    // The event log rides along with the report so a later mismatch
+    // (this machine's, or another one against the same bundle) can be
+    // explained event by event.
+    let event_log =
+        crate::attestation::record_event_log(&mut txn, &report, &request.event_log).await?;
+
     // if the attestation was successful and enabled, we can now vend the certs
     // - get attestation result
     // - if enabled and not successful, send response without certs
@@ -340,6 +347,10 @@ pub(crate) async fn attest_quote(
         false
     };
 
+    if attestation_failed && let Some(event_log) = &event_log {
+        crate::attestation::explain_report_mismatch(&mut txn, &report, event_log).await;
+    }
+
     txn.commit().await?;
 
     if attestation_failed {
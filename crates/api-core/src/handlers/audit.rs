@@ -0,0 +1,75 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::forge as rpc;
+use model::audit::AuditEventFilter;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+
+/// Events returned when the request sets no limit.
+const DEFAULT_LIMIT: u32 = 100;
+
+/// Largest page a caller may ask for; each event carries a request digest of
+/// up to [`::rpc::MAX_ERR_MSG_SIZE`] bytes.
+const MAX_LIMIT: u32 = 1000;
+
+pub(crate) async fn find_audit_events(
+    api: &Api,
+    request: Request<rpc::FindAuditEventsRequest>,
+) -> Result<Response<rpc::AuditEventList>, Status> {
+    log_request_data(&request);
+
+    let rpc::FindAuditEventsRequest {
+        method,
+        principal,
+        target_id,
+        since,
+        until,
+        before_id,
+        limit,
+    } = request.into_inner();
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    if limit == 0 || limit > MAX_LIMIT {
+        return Err(CarbideError::InvalidArgument(format!(
+            "limit must be between 1 and {MAX_LIMIT}"
+        ))
+        .into());
+    }
+    let filter = AuditEventFilter {
+        method,
+        principal,
+        target_id,
+        since: since
+            .map(chrono::DateTime::<chrono::Utc>::try_from)
+            .transpose()
+            .map_err(|e| CarbideError::InvalidArgument(format!("invalid since: {e}")))?,
+        until: until
+            .map(chrono::DateTime::<chrono::Utc>::try_from)
+            .transpose()
+            .map_err(|e| CarbideError::InvalidArgument(format!("invalid until: {e}")))?,
+        before_id,
+    };
+
+    let events = db::audit::find(&mut api.db_reader(), &filter, limit).await?;
+
+    Ok(Response::new(rpc::AuditEventList {
+        events: events.into_iter().map(Into::into).collect(),
+    }))
+}
@@ -25,6 +25,7 @@ use model::instance::snapshot::InstanceSnapshot;
 use model::machine::{InstanceState, MachineInterfaceSnapshot, ManagedHostState};
 use model::machine_interface::InterfaceType;
 use model::network_segment::NetworkSegmentType;
+use model::tenant::TenantKeysetIdentifier;
 use sqlx::PgConnection;
 
 use crate::CarbideError;
@@ -275,17 +276,27 @@ pub(super) async fn resolve_cloud_init_instructions(
     };
 
     match resolved_ip {
-        ResolvedClient::Instance(instance) => Ok(rpc::CloudInitInstructions {
-            custom_cloud_init: instance.config.os.user_data,
-            discovery_instructions: None,
-            metadata: Some(rpc::CloudInitMetaData {
-                instance_id: instance.id.to_string(),
-                cloud_name,
-                platform,
-            }),
-            api_url_override: None,
-            pxe_url_override: None,
-        }),
+        ResolvedClient::Instance(instance) => {
+            let user_data_format =
+                db::operating_system::find_user_data_format_for_instance(&mut *conn, instance.id)
+                    .await?
+                    .unwrap_or_default();
+            let ssh_authorized_keys = instance_ssh_authorized_keys(&mut *conn, &instance).await?;
+
+            Ok(rpc::CloudInitInstructions {
+                custom_cloud_init: instance.config.os.user_data,
+                discovery_instructions: None,
+                metadata: Some(rpc::CloudInitMetaData {
+                    instance_id: instance.id.to_string(),
+                    cloud_name,
+                    platform,
+                }),
+                api_url_override: None,
+                pxe_url_override: None,
+                user_data_format: rpc::UserDataFormat::from(user_data_format) as i32,
+                ssh_authorized_keys,
+            })
+        }
         ResolvedClient::MachineInterface(machine_interface) => {
             let domain_id = machine_interface.domain_id.ok_or_else(|| {
                 CarbideError::internal(format!(
@@ -367,7 +378,36 @@ pub(super) async fn resolve_cloud_init_instructions(
                 metadata,
                 api_url_override,
                 pxe_url_override,
+                user_data_format: rpc::UserDataFormat::CloudInit as i32,
+                ssh_authorized_keys: vec![],
             })
         }
     }
 }
+
+/// The public keys of the instance's tenant keysets, one authorized_keys
+/// line each, for provisioning formats that install keys themselves.
+async fn instance_ssh_authorized_keys(
+    conn: &mut PgConnection,
+    instance: &InstanceSnapshot,
+) -> Result<Vec<String>, CarbideError> {
+    let tenant = &instance.config.tenant;
+    if tenant.tenant_keyset_ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let keyset_ids = tenant
+        .tenant_keyset_ids
+        .iter()
+        .map(|keyset_id| TenantKeysetIdentifier {
+            organization_id: tenant.tenant_organization_id.clone(),
+            keyset_id: keyset_id.clone(),
+        })
+        .collect();
+
+    let keysets = db::tenant_keyset::find_by_ids(&mut *conn, keyset_ids, true).await?;
+    Ok(keysets
+        .into_iter()
+        .flat_map(|keyset| keyset.keyset_content.public_keys)
+        .map(|key| key.public_key.to_string())
+        .collect())
+}
@@ -2876,6 +2876,7 @@ mod tests {
             controller_state_outcome: None,
             firmware_upgrade_job: job,
             nvos_update_job: None,
+            power_budget: None,
             health_reports: Default::default(),
             created: chrono::Utc::now(),
             updated: chrono::Utc::now(),
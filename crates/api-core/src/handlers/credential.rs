@@ -26,6 +26,7 @@ use carbide_secrets::credentials::{
     BgpCredentialType, BmcCredentialType, CredentialKey, CredentialReader, CredentialType,
     Credentials, NicLockdownIkm,
 };
+use carbide_secrets::local_ca::normalize_serial;
 use mac_address::MacAddress;
 use model::ConfigValidationError;
 use model::ib::DEFAULT_IB_FABRIC_NAME;
@@ -852,3 +853,18 @@ pub(crate) async fn set_container_registry_credential(
         .map_err(|e| CarbideError::internal(format!("set registry credential: {e:?}")))?;
     Ok(Response::new(()))
 }
+
+pub(crate) async fn revoke_certificate(
+    api: &Api,
+    request: Request<rpc::RevokeCertificateRequest>,
+) -> Result<Response<()>, Status> {
+    crate::api::log_request_data(&request);
+    let serial_number = normalize_serial(&request.into_inner().serial_number)
+        .map_err(|e| CarbideError::InvalidArgument(e.to_string()))?;
+    if api.certificate_revocations.revoke(&serial_number).await? {
+        tracing::info!(%serial_number, "Revoked certificate");
+    } else {
+        tracing::info!(%serial_number, "Certificate was already revoked");
+    }
+    Ok(Response::new(()))
+}
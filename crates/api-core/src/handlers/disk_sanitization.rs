@@ -0,0 +1,148 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Disk sanitization certificates: verifying and storing the one scout sends
+//! with `CleanupMachineCompleted`, and serving them back.
+
+use ::rpc::forge as rpc;
+use ::rpc::sanitization_certificate::SanitizationCertificate;
+use carbide_uuid::machine::MachineId;
+use model::disk_sanitization::DiskSanitizationRecord;
+use sqlx::PgConnection;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_machine_id, log_request_data};
+
+/// Verifies a certificate sent by `machine_id`'s scout and stores it with the
+/// machine's pending release, if any.
+///
+/// A certificate that does not verify is logged and dropped rather than
+/// failing the call: it must not hold up the cleanup it describes.
+pub(crate) async fn record_certificate(
+    api: &Api,
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    signed_certificate: &str,
+) -> Result<(), CarbideError> {
+    let signer = match verify(api, machine_id, signed_certificate) {
+        Ok(signer) => signer,
+        Err(error) => {
+            tracing::warn!(
+                machine_id = %machine_id,
+                %error,
+                "Dropping disk sanitization certificate that does not verify"
+            );
+            return Ok(());
+        }
+    };
+    db::disk_sanitization::record_certificate(txn, machine_id, &signer, signed_certificate).await?;
+    Ok(())
+}
+
+/// Returns the signer of a certificate that is signed by a certificate the
+/// client CA issued to `machine_id` and describes that machine.
+fn verify(api: &Api, machine_id: &MachineId, signed_certificate: &str) -> Result<String, String> {
+    let Some(verifier) = &api.client_ca_verifier else {
+        return Err("no client CA roots are loaded to verify the certificate chain".to_string());
+    };
+    verifier.verify_document_chain(signed_certificate)?;
+    let verified =
+        SanitizationCertificate::verify(signed_certificate).map_err(|e| e.to_string())?;
+    if verified.document.machine_id != *machine_id {
+        return Err(format!(
+            "certificate describes machine {}",
+            verified.document.machine_id
+        ));
+    }
+    let signer_machine_id = verified.signer.rsplit('/').next();
+    if signer_machine_id != Some(machine_id.to_string().as_str()) {
+        return Err(format!(
+            "certificate is signed by {}, not by the machine",
+            verified.signer
+        ));
+    }
+    Ok(verified.signer)
+}
+
+pub(crate) async fn find_disk_sanitization_certificates(
+    api: &Api,
+    request: Request<rpc::DiskSanitizationCertificateQuery>,
+) -> Result<Response<rpc::DiskSanitizationCertificateList>, Status> {
+    log_request_data(&request);
+
+    let rpc::DiskSanitizationCertificateQuery {
+        machine_id,
+        instance_id,
+    } = request.into_inner();
+    if machine_id.is_none() && instance_id.is_none() {
+        return Err(CarbideError::InvalidArgument(
+            "a machine ID or an instance ID is required".to_string(),
+        )
+        .into());
+    }
+    if let Some(machine_id) = &machine_id {
+        log_machine_id(machine_id);
+    }
+
+    let records =
+        db::disk_sanitization::find(&mut api.db_reader(), machine_id.as_ref(), instance_id).await?;
+
+    Ok(Response::new(rpc::DiskSanitizationCertificateList {
+        certificates: records
+            .into_iter()
+            .map(certificate_to_rpc)
+            .collect::<Result<_, _>>()?,
+    }))
+}
+
+/// Decodes a stored record for the response. The devices are read from the
+/// signed certificate, re-verified, rather than from anything the API wrote.
+fn certificate_to_rpc(
+    record: DiskSanitizationRecord,
+) -> Result<rpc::DiskSanitizationCertificate, CarbideError> {
+    let (standard, devices) = match &record.signed_certificate {
+        Some(signed) => {
+            let certificate = SanitizationCertificate::verify(signed)
+                .map_err(|e| {
+                    CarbideError::internal(format!(
+                        "stored disk sanitization certificate {} does not verify: {e}",
+                        record.id
+                    ))
+                })?
+                .document;
+            (
+                certificate.standard,
+                certificate.devices.into_iter().map(Into::into).collect(),
+            )
+        }
+        None => (String::new(), vec![]),
+    };
+
+    Ok(rpc::DiskSanitizationCertificate {
+        id: record.id.to_string(),
+        machine_id: Some(record.machine_id),
+        instance_id: record.instance_id,
+        tenant_organization_id: record.tenant_organization_id,
+        released_at: record.released_at.map(Into::into),
+        sanitized_at: record.sanitized_at.map(Into::into),
+        signer: record.signer.unwrap_or_default(),
+        standard,
+        devices,
+        signed_certificate: record.signed_certificate.unwrap_or_default(),
+    })
+}
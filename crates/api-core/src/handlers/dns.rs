@@ -17,7 +17,8 @@
 use ::rpc::protos;
 use db::db_read::DbReader;
 use db::dns::resource_record;
-use dns_record::{DnsResourceRecordReply, DnsResourceRecordType};
+use dns_record::{DnsResourceRecordReply, DnsResourceRecordType, SoaRecord};
+use sqlx::PgPool;
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
@@ -86,6 +87,72 @@ async fn lookup_records_by_qname(
     Ok(result)
 }
 
+/// Returns the custom (CNAME, TXT, SRV, MX, NS) records stored for the qname.
+/// Like `lookup_records_by_qname`, all types are returned and the DNS server
+/// filters to the requested one.
+async fn lookup_custom_records(
+    txn: impl DbReader<'_>,
+    query_name: &str,
+) -> Result<Vec<DnsResourceRecordReply>, tonic::Status> {
+    tracing::debug!(query_name, "Looking up custom DNS records");
+
+    let qname_with_dot = if !query_name.ends_with('.') {
+        format!("{}.", query_name)
+    } else {
+        query_name.to_string()
+    };
+
+    let result = resource_record::find_custom_records(txn, &qname_with_dot)
+        .await
+        .map_err(CarbideError::from)?
+        .into_iter()
+        .map(|db_record| {
+            let model_record: model::dns::ResourceRecord = db_record.into();
+            model_record.into()
+        })
+        .collect::<Vec<_>>();
+
+    Ok(result)
+}
+
+/// The NS record a zone apex answers with, naming the SOA's primary nameserver.
+fn apex_ns_record(soa: &SoaRecord, zone_with_dot: &str) -> DnsResourceRecordReply {
+    DnsResourceRecordReply {
+        qtype: DnsResourceRecordType::NS.to_string(),
+        qname: zone_with_dot.to_string(),
+        ttl: soa.ttl.0 as u32,
+        content: format!("{}.", soa.primary_ns.trim_end_matches('.')),
+        domain_id: None,
+        scope_mask: None,
+        auth: None,
+    }
+}
+
+/// Resolve an NS query: a zone apex answers with its SOA's primary nameserver,
+/// and any name can carry stored NS records delegating it elsewhere.
+async fn lookup_ns_records(
+    db: &PgPool,
+    query_name: &str,
+) -> Result<Vec<DnsResourceRecordReply>, tonic::Status> {
+    let qname_with_dot = if !query_name.ends_with('.') {
+        format!("{}.", query_name)
+    } else {
+        query_name.to_string()
+    };
+
+    let normalized = db::dns::normalize_domain(query_name);
+    let mut result = match resource_record::get_soa_record(db, &normalized)
+        .await
+        .map_err(CarbideError::from)?
+    {
+        Some(soa) => vec![apex_ns_record(&soa.0, &qname_with_dot)],
+        None => vec![],
+    };
+    result.extend(lookup_custom_records(db, &qname_with_dot).await?);
+
+    Ok(result)
+}
+
 /// Resolve a reverse-DNS (PTR) query. The qname is an address in `in-addr.arpa` /
 /// `ip6.arpa` form, so we parse it back to an `IpAddr` and look the holding
 /// interface up by address (rather than matching a per-row arpa string in a view).
@@ -225,9 +292,15 @@ pub(crate) async fn lookup_record(
             // Reverse DNS: parse the arpa qname back to an address and look up by it.
             lookup_ptr_record(&api.database_connection, &qname).await?
         }
+        DnsResourceRecordType::NS => lookup_ns_records(&api.database_connection, &qname).await?,
         _ => {
-            // For all other types (A, AAAA, MX, CNAME, etc.):
-            lookup_records_by_qname(&api.database_connection, &qname).await?
+            // For all other types (A, AAAA, MX, CNAME, etc.), address records
+            // and stored custom records. A CNAME is returned alongside so the
+            // DNS server can answer with it when the name has no record of the
+            // requested type.
+            let mut records = lookup_records_by_qname(&api.database_connection, &qname).await?;
+            records.extend(lookup_custom_records(&api.database_connection, &qname).await?);
+            records
         }
     };
 
@@ -236,3 +309,252 @@ pub(crate) async fn lookup_record(
     };
     Ok(Response::new(resp.into()))
 }
+
+/// Return every record of a domain for zone transfer: the SOA first, then the
+/// apex NS, the address records and the stored custom records. The DNS server
+/// closes an AXFR with the SOA again, so it is not repeated here.
+///
+/// PTR answers are computed from the queried address rather than stored per
+/// name, so a reverse zone transfers only its SOA and NS.
+pub(crate) async fn get_all_records_for_domain(
+    api: &Api,
+    request: Request<protos::dns::GetAllRecordsForDomainRequest>,
+) -> Result<Response<protos::dns::GetAllRecordsForDomainResponse>, Status> {
+    log_request_data(&request);
+
+    let name = request.into_inner().name;
+    if name.is_empty() {
+        return Err(CarbideError::InvalidArgument("name cannot be empty".to_string()).into());
+    }
+
+    let normalized = db::dns::normalize_domain(&name);
+    let zone_with_dot = format!("{normalized}.");
+    let soa = resource_record::get_soa_record(&api.database_connection, &normalized)
+        .await
+        .map_err(CarbideError::from)?
+        .ok_or_else(|| CarbideError::NotFoundError {
+            kind: "domain",
+            id: name.clone(),
+        })?
+        .0;
+
+    let mut records = vec![
+        DnsResourceRecordReply {
+            qtype: DnsResourceRecordType::SOA.to_string(),
+            qname: zone_with_dot.clone(),
+            ttl: soa.ttl.0 as u32,
+            content: soa.to_string(),
+            domain_id: None,
+            scope_mask: None,
+            auth: None,
+        },
+        apex_ns_record(&soa, &zone_with_dot),
+    ];
+
+    let address_records =
+        resource_record::get_all_records(&api.database_connection, &normalized).await?;
+    let custom_records =
+        resource_record::get_all_custom_records(&api.database_connection, &normalized).await?;
+    records.extend(
+        address_records
+            .into_iter()
+            .chain(custom_records)
+            .map(|db_record| {
+                let model_record: model::dns::ResourceRecord = db_record.into();
+                DnsResourceRecordReply::from(model_record)
+            }),
+    );
+
+    tracing::debug!(zone = %normalized, record_count = records.len(), "Collected zone records");
+
+    Ok(Response::new(protos::dns::GetAllRecordsForDomainResponse {
+        result: records.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Checks a custom record's owner name against its domain and returns the
+/// normalized name: the domain itself or a name below it, made of non-empty
+/// labels of at most 63 characters.
+fn validate_custom_record_name(q_name: &str, domain_name: &str) -> Result<String, CarbideError> {
+    let q_name = db::dns::normalize_domain(q_name);
+    let domain_name = db::dns::normalize_domain(domain_name);
+    if q_name != domain_name && !q_name.ends_with(&format!(".{domain_name}")) {
+        return Err(CarbideError::InvalidArgument(format!(
+            "qname {q_name} is not within domain {domain_name}"
+        )));
+    }
+    if q_name
+        .split('.')
+        .any(|label| label.is_empty() || label.len() > 63)
+    {
+        return Err(CarbideError::InvalidArgument(format!(
+            "qname {q_name} is not a valid DNS name"
+        )));
+    }
+    Ok(q_name)
+}
+
+fn custom_record_ttl(ttl: Option<u32>) -> Result<Option<i32>, CarbideError> {
+    ttl.map(|ttl| {
+        i32::try_from(ttl)
+            .map_err(|_| CarbideError::InvalidArgument(format!("ttl {ttl} is out of range")))
+    })
+    .transpose()
+}
+
+/// Checks that `content` parses as the record type's RDATA, the same way
+/// carbide-dns parses it when serving the record.
+fn custom_record_content(q_type: &str, content: String) -> Result<String, CarbideError> {
+    if content.trim().is_empty() {
+        return Err(CarbideError::InvalidArgument(
+            "content cannot be empty".to_string(),
+        ));
+    }
+    let q_type = DnsResourceRecordType::try_from(q_type).map_err(CarbideError::InvalidArgument)?;
+    dns_record::rdata::parse_rdata(q_type, &content)
+        .map_err(|e| CarbideError::InvalidArgument(e.to_string()))?;
+    Ok(content)
+}
+
+/// A CNAME must be the only record at its name: reject a CNAME where other
+/// records exist, and any record where a CNAME exists. Storing the same CNAME
+/// again is left to the store, which reports it as already existing.
+async fn check_cname_exclusivity(
+    q_name: &str,
+    q_type: &str,
+    content: &str,
+    txn: &mut db::Transaction<'_>,
+) -> Result<(), CarbideError> {
+    let existing = resource_record::lock_records_at_name(txn, &format!("{q_name}.")).await?;
+    let is_cname = q_type == DnsResourceRecordType::CNAME.to_string();
+    let conflict = existing.iter().find(|record| {
+        if is_cname {
+            record.q_type != q_type || record.record != content
+        } else {
+            record.q_type == DnsResourceRecordType::CNAME.to_string()
+        }
+    });
+    match conflict {
+        Some(record) if is_cname => Err(CarbideError::FailedPrecondition(format!(
+            "{q_name} already has a {} record, so it cannot have a CNAME",
+            record.q_type
+        ))),
+        Some(_) => Err(CarbideError::FailedPrecondition(format!(
+            "{q_name} has a CNAME record, so it cannot have other records"
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn custom_record_id(id: Option<::rpc::common::Uuid>) -> Result<uuid::Uuid, CarbideError> {
+    let id = id.ok_or(CarbideError::MissingArgument("id"))?;
+    uuid::Uuid::try_from(id).map_err(CarbideError::from)
+}
+
+/// Store a CNAME, TXT, SRV, MX or NS record for a live domain. The domain's
+/// SOA serial is bumped in the same transaction so secondaries pick it up.
+pub(crate) async fn create_custom_record(
+    api: &Api,
+    request: Request<protos::dns::CreateDnsCustomRecordRequest>,
+) -> Result<Response<protos::dns::DnsCustomRecord>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+    let domain_id = req
+        .domain_id
+        .ok_or(CarbideError::MissingArgument("domain_id"))?;
+    let q_type = req.qtype.to_ascii_uppercase();
+    if !model::dns::custom_record::CUSTOM_RECORD_TYPES.contains(&q_type.as_str()) {
+        return Err(CarbideError::InvalidArgument(format!(
+            "qtype {} cannot be stored, expected one of {}",
+            req.qtype,
+            model::dns::custom_record::CUSTOM_RECORD_TYPES.join(", ")
+        ))
+        .into());
+    }
+    let ttl = custom_record_ttl(req.ttl)?;
+    let content = custom_record_content(&q_type, req.content)?;
+
+    let mut txn = api.txn_begin().await?;
+
+    let domain = db::dns::domain::find_by_uuid(&mut txn, domain_id)
+        .await?
+        .filter(|domain| domain.deleted.is_none())
+        .ok_or_else(|| CarbideError::NotFoundError {
+            kind: "domain",
+            id: domain_id.to_string(),
+        })?;
+    let q_name = validate_custom_record_name(&req.qname, &domain.name)?;
+    check_cname_exclusivity(&q_name, &q_type, &content, &mut txn).await?;
+
+    let record = resource_record::persist_custom_record(
+        resource_record::NewCustomRecord {
+            domain_id,
+            q_name,
+            q_type,
+            ttl,
+            content,
+        },
+        &mut txn,
+    )
+    .await?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(record.into()))
+}
+
+/// Replace the TTL and content of a stored custom record.
+pub(crate) async fn update_custom_record(
+    api: &Api,
+    request: Request<protos::dns::UpdateDnsCustomRecordRequest>,
+) -> Result<Response<protos::dns::DnsCustomRecord>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+    let id = custom_record_id(req.id)?;
+    let ttl = custom_record_ttl(req.ttl)?;
+
+    let mut txn = api.txn_begin().await?;
+    let stored = resource_record::find_custom_record_by_id(&mut txn, id)
+        .await?
+        .ok_or_else(|| CarbideError::NotFoundError {
+            kind: "dns_custom_record",
+            id: id.to_string(),
+        })?;
+    let content = custom_record_content(&stored.q_type, req.content)?;
+    let record = resource_record::update_custom_record(id, ttl, &content, &mut txn).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(record.into()))
+}
+
+pub(crate) async fn delete_custom_record(
+    api: &Api,
+    request: Request<protos::dns::DeleteDnsCustomRecordRequest>,
+) -> Result<Response<protos::dns::DeleteDnsCustomRecordResponse>, Status> {
+    log_request_data(&request);
+
+    let id = custom_record_id(request.into_inner().id)?;
+
+    let mut txn = api.txn_begin().await?;
+    resource_record::delete_custom_record(id, &mut txn).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(protos::dns::DeleteDnsCustomRecordResponse {}))
+}
+
+pub(crate) async fn find_custom_records(
+    api: &Api,
+    request: Request<protos::dns::FindDnsCustomRecordsRequest>,
+) -> Result<Response<protos::dns::DnsCustomRecordList>, Status> {
+    log_request_data(&request);
+
+    let domain_id = request.into_inner().domain_id;
+    let records =
+        resource_record::find_custom_records_by_domain(&api.database_connection, domain_id).await?;
+
+    Ok(Response::new(protos::dns::DnsCustomRecordList {
+        records: records.into_iter().map(Into::into).collect(),
+    }))
+}
@@ -80,7 +80,7 @@ fn build_consolidated_network_config(
 /// FNN renders family-specific deny policies, while ETV exposes only its IPv4 policy. Filter at
 /// this per-DPU boundary so a mixed site can send IPv6 to FNN without changing the IPv4-only wire
 /// contract for ETV, Flat, and older agents.
-fn deny_prefixes_for_agent(
+pub(crate) fn deny_prefixes_for_agent(
     prefixes: &[IpNetwork],
     network_virtualization_type: VpcVirtualizationType,
 ) -> Vec<String> {
@@ -729,7 +729,11 @@ async fn get_managed_host_network_config_inner(
             .network_security_group
             .policy_overrides
             .iter()
-            .map(|r| ethernet_virtualization::resolve_security_group_rule(r.clone()))
+            // Policy overrides can only contain prefixes, so there is
+            // nothing to resolve.
+            .map(|r| {
+                ethernet_virtualization::resolve_security_group_rule(r.clone(), &Default::default())
+            })
             .collect::<Result<Vec<rpc::ResolvedNetworkSecurityGroupRule>, CarbideError>>()?,
         stateful_acls_enabled: api
             .runtime_config
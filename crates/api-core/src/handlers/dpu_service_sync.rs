@@ -409,6 +409,7 @@ async fn release_one_inner(
             &snapshot.dpu_snapshots,
             policy,
             MachinePendingActionActor::AdminCli,
+            None,
         )
         .await,
     ))
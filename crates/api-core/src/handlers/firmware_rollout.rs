@@ -0,0 +1,153 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::errors::RpcDataConversionError;
+use ::rpc::forge as rpc;
+use model::firmware_rollout::{FirmwareRolloutSpec, FirmwareRolloutTarget};
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+
+pub(crate) async fn create(
+    api: &Api,
+    request: Request<rpc::CreateFirmwareRolloutRequest>,
+) -> Result<Response<rpc::FirmwareRollout>, Status> {
+    log_request_data(&request);
+
+    let rpc::CreateFirmwareRolloutRequest { name, target, spec } = request.into_inner();
+    if name.trim().is_empty() {
+        return Err(CarbideError::InvalidArgument("name must not be empty".to_string()).into());
+    }
+    let target = rpc::FirmwareRolloutTarget::try_from(target)
+        .map_err(|_| RpcDataConversionError::InvalidValue("target".into(), target.to_string()))
+        .and_then(FirmwareRolloutTarget::try_from)
+        .map_err(CarbideError::from)?;
+    let spec = FirmwareRolloutSpec::try_from(spec.ok_or(CarbideError::from(
+        RpcDataConversionError::MissingArgument("spec"),
+    ))?)
+    .map_err(CarbideError::from)?;
+    spec.validate().map_err(CarbideError::InvalidArgument)?;
+
+    let mut txn = api.txn_begin().await?;
+    let rollout = db::firmware_rollout::create(&mut txn, &name, target, &spec).await?;
+    txn.commit().await?;
+
+    Ok(Response::new((rollout, vec![]).into()))
+}
+
+pub(crate) async fn find(
+    api: &Api,
+    request: Request<rpc::FindFirmwareRolloutsRequest>,
+) -> Result<Response<rpc::FirmwareRolloutList>, Status> {
+    log_request_data(&request);
+
+    let rpc::FindFirmwareRolloutsRequest { name } = request.into_inner();
+    let mut reader = api.db_reader();
+    let rollouts = db::firmware_rollout::find(&mut reader, name.as_deref()).await?;
+    let mut found = Vec::with_capacity(rollouts.len());
+    for rollout in rollouts {
+        let machines = db::firmware_rollout::find_machines(&mut reader, &rollout.name).await?;
+        found.push((rollout, machines).into());
+    }
+
+    Ok(Response::new(rpc::FirmwareRolloutList { rollouts: found }))
+}
+
+pub(crate) async fn pause(
+    api: &Api,
+    request: Request<rpc::PauseFirmwareRolloutRequest>,
+) -> Result<Response<rpc::FirmwareRollout>, Status> {
+    log_request_data(&request);
+
+    let rpc::PauseFirmwareRolloutRequest { name, reason } = request.into_inner();
+    let reason = if reason.trim().is_empty() {
+        "paused by an operator".to_string()
+    } else {
+        reason
+    };
+
+    let mut txn = api.txn_begin().await?;
+    let rollout = db::firmware_rollout::pause(&mut txn, &name, &reason).await?;
+    let Some(rollout) = rollout else {
+        return Err(not_in_state(&mut txn, &name, "active").await);
+    };
+    let machines = db::firmware_rollout::find_machines(&mut txn, &name).await?;
+    txn.commit().await?;
+
+    Ok(Response::new((rollout, machines).into()))
+}
+
+pub(crate) async fn resume(
+    api: &Api,
+    request: Request<rpc::FirmwareRolloutName>,
+) -> Result<Response<rpc::FirmwareRollout>, Status> {
+    log_request_data(&request);
+
+    let rpc::FirmwareRolloutName { name } = request.into_inner();
+
+    let mut txn = api.txn_begin().await?;
+    let rollout = db::firmware_rollout::resume(&mut txn, &name).await?;
+    let Some(rollout) = rollout else {
+        return Err(not_in_state(&mut txn, &name, "paused").await);
+    };
+    let machines = db::firmware_rollout::find_machines(&mut txn, &name).await?;
+    txn.commit().await?;
+
+    Ok(Response::new((rollout, machines).into()))
+}
+
+pub(crate) async fn delete(
+    api: &Api,
+    request: Request<rpc::FirmwareRolloutName>,
+) -> Result<Response<()>, Status> {
+    log_request_data(&request);
+
+    let rpc::FirmwareRolloutName { name } = request.into_inner();
+
+    let mut txn = api.txn_begin().await?;
+    if !db::firmware_rollout::delete(&mut txn, &name).await? {
+        return Err(CarbideError::NotFoundError {
+            kind: "firmware_rollout",
+            id: name,
+        }
+        .into());
+    }
+    txn.commit().await?;
+
+    Ok(Response::new(()))
+}
+
+/// The error for pausing or resuming a rollout that is not `expected`:
+/// NotFound if it does not exist at all.
+async fn not_in_state(txn: &mut db::Transaction<'_>, name: &str, expected: &str) -> Status {
+    match db::firmware_rollout::find(txn, Some(name)).await {
+        Ok(found) => match found.first() {
+            None => CarbideError::NotFoundError {
+                kind: "firmware_rollout",
+                id: name.to_string(),
+            }
+            .into(),
+            Some(rollout) => CarbideError::FailedPrecondition(format!(
+                "firmware rollout {name} is {:?}, not {expected}",
+                rollout.state
+            ))
+            .into(),
+        },
+        Err(e) => CarbideError::from(e).into(),
+    }
+}
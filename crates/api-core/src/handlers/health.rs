@@ -16,10 +16,12 @@
  */
 
 use ::rpc::forge::{self as rpc, HealthReportEntry};
+use carbide_machine_controller::io::MachineStateControllerIO;
 use carbide_uuid::machine::MachineId;
 use health_report::HealthReportApplyMode;
 use model::machine::machine_search_config::MachineSearchConfig;
 use sqlx::PgConnection;
+use state_controller::controller::Enqueuer;
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
@@ -95,6 +97,30 @@ async fn remove_by_source(
     Ok(())
 }
 
+/// Has the state handler of the managed host that `machine_id` belongs to
+/// react to a health report change as soon as `txn` commits.
+async fn enqueue_managed_host(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+) -> Result<(), CarbideError> {
+    let host_machine_id = if machine_id.machine_type().is_dpu() {
+        match db::machine::lookup_host_machine_ids_by_dpu_ids(&mut *txn, &[*machine_id])
+            .await?
+            .remove(machine_id)
+        {
+            Some(host_machine_id) => host_machine_id,
+            // A DPU that is not attached to a host has no state handler to wake up.
+            None => return Ok(()),
+        }
+    } else {
+        *machine_id
+    };
+
+    Enqueuer::<MachineStateControllerIO>::enqueue_object_in_txn(txn, &host_machine_id).await?;
+
+    Ok(())
+}
+
 pub(crate) async fn insert_machine_health_report(
     api: &Api,
     request: Request<rpc::InsertMachineHealthReportRequest>,
@@ -144,6 +170,7 @@ pub(crate) async fn insert_machine_health_report(
     }
 
     db::machine::insert_health_report(&mut txn, &machine_id, mode, &report, false).await?;
+    enqueue_managed_host(&mut txn, &machine_id).await?;
 
     txn.commit().await?;
 
@@ -160,6 +187,7 @@ pub(crate) async fn remove_machine_health_report(
     let machine_id = convert_and_log_machine_id(machine_id.as_ref())?;
 
     remove_by_source(&mut txn, machine_id, source).await?;
+    enqueue_managed_host(&mut txn, &machine_id).await?;
     txn.commit().await?;
 
     Ok(Response::new(()))
@@ -20,10 +20,12 @@ use std::str::FromStr;
 use ::rpc::errors::RpcDataConversionError;
 use ::rpc::forge::{self as rpc, AdminForceDeleteMachineResponse};
 use ::rpc::model::RpcTryFrom;
+use carbide_machine_controller::io::MachineStateControllerIO;
 use carbide_redfish::libredfish::RedfishAuth;
 use carbide_secrets::credentials::{BmcCredentialType, CredentialKey};
 use carbide_uuid::infiniband::IBPartitionId;
 use carbide_uuid::instance::InstanceId;
+use carbide_uuid::instance_type::InstanceTypeId;
 use carbide_uuid::machine::MachineId;
 use carbide_uuid::network::NetworkSegmentId;
 use carbide_uuid::vpc::VpcId;
@@ -56,6 +58,7 @@ use model::os::OperatingSystem;
 use model::vpc::{FabricInterfaceType, VpcVirtualizationTypeCapabilities};
 use serde_json::json;
 use sqlx::PgConnection;
+use state_controller::controller::Enqueuer;
 use tonic::{Request, Response, Status};
 
 use crate::api::{Api, log_machine_id, log_request_data, log_tenant_organization_id};
@@ -64,7 +67,7 @@ use crate::ethernet_virtualization::validate_instance_interface_routing_profiles
 use crate::handlers::utils::convert_and_log_machine_id;
 use crate::instance::{
     InstanceAllocationRequest, allocate_ib_port_guid, allocate_instance, allocate_network,
-    allocate_spx_port_mac, validate_ib_partition_ownership,
+    allocate_spx_port_mac, placement, validate_ib_partition_ownership,
     validate_instance_vfs_against_dpf_topology, validate_os_definition_usable,
     validate_spx_partition_ownership,
 };
@@ -172,14 +175,108 @@ pub(crate) async fn batch_allocate(
 ) -> Result<Response<rpc::BatchInstanceAllocationResponse>, Status> {
     log_request_data(&request);
 
-    let mut batch_request = request.into_inner();
+    let instances = allocate_batch(api, request.into_inner()).await?;
 
+    Ok(Response::new(rpc::BatchInstanceAllocationResponse {
+        instances,
+    }))
+}
+
+/// Places `count` instances of an instance type on Ready machines picked by
+/// [`crate::instance::placement`], then allocates them as one batch.
+///
+/// Candidates are chosen outside the allocation transaction. If another
+/// request takes one of them first, the batch fails its usability check and
+/// nothing is allocated; the caller can simply retry.
+pub(crate) async fn allocate_by_type(
+    api: &Api,
+    request: Request<rpc::InstanceTypeAllocationRequest>,
+) -> Result<Response<rpc::BatchInstanceAllocationResponse>, Status> {
+    log_request_data(&request);
+
+    let rpc::InstanceTypeAllocationRequest {
+        instance_type_id,
+        count,
+        config,
+        metadata,
+        placement: placement_policy,
+    } = request.into_inner();
+
+    let instance_type_id = instance_type_id.parse::<InstanceTypeId>().map_err(|e| {
+        CarbideError::from(RpcDataConversionError::InvalidInstanceTypeId(e.value()))
+    })?;
+    if count == 0 {
+        return Err(CarbideError::InvalidArgument("count must be at least 1".to_string()).into());
+    }
+    let max_count = api.runtime_config.max_instance_allocation_batch_size;
+    if count > max_count {
+        return Err(
+            CarbideError::InvalidArgument(format!("count must be at most {max_count}")).into(),
+        );
+    }
+    let policy = rpc::InstancePlacementPolicy::try_from(placement_policy)
+        .map(placement::PlacementPolicy::from)
+        .map_err(|_| {
+            CarbideError::InvalidArgument(format!("unknown placement policy {placement_policy}"))
+        })?;
+
+    let candidates = placement::find_candidates(api, &instance_type_id).await?;
+    let available = candidates.len();
+    let machine_ids = match placement::choose_machines(policy, candidates, count as usize) {
+        Ok(machine_ids) => machine_ids,
+        Err(err) => {
+            // Parked hosts are no candidates; have enough of them powered on
+            // for a retry to find them.
+            crate::idle_host_power::record_unmet_demand(
+                &api.database_connection,
+                &api.runtime_config.power_manager_options,
+                &instance_type_id,
+                count.saturating_sub(available as u32),
+            )
+            .await;
+            return Err(err.into());
+        }
+    };
+
+    let instance_requests = machine_ids
+        .into_iter()
+        .map(|machine_id| rpc::InstanceAllocationRequest {
+            machine_id: Some(machine_id),
+            config: config.clone(),
+            instance_id: None,
+            instance_type_id: Some(instance_type_id.to_string()),
+            metadata: metadata.clone(),
+            allow_unhealthy_machine: false,
+        })
+        .collect();
+    let instances = allocate_batch(
+        api,
+        rpc::BatchInstanceAllocationRequest { instance_requests },
+    )
+    .await?;
+
+    Ok(Response::new(rpc::BatchInstanceAllocationResponse {
+        instances,
+    }))
+}
+
+async fn allocate_batch(
+    api: &Api,
+    mut batch_request: rpc::BatchInstanceAllocationRequest,
+) -> Result<Vec<rpc::Instance>, Status> {
     if batch_request.instance_requests.is_empty() {
         return Err(CarbideError::InvalidArgument(
             "batch request must contain at least one instance".to_string(),
         )
         .into());
     }
+    let max_count = api.runtime_config.max_instance_allocation_batch_size as usize;
+    if batch_request.instance_requests.len() > max_count {
+        return Err(CarbideError::InvalidArgument(format!(
+            "batch request must contain at most {max_count} instances"
+        ))
+        .into());
+    }
 
     tracing::info!(
         instance_request_count = batch_request.instance_requests.len(),
@@ -229,9 +326,7 @@ pub(crate) async fn batch_allocate(
         "Successfully allocated batch of instances"
     );
 
-    Ok(Response::new(rpc::BatchInstanceAllocationResponse {
-        instances,
-    }))
+    Ok(instances)
 }
 
 pub(crate) async fn find_ids(
@@ -812,6 +907,10 @@ pub(crate) async fn release(
     // we convert this case of the DatabaseError into NotFound too.
     db::instance::mark_as_deleted(instance_id, &mut txn).await?;
 
+    // Start tearing down the instance as soon as the release is committed.
+    Enqueuer::<MachineStateControllerIO>::enqueue_object_in_txn(&mut txn, &instance.machine_id)
+        .await?;
+
     txn.commit().await?;
 
     Ok(Response::new(rpc::InstanceReleaseResult {}))
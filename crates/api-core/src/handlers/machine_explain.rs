@@ -0,0 +1,136 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Explaining what the machine state handler would do next for a machine,
+//! without letting it do it.
+
+use ::rpc::forge as rpc;
+use model::controller_outcome::PersistentSourceReference;
+use model::machine::ManagedHostState;
+use state_controller::explain::{ExplainedOutcome, SideEffect};
+use state_controller::state_handler::StateHandlerError;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_machine_id, log_request_data};
+
+pub(crate) async fn explain_machine_state_handling(
+    api: &Api,
+    request: Request<rpc::ExplainMachineStateHandlingRequest>,
+) -> Result<Response<rpc::ExplainMachineStateHandlingResponse>, Status> {
+    log_request_data(&request);
+
+    let machine_id = request
+        .into_inner()
+        .machine_id
+        .ok_or(CarbideError::MissingArgument("machine_id"))?;
+    log_machine_id(&machine_id);
+    if machine_id.machine_type().is_dpu() {
+        return Err(CarbideError::InvalidArgument(
+            "DPUs are handled as part of their host; explain the host instead".to_string(),
+        )
+        .into());
+    }
+
+    let explainer = api.machine_state_explainer.get().ok_or_else(|| {
+        CarbideError::UnavailableError("the machine state controller is not running".to_string())
+    })?;
+
+    let explanation = explainer.explain(&machine_id).await.map_err(|e| match e {
+        StateHandlerError::MissingData {
+            missing: "object_state",
+            ..
+        } => CarbideError::NotFoundError {
+            kind: "machine",
+            id: machine_id.to_string(),
+        },
+        e => CarbideError::internal(format!("failed to explain state handling: {e}")),
+    })?;
+
+    let (outcome, outcome_msg, source_ref, next_state, recheck_at) = match explanation.outcome {
+        Ok(ExplainedOutcome::Wait {
+            reason,
+            recheck_at,
+            source_ref,
+        }) => (
+            rpc::ControllerStateOutcome::Wait,
+            Some(reason),
+            Some(source_ref),
+            None,
+            recheck_at,
+        ),
+        Ok(ExplainedOutcome::Transition {
+            next_state,
+            source_ref,
+        }) => (
+            rpc::ControllerStateOutcome::Transition,
+            None,
+            Some(source_ref),
+            Some(state_to_json(&next_state)?),
+            None,
+        ),
+        Ok(ExplainedOutcome::DoNothing { source_ref })
+        | Ok(ExplainedOutcome::Deleted { source_ref }) => (
+            rpc::ControllerStateOutcome::DoNothing,
+            None,
+            Some(source_ref),
+            None,
+            None,
+        ),
+        Err(e) => (
+            rpc::ControllerStateOutcome::Error,
+            Some(e.to_string()),
+            None,
+            None,
+            None,
+        ),
+    };
+
+    Ok(Response::new(rpc::ExplainMachineStateHandlingResponse {
+        machine_id: Some(machine_id),
+        state: state_to_json(&explanation.controller_state)?,
+        state_version: explanation.state_version.version_string(),
+        time_in_state_above_sla: explanation.time_in_state_above_sla,
+        reason: Some(rpc::ControllerStateReason {
+            outcome: outcome.into(),
+            outcome_msg,
+            source_ref: source_ref
+                .map(|source_ref| PersistentSourceReference::from(&source_ref).into()),
+        }),
+        next_state,
+        recheck_at: recheck_at.map(Into::into),
+        side_effects: explanation
+            .side_effects
+            .into_iter()
+            .map(side_effect_to_rpc)
+            .collect(),
+    }))
+}
+
+fn state_to_json(state: &ManagedHostState) -> Result<String, CarbideError> {
+    serde_json::to_string(state)
+        .map_err(|e| CarbideError::internal(format!("failed to serialize machine state: {e}")))
+}
+
+fn side_effect_to_rpc(side_effect: SideEffect) -> rpc::StateHandlerSideEffect {
+    rpc::StateHandlerSideEffect {
+        service: side_effect.service.to_string(),
+        operation: side_effect.operation,
+        target: side_effect.target,
+        details: side_effect.details,
+    }
+}
@@ -50,6 +50,18 @@ pub(crate) async fn cleanup_machine_completed(
         .load_machine(&machine_id, MachineSearchConfig::default())
         .await?;
 
+    // Failed sanitizations are recorded too: the certificate says which
+    // device failed and how.
+    if !cleanup_info.sanitization_certificate.is_empty() {
+        crate::handlers::disk_sanitization::record_certificate(
+            api,
+            &mut txn,
+            &machine_id,
+            &cleanup_info.sanitization_certificate,
+        )
+        .await?;
+    }
+
     let cleanup_error = [
         ("NVMe", cleanup_info.nvme.as_ref()),
         ("HDD/SAS", cleanup_info.hdd.as_ref()),
@@ -18,6 +18,7 @@
 pub(super) mod api;
 mod astra;
 pub(super) mod attestation;
+pub(super) mod audit;
 pub(super) mod bmc_credential_rotation;
 pub(super) mod bmc_endpoint_explorer;
 pub(super) mod bmc_metadata;
@@ -28,6 +29,7 @@ pub(super) mod compute_allocation;
 pub(super) mod credential;
 pub(super) mod credential_rotation;
 pub(super) mod db;
+pub(super) mod disk_sanitization;
 pub(super) mod dns;
 pub(super) mod domain;
 pub(super) mod dpa;
@@ -42,6 +44,7 @@ pub(super) mod expected_switch;
 pub(super) mod extension_service;
 pub(super) mod finder;
 pub(super) mod firmware;
+pub(super) mod firmware_rollout;
 pub(super) mod health;
 pub(super) mod host_reprovisioning;
 pub(super) mod ib_fabric;
@@ -52,6 +55,7 @@ pub(super) mod logical_partition;
 pub(super) mod machine;
 pub(super) mod machine_boot_interfaces;
 pub(super) mod machine_discovery;
+pub(super) mod machine_explain;
 pub(super) mod machine_hardware_info;
 pub(super) mod machine_identity;
 pub(super) mod machine_interface;
@@ -83,6 +87,8 @@ pub(super) mod site_explorer;
 pub(super) mod site_prefix;
 pub(super) mod sku;
 pub(super) mod spx_partition;
+pub(super) mod state_analytics;
+pub(super) mod state_watch;
 mod static_address_metrics;
 pub(super) mod svpc;
 pub(super) mod switch;
@@ -16,22 +16,30 @@
  */
 
 use std::collections::HashSet;
+use std::net::IpAddr;
 
 use ::rpc::errors::RpcDataConversionError;
 use ::rpc::forge as rpc;
+use carbide_network::virtualization::VpcVirtualizationType;
 use carbide_uuid::instance::InstanceId;
 use carbide_uuid::network_security_group::NetworkSecurityGroupId;
 use carbide_uuid::vpc::VpcId;
 use config_version::ConfigVersion;
-use db::network_security_group;
+use db::{ObjectColumnFilter, network_security_group};
 use model::metadata::Metadata;
-use model::network_security_group::{NetworkSecurityGroupRule, NetworkSecurityGroupRuleNet};
+use model::network_security_group::{
+    NetworkSecurityGroupRule, NetworkSecurityGroupRuleNet, ResolvedNetworkSecurityGroupReferences,
+    rule_references,
+};
 use model::tenant::{InvalidTenantOrg, TenantOrganizationId};
+use sqlx::PgConnection;
 use tonic::{Request, Response, Status};
 use uuid::Uuid;
 
-use crate::CarbideError;
 use crate::api::{Api, log_request_data, log_tenant_organization_id};
+use crate::handlers::dpu;
+use crate::network_security_group::simulation;
+use crate::{CarbideError, ethernet_virtualization};
 
 pub(crate) async fn create(
     api: &Api,
@@ -77,13 +85,6 @@ pub(crate) async fn create(
         )
     };
 
-    let max_nsg_size = api
-        .runtime_config
-        .network_security_group
-        .max_network_security_group_size as usize;
-
-    validate_expanded_rule_set(&rules, max_nsg_size)?;
-
     // Log tenant organization ID
     log_tenant_organization_id(&req.tenant_organization_id);
 
@@ -98,6 +99,16 @@ pub(crate) async fn create(
     // Start a new transaction for a db write.
     let mut txn = api.txn_begin().await?;
 
+    let max_nsg_size = api
+        .runtime_config
+        .network_security_group
+        .max_network_security_group_size as usize;
+
+    let resolved_references =
+        resolve_rule_references(&mut txn, &id, &tenant_organization_id, &rules).await?;
+
+    validate_expanded_rule_set(&rules, &resolved_references, max_nsg_size)?;
+
     // Write a new NetworkSecurityGroup to the DB and get back
     // our new NetworkSecurityGroup.
     let network_security_group = network_security_group::create(
@@ -111,6 +122,8 @@ pub(crate) async fn create(
     )
     .await?;
 
+    network_security_group::record_resolved_references(&mut txn, &id, &resolved_references).await?;
+
     // Prepare the response to send back
     let rpc_out = rpc::CreateNetworkSecurityGroupResponse {
         network_security_group: Some(network_security_group.try_into()?),
@@ -288,13 +301,6 @@ pub(crate) async fn update(
         )
     };
 
-    let max_nsg_size = api
-        .runtime_config
-        .network_security_group
-        .max_network_security_group_size as usize;
-
-    validate_expanded_rule_set(&rules, max_nsg_size)?;
-
     // Log tenant organization ID from request
     log_tenant_organization_id(&req.tenant_organization_id);
 
@@ -367,6 +373,16 @@ pub(crate) async fn update(
         }
     };
 
+    let max_nsg_size = api
+        .runtime_config
+        .network_security_group
+        .max_network_security_group_size as usize;
+
+    let resolved_references =
+        resolve_rule_references(&mut txn, &id, &tenant_organization_id, &rules).await?;
+
+    validate_expanded_rule_set(&rules, &resolved_references, max_nsg_size)?;
+
     // Update record in the DB and get back
     // our new NetworkSecurityGroup state.
     let network_security_group = network_security_group::update(
@@ -381,6 +397,8 @@ pub(crate) async fn update(
     )
     .await?;
 
+    network_security_group::record_resolved_references(&mut txn, &id, &resolved_references).await?;
+
     // Prepare the response to send back
     let rpc_out = rpc::UpdateNetworkSecurityGroupResponse {
         network_security_group: Some(network_security_group.try_into()?),
@@ -621,8 +639,426 @@ pub(crate) async fn get_attachments(
     Ok(Response::new(rpc_out))
 }
 
-fn validate_expanded_rule_set(
+pub(crate) async fn simulate_flow(
+    api: &Api,
+    request: Request<rpc::SimulateNetworkSecurityGroupFlowRequest>,
+) -> Result<Response<rpc::SimulateNetworkSecurityGroupFlowResponse>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+
+    let protocol = req.protocol();
+    if matches!(
+        protocol,
+        rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoInvalid
+            | rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoAny
+    ) {
+        return Err(CarbideError::InvalidArgument(
+            "a flow must use a specific protocol".to_string(),
+        )
+        .into());
+    }
+
+    let source = FlowEndpoint::try_from_rpc(req.source, "source")?;
+    let destination = FlowEndpoint::try_from_rpc(req.destination, "destination")?;
+
+    if matches!(
+        (&source, &destination),
+        (FlowEndpoint::Ip(_), FlowEndpoint::Ip(_))
+    ) {
+        return Err(CarbideError::InvalidArgument(
+            "at least one endpoint of the flow must be an instance".to_string(),
+        )
+        .into());
+    }
+
+    let ipv6 = match (source.ip(), destination.ip()) {
+        (Some(s), Some(d)) if s.is_ipv6() != d.is_ipv6() => {
+            return Err(CarbideError::InvalidArgument(
+                "source and destination IPs must be of the same address family".to_string(),
+            )
+            .into());
+        }
+        (Some(ip), _) | (_, Some(ip)) => ip.is_ipv6(),
+        (None, None) => req.ipv6,
+    };
+
+    let mut txn = api.txn_begin().await?;
+
+    let (source_instance, src_ip) = source.resolve(&mut txn, ipv6).await?;
+    let (destination_instance, dst_ip) = destination.resolve(&mut txn, ipv6).await?;
+
+    let flow = simulation::Flow {
+        src_ip,
+        dst_ip,
+        protocol,
+        src_port: req.src_port,
+        dst_port: req.dst_port,
+    };
+
+    let policy_overrides = api
+        .runtime_config
+        .network_security_group
+        .policy_overrides
+        .iter()
+        .map(|r| {
+            ethernet_virtualization::resolve_security_group_rule(r.clone(), &Default::default())
+        })
+        .collect::<Result<Vec<rpc::ResolvedNetworkSecurityGroupRule>, CarbideError>>()?;
+
+    let mut evaluations = vec![];
+
+    for (instance, direction) in [
+        (
+            source_instance,
+            rpc::NetworkSecurityGroupRuleDirection::NsgRuleDirectionEgress,
+        ),
+        (
+            destination_instance,
+            rpc::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress,
+        ),
+    ] {
+        let Some(instance) = instance else {
+            continue;
+        };
+
+        let deny_prefixes = dpu::deny_prefixes_for_agent(
+            &api.eth_data.deny_prefixes,
+            instance.network_virtualization_type,
+        );
+
+        let acls = simulation::RenderedAcls {
+            network_virtualization_type: instance.network_virtualization_type,
+            deny_prefixes: &deny_prefixes,
+            vpc_prefixes: &instance.vpc_prefixes,
+            policy_overrides: &policy_overrides,
+            network_security_group: instance.network_security_group.as_ref(),
+            stateful_acls_enabled: api
+                .runtime_config
+                .network_security_group
+                .stateful_acls_enabled,
+        };
+
+        let (decision, steps) = simulation::evaluate(&acls, direction, &flow);
+
+        evaluations.push(rpc::NetworkSecurityGroupFlowEvaluation {
+            instance_id: instance.id.to_string(),
+            direction: direction.into(),
+            network_security_group: Some(match instance.network_security_group.as_ref() {
+                Some(nsg) => rpc::NetworkSecurityGroupStatus {
+                    source: nsg.source,
+                    id: nsg.id.clone(),
+                    version: nsg.version.clone(),
+                },
+                None => rpc::NetworkSecurityGroupStatus {
+                    source: rpc::NetworkSecurityGroupSource::NsgSourceNone.into(),
+                    ..Default::default()
+                },
+            }),
+            decision: decision.into(),
+            steps,
+        });
+    }
+
+    txn.commit().await?;
+
+    let decision = if evaluations
+        .iter()
+        .all(|e| e.decision() == rpc::NetworkSecurityGroupFlowDecision::NsgFlowDecisionPermit)
+    {
+        rpc::NetworkSecurityGroupFlowDecision::NsgFlowDecisionPermit
+    } else {
+        rpc::NetworkSecurityGroupFlowDecision::NsgFlowDecisionDeny
+    };
+
+    Ok(Response::new(
+        rpc::SimulateNetworkSecurityGroupFlowResponse {
+            decision: decision.into(),
+            src_ip: flow.src_ip.to_string(),
+            dst_ip: flow.dst_ip.to_string(),
+            evaluations,
+        },
+    ))
+}
+
+/// One end of a simulated flow as sent in by the caller.
+enum FlowEndpoint {
+    /// An instance, optionally narrowed down to one of its addresses.
+    Instance(InstanceId, Option<IpAddr>),
+    Ip(IpAddr),
+}
+
+impl FlowEndpoint {
+    fn try_from_rpc(
+        endpoint: Option<rpc::NetworkSecurityGroupFlowEndpoint>,
+        name: &'static str,
+    ) -> Result<Self, CarbideError> {
+        let endpoint = endpoint.ok_or(RpcDataConversionError::MissingArgument(name))?;
+
+        let instance_id = endpoint
+            .instance_id
+            .map(|i| i.parse::<InstanceId>())
+            .transpose()
+            .map_err(|e| RpcDataConversionError::InvalidInstanceId(e.to_string()))?;
+
+        let ip = endpoint
+            .ip
+            .map(|i| i.parse::<IpAddr>())
+            .transpose()
+            .map_err(|e| RpcDataConversionError::InvalidIpAddress(e.to_string()))?;
+
+        match (instance_id, ip) {
+            (Some(instance_id), ip) => Ok(FlowEndpoint::Instance(instance_id, ip)),
+            (None, Some(ip)) => Ok(FlowEndpoint::Ip(ip)),
+            (None, None) => Err(CarbideError::InvalidArgument(format!(
+                "{name} must have an instance ID or an IP"
+            ))),
+        }
+    }
+
+    fn ip(&self) -> Option<IpAddr> {
+        match self {
+            FlowEndpoint::Instance(_, ip) => *ip,
+            FlowEndpoint::Ip(ip) => Some(*ip),
+        }
+    }
+
+    /// Loads the instance of the endpoint, if any, and returns
+    /// it together with the address the flow uses.
+    async fn resolve(
+        self,
+        txn: &mut PgConnection,
+        ipv6: bool,
+    ) -> Result<(Option<FlowInstance>, IpAddr), CarbideError> {
+        match self {
+            FlowEndpoint::Instance(id, ip) => {
+                let instance = load_flow_instance(txn, id, ip, ipv6).await?;
+                let ip = instance.ip;
+                Ok((Some(instance), ip))
+            }
+            FlowEndpoint::Ip(ip) => Ok((None, ip)),
+        }
+    }
+}
+
+/// An instance taking part in a simulated flow, together with
+/// the NSG and prefixes that its DPU would currently be sent.
+struct FlowInstance {
+    id: InstanceId,
+    ip: IpAddr,
+    network_virtualization_type: VpcVirtualizationType,
+    vpc_prefixes: Vec<String>,
+    network_security_group: Option<rpc::FlatInterfaceNetworkSecurityGroupConfig>,
+}
+
+/// Loads an instance of a flow and works out the NSG configuration
+/// its DPU receives the same way `GetManagedHostNetworkConfig` does:
+/// the NSG of the instance overrides the NSG of the VPC, and references
+/// are resolved to their current prefixes.
+async fn load_flow_instance(
+    txn: &mut PgConnection,
+    id: InstanceId,
+    ip: Option<IpAddr>,
+    ipv6: bool,
+) -> Result<FlowInstance, CarbideError> {
+    let instance = db::instance::find_by_id(&mut *txn, id)
+        .await?
+        .ok_or_else(|| CarbideError::NotFoundError {
+            kind: "instance",
+            id: id.to_string(),
+        })?;
+
+    let interfaces = &instance.config.network.interfaces;
+
+    // Pick the interface that holds the address of the flow.
+    let (interface, ip) = match ip {
+        Some(ip) => interfaces
+            .iter()
+            .find(|i| i.ip_addrs.values().any(|a| *a == ip))
+            .map(|i| (i, ip))
+            .ok_or_else(|| {
+                CarbideError::InvalidArgument(format!("{ip} is not an address of instance {id}"))
+            })?,
+        None => interfaces
+            .iter()
+            .find_map(|i| {
+                i.ip_addrs
+                    .values()
+                    .filter(|a| a.is_ipv6() == ipv6)
+                    .min()
+                    .map(|a| (i, *a))
+            })
+            .ok_or_else(|| {
+                CarbideError::FailedPrecondition(format!(
+                    "instance {id} has no {} address",
+                    if ipv6 { "IPv6" } else { "IPv4" }
+                ))
+            })?,
+    };
+
+    let segment_id = interface
+        .network_segment_id
+        .ok_or(CarbideError::NetworkSegmentNotAllocated)?;
+    let vpc = db::vpc::find_by_segment(&mut *txn, segment_id)
+        .await?
+        .ok_or_else(|| {
+            CarbideError::FailedPrecondition("network segment is not a member of a VPC".to_string())
+        })?;
+
+    // Instances of flat VPCs live directly on the underlay
+    // and there is no DPU to enforce rules for them.
+    if vpc.config.network_virtualization_type == VpcVirtualizationType::Flat {
+        return Err(CarbideError::FailedPrecondition(format!(
+            "instance {id} is in a flat VPC, and its traffic is not filtered by a DPU"
+        )));
+    }
+
+    let vpc_prefixes = db::vpc_prefix::find_by_vpc(&mut *txn, vpc.id)
+        .await?
+        .into_iter()
+        .map(|vpc_prefix| vpc_prefix.config.prefix.to_string())
+        .chain(
+            db::network_prefix::find_by_vpc(&mut *txn, vpc.id)
+                .await?
+                .into_iter()
+                .map(|segment_prefix| segment_prefix.prefix.to_string()),
+        )
+        .collect();
+
+    let tenant_organization_id = &instance.config.tenant.tenant_organization_id;
+    let (source, network_security_group_id) = match (
+        instance.config.network_security_group_id.as_ref(),
+        vpc.config.network_security_group_id.as_ref(),
+    ) {
+        (Some(nsg_id), _) => (
+            rpc::NetworkSecurityGroupSource::NsgSourceInstance,
+            Some(nsg_id),
+        ),
+        (None, Some(nsg_id)) => (rpc::NetworkSecurityGroupSource::NsgSourceVpc, Some(nsg_id)),
+        (None, None) => (rpc::NetworkSecurityGroupSource::NsgSourceNone, None),
+    };
+
+    let network_security_group = match network_security_group_id {
+        None => None,
+        Some(nsg_id) => {
+            let nsg = network_security_group::find_by_ids(
+                &mut *txn,
+                std::slice::from_ref(nsg_id),
+                Some(tenant_organization_id),
+                false,
+            )
+            .await?
+            .pop()
+            .ok_or_else(|| CarbideError::NotFoundError {
+                kind: "NetworkSecurityGroup",
+                id: nsg_id.to_string(),
+            })?;
+
+            let references = network_security_group::find_resolved_references(txn, &nsg).await?;
+
+            Some(rpc::FlatInterfaceNetworkSecurityGroupConfig {
+                id: nsg.id.to_string(),
+                version: nsg.version.to_string(),
+                source: source.into(),
+                stateful_egress: nsg.stateful_egress,
+                rules: nsg
+                    .rules
+                    .into_iter()
+                    .map(|r| ethernet_virtualization::resolve_security_group_rule(r, &references))
+                    .collect::<Result<Vec<_>, _>>()?,
+            })
+        }
+    };
+
+    Ok(FlowInstance {
+        id,
+        ip,
+        network_virtualization_type: vpc.config.network_virtualization_type,
+        vpc_prefixes,
+        network_security_group,
+    })
+}
+
+/// Checks that every security-group and VPC reference in the rules
+/// points at an object owned by the tenant, then resolves all
+/// references to the prefixes they currently expand to.
+/// A rule may reference the NSG that contains it.
+async fn resolve_rule_references(
+    txn: &mut PgConnection,
+    id: &NetworkSecurityGroupId,
+    tenant_organization_id: &TenantOrganizationId,
     rules: &[NetworkSecurityGroupRule],
+) -> Result<ResolvedNetworkSecurityGroupReferences, CarbideError> {
+    let references = rule_references(rules);
+
+    let network_security_group_ids = references
+        .iter()
+        .filter_map(|r| match r {
+            NetworkSecurityGroupRuleNet::NetworkSecurityGroupId(i) if i != id => Some(i.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if !network_security_group_ids.is_empty() {
+        let found = network_security_group::find_by_ids(
+            &mut *txn,
+            &network_security_group_ids,
+            Some(tenant_organization_id),
+            false,
+        )
+        .await?;
+
+        if let Some(missing) = network_security_group_ids
+            .iter()
+            .find(|i| !found.iter().any(|nsg| &nsg.id == *i))
+        {
+            return Err(CarbideError::NotFoundError {
+                kind: "NetworkSecurityGroup",
+                id: format!("{missing} for tenant org `{tenant_organization_id}`"),
+            });
+        }
+    }
+
+    let vpc_ids = references
+        .iter()
+        .filter_map(|r| match r {
+            NetworkSecurityGroupRuleNet::VpcId(i) => Some(*i),
+            _ => None,
+        })
+        .collect::<Vec<VpcId>>();
+
+    if !vpc_ids.is_empty() {
+        let tenant = tenant_organization_id.to_string();
+        let found = db::vpc::find_by(
+            &mut *txn,
+            ObjectColumnFilter::List(db::vpc::IdColumn, &vpc_ids),
+        )
+        .await?;
+
+        if let Some(missing) = vpc_ids.iter().find(|i| {
+            !found
+                .iter()
+                .any(|vpc| &vpc.id == *i && vpc.config.tenant_organization_id == tenant)
+        }) {
+            return Err(CarbideError::NotFoundError {
+                kind: "Vpc",
+                id: format!("{missing} for tenant org `{tenant_organization_id}`"),
+            });
+        }
+    }
+
+    Ok(
+        network_security_group::resolve_references(txn, tenant_organization_id, &references)
+            .await?,
+    )
+}
+
+/// Checks the size of the rule set once every rule is expanded into
+/// the cartesian product of its port ranges and prefix lists.  References
+/// count as the number of prefixes they currently resolve to.
+pub(crate) fn validate_expanded_rule_set(
+    rules: &[NetworkSecurityGroupRule],
+    references: &ResolvedNetworkSecurityGroupReferences,
     limit: usize,
 ) -> Result<(), CarbideError> {
     let mut total_rules = 0u32;
@@ -635,6 +1071,18 @@ fn validate_expanded_rule_set(
         )));
     }
 
+    let prefix_count = |net: &NetworkSecurityGroupRuleNet, ipv6: bool| -> u32 {
+        match net {
+            NetworkSecurityGroupRuleNet::Prefix(_) => 1,
+            reference => references
+                .get(reference)
+                .map(|prefixes| prefixes.iter().filter(|p| p.is_ipv6() == ipv6).count())
+                .unwrap_or_default()
+                .try_into()
+                .unwrap_or(u32::MAX),
+        }
+    };
+
     for rule in rules {
         if !ids.insert(rule.id.clone()) {
             return Err(CarbideError::InvalidArgument(format!(
@@ -643,33 +1091,30 @@ fn validate_expanded_rule_set(
             )));
         }
 
-        match (&rule.src_net, &rule.dst_net) {
-            (NetworkSecurityGroupRuleNet::Prefix(_), NetworkSecurityGroupRuleNet::Prefix(_)) => {
-                // Negative ranges are caught when we convert from rpc to internal struct.
-                // so we can keep this simple.
-                let rule_count = (rule.src_port_end.unwrap_or_default()
-                    - rule.src_port_start.unwrap_or_default()
-                    + 1)
-                .saturating_mul(
-                    rule.dst_port_end.unwrap_or_default() - rule.dst_port_start.unwrap_or_default()
-                        + 1,
-                );
-
-                total_rules = match total_rules.overflowing_add(rule_count) {
-                    (_, true) => {
-                        return Err(CarbideError::InvalidArgument(format!(
-                            "expanded rule set contains more than {limit} maximum number of rules"
-                        )));
-                    }
-                    (v, false) => v,
-                };
-
-                if total_rules as usize > limit {
-                    return Err(CarbideError::InvalidArgument(format!(
-                        "expanded rule set contains more than {limit} maximum number of rules"
-                    )));
-                }
+        // Negative ranges are caught when we convert from rpc to internal struct.
+        // so we can keep this simple.
+        let rule_count = (rule.src_port_end.unwrap_or_default()
+            - rule.src_port_start.unwrap_or_default()
+            + 1)
+        .saturating_mul(
+            rule.dst_port_end.unwrap_or_default() - rule.dst_port_start.unwrap_or_default() + 1,
+        )
+        .saturating_mul(prefix_count(&rule.src_net, rule.ipv6))
+        .saturating_mul(prefix_count(&rule.dst_net, rule.ipv6));
+
+        total_rules = match total_rules.overflowing_add(rule_count) {
+            (_, true) => {
+                return Err(CarbideError::InvalidArgument(format!(
+                    "expanded rule set contains more than {limit} maximum number of rules"
+                )));
             }
+            (v, false) => v,
+        };
+
+        if total_rules as usize > limit {
+            return Err(CarbideError::InvalidArgument(format!(
+                "expanded rule set contains more than {limit} maximum number of rules"
+            )));
         }
     }
 
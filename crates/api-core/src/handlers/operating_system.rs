@@ -115,6 +115,14 @@ fn rpc_to_ipxe_script(
     }
 }
 
+fn user_data_format_from_rpc(
+    value: i32,
+) -> Result<model::operating_system_definition::UserDataFormat, Status> {
+    rpc::UserDataFormat::try_from(value)
+        .map(Into::into)
+        .map_err(|_| Status::invalid_argument(format!("unknown user_data_format {value}")))
+}
+
 fn params_from_json(json: Option<&serde_json::Value>) -> Vec<rpc::IpxeTemplateParameter> {
     let Some(serde_json::Value::Array(arr)) = json else {
         return vec![];
@@ -281,6 +289,7 @@ pub(crate) async fn create_operating_system(
         ));
     }
 
+    let user_data_format = user_data_format_from_rpc(req.user_data_format)?;
     let id = req.id.map(Uuid::from);
 
     let input = db::operating_system::CreateOperatingSystem {
@@ -294,6 +303,7 @@ pub(crate) async fn create_operating_system(
         allow_override: req.allow_override,
         phone_home_enabled: req.phone_home_enabled,
         user_data: req.user_data,
+        user_data_format,
         ipxe_script,
         ipxe_template_id,
         ipxe_parameters,
@@ -452,6 +462,11 @@ pub(crate) async fn update_operating_system(
         None
     };
 
+    let user_data_format = req
+        .user_data_format
+        .map(user_data_format_from_rpc)
+        .transpose()?;
+
     let input = db::operating_system::UpdateOperatingSystem {
         id,
         name: req.name,
@@ -460,6 +475,7 @@ pub(crate) async fn update_operating_system(
         allow_override: req.allow_override,
         phone_home_enabled: req.phone_home_enabled,
         user_data: req.user_data,
+        user_data_format,
         ipxe_script: req.ipxe_script,
         ipxe_template_id: req_template_id,
         ipxe_parameters,
@@ -632,6 +648,7 @@ pub(crate) async fn update_operating_system_cachable_ipxe_script_artifacts(
         allow_override: None,
         phone_home_enabled: None,
         user_data: None,
+        user_data_format: None,
         ipxe_script: None,
         ipxe_template_id: None,
         ipxe_parameters: None,
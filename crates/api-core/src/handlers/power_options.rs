@@ -18,9 +18,11 @@
 use std::str::FromStr;
 
 use ::rpc::forge as rpc;
+use carbide_machine_controller::io::MachineStateControllerIO;
 use db::DatabaseError;
 use mac_address::MacAddress;
 use model::machine::LoadSnapshotOptions;
+use state_controller::controller::Enqueuer;
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
@@ -130,6 +132,9 @@ pub(crate) async fn update_power_option(
     )
     .await?;
 
+    // Act on the new desired power state as soon as it is committed.
+    Enqueuer::<MachineStateControllerIO>::enqueue_object_in_txn(&mut txn, &machine_id).await?;
+
     txn.commit().await?;
 
     Ok(Response::new(rpc::PowerOptionResponse {
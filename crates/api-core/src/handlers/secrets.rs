@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+use ::rpc::Timestamp;
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
@@ -78,3 +79,47 @@ pub(crate) async fn re_wrap_secrets(
         stale_remaining: result.stale_remaining,
     }))
 }
+
+pub(crate) async fn get_secret_history(
+    api: &Api,
+    request: Request<rpc::forge::GetSecretHistoryRequest>,
+) -> Result<Response<rpc::forge::GetSecretHistoryResponse>, Status> {
+    crate::api::log_request_data(&request);
+
+    let path = request.into_inner().path;
+    // Internal bookkeeping paths start with a slash and hold no credential.
+    if path.is_empty() || path.starts_with('/') {
+        return Err(
+            CarbideError::InvalidArgument(format!("invalid credential path {path:?}")).into(),
+        );
+    }
+
+    let ctx = api.secrets_context.as_ref().ok_or_else(|| {
+        CarbideError::FailedPrecondition(
+            "secrets backend not configured -- no [secrets] section in config".to_string(),
+        )
+    })?;
+    let manager = crate::secrets::PostgresCredentialManager::new(
+        api.database_connection.clone(),
+        ctx.routing.clone(),
+        ctx.kms.clone(),
+    );
+
+    let versions = manager
+        .get_path_history(&path)
+        .await
+        .map_err(|e| CarbideError::internal(format!("reading secret history failed: {e}")))?
+        .into_iter()
+        .map(|entry| rpc::forge::SecretVersion {
+            secret_id: entry.secret_id.to_string(),
+            seq: entry.seq,
+            kek_id: entry.kek_id.clone(),
+            created_at: Some(Timestamp::from(entry.created_at)),
+            tombstone: entry.is_tombstone(),
+        })
+        .collect();
+
+    Ok(Response::new(rpc::forge::GetSecretHistoryResponse {
+        versions,
+    }))
+}
@@ -0,0 +1,104 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::errors::RpcDataConversionError;
+use ::rpc::forge as rpc;
+use chrono::{DateTime, TimeDelta, Utc};
+use model::state_analytics::{StateAnalyticsFilter, StateAnalyticsObjectType, StateDwellAnalytics};
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+
+/// Window length when the request sets no `since`.
+const DEFAULT_WINDOW: TimeDelta = TimeDelta::hours(24);
+
+/// Stuck threshold when the request sets no `stuck_after`. Matches the
+/// shortest machine state SLAs.
+const DEFAULT_STUCK_AFTER: std::time::Duration = std::time::Duration::from_secs(30 * 60);
+
+pub(crate) async fn get_state_dwell_analytics(
+    api: &Api,
+    request: Request<rpc::StateDwellAnalyticsRequest>,
+) -> Result<Response<rpc::StateDwellAnalytics>, Status> {
+    log_request_data(&request);
+
+    let rpc::StateDwellAnalyticsRequest {
+        object_type,
+        since,
+        until,
+        sku,
+        rack_id,
+        instance_type_id,
+        stuck_after,
+    } = request.into_inner();
+
+    let object_type = rpc::StateAnalyticsObjectType::try_from(object_type)
+        .map_err(|_| {
+            RpcDataConversionError::InvalidValue("object_type".into(), object_type.to_string())
+        })
+        .and_then(StateAnalyticsObjectType::try_from)
+        .map_err(CarbideError::from)?;
+    if object_type != StateAnalyticsObjectType::Machine
+        && (sku.is_some() || instance_type_id.is_some())
+    {
+        return Err(CarbideError::InvalidArgument(
+            "sku and instance_type_id only apply to machines".to_string(),
+        )
+        .into());
+    }
+
+    let until = until
+        .map(DateTime::<Utc>::try_from)
+        .transpose()
+        .map_err(|e| CarbideError::InvalidArgument(format!("invalid until: {e}")))?
+        .unwrap_or_else(Utc::now);
+    let since = since
+        .map(DateTime::<Utc>::try_from)
+        .transpose()
+        .map_err(|e| CarbideError::InvalidArgument(format!("invalid since: {e}")))?
+        .unwrap_or(until - DEFAULT_WINDOW);
+    if since >= until {
+        return Err(CarbideError::InvalidArgument("since must be before until".to_string()).into());
+    }
+    let stuck_after = stuck_after
+        .map(std::time::Duration::try_from)
+        .transpose()
+        .map_err(|e| CarbideError::InvalidArgument(format!("invalid stuck_after: {e}")))?
+        .unwrap_or(DEFAULT_STUCK_AFTER);
+
+    let filter = StateAnalyticsFilter {
+        object_type,
+        since,
+        until,
+        sku,
+        rack_id,
+        instance_type_id,
+    };
+    let spans = db::state_history::find_state_spans(&mut api.db_reader(), &filter).await?;
+
+    Ok(Response::new(
+        StateDwellAnalytics::compute(
+            &spans,
+            since,
+            until,
+            stuck_after,
+            object_type.steady_states(),
+        )
+        .into(),
+    ))
+}
@@ -0,0 +1,155 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use ::rpc::forge as rpc;
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use carbide_uuid::power_shelf::PowerShelfId;
+use carbide_uuid::rack::RackId;
+use carbide_uuid::switch::SwitchId;
+use config_version::ConfigVersion;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+use crate::state_watch::{StateWatchStreamType, WatchRequest, WatchedObjectType};
+
+pub(crate) async fn watch_machine_states(
+    api: &Api,
+    request: Request<rpc::StateWatchRequest>,
+) -> Result<Response<StateWatchStreamType>, Status> {
+    watch_states::<MachineId>(api, request, WatchedObjectType::Machine)
+}
+
+pub(crate) async fn watch_instance_states(
+    api: &Api,
+    request: Request<rpc::StateWatchRequest>,
+) -> Result<Response<StateWatchStreamType>, Status> {
+    watch_states::<InstanceId>(api, request, WatchedObjectType::Instance)
+}
+
+pub(crate) async fn watch_rack_states(
+    api: &Api,
+    request: Request<rpc::StateWatchRequest>,
+) -> Result<Response<StateWatchStreamType>, Status> {
+    watch_states::<RackId>(api, request, WatchedObjectType::Rack)
+}
+
+pub(crate) async fn watch_switch_states(
+    api: &Api,
+    request: Request<rpc::StateWatchRequest>,
+) -> Result<Response<StateWatchStreamType>, Status> {
+    watch_states::<SwitchId>(api, request, WatchedObjectType::Switch)
+}
+
+pub(crate) async fn watch_power_shelf_states(
+    api: &Api,
+    request: Request<rpc::StateWatchRequest>,
+) -> Result<Response<StateWatchStreamType>, Status> {
+    watch_states::<PowerShelfId>(api, request, WatchedObjectType::PowerShelf)
+}
+
+fn watch_states<Id>(
+    api: &Api,
+    request: Request<rpc::StateWatchRequest>,
+    object_type: WatchedObjectType,
+) -> Result<Response<StateWatchStreamType>, Status>
+where
+    Id: FromStr + Display,
+    Id::Err: Display,
+{
+    log_request_data(&request);
+
+    let request = parse_watch_request::<Id>(
+        request.into_inner(),
+        object_type,
+        api.runtime_config.max_find_by_ids as usize,
+    )?;
+    let stream = api
+        .state_watch_hub
+        .open(api.database_connection.clone(), request)?;
+
+    Ok(Response::new(stream))
+}
+
+/// Validates a `StateWatchRequest` and converts its object IDs to the
+/// canonical form of `Id`.
+fn parse_watch_request<Id>(
+    request: rpc::StateWatchRequest,
+    object_type: WatchedObjectType,
+    max_object_ids: usize,
+) -> Result<WatchRequest, CarbideError>
+where
+    Id: FromStr + Display,
+    Id::Err: Display,
+{
+    if request.object_ids.len() > max_object_ids {
+        return Err(CarbideError::InvalidArgument(format!(
+            "no more than {max_object_ids} IDs can be accepted"
+        )));
+    }
+
+    let object_ids = request
+        .object_ids
+        .iter()
+        .map(|object_id| canonical_object_id::<Id>(object_id))
+        .collect::<Result<HashSet<_>, _>>()?;
+    let object_ids = (!object_ids.is_empty()).then_some(object_ids);
+
+    let mut resume_from = HashMap::new();
+    for resume_point in request.resume_from {
+        let object_id = canonical_object_id::<Id>(&resume_point.object_id)?;
+        if object_ids
+            .as_ref()
+            .is_some_and(|object_ids| !object_ids.contains(&object_id))
+        {
+            return Err(CarbideError::InvalidArgument(format!(
+                "resume point for {object_id} refers to an object that is not watched"
+            )));
+        }
+        let state_version = resume_point.state_version.parse::<ConfigVersion>()?;
+        if resume_from
+            .insert(object_id.clone(), state_version)
+            .is_some()
+        {
+            return Err(CarbideError::InvalidArgument(format!(
+                "more than one resume point for {object_id}"
+            )));
+        }
+    }
+
+    Ok(WatchRequest {
+        object_type,
+        object_ids,
+        resume_from,
+    })
+}
+
+fn canonical_object_id<Id>(object_id: &str) -> Result<String, CarbideError>
+where
+    Id: FromStr + Display,
+    Id::Err: Display,
+{
+    object_id
+        .parse::<Id>()
+        .map(|id| id.to_string())
+        .map_err(|e| CarbideError::InvalidArgument(format!("invalid object ID {object_id}: {e}")))
+}
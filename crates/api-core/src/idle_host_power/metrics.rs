@@ -0,0 +1,156 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use ::carbide_utils::metrics::SharedMetricsHolder;
+use carbide_uuid::instance_type::InstanceTypeId;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::Meter;
+
+/// The hosts of one instance type, as counted in the last pass.
+#[derive(Clone, Debug, Default)]
+pub(super) struct InstanceTypeIdleMetrics {
+    pub(super) warm_hosts: u64,
+    pub(super) parked_hosts: u64,
+    pub(super) waking_hosts: u64,
+    /// Estimated power not drawn because of the parked hosts.
+    pub(super) saved_watts: u64,
+    /// Estimated energy not drawn since the manager started.
+    pub(super) saved_energy_watt_hours: f64,
+}
+
+/// IdleHostPowerMetrics stores the hosts counted in one
+/// `IdleHostPowerManager` pass, keyed by instance type.
+#[derive(Clone, Debug)]
+pub(super) struct IdleHostPowerMetrics {
+    // When we finished recording the metrics.
+    pub(super) recording_finished_at: std::time::Instant,
+    pub(super) instance_types: HashMap<InstanceTypeId, InstanceTypeIdleMetrics>,
+}
+
+impl IdleHostPowerMetrics {
+    pub(super) fn new() -> Self {
+        Self {
+            recording_finished_at: Instant::now(),
+            instance_types: HashMap::new(),
+        }
+    }
+}
+
+fn hydrate_meter(meter: Meter, shared_metrics: SharedMetricsHolder<IdleHostPowerMetrics>) {
+    let u64_gauges: [(
+        &'static str,
+        &'static str,
+        fn(&InstanceTypeIdleMetrics) -> u64,
+    ); 4] = [
+        (
+            "carbide_idle_power_warm_hosts",
+            "Number of allocatable hosts of an instance type that are kept powered on.",
+            |metrics| metrics.warm_hosts,
+        ),
+        (
+            "carbide_idle_power_parked_hosts",
+            "Number of hosts of an instance type powered off by the idle host power policy.",
+            |metrics| metrics.parked_hosts,
+        ),
+        (
+            "carbide_idle_power_waking_hosts",
+            "Number of parked hosts of an instance type that are being powered back on.",
+            |metrics| metrics.waking_hosts,
+        ),
+        (
+            "carbide_idle_power_saved_watts",
+            "Estimated power not drawn by the parked hosts of an instance type.",
+            |metrics| metrics.saved_watts,
+        ),
+    ];
+
+    for (name, description, value) in u64_gauges {
+        let metrics = shared_metrics.clone();
+        meter
+            .u64_observable_gauge(name)
+            .with_description(description)
+            .with_callback(move |observer| {
+                metrics.if_available(|metrics, attrs| {
+                    for (instance_type_id, type_metrics) in metrics.instance_types.iter() {
+                        observer.observe(
+                            value(type_metrics),
+                            &[
+                                attrs,
+                                &[KeyValue::new(
+                                    "instance_type_id",
+                                    instance_type_id.to_string(),
+                                )],
+                            ]
+                            .concat(),
+                        );
+                    }
+                });
+            })
+            .build();
+    }
+
+    {
+        let metrics = shared_metrics;
+        meter
+            .f64_observable_counter("carbide_idle_power_saved_energy_watt_hours_total")
+            .with_description(
+                "Estimated energy not drawn by parked hosts of an instance type since the manager started.",
+            )
+            .with_callback(move |observer| {
+                metrics.if_available(|metrics, attrs| {
+                    for (instance_type_id, type_metrics) in metrics.instance_types.iter() {
+                        observer.observe(
+                            type_metrics.saved_energy_watt_hours,
+                            &[
+                                attrs,
+                                &[KeyValue::new(
+                                    "instance_type_id",
+                                    instance_type_id.to_string(),
+                                )],
+                            ]
+                            .concat(),
+                        );
+                    }
+                });
+            })
+            .build();
+    }
+}
+
+/// Stores Metric data shared between the idle host power manager and the OpenTelemetry background task
+pub(super) struct MetricHolder {
+    last_iteration_metrics: SharedMetricsHolder<IdleHostPowerMetrics>,
+}
+
+impl MetricHolder {
+    pub(super) fn new(meter: Meter, hold_period: std::time::Duration) -> Self {
+        let last_iteration_metrics = SharedMetricsHolder::with_hold_period(hold_period);
+        hydrate_meter(meter, last_iteration_metrics.clone());
+        Self {
+            last_iteration_metrics,
+        }
+    }
+
+    /// Updates the most recent metrics
+    pub(super) fn update_metrics(&self, mut metrics: IdleHostPowerMetrics) {
+        metrics.recording_finished_at = Instant::now();
+        self.last_iteration_metrics.update(metrics)
+    }
+}
@@ -0,0 +1,486 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Powers off Ready hosts that nobody uses. For every instance type listed
+//! in `power_manager_options.idle_host_policy`, each pass counts the hosts
+//! that are allocatable and powered on (the warm pool), parks the ones
+//! beyond `warm_pool_size` that have been idle for longer than
+//! `idle_timeout`, and wakes parked hosts when the warm pool runs short or
+//! when allocations failed for want of powered-on hosts. Allocations record
+//! such unmet demand with [`record_unmet_demand`]; the next pass takes it.
+//!
+//! Parking only records the decision on the host's power options: the
+//! desired power state becomes Off and `idle_parked_at` is set, which takes
+//! the host out of allocation. The machine state handler's power manager
+//! then shuts the host down. Waking sets the desired state back to On and,
+//! if configured, requests on-demand machine validation, so the host is
+//! re-validated before it becomes allocatable again.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use carbide_machine_controller::config::PowerManagerOptions;
+use carbide_machine_controller::config::power_manager::{
+    IdleHostPowerPolicy, InstanceTypeIdlePolicy,
+};
+use carbide_utils::managed_loop::{self, LoopManager};
+use carbide_uuid::instance_type::InstanceTypeId;
+use carbide_uuid::machine::MachineId;
+use config_version::ConfigVersion;
+use db::work_lock_manager::{AcquireLockError, WorkLockManagerHandle};
+use model::machine::machine_search_config::MachineSearchConfig;
+use model::machine::{
+    HostHealthConfig, LoadSnapshotOptions, MachineValidationContext, MachineValidationFilter,
+    ManagedHostStateSnapshot,
+};
+use model::power_manager::PowerState;
+use opentelemetry::metrics::Meter;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use crate::{CarbideError, CarbideResult};
+
+mod metrics;
+mod plan;
+
+use plan::IdleHostState;
+
+const IDLE_HOST_WORK_KEY: &str = "idle_host_power_manager::iteration";
+
+/// A host of an instance type, as far as the idle policy is concerned.
+struct IdleHost {
+    machine_id: MachineId,
+    desired_power_state_version: ConfigVersion,
+    state: IdleHostState,
+}
+
+/// Energy not drawn by parked hosts, summed over the passes of this manager.
+#[derive(Default)]
+struct SavedEnergy {
+    last_pass_at: Option<Instant>,
+    watt_hours: HashMap<InstanceTypeId, f64>,
+}
+
+/// `IdleHostPowerManager` powers off idle Ready hosts while keeping a warm
+/// pool of powered-on hosts per instance type.
+pub(crate) struct IdleHostPowerManager {
+    database_connection: sqlx::PgPool,
+    config: IdleHostPowerPolicy,
+    power_manager_enabled: bool,
+    revalidate_on_wake: bool,
+    host_health: HostHealthConfig,
+    work_lock_manager_handle: WorkLockManagerHandle,
+    metric_holder: metrics::MetricHolder,
+    saved_energy: Mutex<SavedEnergy>,
+}
+
+impl IdleHostPowerManager {
+    pub(crate) fn new(
+        database_connection: sqlx::PgPool,
+        power_manager_options: PowerManagerOptions,
+        machine_validation_enabled: bool,
+        host_health: HostHealthConfig,
+        work_lock_manager_handle: WorkLockManagerHandle,
+        meter: Meter,
+    ) -> Self {
+        let config = power_manager_options.idle_host_policy;
+        let hold_period = run_interval(&config).saturating_add(std::time::Duration::from_secs(60));
+        IdleHostPowerManager {
+            database_connection,
+            power_manager_enabled: power_manager_options.enabled,
+            // Validation can only be requested if the site runs it at all.
+            revalidate_on_wake: config.revalidate_on_wake && machine_validation_enabled,
+            config,
+            host_health,
+            work_lock_manager_handle,
+            metric_holder: metrics::MetricHolder::new(meter, hold_period),
+            saved_energy: Mutex::new(SavedEnergy::default()),
+        }
+    }
+
+    /// Spawn the manager's background loop into `join_set`.
+    pub(crate) fn start(
+        self,
+        join_set: &mut JoinSet<()>,
+        cancel_token: CancellationToken,
+    ) -> std::io::Result<()> {
+        // Parked hosts are powered off and on by the machine state handler's
+        // power manager, so the policy does nothing without it.
+        if self.config.enabled && self.power_manager_enabled {
+            join_set
+                .build_task()
+                .name("idle_host_power_manager")
+                .spawn(async move { self.run(cancel_token).await })?;
+        } else if self.config.enabled {
+            tracing::warn!(
+                "Idle host power policy is enabled, but the power manager is not; not starting IdleHostPowerManager"
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn run(&self, cancel_token: CancellationToken) {
+        loop {
+            let result = self.run_single_iteration().await;
+            managed_loop::record_iteration(LoopManager::IdleHostPowerManager, &result);
+
+            tokio::select! {
+                _ = tokio::time::sleep(run_interval(&self.config)) => {},
+                _ = cancel_token.cancelled() => {
+                    tracing::info!("IdleHostPowerManager stop was requested");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Runs one pass over all configured instance types and returns the
+    /// number of hosts that were parked or woken.
+    pub(crate) async fn run_single_iteration(&self) -> CarbideResult<usize> {
+        let _work_lock = match self
+            .work_lock_manager_handle
+            .try_acquire_lock(IDLE_HOST_WORK_KEY.into())
+            .await
+        {
+            Ok(lock) => lock,
+            Err(AcquireLockError::WorkAlreadyLocked(_)) => {
+                tracing::debug!(
+                    lock = IDLE_HOST_WORK_KEY,
+                    "Skipping idle host power pass; another instance holds the lock"
+                );
+                return Ok(0);
+            }
+            Err(e) => {
+                return Err(CarbideError::Internal {
+                    message: format!(
+                        "unable to acquire idle host power lock `{IDLE_HOST_WORK_KEY}`: {e}"
+                    ),
+                });
+            }
+        };
+
+        let elapsed_hours = {
+            let mut saved_energy = self.saved_energy.lock().unwrap();
+            let now = Instant::now();
+            let elapsed = saved_energy
+                .last_pass_at
+                .replace(now)
+                .map(|last_pass_at| now.duration_since(last_pass_at));
+            elapsed.map_or(0.0, |elapsed| elapsed.as_secs_f64() / 3600.0)
+        };
+
+        let mut iteration_metrics = metrics::IdleHostPowerMetrics::new();
+        let mut changed_hosts = 0;
+        for (instance_type_id, policy) in self.config.instance_types.iter() {
+            let instance_type_id = match instance_type_id.parse::<InstanceTypeId>() {
+                Ok(instance_type_id) => instance_type_id,
+                Err(err) => {
+                    tracing::warn!(
+                        instance_type_id,
+                        error = %err,
+                        "Ignoring idle host power policy of invalid instance type"
+                    );
+                    continue;
+                }
+            };
+
+            match self.apply_policy(&instance_type_id, policy).await {
+                Ok((mut type_metrics, changed)) => {
+                    changed_hosts += changed;
+                    let mut saved_energy = self.saved_energy.lock().unwrap();
+                    let watt_hours = saved_energy
+                        .watt_hours
+                        .entry(instance_type_id.clone())
+                        .or_default();
+                    *watt_hours += type_metrics.saved_watts as f64 * elapsed_hours;
+                    type_metrics.saved_energy_watt_hours = *watt_hours;
+                    iteration_metrics
+                        .instance_types
+                        .insert(instance_type_id, type_metrics);
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        %instance_type_id,
+                        error = %err,
+                        "Idle host power pass failed"
+                    );
+                }
+            }
+        }
+
+        self.metric_holder.update_metrics(iteration_metrics);
+        Ok(changed_hosts)
+    }
+
+    /// Applies the policy to the hosts of one instance type. Returns the
+    /// host counts as they were before this pass, so the saved power
+    /// covers the time since the previous pass, and the number of hosts
+    /// parked or woken.
+    async fn apply_policy(
+        &self,
+        instance_type_id: &InstanceTypeId,
+        policy: &InstanceTypeIdlePolicy,
+    ) -> CarbideResult<(metrics::InstanceTypeIdleMetrics, usize)> {
+        let hosts = self.load_hosts(instance_type_id).await?;
+        let unmet_demand = self.take_unmet_demand(instance_type_id).await?;
+        if unmet_demand > 0 {
+            tracing::info!(
+                %instance_type_id,
+                unmet_demand,
+                "Allocations were short of powered-on hosts"
+            );
+        }
+        let states = hosts.iter().map(|host| host.state).collect::<Vec<_>>();
+        let plan = plan::plan(
+            &states,
+            policy.warm_pool_size,
+            unmet_demand,
+            policy.idle_timeout,
+            chrono::Utc::now(),
+        );
+
+        let mut type_metrics = metrics::InstanceTypeIdleMetrics::default();
+        for state in states.iter() {
+            match state {
+                IdleHostState::Warm { .. } => type_metrics.warm_hosts += 1,
+                IdleHostState::Parked { .. } => type_metrics.parked_hosts += 1,
+                IdleHostState::Waking => type_metrics.waking_hosts += 1,
+            }
+        }
+        type_metrics.saved_watts = type_metrics.parked_hosts * u64::from(policy.idle_watts);
+
+        let mut changed = 0;
+        for idx in plan.park {
+            let host = &hosts[idx];
+            match self.park(host).await {
+                Ok(true) => {
+                    changed += 1;
+                    tracing::info!(
+                        machine_id = %host.machine_id,
+                        %instance_type_id,
+                        "Powering off idle host"
+                    );
+                }
+                Ok(false) => {
+                    tracing::debug!(
+                        machine_id = %host.machine_id,
+                        "Idle host changed since it was loaded; not powering it off"
+                    );
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        machine_id = %host.machine_id,
+                        error = %err,
+                        "Unable to power off idle host"
+                    );
+                }
+            }
+        }
+        for idx in plan.wake {
+            let host = &hosts[idx];
+            match self.wake(host).await {
+                Ok(()) => {
+                    changed += 1;
+                    tracing::info!(
+                        machine_id = %host.machine_id,
+                        %instance_type_id,
+                        "Powering on parked host"
+                    );
+                }
+                Err(CarbideError::ConcurrentModificationError(_, _)) => {
+                    tracing::debug!(
+                        machine_id = %host.machine_id,
+                        "Parked host changed since it was loaded; not powering it on"
+                    );
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        machine_id = %host.machine_id,
+                        error = %err,
+                        "Unable to power on parked host"
+                    );
+                }
+            }
+        }
+
+        Ok((type_metrics, changed))
+    }
+
+    /// Returns the hosts of the instance type the policy applies to, sorted
+    /// by machine id so that ties are broken in a stable order.
+    async fn load_hosts(&self, instance_type_id: &InstanceTypeId) -> CarbideResult<Vec<IdleHost>> {
+        let mut txn = db::Transaction::begin(&self.database_connection).await?;
+        let machine_ids = db::machine::find_machine_ids(
+            &mut txn,
+            MachineSearchConfig {
+                instance_type_id: Some(instance_type_id.clone()),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let snapshots = db::managed_host::load_by_machine_ids(
+            &mut txn,
+            &machine_ids,
+            LoadSnapshotOptions::default().with_host_health(self.host_health),
+        )
+        .await?;
+        txn.commit().await?;
+
+        let mut hosts = snapshots
+            .values()
+            .filter_map(|snapshot| {
+                let power_options = snapshot.host_snapshot.status.power_options.as_ref()?;
+                Some(IdleHost {
+                    machine_id: snapshot.host_snapshot.id,
+                    desired_power_state_version: power_options.desired_power_state_version,
+                    state: idle_host_state(snapshot)?,
+                })
+            })
+            .collect::<Vec<_>>();
+        hosts.sort_by_key(|host| host.machine_id);
+        Ok(hosts)
+    }
+
+    async fn take_unmet_demand(&self, instance_type_id: &InstanceTypeId) -> CarbideResult<u32> {
+        let mut txn = db::Transaction::begin(&self.database_connection).await?;
+        let unmet_demand =
+            db::power_options::take_idle_wake_demand(instance_type_id, &mut txn).await?;
+        txn.commit().await?;
+        Ok(unmet_demand)
+    }
+
+    /// Returns whether the host was parked.
+    async fn park(&self, host: &IdleHost) -> CarbideResult<bool> {
+        let mut txn = db::Transaction::begin(&self.database_connection).await?;
+        let parked = db::power_options::park_idle_host(
+            &host.machine_id,
+            &host.desired_power_state_version,
+            &mut txn,
+        )
+        .await?;
+        txn.commit().await?;
+        Ok(parked.is_some())
+    }
+
+    /// Fails with `ConcurrentModificationError` if the host's desired power
+    /// state changed since it was loaded or it is no longer parked.
+    async fn wake(&self, host: &IdleHost) -> CarbideResult<()> {
+        let mut txn = db::Transaction::begin(&self.database_connection).await?;
+        let woken = db::power_options::wake_idle_host(
+            &host.machine_id,
+            &host.desired_power_state_version,
+            &mut txn,
+        )
+        .await?;
+        if woken.is_none() {
+            return Err(CarbideError::ConcurrentModificationError(
+                "power_options",
+                host.desired_power_state_version.to_string(),
+            ));
+        }
+
+        if self.revalidate_on_wake {
+            let validation = db::machine_validation::create_new_run(
+                &mut txn,
+                &host.machine_id,
+                MachineValidationContext::OnDemand,
+                MachineValidationFilter {
+                    tags: vec![],
+                    allowed_tests: vec![],
+                    run_unverfied_tests: Some(false),
+                    contexts: Some(vec![]),
+                },
+            )
+            .await?;
+            tracing::debug!(
+                machine_id = %host.machine_id,
+                machine_validation_id = %validation.id,
+                "Requested validation of woken host"
+            );
+            db::machine::set_machine_validation_request(&mut txn, &host.machine_id, true).await?;
+        }
+
+        txn.commit().await?;
+        Ok(())
+    }
+}
+
+/// Records that an allocation of `instance_type_id` was short `hosts` hosts,
+/// so the next pass of the idle host power manager wakes that many parked
+/// hosts. Does nothing unless the policy applies to the instance type.
+/// Failures are only logged: the allocation fails either way.
+pub(crate) async fn record_unmet_demand(
+    database_connection: &sqlx::PgPool,
+    power_manager_options: &PowerManagerOptions,
+    instance_type_id: &InstanceTypeId,
+    hosts: u32,
+) {
+    let policy = &power_manager_options.idle_host_policy;
+    if hosts == 0
+        || !power_manager_options.enabled
+        || !policy.enabled
+        || !policy
+            .instance_types
+            .contains_key(&instance_type_id.to_string())
+    {
+        return;
+    }
+
+    let result = async {
+        let mut txn = db::Transaction::begin(database_connection).await?;
+        db::power_options::record_idle_wake_demand(instance_type_id, hosts, &mut txn).await?;
+        txn.commit().await?;
+        Ok::<_, CarbideError>(())
+    }
+    .await;
+    if let Err(err) = result {
+        tracing::warn!(
+            %instance_type_id,
+            hosts,
+            error = %err,
+            "Unable to record unmet demand for parked hosts"
+        );
+    }
+}
+
+/// Classifies a host. Hosts that are neither parked nor allocatable, such
+/// as ones running an instance or powered off by an operator, are left out.
+fn idle_host_state(snapshot: &ManagedHostStateSnapshot) -> Option<IdleHostState> {
+    let power_options = snapshot.host_snapshot.status.power_options.as_ref()?;
+    match (
+        power_options.idle_parked_at,
+        power_options.desired_power_state,
+    ) {
+        (Some(parked_at), PowerState::Off) => Some(IdleHostState::Parked { parked_at }),
+        (Some(_), PowerState::On) => Some(IdleHostState::Waking),
+        (None, PowerState::On) if snapshot.is_usable_as_instance(false).is_ok() => {
+            Some(IdleHostState::Warm {
+                idle_since: snapshot.host_snapshot.current_version().timestamp(),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn run_interval(config: &IdleHostPowerPolicy) -> std::time::Duration {
+    config
+        .run_interval
+        .to_std()
+        .unwrap_or(std::time::Duration::from_secs(60))
+}
@@ -0,0 +1,194 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Decides which hosts of an instance type to power off or back on, kept
+//! free of I/O so the warm pool rules can be tested on their own.
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// A host of an instance type, as far as the idle policy is concerned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum IdleHostState {
+    /// Powered on and allocatable, idle since it entered Ready.
+    Warm { idle_since: DateTime<Utc> },
+    /// Powered off by the policy.
+    Parked { parked_at: DateTime<Utc> },
+    /// Parked, and asked to power back on.
+    Waking,
+}
+
+/// What one pass does. Both lists hold positions in the hosts passed in.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(super) struct Plan {
+    pub(super) park: Vec<usize>,
+    pub(super) wake: Vec<usize>,
+}
+
+/// Waking hosts count towards the warm pool, since they will be allocatable
+/// once they are back. Hosts are woken, the ones parked longest first, when
+/// the pool is short or when allocations asked for `unmet_demand` more hosts
+/// than were powered on; demand that hosts already waking will serve wakes
+/// nothing more. Otherwise the hosts beyond the pool that have been idle for
+/// at least `idle_timeout` are parked, the longest idle first. Nothing is
+/// parked while there is unmet demand.
+pub(super) fn plan(
+    hosts: &[IdleHostState],
+    warm_pool_size: u32,
+    unmet_demand: u32,
+    idle_timeout: TimeDelta,
+    now: DateTime<Utc>,
+) -> Plan {
+    let warm_pool_size = warm_pool_size as usize;
+    let available = hosts
+        .iter()
+        .filter(|host| !matches!(host, IdleHostState::Parked { .. }))
+        .count();
+    let waking = hosts
+        .iter()
+        .filter(|host| matches!(host, IdleHostState::Waking))
+        .count();
+    let to_wake = warm_pool_size
+        .saturating_sub(available)
+        .max((unmet_demand as usize).saturating_sub(waking));
+
+    if to_wake > 0 || unmet_demand > 0 {
+        let mut parked = hosts
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, host)| match host {
+                IdleHostState::Parked { parked_at } => Some((*parked_at, idx)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        parked.sort();
+        return Plan {
+            park: Vec::new(),
+            wake: parked
+                .into_iter()
+                .take(to_wake)
+                .map(|(_, idx)| idx)
+                .collect(),
+        };
+    }
+
+    let mut idle = hosts
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, host)| match host {
+            IdleHostState::Warm { idle_since } if *idle_since + idle_timeout <= now => {
+                Some((*idle_since, idx))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    idle.sort();
+    Plan {
+        park: idle
+            .into_iter()
+            .take(available - warm_pool_size)
+            .map(|(_, idx)| idx)
+            .collect(),
+        wake: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::value_scenarios;
+
+    use super::*;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        DateTime::<Utc>::UNIX_EPOCH + TimeDelta::minutes(minutes)
+    }
+
+    fn warm(minutes: i64) -> IdleHostState {
+        IdleHostState::Warm {
+            idle_since: at(minutes),
+        }
+    }
+
+    fn parked(minutes: i64) -> IdleHostState {
+        IdleHostState::Parked {
+            parked_at: at(minutes),
+        }
+    }
+
+    #[test]
+    fn keeps_the_warm_pool_and_parks_the_rest() {
+        // The pass runs at minute 100 with a 30 minute idle timeout.
+        value_scenarios!(run = |(hosts, warm_pool_size): (Vec<IdleHostState>, u32)| {
+            let plan = plan(&hosts, warm_pool_size, 0, TimeDelta::minutes(30), at(100));
+            (plan.park, plan.wake)
+        };
+            "longest idle hosts beyond the pool are parked" {
+                (vec![warm(60), warm(10), warm(40)], 1) => (vec![1, 2], vec![]),
+                (vec![warm(60), warm(10), warm(40)], 0) => (vec![1, 2, 0], vec![]),
+            }
+
+            "hosts idle for less than the timeout stay on" {
+                (vec![warm(80), warm(90)], 0) => (vec![], vec![]),
+                (vec![warm(10), warm(90)], 0) => (vec![0], vec![]),
+            }
+
+            "waking hosts count towards the pool" {
+                (vec![IdleHostState::Waking, warm(10)], 1) => (vec![1], vec![]),
+            }
+
+            "a short pool wakes the hosts parked longest" {
+                (vec![parked(50), warm(10), parked(20), parked(30)], 3) => (vec![], vec![2, 3]),
+                (vec![IdleHostState::Waking, parked(50)], 2) => (vec![], vec![1]),
+            }
+
+            "a pool larger than the fleet wakes everything" {
+                (vec![parked(50), parked(20)], 5) => (vec![], vec![1, 0]),
+            }
+
+            "a full pool leaves parked hosts off" {
+                (vec![parked(50), warm(10)], 1) => (vec![], vec![]),
+            }
+        );
+    }
+
+    #[test]
+    fn unmet_demand_wakes_parked_hosts() {
+        // The pass runs at minute 100 with a 30 minute idle timeout.
+        value_scenarios!(run = |(hosts, warm_pool_size, unmet_demand): (Vec<IdleHostState>, u32, u32)| {
+            let plan = plan(&hosts, warm_pool_size, unmet_demand, TimeDelta::minutes(30), at(100));
+            (plan.park, plan.wake)
+        };
+            "demand beyond a full pool wakes the hosts parked longest" {
+                (vec![parked(50), warm(10), parked(20), parked(30)], 1, 2) => (vec![], vec![2, 3]),
+            }
+
+            "hosts already waking serve the demand" {
+                (vec![IdleHostState::Waking, parked(50), parked(20)], 0, 2) => (vec![], vec![2]),
+                (vec![IdleHostState::Waking, IdleHostState::Waking, parked(20)], 0, 2) => (vec![], vec![]),
+            }
+
+            "the larger of pool shortage and demand is woken" {
+                (vec![parked(50), parked(20), parked(30)], 2, 1) => (vec![], vec![1, 2]),
+                (vec![parked(50), parked(20), parked(30)], 1, 3) => (vec![], vec![1, 2, 0]),
+            }
+
+            "idle hosts are not parked while there is demand" {
+                (vec![warm(10), warm(20), parked(30)], 0, 1) => (vec![], vec![2]),
+                (vec![warm(10), IdleHostState::Waking], 0, 1) => (vec![], vec![]),
+            }
+        );
+    }
+}
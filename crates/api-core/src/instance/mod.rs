@@ -15,12 +15,15 @@
  * limitations under the License.
  */
 
+pub(crate) mod placement;
+
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 
 use ::rpc::errors::RpcDataConversionError;
 use ::rpc::forge as rpc;
+use carbide_machine_controller::io::MachineStateControllerIO;
 use carbide_network::virtualization::VpcVirtualizationType;
 use carbide_uuid::infiniband::IBPartitionId;
 use carbide_uuid::instance::InstanceId;
@@ -59,6 +62,7 @@ use model::tenant::TenantOrganizationId;
 use model::vpc::{FabricInterfaceType, VpcVirtualizationTypeCapabilities};
 use model::vpc_prefix::VpcPrefix;
 use sqlx::PgConnection;
+use state_controller::controller::Enqueuer;
 
 use crate::api::Api;
 use crate::cfg::file::{CarbideConfig, ComputeAllocationEnforcement};
@@ -1411,6 +1415,9 @@ fn not_allocatable_error(machine_id: MachineId, reason: NotAllocatableReason) ->
         }
         NotAllocatableReason::MaintenanceMode => CarbideError::MaintenanceMode,
         NotAllocatableReason::HealthAlert(_) => CarbideError::UnhealthyHost,
+        NotAllocatableReason::IdlePowerParked => CarbideError::FailedPrecondition(format!(
+            "machine {machine_id} is powered off by the idle host power policy; retry after it has been powered on"
+        )),
     }
 }
 
@@ -1620,6 +1627,18 @@ pub(crate) async fn batch_allocate_instances(
                     "Host can not be used as instance due to reason",
                 );
             }
+            if let (NotAllocatableReason::IdlePowerParked, Some(instance_type_id)) =
+                (&e, &mh_snapshot.host_snapshot.config.instance_type_id)
+            {
+                // Asks the idle host power manager to power the host back on.
+                crate::idle_host_power::record_unmet_demand(
+                    &api.database_connection,
+                    &api.runtime_config.power_manager_options,
+                    instance_type_id,
+                    1,
+                )
+                .await;
+            }
             return Err(not_allocatable_error(machine_id, e));
         }
 
@@ -2135,7 +2154,18 @@ pub(crate) async fn batch_allocate_instances(
         snapshots.push(mh_snapshot);
     }
 
-    // ==== Phase 11: Commit ====
+    // ==== Phase 11: Wake up the state handlers ====
+    // The hosts are handled as soon as the instances are committed instead of
+    // on the next periodic pass.
+    for mh_snapshot in &snapshots {
+        Enqueuer::<MachineStateControllerIO>::enqueue_object_in_txn(
+            &mut txn,
+            &mh_snapshot.host_snapshot.id,
+        )
+        .await?;
+    }
+
+    // ==== Phase 12: Commit ====
     txn.commit().await?;
 
     tracing::info!(
@@ -0,0 +1,282 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Chooses machines for instances allocated by instance type rather than by
+//! machine id.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use ::rpc::forge as rpc;
+use carbide_uuid::instance_type::InstanceTypeId;
+use carbide_uuid::machine::MachineId;
+use carbide_uuid::nvlink::NvLinkDomainId;
+use carbide_uuid::rack::RackId;
+use model::machine::LoadSnapshotOptions;
+use model::machine::machine_search_config::MachineSearchConfig;
+
+use crate::api::Api;
+use crate::{CarbideError, CarbideResult};
+
+/// Where instances of one request may be placed relative to each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PlacementPolicy {
+    Any,
+    SameNvLinkDomain,
+    SameRack,
+    SpreadAcrossRacks,
+}
+
+impl From<rpc::InstancePlacementPolicy> for PlacementPolicy {
+    fn from(policy: rpc::InstancePlacementPolicy) -> Self {
+        match policy {
+            rpc::InstancePlacementPolicy::Any => PlacementPolicy::Any,
+            rpc::InstancePlacementPolicy::SameNvlinkDomain => PlacementPolicy::SameNvLinkDomain,
+            rpc::InstancePlacementPolicy::SameRack => PlacementPolicy::SameRack,
+            rpc::InstancePlacementPolicy::SpreadAcrossRacks => PlacementPolicy::SpreadAcrossRacks,
+        }
+    }
+}
+
+/// A machine that could host one of the requested instances.
+#[derive(Clone, Debug)]
+pub(crate) struct PlacementCandidate {
+    pub(crate) machine_id: MachineId,
+    pub(crate) rack_id: Option<RackId>,
+    pub(crate) nvlink_domain_id: Option<NvLinkDomainId>,
+}
+
+/// Returns the machines of an instance type that could take an instance right
+/// now: Ready, unused, healthy and not quarantined.
+pub(crate) async fn find_candidates(
+    api: &Api,
+    instance_type_id: &InstanceTypeId,
+) -> CarbideResult<Vec<PlacementCandidate>> {
+    let mut reader = api.db_reader();
+    let machine_ids = db::machine::find_machine_ids(
+        &mut reader,
+        MachineSearchConfig {
+            instance_type_id: Some(instance_type_id.clone()),
+            controller_state: Some("ready".to_string()),
+            ..MachineSearchConfig::default()
+        },
+    )
+    .await?;
+    let snapshots = db::managed_host::load_by_machine_ids(
+        &mut reader,
+        &machine_ids,
+        LoadSnapshotOptions::default().with_host_health(api.runtime_config.host_health),
+    )
+    .await?;
+
+    Ok(snapshots
+        .into_values()
+        .filter(|snapshot| {
+            snapshot
+                .host_snapshot
+                .network_config
+                .quarantine_state
+                .is_none()
+                && snapshot.is_usable_as_instance(false).is_ok()
+        })
+        .map(|snapshot| PlacementCandidate {
+            machine_id: snapshot.host_snapshot.id,
+            nvlink_domain_id: snapshot
+                .host_snapshot
+                .status
+                .nvlink_info
+                .as_ref()
+                .map(|info| info.domain_uuid),
+            rack_id: snapshot.host_snapshot.rack_id,
+        })
+        .collect())
+}
+
+/// Picks `count` machines from `candidates` that satisfy `policy`.
+///
+/// The same candidates always give the same answer. Rack and NVLink domain
+/// policies only consider machines whose rack or domain is known; of the
+/// groups large enough, the smallest is used so larger groups stay free for
+/// larger requests.
+pub(crate) fn choose_machines(
+    policy: PlacementPolicy,
+    mut candidates: Vec<PlacementCandidate>,
+    count: usize,
+) -> CarbideResult<Vec<MachineId>> {
+    candidates.sort_by_key(|candidate| candidate.machine_id);
+
+    let chosen = match policy {
+        PlacementPolicy::Any => candidates
+            .iter()
+            .map(|candidate| candidate.machine_id)
+            .take(count)
+            .collect(),
+        PlacementPolicy::SameNvLinkDomain => {
+            best_fit_group(&candidates, count, |candidate| candidate.nvlink_domain_id)
+        }
+        PlacementPolicy::SameRack => {
+            best_fit_group(&candidates, count, |candidate| candidate.rack_id.clone())
+        }
+        PlacementPolicy::SpreadAcrossRacks => {
+            spread(&candidates, count, |candidate| candidate.rack_id.clone())
+        }
+    };
+
+    if chosen.len() < count {
+        return Err(CarbideError::FailedPrecondition(format!(
+            "only {} of {count} requested machines are available with placement {policy:?}",
+            chosen.len()
+        )));
+    }
+    Ok(chosen)
+}
+
+fn groups<K: Ord>(
+    candidates: &[PlacementCandidate],
+    key: impl Fn(&PlacementCandidate) -> Option<K>,
+) -> BTreeMap<K, Vec<MachineId>> {
+    let mut groups = BTreeMap::<K, Vec<MachineId>>::new();
+    for candidate in candidates {
+        if let Some(key) = key(candidate) {
+            groups.entry(key).or_default().push(candidate.machine_id);
+        }
+    }
+    groups
+}
+
+/// The smallest group holding `count` machines, or the largest group when
+/// none does.
+fn best_fit_group<K: Ord>(
+    candidates: &[PlacementCandidate],
+    count: usize,
+    key: impl Fn(&PlacementCandidate) -> Option<K>,
+) -> Vec<MachineId> {
+    let groups: Vec<_> = groups(candidates, key).into_values().collect();
+    let mut group = groups
+        .iter()
+        .filter(|group| group.len() >= count)
+        .min_by_key(|group| group.len())
+        .or_else(|| groups.iter().max_by_key(|group| group.len()))
+        .cloned()
+        .unwrap_or_default();
+    group.truncate(count);
+    group
+}
+
+/// Takes one machine from each group in turn, so no group ends up with more
+/// than one machine over any other group that still had capacity.
+fn spread<K: Ord>(
+    candidates: &[PlacementCandidate],
+    count: usize,
+    key: impl Fn(&PlacementCandidate) -> Option<K>,
+) -> Vec<MachineId> {
+    let mut groups: Vec<VecDeque<MachineId>> = groups(candidates, key)
+        .into_values()
+        .map(VecDeque::from)
+        .collect();
+    let mut chosen = Vec::with_capacity(count.min(candidates.len()));
+    while chosen.len() < count {
+        let before = chosen.len();
+        for group in groups.iter_mut() {
+            if chosen.len() == count {
+                break;
+            }
+            if let Some(machine_id) = group.pop_front() {
+                chosen.push(machine_id);
+            }
+        }
+        if chosen.len() == before {
+            break;
+        }
+    }
+    chosen
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::Outcome::*;
+    use carbide_test_support::scenarios;
+    use carbide_uuid::machine::{MachineIdSource, MachineType};
+
+    use super::*;
+
+    fn host(n: u8) -> MachineId {
+        MachineId::new(MachineIdSource::Tpm, [n; 32], MachineType::Host)
+    }
+
+    /// Hosts 1-3 share rack a and domain 1, hosts 4-5 share rack b and
+    /// domain 2, host 6 is in rack c with no NVLink domain and host 7 has
+    /// neither.
+    fn candidates() -> Vec<PlacementCandidate> {
+        [
+            (1, Some("a"), Some(1)),
+            (2, Some("a"), Some(1)),
+            (3, Some("a"), Some(1)),
+            (4, Some("b"), Some(2)),
+            (5, Some("b"), Some(2)),
+            (6, Some("c"), None),
+            (7, None, None),
+        ]
+        .into_iter()
+        .rev()
+        .map(|(n, rack, domain)| PlacementCandidate {
+            machine_id: host(n),
+            rack_id: rack.map(RackId::from),
+            nvlink_domain_id: domain.map(|d| uuid::Uuid::from_u128(d).into()),
+        })
+        .collect()
+    }
+
+    struct Request {
+        policy: PlacementPolicy,
+        count: usize,
+    }
+
+    /// Runs a request against [`candidates`], naming chosen hosts by number.
+    fn choose(Request { policy, count }: Request) -> Result<Vec<u8>, ()> {
+        let hosts: Vec<_> = (1..=7).map(|n| (host(n), n)).collect();
+        choose_machines(policy, candidates(), count)
+            .map(|ids| {
+                ids.iter()
+                    .map(|id| hosts.iter().find(|(host, _)| host == id).unwrap().1)
+                    .collect()
+            })
+            .map_err(|_| ())
+    }
+
+    #[test]
+    fn placement_policies() {
+        use PlacementPolicy::*;
+
+        scenarios!(choose:
+            "any placement takes machines in id order" {
+                Request { policy: Any, count: 2 } => Yields(vec![1, 2]),
+                Request { policy: Any, count: 8 } => Fails,
+            }
+            "grouped placement prefers the smallest group that fits" {
+                Request { policy: SameRack, count: 2 } => Yields(vec![4, 5]),
+                Request { policy: SameRack, count: 3 } => Yields(vec![1, 2, 3]),
+                Request { policy: SameRack, count: 1 } => Yields(vec![6]),
+                Request { policy: SameNvLinkDomain, count: 1 } => Yields(vec![4]),
+                Request { policy: SameNvLinkDomain, count: 4 } => Fails,
+            }
+            "spread placement rotates through racks" {
+                Request { policy: SpreadAcrossRacks, count: 3 } => Yields(vec![1, 4, 6]),
+                Request { policy: SpreadAcrossRacks, count: 5 } => Yields(vec![1, 4, 6, 2, 5]),
+                Request { policy: SpreadAcrossRacks, count: 7 } => Fails,
+            }
+        );
+    }
+}
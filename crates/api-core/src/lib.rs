@@ -40,15 +40,17 @@
 //     configuration loading, and the admin UI route builder; and
 //   - the `carbide-api-web` crate, which needs the `Api` service type and a few shared types
 //     (`AuthContext`, `CarbideError`, `LogStream`/`LogLine`, `NUM_REQUIRED_APPROVALS`, and the
-//     `cfg::file` config types).
+//     `cfg::file` config types), plus `AdminWebAction` for its audit records.
 // Anything that doesn't need to cross a crate boundary should stay private.
 
 mod admission;
 mod api;
 mod attestation;
+mod audit;
 mod auth;
 #[doc(hidden)]
 pub mod bootstrap;
+mod certificate_revocations;
 pub mod cfg;
 mod compat;
 mod credentials;
@@ -60,6 +62,7 @@ mod dynamic_settings;
 mod errors;
 mod ethernet_virtualization;
 mod handlers;
+mod idle_host_power;
 mod instance;
 mod ipxe;
 mod listener;
@@ -69,11 +72,14 @@ mod machine_update_manager;
 mod machine_validation;
 mod measured_boot;
 mod mqtt_state_change_hook;
+mod network_security_group;
 mod network_segment;
 mod node_auth;
+mod rack_power_budget;
 mod scout_stream;
 pub mod secrets;
 mod setup;
+mod state_watch;
 mod storage;
 
 #[cfg(any(test, feature = "test-support"))]
@@ -94,6 +100,7 @@ pub(crate) use errors::CarbideResult;
 
 pub use crate::admission::AdminAdmissionControl;
 pub use crate::api::{Api, DefaultCredential};
+pub use crate::audit::AdminWebAction;
 pub use crate::auth::AuthContext;
 use crate::cfg::file::ToolLink;
 pub use crate::dynamic_settings::DynamicSettings;
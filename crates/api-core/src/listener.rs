@@ -22,6 +22,7 @@ use std::time::{Duration, Instant};
 use ::rpc::forge as rpc;
 use carbide_authn::SpiffeContext;
 use carbide_authn::middleware::{CertDescriptionMiddleware, ConnectionAttributes};
+use carbide_authn::revocation::RevocationList;
 use hyper::server::conn::{http1, http2};
 use hyper_util::rt::{TokioExecutor, TokioIo};
 use hyper_util::service::TowerToHyperService;
@@ -40,8 +41,10 @@ use tower_http::normalize_path::NormalizePath;
 
 use crate::admission::{AdminAdmissionControl, ApiAdmissionControl, enforce_grpc};
 use crate::api::Api;
+use crate::audit::AuditLayer;
 use crate::auth;
 use crate::auth::Authorization;
+use crate::certificate_revocations::CertificateRevocations;
 use crate::cfg::file::AuthConfig;
 use crate::errors::CarbideError;
 use crate::logging::api_logs::LogLayer;
@@ -82,6 +85,44 @@ const TLS_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
 /// identity file amplify inbound traffic into a rebuild per connection.
 const TLS_REFRESH_RETRY_DELAY: Duration = Duration::from_secs(15);
 
+/// Cadence for reloading the revocation list from the database, so a client
+/// certificate revoked through another replica is refused without a restart.
+/// The replica serving a revocation installs it at once.
+const REVOCATION_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Loads the revocation list, if the certificate provider signs one, and
+/// spawns a task into `join_set` that reloads it every
+/// [`REVOCATION_REFRESH_INTERVAL`]. A failed reload keeps the previous list.
+async fn start_revocation_refresh(
+    join_set: &mut JoinSet<()>,
+    revocations: Arc<CertificateRevocations>,
+    cancel_token: CancellationToken,
+) -> eyre::Result<Option<Arc<RevocationList>>> {
+    if !revocations.reload().await? {
+        return Ok(None);
+    }
+    let revocation_list = revocations.list();
+
+    join_set
+        .build_task()
+        .name("client certificate revocation refresh")
+        .spawn(async move {
+            while cancel_token
+                .run_until_cancelled(tokio::time::sleep(REVOCATION_REFRESH_INTERVAL))
+                .await
+                .is_some()
+            {
+                if let Err(error) = revocations.reload().await {
+                    tracing::warn!(
+                        %error,
+                        "Failed to reload the client certificate revocation list"
+                    );
+                }
+            }
+        })?;
+    Ok(Some(revocation_list))
+}
+
 /// Reads the client-CA bundle. Split out so one read can feed both the TLS
 /// acceptor and the node-auth validator: each building from its own read lets a
 /// cert-manager rotation land between them, which would install an acceptor
@@ -368,6 +409,16 @@ pub(crate) async fn start(
         }
         let layer = CertDescriptionMiddleware::new(extra_cli_certs, spiffe_context)
             .with_machine_certs_enabled(machine_certs_enabled);
+        let layer = match start_revocation_refresh(
+            join_set,
+            api_service.certificate_revocations.clone(),
+            cancel_token.clone(),
+        )
+        .await?
+        {
+            Some(revocation_list) => layer.with_revocation_list(revocation_list),
+            None => layer,
+        };
         // When node-auth is enabled, accept bearer JWTs in addition to mTLS
         // client certs (dual-support during the mTLS→JWT migration). Bearer
         // tokens must only be accepted over TLS — never plaintext — so guard the
@@ -484,6 +535,9 @@ pub(crate) async fn start(
     let app = tower::ServiceBuilder::new()
         .layer(LogLayer::new(meter.clone()))
         .layer(cert_description_layer)
+        // Inside authentication, so principals are known; outside
+        // authorization, so denied calls are audited too.
+        .layer(AuditLayer::new(api_service.audit_log.clone()))
         .option_layer(internal_rbac_layer)
         .option_layer(casbin_layer)
         .service(router);
@@ -497,8 +551,9 @@ pub(crate) async fn start(
     // (file reads, PEM parsing, a blocking task) per inbound connection.
     let mut tls_refresh_after = TLS_REFRESH_INTERVAL;
     // Refreshed alongside the TLS acceptor below; both read the same client-CA
-    // bundle, so they must not drift apart.
-    let node_jwt_validator = api_service.node_jwt_validator.clone();
+    // bundle, so they must not drift apart. This is the node-auth validator
+    // too when node auth is enabled.
+    let node_jwt_validator = api_service.client_ca_verifier.clone();
 
     join_set
         .build_task()
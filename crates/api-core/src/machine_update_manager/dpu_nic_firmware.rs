@@ -24,11 +24,12 @@ use carbide_machine_controller::dpf::DpfOperations;
 use carbide_uuid::machine::MachineId;
 use db::dpu_machine_update;
 use model::dpu_machine_update::{DpuMachineUpdate, OutdatedDpfDpu};
+use model::firmware_rollout::FirmwareRolloutTarget;
 use model::machine::ManagedHostStateSnapshot;
 use sqlx::PgConnection;
 
 use super::dpu_nic_firmware_metrics::DpuNicFirmwareUpdateMetrics;
-use super::machine_update_module::MachineUpdateModule;
+use super::machine_update_module::{MachineUpdateModule, UpdateSlots};
 use super::metrics::{
     FirmwareUpdateFailed, FirmwareUpdateFailureCause, FirmwareUpdatePhase, FirmwareUpdateProgress,
     FirmwareUpdateTarget,
@@ -84,35 +85,50 @@ impl MachineUpdateModule for DpuNicFirmwareUpdate {
             .collect())
     }
 
+    fn rollout_target(&self) -> Option<FirmwareRolloutTarget> {
+        Some(FirmwareRolloutTarget::DpuNicFirmware)
+    }
+
     async fn start_updates(
         &self,
         pool: &sqlx::Pool<sqlx::Postgres>,
-        available_updates: i32,
+        slots: &mut UpdateSlots,
         updating_host_machines: &HashSet<MachineId>,
         snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
     ) -> CarbideResult<HashSet<MachineId>> {
+        // Every outdated host is a candidate: the slots decide which ones
+        // start, and a rollout's rack and NVLink domain caps can pass over
+        // hosts early in the list.
         let machine_updates: Vec<DpuMachineUpdate> = self
-            .check_for_updates(snapshots, available_updates)
+            .check_for_updates(snapshots, i32::MAX)
             .await
             .into_iter()
             .filter(|u| updating_host_machines.get(&u.host_machine_id).is_none())
             .collect();
 
-        // The outcome is vec<DpuMachineUpdate>, let's convert it to HashMap<host_machine_id, vec<DpuMachineUpdate>>
+        // The outcome is vec<DpuMachineUpdate>, let's group it by host_machine_id,
+        // keeping the order the hosts were found in.
         // This way we can run our loop based on host_machine id.
-        let mut host_machine_updates: HashMap<MachineId, Vec<DpuMachineUpdate>> = HashMap::new();
+        let mut host_machine_updates: Vec<(MachineId, Vec<DpuMachineUpdate>)> = Vec::new();
 
         for machine_update in machine_updates {
-            host_machine_updates
-                .entry(machine_update.host_machine_id)
-                .or_default()
-                .push(machine_update);
+            match host_machine_updates
+                .iter_mut()
+                .find(|(host_machine_id, _)| *host_machine_id == machine_update.host_machine_id)
+            {
+                Some((_, updates)) => updates.push(machine_update),
+                None => host_machine_updates
+                    .push((machine_update.host_machine_id, vec![machine_update])),
+            }
         }
 
         let mut updates_started = HashSet::default();
 
         for (host_machine_id, machine_updates) in host_machine_updates {
-            if updating_host_machines.contains(&host_machine_id) {
+            if slots.is_exhausted() {
+                break;
+            }
+            if !slots.claim(&host_machine_id) {
                 continue;
             }
 
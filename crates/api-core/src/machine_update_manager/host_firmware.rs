@@ -25,13 +25,14 @@ use async_trait::async_trait;
 use carbide_firmware::FirmwareConfig;
 use carbide_uuid::machine::MachineId;
 use db::{self, desired_firmware, host_firmware_config};
+use model::firmware_rollout::FirmwareRolloutTarget;
 use model::machine::ManagedHostStateSnapshot;
 use model::machine_update_module::HOST_FW_UPDATE_HEALTH_REPORT_SOURCE;
 use opentelemetry::metrics::Meter;
 use sqlx::PgConnection;
 use tokio::sync::Mutex;
 
-use super::machine_update_module::MachineUpdateModule;
+use super::machine_update_module::{MachineUpdateModule, UpdateSlots};
 use super::metrics::{FirmwareUpdatePhase, FirmwareUpdateProgress, FirmwareUpdateTarget};
 use crate::CarbideResult;
 use crate::cfg::file::CarbideConfig;
@@ -60,10 +61,14 @@ impl MachineUpdateModule for HostFirmwareUpdate {
         Ok(current_updating_machines.iter().map(|m| m.id).collect())
     }
 
+    fn rollout_target(&self) -> Option<FirmwareRolloutTarget> {
+        Some(FirmwareRolloutTarget::HostFirmware)
+    }
+
     async fn start_updates(
         &self,
         pool: &sqlx::Pool<sqlx::Postgres>,
-        available_updates: i32,
+        slots: &mut UpdateSlots,
         updating_host_machines: &HashSet<MachineId>,
         _snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
     ) -> CarbideResult<HashSet<MachineId>> {
@@ -83,17 +88,15 @@ impl MachineUpdateModule for HostFirmwareUpdate {
             }
         }
 
-        let machine_updates = self.check_for_updates(&mut txn, available_updates).await?;
+        let machine_updates = self
+            .check_for_updates(&mut txn, slots, updating_host_machines)
+            .await?;
         let mut updates_started = HashSet::default();
         self.metrics
             .pending_firmware_updates
             .store(machine_updates.len() as u64, Ordering::Relaxed);
 
         for machine_update in machine_updates.iter() {
-            if updating_host_machines.contains(machine_update) {
-                continue;
-            }
-
             db::host_machine_update::trigger_host_reprovisioning_request(
                 &mut txn,
                 "Automated",
@@ -219,13 +222,16 @@ impl HostFirmwareUpdate {
             .create_snapshot_with_overrides(host_firmware_configs))
     }
 
+    /// Returns the hosts to update now, claiming a slot for each. Hosts that
+    /// are already updating are skipped without using a slot.
     pub(super) async fn check_for_updates(
         &self,
         txn: &mut PgConnection,
-        mut available_updates: i32,
+        slots: &mut UpdateSlots,
+        updating_host_machines: &HashSet<MachineId>,
     ) -> CarbideResult<Vec<MachineId>> {
         let mut machines = vec![];
-        if available_updates == 0 {
+        if slots.is_exhausted() {
             return Ok(machines);
         };
         // find_upgrade_needed filters for just things that need upgrades
@@ -236,9 +242,12 @@ impl HostFirmwareUpdate {
         )
         .await?
         {
-            if available_updates == 0 {
+            if slots.is_exhausted() {
                 return Ok(machines);
             };
+            if updating_host_machines.contains(&update_needed.id) {
+                continue;
+            }
             if self
                 .config
                 .firmware_global
@@ -249,8 +258,9 @@ impl HostFirmwareUpdate {
                 // This machine is specifically disabled
                 break;
             }
-            available_updates -= 1;
-            machines.push(update_needed.id);
+            if slots.claim(&update_needed.id) {
+                machines.push(update_needed.id);
+            }
         }
         Ok(machines)
     }
@@ -20,9 +20,11 @@ use std::fmt;
 
 use async_trait::async_trait;
 use carbide_uuid::machine::MachineId;
+use model::firmware_rollout::FirmwareRolloutTarget;
 use model::machine::ManagedHostStateSnapshot;
 use sqlx::PgConnection;
 
+use super::rollout::RolloutSlots;
 use crate::CarbideResult;
 
 /// Used by [MachineUpdateManager](crate::machine_update_manager::MachineUpdateManager) to initiate
@@ -38,10 +40,17 @@ pub(crate) trait MachineUpdateModule: Send + Sync + fmt::Display {
         txn: &mut PgConnection,
     ) -> CarbideResult<HashSet<MachineId>>;
 
+    /// The firmware rollout target that gates this module, if any. Modules
+    /// without one are never held back by a rollout.
+    fn rollout_target(&self) -> Option<FirmwareRolloutTarget> {
+        None
+    }
+
+    /// Starts updates on at most as many hosts as `slots` lets it claim.
     async fn start_updates(
         &self,
         pool: &sqlx::Pool<sqlx::Postgres>,
-        available_updates: i32,
+        slots: &mut UpdateSlots,
         updating_host_machines: &HashSet<MachineId>,
         snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
     ) -> CarbideResult<HashSet<MachineId>>;
@@ -54,3 +63,52 @@ pub(crate) trait MachineUpdateModule: Send + Sync + fmt::Display {
         snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
     ) -> CarbideResult<()>;
 }
+
+/// The updates one module may start in one pass of the manager: the share of
+/// `max_concurrent_machine_updates` left over, narrowed by the module's live
+/// firmware rollout, if any.
+pub(crate) struct UpdateSlots {
+    available: i32,
+    rollout: Option<RolloutSlots>,
+}
+
+impl UpdateSlots {
+    pub(crate) fn new(available: i32) -> Self {
+        UpdateSlots {
+            available,
+            rollout: None,
+        }
+    }
+
+    pub(crate) fn with_rollout(self, rollout: RolloutSlots) -> Self {
+        UpdateSlots {
+            rollout: Some(rollout),
+            ..self
+        }
+    }
+
+    /// Whether no further host can be claimed.
+    pub(crate) fn is_exhausted(&self) -> bool {
+        self.available <= 0
+            || self
+                .rollout
+                .as_ref()
+                .is_some_and(|rollout| rollout.is_exhausted())
+    }
+
+    /// Takes a slot for `host_machine_id`. Returns false if the host has to
+    /// wait, either because no slots are left or because the rollout's rack
+    /// or NVLink domain caps exclude it.
+    pub(crate) fn claim(&mut self, host_machine_id: &MachineId) -> bool {
+        if self.available <= 0 {
+            return false;
+        }
+        if let Some(rollout) = self.rollout.as_mut()
+            && !rollout.claim(host_machine_id)
+        {
+            return false;
+        }
+        self.available -= 1;
+        true
+    }
+}
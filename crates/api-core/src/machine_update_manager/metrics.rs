@@ -165,6 +165,30 @@ pub(crate) struct FirmwareUpdateFailed {
     pub(crate) firmware_version: String,
 }
 
+/// A firmware rollout paused itself because a wave crossed its failure
+/// threshold. Nothing more of that target updates until an operator resumes
+/// or deletes the rollout, so any increase is worth a page.
+#[derive(carbide_instrument::Event)]
+#[event(
+    event_name = "firmware_rollout_paused",
+    metric_name = "carbide_firmware_rollouts_paused_total",
+    component = "nico-api",
+    log = warn,
+    metric = counter,
+    message = "Firmware rollout paused",
+    describe = "Number of firmware rollouts paused by their failure threshold, by update target"
+)]
+pub(crate) struct FirmwareRolloutPaused {
+    #[label]
+    pub(crate) target: FirmwareUpdateTarget,
+    /// The rollout's name.
+    #[context]
+    pub(crate) rollout: String,
+    /// How many hosts of which wave failed.
+    #[context]
+    pub(crate) reason: String,
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr as _;
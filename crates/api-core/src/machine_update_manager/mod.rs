@@ -19,6 +19,7 @@ pub(crate) mod dpu_nic_firmware_metrics;
 mod host_firmware;
 pub(crate) mod machine_update_module;
 pub(crate) mod metrics;
+pub(crate) mod rollout;
 
 use std::collections::{HashMap, HashSet};
 use std::io;
@@ -33,7 +34,7 @@ use carbide_uuid::machine::MachineId;
 use db::work_lock_manager::{AcquireLockError, WorkLockManagerHandle};
 use db::{DatabaseError, ObjectFilter, Transaction};
 use host_firmware::HostFirmwareUpdate;
-use machine_update_module::MachineUpdateModule;
+use machine_update_module::{MachineUpdateModule, UpdateSlots};
 use model::dpu_machine_update::DpuMachineUpdate;
 use model::machine::machine_search_config::MachineSearchConfig;
 use model::machine::{HostHealthConfig, LoadSnapshotOptions, ManagedHostStateSnapshot};
@@ -44,6 +45,7 @@ use tokio_util::sync::CancellationToken;
 
 use self::dpu_nic_firmware::DpuNicFirmwareUpdate;
 use self::metrics::MachineUpdateManagerMetrics;
+use self::rollout::RolloutGate;
 use crate::cfg::file::{CarbideConfig, MaxConcurrentUpdates};
 use crate::{CarbideError, CarbideResult};
 
@@ -52,6 +54,8 @@ use crate::{CarbideError, CarbideResult};
 /// 1. collect the number of outstanding updates from all modules.
 /// 2. if there are less than the max allowed updates each module will be told to start updates until
 ///    the number of updates reaches the maximum allowed.
+/// 3. a module with a live [firmware rollout](rollout) only starts updates inside the rollout's
+///    current wave, and none while the rollout is paused or soaking.
 ///
 /// Config from [CarbideConfig]:
 /// * `max_concurrent_machine_updates` the maximum number of updates allowed across all modules
@@ -222,17 +226,30 @@ impl MachineUpdateManager {
         let mut current_updating_machines: HashSet<MachineId> =
             MachineUpdateManager::get_updating_machines(&mut txn).await?;
 
+        let mut module_updating_machines = Vec::with_capacity(self.update_modules.len());
         for update_module in self.update_modules.iter() {
-            current_updating_machines = update_module
-                .get_updates_in_progress(&mut txn)
-                .await?
+            let in_progress = update_module.get_updates_in_progress(&mut txn).await?;
+            current_updating_machines = in_progress
                 .union(&current_updating_machines)
                 .copied()
                 .collect();
+            module_updating_machines.push(in_progress);
         }
 
         let snapshots = self.get_all_snapshots(&mut txn).await?;
 
+        let mut rollout_gates = Vec::with_capacity(self.update_modules.len());
+        for (update_module, in_progress) in
+            self.update_modules.iter().zip(&module_updating_machines)
+        {
+            rollout_gates.push(match update_module.rollout_target() {
+                Some(target) => {
+                    rollout::evaluate(&mut txn, target, in_progress, &snapshots).await?
+                }
+                None => RolloutGate::Free,
+            });
+        }
+
         txn.commit().await?;
 
         let (all_count, unhealthy_count) =
@@ -241,7 +258,7 @@ impl MachineUpdateManager {
             .max_concurrent_machine_updates
             .max_concurrent_updates(all_count, unhealthy_count)
             .unwrap_or(MachineUpdateManager::DEFAULT_MAX_CONCURRENT_MACHINE_UPDATES); // XXX
-        for update_module in self.update_modules.iter() {
+        for (update_module, rollout_gate) in self.update_modules.iter().zip(rollout_gates) {
             if (current_updating_machines.len() as i32) >= max_concurrent_updates {
                 break;
             }
@@ -250,11 +267,26 @@ impl MachineUpdateManager {
                 "Machine updates in progress",
             );
             let available_updates = max_concurrent_updates - current_updating_machines.len() as i32;
+            let (mut slots, open_wave) = match rollout_gate {
+                RolloutGate::Free => (UpdateSlots::new(available_updates), None),
+                RolloutGate::Hold => {
+                    tracing::debug!(%update_module, "Firmware rollout holds updates");
+                    continue;
+                }
+                RolloutGate::Open {
+                    rollout,
+                    in_flight,
+                    slots,
+                } => (
+                    UpdateSlots::new(available_updates).with_rollout(slots),
+                    Some((rollout, in_flight)),
+                ),
+            };
 
             let updates_started = update_module
                 .start_updates(
                     &self.database_connection,
-                    available_updates,
+                    &mut slots,
                     &current_updating_machines,
                     &snapshots,
                 )
@@ -264,6 +296,16 @@ impl MachineUpdateManager {
                 "Machine updates started",
             );
 
+            if let Some((rollout, in_flight)) = open_wave {
+                rollout::record_pass(
+                    &self.database_connection,
+                    &rollout,
+                    in_flight,
+                    &updates_started,
+                )
+                .await?;
+            }
+
             updates_started_count += updates_started.len();
 
             current_updating_machines = current_updating_machines
@@ -0,0 +1,538 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Gates a [`MachineUpdateModule`](super::machine_update_module::MachineUpdateModule)
+//! behind its live firmware rollout: hosts start wave by wave, each wave soaks
+//! before the next opens, and a wave with too many failures pauses the
+//! rollout.
+
+use std::collections::{HashMap, HashSet};
+
+use carbide_uuid::machine::MachineId;
+use carbide_uuid::nvlink::NvLinkDomainId;
+use carbide_uuid::rack::RackId;
+use chrono::{DateTime, Utc};
+use model::firmware_rollout::{
+    FirmwareRollout, FirmwareRolloutMachine, FirmwareRolloutMachineOutcome, FirmwareRolloutState,
+    FirmwareRolloutTarget,
+};
+use model::machine::{ManagedHostState, ManagedHostStateSnapshot};
+use sqlx::{PgConnection, PgPool};
+
+use super::metrics::{FirmwareRolloutPaused, FirmwareUpdateTarget};
+use crate::CarbideResult;
+
+/// What a rollout lets its module do this pass.
+pub(crate) enum RolloutGate {
+    /// No rollout is live for the module's target; it updates freely.
+    Free,
+    /// The rollout is paused or soaking; the module starts nothing.
+    Hold,
+    /// The module may start hosts within `slots`.
+    Open {
+        rollout: Box<FirmwareRollout>,
+        /// Hosts of the rollout still updating.
+        in_flight: usize,
+        slots: RolloutSlots,
+    },
+}
+
+/// Where a host sits, and whether its update has failed.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RolloutHost {
+    pub(crate) rack_id: Option<RackId>,
+    pub(crate) nvlink_domain_id: Option<NvLinkDomainId>,
+    pub(crate) failed: bool,
+}
+
+impl From<&ManagedHostStateSnapshot> for RolloutHost {
+    fn from(snapshot: &ManagedHostStateSnapshot) -> Self {
+        RolloutHost {
+            rack_id: snapshot.host_snapshot.rack_id.clone(),
+            nvlink_domain_id: snapshot
+                .host_snapshot
+                .status
+                .nvlink_info
+                .as_ref()
+                .map(|info| info.domain_uuid),
+            failed: matches!(snapshot.managed_state, ManagedHostState::Failed { .. })
+                || snapshot.managed_state.host_repro_retries_exhausted(),
+        }
+    }
+}
+
+/// The hosts an open wave may still start.
+pub(crate) struct RolloutSlots {
+    remaining: usize,
+    max_per_rack: Option<u32>,
+    max_per_nvlink_domain: Option<u32>,
+    hosts: HashMap<MachineId, RolloutHost>,
+    /// Hosts the rollout already started; none is started twice.
+    started: HashSet<MachineId>,
+    updating_per_rack: HashMap<RackId, u32>,
+    updating_per_nvlink_domain: HashMap<NvLinkDomainId, u32>,
+}
+
+impl RolloutSlots {
+    pub(crate) fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Takes a slot for `host_machine_id` if the wave has room and neither its
+    /// rack nor its NVLink domain is at its cap.
+    pub(crate) fn claim(&mut self, host_machine_id: &MachineId) -> bool {
+        if self.remaining == 0 || self.started.contains(host_machine_id) {
+            return false;
+        }
+        let host = self.hosts.get(host_machine_id).cloned().unwrap_or_default();
+        let at_cap = |count: Option<&u32>, cap: Option<u32>| {
+            cap.is_some_and(|cap| count.copied().unwrap_or(0) >= cap)
+        };
+        if let Some(rack_id) = &host.rack_id
+            && at_cap(self.updating_per_rack.get(rack_id), self.max_per_rack)
+        {
+            return false;
+        }
+        if let Some(domain_id) = &host.nvlink_domain_id
+            && at_cap(
+                self.updating_per_nvlink_domain.get(domain_id),
+                self.max_per_nvlink_domain,
+            )
+        {
+            return false;
+        }
+        self.count_updating(&host);
+        self.started.insert(*host_machine_id);
+        self.remaining -= 1;
+        true
+    }
+
+    fn count_updating(&mut self, host: &RolloutHost) {
+        if let Some(rack_id) = &host.rack_id {
+            *self.updating_per_rack.entry(rack_id.clone()).or_default() += 1;
+        }
+        if let Some(domain_id) = host.nvlink_domain_id {
+            *self
+                .updating_per_nvlink_domain
+                .entry(domain_id)
+                .or_default() += 1;
+        }
+    }
+}
+
+/// The result of [`advance`]: outcomes to record and what the module may do.
+pub(crate) struct RolloutStep {
+    pub(crate) outcomes: Vec<(MachineId, FirmwareRolloutMachineOutcome)>,
+    /// Whether `advance` changed the rollout's state, wave or timestamps.
+    pub(crate) changed: bool,
+    pub(crate) gate: RolloutGate,
+}
+
+/// Moves `rollout` forward given the hosts it started and the module's view
+/// of the fleet.
+///
+/// A started host has succeeded once the module no longer reports it in
+/// `in_progress`, and has failed once it lands in a failed state. A wave ends
+/// when nothing is in flight and it either reached its size or the module ran
+/// out of hosts to start (see [`record_pass`]); after `soak_time` the next
+/// wave opens, and after the last wave the rollout completes.
+pub(crate) fn advance(
+    rollout: &mut FirmwareRollout,
+    machines: &mut [FirmwareRolloutMachine],
+    in_progress: &HashSet<MachineId>,
+    hosts: HashMap<MachineId, RolloutHost>,
+    now: DateTime<Utc>,
+) -> RolloutStep {
+    let mut outcomes = vec![];
+    for machine in machines
+        .iter_mut()
+        .filter(|m| m.outcome == FirmwareRolloutMachineOutcome::InProgress)
+    {
+        let failed = hosts.get(&machine.machine_id).is_some_and(|h| h.failed);
+        let outcome = if failed {
+            FirmwareRolloutMachineOutcome::Failed
+        } else if !in_progress.contains(&machine.machine_id) {
+            FirmwareRolloutMachineOutcome::Succeeded
+        } else {
+            continue;
+        };
+        machine.outcome = outcome;
+        machine.finished_at = Some(now);
+        outcomes.push((machine.machine_id, outcome));
+    }
+
+    let mut step = RolloutStep {
+        outcomes,
+        changed: false,
+        gate: RolloutGate::Hold,
+    };
+    if rollout.state != FirmwareRolloutState::Active {
+        return step;
+    }
+
+    let wave_limit = rollout
+        .spec
+        .wave_limit(rollout.current_wave as usize, hosts.len());
+    let (wave_started, wave_failed) = machines
+        .iter()
+        .filter(|m| m.wave == rollout.current_wave && m.started_at >= rollout.wave_started_at)
+        .fold((0usize, 0usize), |(started, failed), m| {
+            let is_failed = m.outcome == FirmwareRolloutMachineOutcome::Failed;
+            (started + 1, failed + usize::from(is_failed))
+        });
+    // Measure failures against the hosts the wave plans to start, so an early
+    // failure in a large wave does not read as a 100% failure rate.
+    let earlier_waves = machines
+        .iter()
+        .filter(|m| m.wave < rollout.current_wave)
+        .count();
+    let wave_size = wave_limit.saturating_sub(earlier_waves).max(wave_started);
+    if wave_failed * 100 > usize::from(rollout.spec.max_failed_percent) * wave_size {
+        rollout.state = FirmwareRolloutState::Paused;
+        rollout.paused_reason = Some(format!(
+            "{wave_failed} of {wave_size} hosts failed in wave {}",
+            rollout.current_wave + 1
+        ));
+        step.changed = true;
+        return step;
+    }
+
+    let in_flight = machines
+        .iter()
+        .filter(|m| m.outcome == FirmwareRolloutMachineOutcome::InProgress)
+        .count();
+
+    if rollout.wave_completed_at.is_none() && in_flight == 0 && machines.len() >= wave_limit {
+        rollout.wave_completed_at = Some(now);
+        step.changed = true;
+    }
+    if let Some(completed_at) = rollout.wave_completed_at {
+        if now - completed_at
+            < chrono::TimeDelta::from_std(rollout.spec.soak_time).unwrap_or_default()
+        {
+            return step;
+        }
+        step.changed = true;
+        if rollout.current_wave as usize + 1 >= rollout.spec.waves.len() {
+            rollout.state = FirmwareRolloutState::Completed;
+            step.gate = RolloutGate::Free;
+            return step;
+        }
+        rollout.current_wave += 1;
+        rollout.wave_started_at = now;
+        rollout.wave_completed_at = None;
+    }
+
+    let wave_limit = rollout
+        .spec
+        .wave_limit(rollout.current_wave as usize, hosts.len());
+    let mut slots = RolloutSlots {
+        remaining: wave_limit.saturating_sub(machines.len()),
+        max_per_rack: rollout.spec.max_per_rack,
+        max_per_nvlink_domain: rollout.spec.max_per_nvlink_domain,
+        started: machines.iter().map(|m| m.machine_id).collect(),
+        updating_per_rack: HashMap::new(),
+        updating_per_nvlink_domain: HashMap::new(),
+        hosts: HashMap::new(),
+    };
+    for machine in machines
+        .iter()
+        .filter(|m| m.outcome == FirmwareRolloutMachineOutcome::InProgress)
+    {
+        if let Some(host) = hosts.get(&machine.machine_id) {
+            slots.count_updating(host);
+        }
+    }
+    slots.hosts = hosts;
+    step.gate = RolloutGate::Open {
+        rollout: Box::new(rollout.clone()),
+        in_flight,
+        slots,
+    };
+    step
+}
+
+/// Loads the live rollout of `target`, records the outcomes of its hosts and
+/// returns what the module may do this pass.
+pub(crate) async fn evaluate(
+    txn: &mut PgConnection,
+    target: FirmwareRolloutTarget,
+    in_progress: &HashSet<MachineId>,
+    snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
+) -> CarbideResult<RolloutGate> {
+    let Some(mut rollout) = db::firmware_rollout::find_live_for_update(txn, target).await? else {
+        return Ok(RolloutGate::Free);
+    };
+    let mut machines = db::firmware_rollout::find_machines(&mut *txn, &rollout.name).await?;
+    let hosts = snapshots
+        .iter()
+        .map(|(id, snapshot)| (*id, RolloutHost::from(snapshot)))
+        .collect();
+
+    let now = Utc::now();
+    let step = advance(&mut rollout, &mut machines, in_progress, hosts, now);
+    for (machine_id, outcome) in &step.outcomes {
+        db::firmware_rollout::record_outcome(txn, &rollout.name, machine_id, *outcome, now).await?;
+    }
+    if step.changed {
+        db::firmware_rollout::update_progress(txn, &rollout).await?;
+        if rollout.state == FirmwareRolloutState::Paused {
+            carbide_instrument::emit(FirmwareRolloutPaused {
+                target: target.into(),
+                rollout: rollout.name.clone(),
+                reason: rollout.paused_reason.clone().unwrap_or_default(),
+            });
+        }
+    }
+    Ok(step.gate)
+}
+
+/// Records the hosts a module started in an open wave. If it started none
+/// and none are in flight, the wave has no hosts left to give and ends here.
+pub(crate) async fn record_pass(
+    pool: &PgPool,
+    rollout: &FirmwareRollout,
+    in_flight: usize,
+    updates_started: &HashSet<MachineId>,
+) -> CarbideResult<()> {
+    let mut txn = db::Transaction::begin(pool).await?;
+    if !updates_started.is_empty() {
+        let machine_ids = updates_started.iter().copied().collect::<Vec<_>>();
+        db::firmware_rollout::record_started(
+            &mut txn,
+            &rollout.name,
+            rollout.current_wave,
+            &machine_ids,
+        )
+        .await?;
+    } else if in_flight == 0 {
+        db::firmware_rollout::complete_wave(&mut txn, &rollout.name, rollout.current_wave).await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+impl From<FirmwareRolloutTarget> for FirmwareUpdateTarget {
+    fn from(target: FirmwareRolloutTarget) -> Self {
+        match target {
+            FirmwareRolloutTarget::HostFirmware => FirmwareUpdateTarget::Host,
+            FirmwareRolloutTarget::DpuNicFirmware => FirmwareUpdateTarget::DpuNic,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use carbide_uuid::machine::{MachineIdSource, MachineType};
+    use model::firmware_rollout::{FirmwareRolloutSpec, RolloutWaveSize};
+
+    use super::*;
+
+    fn host(n: u8) -> MachineId {
+        MachineId::new(MachineIdSource::Tpm, [n; 32], MachineType::Host)
+    }
+
+    /// Hosts 1-2 in rack a, hosts 3-4 in rack b.
+    fn hosts() -> HashMap<MachineId, RolloutHost> {
+        (1..=4)
+            .map(|n| {
+                let rack = if n <= 2 { "a" } else { "b" };
+                (
+                    host(n),
+                    RolloutHost {
+                        rack_id: Some(RackId::from(rack)),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// A one-host canary, then everything, with a ten minute soak.
+    fn rollout(now: DateTime<Utc>) -> FirmwareRollout {
+        FirmwareRollout {
+            name: "bios".to_string(),
+            target: FirmwareRolloutTarget::HostFirmware,
+            spec: FirmwareRolloutSpec {
+                waves: vec![RolloutWaveSize::Count(1), RolloutWaveSize::Percent(100)],
+                max_per_rack: Some(1),
+                max_per_nvlink_domain: None,
+                soak_time: Duration::from_secs(600),
+                max_failed_percent: 0,
+            },
+            state: FirmwareRolloutState::Active,
+            current_wave: 0,
+            wave_started_at: now,
+            wave_completed_at: None,
+            paused_reason: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn started(n: u8, wave: i32, at: DateTime<Utc>) -> FirmwareRolloutMachine {
+        FirmwareRolloutMachine {
+            machine_id: host(n),
+            wave,
+            outcome: FirmwareRolloutMachineOutcome::InProgress,
+            started_at: at,
+            finished_at: None,
+        }
+    }
+
+    fn slots(gate: RolloutGate) -> RolloutSlots {
+        match gate {
+            RolloutGate::Open { slots, .. } => slots,
+            RolloutGate::Free => panic!("expected an open wave, got Free"),
+            RolloutGate::Hold => panic!("expected an open wave, got Hold"),
+        }
+    }
+
+    #[test]
+    fn canary_soaks_before_the_next_wave() {
+        let t0 = Utc::now();
+        let mut rollout = rollout(t0);
+
+        let step = advance(&mut rollout, &mut [], &HashSet::new(), hosts(), t0);
+        let mut canary = slots(step.gate);
+        assert!(canary.claim(&host(1)));
+        assert!(!canary.claim(&host(3)), "the canary wave has one host");
+
+        // The canary finished: the wave is done and soaks.
+        let mut machines = vec![started(1, 0, t0)];
+        let step = advance(&mut rollout, &mut machines, &HashSet::new(), hosts(), t0);
+        assert_eq!(
+            step.outcomes,
+            vec![(host(1), FirmwareRolloutMachineOutcome::Succeeded)]
+        );
+        assert!(matches!(step.gate, RolloutGate::Hold));
+        assert!(rollout.wave_completed_at.is_some());
+
+        let later = t0 + chrono::TimeDelta::minutes(11);
+        let step = advance(&mut rollout, &mut machines, &HashSet::new(), hosts(), later);
+        assert_eq!(rollout.current_wave, 1);
+        let mut rest = slots(step.gate);
+        assert!(!rest.claim(&host(1)), "hosts are not started twice");
+        assert!(rest.claim(&host(2)));
+        assert!(!rest.claim(&host(2)));
+        assert!(rest.claim(&host(3)));
+        assert!(!rest.claim(&host(4)), "rack b is at its cap");
+    }
+
+    #[test]
+    fn in_flight_hosts_count_against_rack_caps() {
+        let t0 = Utc::now();
+        let mut rollout = rollout(t0);
+        rollout.current_wave = 1;
+        let mut machines = vec![started(1, 1, t0)];
+        let in_progress = HashSet::from([host(1)]);
+
+        let step = advance(&mut rollout, &mut machines, &in_progress, hosts(), t0);
+        assert!(step.outcomes.is_empty());
+        let mut slots = slots(step.gate);
+        assert!(!slots.claim(&host(2)), "host 1 still updates in rack a");
+        assert!(slots.claim(&host(3)));
+    }
+
+    #[test]
+    fn failures_over_the_threshold_pause() {
+        let t0 = Utc::now();
+        let mut rollout = rollout(t0);
+        let mut machines = vec![started(1, 0, t0)];
+        let mut hosts = hosts();
+        hosts.get_mut(&host(1)).unwrap().failed = true;
+
+        let step = advance(
+            &mut rollout,
+            &mut machines,
+            &HashSet::from([host(1)]),
+            hosts,
+            t0,
+        );
+        assert_eq!(
+            step.outcomes,
+            vec![(host(1), FirmwareRolloutMachineOutcome::Failed)]
+        );
+        assert!(step.changed);
+        assert!(matches!(step.gate, RolloutGate::Hold));
+        assert_eq!(rollout.state, FirmwareRolloutState::Paused);
+        assert_eq!(
+            rollout.paused_reason.as_deref(),
+            Some("1 of 1 hosts failed in wave 1")
+        );
+    }
+
+    #[test]
+    fn an_early_failure_in_a_large_wave_does_not_pause() {
+        let t0 = Utc::now();
+        let mut rollout = rollout(t0);
+        rollout.current_wave = 1;
+        rollout.spec.max_failed_percent = 50;
+        rollout.spec.max_per_rack = None;
+        let mut machines = vec![started(1, 0, t0), started(2, 1, t0)];
+        machines[0].outcome = FirmwareRolloutMachineOutcome::Succeeded;
+        let mut hosts = hosts();
+        hosts.get_mut(&host(2)).unwrap().failed = true;
+
+        // The first of the wave's three hosts failed: 1 of 1 started, but
+        // only 1 of 3 planned.
+        let step = advance(
+            &mut rollout,
+            &mut machines,
+            &HashSet::from([host(2)]),
+            hosts,
+            t0,
+        );
+        assert_eq!(
+            step.outcomes,
+            vec![(host(2), FirmwareRolloutMachineOutcome::Failed)]
+        );
+        assert_eq!(rollout.state, FirmwareRolloutState::Active);
+        let mut slots = slots(step.gate);
+        assert!(slots.claim(&host(3)));
+        assert!(slots.claim(&host(4)));
+    }
+
+    #[test]
+    fn failures_before_a_resume_do_not_count() {
+        let t0 = Utc::now();
+        let mut rollout = rollout(t0);
+        let mut machines = vec![started(1, 0, t0)];
+        machines[0].outcome = FirmwareRolloutMachineOutcome::Failed;
+        rollout.spec.waves = vec![RolloutWaveSize::Count(2)];
+        rollout.wave_started_at = t0 + chrono::TimeDelta::seconds(1);
+
+        let step = advance(&mut rollout, &mut machines, &HashSet::new(), hosts(), t0);
+        assert_eq!(rollout.state, FirmwareRolloutState::Active);
+        assert!(slots(step.gate).claim(&host(3)));
+    }
+
+    #[test]
+    fn last_wave_completes_the_rollout() {
+        let t0 = Utc::now();
+        let mut rollout = rollout(t0);
+        rollout.current_wave = 1;
+        rollout.wave_completed_at = Some(t0);
+        let later = t0 + chrono::TimeDelta::minutes(11);
+
+        let step = advance(&mut rollout, &mut [], &HashSet::new(), hosts(), later);
+        assert!(matches!(step.gate, RolloutGate::Free));
+        assert_eq!(rollout.state, FirmwareRolloutState::Completed);
+    }
+}
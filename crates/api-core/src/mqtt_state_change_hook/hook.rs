@@ -148,6 +148,7 @@ mod tests {
             object_id: id,
             previous_state: None,
             new_state: state,
+            state_version: config_version::ConfigVersion::initial(),
             timestamp: chrono::Utc::now(),
         }
     }
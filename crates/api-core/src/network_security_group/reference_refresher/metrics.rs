@@ -0,0 +1,100 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Instant;
+
+use ::carbide_utils::metrics::SharedMetricsHolder;
+use opentelemetry::metrics::Meter;
+
+/// NetworkSecurityGroupReferenceMetrics stores what one
+/// `NetworkSecurityGroupReferenceRefresher` pass found.
+#[derive(Clone, Debug)]
+pub(super) struct NetworkSecurityGroupReferenceMetrics {
+    // When we finished recording the metrics.
+    pub(super) recording_finished_at: std::time::Instant,
+    // NSGs whose version was bumped for a membership change.
+    pub(super) bumped: u64,
+    // NSGs whose references no longer fit in the expanded rule limit
+    // and that are held at their last valid expansion.
+    pub(super) over_limit: u64,
+}
+
+impl NetworkSecurityGroupReferenceMetrics {
+    pub(super) fn new() -> Self {
+        Self {
+            recording_finished_at: Instant::now(),
+            bumped: 0,
+            over_limit: 0,
+        }
+    }
+}
+
+fn hydrate_meter(
+    meter: Meter,
+    shared_metrics: SharedMetricsHolder<NetworkSecurityGroupReferenceMetrics>,
+) {
+    let u64_gauges: [(
+        &'static str,
+        &'static str,
+        fn(&NetworkSecurityGroupReferenceMetrics) -> u64,
+    ); 2] = [
+        (
+            "carbide_network_security_group_reference_bumps_count",
+            "Number of NSGs whose version was bumped for a membership change in the last refresh.",
+            |metrics| metrics.bumped,
+        ),
+        (
+            "carbide_network_security_group_references_over_limit_count",
+            "Number of NSGs whose references exceed the expanded rule limit and are held at their last valid expansion.",
+            |metrics| metrics.over_limit,
+        ),
+    ];
+
+    for (name, description, value) in u64_gauges {
+        let metrics = shared_metrics.clone();
+        meter
+            .u64_observable_gauge(name)
+            .with_description(description)
+            .with_callback(move |observer| {
+                metrics.if_available(|metrics, attrs| {
+                    observer.observe(value(metrics), attrs);
+                });
+            })
+            .build();
+    }
+}
+
+/// Stores Metric data shared between the reference refresher and the OpenTelemetry background task
+pub(super) struct MetricHolder {
+    last_iteration_metrics: SharedMetricsHolder<NetworkSecurityGroupReferenceMetrics>,
+}
+
+impl MetricHolder {
+    pub(super) fn new(meter: Meter, hold_period: std::time::Duration) -> Self {
+        let last_iteration_metrics = SharedMetricsHolder::with_hold_period(hold_period);
+        hydrate_meter(meter, last_iteration_metrics.clone());
+        Self {
+            last_iteration_metrics,
+        }
+    }
+
+    /// Updates the most recent metrics
+    pub(super) fn update_metrics(&self, mut metrics: NetworkSecurityGroupReferenceMetrics) {
+        metrics.recording_finished_at = Instant::now();
+        self.last_iteration_metrics.update(metrics)
+    }
+}
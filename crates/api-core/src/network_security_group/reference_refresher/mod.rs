@@ -0,0 +1,265 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Re-resolves security-group, VPC and label-selector references in NSG
+//! rules and bumps the version of every NSG whose references now expand to
+//! a different set of prefixes.  DPUs pick up the new version like any
+//! other NSG change, so the existing propagation status tracks the rollout
+//! of membership changes without any extra bookkeeping.
+//!
+//! A membership change that would push an NSG past the expanded rule limit
+//! is not published: DPUs keep the last expansion that fit, and the NSG is
+//! checked again on every pass until its references fit again.
+
+mod metrics;
+
+use std::collections::BTreeMap;
+
+use carbide_utils::managed_loop::{self, LoopManager};
+use db::network_security_group;
+use db::work_lock_manager::{AcquireLockError, WorkLockManagerHandle};
+use model::network_security_group::ResolvedNetworkSecurityGroupReferences;
+use opentelemetry::metrics::Meter;
+use sha2::{Digest, Sha256};
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use crate::cfg::file::NetworkSecurityGroupConfig;
+use crate::handlers::network_security_group::validate_expanded_rule_set;
+use crate::{CarbideError, CarbideResult};
+
+const REFRESH_WORK_KEY: &str = "network_security_group_reference_refresher::iteration";
+
+/// `NetworkSecurityGroupReferenceRefresher` keeps the version of NSGs with
+/// object references in step with the membership of the referenced objects.
+pub(crate) struct NetworkSecurityGroupReferenceRefresher {
+    database_connection: sqlx::PgPool,
+    config: NetworkSecurityGroupConfig,
+    work_lock_manager_handle: WorkLockManagerHandle,
+    metric_holder: metrics::MetricHolder,
+}
+
+impl NetworkSecurityGroupReferenceRefresher {
+    pub(crate) fn new(
+        database_connection: sqlx::PgPool,
+        config: NetworkSecurityGroupConfig,
+        work_lock_manager_handle: WorkLockManagerHandle,
+        meter: Meter,
+    ) -> Self {
+        let hold_period = config
+            .reference_refresh_interval
+            .saturating_add(std::time::Duration::from_secs(60));
+        NetworkSecurityGroupReferenceRefresher {
+            database_connection,
+            config,
+            work_lock_manager_handle,
+            metric_holder: metrics::MetricHolder::new(meter, hold_period),
+        }
+    }
+
+    /// Spawn the refresher's background loop into `join_set`.
+    pub(crate) fn start(
+        self,
+        join_set: &mut JoinSet<()>,
+        cancel_token: CancellationToken,
+    ) -> std::io::Result<()> {
+        join_set
+            .build_task()
+            .name("network_security_group_reference_refresher")
+            .spawn(async move { self.run(cancel_token).await })?;
+
+        Ok(())
+    }
+
+    async fn run(&self, cancel_token: CancellationToken) {
+        loop {
+            let result = self.run_single_iteration().await;
+            managed_loop::record_iteration(
+                LoopManager::NetworkSecurityGroupReferenceRefresher,
+                &result,
+            );
+
+            tokio::select! {
+                _ = tokio::time::sleep(self.config.reference_refresh_interval) => {},
+                _ = cancel_token.cancelled() => {
+                    tracing::info!("NetworkSecurityGroupReferenceRefresher stop was requested");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Runs one refresh pass and returns the number of NSGs
+    /// whose version was bumped.
+    pub(crate) async fn run_single_iteration(&self) -> CarbideResult<usize> {
+        let _work_lock = match self
+            .work_lock_manager_handle
+            .try_acquire_lock(REFRESH_WORK_KEY.into())
+            .await
+        {
+            Ok(lock) => lock,
+            Err(AcquireLockError::WorkAlreadyLocked(_)) => {
+                tracing::debug!(
+                    lock = REFRESH_WORK_KEY,
+                    "Skipping NSG reference refresh; another instance holds the lock"
+                );
+                return Ok(0);
+            }
+            Err(e) => {
+                return Err(CarbideError::Internal {
+                    message: format!(
+                        "unable to acquire NSG reference refresh lock `{REFRESH_WORK_KEY}`: {e}"
+                    ),
+                });
+            }
+        };
+
+        let mut txn = db::Transaction::begin(&self.database_connection).await?;
+
+        let digests = network_security_group::find_references_digests(&mut txn).await?;
+        let ids = digests.iter().map(|(id, _)| id.clone()).collect::<Vec<_>>();
+        let network_security_groups =
+            network_security_group::find_by_ids(&mut txn, &ids, None, true).await?;
+
+        let mut iteration_metrics = metrics::NetworkSecurityGroupReferenceMetrics::new();
+
+        for nsg in network_security_groups {
+            let previous = digests
+                .iter()
+                .find(|(id, _)| id == &nsg.id)
+                .and_then(|(_, digest)| digest.as_deref());
+
+            let resolved = network_security_group::resolve_references(
+                &mut txn,
+                &nsg.tenant_organization_id,
+                &nsg.references(),
+            )
+            .await?;
+            let digest = references_digest(&resolved);
+
+            if previous == Some(digest.as_str()) {
+                continue;
+            }
+
+            if let Err(e) = validate_expanded_rule_set(
+                &nsg.rules,
+                &resolved,
+                self.config.max_network_security_group_size as usize,
+            ) {
+                // Membership changes can't be rejected the way a rule change
+                // can.  Neither the digest nor the expansion is recorded, so
+                // DPUs keep the last expansion that fit and the next pass
+                // checks again.
+                tracing::warn!(
+                    network_security_group_id = %nsg.id,
+                    error = %e,
+                    "NetworkSecurityGroup references exceed the expanded rule limit; keeping the last valid expansion"
+                );
+                iteration_metrics.over_limit += 1;
+                continue;
+            }
+
+            // The rules were created or changed since the last pass, and
+            // that change already bumped the version, so only the digest
+            // needs to be recorded.
+            let bump_version = previous.is_some();
+
+            if network_security_group::record_references_digest(
+                &mut txn,
+                &nsg.id,
+                nsg.version,
+                &digest,
+                &resolved,
+                bump_version,
+            )
+            .await?
+                && bump_version
+            {
+                tracing::info!(
+                    network_security_group_id = %nsg.id,
+                    "NetworkSecurityGroup references changed; bumped version"
+                );
+                iteration_metrics.bumped += 1;
+            }
+        }
+
+        txn.commit().await?;
+
+        let bumped = iteration_metrics.bumped as usize;
+        self.metric_holder.update_metrics(iteration_metrics);
+
+        Ok(bumped)
+    }
+}
+
+/// Returns a stable digest of a set of resolved references
+/// that does not depend on map iteration order.
+fn references_digest(resolved: &ResolvedNetworkSecurityGroupReferences) -> String {
+    let ordered = resolved
+        .iter()
+        .map(|(net, prefixes)| (net.to_string(), prefixes))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut hasher = Sha256::new();
+    for (net, prefixes) in ordered {
+        hasher.update(net.as_bytes());
+        for prefix in prefixes {
+            hasher.update(b" ");
+            hasher.update(prefix.to_string().as_bytes());
+        }
+        hasher.update(b"\n");
+    }
+
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use model::network_security_group::NetworkSecurityGroupRuleNet;
+
+    use super::*;
+
+    #[test]
+    fn references_digest_tracks_membership() {
+        let selector = NetworkSecurityGroupRuleNet::LabelSelector(BTreeMap::from([(
+            "role".to_string(),
+            "web".to_string(),
+        )]));
+        let vpc = NetworkSecurityGroupRuleNet::VpcId(
+            "1ec6e5fa-98c7-4c5a-a9a3-4a1b7c4b0f2d".parse().unwrap(),
+        );
+
+        let mut resolved = ResolvedNetworkSecurityGroupReferences::new();
+        resolved.insert(selector.clone(), vec!["10.0.0.1/32".parse().unwrap()]);
+        resolved.insert(vpc.clone(), vec![]);
+
+        let mut reordered = ResolvedNetworkSecurityGroupReferences::new();
+        reordered.insert(vpc, vec![]);
+        reordered.insert(selector.clone(), vec!["10.0.0.1/32".parse().unwrap()]);
+
+        assert_eq!(references_digest(&resolved), references_digest(&reordered));
+
+        resolved.insert(
+            selector,
+            vec![
+                "10.0.0.1/32".parse().unwrap(),
+                "10.0.0.2/32".parse().unwrap(),
+            ],
+        );
+        assert_ne!(references_digest(&resolved), references_digest(&reordered));
+    }
+}
@@ -0,0 +1,575 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Evaluates a single flow against the ACLs a DPU renders on the host port
+//! of an instance.  The input is the same data that is sent to the DPU agent,
+//! and the evaluation follows the NVUE templates of the agent: the ACLs are
+//! walked in name order (deny prefixes, policy overrides, NSG) and the first
+//! matching rule decides the flow.
+
+use std::net::IpAddr;
+
+use ::rpc::forge as rpc;
+use carbide_network::virtualization::VpcVirtualizationType;
+use ipnetwork::IpNetwork;
+
+use rpc::NetworkSecurityGroupFlowDecision as Decision;
+use rpc::NetworkSecurityGroupFlowStage as Stage;
+use rpc::NetworkSecurityGroupRuleAction as Action;
+use rpc::NetworkSecurityGroupRuleDirection as Direction;
+use rpc::NetworkSecurityGroupRuleProtocol as Protocol;
+
+/// A single packet of a new flow.
+#[derive(Clone, Debug)]
+pub(crate) struct Flow {
+    pub src_ip: IpAddr,
+    pub dst_ip: IpAddr,
+    pub protocol: Protocol,
+    pub src_port: Option<u32>,
+    pub dst_port: Option<u32>,
+}
+
+/// The parts of the network config of an instance's DPU that decide
+/// the fate of a flow, in the form they are sent to the DPU agent.
+pub(crate) struct RenderedAcls<'a> {
+    pub network_virtualization_type: VpcVirtualizationType,
+    pub deny_prefixes: &'a [String],
+    pub vpc_prefixes: &'a [String],
+    pub policy_overrides: &'a [rpc::ResolvedNetworkSecurityGroupRule],
+    pub network_security_group: Option<&'a rpc::FlatInterfaceNetworkSecurityGroupConfig>,
+    pub stateful_acls_enabled: bool,
+}
+
+impl RenderedAcls<'_> {
+    /// Mirrors the template conditions for connection tracking.  ETV
+    /// tracks connections whenever stateful ACLs are enabled for the site,
+    /// FNN additionally requires the NSG to ask for stateful egress.
+    /// Neither renders connection tracking for IPv6.
+    fn tracks_connections(
+        &self,
+        nsg: &rpc::FlatInterfaceNetworkSecurityGroupConfig,
+        ipv6: bool,
+    ) -> bool {
+        let nsg_enabled = match self.network_virtualization_type {
+            VpcVirtualizationType::Fnn => nsg.stateful_egress,
+            _ => true,
+        };
+
+        self.stateful_acls_enabled && nsg_enabled && !ipv6
+    }
+}
+
+/// Evaluates `flow` on the DPU of an instance.  `direction` is from the
+/// perspective of the instance, so egress means the instance is the
+/// source of the flow.
+pub(crate) fn evaluate(
+    acls: &RenderedAcls,
+    direction: Direction,
+    flow: &Flow,
+) -> (Decision, Vec<rpc::NetworkSecurityGroupFlowStep>) {
+    let ipv6 = flow.dst_ip.is_ipv6();
+    let mut steps = vec![];
+
+    // p0000_deny_prefixes only filters traffic leaving the instance.
+    if direction == Direction::NsgRuleDirectionEgress {
+        let deny_prefixes = prefixes_of_family(acls.deny_prefixes, ipv6);
+
+        if !deny_prefixes.is_empty() {
+            // Without an NSG, ETV lets traffic to the VPC's own prefixes
+            // through before the deny list is applied.
+            let vpc_prefixes = match (
+                acls.network_virtualization_type,
+                acls.network_security_group,
+            ) {
+                (VpcVirtualizationType::Fnn, _) | (_, Some(_)) => vec![],
+                _ => prefixes_of_family(acls.vpc_prefixes, ipv6),
+            };
+
+            if let Some(prefix) = vpc_prefixes.iter().find(|p| p.contains(flow.dst_ip)) {
+                steps.push(prefix_step(
+                    Action::NsgRuleActionPermit,
+                    prefix,
+                    "destination is within a prefix of the VPC",
+                ));
+                return (Decision::NsgFlowDecisionPermit, steps);
+            }
+
+            if let Some(prefix) = deny_prefixes.iter().find(|p| p.contains(flow.dst_ip)) {
+                steps.push(prefix_step(
+                    Action::NsgRuleActionDeny,
+                    prefix,
+                    "destination is within a site deny prefix",
+                ));
+                return (Decision::NsgFlowDecisionDeny, steps);
+            }
+
+            steps.push(unmatched_step(
+                Stage::NsgFlowStageDenyPrefixes,
+                "destination is outside of all site deny prefixes",
+            ));
+        }
+    }
+
+    let overrides = ordered_rules(acls.policy_overrides, direction, ipv6);
+    if !overrides.is_empty() {
+        if let Some(rule) = overrides.into_iter().find(|r| rule_matches(r, flow)) {
+            let (decision, step) = rule_step(Stage::NsgFlowStagePolicyOverride, rule, false);
+            steps.push(step);
+            return (decision, steps);
+        }
+
+        steps.push(unmatched_step(
+            Stage::NsgFlowStagePolicyOverride,
+            "no policy override rule matches",
+        ));
+    }
+
+    let Some(nsg) = acls.network_security_group else {
+        steps.push(rpc::NetworkSecurityGroupFlowStep {
+            stage: Stage::NsgFlowStageDefault.into(),
+            matched: true,
+            action: Action::NsgRuleActionPermit.into(),
+            details: "no network security group applies to the instance".to_string(),
+            ..Default::default()
+        });
+        return (Decision::NsgFlowDecisionPermit, steps);
+    };
+
+    let tracks_connections = acls.tracks_connections(nsg, ipv6);
+
+    if let Some(rule) = ordered_rules(&nsg.rules, direction, ipv6)
+        .into_iter()
+        .find(|r| rule_matches(r, flow))
+    {
+        let stateful = tracks_connections
+            && direction == Direction::NsgRuleDirectionEgress
+            && rule.rule.as_ref().is_some_and(can_be_stateful);
+        let (decision, step) = rule_step(Stage::NsgFlowStageNetworkSecurityGroup, rule, stateful);
+        steps.push(step);
+        return (decision, steps);
+    }
+
+    steps.push(unmatched_step(
+        Stage::NsgFlowStageNetworkSecurityGroup,
+        "no rule of the network security group matches",
+    ));
+
+    if tracks_connections && direction == Direction::NsgRuleDirectionIngress {
+        steps.push(unmatched_step(
+            Stage::NsgFlowStageStatefulReturn,
+            "replies to connections opened by stateful egress rules are permitted, \
+             but a new flow is not",
+        ));
+    }
+
+    steps.push(rpc::NetworkSecurityGroupFlowStep {
+        stage: Stage::NsgFlowStageDefault.into(),
+        matched: true,
+        action: Action::NsgRuleActionDeny.into(),
+        details: "traffic not permitted by the network security group is denied".to_string(),
+        ..Default::default()
+    });
+
+    (Decision::NsgFlowDecisionDeny, steps)
+}
+
+/// Returns the rules of one direction and IP version ordered the
+/// same way the DPU agent orders them.  The sort is stable, so
+/// rules with equal priority keep the order they were defined in.
+fn ordered_rules(
+    rules: &[rpc::ResolvedNetworkSecurityGroupRule],
+    direction: Direction,
+    ipv6: bool,
+) -> Vec<&rpc::ResolvedNetworkSecurityGroupRule> {
+    let mut rules = rules
+        .iter()
+        .filter(|r| {
+            r.rule
+                .as_ref()
+                .is_some_and(|a| a.direction() == direction && a.ipv6 == ipv6)
+        })
+        .collect::<Vec<_>>();
+
+    rules.sort_by_key(|r| r.rule.as_ref().map(|a| a.priority).unwrap_or_default());
+
+    rules
+}
+
+fn rule_matches(resolved: &rpc::ResolvedNetworkSecurityGroupRule, flow: &Flow) -> bool {
+    let Some(rule) = resolved.rule.as_ref() else {
+        return false;
+    };
+
+    // The agent only renders port matches for complete ranges.
+    let port_matches = |start: Option<u32>, end: Option<u32>, port: Option<u32>| match (start, end)
+    {
+        (Some(start), Some(end)) => port.is_some_and(|p| (start..=end).contains(&p)),
+        _ => true,
+    };
+
+    (rule.protocol() == Protocol::NsgRuleProtoAny || rule.protocol() == flow.protocol)
+        && port_matches(rule.src_port_start, rule.src_port_end, flow.src_port)
+        && port_matches(rule.dst_port_start, rule.dst_port_end, flow.dst_port)
+        && prefixes_contain(&resolved.src_prefixes, flow.src_ip)
+        && prefixes_contain(&resolved.dst_prefixes, flow.dst_ip)
+}
+
+/// Connection tracking is only rendered for egress rules that specify
+/// TCP/UDP, a destination port and no source port.
+fn can_be_stateful(rule: &rpc::NetworkSecurityGroupRuleAttributes) -> bool {
+    matches!(
+        rule.protocol(),
+        Protocol::NsgRuleProtoTcp | Protocol::NsgRuleProtoUdp
+    ) && rule.dst_port_start.is_some()
+        && rule.src_port_start.is_none()
+}
+
+fn prefixes_of_family(prefixes: &[String], ipv6: bool) -> Vec<IpNetwork> {
+    prefixes
+        .iter()
+        .filter_map(|p| p.parse::<IpNetwork>().ok())
+        .filter(|p| p.is_ipv6() == ipv6)
+        .collect()
+}
+
+fn prefixes_contain(prefixes: &[String], ip: IpAddr) -> bool {
+    prefixes
+        .iter()
+        .filter_map(|p| p.parse::<IpNetwork>().ok())
+        .any(|p| p.contains(ip))
+}
+
+fn prefix_step(
+    action: Action,
+    prefix: &IpNetwork,
+    details: &str,
+) -> rpc::NetworkSecurityGroupFlowStep {
+    rpc::NetworkSecurityGroupFlowStep {
+        stage: Stage::NsgFlowStageDenyPrefixes.into(),
+        matched: true,
+        action: action.into(),
+        prefix: Some(prefix.to_string()),
+        details: details.to_string(),
+        ..Default::default()
+    }
+}
+
+fn unmatched_step(stage: Stage, details: &str) -> rpc::NetworkSecurityGroupFlowStep {
+    rpc::NetworkSecurityGroupFlowStep {
+        stage: stage.into(),
+        matched: false,
+        details: details.to_string(),
+        ..Default::default()
+    }
+}
+
+fn rule_step(
+    stage: Stage,
+    rule: &rpc::ResolvedNetworkSecurityGroupRule,
+    stateful: bool,
+) -> (Decision, rpc::NetworkSecurityGroupFlowStep) {
+    let attributes = rule.rule.clone().unwrap_or_default();
+    let action = attributes.action();
+    let decision = match action {
+        Action::NsgRuleActionPermit => Decision::NsgFlowDecisionPermit,
+        _ => Decision::NsgFlowDecisionDeny,
+    };
+
+    let step = rpc::NetworkSecurityGroupFlowStep {
+        stage: stage.into(),
+        matched: true,
+        action: action.into(),
+        rule: Some(rule.clone()),
+        stateful,
+        details: format!(
+            "rule {} with priority {} matches",
+            attributes.id.as_deref().unwrap_or("<unnamed>"),
+            attributes.priority
+        ),
+        ..Default::default()
+    };
+
+    (decision, step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(
+        id: &str,
+        direction: Direction,
+        priority: u32,
+        protocol: Protocol,
+        dst_ports: Option<(u32, u32)>,
+        action: Action,
+    ) -> rpc::ResolvedNetworkSecurityGroupRule {
+        rpc::ResolvedNetworkSecurityGroupRule {
+            rule: Some(rpc::NetworkSecurityGroupRuleAttributes {
+                id: Some(id.to_string()),
+                direction: direction.into(),
+                ipv6: false,
+                src_port_start: None,
+                src_port_end: None,
+                dst_port_start: dst_ports.map(|(s, _)| s),
+                dst_port_end: dst_ports.map(|(_, e)| e),
+                protocol: protocol.into(),
+                action: action.into(),
+                priority,
+                source_net: None,
+                destination_net: None,
+            }),
+            src_prefixes: vec!["0.0.0.0/0".to_string()],
+            dst_prefixes: vec!["0.0.0.0/0".to_string()],
+        }
+    }
+
+    fn with_prefixes(
+        mut rule: rpc::ResolvedNetworkSecurityGroupRule,
+        src_prefix: &str,
+        dst_prefix: &str,
+    ) -> rpc::ResolvedNetworkSecurityGroupRule {
+        rule.src_prefixes = vec![src_prefix.to_string()];
+        rule.dst_prefixes = vec![dst_prefix.to_string()];
+        rule
+    }
+
+    fn nsg(
+        stateful_egress: bool,
+        rules: Vec<rpc::ResolvedNetworkSecurityGroupRule>,
+    ) -> rpc::FlatInterfaceNetworkSecurityGroupConfig {
+        rpc::FlatInterfaceNetworkSecurityGroupConfig {
+            id: "nsg".to_string(),
+            version: "V1-T1".to_string(),
+            source: rpc::NetworkSecurityGroupSource::NsgSourceInstance.into(),
+            stateful_egress,
+            rules,
+        }
+    }
+
+    fn acls<'a>(
+        network_security_group: Option<&'a rpc::FlatInterfaceNetworkSecurityGroupConfig>,
+        policy_overrides: &'a [rpc::ResolvedNetworkSecurityGroupRule],
+        deny_prefixes: &'a [String],
+        vpc_prefixes: &'a [String],
+    ) -> RenderedAcls<'a> {
+        RenderedAcls {
+            network_virtualization_type: VpcVirtualizationType::EthernetVirtualizer,
+            deny_prefixes,
+            vpc_prefixes,
+            policy_overrides,
+            network_security_group,
+            stateful_acls_enabled: true,
+        }
+    }
+
+    fn tcp_flow(src_ip: &str, dst_ip: &str, dst_port: u32) -> Flow {
+        Flow {
+            src_ip: src_ip.parse().unwrap(),
+            dst_ip: dst_ip.parse().unwrap(),
+            protocol: Protocol::NsgRuleProtoTcp,
+            src_port: Some(40000),
+            dst_port: Some(dst_port),
+        }
+    }
+
+    fn stages(steps: &[rpc::NetworkSecurityGroupFlowStep]) -> Vec<(Stage, bool)> {
+        steps.iter().map(|s| (s.stage(), s.matched)).collect()
+    }
+
+    #[test]
+    fn test_lowest_priority_rule_wins() {
+        let nsg = nsg(
+            false,
+            vec![
+                rule(
+                    "allow-web",
+                    Direction::NsgRuleDirectionIngress,
+                    200,
+                    Protocol::NsgRuleProtoTcp,
+                    Some((443, 443)),
+                    Action::NsgRuleActionPermit,
+                ),
+                with_prefixes(
+                    rule(
+                        "block-bad-net",
+                        Direction::NsgRuleDirectionIngress,
+                        100,
+                        Protocol::NsgRuleProtoAny,
+                        None,
+                        Action::NsgRuleActionDeny,
+                    ),
+                    "198.51.100.0/24",
+                    "0.0.0.0/0",
+                ),
+            ],
+        );
+        let acls = acls(Some(&nsg), &[], &[], &[]);
+
+        let (decision, steps) = evaluate(
+            &acls,
+            Direction::NsgRuleDirectionIngress,
+            &tcp_flow("198.51.100.7", "10.0.0.5", 443),
+        );
+        assert_eq!(decision, Decision::NsgFlowDecisionDeny);
+        assert_eq!(
+            steps[0].rule.as_ref().unwrap().rule.as_ref().unwrap().id,
+            Some("block-bad-net".to_string())
+        );
+
+        let (decision, steps) = evaluate(
+            &acls,
+            Direction::NsgRuleDirectionIngress,
+            &tcp_flow("203.0.113.7", "10.0.0.5", 443),
+        );
+        assert_eq!(decision, Decision::NsgFlowDecisionPermit);
+        assert_eq!(
+            steps[0].rule.as_ref().unwrap().rule.as_ref().unwrap().id,
+            Some("allow-web".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unmatched_flow_hits_default_deny() {
+        let nsg = nsg(
+            false,
+            vec![rule(
+                "allow-web",
+                Direction::NsgRuleDirectionIngress,
+                100,
+                Protocol::NsgRuleProtoTcp,
+                Some((443, 443)),
+                Action::NsgRuleActionPermit,
+            )],
+        );
+        let acls = acls(Some(&nsg), &[], &[], &[]);
+
+        let (decision, steps) = evaluate(
+            &acls,
+            Direction::NsgRuleDirectionIngress,
+            &tcp_flow("203.0.113.7", "10.0.0.5", 22),
+        );
+        assert_eq!(decision, Decision::NsgFlowDecisionDeny);
+        assert_eq!(
+            stages(&steps),
+            vec![
+                (Stage::NsgFlowStageNetworkSecurityGroup, false),
+                (Stage::NsgFlowStageStatefulReturn, false),
+                (Stage::NsgFlowStageDefault, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_network_security_group_permits() {
+        let acls = acls(None, &[], &[], &[]);
+
+        let (decision, steps) = evaluate(
+            &acls,
+            Direction::NsgRuleDirectionIngress,
+            &tcp_flow("203.0.113.7", "10.0.0.5", 22),
+        );
+        assert_eq!(decision, Decision::NsgFlowDecisionPermit);
+        assert_eq!(stages(&steps), vec![(Stage::NsgFlowStageDefault, true)]);
+    }
+
+    #[test]
+    fn test_policy_override_takes_precedence() {
+        let overrides = vec![with_prefixes(
+            rule(
+                "site-dns",
+                Direction::NsgRuleDirectionEgress,
+                1,
+                Protocol::NsgRuleProtoTcp,
+                Some((53, 53)),
+                Action::NsgRuleActionPermit,
+            ),
+            "0.0.0.0/0",
+            "192.0.2.53/32",
+        )];
+        let nsg = nsg(false, vec![]);
+        let acls = acls(Some(&nsg), &overrides, &[], &[]);
+
+        let (decision, steps) = evaluate(
+            &acls,
+            Direction::NsgRuleDirectionEgress,
+            &tcp_flow("10.0.0.5", "192.0.2.53", 53),
+        );
+        assert_eq!(decision, Decision::NsgFlowDecisionPermit);
+        assert_eq!(
+            stages(&steps),
+            vec![(Stage::NsgFlowStagePolicyOverride, true)]
+        );
+    }
+
+    #[test]
+    fn test_deny_prefixes_and_vpc_exemption() {
+        let deny_prefixes = vec!["10.0.0.0/8".to_string()];
+        let vpc_prefixes = vec!["10.1.0.0/16".to_string()];
+        let flow = tcp_flow("10.1.0.5", "10.1.0.6", 22);
+
+        // Without an NSG, traffic inside the VPC is exempt.
+        let acls_without_nsg = acls(None, &[], &deny_prefixes, &vpc_prefixes);
+        let (decision, steps) =
+            evaluate(&acls_without_nsg, Direction::NsgRuleDirectionEgress, &flow);
+        assert_eq!(decision, Decision::NsgFlowDecisionPermit);
+        assert_eq!(steps[0].prefix.as_deref(), Some("10.1.0.0/16"));
+
+        // With an NSG the deny list applies to the VPC as well.
+        let nsg = nsg(false, vec![]);
+        let acls_with_nsg = acls(Some(&nsg), &[], &deny_prefixes, &vpc_prefixes);
+        let (decision, steps) = evaluate(&acls_with_nsg, Direction::NsgRuleDirectionEgress, &flow);
+        assert_eq!(decision, Decision::NsgFlowDecisionDeny);
+        assert_eq!(steps[0].prefix.as_deref(), Some("10.0.0.0/8"));
+
+        // Deny prefixes never filter ingress traffic.
+        let (_, steps) = evaluate(&acls_with_nsg, Direction::NsgRuleDirectionIngress, &flow);
+        assert!(
+            steps
+                .iter()
+                .all(|s| s.stage() != Stage::NsgFlowStageDenyPrefixes)
+        );
+    }
+
+    #[test]
+    fn test_stateful_egress_depends_on_virtualization_type() {
+        let rules = vec![rule(
+            "allow-https-out",
+            Direction::NsgRuleDirectionEgress,
+            100,
+            Protocol::NsgRuleProtoTcp,
+            Some((443, 443)),
+            Action::NsgRuleActionPermit,
+        )];
+        let nsg = nsg(false, rules);
+        let flow = tcp_flow("10.0.0.5", "203.0.113.7", 443);
+
+        let mut acls = acls(Some(&nsg), &[], &[], &[]);
+        let (_, steps) = evaluate(&acls, Direction::NsgRuleDirectionEgress, &flow);
+        assert!(steps[0].stateful);
+
+        // FNN also needs stateful_egress on the NSG.
+        acls.network_virtualization_type = VpcVirtualizationType::Fnn;
+        let (_, steps) = evaluate(&acls, Direction::NsgRuleDirectionEgress, &flow);
+        assert!(!steps[0].stateful);
+
+        acls.stateful_acls_enabled = false;
+        acls.network_virtualization_type = VpcVirtualizationType::EthernetVirtualizer;
+        let (_, steps) = evaluate(&acls, Direction::NsgRuleDirectionEgress, &flow);
+        assert!(!steps[0].stateful);
+    }
+}
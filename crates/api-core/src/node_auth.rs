@@ -222,13 +222,12 @@ impl NodeJwtValidator {
         .map_err(|e| NodeAuthError::Verifier(e.to_string()))
     }
 
-    fn validate(&self, token: &str) -> Result<String, RejectReason> {
-        let header = decode_header(token).map_err(RejectReason::Malformed)?;
-        if header.alg != Algorithm::ES256 {
-            return Err(RejectReason::Algorithm(header.alg));
-        }
-
-        // 1. The certificate chain must verify against the trusted roots.
+    /// Verifies the `x5c` chain in `header` against the trusted roots and
+    /// returns the leaf.
+    fn verify_chain(
+        &self,
+        header: &jsonwebtoken::Header,
+    ) -> Result<CertificateDer<'static>, RejectReason> {
         let chain = header
             .x5c_der()
             .map_err(RejectReason::Malformed)?
@@ -247,6 +246,28 @@ impl NodeJwtValidator {
         cert_verifier
             .verify_client_cert(&leaf, &intermediates, UnixTime::now())
             .map_err(RejectReason::Chain)?;
+        Ok(leaf)
+    }
+
+    /// Checks that a document signed with
+    /// [`NodeJwtMinter::sign_document`](rpc::node_jwt::NodeJwtMinter::sign_document)
+    /// carries a certificate chain the client-certificate PKI trusts.
+    /// `rpc::node_jwt::verify_document` checks everything else.
+    pub(crate) fn verify_document_chain(&self, signed: &str) -> Result<(), String> {
+        let header = decode_header(signed).map_err(|e| e.to_string())?;
+        self.verify_chain(&header)
+            .map(|_| ())
+            .map_err(|reason| reason.to_string())
+    }
+
+    fn validate(&self, token: &str) -> Result<String, RejectReason> {
+        let header = decode_header(token).map_err(RejectReason::Malformed)?;
+        if header.alg != Algorithm::ES256 {
+            return Err(RejectReason::Algorithm(header.alg));
+        }
+
+        // 1. The certificate chain must verify against the trusted roots.
+        let leaf = self.verify_chain(&header)?;
 
         // 2. The token must be signed by the verified leaf's key.
         let (_, x509) = X509Certificate::from_der(leaf.as_ref())
@@ -0,0 +1,191 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The arithmetic of a rack power budget, kept free of I/O so the rules for
+//! PSU loss and host priority can be tested on their own.
+
+use model::rack_type::RackPowerBudget;
+
+/// One power shelf PSU as seen in the last telemetry read.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct PowerSupplyReading {
+    /// `PowerCapacityWatts` as reported by the PSU, if it reports one.
+    pub(super) capacity_watts: Option<f64>,
+    /// Whether the PSU is enabled and reports `OK` health.
+    pub(super) healthy: bool,
+}
+
+/// The order in which hosts receive power above their minimum limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum HostPriority {
+    /// Hosts running a tenant instance.
+    Assigned,
+    /// Every other host, Ready ones included.
+    Other,
+}
+
+/// The limits handed out for one rack.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct Allocation {
+    /// One limit per host, in the order the hosts were passed in.
+    pub(super) host_limits_watts: Vec<u32>,
+    pub(super) allocated_watts: u32,
+    pub(super) headroom_watts: i64,
+}
+
+/// Watts the rack can hand out to hosts: the capacity of its healthy PSUs,
+/// bounded by the rack limit, minus the profile's reservation. PSUs that do
+/// not report a capacity are counted at the profile's rated capacity.
+pub(super) fn rack_capacity_watts(
+    budget: &RackPowerBudget,
+    power_supplies: &[PowerSupplyReading],
+) -> u32 {
+    let supply_watts: u64 = power_supplies
+        .iter()
+        .filter(|psu| psu.healthy)
+        .map(|psu| match psu.capacity_watts {
+            Some(watts) if watts > 0.0 => watts as u64,
+            _ => u64::from(budget.psu_capacity_watts),
+        })
+        .sum();
+    let supply_watts = u32::try_from(supply_watts).unwrap_or(u32::MAX);
+    let supply_watts = match budget.rack_limit_watts {
+        Some(limit) => supply_watts.min(limit),
+        None => supply_watts,
+    };
+
+    supply_watts.saturating_sub(budget.reserved_watts)
+}
+
+/// Splits `capacity_watts` between hosts. Every host gets the profile's
+/// minimum limit even when the capacity cannot cover it, which shows up as
+/// negative headroom. What is left is shared evenly, up to the maximum
+/// limit, first among Assigned hosts and then among the others.
+pub(super) fn allocate(
+    budget: &RackPowerBudget,
+    capacity_watts: u32,
+    priorities: &[HostPriority],
+) -> Allocation {
+    let host_min = budget.host_min_watts;
+    let host_max = budget.host_max_watts.max(host_min);
+    let mut host_limits_watts = vec![host_min; priorities.len()];
+
+    let floor_watts = u64::from(host_min) * priorities.len() as u64;
+    let mut remaining = u64::from(capacity_watts).saturating_sub(floor_watts);
+
+    for tier in [HostPriority::Assigned, HostPriority::Other] {
+        let members: Vec<usize> = priorities
+            .iter()
+            .enumerate()
+            .filter(|(_, priority)| **priority == tier)
+            .map(|(idx, _)| idx)
+            .collect();
+        if members.is_empty() || remaining == 0 {
+            continue;
+        }
+
+        let share = (remaining / members.len() as u64).min(u64::from(host_max - host_min));
+        for idx in members.iter() {
+            host_limits_watts[*idx] += share as u32;
+        }
+        remaining -= share * members.len() as u64;
+    }
+
+    let allocated_watts: u64 = host_limits_watts.iter().copied().map(u64::from).sum();
+    Allocation {
+        host_limits_watts,
+        allocated_watts: u32::try_from(allocated_watts).unwrap_or(u32::MAX),
+        headroom_watts: i64::from(capacity_watts) - allocated_watts as i64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::value_scenarios;
+
+    use super::HostPriority::{Assigned, Other};
+    use super::*;
+
+    fn budget() -> RackPowerBudget {
+        RackPowerBudget {
+            psu_capacity_watts: 5_500,
+            rack_limit_watts: None,
+            reserved_watts: 1_000,
+            host_min_watts: 1_000,
+            host_max_watts: 4_000,
+        }
+    }
+
+    fn psu(capacity_watts: Option<f64>, healthy: bool) -> PowerSupplyReading {
+        PowerSupplyReading {
+            capacity_watts,
+            healthy,
+        }
+    }
+
+    #[test]
+    fn capacity_counts_healthy_supplies_only() {
+        value_scenarios!(run = |(budget, supplies): (RackPowerBudget, Vec<PowerSupplyReading>)| {
+            rack_capacity_watts(&budget, &supplies)
+        };
+            "reported capacity wins over the rated one" {
+                (budget(), vec![psu(Some(3_000.0), true), psu(Some(3_000.0), true)]) => 5_000,
+            }
+
+            "missing capacity falls back to the rating" {
+                (budget(), vec![psu(None, true), psu(Some(0.0), true)]) => 10_000,
+            }
+
+            "failed supplies tighten the budget" {
+                (budget(), vec![psu(None, true), psu(None, false)]) => 4_500,
+                (budget(), vec![psu(None, false)]) => 0,
+            }
+
+            "rack limit bounds the supplies" {
+                (
+                    RackPowerBudget { rack_limit_watts: Some(8_000), ..budget() },
+                    vec![psu(None, true), psu(None, true)],
+                ) => 7_000,
+            }
+        );
+    }
+
+    #[test]
+    fn allocation_prefers_assigned_hosts() {
+        value_scenarios!(run = |(capacity, priorities): (u32, Vec<HostPriority>)| {
+            let allocation = allocate(&budget(), capacity, &priorities);
+            (allocation.host_limits_watts, allocation.headroom_watts)
+        };
+            "everyone reaches the maximum" {
+                (20_000, vec![Assigned, Other]) => (vec![4_000, 4_000], 12_000),
+            }
+
+            "assigned hosts are raised first" {
+                (8_000, vec![Other, Assigned, Other]) => (vec![1_500, 4_000, 1_500], 1_000),
+                (4_000, vec![Other, Assigned, Other]) => (vec![1_000, 2_000, 1_000], 0),
+            }
+
+            "minimum limits may exceed the capacity" {
+                (2_500, vec![Assigned, Other, Other]) => (vec![1_000, 1_000, 1_000], -500),
+            }
+
+            "no hosts leaves all capacity as headroom" {
+                (3_000, vec![]) => (vec![], 3_000),
+            }
+        );
+    }
+}
@@ -0,0 +1,127 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use ::carbide_utils::metrics::SharedMetricsHolder;
+use carbide_uuid::rack::RackId;
+use model::rack::RackPowerBudgetStatus;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::Meter;
+
+/// RackPowerBudgetMetrics stores the budgets computed in one
+/// `RackPowerBudgetManager` pass, keyed by rack.
+#[derive(Clone, Debug)]
+pub(super) struct RackPowerBudgetMetrics {
+    // When we finished recording the metrics.
+    pub(super) recording_finished_at: std::time::Instant,
+    // The budget of every rack whose profile defines one.
+    pub(super) racks: HashMap<RackId, RackPowerBudgetStatus>,
+}
+
+impl RackPowerBudgetMetrics {
+    pub(super) fn new() -> Self {
+        Self {
+            recording_finished_at: Instant::now(),
+            racks: HashMap::new(),
+        }
+    }
+}
+
+fn hydrate_meter(meter: Meter, shared_metrics: SharedMetricsHolder<RackPowerBudgetMetrics>) {
+    let u64_gauges: [(
+        &'static str,
+        &'static str,
+        fn(&RackPowerBudgetStatus) -> u64,
+    ); 3] = [
+        (
+            "carbide_rack_power_capacity_watts",
+            "Watts the power shelves of a rack can deliver to its hosts.",
+            |status| u64::from(status.capacity_watts),
+        ),
+        (
+            "carbide_rack_power_allocated_watts",
+            "Sum of the power limits assigned to the hosts of a rack.",
+            |status| u64::from(status.allocated_watts),
+        ),
+        (
+            "carbide_rack_power_healthy_power_supplies",
+            "Number of enabled and healthy power shelf PSUs in a rack.",
+            |status| u64::from(status.healthy_power_supplies),
+        ),
+    ];
+
+    for (name, description, value) in u64_gauges {
+        let metrics = shared_metrics.clone();
+        meter
+            .u64_observable_gauge(name)
+            .with_description(description)
+            .with_callback(move |observer| {
+                metrics.if_available(|metrics, attrs| {
+                    for (rack_id, status) in metrics.racks.iter() {
+                        observer.observe(
+                            value(status),
+                            &[attrs, &[KeyValue::new("rack_id", rack_id.to_string())]].concat(),
+                        );
+                    }
+                });
+            })
+            .build();
+    }
+
+    {
+        let metrics = shared_metrics;
+        meter
+            .i64_observable_gauge("carbide_rack_power_headroom_watts")
+            .with_description(
+                "Watts of a rack's capacity not assigned to any host; negative when the hosts' minimum limits exceed it.",
+            )
+            .with_callback(move |observer| {
+                metrics.if_available(|metrics, attrs| {
+                    for (rack_id, status) in metrics.racks.iter() {
+                        observer.observe(
+                            status.headroom_watts,
+                            &[attrs, &[KeyValue::new("rack_id", rack_id.to_string())]].concat(),
+                        );
+                    }
+                });
+            })
+            .build();
+    }
+}
+
+/// Stores Metric data shared between the budget manager and the OpenTelemetry background task
+pub(super) struct MetricHolder {
+    last_iteration_metrics: SharedMetricsHolder<RackPowerBudgetMetrics>,
+}
+
+impl MetricHolder {
+    pub(super) fn new(meter: Meter, hold_period: std::time::Duration) -> Self {
+        let last_iteration_metrics = SharedMetricsHolder::with_hold_period(hold_period);
+        hydrate_meter(meter, last_iteration_metrics.clone());
+        Self {
+            last_iteration_metrics,
+        }
+    }
+
+    /// Updates the most recent metrics
+    pub(super) fn update_metrics(&self, mut metrics: RackPowerBudgetMetrics) {
+        metrics.recording_finished_at = Instant::now();
+        self.last_iteration_metrics.update(metrics)
+    }
+}
@@ -0,0 +1,395 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Keeps the hosts of each rack within the power its power shelves can
+//! deliver. Every pass reads the PSUs of the rack's power shelves, derives
+//! the rack's capacity from them and the rack profile's `power_budget`, and
+//! sets a Redfish `PowerLimit` on every host, Assigned hosts first. A lost
+//! PSU lowers the capacity, and the next pass tightens the limits to match.
+//! The outcome is stored on the rack, so the API and metrics can report the
+//! headroom that is left.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use carbide_redfish::nv_redfish::NvRedfishClientPool;
+use carbide_secrets::credentials::CredentialManager;
+use carbide_utils::managed_loop::{self, LoopManager};
+use carbide_uuid::machine::MachineId;
+use db::work_lock_manager::{AcquireLockError, WorkLockManagerHandle};
+use db::{ObjectColumnFilter, ObjectFilter};
+use futures::stream::{self, StreamExt};
+use model::DeletedFilter;
+use model::bmc_info::BmcInfo;
+use model::machine::ManagedHostState;
+use model::machine::machine_search_config::MachineSearchConfig;
+use model::power_shelf::PowerShelfSearchFilter;
+use model::rack::{HostPowerLimit, Rack, RackPowerBudgetStatus};
+use model::rack_type::{RackPowerBudget, RackProfileConfig};
+use opentelemetry::metrics::Meter;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use crate::cfg::file::RackPowerBudgetConfig;
+use crate::{CarbideError, CarbideResult};
+
+mod budget;
+mod metrics;
+mod redfish;
+
+use budget::{HostPriority, PowerSupplyReading};
+
+const BUDGET_WORK_KEY: &str = "rack_power_budget_manager::iteration";
+
+/// A host of a rack, as far as the budget is concerned.
+struct BudgetHost {
+    machine_id: MachineId,
+    priority: HostPriority,
+    bmc_info: BmcInfo,
+}
+
+/// `RackPowerBudgetManager` assigns Redfish power limits to hosts so that
+/// every rack stays within its power budget.
+pub(crate) struct RackPowerBudgetManager {
+    database_connection: sqlx::PgPool,
+    config: RackPowerBudgetConfig,
+    rack_profiles: RackProfileConfig,
+    redfish_pool: Arc<NvRedfishClientPool>,
+    credential_manager: Arc<dyn CredentialManager>,
+    work_lock_manager_handle: WorkLockManagerHandle,
+    metric_holder: metrics::MetricHolder,
+}
+
+impl RackPowerBudgetManager {
+    pub(crate) fn new(
+        database_connection: sqlx::PgPool,
+        config: RackPowerBudgetConfig,
+        rack_profiles: RackProfileConfig,
+        redfish_pool: Arc<NvRedfishClientPool>,
+        credential_manager: Arc<dyn CredentialManager>,
+        work_lock_manager_handle: WorkLockManagerHandle,
+        meter: Meter,
+    ) -> Self {
+        let hold_period = config
+            .run_interval
+            .saturating_add(std::time::Duration::from_secs(60));
+        RackPowerBudgetManager {
+            database_connection,
+            config,
+            rack_profiles,
+            redfish_pool,
+            credential_manager,
+            work_lock_manager_handle,
+            metric_holder: metrics::MetricHolder::new(meter, hold_period),
+        }
+    }
+
+    /// Spawn the manager's background loop into `join_set`.
+    pub(crate) fn start(
+        self,
+        join_set: &mut JoinSet<()>,
+        cancel_token: CancellationToken,
+    ) -> std::io::Result<()> {
+        if self.config.enabled {
+            join_set
+                .build_task()
+                .name("rack_power_budget_manager")
+                .spawn(async move { self.run(cancel_token).await })?;
+        }
+
+        Ok(())
+    }
+
+    async fn run(&self, cancel_token: CancellationToken) {
+        loop {
+            let result = self.run_single_iteration().await;
+            managed_loop::record_iteration(LoopManager::RackPowerBudgetManager, &result);
+
+            tokio::select! {
+                _ = tokio::time::sleep(self.config.run_interval) => {},
+                _ = cancel_token.cancelled() => {
+                    tracing::info!("RackPowerBudgetManager stop was requested");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Runs one capping pass over all racks and returns the number of racks
+    /// whose budget was recomputed.
+    pub(crate) async fn run_single_iteration(&self) -> CarbideResult<usize> {
+        let _work_lock = match self
+            .work_lock_manager_handle
+            .try_acquire_lock(BUDGET_WORK_KEY.into())
+            .await
+        {
+            Ok(lock) => lock,
+            Err(AcquireLockError::WorkAlreadyLocked(_)) => {
+                tracing::debug!(
+                    lock = BUDGET_WORK_KEY,
+                    "Skipping rack power budget pass; another instance holds the lock"
+                );
+                return Ok(0);
+            }
+            Err(e) => {
+                return Err(CarbideError::Internal {
+                    message: format!(
+                        "unable to acquire rack power budget lock `{BUDGET_WORK_KEY}`: {e}"
+                    ),
+                });
+            }
+        };
+
+        let mut txn = db::Transaction::begin(&self.database_connection).await?;
+        let racks =
+            db::rack::find_by(&mut txn, ObjectColumnFilter::<db::rack::IdColumn>::All).await?;
+        txn.commit().await?;
+
+        let mut iteration_metrics = metrics::RackPowerBudgetMetrics::new();
+        for rack in racks {
+            if rack.deleted.is_some() {
+                continue;
+            }
+            let Some(budget) = rack
+                .rack_profile_id
+                .as_ref()
+                .and_then(|profile_id| self.rack_profiles.get(profile_id.as_str()))
+                .and_then(|profile| profile.power_budget.as_ref())
+            else {
+                continue;
+            };
+
+            match self.enforce_rack_budget(&rack, budget).await {
+                Ok(status) => {
+                    iteration_metrics.racks.insert(rack.id.clone(), status);
+                }
+                Err(err) => {
+                    tracing::warn!(rack_id = %rack.id, error = %err, "Rack power budget pass failed");
+                }
+            }
+        }
+
+        let budgeted_racks = iteration_metrics.racks.len();
+        self.metric_holder.update_metrics(iteration_metrics);
+        Ok(budgeted_racks)
+    }
+
+    async fn enforce_rack_budget(
+        &self,
+        rack: &Rack,
+        budget: &RackPowerBudget,
+    ) -> CarbideResult<RackPowerBudgetStatus> {
+        let (shelf_bmcs, hosts) = self.load_rack_members(rack).await?;
+
+        let power_supplies = stream::iter(shelf_bmcs)
+            .map(|bmc_info| async move {
+                match self.read_power_supplies(&bmc_info).await {
+                    Ok(readings) => readings,
+                    Err(err) => {
+                        // An unreachable shelf is counted as delivering
+                        // nothing, so its loss tightens the budget.
+                        tracing::warn!(
+                            rack_id = %rack.id,
+                            bmc_ip = ?bmc_info.ip,
+                            error = %err,
+                            "Unable to read power shelf PSUs"
+                        );
+                        Vec::new()
+                    }
+                }
+            })
+            .buffer_unordered(self.config.concurrency.max(1))
+            .concat()
+            .await;
+
+        let capacity_watts = budget::rack_capacity_watts(budget, &power_supplies);
+        let priorities = hosts.iter().map(|host| host.priority).collect::<Vec<_>>();
+        let allocation = budget::allocate(budget, capacity_watts, &priorities);
+
+        let applied = rack
+            .power_budget
+            .as_ref()
+            .map(|status| {
+                status
+                    .host_power_limits
+                    .iter()
+                    .map(|limit| (limit.machine_id, limit))
+                    .collect::<HashMap<_, _>>()
+            })
+            .unwrap_or_default();
+        let host_power_limits = self
+            .apply_host_limits(rack, &hosts, &allocation.host_limits_watts, &applied)
+            .await;
+
+        let status = RackPowerBudgetStatus {
+            capacity_watts,
+            allocated_watts: allocation.allocated_watts,
+            headroom_watts: allocation.headroom_watts,
+            healthy_power_supplies: power_supplies.iter().filter(|psu| psu.healthy).count() as u32,
+            total_power_supplies: power_supplies.len() as u32,
+            host_power_limits,
+            updated_at: chrono::Utc::now(),
+        };
+
+        let mut txn = db::Transaction::begin(&self.database_connection).await?;
+        db::rack::update_power_budget(&mut txn, &rack.id, Some(&status)).await?;
+        txn.commit().await?;
+
+        Ok(status)
+    }
+
+    /// Returns the BMCs of the rack's power shelves and its hosts, sorted by
+    /// machine id so that limits are handed out in a stable order.
+    async fn load_rack_members(
+        &self,
+        rack: &Rack,
+    ) -> CarbideResult<(Vec<BmcInfo>, Vec<BudgetHost>)> {
+        let mut txn = db::Transaction::begin(&self.database_connection).await?;
+        let power_shelf_ids = db::power_shelf::find_ids(
+            &mut txn,
+            PowerShelfSearchFilter {
+                rack_id: Some(rack.id.clone()),
+                deleted: DeletedFilter::Exclude,
+                ..Default::default()
+            },
+        )
+        .await?;
+        let shelf_bmcs = db::power_shelf::find_by(
+            &mut txn,
+            ObjectColumnFilter::List(db::power_shelf::IdColumn, &power_shelf_ids),
+        )
+        .await?
+        .into_iter()
+        .filter_map(|power_shelf| power_shelf.bmc_info)
+        .collect();
+
+        let mut hosts = db::machine::find(
+            &mut txn,
+            ObjectFilter::All,
+            MachineSearchConfig {
+                rack_id: Some(rack.id.clone()),
+                ..Default::default()
+            },
+        )
+        .await?
+        .into_iter()
+        .filter(|machine| !machine.is_dpu())
+        .map(|machine| BudgetHost {
+            machine_id: machine.id,
+            priority: match machine.current_state() {
+                ManagedHostState::Assigned { .. } => HostPriority::Assigned,
+                _ => HostPriority::Other,
+            },
+            bmc_info: machine.status.bmc_info.clone(),
+        })
+        .collect::<Vec<_>>();
+        txn.commit().await?;
+
+        hosts.sort_by_key(|host| host.machine_id);
+        Ok((shelf_bmcs, hosts))
+    }
+
+    async fn read_power_supplies(
+        &self,
+        bmc_info: &BmcInfo,
+    ) -> CarbideResult<Vec<PowerSupplyReading>> {
+        let bmc = redfish::connect(
+            &self.redfish_pool,
+            self.credential_manager.as_ref(),
+            bmc_info,
+        )
+        .await?;
+        redfish::power_supplies(bmc).await
+    }
+
+    /// Sets the limits that differ from the ones applied in the last pass,
+    /// and those applied longer than `reassert_interval` ago, since a BMC
+    /// reset may have dropped them. Limits are lowered before any is raised
+    /// so the rack never overshoots in between. Returns the limits now in
+    /// effect; hosts whose update failed are left out, so the next pass
+    /// retries them.
+    async fn apply_host_limits(
+        &self,
+        rack: &Rack,
+        hosts: &[BudgetHost],
+        limits_watts: &[u32],
+        applied: &HashMap<MachineId, &HostPowerLimit>,
+    ) -> Vec<HostPowerLimit> {
+        let reassert_before = chrono::Utc::now()
+            - chrono::TimeDelta::from_std(self.config.reassert_interval)
+                .unwrap_or(chrono::TimeDelta::MAX);
+        let mut in_effect = Vec::with_capacity(hosts.len());
+        let mut decreases = Vec::new();
+        let mut increases = Vec::new();
+        for (host, limit_watts) in hosts.iter().zip(limits_watts.iter().copied()) {
+            match applied.get(&host.machine_id) {
+                Some(previous)
+                    if previous.limit_watts == limit_watts
+                        && previous.applied_at > reassert_before =>
+                {
+                    in_effect.push((*previous).clone())
+                }
+                Some(previous) if previous.limit_watts < limit_watts => {
+                    increases.push((host, limit_watts))
+                }
+                // Re-asserting an unchanged limit cannot overshoot either.
+                _ => decreases.push((host, limit_watts)),
+            }
+        }
+
+        for changes in [decreases, increases] {
+            let updated = stream::iter(changes)
+                .map(|(host, limit_watts)| async move {
+                    let result = async {
+                        let bmc = redfish::connect(
+                            &self.redfish_pool,
+                            self.credential_manager.as_ref(),
+                            &host.bmc_info,
+                        )
+                        .await?;
+                        redfish::set_power_limit(bmc, limit_watts).await
+                    }
+                    .await;
+                    match result {
+                        Ok(()) => Some(HostPowerLimit {
+                            machine_id: host.machine_id,
+                            limit_watts,
+                            applied_at: chrono::Utc::now(),
+                        }),
+                        Err(err) => {
+                            tracing::warn!(
+                                rack_id = %rack.id,
+                                machine_id = %host.machine_id,
+                                limit_watts,
+                                error = %err,
+                                "Unable to set host power limit"
+                            );
+                            None
+                        }
+                    }
+                })
+                .buffer_unordered(self.config.concurrency.max(1))
+                .filter_map(std::future::ready)
+                .collect::<Vec<_>>()
+                .await;
+            in_effect.extend(updated);
+        }
+
+        in_effect.sort_by_key(|limit| limit.machine_id);
+        in_effect
+    }
+}
@@ -0,0 +1,243 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The two Redfish operations the budget manager needs: reading the PSUs of
+//! a power shelf and writing `PowerLimit` on a host.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use carbide_redfish::nv_redfish::{NvRedfishClientPool, RedfishBmc};
+use carbide_secrets::credentials::{
+    BmcCredentialType, CredentialKey, CredentialManager, Credentials,
+};
+use mac_address::MacAddress;
+use model::bmc_info::BmcInfo;
+use nv_redfish::ServiceRoot;
+use nv_redfish::bmc_http::BmcCredentials;
+use nv_redfish::core::{Bmc, EntityTypeRef, ODataETag, ODataId};
+use nv_redfish::resource::{Health, State};
+use serde::Deserialize;
+
+use super::budget::PowerSupplyReading;
+use crate::{CarbideError, CarbideResult};
+
+/// Opens a Redfish connection to the BMC described by `bmc_info`, using the
+/// BMC's root credentials.
+pub(super) async fn connect(
+    redfish_pool: &NvRedfishClientPool,
+    credential_manager: &dyn CredentialManager,
+    bmc_info: &BmcInfo,
+) -> CarbideResult<Arc<RedfishBmc>> {
+    let (Some(ip), Some(mac)) = (bmc_info.ip, bmc_info.mac) else {
+        return Err(CarbideError::internal(
+            "BMC address or MAC address is unknown".to_string(),
+        ));
+    };
+    let bmc_address = SocketAddr::new(ip, bmc_info.port.unwrap_or(443));
+    let Credentials::UsernamePassword { username, password } =
+        root_credentials(credential_manager, mac).await?;
+
+    redfish_pool
+        .create_bmc(bmc_address, BmcCredentials::new(username, password), false)
+        .map_err(|err| CarbideError::internal(format!("{bmc_address}: {err}")))
+}
+
+async fn root_credentials(
+    credential_manager: &dyn CredentialManager,
+    bmc_mac_address: MacAddress,
+) -> CarbideResult<Credentials> {
+    credential_manager
+        .get_credentials(&CredentialKey::BmcCredentials {
+            credential_type: BmcCredentialType::BmcRoot { bmc_mac_address },
+        })
+        .await
+        .map_err(|err| CarbideError::internal(format!("{err}")))?
+        .ok_or_else(|| {
+            CarbideError::internal(format!(
+                "no root credentials stored for BMC {bmc_mac_address}"
+            ))
+        })
+}
+
+/// Reads every PSU below every chassis of a power shelf BMC.
+pub(super) async fn power_supplies<B: Bmc>(bmc: Arc<B>) -> CarbideResult<Vec<PowerSupplyReading>> {
+    let service_root = ServiceRoot::new(bmc).await.map_err(redfish_error)?;
+    let Some(chassis_collection) = service_root.chassis().await.map_err(redfish_error)? else {
+        return Ok(Vec::new());
+    };
+
+    let mut readings = Vec::new();
+    for chassis in chassis_collection.members().await.map_err(redfish_error)? {
+        for power_supply in chassis.power_supplies().await.map_err(redfish_error)? {
+            let raw = power_supply.raw();
+            let status = raw.status.as_ref();
+            let enabled = status.and_then(|status| status.state.flatten()) == Some(State::Enabled);
+            let health_ok = status.and_then(|status| status.health.flatten()) == Some(Health::Ok);
+            readings.push(PowerSupplyReading {
+                capacity_watts: raw.power_capacity_watts.flatten(),
+                healthy: enabled && health_ok,
+            });
+        }
+    }
+
+    Ok(readings)
+}
+
+/// Sets `PowerLimit` on the chassis that the host's ComputerSystem links to
+/// and reads it back, since some BMCs accept the PATCH without applying it.
+/// Hosts expose further chassis (GPUs, DPUs) that do not cap the host, so
+/// those are never tried. The first linked chassis that reports the limit
+/// afterwards wins.
+pub(super) async fn set_power_limit<B: Bmc>(bmc: Arc<B>, limit_watts: u32) -> CarbideResult<()> {
+    let service_root = ServiceRoot::new(bmc.clone()).await.map_err(redfish_error)?;
+    let Some(systems) = service_root.systems().await.map_err(redfish_error)? else {
+        return Err(CarbideError::internal(
+            "BMC does not expose a Systems collection".to_string(),
+        ));
+    };
+    let linked_chassis = systems
+        .members()
+        .await
+        .map_err(redfish_error)?
+        .iter()
+        .flat_map(|system| {
+            system
+                .raw()
+                .links
+                .as_ref()
+                .and_then(|links| links.chassis.as_ref())
+                .into_iter()
+                .flatten()
+                .map(|chassis| chassis.id().clone())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let body = serde_json::json!({
+        "PowerControl": [{ "PowerLimit": { "LimitInWatts": limit_watts } }]
+    });
+    let mut last_error = None;
+    for chassis_id in linked_chassis {
+        let power = ODataId::from(format!("{chassis_id}/Power"));
+        let result = async {
+            bmc.update::<serde_json::Value, serde_json::Value>(&power, None, &body)
+                .await
+                .map_err(|err| err.to_string())?;
+            let applied = bmc
+                .get::<Power>(&power)
+                .await
+                .map_err(|err| err.to_string())?
+                .limit_watts();
+            match applied {
+                Some(applied) if applied.round() == f64::from(limit_watts) => Ok(()),
+                applied => Err(format!(
+                    "limit reads {applied:?} W after setting {limit_watts} W"
+                )),
+            }
+        }
+        .await;
+        match result {
+            Ok(()) => return Ok(()),
+            Err(err) => last_error = Some(format!("{power}: {err}")),
+        }
+    }
+
+    Err(CarbideError::internal(last_error.unwrap_or_else(|| {
+        "ComputerSystem does not link any chassis".to_string()
+    })))
+}
+
+/// The part of a chassis' deprecated `Power` resource that holds the host
+/// power limit.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Power {
+    #[serde(rename = "@odata.id")]
+    odata_id: ODataId,
+    #[serde(default)]
+    power_control: Vec<PowerControl>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PowerControl {
+    power_limit: Option<PowerLimit>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PowerLimit {
+    limit_in_watts: Option<f64>,
+}
+
+impl Power {
+    fn limit_watts(&self) -> Option<f64> {
+        self.power_control
+            .first()?
+            .power_limit
+            .as_ref()?
+            .limit_in_watts
+    }
+}
+
+impl EntityTypeRef for Power {
+    fn odata_id(&self) -> &ODataId {
+        &self.odata_id
+    }
+
+    fn etag(&self) -> Option<&ODataETag> {
+        None
+    }
+}
+
+fn redfish_error(err: impl std::fmt::Display) -> CarbideError {
+    CarbideError::internal(format!("redfish request failed: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use bmc_mock::HardwareType;
+    use bmc_mock::test_support::host_bmc;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn power_limit_is_set_on_the_linked_chassis_and_read_back() {
+        let bmc = host_bmc(HardwareType::DellPowerEdgeR750);
+
+        set_power_limit(bmc.clone(), 450).await.unwrap();
+
+        let power = ODataId::from("/redfish/v1/Chassis/System.Embedded.1/Power".to_string());
+        let applied = bmc.get::<Power>(&power).await.unwrap();
+        assert_eq!(applied.limit_watts(), Some(450.0));
+
+        // Lowering the limit later takes effect as well.
+        set_power_limit(bmc.clone(), 300).await.unwrap();
+        let applied = bmc.get::<Power>(&power).await.unwrap();
+        assert_eq!(applied.limit_watts(), Some(300.0));
+    }
+
+    #[tokio::test]
+    async fn power_limit_fails_without_a_cappable_linked_chassis() {
+        // The linked chassis of this host does not serve a `Power` resource.
+        let bmc = host_bmc(HardwareType::GenericAmi);
+
+        let err = set_power_limit(bmc, 450).await.unwrap_err();
+        assert!(err.to_string().contains("/Power"), "{err}");
+    }
+}
@@ -148,20 +148,34 @@ impl From<PgSecretsError> for SecretsError {
 }
 
 /// A decrypted journal entry, returned by the history and lookup methods.
-#[allow(dead_code)] // Staged for credential rotation: https://github.com/NVIDIA/infra-controller/issues/367.
-struct SecretEntry {
+pub(crate) struct SecretEntry {
     /// Identifies this journal entry.
-    secret_id: carbide_uuid::secret::SecretId,
+    pub(crate) secret_id: carbide_uuid::secret::SecretId,
     /// The journal order -- higher means written later.
-    seq: i64,
+    pub(crate) seq: i64,
     /// The credential path.
+    #[allow(dead_code)]
+    // Staged for credential rotation: https://github.com/NVIDIA/infra-controller/issues/367.
     path: String,
     /// The decrypted credential value.
     credentials: Credentials,
     /// The KEK that wrapped this entry's DEK.
-    kek_id: String,
+    pub(crate) kek_id: String,
     /// When this entry was written.
-    created_at: chrono::DateTime<chrono::Utc>,
+    pub(crate) created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl SecretEntry {
+    /// An empty password reads as no credential at all, exactly like the
+    /// Vault reader: the UFM and site-wide BMC delete flows "delete" by
+    /// writing an empty-password tombstone, and their consumers depend on
+    /// getting None back.
+    pub(crate) fn is_tombstone(&self) -> bool {
+        matches!(
+            &self.credentials,
+            Credentials::UsernamePassword { password, .. } if password.is_empty()
+        )
+    }
 }
 
 /// The `CredentialManager` backed by the Postgres secrets journal. Reads
@@ -201,8 +215,15 @@ impl PostgresCredentialManager {
     /// Return every journal entry for a credential, newest first.
     #[allow(dead_code)] // Staged for credential rotation: https://github.com/NVIDIA/infra-controller/issues/367.
     async fn get_history(&self, key: &CredentialKey) -> Result<Vec<SecretEntry>, PgSecretsError> {
-        let path = key.to_key_str();
-        let rows = db::secrets::get_history(&self.pool, &path).await?;
+        self.get_path_history(&key.to_key_str()).await
+    }
+
+    /// Return every journal entry for a credential path, newest first.
+    pub(crate) async fn get_path_history(
+        &self,
+        path: &str,
+    ) -> Result<Vec<SecretEntry>, PgSecretsError> {
+        let rows = db::secrets::get_history(&self.pool, path).await?;
         self.decrypt_rows(rows).await
     }
 
@@ -371,13 +392,10 @@ impl CredentialReader for PostgresCredentialManager {
         let entry = self.decrypt_row(row).await?;
 
         timer.succeed();
-        // An empty password reads as no credential at all, exactly like the
-        // Vault reader: the UFM and site-wide BMC delete flows "delete" by
-        // writing an empty-password tombstone, and their consumers depend
-        // on getting None back.
-        match entry.credentials {
-            Credentials::UsernamePassword { ref password, .. } if password.is_empty() => Ok(None),
-            credentials => Ok(Some(credentials)),
+        if entry.is_tombstone() {
+            Ok(None)
+        } else {
+            Ok(Some(entry.credentials))
         }
     }
 }
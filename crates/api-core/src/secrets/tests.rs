@@ -316,6 +316,38 @@ async fn empty_password_reads_as_none(pool: sqlx::PgPool) {
     );
 }
 
+// Verifies what the GetSecretHistory RPC reports: every version of a path,
+// newest first, with tombstones flagged and each version's own KEK.
+#[crate::sqlx_test]
+async fn path_history_lists_versions_newest_first(pool: sqlx::PgPool) {
+    let kms = kms_with_keys(&[("old-key", 1), ("new-key", 2)]);
+    let key = ufm_key("fab1");
+    let path = key.to_key_str().to_string();
+
+    manager(&pool, catch_all_routing("old-key"), kms.clone())
+        .set_credentials(&key, &cred("admin", "v1"))
+        .await
+        .expect("set v1");
+    let mgr = manager(&pool, catch_all_routing("new-key"), kms);
+    mgr.set_credentials(&key, &cred("admin", ""))
+        .await
+        .expect("set tombstone");
+
+    let history = mgr.get_path_history(&path).await.expect("history");
+    let summary: Vec<_> = history
+        .iter()
+        .map(|entry| (entry.kek_id.as_str(), entry.is_tombstone()))
+        .collect();
+    assert_eq!(summary, vec![("new-key", true), ("old-key", false)]);
+    assert!(history[0].seq > history[1].seq);
+    assert!(
+        mgr.get_path_history("ufm/never-written")
+            .await
+            .expect("history")
+            .is_empty()
+    );
+}
+
 // Verifies the associated-data binding end to end: a ciphertext copied
 // onto another path fails to decrypt instead of serving the wrong
 // credential.
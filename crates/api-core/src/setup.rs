@@ -36,6 +36,7 @@ use carbide_machine_controller::context::MachineStateHandlerServices;
 use carbide_machine_controller::dpf::{
     CarbideBmcPasswordProvider, CarbideDPFLabeler, DpfOperations, DpfSdkOps,
 };
+use carbide_machine_controller::dry_run::MachineStateExplainer;
 use carbide_machine_controller::handler::MachineStateHandlerBuilder;
 use carbide_machine_controller::io::MachineStateControllerIO;
 use carbide_machine_controller::per_object::MachinePerObjectInfo;
@@ -94,11 +95,14 @@ use tokio_util::sync::CancellationToken;
 
 use crate::api::Api;
 use crate::api::metrics::ApiMetricsEmitter;
+use crate::audit::AuditLog;
+use crate::certificate_revocations::CertificateRevocations;
 use crate::cfg::file::{CarbideConfig, InitialObjectsConfig, ListenMode, VmaasConfig};
 use crate::cfg::load::all_configuration_files;
 use crate::dpa::handler::start_dpa_handler;
 use crate::dynamic_settings::DynamicSettings;
 use crate::handlers::machine_validation::apply_config_on_startup;
+use crate::idle_host_power::IdleHostPowerManager;
 use crate::listener::{AdminUiRoutesBuilder, ApiListenMode};
 use crate::logging::log_limiter::LogLimiter;
 use crate::logging::service_health_metrics::{
@@ -110,7 +114,10 @@ use crate::mqtt_state_change_hook::hook::MqttStateChangeHook;
 use crate::mqtt_state_change_hook::republisher::{
     ManagedHostStateRepublisher, ManagedHostStateRepublisherParams,
 };
+use crate::network_security_group::reference_refresher::NetworkSecurityGroupReferenceRefresher;
+use crate::rack_power_budget::RackPowerBudgetManager;
 use crate::scout_stream::ConnectionRegistry;
+use crate::state_watch::{StateFeed, StateWatchHub};
 use crate::{CarbideError, attestation, db_init, ethernet_virtualization, listener};
 
 fn create_ipmi_tool(
@@ -430,6 +437,27 @@ pub(crate) async fn start_runtime(
     // Validate unconditionally; when explicitly enabled, missing prerequisites
     // fail rather than silently degrading.
     carbide_config.node_auth.validate()?;
+    // Documents nodes sign (disk sanitization certificates) chain to the
+    // client-certificate PKI whether or not bearer tokens are accepted, so its
+    // trust anchors are loaded whenever TLS is configured. Without them such
+    // documents are rejected.
+    let client_ca_verifier = match carbide_config.tls.as_ref() {
+        None => None,
+        Some(tls_ref) => match crate::node_auth::NodeJwtValidator::from_root_ca_file(
+            &tls_ref.root_cafile_path,
+            &carbide_config.node_auth,
+        ) {
+            Ok(validator) => Some(Arc::new(validator)),
+            Err(error) if !carbide_config.node_auth.enabled => {
+                tracing::error!(
+                    %error,
+                    "could not load the client CA roots; node-signed documents will be rejected"
+                );
+                None
+            }
+            Err(error) => return Err(error.into()),
+        },
+    };
     let node_jwt_validator = if carbide_config.node_auth.enabled {
         // Bearer tokens must never be accepted over plaintext, and the
         // validator trusts the same roots the TLS listener uses for client
@@ -439,16 +467,10 @@ pub(crate) async fn start_runtime(
                 "[node_auth] is enabled but listen_mode is not \"tls\"; bearer tokens must not be accepted over plaintext"
             ));
         }
-        let tls_ref = carbide_config
-            .tls
-            .as_ref()
-            .ok_or_else(|| eyre::eyre!("[node_auth] is enabled but [tls] is unset"))?;
-        Some(Arc::new(
-            crate::node_auth::NodeJwtValidator::from_root_ca_file(
-                &tls_ref.root_cafile_path,
-                &carbide_config.node_auth,
-            )?,
-        ))
+        if carbide_config.tls.is_none() {
+            return Err(eyre::eyre!("[node_auth] is enabled but [tls] is unset"));
+        }
+        client_ca_verifier.clone()
     } else {
         None
     };
@@ -509,11 +531,17 @@ pub(crate) async fn start_runtime(
     };
 
     let api_service = Arc::new(Api {
+        certificate_revocations: Arc::new(CertificateRevocations::new(
+            certificate_provider.clone(),
+            db_pool.clone(),
+        )),
         certificate_provider,
         common_pools,
         credential_manager,
         node_jwt_validator,
+        client_ca_verifier,
         database_connection: db_pool.clone(),
+        audit_log: AuditLog::start(db_pool.clone()),
         dpu_health_log_limiter: LogLimiter::default(),
         dynamic_settings,
         endpoint_explorer: bmc_explorer,
@@ -532,7 +560,9 @@ pub(crate) async fn start_runtime(
         metric_emitter: ApiMetricsEmitter::new(&meter),
         component_manager,
         bms_client: std::sync::OnceLock::new(),
+        machine_state_explainer: std::sync::OnceLock::new(),
         secrets_context,
+        state_watch_hub: StateWatchHub::default(),
     });
 
     if carbide_config.listen_only {
@@ -1063,6 +1093,7 @@ async fn initialize_and_start_controllers<'a>(
         component_manager,
         dpf_sdk,
         credential_manager,
+        state_watch_hub,
         ..
     } = api_service.as_ref();
     // As soon as we get the database up, observe this version of forge so that we know when it was
@@ -1222,7 +1253,8 @@ async fn initialize_and_start_controllers<'a>(
 
     // Create state change emitter with DSX Exchange Event Bus hook if enabled
     let state_change_emitter = {
-        let mut emitter_builder = StateChangeEmitterBuilder::default();
+        let mut emitter_builder = StateChangeEmitterBuilder::default()
+            .hook(Box::new(state_watch_hub.hook(StateFeed::Machine)));
 
         if let Some(ref config) = carbide_config.dsx_exchange_event_bus
             && config.enabled
@@ -1436,99 +1468,109 @@ async fn initialize_and_start_controllers<'a>(
         })
         .transpose()?;
 
+    let machine_state_handler_services = Arc::new(MachineStateHandlerServices {
+        db_pool: db_pool.clone(),
+        db_reader: db_pool.clone().into(),
+        redfish_client_pool: shared_redfish_pool.clone(),
+        ipmi_tool: ipmi_tool.clone(),
+        site_config: carbide_config.machine_state_handler_site_config().into(),
+        component_manager: component_manager.clone().map(Arc::new),
+        credential_manager: credential_manager.clone(),
+        bmc_rotation_gate: carbide_credential_rotation::RotationGate::new_for_family(
+            db::credential_rotation::CredentialRotationType::Bmc,
+        ),
+        host_uefi_rotation_gate: carbide_credential_rotation::RotationGate::new_for_family(
+            db::credential_rotation::CredentialRotationType::HostUefi,
+        ),
+        dpu_uefi_rotation_gate: carbide_credential_rotation::RotationGate::new_for_family(
+            db::credential_rotation::CredentialRotationType::DpuUefi,
+        ),
+        per_object_metrics_registry: per_object_metrics_registry.clone(),
+        per_object_info: machine_per_object_info,
+        dry_run: None,
+    });
+    let machine_state_handler = Arc::new(
+        MachineStateHandlerBuilder::builder()
+            .dpu_up_threshold(carbide_config.machine_state_controller.dpu_up_threshold)
+            .dpu_nic_firmware_reprovision_update_enabled(
+                carbide_config
+                    .dpu_config
+                    .dpu_nic_firmware_reprovision_update_enabled,
+            )
+            .dpu_enable_secure_boot(carbide_config.dpu_config.dpu_enable_secure_boot)
+            .dpu_wait_time(carbide_config.machine_state_controller.dpu_wait_time)
+            .power_down_wait(carbide_config.machine_state_controller.power_down_wait)
+            .failure_retry_time(carbide_config.machine_state_controller.failure_retry_time)
+            .scout_reporting_timeout(
+                carbide_config
+                    .machine_state_controller
+                    .scout_reporting_timeout,
+            )
+            .waiting_for_measurements_timeout(
+                carbide_config
+                    .machine_state_controller
+                    .waiting_for_measurements_timeout,
+            )
+            .uefi_boot_wait(carbide_config.machine_state_controller.uefi_boot_wait)
+            .hardware_models(carbide_config.get_firmware_config())
+            .firmware_downloader(&downloader)
+            .attestation_enabled(carbide_config.attestation_enabled)
+            .upload_limiter(upload_limiter.clone())
+            .machine_validation_config(carbide_config.machine_validation_config.clone())
+            .common_pools(common_pools.clone())
+            .bom_validation(carbide_config.bom_validation)
+            .no_firmware_update_reset_retries(carbide_config.firmware_global.no_reset_retries)
+            .instance_autoreboot_period(
+                carbide_config
+                    .machine_updater
+                    .instance_autoreboot_period
+                    .clone(),
+            )
+            .credential_reader(api_service.credential_manager.clone())
+            .power_options_config(carbide_config.power_manager_options.clone().into())
+            .dpf_sdk(dpf_sdk.clone())
+            .build(),
+    );
+    let machine_state_controller_io = Arc::new(MachineStateControllerIO {
+        host_health: HostHealthConfig {
+            hardware_health_reports: carbide_config.host_health.hardware_health_reports,
+            dpu_agent_version_staleness_threshold: carbide_config
+                .host_health
+                .dpu_agent_version_staleness_threshold,
+            prevent_allocations_on_stale_dpu_agent_version: carbide_config
+                .host_health
+                .prevent_allocations_on_stale_dpu_agent_version,
+            prevent_allocations_on_scout_heartbeat_timeout: carbide_config
+                .host_health
+                .prevent_allocations_on_scout_heartbeat_timeout,
+            suppress_external_alerting_on_scout_heartbeat_timeout: carbide_config
+                .host_health
+                .suppress_external_alerting_on_scout_heartbeat_timeout,
+        },
+        sla_config: model::machine::slas::MachineSlaConfig::new(
+            carbide_config.machine_state_controller.failure_retry_time,
+        ),
+    });
+    api_service
+        .machine_state_explainer
+        .set(Arc::new(MachineStateExplainer::new(
+            machine_state_handler.clone(),
+            machine_state_handler_services.clone(),
+            machine_state_controller_io.clone(),
+        )))
+        .map_err(|_| eyre::eyre!("machine state explainer already initialized"))?;
+
     // handles need to be stored in a variable
     // If they are assigned to _ then the destructor will be immediately called
     StateController::<MachineStateControllerIO>::builder()
         .database(db_pool.clone(), work_lock_manager_handle.clone())
         .meter("carbide_machines", meter.clone())
         .processor_id(state_controller_id.clone())
-        .services(
-            MachineStateHandlerServices {
-                db_pool: db_pool.clone(),
-                db_reader: db_pool.clone().into(),
-                redfish_client_pool: shared_redfish_pool.clone(),
-                ipmi_tool: ipmi_tool.clone(),
-                site_config: carbide_config.machine_state_handler_site_config().into(),
-                component_manager: component_manager.clone().map(Arc::new),
-                credential_manager: credential_manager.clone(),
-                bmc_rotation_gate: carbide_credential_rotation::RotationGate::new_for_family(
-                    db::credential_rotation::CredentialRotationType::Bmc,
-                ),
-                host_uefi_rotation_gate: carbide_credential_rotation::RotationGate::new_for_family(
-                    db::credential_rotation::CredentialRotationType::HostUefi,
-                ),
-                dpu_uefi_rotation_gate: carbide_credential_rotation::RotationGate::new_for_family(
-                    db::credential_rotation::CredentialRotationType::DpuUefi,
-                ),
-                per_object_metrics_registry: per_object_metrics_registry.clone(),
-                per_object_info: machine_per_object_info,
-            }
-            .into(),
-        )
+        .services(machine_state_handler_services.clone())
         .per_object_state_metrics(machine_state_recorder)
         .iteration_config((&carbide_config.machine_state_controller.controller).into())
-        .state_handler(Arc::new(
-            MachineStateHandlerBuilder::builder()
-                .dpu_up_threshold(carbide_config.machine_state_controller.dpu_up_threshold)
-                .dpu_nic_firmware_reprovision_update_enabled(
-                    carbide_config
-                        .dpu_config
-                        .dpu_nic_firmware_reprovision_update_enabled,
-                )
-                .dpu_enable_secure_boot(carbide_config.dpu_config.dpu_enable_secure_boot)
-                .dpu_wait_time(carbide_config.machine_state_controller.dpu_wait_time)
-                .power_down_wait(carbide_config.machine_state_controller.power_down_wait)
-                .failure_retry_time(carbide_config.machine_state_controller.failure_retry_time)
-                .scout_reporting_timeout(
-                    carbide_config
-                        .machine_state_controller
-                        .scout_reporting_timeout,
-                )
-                .waiting_for_measurements_timeout(
-                    carbide_config
-                        .machine_state_controller
-                        .waiting_for_measurements_timeout,
-                )
-                .uefi_boot_wait(carbide_config.machine_state_controller.uefi_boot_wait)
-                .hardware_models(carbide_config.get_firmware_config())
-                .firmware_downloader(&downloader)
-                .attestation_enabled(carbide_config.attestation_enabled)
-                .upload_limiter(upload_limiter.clone())
-                .machine_validation_config(carbide_config.machine_validation_config.clone())
-                .common_pools(common_pools.clone())
-                .bom_validation(carbide_config.bom_validation)
-                .no_firmware_update_reset_retries(carbide_config.firmware_global.no_reset_retries)
-                .instance_autoreboot_period(
-                    carbide_config
-                        .machine_updater
-                        .instance_autoreboot_period
-                        .clone(),
-                )
-                .credential_reader(api_service.credential_manager.clone())
-                .power_options_config(carbide_config.power_manager_options.clone().into())
-                .dpf_sdk(dpf_sdk.clone())
-                .build(),
-        ))
-        .io(Arc::new(MachineStateControllerIO {
-            host_health: HostHealthConfig {
-                hardware_health_reports: carbide_config.host_health.hardware_health_reports,
-                dpu_agent_version_staleness_threshold: carbide_config
-                    .host_health
-                    .dpu_agent_version_staleness_threshold,
-                prevent_allocations_on_stale_dpu_agent_version: carbide_config
-                    .host_health
-                    .prevent_allocations_on_stale_dpu_agent_version,
-                prevent_allocations_on_scout_heartbeat_timeout: carbide_config
-                    .host_health
-                    .prevent_allocations_on_scout_heartbeat_timeout,
-                suppress_external_alerting_on_scout_heartbeat_timeout: carbide_config
-                    .host_health
-                    .suppress_external_alerting_on_scout_heartbeat_timeout,
-            },
-            sla_config: model::machine::slas::MachineSlaConfig::new(
-                carbide_config.machine_state_controller.failure_retry_time,
-            ),
-        }))
+        .state_handler(machine_state_handler.clone())
+        .io(machine_state_controller_io.clone())
         .state_change_emitter(state_change_emitter)
         .build_and_spawn(join_set, cancel_token.clone())
         .expect("Unable to build MachineStateController");
@@ -1651,6 +1693,11 @@ async fn initialize_and_start_controllers<'a>(
         .per_object_state_metrics(per_object_state_recorder("power_shelf"))
         .iteration_config((&carbide_config.power_shelf_state_controller.controller).into())
         .state_handler(Arc::new(PowerShelfStateHandler::default()))
+        .state_change_emitter(
+            StateChangeEmitterBuilder::default()
+                .hook(Box::new(state_watch_hub.hook(StateFeed::PowerShelf)))
+                .build(),
+        )
         .build_and_spawn(join_set, cancel_token.clone())
         .expect("Unable to build PowerShelfStateController");
 
@@ -1697,6 +1744,11 @@ async fn initialize_and_start_controllers<'a>(
         .per_object_state_metrics(per_object_state_recorder("rack"))
         .iteration_config((&carbide_config.rack_state_controller.controller).into())
         .state_handler(Arc::new(RackStateHandler::default()))
+        .state_change_emitter(
+            StateChangeEmitterBuilder::default()
+                .hook(Box::new(state_watch_hub.hook(StateFeed::Rack)))
+                .build(),
+        )
         .build_and_spawn(join_set, cancel_token.clone())
         .expect("Unable to build RackStateController");
 
@@ -1724,6 +1776,11 @@ async fn initialize_and_start_controllers<'a>(
         .per_object_state_metrics(per_object_state_recorder("switch"))
         .iteration_config((&carbide_config.switch_state_controller.controller).into())
         .state_handler(Arc::new(SwitchStateHandler::default()))
+        .state_change_emitter(
+            StateChangeEmitterBuilder::default()
+                .hook(Box::new(state_watch_hub.hook(StateFeed::Switch)))
+                .build(),
+        )
         .build_and_spawn(join_set, cancel_token.clone())
         .expect("Unable to build SwitchStateController");
 
@@ -1842,6 +1899,35 @@ async fn initialize_and_start_controllers<'a>(
     )
     .start(join_set, cancel_token.clone())?;
 
+    NetworkSecurityGroupReferenceRefresher::new(
+        db_pool.clone(),
+        carbide_config.network_security_group.clone(),
+        work_lock_manager_handle.clone(),
+        meter.clone(),
+    )
+    .start(join_set, cancel_token.clone())?;
+
+    RackPowerBudgetManager::new(
+        db_pool.clone(),
+        carbide_config.rack_power_budget.clone(),
+        carbide_config.rack_profiles.clone(),
+        carbide_redfish::nv_redfish::new_pool(carbide_config.site_explorer.bmc_proxy.clone()),
+        credential_manager.clone(),
+        work_lock_manager_handle.clone(),
+        meter.clone(),
+    )
+    .start(join_set, cancel_token.clone())?;
+
+    IdleHostPowerManager::new(
+        db_pool.clone(),
+        carbide_config.power_manager_options.clone(),
+        carbide_config.machine_validation_config.enabled,
+        carbide_config.host_health,
+        work_lock_manager_handle.clone(),
+        meter.clone(),
+    )
+    .start(join_set, cancel_token.clone())?;
+
     // we need to create ek_cert_status entries for all existing machines
     attestation::backfill_ek_cert_status_for_existing_machines(db_pool).await?;
 
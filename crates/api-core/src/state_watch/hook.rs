@@ -0,0 +1,135 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! State change hook that feeds the watch hub.
+
+use model::state_history::StateHistoryRecord;
+use serde::Serialize;
+use state_controller::state_change_emitter::{StateChangeEvent, StateChangeHook};
+use tokio::sync::broadcast;
+
+use super::WatchedTransition;
+
+/// Publishes the transitions of a state controller to the watch streams of
+/// this API instance.
+///
+/// States are serialized to JSON, the same representation that the state
+/// history tables return. Transitions are dropped while no stream is open.
+pub(crate) struct StateWatchHook {
+    sender: broadcast::Sender<WatchedTransition>,
+}
+
+impl StateWatchHook {
+    pub(super) fn new(sender: broadcast::Sender<WatchedTransition>) -> Self {
+        Self { sender }
+    }
+}
+
+impl<Id, S> StateChangeHook<Id, S> for StateWatchHook
+where
+    Id: std::fmt::Display + Clone,
+    S: Serialize + Clone,
+{
+    fn on_state_changed(&self, event: &StateChangeEvent<'_, Id, S>) {
+        if self.sender.receiver_count() == 0 {
+            return;
+        }
+
+        let state = match serde_json::to_string(event.new_state) {
+            Ok(state) => state,
+            Err(e) => {
+                tracing::warn!(
+                    object_id = %event.object_id,
+                    error = %e,
+                    "failed to serialize state for watch streams",
+                );
+                return;
+            }
+        };
+
+        // Sending only fails if the last stream closed since the check above.
+        self.sender
+            .send(WatchedTransition {
+                object_id: event.object_id.to_string(),
+                record: StateHistoryRecord {
+                    state,
+                    state_version: event.state_version,
+                    time: Some(event.timestamp),
+                },
+            })
+            .ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use config_version::ConfigVersion;
+
+    use super::*;
+    use crate::state_watch::{StateFeed, StateWatchHub};
+
+    #[derive(Debug, Clone, Serialize)]
+    #[serde(tag = "state", rename_all = "lowercase")]
+    enum TestState {
+        Ready,
+    }
+
+    fn emit(hook: &StateWatchHook, version: ConfigVersion) {
+        let object_id = "object-1".to_string();
+        hook.on_state_changed(&StateChangeEvent {
+            object_id: &object_id,
+            previous_state: None,
+            new_state: &TestState::Ready,
+            state_version: version,
+            timestamp: Utc::now(),
+        });
+    }
+
+    #[test]
+    fn publishes_serialized_state_to_subscribers() {
+        let hub = StateWatchHub::default();
+        let hook = hub.hook(StateFeed::Rack);
+        let mut receiver = hub.sender(StateFeed::Rack).subscribe();
+        let version = ConfigVersion::new(3);
+
+        emit(&hook, version);
+
+        let transition = receiver.try_recv().expect("transition should be published");
+        assert_eq!(transition.object_id, "object-1");
+        assert_eq!(transition.record.state, r#"{"state":"ready"}"#);
+        assert_eq!(transition.record.state_version, version);
+        assert!(
+            hub.sender(StateFeed::Machine)
+                .subscribe()
+                .try_recv()
+                .is_err(),
+            "other feeds must not receive the transition",
+        );
+    }
+
+    #[test]
+    fn publishing_without_subscribers_is_a_no_op() {
+        let hub = StateWatchHub::default();
+        let hook = hub.hook(StateFeed::Machine);
+
+        emit(&hook, ConfigVersion::new(1));
+
+        let mut receiver = hub.sender(StateFeed::Machine).subscribe();
+        assert!(receiver.try_recv().is_err());
+    }
+}
@@ -0,0 +1,140 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Server-streaming watches over the lifecycle state of managed objects.
+//!
+//! Transitions reach a watch stream through two paths:
+//! - A [`StateWatchHook`] registered with each local state controller publishes
+//!   every transition the controller commits, so watchers see it immediately.
+//! - Each stream periodically reads the state history tables. This delivers
+//!   transitions committed by the state controllers of other API replicas, and
+//!   recovers local transitions the stream fell behind on.
+//!
+//! Both paths carry the `state_version` of the transition. A stream forwards a
+//! transition only if its version is newer than the last one it delivered for
+//! the object, so the two paths can overlap without repeating events.
+
+mod hook;
+mod stream;
+
+use std::sync::Arc;
+
+use db::state_history::StateHistoryTableId;
+pub(crate) use hook::StateWatchHook;
+use model::state_history::StateHistoryRecord;
+pub(crate) use stream::{StateWatchStreamType, WatchRequest};
+use tokio::sync::{Semaphore, broadcast};
+
+// Transitions are small (an object ID and its serialized state). A stream that
+// falls more than this many transitions behind recovers the dropped ones from
+// the state history tables on its next poll, so the capacity only needs to
+// absorb bursts such as a rack of hosts transitioning together.
+const TRANSITION_CHANNEL_CAPACITY: usize = 1024;
+
+// Each open stream holds a task, a broadcast receiver, and a database poll
+// every `stream::HISTORY_POLL_INTERVAL`. Streams beyond this limit are rejected
+// with RESOURCE_EXHAUSTED instead of being queued; clients retry with backoff.
+const MAX_WATCH_STREAMS: usize = 128;
+
+/// The object types that can be watched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WatchedObjectType {
+    Machine,
+    /// Instances are watched through the state of their host machine.
+    Instance,
+    Rack,
+    Switch,
+    PowerShelf,
+}
+
+impl WatchedObjectType {
+    fn feed(self) -> StateFeed {
+        match self {
+            WatchedObjectType::Machine | WatchedObjectType::Instance => StateFeed::Machine,
+            WatchedObjectType::Rack => StateFeed::Rack,
+            WatchedObjectType::Switch => StateFeed::Switch,
+            WatchedObjectType::PowerShelf => StateFeed::PowerShelf,
+        }
+    }
+}
+
+/// The state controllers whose transitions feed watch streams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StateFeed {
+    Machine,
+    Rack,
+    Switch,
+    PowerShelf,
+}
+
+impl StateFeed {
+    fn history_table(self) -> StateHistoryTableId {
+        match self {
+            StateFeed::Machine => StateHistoryTableId::Machine,
+            StateFeed::Rack => StateHistoryTableId::Rack,
+            StateFeed::Switch => StateHistoryTableId::Switch,
+            StateFeed::PowerShelf => StateHistoryTableId::PowerShelf,
+        }
+    }
+}
+
+/// A state transition committed by a local state controller.
+#[derive(Debug, Clone)]
+struct WatchedTransition {
+    object_id: String,
+    record: StateHistoryRecord,
+}
+
+/// Fans out the state transitions committed by local state controllers to the
+/// open watch streams, and bounds the number of open streams.
+#[derive(Clone)]
+pub(crate) struct StateWatchHub {
+    machines: broadcast::Sender<WatchedTransition>,
+    racks: broadcast::Sender<WatchedTransition>,
+    switches: broadcast::Sender<WatchedTransition>,
+    power_shelves: broadcast::Sender<WatchedTransition>,
+    stream_permits: Arc<Semaphore>,
+}
+
+impl Default for StateWatchHub {
+    fn default() -> Self {
+        Self {
+            machines: broadcast::channel(TRANSITION_CHANNEL_CAPACITY).0,
+            racks: broadcast::channel(TRANSITION_CHANNEL_CAPACITY).0,
+            switches: broadcast::channel(TRANSITION_CHANNEL_CAPACITY).0,
+            power_shelves: broadcast::channel(TRANSITION_CHANNEL_CAPACITY).0,
+            stream_permits: Arc::new(Semaphore::new(MAX_WATCH_STREAMS)),
+        }
+    }
+}
+
+impl StateWatchHub {
+    /// Returns a hook that publishes the transitions of the state controller
+    /// behind `feed` to this hub.
+    pub(crate) fn hook(&self, feed: StateFeed) -> StateWatchHook {
+        StateWatchHook::new(self.sender(feed).clone())
+    }
+
+    fn sender(&self, feed: StateFeed) -> &broadcast::Sender<WatchedTransition> {
+        match feed {
+            StateFeed::Machine => &self.machines,
+            StateFeed::Rack => &self.racks,
+            StateFeed::Switch => &self.switches,
+            StateFeed::PowerShelf => &self.power_shelves,
+        }
+    }
+}
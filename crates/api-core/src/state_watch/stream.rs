@@ -0,0 +1,469 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The per-stream task behind the `Watch*States` RPCs.
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use ::rpc::forge as rpc;
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use config_version::ConfigVersion;
+use db::state_history::ObjectStateHistoryRecord;
+use model::state_history::StateHistoryRecord;
+use sqlx::PgPool;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{OwnedSemaphorePermit, broadcast, mpsc};
+use tokio::time::MissedTickBehavior;
+use tokio_stream::Stream;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::Status;
+
+use super::{StateWatchHub, WatchedObjectType, WatchedTransition};
+use crate::{CarbideError, CarbideResult};
+
+pub(crate) type StateWatchStreamType =
+    Pin<Box<dyn Stream<Item = Result<rpc::StateWatchEvent, Status>> + Send>>;
+
+// How often a stream reads the state history tables for transitions committed
+// by other API replicas. This bounds the delivery delay of those transitions.
+pub(super) const HISTORY_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+// Consecutive history polls overlap by this much, because a history record
+// carries the start time of the transaction that inserted it and can become
+// visible after records with later timestamps. Transitions whose transaction
+// stays open for longer than this and that are committed by another replica
+// are only delivered once a later transition of the same object fills the gap.
+const HISTORY_POLL_OVERLAP: Duration = Duration::from_secs(60);
+
+// Deadline for each state history or instance query a stream runs, including
+// waiting for a pooled connection. A poll that misses it is retried on the
+// next tick with an unchanged window; a replay that misses it ends the stream.
+const HISTORY_QUERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Events a stream buffers for a slow client. Once full, the stream stops
+// reading transitions until the client catches up, and recovers the skipped
+// ones from the state history tables.
+const STREAM_BUFFER_SIZE: usize = 128;
+
+/// A validated `StateWatchRequest`.
+///
+/// Object IDs are in the canonical string form of their type.
+pub(crate) struct WatchRequest {
+    pub(crate) object_type: WatchedObjectType,
+    /// The objects to watch. `None` watches every object of the type.
+    pub(crate) object_ids: Option<HashSet<String>>,
+    /// The state version of the last event the client processed per object.
+    pub(crate) resume_from: HashMap<String, ConfigVersion>,
+}
+
+impl StateWatchHub {
+    /// Opens a watch stream and starts the task that feeds it.
+    ///
+    /// The task stops once the client drops the stream. Fails with
+    /// [`CarbideError::ResourceExhausted`] if too many streams are open.
+    pub(crate) fn open(
+        &self,
+        db_pool: PgPool,
+        request: WatchRequest,
+    ) -> CarbideResult<StateWatchStreamType> {
+        let permit = self
+            .stream_permits
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| {
+                CarbideError::ResourceExhausted("too many open state watch streams".to_string())
+            })?;
+
+        // Subscribe before replaying history, so no transition committed
+        // during the replay is lost.
+        let transitions = self.sender(request.object_type.feed()).subscribe();
+        let (sender, receiver) = mpsc::channel(STREAM_BUFFER_SIZE);
+        let delivered = request
+            .resume_from
+            .iter()
+            .map(|(object_id, version)| (object_id.clone(), version.version_nr()))
+            .collect();
+
+        let task = WatchTask {
+            db_pool,
+            request,
+            delivered,
+            instances_by_machine: HashMap::new(),
+            polled: HashMap::new(),
+            sender,
+        };
+        tokio::spawn(task.run(transitions, permit));
+
+        Ok(Box::pin(ReceiverStream::new(receiver)))
+    }
+}
+
+struct WatchTask {
+    db_pool: PgPool,
+    request: WatchRequest,
+    /// The state version number of the last event delivered per object ID.
+    /// Holds at most one entry per object of the watched type.
+    delivered: HashMap<String, u64>,
+    /// The instance hosted by each machine. Only used for instance watches.
+    instances_by_machine: HashMap<MachineId, InstanceId>,
+    /// The state history records the current poll window returned so far, by
+    /// record ID, with their timestamps. Later polls of the window skip them.
+    polled: HashMap<i64, DateTime<Utc>>,
+    sender: mpsc::Sender<Result<rpc::StateWatchEvent, Status>>,
+}
+
+/// Whether the client is still receiving events.
+#[derive(Debug, PartialEq, Eq)]
+enum Delivery {
+    Open,
+    ClientGone,
+}
+
+impl WatchTask {
+    async fn run(
+        mut self,
+        mut transitions: broadcast::Receiver<WatchedTransition>,
+        _permit: OwnedSemaphorePermit,
+    ) {
+        let opened_at = Utc::now();
+
+        let window_floor = match self.replay(opened_at).await {
+            Ok((Delivery::Open, window_floor)) => window_floor,
+            Ok((Delivery::ClientGone, _)) => return,
+            Err(e) => {
+                self.sender.send(Err(e.into())).await.ok();
+                return;
+            }
+        };
+
+        let mut poll_interval = tokio::time::interval(HISTORY_POLL_INTERVAL);
+        poll_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut poll_since = window_floor;
+
+        loop {
+            let delivery = tokio::select! {
+                _ = self.sender.closed() => Delivery::ClientGone,
+                transition = transitions.recv() => match transition {
+                    Ok(transition) => {
+                        self.forward_live(&transition.object_id, transition.record).await
+                    }
+                    Err(RecvError::Lagged(skipped_transition_count)) => {
+                        // The skipped transitions are recovered by the next poll.
+                        tracing::debug!(
+                            skipped_transition_count,
+                            "state watch stream fell behind local transitions",
+                        );
+                        Delivery::Open
+                    }
+                    Err(RecvError::Closed) => Delivery::ClientGone,
+                },
+                _ = poll_interval.tick() => {
+                    let polled_at = Utc::now();
+                    match self.poll_history(poll_since).await {
+                        Ok(delivery) => {
+                            poll_since = (polled_at - HISTORY_POLL_OVERLAP).max(window_floor);
+                            delivery
+                        }
+                        Err(e) => {
+                            tracing::warn!(
+                                object_type = ?self.request.object_type,
+                                error = %e,
+                                "state watch stream failed to poll state history",
+                            );
+                            Delivery::Open
+                        }
+                    }
+                }
+            };
+
+            if delivery == Delivery::ClientGone {
+                return;
+            }
+        }
+    }
+
+    /// Sends every recorded transition that is newer than the resume points,
+    /// and returns the earliest time the history polls need to look at.
+    async fn replay(
+        &mut self,
+        opened_at: DateTime<Utc>,
+    ) -> CarbideResult<(Delivery, DateTime<Utc>)> {
+        let Some(oldest_resume_point) = self
+            .request
+            .resume_from
+            .values()
+            .map(ConfigVersion::timestamp)
+            .min()
+        else {
+            return Ok((Delivery::Open, opened_at));
+        };
+
+        if self.request.object_ids.is_none() {
+            // The client may have missed transitions of any object, including
+            // objects created while it was disconnected, so replay every
+            // transition since its oldest resume point.
+            let since = (oldest_resume_point - HISTORY_POLL_OVERLAP).min(opened_at);
+            let delivery = self.poll_history(since).await?;
+            return Ok((delivery, since));
+        }
+
+        self.refresh_instances().await?;
+
+        let source_ids = self
+            .request
+            .resume_from
+            .keys()
+            .filter_map(|object_id| self.source_object_id(object_id))
+            .collect::<Vec<_>>();
+        let table_id = self.request.object_type.feed().history_table();
+        let db_pool = self.db_pool.clone();
+        let histories = with_deadline(async move {
+            let mut conn = db_pool
+                .acquire()
+                .await
+                .map_err(db::DatabaseError::acquire)?;
+            db::state_history::find_by_object_ids(&mut conn, table_id, &source_ids).await
+        })
+        .await?;
+
+        for (source_id, records) in histories {
+            for record in records {
+                if self.forward(&source_id, record).await == Delivery::ClientGone {
+                    return Ok((Delivery::ClientGone, opened_at));
+                }
+            }
+        }
+        Ok((Delivery::Open, opened_at))
+    }
+
+    /// Sends the transitions recorded at or after `since` that were not
+    /// delivered yet.
+    ///
+    /// Only reads the records that earlier polls of the window did not return.
+    async fn poll_history(&mut self, since: DateTime<Utc>) -> CarbideResult<Delivery> {
+        self.refresh_instances().await?;
+
+        let source_ids = self.request.object_ids.as_ref().map(|object_ids| {
+            object_ids
+                .iter()
+                .filter_map(|object_id| self.source_object_id(object_id))
+                .collect::<Vec<_>>()
+        });
+        let table_id = self.request.object_type.feed().history_table();
+        let skip_ids = self.polled.keys().copied().collect::<Vec<_>>();
+        let records = with_deadline(db::state_history::find_recorded_since(
+            &self.db_pool,
+            table_id,
+            source_ids.as_deref(),
+            since,
+            &skip_ids,
+        ))
+        .await?;
+
+        if self.request.object_ids.is_none() {
+            self.map_instances(&records).await?;
+        }
+        self.polled.retain(|_, recorded_at| *recorded_at >= since);
+        self.polled.extend(
+            records
+                .iter()
+                .map(|record| (record.history_id, record.record.time.unwrap_or(since))),
+        );
+
+        for record in records {
+            if self.forward(&record.object_id, record.record).await == Delivery::ClientGone {
+                return Ok(Delivery::ClientGone);
+            }
+        }
+        Ok(Delivery::Open)
+    }
+
+    /// Sends a transition published by a local state controller.
+    ///
+    /// State versions grow by one per transition. If the transition skips
+    /// versions after the last delivered one, another replica committed the
+    /// transitions in between, and they are read from the state history first
+    /// so the client sees every transition in order.
+    async fn forward_live(&mut self, source_id: &str, record: StateHistoryRecord) -> Delivery {
+        if let Some(object_id) = self.watched_object_id(source_id)
+            && let Some(last_delivered) = self.delivered.get(&object_id).copied()
+            && record.state_version.version_nr() > last_delivered + 1
+        {
+            let table_id = self.request.object_type.feed().history_table();
+            let db_pool = self.db_pool.clone();
+            let owned_source_id = source_id.to_string();
+            let history = with_deadline(async move {
+                let mut conn = db_pool
+                    .acquire()
+                    .await
+                    .map_err(db::DatabaseError::acquire)?;
+                db::state_history::for_object(&mut conn, table_id, &owned_source_id).await
+            })
+            .await;
+            match history {
+                Ok(history) => {
+                    for missed in history {
+                        if missed.state_version.version_nr() < record.state_version.version_nr()
+                            && self.forward(source_id, missed).await == Delivery::ClientGone
+                        {
+                            return Delivery::ClientGone;
+                        }
+                    }
+                }
+                Err(e) => {
+                    // Delivering the transition anyway would make the missed
+                    // ones undeliverable, so leave it to the next poll.
+                    tracing::warn!(
+                        %object_id,
+                        error = %e,
+                        "state watch stream failed to read missed transitions",
+                    );
+                    return Delivery::Open;
+                }
+            }
+        }
+
+        self.forward(source_id, record).await
+    }
+
+    /// Sends a transition of the object with ID `source_id` in the state
+    /// history table, unless the object is not watched or the client already
+    /// received this or a later version.
+    async fn forward(&mut self, source_id: &str, record: StateHistoryRecord) -> Delivery {
+        let Some(object_id) = self.watched_object_id(source_id) else {
+            return Delivery::Open;
+        };
+        let version_nr = record.state_version.version_nr();
+        if self
+            .delivered
+            .get(&object_id)
+            .is_some_and(|last_delivered| *last_delivered >= version_nr)
+        {
+            return Delivery::Open;
+        }
+        self.delivered.insert(object_id.clone(), version_nr);
+
+        let machine_id = match self.request.object_type {
+            WatchedObjectType::Instance => source_id.parse::<MachineId>().ok(),
+            _ => None,
+        };
+        let event = rpc::StateWatchEvent {
+            object_id,
+            record: Some(record.into()),
+            machine_id,
+        };
+        match self.sender.send(Ok(event)).await {
+            Ok(()) => Delivery::Open,
+            Err(_) => Delivery::ClientGone,
+        }
+    }
+
+    /// Maps an object ID from a state history table to the watched object ID,
+    /// or `None` if the object is not watched.
+    fn watched_object_id(&self, source_id: &str) -> Option<String> {
+        let object_id = match self.request.object_type {
+            WatchedObjectType::Instance => {
+                let machine_id = source_id.parse::<MachineId>().ok()?;
+                self.instances_by_machine.get(&machine_id)?.to_string()
+            }
+            _ => source_id.to_string(),
+        };
+        match &self.request.object_ids {
+            Some(object_ids) if !object_ids.contains(&object_id) => None,
+            _ => Some(object_id),
+        }
+    }
+
+    /// Maps a watched object ID to its object ID in the state history table.
+    fn source_object_id(&self, object_id: &str) -> Option<String> {
+        match self.request.object_type {
+            WatchedObjectType::Instance => self
+                .instances_by_machine
+                .iter()
+                .find(|(_, instance_id)| instance_id.to_string() == object_id)
+                .map(|(machine_id, _)| machine_id.to_string()),
+            _ => Some(object_id.to_string()),
+        }
+    }
+
+    /// Reloads the host machine of each watched instance. Watches over every
+    /// instance look up the instances of the machines they see transitions of
+    /// instead (see [`Self::map_instances`]).
+    async fn refresh_instances(&mut self) -> CarbideResult<()> {
+        if self.request.object_type != WatchedObjectType::Instance {
+            return Ok(());
+        }
+        let Some(object_ids) = &self.request.object_ids else {
+            return Ok(());
+        };
+        let instance_ids = object_ids
+            .iter()
+            .map(|object_id| object_id.parse::<InstanceId>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let hosts = with_deadline(db::instance::find_machine_ids_by_instance_ids(
+            &self.db_pool,
+            &instance_ids,
+        ))
+        .await?;
+        self.instances_by_machine = hosts
+            .into_iter()
+            .map(|(instance_id, machine_id)| (machine_id, instance_id))
+            .collect();
+        Ok(())
+    }
+
+    /// Looks up the instance hosted by each machine that `records` belong to.
+    async fn map_instances(&mut self, records: &[ObjectStateHistoryRecord]) -> CarbideResult<()> {
+        if self.request.object_type != WatchedObjectType::Instance || records.is_empty() {
+            return Ok(());
+        }
+        let machine_ids = records
+            .iter()
+            .filter_map(|record| record.object_id.parse::<MachineId>().ok())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let hosts = with_deadline(db::instance::find_instance_ids_by_machine_ids(
+            &self.db_pool,
+            &machine_ids,
+        ))
+        .await?;
+        for machine_id in &machine_ids {
+            self.instances_by_machine.remove(machine_id);
+        }
+        self.instances_by_machine.extend(
+            hosts
+                .into_iter()
+                .map(|(instance_id, machine_id)| (machine_id, instance_id)),
+        );
+        Ok(())
+    }
+}
+
+async fn with_deadline<T>(
+    query: impl Future<Output = Result<T, db::DatabaseError>>,
+) -> CarbideResult<T> {
+    tokio::time::timeout(HISTORY_QUERY_TIMEOUT, query)
+        .await
+        .map_err(|_| CarbideError::UnavailableError("state history query timed out".to_string()))?
+        .map_err(Into::into)
+}
@@ -40,6 +40,7 @@ use tracing_subscriber::EnvFilter;
 
 use super::Api;
 use crate::api::metrics::ApiMetricsEmitter;
+use crate::certificate_revocations::CertificateRevocations;
 use crate::cfg::file::CarbideConfig;
 use crate::dynamic_settings::DynamicSettings;
 use crate::ethernet_virtualization::EthVirtData;
@@ -195,6 +196,16 @@ impl TestApiBuilder {
             .credential_manager
             .unwrap_or_else(|| Arc::new(TestCredentialManager::default()));
 
+        // As in production, loaded from `[tls]` when it names a readable
+        // bundle. The default test config does not.
+        let client_ca_verifier = runtime_config.tls.as_ref().and_then(|tls| {
+            crate::node_auth::NodeJwtValidator::from_root_ca_file(
+                &tls.root_cafile_path,
+                &runtime_config.node_auth,
+            )
+            .ok()
+            .map(Arc::new)
+        });
         let certificate_provider = Arc::new(TestCertificateProvider::new());
         let machine_state_handler_enqueuer = Enqueuer::new(self.db_pool.clone());
         let dpu_health_log_limiter = LogLimiter::default();
@@ -266,10 +277,16 @@ impl TestApiBuilder {
             .nmxc_client_pool
             .unwrap_or_else(|| Arc::new(NmxcSimClient::default()));
 
+        let audit_log = crate::audit::AuditLog::start(self.db_pool.clone());
+
         Api {
             dpf_sdk: self.dpf_sdk,
             runtime_config,
             credential_manager,
+            certificate_revocations: Arc::new(CertificateRevocations::new(
+                certificate_provider.clone(),
+                self.db_pool.clone(),
+            )),
             certificate_provider,
             database_connection: self.db_pool,
             redfish_pool,
@@ -289,8 +306,12 @@ impl TestApiBuilder {
             component_manager: self.component_manager.map(|cm| (*cm).clone()),
             bmc_session_manager,
             bms_client: std::sync::OnceLock::new(),
+            machine_state_explainer: std::sync::OnceLock::new(),
             secrets_context: self.secrets_context,
+            state_watch_hub: crate::state_watch::StateWatchHub::default(),
+            audit_log,
             node_jwt_validator: None,
+            client_ca_verifier,
         }
     }
 }
@@ -44,12 +44,13 @@ use crate::cfg::file::{
     IbPartitionStateControllerConfig, KmsConfig, ListenMode, MachineUpdater,
     MeasuredBootMetricsCollectorConfig, MqttAuthConfig, NetworkSecurityGroupConfig,
     NetworkSegmentStateControllerConfig, NodeAuthConfig, PowerShelfStateControllerConfig,
-    RackStateControllerConfig, SecretsConfig, SpdmConfig, SpdmStateControllerConfig,
-    SwitchStateControllerConfig, TracingConfig, VmaasConfig, VpcPeeringPolicy,
-    VpcPrefixStateControllerConfig, default_bmc_session_lockout_threshold,
+    RackPowerBudgetConfig, RackStateControllerConfig, SecretsConfig, SpdmConfig,
+    SpdmStateControllerConfig, SwitchStateControllerConfig, TracingConfig, VmaasConfig,
+    VpcPeeringPolicy, VpcPrefixStateControllerConfig, default_bmc_session_lockout_threshold,
     default_database_pool_acquire_timeout, default_database_pool_idle_timeout,
     default_database_pool_max_lifetime, default_max_find_by_ids,
-    default_max_site_prefixes_per_tenant, default_pxe_public_base_url,
+    default_max_instance_allocation_batch_size, default_max_site_prefixes_per_tenant,
+    default_pxe_public_base_url,
 };
 #[cfg(test)]
 use crate::cfg::file::{
@@ -282,6 +283,7 @@ pub fn get() -> CarbideConfig {
             max_concurrent_machine_updates_percent: None,
         },
         max_find_by_ids: default_max_find_by_ids(),
+        max_instance_allocation_batch_size: default_max_instance_allocation_batch_size(),
         network_security_group: NetworkSecurityGroupConfig::default(),
         min_dpu_functioning_links: None,
         dpu_network_monitor_pinger_type: None,
@@ -291,6 +293,7 @@ pub fn get() -> CarbideConfig {
             enabled: true,
             run_interval: std::time::Duration::from_secs(10),
         },
+        rack_power_budget: RackPowerBudgetConfig::default(),
         machine_validation_config: MachineValidationConfig {
             enabled: true,
             ..MachineValidationConfig::default()
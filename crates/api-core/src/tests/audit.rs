@@ -0,0 +1,104 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use common::api_fixtures::create_test_env;
+use model::audit::{AuditEventSource, NewAuditEvent};
+use rpc::forge::forge_server::Forge;
+
+use crate::tests::common;
+
+fn event(method: &str, target_id: &str) -> NewAuditEvent {
+    NewAuditEvent {
+        occurred_at: chrono::Utc::now(),
+        source: AuditEventSource::Grpc,
+        method: method.to_string(),
+        principals: vec!["spiffe-service-id/carbide-admin-cli".to_string()],
+        target_ids: vec![target_id.to_string()],
+        request_digest: format!("{method} {target_id}"),
+        outcome: "Ok".to_string(),
+        outcome_message: None,
+        client_address: Some("10.0.0.1:41234".to_string()),
+    }
+}
+
+#[crate::sqlx_test]
+async fn test_find_audit_events_filters_and_pages(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    {
+        let mut txn = env.pool.begin().await?;
+        db::audit::insert(
+            txn.as_mut(),
+            &[
+                event("SetMaintenance", "target-a"),
+                event("SetMaintenance", "target-b"),
+                event("AdminForceDeleteMachine", "target-a"),
+            ],
+        )
+        .await?;
+        txn.commit().await?;
+    }
+
+    let events = env
+        .api
+        .find_audit_events(tonic::Request::new(rpc::forge::FindAuditEventsRequest {
+            target_id: Some("target-a".to_string()),
+            ..Default::default()
+        }))
+        .await?
+        .into_inner()
+        .events;
+    let methods: Vec<_> = events.iter().map(|e| e.method.as_str()).collect();
+    assert_eq!(methods, ["AdminForceDeleteMachine", "SetMaintenance"]);
+    assert_eq!(events[0].source(), rpc::forge::AuditEventSource::Grpc);
+
+    let older = env
+        .api
+        .find_audit_events(tonic::Request::new(rpc::forge::FindAuditEventsRequest {
+            before_id: Some(events[0].id),
+            limit: Some(1),
+            ..Default::default()
+        }))
+        .await?
+        .into_inner()
+        .events;
+    assert_eq!(older.len(), 1);
+    assert_eq!(older[0].target_ids, ["target-b"]);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_find_audit_events_rejects_out_of_range_limit(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+
+    for limit in [0, 1001] {
+        let status = env
+            .api
+            .find_audit_events(tonic::Request::new(rpc::forge::FindAuditEventsRequest {
+                limit: Some(limit),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    Ok(())
+}
@@ -33,6 +33,7 @@ use carbide_ipmi::IPMITool;
 use carbide_machine_controller::config::machine_validation::MachineValidationConfig;
 use carbide_machine_controller::context::MachineStateHandlerServices;
 use carbide_machine_controller::dpf::DpfOperations;
+use carbide_machine_controller::dry_run::MachineStateExplainer;
 use carbide_machine_controller::handler::{
     MachineStateHandler, MachineStateHandlerBuilder, PowerOptionConfig, ReachabilityParams,
 };
@@ -345,6 +346,7 @@ impl TestEnv {
             ),
             per_object_metrics_registry: self.per_object_metrics_registry(),
             per_object_info: None,
+            dry_run: None,
         }
     }
 
@@ -1525,40 +1527,50 @@ pub(in crate::tests) async fn create_test_env_with_overrides(
         std::time::Duration::from_secs(60),
     );
 
+    let machine_state_handler_services = Arc::new(MachineStateHandlerServices {
+        db_pool: db_pool.clone(),
+        db_reader: db_pool.clone().into(),
+        redfish_client_pool: redfish_sim.clone(),
+        ipmi_tool: ipmi_tool.clone(),
+        site_config: config.machine_state_handler_site_config().into(),
+        component_manager: test_component_manager.clone(),
+        credential_manager: credential_manager.clone(),
+        bmc_rotation_gate: carbide_credential_rotation::RotationGate::new_for_family(
+            db::credential_rotation::CredentialRotationType::Bmc,
+        ),
+        host_uefi_rotation_gate: carbide_credential_rotation::RotationGate::new_for_family(
+            db::credential_rotation::CredentialRotationType::HostUefi,
+        ),
+        dpu_uefi_rotation_gate: carbide_credential_rotation::RotationGate::new_for_family(
+            db::credential_rotation::CredentialRotationType::DpuUefi,
+        ),
+        per_object_metrics_registry: per_object_metrics_registry.clone(),
+        per_object_info: None,
+        dry_run: None,
+    });
+    let machine_state_controller_io = Arc::new(MachineStateControllerIO {
+        host_health: config.host_health,
+        sla_config: model::machine::slas::MachineSlaConfig::new(
+            config.machine_state_controller.failure_retry_time,
+        ),
+    });
+    // Explains with the handler the environment starts with. Handlers swapped
+    // in later through `machine_state_handler` are not picked up.
+    api.machine_state_explainer
+        .set(Arc::new(MachineStateExplainer::new(
+            Arc::new(machine_swap.inner.lock().await.clone()),
+            machine_state_handler_services.clone(),
+            machine_state_controller_io.clone(),
+        )))
+        .unwrap_or_else(|_| panic!("machine state explainer already initialized"));
+
     let machine_controller = StateController::<MachineStateControllerIO>::builder()
         .database(db_pool.clone(), api.work_lock_manager_handle.clone())
         .meter("carbide_machines", test_meter.meter())
         .processor_id(state_controller_id.clone())
-        .services(
-            MachineStateHandlerServices {
-                db_pool: db_pool.clone(),
-                db_reader: db_pool.clone().into(),
-                redfish_client_pool: redfish_sim.clone(),
-                ipmi_tool: ipmi_tool.clone(),
-                site_config: config.machine_state_handler_site_config().into(),
-                component_manager: test_component_manager.clone(),
-                credential_manager: credential_manager.clone(),
-                bmc_rotation_gate: carbide_credential_rotation::RotationGate::new_for_family(
-                    db::credential_rotation::CredentialRotationType::Bmc,
-                ),
-                host_uefi_rotation_gate: carbide_credential_rotation::RotationGate::new_for_family(
-                    db::credential_rotation::CredentialRotationType::HostUefi,
-                ),
-                dpu_uefi_rotation_gate: carbide_credential_rotation::RotationGate::new_for_family(
-                    db::credential_rotation::CredentialRotationType::DpuUefi,
-                ),
-                per_object_metrics_registry: per_object_metrics_registry.clone(),
-                per_object_info: None,
-            }
-            .into(),
-        )
+        .services(machine_state_handler_services.clone())
         .state_handler(Arc::new(machine_swap.clone()))
-        .io(Arc::new(MachineStateControllerIO {
-            host_health: config.host_health,
-            sla_config: model::machine::slas::MachineSlaConfig::new(
-                config.machine_state_controller.failure_retry_time,
-            ),
-        }))
+        .io(machine_state_controller_io.clone())
         .build_for_manual_iterations(cancel_token.clone())
         .expect("Unable to build state controller");
 
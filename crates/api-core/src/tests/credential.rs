@@ -22,7 +22,7 @@ use carbide_secrets::credentials::{
 use rpc::forge::forge_server::Forge;
 use rpc::forge::{
     CredentialCreationRequest, CredentialDeletionRequest, CredentialType as RpcCredentialType,
-    GetBmcCredentialsRequest,
+    GetBmcCredentialsRequest, RevokeCertificateRequest,
 };
 use tonic::Code;
 
@@ -395,3 +395,36 @@ async fn test_missing_default_credentials(pool: sqlx::PgPool) {
         "expected no missing defaults, got {missing:?}"
     );
 }
+
+#[crate::sqlx_test]
+async fn test_revoke_certificate_validates_serial_number(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+
+    for serial_number in ["", "not hex", "00:00"] {
+        let err = env
+            .api
+            .revoke_certificate(tonic::Request::new(RevokeCertificateRequest {
+                serial_number: serial_number.to_string(),
+            }))
+            .await
+            .expect_err("invalid serial numbers should be rejected");
+        assert_eq!(err.code(), Code::InvalidArgument, "{serial_number:?}");
+    }
+
+    // The test certificate provider, like Vault, signs no revocation list, so
+    // the revocation is refused rather than stored unpublished.
+    let err = env
+        .api
+        .revoke_certificate(tonic::Request::new(RevokeCertificateRequest {
+            serial_number: "1f:2e:3d".to_string(),
+        }))
+        .await
+        .expect_err("the provider cannot revoke certificates");
+    assert_eq!(err.code(), Code::FailedPrecondition);
+    assert!(
+        db::certificate_revocation::find_all(&env.pool)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
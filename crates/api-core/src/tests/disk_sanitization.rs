@@ -0,0 +1,284 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::Arc;
+
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use common::api_fixtures::{
+    TestEnv, TestEnvOverrides, create_managed_host, create_test_env,
+    create_test_env_with_overrides, get_config,
+};
+use rpc::forge::forge_server::Forge;
+use rpc::node_jwt::NodeJwtMinter;
+use rpc::sanitization_certificate::{
+    DeviceSanitization, NIST_SP_800_88, SampledVerification, SanitizationCertificate,
+    SanitizationMethod, StorageMedia, VerificationVerdict,
+};
+
+use crate::tests::common;
+
+/// A client CA standing in for the site's client-certificate PKI.
+struct ClientCa {
+    dir: tempfile::TempDir,
+    issuer: rcgen::Issuer<'static, rcgen::KeyPair>,
+}
+
+impl ClientCa {
+    fn new() -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let mut params = rcgen::CertificateParams::default();
+        params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+        params
+            .distinguished_name
+            .push(rcgen::DnType::CommonName, "test client root");
+        let key = rcgen::KeyPair::generate().unwrap();
+        let cert = params.clone().self_signed(&key).unwrap();
+        std::fs::write(dir.path().join("ca.pem"), cert.pem()).unwrap();
+        Self {
+            dir,
+            issuer: rcgen::Issuer::new(params, key),
+        }
+    }
+
+    fn root_cafile_path(&self) -> String {
+        self.dir
+            .path()
+            .join("ca.pem")
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// A minter for a client certificate this CA issued to `machine_id`.
+    fn minter_for(&self, machine_id: &MachineId) -> Arc<NodeJwtMinter> {
+        let mut params = rcgen::CertificateParams::default();
+        params.subject_alt_names = vec![rcgen::SanType::URI(
+            rcgen::string::Ia5String::try_from(format!(
+                "spiffe://forge.local/forge-system/machine/{machine_id}"
+            ))
+            .unwrap(),
+        )];
+        params.use_authority_key_identifier_extension = true;
+        params
+            .extended_key_usages
+            .push(rcgen::ExtendedKeyUsagePurpose::ClientAuth);
+        let key = rcgen::KeyPair::generate().unwrap();
+        let cert = params.signed_by(&key, &self.issuer).unwrap();
+        let cert_path = self.dir.path().join(format!("{machine_id}.pem"));
+        let key_path = self.dir.path().join(format!("{machine_id}.key"));
+        std::fs::write(&cert_path, cert.pem()).unwrap();
+        std::fs::write(&key_path, key.serialize_pem()).unwrap();
+        NodeJwtMinter::new(
+            cert_path.to_string_lossy().into_owned(),
+            key_path.to_string_lossy().into_owned(),
+        )
+    }
+}
+
+/// A test env that trusts `client_ca` for client certificates.
+async fn env_trusting(pool: sqlx::PgPool, client_ca: &ClientCa) -> TestEnv {
+    let mut config = get_config();
+    if let Some(tls) = config.tls.as_mut() {
+        tls.root_cafile_path = client_ca.root_cafile_path();
+    }
+    create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await
+}
+
+fn signed_certificate(minter: &NodeJwtMinter, machine_id: &MachineId) -> String {
+    let now = chrono::Utc::now();
+    SanitizationCertificate {
+        machine_id: *machine_id,
+        standard: NIST_SP_800_88.to_string(),
+        devices: vec![DeviceSanitization {
+            device: "/dev/sda".to_string(),
+            media: StorageMedia::Sata,
+            serial_number: "ZA1B2C3D".to_string(),
+            model: "ST4000NM0035".to_string(),
+            firmware_revision: "TN04".to_string(),
+            capacity_bytes: 4_000_787_030_016,
+            method: SanitizationMethod::Purge,
+            technique: "hdparm --security-erase-enhanced (ATA enhanced secure erase)".to_string(),
+            started_at: now,
+            finished_at: now,
+            succeeded: true,
+            error: None,
+            verification: SampledVerification {
+                verdict: VerificationVerdict::Passed,
+                samples: 16,
+                changed: 16,
+                unchanged: 0,
+                inconclusive: 0,
+                note: None,
+            },
+        }],
+    }
+    .sign(minter)
+    .unwrap()
+}
+
+async fn report_cleanup(env: &TestEnv, machine_id: &MachineId, sanitization_certificate: String) {
+    env.api
+        .cleanup_machine_completed(tonic::Request::new(rpc::forge::MachineCleanupInfo {
+            machine_id: Some(*machine_id),
+            sanitization_certificate,
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+}
+
+async fn find(
+    env: &TestEnv,
+    machine_id: Option<MachineId>,
+    instance_id: Option<InstanceId>,
+) -> Result<Vec<rpc::forge::DiskSanitizationCertificate>, tonic::Status> {
+    env.api
+        .find_disk_sanitization_certificates(tonic::Request::new(
+            rpc::forge::DiskSanitizationCertificateQuery {
+                machine_id,
+                instance_id,
+            },
+        ))
+        .await
+        .map(|response| response.into_inner().certificates)
+}
+
+#[crate::sqlx_test]
+async fn test_certificate_completes_the_instance_release(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let client_ca = ClientCa::new();
+    let env = env_trusting(pool, &client_ca).await;
+    let host_id = create_managed_host(&env).await.id;
+    let instance_id = InstanceId::new();
+    {
+        let mut txn = env.pool.begin().await?;
+        db::disk_sanitization::record_release(txn.as_mut(), &host_id, instance_id, "tenant-a")
+            .await?;
+        txn.commit().await?;
+    }
+
+    let pending = find(&env, None, Some(instance_id)).await?;
+    assert_eq!(pending.len(), 1);
+    assert!(pending[0].sanitized_at.is_none());
+    assert!(pending[0].devices.is_empty());
+
+    let signed = signed_certificate(&client_ca.minter_for(&host_id), &host_id);
+    report_cleanup(&env, &host_id, signed.clone()).await;
+
+    let certificates = find(&env, None, Some(instance_id)).await?;
+    assert_eq!(certificates.len(), 1);
+    let certificate = &certificates[0];
+    assert_eq!(certificate.machine_id, Some(host_id));
+    assert_eq!(
+        certificate.tenant_organization_id.as_deref(),
+        Some("tenant-a")
+    );
+    assert!(certificate.sanitized_at.is_some());
+    assert_eq!(
+        certificate.signer,
+        format!("spiffe://forge.local/forge-system/machine/{host_id}")
+    );
+    assert_eq!(certificate.standard, NIST_SP_800_88);
+    assert_eq!(certificate.signed_certificate, signed);
+    assert_eq!(certificate.devices.len(), 1);
+    assert_eq!(certificate.devices[0].serial_number, "ZA1B2C3D");
+    assert_eq!(
+        certificate.devices[0].method(),
+        rpc::forge::DiskSanitizationMethod::Purge
+    );
+    assert_eq!(
+        certificate.devices[0]
+            .verification
+            .as_ref()
+            .map(|verification| verification.verdict()),
+        Some(rpc::forge::DiskSanitizationVerdict::Passed)
+    );
+
+    Ok(())
+}
+
+/// A certificate signed by another machine, or describing one, is dropped
+/// without failing the cleanup report.
+#[crate::sqlx_test]
+async fn test_certificates_not_from_the_machine_are_dropped(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let client_ca = ClientCa::new();
+    let env = env_trusting(pool, &client_ca).await;
+    let host_id = create_managed_host(&env).await.id;
+    let other_id = create_managed_host(&env).await.id;
+
+    let other_minter = client_ca.minter_for(&other_id);
+    report_cleanup(&env, &host_id, signed_certificate(&other_minter, &host_id)).await;
+    report_cleanup(&env, &host_id, signed_certificate(&other_minter, &other_id)).await;
+    report_cleanup(&env, &host_id, "not.a.jws".to_string()).await;
+
+    assert!(find(&env, Some(host_id), None).await?.is_empty());
+
+    Ok(())
+}
+
+/// A certificate whose chain does not lead to the client CA is dropped.
+#[crate::sqlx_test]
+async fn test_certificates_from_another_ca_are_dropped(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = env_trusting(pool, &ClientCa::new()).await;
+    let host_id = create_managed_host(&env).await.id;
+
+    let untrusted = ClientCa::new();
+    report_cleanup(
+        &env,
+        &host_id,
+        signed_certificate(&untrusted.minter_for(&host_id), &host_id),
+    )
+    .await;
+
+    assert!(find(&env, Some(host_id), None).await?.is_empty());
+
+    Ok(())
+}
+
+/// Without client CA roots to check the chain against, no certificate
+/// verifies.
+#[crate::sqlx_test]
+async fn test_certificates_are_dropped_without_client_ca_roots(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let host_id = create_managed_host(&env).await.id;
+
+    let client_ca = ClientCa::new();
+    report_cleanup(
+        &env,
+        &host_id,
+        signed_certificate(&client_ca.minter_for(&host_id), &host_id),
+    )
+    .await;
+
+    assert!(find(&env, Some(host_id), None).await?.is_empty());
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_find_requires_a_filter(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+
+    let status = find(&env, None, None).await.unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+}
@@ -449,6 +449,394 @@ async fn test_dns_ptr(pool: sqlx::PgPool) {
     }
 }
 
+// Custom records are served alongside the address records, the zone apex
+// answers NS from its SOA, and a zone transfer lists the SOA first followed by
+// every record of the domain.
+#[crate::sqlx_test]
+async fn test_dns_custom_records_and_zone_transfer(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    env.create_vpc_and_tenant_segment().await;
+    let api = &env.api;
+
+    let interface = api
+        .discover_dhcp(DhcpDiscovery::builder("FF:FF:FF:FF:FF:FF", "192.0.2.1").tonic_request())
+        .await
+        .unwrap()
+        .into_inner();
+
+    let domain = db::dns::domain::find_by_name(&env.pool, DOMAIN_NAME)
+        .await
+        .unwrap()
+        .remove(0);
+    for (qname, qtype, content) in [
+        (
+            format!("www.{DOMAIN_NAME}"),
+            "CNAME",
+            format!("{}.", interface.fqdn),
+        ),
+        (
+            DOMAIN_NAME.to_string(),
+            "TXT",
+            "\"v=spf1 -all\"".to_string(),
+        ),
+    ] {
+        api.create_dns_custom_record(tonic::Request::new(
+            rpc::protos::dns::CreateDnsCustomRecordRequest {
+                domain_id: Some(domain.id),
+                qname,
+                qtype: qtype.to_string(),
+                ttl: Some(60),
+                content,
+            },
+        ))
+        .await
+        .unwrap();
+    }
+
+    let lookup = |qname: String, qtype: &'static str| async move {
+        api.lookup_record(tonic::Request::new(
+            rpc::protos::dns::DnsResourceRecordLookupRequest {
+                qname,
+                zone_id: "-1".to_string(),
+                local: None,
+                remote: None,
+                qtype: qtype.to_string(),
+                real_remote: None,
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .records
+    };
+
+    let cname = lookup(format!("www.{DOMAIN_NAME}."), "CNAME").await;
+    assert_eq!(cname.len(), 1);
+    assert_eq!(cname[0].qtype, "CNAME");
+    assert_eq!(cname[0].content, format!("{}.", interface.fqdn));
+    assert_eq!(cname[0].ttl, 60);
+
+    let ns = lookup(format!("{DOMAIN_NAME}."), "NS").await;
+    assert_eq!(ns.len(), 1);
+    assert_eq!(ns[0].content, format!("ns1.{DOMAIN_NAME}."));
+
+    let zone = api
+        .get_all_records_for_domain(tonic::Request::new(
+            rpc::protos::dns::GetAllRecordsForDomainRequest {
+                name: format!("{DOMAIN_NAME}."),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .result;
+    assert_eq!(zone[0].qtype, "SOA", "a zone transfer starts with the SOA");
+    assert_eq!(zone[1].qtype, "NS");
+    for (qtype, qname) in [
+        ("CNAME", format!("www.{DOMAIN_NAME}.")),
+        ("TXT", format!("{DOMAIN_NAME}.")),
+    ] {
+        assert!(
+            zone.iter().any(|r| r.qtype == qtype && r.qname == qname),
+            "zone transfer includes the {qtype} record for {qname}"
+        );
+    }
+
+    let status = api
+        .get_all_records_for_domain(tonic::Request::new(
+            rpc::protos::dns::GetAllRecordsForDomainRequest {
+                name: "unknown.example.com".to_string(),
+            },
+        ))
+        .await
+        .expect_err("an unknown zone cannot be transferred");
+    assert_eq!(status.code(), tonic::Code::NotFound);
+}
+
+async fn soa_serial(pool: &sqlx::PgPool, domain_id: carbide_uuid::domain::DomainId) -> u32 {
+    db::dns::domain::find_by_uuid(pool, domain_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .soa
+        .unwrap()
+        .0
+        .serial
+}
+
+// Custom records are managed over the API, and every change to them or to
+// the addresses a domain serves moves the domain's SOA serial forward so
+// secondaries transfer the zone again.
+#[crate::sqlx_test]
+async fn test_dns_custom_record_management_bumps_soa_serial(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    env.create_vpc_and_tenant_segment().await;
+    let api = &env.api;
+
+    let domain = db::dns::domain::find_by_name(&env.pool, DOMAIN_NAME)
+        .await
+        .unwrap()
+        .remove(0);
+
+    let serial = soa_serial(&env.pool, domain.id).await;
+    api.discover_dhcp(DhcpDiscovery::builder("FF:FF:FF:FF:FF:FF", "192.0.2.1").tonic_request())
+        .await
+        .unwrap();
+    let after_address = soa_serial(&env.pool, domain.id).await;
+    assert!(
+        after_address > serial,
+        "a new address record bumps the serial"
+    );
+
+    let created = api
+        .create_dns_custom_record(tonic::Request::new(
+            rpc::protos::dns::CreateDnsCustomRecordRequest {
+                domain_id: Some(domain.id),
+                qname: format!("Mail.{DOMAIN_NAME}."),
+                qtype: "mx".to_string(),
+                ttl: None,
+                content: format!("10 mx1.{DOMAIN_NAME}."),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(created.qname, format!("mail.{DOMAIN_NAME}."));
+    assert_eq!(created.qtype, "MX");
+    let after_create = soa_serial(&env.pool, domain.id).await;
+    assert!(
+        after_create > after_address,
+        "creating a record bumps the serial"
+    );
+
+    let listed = api
+        .find_dns_custom_records(tonic::Request::new(
+            rpc::protos::dns::FindDnsCustomRecordsRequest {
+                domain_id: Some(domain.id),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .records;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, created.id);
+
+    let updated = api
+        .update_dns_custom_record(tonic::Request::new(
+            rpc::protos::dns::UpdateDnsCustomRecordRequest {
+                id: created.id.clone(),
+                ttl: Some(120),
+                content: format!("20 mx2.{DOMAIN_NAME}."),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(updated.ttl, Some(120));
+    assert_eq!(updated.content, format!("20 mx2.{DOMAIN_NAME}."));
+    let after_update = soa_serial(&env.pool, domain.id).await;
+    assert!(
+        after_update > after_create,
+        "updating a record bumps the serial"
+    );
+
+    api.delete_dns_custom_record(tonic::Request::new(
+        rpc::protos::dns::DeleteDnsCustomRecordRequest {
+            id: created.id.clone(),
+        },
+    ))
+    .await
+    .unwrap();
+    let after_delete = soa_serial(&env.pool, domain.id).await;
+    assert!(
+        after_delete > after_update,
+        "deleting a record bumps the serial"
+    );
+
+    let status = api
+        .delete_dns_custom_record(tonic::Request::new(
+            rpc::protos::dns::DeleteDnsCustomRecordRequest { id: created.id },
+        ))
+        .await
+        .expect_err("a deleted record cannot be deleted again");
+    assert_eq!(status.code(), tonic::Code::NotFound);
+
+    // A domain edit that started from a snapshot taken before the record
+    // changes still moves the serial forward.
+    let mut stale = domain.clone();
+    stale.increment_serial();
+    let mut txn = env.pool.begin().await.unwrap();
+    let edited = db::dns::domain::update(&stale, &mut txn).await.unwrap();
+    txn.commit().await.unwrap();
+    assert!(edited.soa.unwrap().0.serial > after_delete);
+}
+
+// Records outside the domain, of address types, or with empty content are
+// rejected before anything is stored.
+#[crate::sqlx_test]
+async fn test_dns_custom_record_validation(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let api = &env.api;
+
+    let domain = db::dns::domain::find_by_name(&env.pool, DOMAIN_NAME)
+        .await
+        .unwrap()
+        .remove(0);
+
+    for (description, qname, qtype, content) in [
+        (
+            "name outside the domain",
+            "www.example.com",
+            "CNAME",
+            "a.example.com.",
+        ),
+        (
+            "name that only ends like the domain",
+            "www.x{DOMAIN_NAME}",
+            "CNAME",
+            "a.example.com.",
+        ),
+        (
+            "address record type",
+            "www.{DOMAIN_NAME}",
+            "A",
+            "192.0.2.10",
+        ),
+        (
+            "empty label",
+            "www..{DOMAIN_NAME}",
+            "CNAME",
+            "a.example.com.",
+        ),
+        ("empty content", "www.{DOMAIN_NAME}", "CNAME", " "),
+        (
+            "MX without a preference",
+            "mail.{DOMAIN_NAME}",
+            "MX",
+            "mx1.example.com.",
+        ),
+        (
+            "SRV without a port",
+            "_sip._tcp.{DOMAIN_NAME}",
+            "SRV",
+            "10 5 sip.example.com.",
+        ),
+        (
+            "TXT with an unterminated quote",
+            "{DOMAIN_NAME}",
+            "TXT",
+            "\"v=spf1 -all",
+        ),
+        (
+            "CNAME target with an overlong label",
+            "www.{DOMAIN_NAME}",
+            "CNAME",
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.example.com.",
+        ),
+    ] {
+        let status = api
+            .create_dns_custom_record(tonic::Request::new(
+                rpc::protos::dns::CreateDnsCustomRecordRequest {
+                    domain_id: Some(domain.id),
+                    qname: qname.replace("{DOMAIN_NAME}", DOMAIN_NAME),
+                    qtype: qtype.to_string(),
+                    ttl: None,
+                    content: content.to_string(),
+                },
+            ))
+            .await
+            .expect_err(description);
+        assert_eq!(status.code(), tonic::Code::InvalidArgument, "{description}");
+    }
+
+    let request = rpc::protos::dns::CreateDnsCustomRecordRequest {
+        domain_id: Some(domain.id),
+        qname: format!("www.{DOMAIN_NAME}"),
+        qtype: "CNAME".to_string(),
+        ttl: None,
+        content: format!("web.{DOMAIN_NAME}."),
+    };
+    api.create_dns_custom_record(tonic::Request::new(request.clone()))
+        .await
+        .unwrap();
+    let status = api
+        .create_dns_custom_record(tonic::Request::new(request.clone()))
+        .await
+        .expect_err("the same record cannot be stored twice");
+    assert_eq!(status.code(), tonic::Code::AlreadyExists);
+
+    // A CNAME is the only record at its name.
+    let txt = rpc::protos::dns::CreateDnsCustomRecordRequest {
+        qname: format!("info.{DOMAIN_NAME}"),
+        qtype: "TXT".to_string(),
+        content: "\"hello\"".to_string(),
+        ..request.clone()
+    };
+    api.create_dns_custom_record(tonic::Request::new(txt.clone()))
+        .await
+        .unwrap();
+    for (description, conflicting) in [
+        (
+            "another record at a CNAME's name",
+            rpc::protos::dns::CreateDnsCustomRecordRequest {
+                qname: request.qname.clone(),
+                ..txt.clone()
+            },
+        ),
+        (
+            "a second CNAME at a CNAME's name",
+            rpc::protos::dns::CreateDnsCustomRecordRequest {
+                content: format!("web2.{DOMAIN_NAME}."),
+                ..request.clone()
+            },
+        ),
+        (
+            "a CNAME at a name with other records",
+            rpc::protos::dns::CreateDnsCustomRecordRequest {
+                qname: txt.qname.clone(),
+                ..request.clone()
+            },
+        ),
+    ] {
+        let status = api
+            .create_dns_custom_record(tonic::Request::new(conflicting))
+            .await
+            .expect_err(description);
+        assert_eq!(
+            status.code(),
+            tonic::Code::FailedPrecondition,
+            "{description}"
+        );
+    }
+
+    // Updated content is checked against the stored record's type.
+    let stored = api
+        .find_dns_custom_records(tonic::Request::new(
+            rpc::protos::dns::FindDnsCustomRecordsRequest {
+                domain_id: Some(domain.id),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .records
+        .into_iter()
+        .find(|record| record.qtype == "CNAME")
+        .unwrap();
+    let status = api
+        .update_dns_custom_record(tonic::Request::new(
+            rpc::protos::dns::UpdateDnsCustomRecordRequest {
+                id: stored.id,
+                ttl: None,
+                content: format!("{}.example.com.", "a".repeat(64)),
+            },
+        ))
+        .await
+        .expect_err("a CNAME target's labels are at most 63 characters");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+}
+
 /// Issue a PTR `lookup_record` query and return the reply records.
 async fn lookup_ptr(
     api: &crate::api::Api,
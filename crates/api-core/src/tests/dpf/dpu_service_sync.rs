@@ -28,6 +28,7 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::time::Duration;
 
+use ::rpc::forge::forge_server::Forge;
 use carbide_dpf::{DpfError, DpuDeploymentType, DpuPhase};
 use carbide_machine_controller::dpf::{DpfOperations, MockDpfOperations};
 use carbide_uuid::machine::MachineId;
@@ -217,6 +218,54 @@ async fn hold_is_released_when_every_dpu_is_current(pool: sqlx::PgPool) {
     );
 }
 
+/// Explaining a host that is ready to be released neither releases its hold
+/// nor completes the pending action; the next real iteration does both.
+#[crate::sqlx_test]
+async fn explaining_a_releasable_host_changes_nothing(pool: sqlx::PgPool) {
+    let fixture = provisioned(
+        pool,
+        Arc::new(AtomicBool::new(false)),
+        Arc::new(AtomicBool::new(false)),
+    )
+    .await;
+    request_sync(&fixture.pool, &fixture.mh.id).await;
+
+    let explanation = fixture
+        .env
+        .api
+        .explain_machine_state_handling(tonic::Request::new(
+            ::rpc::forge::ExplainMachineStateHandlingRequest {
+                machine_id: Some(fixture.mh.id),
+            },
+        ))
+        .await
+        .expect("explain")
+        .into_inner();
+
+    assert!(
+        explanation
+            .side_effects
+            .iter()
+            .any(|effect| effect.operation == "complete pending DPU service sync"),
+        "{:?}",
+        explanation.side_effects
+    );
+    assert_eq!(fixture.calls.hold_releases.load(Ordering::SeqCst), 0);
+    assert!(
+        is_outstanding(&fixture.pool, &fixture.mh.id).await,
+        "explaining must leave the pending action outstanding"
+    );
+
+    timeout(
+        TEST_TIMEOUT,
+        fixture.env.run_machine_state_controller_iteration(),
+    )
+    .await
+    .expect("timed out during state controller iteration");
+    assert_eq!(fixture.calls.hold_releases.load(Ordering::SeqCst), 1);
+    assert!(!is_outstanding(&fixture.pool, &fixture.mh.id).await);
+}
+
 /// A DPU still awaiting reprovisioning keeps its hold, and the pending action
 /// stays outstanding so a later sweep can retry once the DPU is current.
 #[crate::sqlx_test]
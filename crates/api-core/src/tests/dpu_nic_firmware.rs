@@ -29,7 +29,7 @@ use model::machine_update_module::{
 
 use crate::CarbideResult;
 use crate::machine_update_manager::dpu_nic_firmware::DpuNicFirmwareUpdate;
-use crate::machine_update_manager::machine_update_module::MachineUpdateModule;
+use crate::machine_update_manager::machine_update_module::{MachineUpdateModule, UpdateSlots};
 use crate::tests::common;
 use crate::tests::common::api_fixtures::TestManagedHost;
 use crate::tests::common::api_fixtures::test_managed_host::TestManagedHostSnapshots;
@@ -60,7 +60,12 @@ async fn test_start_updates(pool: sqlx::PgPool) -> Result<(), Box<dyn std::error
     let metrics = MetricsCapture::start();
 
     let started_count = dpu_nic_firmware_update
-        .start_updates(&env.pool, 10, &HashSet::default(), &snapshots)
+        .start_updates(
+            &env.pool,
+            &mut UpdateSlots::new(10),
+            &HashSet::default(),
+            &snapshots,
+        )
         .await?;
 
     assert_eq!(started_count.len(), 1);
@@ -121,7 +126,12 @@ async fn test_start_updates_with_multidpu(
         .expect("Failed to create transaction");
 
     let dpus_started = dpu_nic_firmware_update
-        .start_updates(&env.pool, 10, &HashSet::default(), &snapshots)
+        .start_updates(
+            &env.pool,
+            &mut UpdateSlots::new(10),
+            &HashSet::default(),
+            &snapshots,
+        )
         .await?;
 
     assert_eq!(dpus_started.len(), 1);
@@ -171,7 +181,12 @@ async fn test_get_updates_in_progress(
     assert!(updating_count.is_empty());
 
     let started_count = dpu_nic_firmware_update
-        .start_updates(&env.pool, 10, &HashSet::default(), &snapshots)
+        .start_updates(
+            &env.pool,
+            &mut UpdateSlots::new(10),
+            &HashSet::default(),
+            &snapshots,
+        )
         .await?;
 
     let updating_count = dpu_nic_firmware_update
@@ -250,7 +265,12 @@ async fn test_clear_completed_updates(
         .expect("Failed to create transaction");
 
     let started_count = dpu_nic_firmware_update
-        .start_updates(&env.pool, 10, &HashSet::default(), &snapshots)
+        .start_updates(
+            &env.pool,
+            &mut UpdateSlots::new(10),
+            &HashSet::default(),
+            &snapshots,
+        )
         .await?;
 
     assert!(!started_count.contains(&mh.dpu().id));
@@ -370,7 +390,12 @@ async fn test_wrong_version_failures_count_once_per_outcome(
     };
     let snapshots = get_all_snapshots(&env).await;
     dpu_nic_firmware_update
-        .start_updates(&env.pool, 10, &HashSet::default(), &snapshots)
+        .start_updates(
+            &env.pool,
+            &mut UpdateSlots::new(10),
+            &HashSet::default(),
+            &snapshots,
+        )
         .await?;
 
     let land_on_version = |version: &'static str, env: &TestEnv| {
@@ -447,7 +472,7 @@ async fn test_wrong_version_failures_count_once_per_outcome(
     dpu_nic_firmware_update
         .start_updates(
             &env.pool,
-            10,
+            &mut UpdateSlots::new(10),
             &HashSet::default(),
             &get_all_snapshots(&env).await,
         )
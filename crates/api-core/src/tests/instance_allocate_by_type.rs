@@ -0,0 +1,171 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Tests for allocating instances by instance type and count
+
+use ::rpc::forge::forge_server::Forge;
+use common::api_fixtures::instance::{
+    default_os_config, default_tenant_config, single_interface_network_config,
+};
+use common::api_fixtures::{create_managed_host, create_test_env, get_instance_type_fixture_id};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::tests::common;
+
+/// Quarantine one of three hosts of an instance type, then allocate by type.
+/// Expect only the other two hosts to be chosen, and a third instance to be refused.
+#[crate::sqlx_test]
+async fn test_allocate_instances_by_type_skips_quarantined_hosts(
+    _: PgPoolOptions,
+    options: PgConnectOptions,
+) {
+    let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
+    let env = create_test_env(pool).await;
+    let instance_type_id = get_instance_type_fixture_id(&env).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let hosts = [
+        create_managed_host(&env).await.host().id,
+        create_managed_host(&env).await.host().id,
+        create_managed_host(&env).await.host().id,
+    ];
+
+    env.api
+        .associate_machines_with_instance_type(tonic::Request::new(
+            rpc::forge::AssociateMachinesWithInstanceTypeRequest {
+                instance_type_id: instance_type_id.clone(),
+                machine_ids: hosts.iter().map(ToString::to_string).collect(),
+            },
+        ))
+        .await
+        .unwrap();
+    env.api
+        .set_managed_host_quarantine_state(tonic::Request::new(
+            rpc::forge::SetManagedHostQuarantineStateRequest {
+                machine_id: Some(hosts[1]),
+                quarantine_state: Some(rpc::forge::ManagedHostQuarantineState {
+                    mode: rpc::forge::ManagedHostQuarantineMode::BlockAllTraffic as i32,
+                    reason: Some("test".to_string()),
+                }),
+            },
+        ))
+        .await
+        .unwrap();
+
+    let request = |count, placement: rpc::forge::InstancePlacementPolicy| {
+        tonic::Request::new(rpc::forge::InstanceTypeAllocationRequest {
+            instance_type_id: instance_type_id.clone(),
+            count,
+            config: Some(rpc::forge::InstanceConfig {
+                tenant: Some(default_tenant_config()),
+                os: Some(default_os_config()),
+                network: Some(single_interface_network_config(segment_id)),
+                infiniband: None,
+                network_security_group_id: None,
+                dpu_extension_services: None,
+                nvlink: None,
+                spxconfig: None,
+                power_profile: None,
+            }),
+            metadata: Some(rpc::forge::Metadata {
+                name: "by-type".to_string(),
+                description: "".to_string(),
+                labels: vec![],
+            }),
+            placement: placement as i32,
+        })
+    };
+
+    // Test hosts have no rack, so rack placement has nowhere to go.
+    let status = env
+        .api
+        .allocate_instances_by_type(request(1, rpc::forge::InstancePlacementPolicy::SameRack))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+    let response = env
+        .api
+        .allocate_instances_by_type(request(2, rpc::forge::InstancePlacementPolicy::Any))
+        .await
+        .unwrap()
+        .into_inner();
+    let mut allocated: Vec<_> = response
+        .instances
+        .iter()
+        .map(|instance| instance.machine_id.unwrap())
+        .collect();
+    allocated.sort();
+    let mut expected = vec![hosts[0], hosts[2]];
+    expected.sort();
+    assert_eq!(allocated, expected);
+    assert!(
+        response
+            .instances
+            .iter()
+            .all(|instance| instance.instance_type_id.as_ref() == Some(&instance_type_id))
+    );
+
+    let status = env
+        .api
+        .allocate_instances_by_type(request(1, rpc::forge::InstancePlacementPolicy::Any))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+}
+
+#[crate::sqlx_test]
+async fn test_allocate_instances_by_type_rejects_invalid_count(
+    _: PgPoolOptions,
+    options: PgConnectOptions,
+) {
+    let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
+    let env = create_test_env(pool).await;
+    let instance_type_id = get_instance_type_fixture_id(&env).await;
+
+    for count in [
+        0,
+        env.config.max_instance_allocation_batch_size + 1,
+        u32::MAX,
+    ] {
+        let status = env
+            .api
+            .allocate_instances_by_type(tonic::Request::new(
+                rpc::forge::InstanceTypeAllocationRequest {
+                    instance_type_id: instance_type_id.clone(),
+                    count,
+                    ..Default::default()
+                },
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument, "count {count}");
+    }
+
+    // The same limit governs explicit batch allocations.
+    let status = env
+        .api
+        .allocate_instances(tonic::Request::new(
+            rpc::forge::BatchInstanceAllocationRequest {
+                instance_requests: vec![
+                    Default::default();
+                    env.config.max_instance_allocation_batch_size as usize + 1
+                ],
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+}
@@ -218,6 +218,7 @@ async fn test_create_instance_with_ipxe_template_os(_: PgPoolOptions, options: P
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: Some("os-level-userdata".to_string()),
+                user_data_format: rpc::forge::UserDataFormat::CloudInit as i32,
                 ipxe_script: None,
                 ipxe_template_id: Some("ddbf83c0-a753-5fde-96c1-6b74e9c9db10".parse().unwrap()),
                 ipxe_template_parameters: vec![rpc::forge::IpxeTemplateParameter {
@@ -306,6 +307,7 @@ async fn create_os_definition(
                 allow_override: false,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: rpc::forge::UserDataFormat::CloudInit as i32,
                 ipxe_script: None,
                 ipxe_template_id: Some("ddbf83c0-a753-5fde-96c1-6b74e9c9db10".parse().unwrap()),
                 ipxe_template_parameters: vec![IpxeTemplateParameter {
@@ -395,6 +397,7 @@ async fn test_allocate_instance_rejects_not_ready_os(_: PgPoolOptions, options:
                 allow_override: false,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: rpc::forge::UserDataFormat::CloudInit as i32,
                 ipxe_script: None,
                 ipxe_template_id: Some("ddbf83c0-a753-5fde-96c1-6b74e9c9db10".parse().unwrap()),
                 ipxe_template_parameters: vec![IpxeTemplateParameter {
@@ -662,6 +665,7 @@ async fn test_create_instance_with_raw_ipxe_os_and_verify_pxe_rendering(
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: Some("os-level-userdata".to_string()),
+                user_data_format: rpc::forge::UserDataFormat::CloudInit as i32,
                 ipxe_script: Some(raw_script.to_string()),
                 ipxe_template_id: None,
                 ipxe_template_parameters: vec![],
@@ -748,6 +752,7 @@ async fn test_create_instance_with_templated_ipxe_os_with_artifacts_and_verify_p
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: Some("os-level-userdata".to_string()),
+                user_data_format: rpc::forge::UserDataFormat::CloudInit as i32,
                 ipxe_script: None,
                 ipxe_template_id: Some("ea756ddd-add3-5e42-a202-44bfc2d5aac2".parse().unwrap()),
                 ipxe_template_parameters: vec![IpxeTemplateParameter {
@@ -0,0 +1,184 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::machine::MachineId;
+use common::api_fixtures::{TestEnv, create_managed_host, create_test_env};
+use model::bmc_suppression::BmcSuppressionSubsystem;
+use model::machine::{
+    FactoryResetBmcState, FailureCause, FailureDetails, FailureSource,
+    HostPlatformConfigurationState, InstanceState, ManagedHostState,
+};
+use rpc::forge::forge_server::Forge;
+use rpc::forge::{ControllerStateOutcome, ExplainMachineStateHandlingRequest};
+
+use crate::tests::common;
+
+async fn explain(
+    env: &TestEnv,
+    machine_id: MachineId,
+) -> Result<rpc::forge::ExplainMachineStateHandlingResponse, tonic::Status> {
+    env.api
+        .explain_machine_state_handling(tonic::Request::new(ExplainMachineStateHandlingRequest {
+            machine_id: Some(machine_id),
+        }))
+        .await
+        .map(|response| response.into_inner())
+}
+
+#[crate::sqlx_test]
+async fn test_explain_reports_transition_without_taking_it(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+
+    let mut txn = env.db_txn().await;
+    let host = mh.host().db_machine(&mut txn).await;
+    db::machine::update_failure_details(
+        &host,
+        &mut txn,
+        FailureDetails {
+            cause: FailureCause::Discovery {
+                err: "host discovery failed".to_string(),
+            },
+            failed_at: chrono::Utc::now(),
+            source: FailureSource::Scout,
+        },
+    )
+    .await
+    .unwrap();
+    txn.commit().await.unwrap();
+
+    let explanation = explain(&env, mh.id).await.unwrap();
+    let reason = explanation.reason.unwrap();
+    assert_eq!(reason.outcome, ControllerStateOutcome::Transition as i32);
+    assert!(reason.source_ref.unwrap().file.ends_with(".rs"));
+    let next_state: ManagedHostState =
+        serde_json::from_str(&explanation.next_state.unwrap()).unwrap();
+    assert!(matches!(next_state, ManagedHostState::Failed { .. }));
+    let current_state: ManagedHostState = serde_json::from_str(&explanation.state).unwrap();
+    assert_eq!(current_state, ManagedHostState::Ready);
+    assert_eq!(
+        explanation.state_version,
+        host.current_version().version_string()
+    );
+    assert!(
+        explanation
+            .side_effects
+            .iter()
+            .any(|effect| effect.service == "database"
+                && effect.operation == "persist controller state"),
+        "{:?}",
+        explanation.side_effects
+    );
+
+    // Nothing was persisted, so the real iteration still sees the failure.
+    let mut txn = env.db_txn().await;
+    let host = mh.host().db_machine(&mut txn).await;
+    txn.rollback().await.unwrap();
+    assert_eq!(host.current_state(), &ManagedHostState::Ready);
+    assert_eq!(
+        host.current_version().version_string(),
+        explanation.state_version
+    );
+
+    env.run_machine_state_controller_iteration().await;
+    let mut txn = env.db_txn().await;
+    let host = mh.host().db_machine(&mut txn).await;
+    txn.rollback().await.unwrap();
+    assert!(matches!(
+        host.current_state(),
+        ManagedHostState::Failed { .. }
+    ));
+}
+
+#[crate::sqlx_test]
+async fn test_explain_rejects_dpus(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+
+    let err = explain(&env, mh.dpu().id).await.unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+}
+
+/// The factory-reset flow suppresses site-explorer on a transaction of its own
+/// rather than the one it returns. Explaining it must record the suppression
+/// without creating it.
+#[crate::sqlx_test]
+async fn test_explain_does_not_suppress_site_explorer(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+    mh.instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build()
+        .await;
+
+    let mut txn = env.db_txn().await;
+    let host = mh.host().db_machine(&mut txn).await;
+    txn.rollback().await.unwrap();
+    let host_bmc_mac = host.status.bmc_info.mac.unwrap();
+
+    let state = ManagedHostState::Assigned {
+        instance_state: InstanceState::HostPlatformConfiguration {
+            platform_config_state: HostPlatformConfigurationState::FactoryResetBmc {
+                reset_state: FactoryResetBmcState::SuppressExploration,
+            },
+        },
+    };
+    sqlx::query(
+        "UPDATE machines SET controller_state = $1, controller_state_outcome = NULL WHERE id = $2",
+    )
+    .bind(sqlx::types::Json(serde_json::to_value(&state).unwrap()))
+    .bind(mh.id)
+    .execute(&env.pool)
+    .await
+    .unwrap();
+
+    let explanation = explain(&env, mh.id).await.unwrap();
+    assert!(
+        explanation
+            .side_effects
+            .iter()
+            .any(|effect| effect.service == "database"
+                && effect.operation == "suppress site-explorer"
+                && effect.target == host_bmc_mac.to_string()),
+        "{:?}",
+        explanation.side_effects
+    );
+    assert!(
+        db::bmc_suppression::find(
+            &env.pool,
+            host_bmc_mac,
+            BmcSuppressionSubsystem::SiteExplorer
+        )
+        .await
+        .unwrap()
+        .is_none(),
+        "explaining must not create the suppression"
+    );
+
+    env.run_machine_state_controller_iteration().await;
+    assert!(
+        db::bmc_suppression::find(
+            &env.pool,
+            host_bmc_mac,
+            BmcSuppressionSubsystem::SiteExplorer
+        )
+        .await
+        .unwrap()
+        .is_some()
+    );
+}
@@ -17,6 +17,7 @@
 
 use std::str::FromStr;
 
+use carbide_uuid::machine::MachineId;
 use db::machine::update_dpu_agent_health_report;
 use db::{self};
 use health_report::HealthReportApplyMode;
@@ -769,6 +770,40 @@ async fn test_double_insert(pool: sqlx::PgPool) -> Result<(), Box<dyn std::error
     Ok(())
 }
 
+/// Health report overrides change what the state handler decides, so writing
+/// one wakes up the managed host it applies to, even if it targets a DPU.
+#[crate::sqlx_test]
+async fn test_health_report_override_enqueues_host(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_env(pool).await;
+
+    let (host_machine_id, dpu_machine_id) = create_managed_host(&env).await.into();
+
+    dequeue_machine(&env, &host_machine_id).await;
+    env.api
+        .insert_machine_health_report(Request::new(rpc::forge::InsertMachineHealthReportRequest {
+            machine_id: Some(dpu_machine_id),
+            health_report_entry: Some(rpc::forge::HealthReportEntry {
+                report: Some(hr("over", vec![], vec![("Fan", None, "")]).into()),
+                mode: health_report::HealthReportApplyMode::Merge as i32,
+            }),
+        }))
+        .await?;
+    assert!(is_machine_queued(&env, &host_machine_id).await);
+
+    dequeue_machine(&env, &host_machine_id).await;
+    env.api
+        .remove_machine_health_report(Request::new(rpc::forge::RemoveMachineHealthReportRequest {
+            machine_id: Some(dpu_machine_id),
+            source: "over".to_string(),
+        }))
+        .await?;
+    assert!(is_machine_queued(&env, &host_machine_id).await);
+
+    Ok(())
+}
+
 /// A continuously-firing alert re-reported through `InsertMachineHealthReport`
 /// must keep the `in_alert_since` of its first occurrence, so operators can tell
 /// how long the condition has lasted. New alerts appearing in a later report
@@ -1140,6 +1175,26 @@ async fn load_health_alerts_by_time_range(
 }
 
 /// Inserts health report and processes it via state controller
+async fn dequeue_machine(env: &TestEnv, host_machine_id: &MachineId) {
+    sqlx::query("DELETE FROM machine_state_controller_queued_objects WHERE object_id = $1")
+        .bind(host_machine_id.to_string())
+        .execute(&env.pool)
+        .await
+        .unwrap();
+}
+
+async fn is_machine_queued(env: &TestEnv, host_machine_id: &MachineId) -> bool {
+    sqlx::query_scalar(
+        "SELECT EXISTS (
+            SELECT 1 FROM machine_state_controller_queued_objects WHERE object_id = $1
+        )",
+    )
+    .bind(host_machine_id.to_string())
+    .fetch_one(&env.pool)
+    .await
+    .unwrap()
+}
+
 async fn insert_health_and_process(
     env: &TestEnv,
     machine_id: &::carbide_uuid::machine::MachineId,
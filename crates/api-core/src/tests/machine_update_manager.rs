@@ -36,7 +36,7 @@ use tokio_util::sync::CancellationToken;
 
 use crate::cfg::file::CarbideConfig;
 use crate::machine_update_manager::MachineUpdateManager;
-use crate::machine_update_manager::machine_update_module::MachineUpdateModule;
+use crate::machine_update_manager::machine_update_module::{MachineUpdateModule, UpdateSlots};
 use crate::tests::common;
 use crate::tests::common::api_fixtures::create_managed_host;
 use crate::{CarbideError, CarbideResult};
@@ -63,7 +63,7 @@ impl MachineUpdateModule for TestUpdateModule {
     async fn start_updates(
         &self,
         _pool: &sqlx::Pool<sqlx::Postgres>,
-        _available_updates: i32,
+        _slots: &mut UpdateSlots,
         _updating_machines: &HashSet<MachineId>,
         _snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
     ) -> CarbideResult<HashSet<MachineId>> {
@@ -15,10 +15,12 @@
  * limitations under the License.
  */
 
+mod audit;
 mod boot_interface_resolution;
 mod client_resolution;
 pub(in crate::tests) mod common;
 mod credential;
+mod disk_sanitization;
 mod dns;
 mod dpa_interfaces;
 mod dpf;
@@ -43,6 +45,7 @@ mod ib_partition_find;
 mod ib_partition_lifecycle;
 mod instance;
 mod instance_allocate;
+mod instance_allocate_by_type;
 mod instance_batch_allocate;
 mod instance_config_update;
 mod instance_find;
@@ -57,6 +60,7 @@ mod machine_admin_force_delete;
 mod machine_boot_override;
 mod machine_dhcp;
 mod machine_discovery;
+mod machine_explain;
 mod machine_find;
 mod machine_health;
 mod machine_history;
@@ -90,6 +94,8 @@ mod site_explorer;
 mod site_prefix;
 mod sku;
 mod spdm;
+mod state_analytics;
+mod state_watch;
 mod switch;
 mod switch_health;
 mod switch_state_controller;
@@ -23,6 +23,7 @@ use carbide_uuid::vpc::VpcId;
 use config_version::ConfigVersion;
 use model::instance::config::network::DeviceLocator;
 use model::metadata::Metadata;
+use model::network_security_group::NetworkSecurityGroupRuleNet;
 use model::test_support::{DpuConfig, ManagedHostConfig};
 use rpc::forge::forge_server::Forge;
 use rpc::health::HealthReport;
@@ -31,6 +32,7 @@ use uuid::uuid;
 
 use super::common::api_fixtures::TestEnv;
 use crate::cfg::file::default_max_network_security_group_size;
+use crate::network_security_group::reference_refresher::NetworkSecurityGroupReferenceRefresher;
 use crate::test_support::fixture_config::FixtureDefault as _;
 use crate::tests::common::api_fixtures::instance::{
     default_os_config, default_tenant_config, interface_network_config_with_devices,
@@ -1888,3 +1890,450 @@ async fn test_network_security_group_get_attachments(
 
     Ok(())
 }
+
+fn reference_rule(
+    source_net: rpc::forge::network_security_group_rule_attributes::SourceNet,
+) -> rpc::forge::NetworkSecurityGroupAttributes {
+    rpc::forge::NetworkSecurityGroupAttributes {
+        stateful_egress: false,
+        rules: vec![rpc::forge::NetworkSecurityGroupRuleAttributes {
+            id: Some("from_vpc".to_string()),
+            direction: rpc::forge::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress
+                .into(),
+            ipv6: false,
+            src_port_start: None,
+            src_port_end: None,
+            dst_port_start: Some(443),
+            dst_port_end: Some(443),
+            protocol: rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp.into(),
+            action: rpc::forge::NetworkSecurityGroupRuleAction::NsgRuleActionPermit.into(),
+            priority: 100,
+            source_net: Some(source_net),
+            destination_net: Some(
+                rpc::forge::network_security_group_rule_attributes::DestinationNet::DstPrefix(
+                    "0.0.0.0/0".to_string(),
+                ),
+            ),
+        }],
+    }
+}
+
+async fn network_security_group_version(env: &TestEnv, id: &str) -> String {
+    env.api
+        .find_network_security_groups_by_ids(tonic::Request::new(
+            rpc::forge::FindNetworkSecurityGroupsByIdsRequest {
+                network_security_group_ids: vec![id.to_string()],
+                tenant_organization_id: None,
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .network_security_groups
+        .pop()
+        .unwrap()
+        .version
+}
+
+#[crate::sqlx_test]
+async fn test_network_security_group_vpc_reference(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+
+    populate_network_security_groups(env.api.clone()).await;
+
+    // Provided by fixtures
+    let default_tenant_org = "Tenant1";
+    let other_tenant_org = "Tenant2";
+
+    let vpc_id: VpcId = uuid!("6b1c9a32-7a7e-4e3c-9d61-0c8b9e2a4f10").into();
+    let id = "9e0f4bb6-3c1e-4ac4-8d1e-5f37a0b0c0de";
+
+    let segment_id = env
+        .create_vpc_and_tenant_segment_with_vpc_details(
+            VpcCreationRequest::builder(default_tenant_org)
+                .id(vpc_id)
+                .metadata(Metadata {
+                    name: "referenced vpc".to_string(),
+                    ..Default::default()
+                })
+                .rpc(),
+        )
+        .await;
+
+    let from_vpc = reference_rule(
+        rpc::forge::network_security_group_rule_attributes::SourceNet::SrcVpcId(vpc_id.to_string()),
+    );
+
+    // References are only resolved within the tenant, so another
+    // tenant can't reference the VPC.
+    let err = env
+        .api
+        .create_network_security_group(tonic::Request::new(
+            rpc::forge::CreateNetworkSecurityGroupRequest {
+                id: None,
+                tenant_organization_id: other_tenant_org.to_string(),
+                metadata: Some(rpc::forge::Metadata {
+                    name: "other tenant".to_string(),
+                    ..Default::default()
+                }),
+                network_security_group_attributes: Some(from_vpc.clone()),
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::NotFound);
+
+    let _ = env
+        .api
+        .create_network_security_group(tonic::Request::new(
+            rpc::forge::CreateNetworkSecurityGroupRequest {
+                id: Some(id.to_string()),
+                tenant_organization_id: default_tenant_org.to_string(),
+                metadata: Some(rpc::forge::Metadata {
+                    name: "from vpc".to_string(),
+                    ..Default::default()
+                }),
+                network_security_group_attributes: Some(from_vpc),
+            },
+        ))
+        .await
+        .unwrap();
+
+    let refresher = NetworkSecurityGroupReferenceRefresher::new(
+        env.pool.clone(),
+        env.config.network_security_group.clone(),
+        env.api.work_lock_manager_handle.clone(),
+        env.test_meter.meter(),
+    );
+
+    // The first pass only records what the references resolve to.
+    let created_version = network_security_group_version(&env, id).await;
+    assert_eq!(refresher.run_single_iteration().await?, 0);
+    assert_eq!(
+        network_security_group_version(&env, id).await,
+        created_version
+    );
+
+    // Adding an instance to the VPC changes the membership,
+    // so the next pass bumps the version.
+    let mh = site_explorer::new_host(&env, ManagedHostConfig::default())
+        .await
+        .unwrap();
+    let test_managed_host =
+        TestManagedHost::from_rpc_machine(&mh.host_snapshot.clone().into(), env.api.clone());
+    let _instance = test_managed_host
+        .instance_builer(&env)
+        .config(rpc::InstanceConfig {
+            tenant: Some(default_tenant_config()),
+            os: Some(default_os_config()),
+            network: Some(single_interface_network_config(segment_id)),
+            infiniband: None,
+            nvlink: None,
+            spxconfig: None,
+            network_security_group_id: None,
+            dpu_extension_services: None,
+            power_profile: None,
+        })
+        .build()
+        .await;
+
+    let mut txn = env.pool.begin().await?;
+    let resolved = db::network_security_group::resolve_references(
+        &mut txn,
+        &default_tenant_org.parse().unwrap(),
+        &[NetworkSecurityGroupRuleNet::VpcId(vpc_id)],
+    )
+    .await?;
+    txn.rollback().await?;
+    let prefixes = resolved
+        .get(&NetworkSecurityGroupRuleNet::VpcId(vpc_id))
+        .unwrap();
+    assert_eq!(prefixes.len(), 1);
+    assert!(prefixes.iter().all(|p| p.prefix() == 32));
+
+    assert_eq!(refresher.run_single_iteration().await?, 1);
+    let bumped_version = network_security_group_version(&env, id).await;
+    assert_ne!(bumped_version, created_version);
+
+    // Nothing changed since the last pass.
+    assert_eq!(refresher.run_single_iteration().await?, 0);
+    assert_eq!(
+        network_security_group_version(&env, id).await,
+        bumped_version
+    );
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_network_security_group_reference_over_limit(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+
+    // Provided by fixtures
+    let default_tenant_org = "Tenant1";
+
+    let vpc_id: VpcId = uuid!("0d3f5c8e-2b7a-4f1e-9c6d-8a4e1b2c3d5f").into();
+    let id = "5a2e8c1d-7f4b-4e3a-b6d9-1c0f2e3a4b5c";
+
+    let segment_id = env
+        .create_vpc_and_tenant_segment_with_vpc_details(
+            VpcCreationRequest::builder(default_tenant_org)
+                .id(vpc_id)
+                .metadata(Metadata {
+                    name: "referenced vpc".to_string(),
+                    ..Default::default()
+                })
+                .rpc(),
+        )
+        .await;
+
+    // Every address in the VPC expands to two rules.
+    let mut from_vpc = reference_rule(
+        rpc::forge::network_security_group_rule_attributes::SourceNet::SrcVpcId(vpc_id.to_string()),
+    );
+    from_vpc.rules[0].dst_port_end = Some(444);
+
+    let _ = env
+        .api
+        .create_network_security_group(tonic::Request::new(
+            rpc::forge::CreateNetworkSecurityGroupRequest {
+                id: Some(id.to_string()),
+                tenant_organization_id: default_tenant_org.to_string(),
+                metadata: Some(rpc::forge::Metadata {
+                    name: "from vpc".to_string(),
+                    ..Default::default()
+                }),
+                network_security_group_attributes: Some(from_vpc),
+            },
+        ))
+        .await
+        .unwrap();
+
+    let mut config = env.config.network_security_group.clone();
+    config.max_network_security_group_size = 1;
+    let refresher = NetworkSecurityGroupReferenceRefresher::new(
+        env.pool.clone(),
+        config,
+        env.api.work_lock_manager_handle.clone(),
+        env.test_meter.meter(),
+    );
+
+    assert_eq!(refresher.run_single_iteration().await?, 0);
+    let created_version = network_security_group_version(&env, id).await;
+
+    // A single instance in the VPC pushes the expansion past the limit.
+    let mh = site_explorer::new_host(&env, ManagedHostConfig::default())
+        .await
+        .unwrap();
+    let test_managed_host =
+        TestManagedHost::from_rpc_machine(&mh.host_snapshot.clone().into(), env.api.clone());
+    let _instance = test_managed_host
+        .instance_builer(&env)
+        .config(rpc::InstanceConfig {
+            tenant: Some(default_tenant_config()),
+            os: Some(default_os_config()),
+            network: Some(single_interface_network_config(segment_id)),
+            infiniband: None,
+            nvlink: None,
+            spxconfig: None,
+            network_security_group_id: None,
+            dpu_extension_services: None,
+            power_profile: None,
+        })
+        .build()
+        .await;
+
+    // The change is not published and DPUs keep the last valid expansion.
+    assert_eq!(refresher.run_single_iteration().await?, 0);
+    assert_eq!(
+        network_security_group_version(&env, id).await,
+        created_version
+    );
+
+    let mut txn = env.pool.begin().await?;
+    let nsg = db::network_security_group::find_by_ids(&mut txn, &[id.parse()?], None, false)
+        .await?
+        .pop()
+        .unwrap();
+    let resolved = db::network_security_group::find_resolved_references(&mut txn, &nsg).await?;
+    txn.rollback().await?;
+    assert_eq!(
+        resolved.get(&NetworkSecurityGroupRuleNet::VpcId(vpc_id)),
+        Some(&vec![])
+    );
+
+    assert_eq!(
+        env.test_meter
+            .formatted_metric("carbide_network_security_group_references_over_limit_count")
+            .unwrap(),
+        "1"
+    );
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_network_security_group_simulate_flow(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+
+    // Provided by fixtures
+    let default_tenant_org = "Tenant1";
+
+    let id = "3f7d2a5e-8c41-4b7a-9e6d-2a1b0c9d8e7f";
+
+    let mut attributes = reference_rule(
+        rpc::forge::network_security_group_rule_attributes::SourceNet::SrcPrefix(
+            "0.0.0.0/0".to_string(),
+        ),
+    );
+    attributes.rules[0].id = Some("allow_https".to_string());
+
+    let _ = env
+        .api
+        .create_network_security_group(tonic::Request::new(
+            rpc::forge::CreateNetworkSecurityGroupRequest {
+                id: Some(id.to_string()),
+                tenant_organization_id: default_tenant_org.to_string(),
+                metadata: Some(rpc::forge::Metadata {
+                    name: "allow https".to_string(),
+                    ..Default::default()
+                }),
+                network_security_group_attributes: Some(attributes),
+            },
+        ))
+        .await
+        .unwrap();
+
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+
+    let mh = site_explorer::new_host(&env, ManagedHostConfig::default())
+        .await
+        .unwrap();
+    let test_managed_host =
+        TestManagedHost::from_rpc_machine(&mh.host_snapshot.clone().into(), env.api.clone());
+    let instance = test_managed_host
+        .instance_builer(&env)
+        .config(rpc::InstanceConfig {
+            tenant: Some(default_tenant_config()),
+            os: Some(default_os_config()),
+            network: Some(single_interface_network_config(segment_id)),
+            infiniband: None,
+            nvlink: None,
+            spxconfig: None,
+            network_security_group_id: Some(id.to_string()),
+            dpu_extension_services: None,
+            power_profile: None,
+        })
+        .build()
+        .await;
+
+    let simulate = |protocol: rpc::forge::NetworkSecurityGroupRuleProtocol, dst_port: u32| {
+        rpc::forge::SimulateNetworkSecurityGroupFlowRequest {
+            source: Some(rpc::forge::NetworkSecurityGroupFlowEndpoint {
+                instance_id: None,
+                ip: Some("203.0.113.7".to_string()),
+            }),
+            destination: Some(rpc::forge::NetworkSecurityGroupFlowEndpoint {
+                instance_id: Some(instance.id.to_string()),
+                ip: None,
+            }),
+            protocol: protocol.into(),
+            src_port: Some(40000),
+            dst_port: Some(dst_port),
+            ipv6: false,
+        }
+    };
+
+    // HTTPS to the instance is permitted by the NSG of the instance.
+    let simulation = env
+        .api
+        .simulate_network_security_group_flow(tonic::Request::new(simulate(
+            rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp,
+            443,
+        )))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(
+        simulation.decision(),
+        rpc::forge::NetworkSecurityGroupFlowDecision::NsgFlowDecisionPermit
+    );
+    assert_eq!(simulation.evaluations.len(), 1);
+
+    let evaluation = &simulation.evaluations[0];
+    assert_eq!(evaluation.instance_id, instance.id.to_string());
+    assert_eq!(
+        evaluation.direction(),
+        rpc::forge::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress
+    );
+    assert_eq!(
+        evaluation.network_security_group.as_ref().unwrap().source(),
+        rpc::forge::NetworkSecurityGroupSource::NsgSourceInstance
+    );
+
+    let step = evaluation.steps.last().unwrap();
+    assert_eq!(
+        step.stage(),
+        rpc::forge::NetworkSecurityGroupFlowStage::NsgFlowStageNetworkSecurityGroup
+    );
+    assert_eq!(
+        step.rule.as_ref().unwrap().rule.as_ref().unwrap().id,
+        Some("allow_https".to_string())
+    );
+
+    // Anything else falls through to the default deny.
+    let simulation = env
+        .api
+        .simulate_network_security_group_flow(tonic::Request::new(simulate(
+            rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp,
+            22,
+        )))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(
+        simulation.decision(),
+        rpc::forge::NetworkSecurityGroupFlowDecision::NsgFlowDecisionDeny
+    );
+    assert_eq!(
+        simulation.evaluations[0].steps.last().unwrap().stage(),
+        rpc::forge::NetworkSecurityGroupFlowStage::NsgFlowStageDefault
+    );
+
+    // A flow needs a specific protocol.
+    let err = env
+        .api
+        .simulate_network_security_group_flow(tonic::Request::new(simulate(
+            rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoAny,
+            443,
+        )))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    // At least one end of the flow must be an instance.
+    let mut request = simulate(
+        rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp,
+        443,
+    );
+    request.destination = Some(rpc::forge::NetworkSecurityGroupFlowEndpoint {
+        instance_id: None,
+        ip: Some("198.51.100.1".to_string()),
+    });
+    let err = env
+        .api
+        .simulate_network_security_group_flow(tonic::Request::new(request))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    Ok(())
+}
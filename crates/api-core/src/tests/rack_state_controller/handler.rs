@@ -181,6 +181,7 @@ pub(crate) fn config_with_rack_profiles() -> crate::cfg::file::CarbideConfig {
                     rack_hardware_class: Some(RackHardwareClass::Prod),
                     attributes: Default::default(),
                     rack_capabilities: simple_capabilities(),
+                    power_budget: None,
                 },
             ),
             (
@@ -193,6 +194,7 @@ pub(crate) fn config_with_rack_profiles() -> crate::cfg::file::CarbideConfig {
                     rack_hardware_class: Some(RackHardwareClass::Prod),
                     attributes: Default::default(),
                     rack_capabilities: single_capabilities(),
+                    power_budget: None,
                 },
             ),
             ("Empty".to_string(), RackProfile::default()),
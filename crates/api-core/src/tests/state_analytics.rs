@@ -0,0 +1,106 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use common::api_fixtures::{create_managed_host, create_test_env};
+use rpc::forge::forge_server::Forge;
+use rpc::forge::{StateAnalyticsObjectType, StateDwellAnalyticsRequest};
+
+use crate::tests::common;
+
+#[crate::sqlx_test]
+async fn test_state_dwell_analytics_for_managed_host(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    create_managed_host(&env).await;
+
+    let analytics = env
+        .api
+        .get_state_dwell_analytics(tonic::Request::new(StateDwellAnalyticsRequest {
+            object_type: StateAnalyticsObjectType::Machine.into(),
+            stuck_after: Some(std::time::Duration::ZERO.into()),
+            ..Default::default()
+        }))
+        .await?
+        .into_inner();
+
+    // The host and its DPU share the managed host state.
+    assert_eq!(analytics.object_count, 2);
+    let ready = analytics
+        .states
+        .iter()
+        .find(|stats| stats.state == "ready")
+        .unwrap();
+    assert_eq!((ready.in_progress, ready.stuck), (2, 0));
+    let hostinit = analytics
+        .states
+        .iter()
+        .find(|stats| stats.state == "hostinit")
+        .unwrap();
+    assert!(hostinit.completed >= 2);
+    assert!(analytics.transitions.iter().any(|transition| {
+        transition.from_state == "created"
+            && transition.to_state == "dpudiscoveringstate"
+            && transition.count == 2
+    }));
+    assert!(analytics.stuck_objects.is_empty());
+
+    let other_sku = env
+        .api
+        .get_state_dwell_analytics(tonic::Request::new(StateDwellAnalyticsRequest {
+            object_type: StateAnalyticsObjectType::Machine.into(),
+            sku: Some("no-such-sku".to_string()),
+            ..Default::default()
+        }))
+        .await?
+        .into_inner();
+    assert_eq!(other_sku.object_count, 0);
+    assert!(other_sku.states.is_empty());
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_state_dwell_analytics_rejects_invalid_requests(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let now = chrono::Utc::now();
+
+    for request in [
+        StateDwellAnalyticsRequest::default(),
+        StateDwellAnalyticsRequest {
+            object_type: StateAnalyticsObjectType::Switch.into(),
+            sku: Some("sku".to_string()),
+            ..Default::default()
+        },
+        StateDwellAnalyticsRequest {
+            object_type: StateAnalyticsObjectType::Rack.into(),
+            since: Some(now.into()),
+            until: Some((now - chrono::TimeDelta::hours(1)).into()),
+            ..Default::default()
+        },
+    ] {
+        let status = env
+            .api
+            .get_state_dwell_analytics(tonic::Request::new(request))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use common::api_fixtures::{create_managed_host, create_test_env};
+use db::state_history::StateHistoryTableId;
+use rpc::forge::forge_server::Forge;
+use tokio_stream::StreamExt;
+
+use crate::tests::common;
+
+#[crate::sqlx_test]
+async fn test_watch_machine_states_replays_after_resume_point(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let (host_machine_id, _dpu_machine_id) = create_managed_host(&env).await.into();
+
+    let history = {
+        let mut txn = env.pool.begin().await?;
+        db::state_history::for_object(txn.as_mut(), StateHistoryTableId::Machine, &host_machine_id)
+            .await?
+    };
+    assert!(history.len() > 3);
+
+    let mut stream = env
+        .api
+        .watch_machine_states(tonic::Request::new(rpc::forge::StateWatchRequest {
+            object_ids: vec![host_machine_id.to_string()],
+            resume_from: vec![rpc::forge::StateWatchResumePoint {
+                object_id: host_machine_id.to_string(),
+                state_version: history[2].state_version.version_string(),
+            }],
+        }))
+        .await?
+        .into_inner();
+
+    for expected in &history[3..] {
+        let event = tokio::time::timeout(Duration::from_secs(10), stream.next())
+            .await?
+            .expect("stream ended")?;
+        assert_eq!(event.object_id, host_machine_id.to_string());
+        assert_eq!(
+            event.record.unwrap().version,
+            expected.state_version.version_string()
+        );
+    }
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_watch_all_machine_states_replays_objects_without_resume_point(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let (host_machine_id, dpu_machine_id) = create_managed_host(&env).await.into();
+
+    let (host_history, dpu_history) = {
+        let mut txn = env.pool.begin().await?;
+        (
+            db::state_history::for_object(
+                txn.as_mut(),
+                StateHistoryTableId::Machine,
+                &host_machine_id,
+            )
+            .await?,
+            db::state_history::for_object(
+                txn.as_mut(),
+                StateHistoryTableId::Machine,
+                &dpu_machine_id,
+            )
+            .await?,
+        )
+    };
+    assert!(host_history.len() > 3);
+
+    // Only the host has a resume point. The DPU transitioned within the
+    // replay window, so all of its transitions are replayed too.
+    let mut stream = env
+        .api
+        .watch_machine_states(tonic::Request::new(rpc::forge::StateWatchRequest {
+            object_ids: vec![],
+            resume_from: vec![rpc::forge::StateWatchResumePoint {
+                object_id: host_machine_id.to_string(),
+                state_version: host_history[2].state_version.version_string(),
+            }],
+        }))
+        .await?
+        .into_inner();
+
+    let expected = host_history[3..]
+        .iter()
+        .map(|record| {
+            (
+                host_machine_id.to_string(),
+                record.state_version.version_string(),
+            )
+        })
+        .chain(dpu_history.iter().map(|record| {
+            (
+                dpu_machine_id.to_string(),
+                record.state_version.version_string(),
+            )
+        }))
+        .collect::<HashSet<_>>();
+    let mut received = HashSet::new();
+    while !expected.is_subset(&received) {
+        let event = tokio::time::timeout(Duration::from_secs(10), stream.next())
+            .await?
+            .expect("stream ended")?;
+        received.insert((event.object_id, event.record.unwrap().version));
+    }
+    for processed in &host_history[..3] {
+        assert!(!received.contains(&(
+            host_machine_id.to_string(),
+            processed.state_version.version_string()
+        )));
+    }
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_watch_rejects_resume_point_of_unwatched_object(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let (host_machine_id, dpu_machine_id) = create_managed_host(&env).await.into();
+
+    let result = env
+        .api
+        .watch_machine_states(tonic::Request::new(rpc::forge::StateWatchRequest {
+            object_ids: vec![host_machine_id.to_string()],
+            resume_from: vec![rpc::forge::StateWatchResumePoint {
+                object_id: dpu_machine_id.to_string(),
+                state_version: config_version::ConfigVersion::initial().version_string(),
+            }],
+        }))
+        .await;
+
+    let Err(status) = result else {
+        panic!("expected the watch to be rejected");
+    };
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    Ok(())
+}
@@ -20,7 +20,7 @@ use carbide_uuid::operating_system::OperatingSystemId;
 use rpc::forge::forge_server::Forge;
 use rpc::forge::{
     IpxeTemplateArtifact, IpxeTemplateArtifactCacheStrategy, IpxeTemplateArtifacts,
-    OperatingSystemType, TenantState,
+    OperatingSystemType, TenantState, UserDataFormat,
 };
 use tonic::Code;
 
@@ -40,6 +40,7 @@ async fn test_create_operating_system_ipxe(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: Some("cloud-init data".to_string()),
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: Some("chain --autofree https://boot.netboot.xyz".to_string()),
                 ipxe_template_id: None,
                 ipxe_template_parameters: vec![],
@@ -82,6 +83,7 @@ async fn test_create_operating_system_requires_name(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: Some("chain http://example.com".to_string()),
                 ipxe_template_id: None,
                 ipxe_template_parameters: vec![],
@@ -110,6 +112,7 @@ async fn test_create_operating_system_requires_variant(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: None,
                 ipxe_template_id: None,
                 ipxe_template_parameters: vec![],
@@ -138,6 +141,7 @@ async fn test_create_operating_system_allows_omitted_org(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: Some("chain http://example.com".to_string()),
                 ipxe_template_id: None,
                 ipxe_template_parameters: vec![],
@@ -170,6 +174,7 @@ async fn test_create_operating_system_rejects_empty_org(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: Some("chain http://example.com".to_string()),
                 ipxe_template_id: None,
                 ipxe_template_parameters: vec![],
@@ -198,6 +203,7 @@ async fn test_get_operating_system(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: Some("chain http://boot.example.com".to_string()),
                 ipxe_template_id: None,
                 ipxe_template_parameters: vec![],
@@ -252,6 +258,7 @@ async fn test_update_operating_system(pool: PgPool) {
                 allow_override: false,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: Some("chain http://example.com".to_string()),
                 ipxe_template_id: None,
                 ipxe_template_parameters: vec![],
@@ -275,6 +282,7 @@ async fn test_update_operating_system(pool: PgPool) {
                 allow_override: Some(true),
                 phone_home_enabled: Some(true),
                 user_data: Some("new user-data".to_string()),
+                user_data_format: None,
                 ipxe_script: Some("chain http://updated.example.com".to_string()),
                 ipxe_template_id: None,
                 ipxe_template_parameters: None,
@@ -298,6 +306,77 @@ async fn test_update_operating_system(pool: PgPool) {
     );
 }
 
+#[sqlx_test]
+async fn test_operating_system_user_data_format(pool: PgPool) {
+    let env = TestHarness::builder(pool).build().await;
+
+    let created = env
+        .api()
+        .create_operating_system(tonic::Request::new(
+            rpc::forge::CreateOperatingSystemRequest {
+                id: None,
+                name: "flatcar".to_string(),
+                tenant_organization_id: Some("org1".to_string()),
+                description: None,
+                is_active: true,
+                allow_override: false,
+                phone_home_enabled: false,
+                user_data: Some("variant: flatcar\nversion: 1.1.0\n".to_string()),
+                user_data_format: UserDataFormat::Butane as i32,
+                ipxe_script: Some("chain http://example.com/flatcar.ipxe".to_string()),
+                ipxe_template_id: None,
+                ipxe_template_parameters: vec![],
+                ipxe_template_artifacts: vec![],
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(created.user_data_format, UserDataFormat::Butane as i32);
+
+    let update = |user_data_format: Option<i32>| rpc::forge::UpdateOperatingSystemRequest {
+        id: created.id,
+        name: None,
+        description: None,
+        is_active: None,
+        allow_override: None,
+        phone_home_enabled: None,
+        user_data: None,
+        user_data_format,
+        ipxe_script: None,
+        ipxe_template_id: None,
+        ipxe_template_parameters: None,
+        ipxe_template_artifacts: None,
+        ipxe_template_definition_hash: None,
+    };
+
+    // Leaving the format out keeps the stored one.
+    let unchanged = env
+        .api()
+        .update_operating_system(tonic::Request::new(update(None)))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(unchanged.user_data_format, UserDataFormat::Butane as i32);
+
+    let updated = env
+        .api()
+        .update_operating_system(tonic::Request::new(update(Some(
+            UserDataFormat::Ignition as i32,
+        ))))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(updated.user_data_format, UserDataFormat::Ignition as i32);
+
+    let err = env
+        .api()
+        .update_operating_system(tonic::Request::new(update(Some(42))))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+}
+
 #[sqlx_test]
 async fn test_delete_operating_system(pool: PgPool) {
     let env = TestHarness::builder(pool).build().await;
@@ -314,6 +393,7 @@ async fn test_delete_operating_system(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: Some("chain http://example.com".to_string()),
                 ipxe_template_id: None,
                 ipxe_template_parameters: vec![],
@@ -356,6 +436,7 @@ async fn test_find_operating_system_ids(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: Some("chain http://one.example.com".to_string()),
                 ipxe_template_id: None,
                 ipxe_template_parameters: vec![],
@@ -378,6 +459,7 @@ async fn test_find_operating_system_ids(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: Some("chain http://two.example.com".to_string()),
                 ipxe_template_id: None,
                 ipxe_template_parameters: vec![],
@@ -422,6 +504,7 @@ async fn test_find_operating_systems_by_ids(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: Some("chain http://one.example.com".to_string()),
                 ipxe_template_id: None,
                 ipxe_template_parameters: vec![],
@@ -516,6 +599,7 @@ async fn create_os_with_artifacts(env: &TestHarness) -> OperatingSystemId {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: None,
                 ipxe_template_id: Some("ea756ddd-add3-5e42-a202-44bfc2d5aac2".parse().unwrap()),
                 ipxe_template_parameters: vec![rpc::forge::IpxeTemplateParameter {
@@ -652,6 +736,7 @@ async fn test_set_artifacts_cached_url_ordered_duplicate_names(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: None,
                 ipxe_template_id: Some("ea756ddd-add3-5e42-a202-44bfc2d5aac2".parse().unwrap()),
                 ipxe_template_parameters: vec![rpc::forge::IpxeTemplateParameter {
@@ -954,6 +1039,7 @@ async fn test_create_strips_cached_url(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: None,
                 ipxe_template_id: Some("ea756ddd-add3-5e42-a202-44bfc2d5aac2".parse().unwrap()),
                 ipxe_template_parameters: vec![rpc::forge::IpxeTemplateParameter {
@@ -1007,6 +1093,7 @@ async fn test_create_with_cached_only_sets_provisioning(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: None,
                 ipxe_template_id: Some("ea756ddd-add3-5e42-a202-44bfc2d5aac2".parse().unwrap()),
                 ipxe_template_parameters: vec![rpc::forge::IpxeTemplateParameter {
@@ -1071,6 +1158,7 @@ async fn test_update_strips_cached_url_from_artifacts(pool: PgPool) {
                 allow_override: None,
                 phone_home_enabled: None,
                 user_data: None,
+                user_data_format: None,
                 ipxe_script: None,
                 ipxe_template_id: None,
                 ipxe_template_parameters: None,
@@ -1165,6 +1253,7 @@ async fn test_update_with_cached_only_artifacts_recomputes_status(pool: PgPool)
                 allow_override: None,
                 phone_home_enabled: None,
                 user_data: None,
+                user_data_format: None,
                 ipxe_script: None,
                 ipxe_template_id: None,
                 ipxe_template_parameters: None,
@@ -1234,6 +1323,7 @@ async fn test_update_promotes_to_ready_when_no_cached_only_remains(pool: PgPool)
                 allow_override: None,
                 phone_home_enabled: None,
                 user_data: None,
+                user_data_format: None,
                 ipxe_script: None,
                 ipxe_template_id: None,
                 ipxe_template_parameters: None,
@@ -1317,6 +1407,7 @@ async fn test_create_operating_system_with_explicit_id(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: Some("chain http://example.com".to_string()),
                 ipxe_template_id: None,
                 ipxe_template_parameters: vec![],
@@ -1346,6 +1437,7 @@ async fn test_deleted_os_not_returned_by_find_ids(pool: PgPool) {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit as i32,
                 ipxe_script: Some("chain http://example.com".to_string()),
                 ipxe_template_id: None,
                 ipxe_template_parameters: vec![],
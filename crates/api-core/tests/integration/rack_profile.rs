@@ -61,6 +61,7 @@ fn profile(
             },
         },
         attributes: Default::default(),
+        power_budget: None,
     }
 }
 
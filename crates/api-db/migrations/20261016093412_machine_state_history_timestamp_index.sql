@@ -0,0 +1,3 @@
+-- State watch streams poll recently persisted machine transitions by timestamp,
+-- like the rack, switch and power shelf history tables already support.
+CREATE INDEX idx_machine_state_history_timestamp ON machine_state_history USING btree ("timestamp");
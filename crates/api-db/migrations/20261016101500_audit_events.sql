@@ -0,0 +1,51 @@
+-- Durable audit trail of mutating API calls.
+--
+-- Authorization decisions and request payloads already reach tracing, but logs
+-- rotate and are not queryable by the object an operator touched. Each row here
+-- records one mutating `Forge` RPC or `/admin` web action: who made it, which
+-- objects it named, a redacted rendering of the request, and how it ended.
+--
+-- Rows are append-only. Retention is an operator decision, applied with
+-- `TrimTable` (target `AuditEvents`) rather than by a background job.
+CREATE TYPE audit_event_source AS ENUM (
+    'grpc',
+    'admin_web'
+);
+
+CREATE TABLE audit_events (
+    id bigint GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    occurred_at timestamptz NOT NULL,
+    source audit_event_source NOT NULL,
+    method text NOT NULL,
+    principals text[] NOT NULL,
+    target_ids text[] NOT NULL,
+    request_digest text NOT NULL,
+    outcome text NOT NULL,
+    outcome_message text,
+    client_address text
+);
+
+CREATE INDEX audit_events_occurred_at_idx ON audit_events (occurred_at);
+CREATE INDEX audit_events_method_idx ON audit_events (method);
+-- "Everything that touched this machine" is the common question, and target_ids
+-- is an array, so only a GIN index can answer it without a full scan.
+CREATE INDEX audit_events_target_ids_idx ON audit_events USING GIN (target_ids);
+
+-- Deletes all but the newest `keep_rows` events, returning how many went.
+CREATE FUNCTION audit_events_keep_limit(keep_rows integer) RETURNS integer
+    LANGUAGE plpgsql
+    AS $$
+DECLARE
+  deleted_total INTEGER;
+BEGIN
+    -- An empty kept set means keep_rows is zero (or the table is empty), so
+    -- the cutoff falls back to "everything".
+    DELETE FROM audit_events
+    WHERE id < (
+        SELECT COALESCE(MIN(id), 9223372036854775807)
+        FROM (SELECT id FROM audit_events ORDER BY id DESC LIMIT keep_rows) AS kept
+    );
+    GET DIAGNOSTICS deleted_total = ROW_COUNT;
+    RETURN deleted_total;
+END;
+$$;
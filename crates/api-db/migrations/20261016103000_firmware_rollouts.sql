@@ -0,0 +1,56 @@
+-- Staged firmware rollouts for the machine update manager.
+--
+-- A rollout gates one update module (host firmware or DPU NIC firmware) in
+-- waves: a canary count, then growing shares of the fleet, with a soak between
+-- waves and per-rack / per-NVLink-domain concurrency caps inside a wave. The
+-- wave schedule and limits live in `spec` (see `FirmwareRolloutSpec`); the
+-- columns below are the progress the update manager advances on each pass.
+CREATE TYPE firmware_rollout_target AS ENUM (
+    'host_firmware',
+    'dpu_nic_firmware'
+);
+
+CREATE TYPE firmware_rollout_state AS ENUM (
+    'active',
+    'paused',
+    'completed'
+);
+
+CREATE TYPE firmware_rollout_machine_outcome AS ENUM (
+    'in_progress',
+    'succeeded',
+    'failed'
+);
+
+CREATE TABLE firmware_rollouts (
+    name text PRIMARY KEY,
+    target firmware_rollout_target NOT NULL,
+    spec jsonb NOT NULL,
+    state firmware_rollout_state NOT NULL DEFAULT 'active',
+    -- Zero-based index into spec.waves.
+    current_wave integer NOT NULL DEFAULT 0,
+    -- Failures count against the wave's threshold from here; resuming a
+    -- paused rollout moves it forward.
+    wave_started_at timestamptz NOT NULL DEFAULT now(),
+    -- Set once the wave has nothing left to start or in flight; the next wave
+    -- opens after the soak time has passed.
+    wave_completed_at timestamptz,
+    paused_reason text,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now()
+);
+
+-- Two live rollouts for one module would fight over the same machines.
+CREATE UNIQUE INDEX firmware_rollouts_one_live_per_target_idx
+    ON firmware_rollouts (target) WHERE state <> 'completed';
+
+CREATE TABLE firmware_rollout_machines (
+    rollout_name text NOT NULL REFERENCES firmware_rollouts (name) ON DELETE CASCADE,
+    machine_id varchar(64) NOT NULL
+        REFERENCES machines(id) ON UPDATE CASCADE ON DELETE CASCADE,
+    wave integer NOT NULL,
+    outcome firmware_rollout_machine_outcome NOT NULL DEFAULT 'in_progress',
+    started_at timestamptz NOT NULL DEFAULT now(),
+    finished_at timestamptz,
+    PRIMARY KEY (rollout_name, machine_id)
+);
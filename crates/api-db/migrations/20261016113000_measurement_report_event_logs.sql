@@ -0,0 +1,12 @@
+-- Raw TCG2 event logs for measurement reports.
+--
+-- A report only stores final PCR values, which say that a machine's boot
+-- chain changed but not what changed. Keeping the event log that produced a
+-- report lets a later mismatch be diffed event by event against the log of a
+-- report that matched the bundle. Only logs that replay to the quoted PCR
+-- values are stored, so every row here is trustworthy as a reference.
+CREATE TABLE measurement_report_event_logs (
+    report_id uuid PRIMARY KEY REFERENCES measurement_reports (report_id) ON DELETE CASCADE,
+    event_log bytea NOT NULL,
+    ts timestamptz NOT NULL DEFAULT clock_timestamp()
+);
@@ -0,0 +1,20 @@
+-- Records served for a domain that are not derived from machine or instance
+-- addresses: tenant CNAME, TXT, SRV, MX and delegating NS records. The
+-- `dns_records` view only carries address records, so these are stored
+-- separately and merged in on lookup and zone transfer.
+--
+-- `content` is the record's presentation-format RDATA, the same string
+-- `LookupRecord` returns, e.g. `10 5 5060 sip.example.com.` for SRV.
+CREATE TABLE dns_custom_records (
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    domain_id uuid NOT NULL REFERENCES domains(id),
+    q_name character varying NOT NULL,
+    q_type character varying(10) NOT NULL
+        CHECK (q_type IN ('CNAME', 'TXT', 'SRV', 'MX', 'NS')),
+    ttl integer,
+    content text NOT NULL,
+    created timestamptz NOT NULL DEFAULT now(),
+    UNIQUE (q_name, q_type, content)
+);
+
+CREATE INDEX dns_custom_records_domain_id_idx ON dns_custom_records (domain_id);
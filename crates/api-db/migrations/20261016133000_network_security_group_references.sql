@@ -0,0 +1,7 @@
+-- Digest of the prefixes that the security-group, VPC and label-selector
+-- references in an NSG's rules currently resolve to. The reference refresher
+-- bumps the NSG version whenever the digest changes so that DPUs pick up the
+-- new membership and propagation status tracks the rollout. NULL means the
+-- references have not been resolved since the rules last changed.
+ALTER TABLE network_security_groups
+    ADD COLUMN references_digest TEXT;
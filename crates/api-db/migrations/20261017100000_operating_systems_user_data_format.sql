@@ -0,0 +1,5 @@
+-- Records how an operating system definition's user_data is interpreted:
+-- 'cloud-init', 'ignition' (Ignition JSON) or 'butane' (Butane YAML that
+-- carbide-pxe translates to Ignition).
+ALTER TABLE operating_systems
+    ADD COLUMN user_data_format TEXT NOT NULL DEFAULT 'cloud-init';
@@ -0,0 +1,25 @@
+-- Disk sanitization certificates from scout deprovisioning.
+--
+-- A row is opened when an instance is released, so the tenant the disks
+-- belonged to is known even though the instance row is deleted before scout
+-- reports. Scout's signed per-device record fills it in when cleanup
+-- completes. Cleanups without a preceding release (e.g. a forced
+-- deprovision) get a row of their own. The JWS is stored exactly as signed;
+-- the API re-verifies it on every read instead of trusting decoded columns.
+CREATE TABLE disk_sanitization_certificates (
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    machine_id text NOT NULL,
+    instance_id uuid,
+    tenant_organization_id text,
+    released_at timestamptz,
+    sanitized_at timestamptz,
+    signer text,
+    signed_certificate text,
+    CHECK ((sanitized_at IS NULL) = (signed_certificate IS NULL))
+);
+
+CREATE INDEX disk_sanitization_certificates_machine_id_idx
+    ON disk_sanitization_certificates (machine_id);
+CREATE INDEX disk_sanitization_certificates_instance_id_idx
+    ON disk_sanitization_certificates (instance_id)
+    WHERE instance_id IS NOT NULL;
@@ -0,0 +1,45 @@
+-- Deadline-based wake-ups for state controllers.
+--
+-- run_after: a handler that waits on a timer leaves its object queued until
+-- this time. The periodic enqueuer's inserts conflict with the row, so the
+-- object is not handled again before the deadline unless it is requested
+-- explicitly, which clears the deadline.
+--
+-- notified: set when an object is requested while it is being processed, so
+-- the processor runs it once more instead of dropping the request.
+
+ALTER TABLE attestation_controller_queued_objects
+    ADD COLUMN run_after timestamptz,
+    ADD COLUMN notified boolean NOT NULL DEFAULT false;
+
+ALTER TABLE dpa_interfaces_controller_queued_objects
+    ADD COLUMN run_after timestamptz,
+    ADD COLUMN notified boolean NOT NULL DEFAULT false;
+
+ALTER TABLE ib_partition_controller_queued_objects
+    ADD COLUMN run_after timestamptz,
+    ADD COLUMN notified boolean NOT NULL DEFAULT false;
+
+ALTER TABLE machine_state_controller_queued_objects
+    ADD COLUMN run_after timestamptz,
+    ADD COLUMN notified boolean NOT NULL DEFAULT false;
+
+ALTER TABLE network_segments_controller_queued_objects
+    ADD COLUMN run_after timestamptz,
+    ADD COLUMN notified boolean NOT NULL DEFAULT false;
+
+ALTER TABLE network_vpc_prefixes_controller_queued_objects
+    ADD COLUMN run_after timestamptz,
+    ADD COLUMN notified boolean NOT NULL DEFAULT false;
+
+ALTER TABLE power_shelf_controller_queued_objects
+    ADD COLUMN run_after timestamptz,
+    ADD COLUMN notified boolean NOT NULL DEFAULT false;
+
+ALTER TABLE rack_controller_queued_objects
+    ADD COLUMN run_after timestamptz,
+    ADD COLUMN notified boolean NOT NULL DEFAULT false;
+
+ALTER TABLE switch_controller_queued_objects
+    ADD COLUMN run_after timestamptz,
+    ADD COLUMN notified boolean NOT NULL DEFAULT false;
@@ -0,0 +1,3 @@
+-- Outcome of the last rack power budget pass: capacity, allocation, headroom
+-- and the power limits applied to each host of the rack.
+ALTER TABLE racks ADD COLUMN power_budget jsonb;
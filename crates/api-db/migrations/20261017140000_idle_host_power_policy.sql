@@ -0,0 +1,2 @@
+-- Set while the idle host power policy keeps the host powered off.
+ALTER TABLE power_options ADD COLUMN idle_parked_at timestamptz;
@@ -0,0 +1,113 @@
+-- Secondaries only re-transfer a zone when its SOA serial moves, so every
+-- change to a record a domain serves must bump that domain's serial, not
+-- just edits of the domain itself. The serial keeps the YYYYMMDDnn scheme of
+-- `SoaRecord::increment_serial`: the first change of a day starts at
+-- YYYYMMDD01, later ones count up from the stored serial.
+--
+-- Only the `serial` field of `domains.soa` changes. `updated` is left alone,
+-- so record churn does not invalidate a concurrent domain edit; that edit
+-- keeps the higher of both serials (see `db::dns::domain::update`).
+CREATE FUNCTION bump_domain_soa_serial(bumped_domain_id uuid)
+RETURNS void AS $$
+BEGIN
+    UPDATE domains
+    SET soa = jsonb_set(
+        soa,
+        '{serial}',
+        to_jsonb(GREATEST(
+            (soa->>'serial')::bigint + 1,
+            to_char(now() AT TIME ZONE 'UTC', 'YYYYMMDD')::bigint * 100 + 1
+        ))
+    )
+    WHERE id = bumped_domain_id
+      AND jsonb_typeof(soa->'serial') = 'number';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION dns_custom_records_bump_serial()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        PERFORM bump_domain_soa_serial(OLD.domain_id);
+    END IF;
+    IF TG_OP = 'INSERT' OR (TG_OP = 'UPDATE' AND NEW.domain_id IS DISTINCT FROM OLD.domain_id) THEN
+        PERFORM bump_domain_soa_serial(NEW.domain_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER dns_custom_records_bump_serial
+AFTER INSERT OR UPDATE OR DELETE ON dns_custom_records
+FOR EACH ROW
+EXECUTE FUNCTION dns_custom_records_bump_serial();
+
+CREATE FUNCTION machine_interface_addresses_bump_serial()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        PERFORM bump_domain_soa_serial(mi.domain_id)
+        FROM machine_interfaces mi
+        WHERE mi.id = OLD.interface_id AND mi.domain_id IS NOT NULL;
+    END IF;
+    IF TG_OP = 'INSERT' OR (TG_OP = 'UPDATE' AND NEW.interface_id IS DISTINCT FROM OLD.interface_id) THEN
+        PERFORM bump_domain_soa_serial(mi.domain_id)
+        FROM machine_interfaces mi
+        WHERE mi.id = NEW.interface_id AND mi.domain_id IS NOT NULL;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER machine_interface_addresses_bump_serial
+AFTER INSERT OR UPDATE OF address, interface_id OR DELETE ON machine_interface_addresses
+FOR EACH ROW
+EXECUTE FUNCTION machine_interface_addresses_bump_serial();
+
+-- Renaming an interface, moving it to another domain or changing whether it
+-- is primary changes the names its addresses are served under.
+CREATE FUNCTION machine_interfaces_bump_serial()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF OLD.domain_id IS NOT NULL THEN
+        PERFORM bump_domain_soa_serial(OLD.domain_id);
+    END IF;
+    IF NEW.domain_id IS NOT NULL AND NEW.domain_id IS DISTINCT FROM OLD.domain_id THEN
+        PERFORM bump_domain_soa_serial(NEW.domain_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER machine_interfaces_bump_serial
+AFTER UPDATE OF hostname, domain_id, machine_id, primary_interface ON machine_interfaces
+FOR EACH ROW
+WHEN (
+    OLD.hostname IS DISTINCT FROM NEW.hostname
+    OR OLD.domain_id IS DISTINCT FROM NEW.domain_id
+    OR OLD.machine_id IS DISTINCT FROM NEW.machine_id
+    OR OLD.primary_interface IS DISTINCT FROM NEW.primary_interface
+)
+EXECUTE FUNCTION machine_interfaces_bump_serial();
+
+CREATE FUNCTION instance_addresses_bump_serial()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        PERFORM bump_domain_soa_serial(ns.subdomain_id)
+        FROM network_segments ns
+        WHERE ns.id = OLD.segment_id AND ns.subdomain_id IS NOT NULL;
+    END IF;
+    IF TG_OP = 'INSERT' OR (TG_OP = 'UPDATE' AND NEW.segment_id IS DISTINCT FROM OLD.segment_id) THEN
+        PERFORM bump_domain_soa_serial(ns.subdomain_id)
+        FROM network_segments ns
+        WHERE ns.id = NEW.segment_id AND ns.subdomain_id IS NOT NULL;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER instance_addresses_bump_serial
+AFTER INSERT OR UPDATE OF address, hostname, segment_id OR DELETE ON instance_addresses
+FOR EACH ROW
+EXECUTE FUNCTION instance_addresses_bump_serial();
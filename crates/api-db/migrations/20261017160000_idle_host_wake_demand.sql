@@ -0,0 +1,8 @@
+-- Hosts an allocation asked for but could not get because they were parked
+-- by the idle host power policy, per instance type. The idle host power
+-- manager takes the count on its next pass and wakes that many hosts.
+CREATE TABLE idle_host_wake_demand (
+    instance_type_id character varying(64) PRIMARY KEY,
+    hosts integer NOT NULL CHECK (hosts > 0),
+    updated timestamptz NOT NULL DEFAULT now()
+);
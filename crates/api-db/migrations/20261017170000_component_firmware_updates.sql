@@ -0,0 +1,11 @@
+-- Firmware updates pushed by the `core` component manager backends, keyed by
+-- the kind of device and its identifier, so that status polls can still
+-- follow the BMC tasks applying them after a restart.
+CREATE TABLE component_firmware_updates (
+    device_kind character varying(32) NOT NULL,
+    device_id character varying(64) NOT NULL,
+    target_version text NOT NULL,
+    task_ids text[] NOT NULL,
+    created timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (device_kind, device_id)
+);
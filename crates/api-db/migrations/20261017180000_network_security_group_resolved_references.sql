@@ -0,0 +1,7 @@
+-- The expansion of an NSG's references that DPUs are given. It is recorded
+-- whenever the rules are written and whenever the reference refresher sees
+-- a membership change that still fits in the expanded rule limit. Changes
+-- that don't fit are not recorded, so DPUs keep the last expansion that did.
+-- NULL means the references are resolved when the DPU config is built.
+ALTER TABLE network_security_groups
+    ADD COLUMN resolved_references JSONB;
@@ -0,0 +1,109 @@
+-- Bumping `domains.soa` from the address tables' triggers made every address
+-- allocation in a domain queue behind the same domain row, and could deadlock
+-- against a concurrent domain edit. Address changes now only append to
+-- `dns_record_changes`, which takes no lock on `domains`. The SOA serial a
+-- domain serves is its stored serial plus its pending changes (see
+-- `dns_served_soa`), and a domain edit folds those changes into the stored
+-- serial (see `db::dns::domain::update`).
+--
+-- Custom records keep bumping the stored serial directly: they are only
+-- changed over the API, one record at a time.
+DROP TRIGGER machine_interface_addresses_bump_serial ON machine_interface_addresses;
+DROP FUNCTION machine_interface_addresses_bump_serial();
+DROP TRIGGER machine_interfaces_bump_serial ON machine_interfaces;
+DROP FUNCTION machine_interfaces_bump_serial();
+DROP TRIGGER instance_addresses_bump_serial ON instance_addresses;
+DROP FUNCTION instance_addresses_bump_serial();
+
+CREATE TABLE dns_record_changes (
+    domain_id uuid NOT NULL,
+    changed_at timestamp with time zone NOT NULL DEFAULT now()
+);
+
+CREATE INDEX dns_record_changes_domain_id_idx ON dns_record_changes (domain_id);
+
+CREATE FUNCTION dns_served_soa(served_domain_id uuid, soa jsonb)
+RETURNS jsonb AS $$
+    SELECT CASE
+        WHEN jsonb_typeof(soa->'serial') = 'number'
+        THEN jsonb_set(soa, '{serial}', to_jsonb(
+            (soa->>'serial')::bigint
+            + (SELECT count(*) FROM dns_record_changes WHERE domain_id = served_domain_id)
+        ))
+        ELSE soa
+    END
+$$ LANGUAGE sql STABLE;
+
+CREATE FUNCTION machine_interface_addresses_record_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        INSERT INTO dns_record_changes (domain_id)
+        SELECT mi.domain_id
+        FROM machine_interfaces mi
+        WHERE mi.id = OLD.interface_id AND mi.domain_id IS NOT NULL;
+    END IF;
+    IF TG_OP = 'INSERT' OR (TG_OP = 'UPDATE' AND NEW.interface_id IS DISTINCT FROM OLD.interface_id) THEN
+        INSERT INTO dns_record_changes (domain_id)
+        SELECT mi.domain_id
+        FROM machine_interfaces mi
+        WHERE mi.id = NEW.interface_id AND mi.domain_id IS NOT NULL;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER machine_interface_addresses_record_change
+AFTER INSERT OR UPDATE OF address, interface_id OR DELETE ON machine_interface_addresses
+FOR EACH ROW
+EXECUTE FUNCTION machine_interface_addresses_record_change();
+
+-- Renaming an interface, moving it to another domain or changing whether it
+-- is primary changes the names its addresses are served under.
+CREATE FUNCTION machine_interfaces_record_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF OLD.domain_id IS NOT NULL THEN
+        INSERT INTO dns_record_changes (domain_id) VALUES (OLD.domain_id);
+    END IF;
+    IF NEW.domain_id IS NOT NULL AND NEW.domain_id IS DISTINCT FROM OLD.domain_id THEN
+        INSERT INTO dns_record_changes (domain_id) VALUES (NEW.domain_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER machine_interfaces_record_change
+AFTER UPDATE OF hostname, domain_id, machine_id, primary_interface ON machine_interfaces
+FOR EACH ROW
+WHEN (
+    OLD.hostname IS DISTINCT FROM NEW.hostname
+    OR OLD.domain_id IS DISTINCT FROM NEW.domain_id
+    OR OLD.machine_id IS DISTINCT FROM NEW.machine_id
+    OR OLD.primary_interface IS DISTINCT FROM NEW.primary_interface
+)
+EXECUTE FUNCTION machine_interfaces_record_change();
+
+CREATE FUNCTION instance_addresses_record_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        INSERT INTO dns_record_changes (domain_id)
+        SELECT ns.subdomain_id
+        FROM network_segments ns
+        WHERE ns.id = OLD.segment_id AND ns.subdomain_id IS NOT NULL;
+    END IF;
+    IF TG_OP = 'INSERT' OR (TG_OP = 'UPDATE' AND NEW.segment_id IS DISTINCT FROM OLD.segment_id) THEN
+        INSERT INTO dns_record_changes (domain_id)
+        SELECT ns.subdomain_id
+        FROM network_segments ns
+        WHERE ns.id = NEW.segment_id AND ns.subdomain_id IS NOT NULL;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER instance_addresses_record_change
+AFTER INSERT OR UPDATE OF address, hostname, segment_id OR DELETE ON instance_addresses
+FOR EACH ROW
+EXECUTE FUNCTION instance_addresses_record_change();
@@ -0,0 +1,7 @@
+-- Serial numbers of client certificates revoked through `RevokeCertificate`.
+-- Every replica signs its CRL from this table, so a revocation made through
+-- one replica is refused by all of them.
+CREATE TABLE certificate_revocations (
+    serial_number text PRIMARY KEY,
+    revoked_at timestamptz NOT NULL DEFAULT now()
+);
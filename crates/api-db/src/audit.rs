@@ -0,0 +1,250 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The append-only audit trail of mutating API calls.
+
+use model::audit::{AuditEvent, AuditEventFilter, NewAuditEvent};
+use sqlx::PgConnection;
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+/// Upper bound on events per [`insert`] call: ten binds per row keeps a full
+/// batch far below Postgres' 65535-parameter limit.
+pub const MAX_INSERT_BATCH: usize = 256;
+
+/// Writes `events` in a single statement.
+///
+/// Callers batch up to [`MAX_INSERT_BATCH`] events; an empty slice is a no-op.
+pub async fn insert(txn: &mut PgConnection, events: &[NewAuditEvent]) -> DatabaseResult<()> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    const QUERY: &str = "INSERT INTO audit_events (
+        occurred_at,
+        source,
+        method,
+        principals,
+        target_ids,
+        request_digest,
+        outcome,
+        outcome_message,
+        client_address
+    ) ";
+
+    let mut query = sqlx::QueryBuilder::new(QUERY);
+    query.push_values(events, |mut row, event| {
+        row.push_bind(event.occurred_at)
+            .push_bind(event.source)
+            .push_bind(&event.method)
+            .push_bind(&event.principals)
+            .push_bind(&event.target_ids)
+            .push_bind(&event.request_digest)
+            .push_bind(&event.outcome)
+            .push_bind(&event.outcome_message)
+            .push_bind(&event.client_address);
+    });
+    query
+        .build()
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(QUERY, e))?;
+    Ok(())
+}
+
+/// Returns up to `limit` events matching `filter`, newest first.
+///
+/// Pass the smallest returned id as [`AuditEventFilter::before_id`] to fetch
+/// the next page.
+pub async fn find(
+    db: impl DbReader<'_>,
+    filter: &AuditEventFilter,
+    limit: u32,
+) -> DatabaseResult<Vec<AuditEvent>> {
+    const QUERY: &str = "SELECT
+        id,
+        occurred_at,
+        source,
+        method,
+        principals,
+        target_ids,
+        request_digest,
+        outcome,
+        outcome_message,
+        client_address
+    FROM audit_events
+    WHERE TRUE";
+
+    let mut query = sqlx::QueryBuilder::new(QUERY);
+    if let Some(method) = &filter.method {
+        query.push(" AND method = ").push_bind(method);
+    }
+    if let Some(principal) = &filter.principal {
+        query
+            .push(" AND EXISTS (SELECT 1 FROM unnest(principals) AS p WHERE strpos(p, ")
+            .push_bind(principal)
+            .push(") > 0)");
+    }
+    if let Some(target_id) = &filter.target_id {
+        // `@>` rather than `= ANY` so the GIN index on target_ids is usable.
+        query
+            .push(" AND target_ids @> ")
+            .push_bind(vec![target_id.clone()]);
+    }
+    if let Some(since) = filter.since {
+        query.push(" AND occurred_at >= ").push_bind(since);
+    }
+    if let Some(until) = filter.until {
+        query.push(" AND occurred_at < ").push_bind(until);
+    }
+    if let Some(before_id) = filter.before_id {
+        query.push(" AND id < ").push_bind(before_id);
+    }
+    query
+        .push(" ORDER BY id DESC LIMIT ")
+        .push_bind(i64::from(limit));
+
+    query
+        .build_query_as()
+        .fetch_all(db)
+        .await
+        .map_err(|e| DatabaseError::query(QUERY, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use model::audit::{AuditEventFilter, AuditEventSource, NewAuditEvent};
+    use sqlx::PgPool;
+
+    use super::{find, insert};
+    use crate::trim_table::trim_table;
+
+    fn event(method: &str, principal: &str, target_ids: &[&str]) -> NewAuditEvent {
+        NewAuditEvent {
+            occurred_at: Utc::now(),
+            source: AuditEventSource::Grpc,
+            method: method.to_string(),
+            principals: vec![principal.to_string()],
+            target_ids: target_ids.iter().map(|id| id.to_string()).collect(),
+            request_digest: format!("{method}Request {{ .. }}"),
+            outcome: "Ok".to_string(),
+            outcome_message: None,
+            client_address: Some("10.0.0.1:4242".to_string()),
+        }
+    }
+
+    #[crate::sqlx_test]
+    async fn filters_narrow_the_result(pool: PgPool) -> Result<(), Box<dyn std::error::Error>> {
+        let mut txn = pool.begin().await?;
+        insert(
+            txn.as_mut(),
+            &[
+                event(
+                    "AdminForceDeleteMachine",
+                    "external-role/ops:alice",
+                    &["m-1"],
+                ),
+                event("SetMaintenance", "external-role/ops:bob", &["m-1", "m-2"]),
+                event("SetMaintenance", "spiffe-service-id/agent", &["m-3"]),
+            ],
+        )
+        .await?;
+
+        let all = find(txn.as_mut(), &AuditEventFilter::default(), 100).await?;
+        assert_eq!(all.len(), 3);
+        assert!(all.windows(2).all(|pair| pair[0].id > pair[1].id));
+
+        let by_target = AuditEventFilter {
+            target_id: Some("m-1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(find(txn.as_mut(), &by_target, 100).await?.len(), 2);
+
+        let by_principal = AuditEventFilter {
+            principal: Some("bob".to_string()),
+            ..Default::default()
+        };
+        let found = find(txn.as_mut(), &by_principal, 100).await?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].target_ids, vec!["m-1", "m-2"]);
+
+        let by_method_and_window = AuditEventFilter {
+            method: Some("SetMaintenance".to_string()),
+            since: Some(Utc::now() - Duration::hours(1)),
+            until: Some(Utc::now() + Duration::hours(1)),
+            ..Default::default()
+        };
+        assert_eq!(
+            find(txn.as_mut(), &by_method_and_window, 100).await?.len(),
+            2
+        );
+
+        Ok(())
+    }
+
+    #[crate::sqlx_test]
+    async fn pages_walk_backwards_by_id(pool: PgPool) -> Result<(), Box<dyn std::error::Error>> {
+        let mut txn = pool.begin().await?;
+        let events: Vec<_> = (0..5)
+            .map(|i| event("SetMaintenance", "anonymous", &[&format!("m-{i}")]))
+            .collect();
+        insert(txn.as_mut(), &events).await?;
+
+        let first = find(txn.as_mut(), &AuditEventFilter::default(), 3).await?;
+        assert_eq!(first.len(), 3);
+        let next = AuditEventFilter {
+            before_id: first.last().map(|event| event.id),
+            ..Default::default()
+        };
+        let second = find(txn.as_mut(), &next, 3).await?;
+        assert_eq!(second.len(), 2);
+        assert!(second[0].id < first[2].id);
+
+        Ok(())
+    }
+
+    #[crate::sqlx_test]
+    async fn trimming_keeps_the_newest(pool: PgPool) -> Result<(), Box<dyn std::error::Error>> {
+        let mut txn = pool.begin().await?;
+        let events: Vec<_> = (0..4)
+            .map(|i| event("SetMaintenance", "anonymous", &[&format!("m-{i}")]))
+            .collect();
+        insert(txn.as_mut(), &events).await?;
+
+        let deleted = trim_table(
+            txn.as_mut(),
+            model::trim_table::TrimTableTarget::AuditEvents,
+            1,
+        )
+        .await?;
+        assert_eq!(deleted, 3);
+        let kept = find(txn.as_mut(), &AuditEventFilter::default(), 100).await?;
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].target_ids, vec!["m-3"]);
+
+        let deleted = trim_table(
+            txn.as_mut(),
+            model::trim_table::TrimTableTarget::AuditEvents,
+            0,
+        )
+        .await?;
+        assert_eq!(deleted, 1);
+
+        Ok(())
+    }
+}
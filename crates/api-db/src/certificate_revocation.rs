@@ -0,0 +1,76 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Revoked client certificates. Every replica signs its CRL from this table
+//! (see `crate::certificate_revocations` in api-core), so there is one
+//! revocation list for the whole site.
+
+use model::certificate_revocation::CertificateRevocation;
+use sqlx::PgConnection;
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+/// Records the revocation of `serial_number`, which must already be in the
+/// canonical form of [`CertificateRevocation::serial_number`]. Returns `false`
+/// if it was already revoked; the first revocation time is kept.
+pub async fn insert(txn: &mut PgConnection, serial_number: &str) -> DatabaseResult<bool> {
+    let query = "INSERT INTO certificate_revocations (serial_number)
+                 VALUES ($1)
+                 ON CONFLICT (serial_number) DO NOTHING";
+
+    sqlx::query(query)
+        .bind(serial_number)
+        .execute(txn)
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+pub async fn find_all(txn: impl DbReader<'_>) -> DatabaseResult<Vec<CertificateRevocation>> {
+    let query = "SELECT serial_number, revoked_at
+                 FROM certificate_revocations
+                 ORDER BY revoked_at, serial_number";
+
+    sqlx::query_as(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use super::{find_all, insert};
+
+    #[crate::sqlx_test]
+    async fn a_serial_is_revoked_once(pool: PgPool) -> Result<(), Box<dyn std::error::Error>> {
+        let mut txn = pool.begin().await?;
+
+        assert!(insert(txn.as_mut(), "7f01").await?);
+        let first = find_all(txn.as_mut()).await?;
+        assert!(!insert(txn.as_mut(), "7f01").await?);
+        assert!(insert(txn.as_mut(), "02").await?);
+
+        let revocations = find_all(txn.as_mut()).await?;
+        assert_eq!(revocations.len(), 2);
+        assert!(revocations.contains(&first[0]));
+
+        Ok(())
+    }
+}
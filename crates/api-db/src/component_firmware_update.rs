@@ -0,0 +1,104 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Firmware updates pushed by the `core` component manager backends. A device
+//! is identified by its kind (e.g. `power_shelf`) and an identifier that is
+//! unique within that kind, such as its BMC MAC or IP address.
+
+use model::component_manager::TrackedFirmwareUpdate;
+use sqlx::{FromRow, PgConnection};
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+#[derive(Debug, FromRow)]
+struct DbComponentFirmwareUpdate {
+    target_version: String,
+    task_ids: Vec<String>,
+}
+
+impl From<DbComponentFirmwareUpdate> for TrackedFirmwareUpdate {
+    fn from(update: DbComponentFirmwareUpdate) -> Self {
+        Self {
+            target_version: update.target_version,
+            task_ids: update.task_ids,
+        }
+    }
+}
+
+/// Returns the firmware update last pushed to the device, if any.
+pub async fn find(
+    db: impl DbReader<'_>,
+    device_kind: &str,
+    device_id: &str,
+) -> DatabaseResult<Option<TrackedFirmwareUpdate>> {
+    let query = "SELECT target_version, task_ids FROM component_firmware_updates
+    WHERE device_kind = $1 AND device_id = $2";
+
+    sqlx::query_as::<_, DbComponentFirmwareUpdate>(query)
+        .bind(device_kind)
+        .bind(device_id)
+        .fetch_optional(db)
+        .await
+        .map(|update| update.map(Into::into))
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Replaces the firmware update tracked for the device.
+pub async fn upsert(
+    txn: &mut PgConnection,
+    device_kind: &str,
+    device_id: &str,
+    update: &TrackedFirmwareUpdate,
+) -> DatabaseResult<()> {
+    let query =
+        "INSERT INTO component_firmware_updates (device_kind, device_id, target_version, task_ids)
+VALUES ($1, $2, $3, $4)
+ON CONFLICT (device_kind, device_id) DO UPDATE SET
+    target_version = EXCLUDED.target_version,
+    task_ids = EXCLUDED.task_ids,
+    created = now()";
+
+    sqlx::query(query)
+        .bind(device_kind)
+        .bind(device_id)
+        .bind(&update.target_version)
+        .bind(&update.task_ids)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(())
+}
+
+/// Stops tracking firmware updates of the device.
+pub async fn delete(
+    txn: &mut PgConnection,
+    device_kind: &str,
+    device_id: &str,
+) -> DatabaseResult<()> {
+    let query = "DELETE FROM component_firmware_updates WHERE device_kind = $1 AND device_id = $2";
+
+    sqlx::query(query)
+        .bind(device_kind)
+        .bind(device_id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(())
+}
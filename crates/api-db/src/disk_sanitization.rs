@@ -0,0 +1,193 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Disk sanitization certificates: opened when an instance is released,
+//! completed when scout reports the signed certificate for the cleanup.
+
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use model::disk_sanitization::DiskSanitizationRecord;
+use sqlx::PgConnection;
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+const COLUMNS: &str = "id,
+    machine_id,
+    instance_id,
+    tenant_organization_id,
+    released_at,
+    sanitized_at,
+    signer,
+    signed_certificate";
+
+/// Opens a pending record for the release of `instance_id` from
+/// `machine_id`, so the certificate scout sends for the following cleanup
+/// can be attributed to the tenant after the instance is gone.
+pub async fn record_release(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    instance_id: InstanceId,
+    tenant_organization_id: &str,
+) -> DatabaseResult<()> {
+    let query = "INSERT INTO disk_sanitization_certificates
+        (machine_id, instance_id, tenant_organization_id, released_at)
+        VALUES ($1, $2, $3, clock_timestamp())";
+    sqlx::query(query)
+        .bind(machine_id)
+        .bind(instance_id)
+        .bind(tenant_organization_id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+/// Stores a verified certificate for `machine_id`.
+///
+/// The newest pending release record for the machine is completed. A
+/// cleanup that no release preceded gets a record of its own.
+pub async fn record_certificate(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    signer: &str,
+    signed_certificate: &str,
+) -> DatabaseResult<DiskSanitizationRecord> {
+    let query = format!(
+        "UPDATE disk_sanitization_certificates
+        SET sanitized_at = clock_timestamp(), signer = $2, signed_certificate = $3
+        WHERE id = (
+            SELECT id FROM disk_sanitization_certificates
+            WHERE machine_id = $1 AND sanitized_at IS NULL
+            ORDER BY released_at DESC NULLS LAST
+            LIMIT 1
+        )
+        RETURNING {COLUMNS}"
+    );
+    let completed = sqlx::query_as(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(machine_id)
+        .bind(signer)
+        .bind(signed_certificate)
+        .fetch_optional(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))?;
+    if let Some(completed) = completed {
+        return Ok(completed);
+    }
+
+    let query = format!(
+        "INSERT INTO disk_sanitization_certificates
+            (machine_id, sanitized_at, signer, signed_certificate)
+        VALUES ($1, clock_timestamp(), $2, $3)
+        RETURNING {COLUMNS}"
+    );
+    sqlx::query_as(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(machine_id)
+        .bind(signer)
+        .bind(signed_certificate)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// Returns the records for `machine_id` and/or `instance_id`, newest first.
+/// Unset filters match everything.
+pub async fn find(
+    db: impl DbReader<'_>,
+    machine_id: Option<&MachineId>,
+    instance_id: Option<InstanceId>,
+) -> DatabaseResult<Vec<DiskSanitizationRecord>> {
+    let select = format!("SELECT {COLUMNS} FROM disk_sanitization_certificates WHERE TRUE");
+    let mut query = sqlx::QueryBuilder::new(&select);
+    if let Some(machine_id) = machine_id {
+        query.push(" AND machine_id = ").push_bind(machine_id);
+    }
+    if let Some(instance_id) = instance_id {
+        query.push(" AND instance_id = ").push_bind(instance_id);
+    }
+    query.push(" ORDER BY COALESCE(sanitized_at, released_at) DESC");
+
+    query
+        .build_query_as()
+        .fetch_all(db)
+        .await
+        .map_err(|e| DatabaseError::query(&select, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use carbide_uuid::instance::InstanceId;
+    use carbide_uuid::machine::MachineId;
+    use sqlx::PgPool;
+
+    use super::{find, record_certificate, record_release};
+
+    fn machine_id() -> MachineId {
+        MachineId::from_str("fm100htjtiaehv1n5vh67tbmqq4eabcjdng40f7jupsadbedhruh6rag1l0").unwrap()
+    }
+
+    #[crate::sqlx_test]
+    async fn certificates_complete_the_newest_release(
+        pool: PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut txn = pool.begin().await?;
+        let machine_id = machine_id();
+        let instance_id = InstanceId::new();
+        record_release(txn.as_mut(), &machine_id, instance_id, "tenant-a").await?;
+
+        let pending = find(txn.as_mut(), None, Some(instance_id)).await?;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].sanitized_at, None);
+
+        let completed =
+            record_certificate(txn.as_mut(), &machine_id, "spiffe://a", "a.b.c").await?;
+        assert_eq!(completed.id, pending[0].id);
+        assert_eq!(completed.instance_id, Some(instance_id));
+        assert_eq!(
+            completed.tenant_organization_id.as_deref(),
+            Some("tenant-a")
+        );
+        assert_eq!(completed.signed_certificate.as_deref(), Some("a.b.c"));
+        assert!(completed.sanitized_at.is_some());
+
+        Ok(())
+    }
+
+    #[crate::sqlx_test]
+    async fn cleanups_without_a_release_get_their_own_record(
+        pool: PgPool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut txn = pool.begin().await?;
+        let machine_id = machine_id();
+        record_release(txn.as_mut(), &machine_id, InstanceId::new(), "tenant-a").await?;
+        record_certificate(txn.as_mut(), &machine_id, "spiffe://a", "first").await?;
+
+        // No release is pending anymore, so this cleanup is standalone.
+        let standalone =
+            record_certificate(txn.as_mut(), &machine_id, "spiffe://a", "second").await?;
+        assert_eq!(standalone.instance_id, None);
+        assert_eq!(standalone.released_at, None);
+
+        let all = find(txn.as_mut(), Some(&machine_id), None).await?;
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].signed_certificate.as_deref(), Some("second"));
+
+        Ok(())
+    }
+}
@@ -45,6 +45,13 @@ fn validate_domain_name(name: &str) -> Result<(), DatabaseError> {
     Ok(())
 }
 
+/// Domain columns with the SOA a domain serves: address record changes are
+/// counted in `dns_record_changes` until the next domain edit folds them into
+/// the stored serial.
+const SELECT_DOMAINS: &str = "SELECT id, name, created, updated, deleted,
+                                     dns_served_soa(id, soa) AS soa, domain_metadata_id
+                              FROM domains";
+
 #[derive(Clone, Debug, FromRow)]
 pub struct DbDomain {
     pub id: DomainId,
@@ -164,7 +171,7 @@ pub async fn find_all_by<'a, C: ColumnInfo<'a, TableType = Domain>>(
     filter: ObjectColumnFilter<'a, C>,
     include_deleted: bool,
 ) -> Result<Vec<Domain>, DatabaseError> {
-    let mut query = FilterableQueryBuilder::new("SELECT * FROM domains").filter(&filter);
+    let mut query = FilterableQueryBuilder::new(SELECT_DOMAINS).filter(&filter);
     if !include_deleted {
         query.push(" AND deleted IS NULL");
     }
@@ -198,20 +205,22 @@ pub async fn find_reverse_zone_by_normalized_name(
     txn: impl DbReader<'_>,
     name: &str,
 ) -> Result<Vec<Domain>, DatabaseError> {
-    let query = "SELECT * FROM domains
-                 WHERE lower(rtrim(name, '.')) = $1
-                   AND deleted IS NULL
-                   AND (
-                       lower(rtrim(name, '.')) LIKE '%.in-addr.arpa'
-                       OR lower(rtrim(name, '.')) LIKE '%.ip6.arpa'
-                   )";
+    let query = format!(
+        "{SELECT_DOMAINS}
+         WHERE lower(rtrim(name, '.')) = $1
+           AND deleted IS NULL
+           AND (
+               lower(rtrim(name, '.')) LIKE '%.in-addr.arpa'
+               OR lower(rtrim(name, '.')) LIKE '%.ip6.arpa'
+           )"
+    );
     let name = super::normalize_domain(name);
-    sqlx::query_as::<_, DbDomain>(query)
+    sqlx::query_as::<_, DbDomain>(sqlx::AssertSqlSafe(query.as_str()))
         .bind(name)
         .fetch_all(txn)
         .await
         .map(|domains| domains.into_iter().map(Domain::from).collect())
-        .map_err(|error| DatabaseError::query(query, error))
+        .map_err(|error| DatabaseError::query(&query, error))
 }
 
 /// Find the domain with the given ID, even if it is deleted.
@@ -274,13 +283,32 @@ pub async fn delete(value: Domain, txn: &mut PgConnection) -> Result<Domain, Dat
 /// that no longer exists. The new timestamp always advances, including for
 /// multiple updates in one transaction, so a later writer cannot reuse the same
 /// snapshot.
+///
+/// Record changes move the served SOA serial without touching `updated`, so
+/// the snapshot's serial may be behind; the update keeps the served serial
+/// plus one if that is higher. Pending address record changes are folded into
+/// the stored serial in the same statement.
 pub async fn update(value: &Domain, txn: &mut PgConnection) -> Result<Domain, DatabaseError> {
     validate_domain_name(&value.name)?;
 
-    let query = "UPDATE domains
+    let query = "WITH folded AS (
+                     DELETE FROM dns_record_changes
+                     WHERE domain_id = $3
+                       AND EXISTS (SELECT 1 FROM domains WHERE id = $3 AND updated = $4)
+                     RETURNING 1
+                 )
+                 UPDATE domains
                  SET name = $1,
                      updated = GREATEST(statement_timestamp(), updated + interval '1 microsecond'),
-                     soa = $2
+                     soa = CASE
+                         WHEN jsonb_typeof($2::jsonb->'serial') = 'number'
+                          AND jsonb_typeof(soa->'serial') = 'number'
+                         THEN jsonb_set($2::jsonb, '{serial}', to_jsonb(GREATEST(
+                             ($2::jsonb->>'serial')::bigint,
+                             (soa->>'serial')::bigint + (SELECT count(*) FROM folded) + 1
+                         )))
+                         ELSE $2::jsonb
+                     END
                  WHERE id = $3
                    AND updated = $4
                  RETURNING *";
@@ -19,7 +19,7 @@ use std::net::IpAddr;
 use carbide_uuid::domain::DomainId;
 use dns_record::SoaRecord;
 use sqlx::postgres::PgRow;
-use sqlx::{Error, FromRow, Row};
+use sqlx::{Error, FromRow, PgConnection, Row};
 
 use crate::DatabaseError;
 use crate::db_read::DbReader;
@@ -82,7 +82,7 @@ pub async fn get_soa_record(
     let (query, domain_name) =
         if let Some(reverse_zone) = crate::dns::normalize_reverse_zone_name(query_name) {
             (
-                "SELECT soa FROM domains
+                "SELECT dns_served_soa(id, soa) AS soa FROM domains
              WHERE lower(rtrim(name, '.')) = $1
                AND deleted IS NULL
                AND (
@@ -93,7 +93,8 @@ pub async fn get_soa_record(
             )
         } else {
             (
-                "SELECT soa FROM domains WHERE name = $1 AND deleted IS NULL",
+                "SELECT dns_served_soa(id, soa) AS soa FROM domains
+             WHERE name = $1 AND deleted IS NULL",
                 crate::dns::normalize_domain(query_name),
             )
         };
@@ -264,6 +265,254 @@ pub async fn get_all_records(
         .map_err(|e| DatabaseError::query(query, e))
 }
 
+/// A record stored in `dns_custom_records` rather than derived from an address:
+/// a CNAME, TXT, SRV, MX or delegating NS record. `content` is the record's
+/// presentation-format RDATA, returned to the DNS server unchanged.
+#[derive(Debug, Clone)]
+pub struct NewCustomRecord {
+    pub domain_id: DomainId,
+    pub q_name: String,
+    pub q_type: String,
+    pub ttl: Option<i32>,
+    pub content: String,
+}
+
+struct DbCustomRecord(DbResourceRecord);
+
+impl<'r> FromRow<'r, PgRow> for DbCustomRecord {
+    fn from_row(row: &'r PgRow) -> Result<Self, Error> {
+        Ok(DbCustomRecord(DbResourceRecord {
+            q_name: row.try_get("q_name")?,
+            record: row.try_get("content")?,
+            q_type: row.try_get("q_type")?,
+            ttl: row.try_get("ttl")?,
+            domain_id: row.try_get("domain_id")?,
+        }))
+    }
+}
+
+/// A `dns_custom_records` row as managed over the API.
+struct DbStoredCustomRecord(model::dns::CustomRecord);
+
+impl<'r> FromRow<'r, PgRow> for DbStoredCustomRecord {
+    fn from_row(row: &'r PgRow) -> Result<Self, Error> {
+        let ttl: Option<i32> = row.try_get("ttl")?;
+        Ok(DbStoredCustomRecord(model::dns::CustomRecord {
+            id: row.try_get("id")?,
+            domain_id: row.try_get("domain_id")?,
+            q_name: row.try_get("q_name")?,
+            q_type: row.try_get("q_type")?,
+            ttl: ttl.map(|ttl| ttl as u32),
+            content: row.try_get("content")?,
+            created: row.try_get("created")?,
+        }))
+    }
+}
+
+const CUSTOM_RECORD_COLUMNS: &str = "id, domain_id, q_name, q_type, ttl, content, created";
+
+/// Maps a violation of the `(q_name, q_type, content)` uniqueness to
+/// `AlreadyFoundError`, so storing the same record twice is reported as such.
+fn custom_record_write_error(query: &str, q_name: &str, error: Error) -> DatabaseError {
+    if error
+        .as_database_error()
+        .is_some_and(|e| e.is_unique_violation())
+    {
+        DatabaseError::AlreadyFoundError {
+            kind: "dns_custom_record",
+            id: q_name.to_string(),
+        }
+    } else {
+        DatabaseError::query(query, error)
+    }
+}
+
+/// Store a custom record. The name is stored lowercased with a trailing dot so
+/// it matches the FQDN form the `dns_records` view and `find_custom_records`
+/// use. The domain's SOA serial is bumped by the table's trigger.
+pub async fn persist_custom_record(
+    value: NewCustomRecord,
+    txn: &mut PgConnection,
+) -> Result<model::dns::CustomRecord, DatabaseError> {
+    let q_name = format!("{}.", crate::dns::normalize_domain(&value.q_name));
+    let query = format!(
+        "INSERT INTO dns_custom_records (domain_id, q_name, q_type, ttl, content)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING {CUSTOM_RECORD_COLUMNS}"
+    );
+    sqlx::query_as::<_, DbStoredCustomRecord>(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(value.domain_id)
+        .bind(&q_name)
+        .bind(value.q_type)
+        .bind(value.ttl)
+        .bind(value.content)
+        .fetch_one(txn)
+        .await
+        .map(|record| record.0)
+        .map_err(|e| custom_record_write_error(&query, &q_name, e))
+}
+
+/// Replace the TTL and content of a custom record. Its name and type are
+/// fixed; a record of another name or type is a new record.
+pub async fn update_custom_record(
+    id: uuid::Uuid,
+    ttl: Option<i32>,
+    content: &str,
+    txn: &mut PgConnection,
+) -> Result<model::dns::CustomRecord, DatabaseError> {
+    let query = format!(
+        "UPDATE dns_custom_records SET ttl = $2, content = $3
+        WHERE id = $1
+        RETURNING {CUSTOM_RECORD_COLUMNS}"
+    );
+    sqlx::query_as::<_, DbStoredCustomRecord>(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(id)
+        .bind(ttl)
+        .bind(content)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| custom_record_write_error(&query, &id.to_string(), e))?
+        .map(|record| record.0)
+        .ok_or_else(|| DatabaseError::NotFoundError {
+            kind: "dns_custom_record",
+            id: id.to_string(),
+        })
+}
+
+/// Delete a custom record, returning what was deleted.
+pub async fn delete_custom_record(
+    id: uuid::Uuid,
+    txn: &mut PgConnection,
+) -> Result<model::dns::CustomRecord, DatabaseError> {
+    let query =
+        format!("DELETE FROM dns_custom_records WHERE id = $1 RETURNING {CUSTOM_RECORD_COLUMNS}");
+    sqlx::query_as::<_, DbStoredCustomRecord>(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))?
+        .map(|record| record.0)
+        .ok_or_else(|| DatabaseError::NotFoundError {
+            kind: "dns_custom_record",
+            id: id.to_string(),
+        })
+}
+
+/// Find a custom record by its ID.
+pub async fn find_custom_record_by_id(
+    txn: impl DbReader<'_>,
+    id: uuid::Uuid,
+) -> Result<Option<model::dns::CustomRecord>, DatabaseError> {
+    let query = format!("SELECT {CUSTOM_RECORD_COLUMNS} FROM dns_custom_records WHERE id = $1");
+    sqlx::query_as::<_, DbStoredCustomRecord>(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(id)
+        .fetch_optional(txn)
+        .await
+        .map(|record| record.map(|r| r.0))
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// The custom records stored for a domain, or for all domains, ordered by
+/// name and type.
+pub async fn find_custom_records_by_domain(
+    txn: impl DbReader<'_>,
+    domain_id: Option<DomainId>,
+) -> Result<Vec<model::dns::CustomRecord>, DatabaseError> {
+    let query = format!(
+        "SELECT {CUSTOM_RECORD_COLUMNS} FROM dns_custom_records
+        WHERE $1::uuid IS NULL OR domain_id = $1
+        ORDER BY q_name, q_type, content"
+    );
+    sqlx::query_as::<_, DbStoredCustomRecord>(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(domain_id)
+        .fetch_all(txn)
+        .await
+        .map(|records| records.into_iter().map(|r| r.0).collect())
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// Find the custom records for a FQDN (with trailing dot) in a live domain.
+pub async fn find_custom_records(
+    txn: impl DbReader<'_>,
+    query_name: &str,
+) -> Result<Vec<DbResourceRecord>, DatabaseError> {
+    let query = r#"
+        SELECT cr.q_name, cr.q_type, cr.content, cr.domain_id,
+               COALESCE(cr.ttl, 300) as ttl
+        FROM dns_custom_records cr
+        JOIN domains d ON d.id = cr.domain_id
+        WHERE cr.q_name = lower($1) AND d.deleted IS NULL
+    "#;
+
+    sqlx::query_as::<_, DbCustomRecord>(query)
+        .bind(query_name)
+        .fetch_all(txn)
+        .await
+        .map(|records| records.into_iter().map(|r| r.0).collect())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Every record served at a FQDN (with trailing dot), custom and address
+/// records alike, for checking a new custom record against them. A
+/// transaction-scoped advisory lock on the name is taken first, so writers of
+/// records at the same name check and store one after another.
+pub async fn lock_records_at_name(
+    txn: &mut PgConnection,
+    query_name: &str,
+) -> Result<Vec<DbResourceRecord>, DatabaseError> {
+    let query_name = query_name.to_lowercase();
+    let lock = "SELECT pg_advisory_xact_lock(hashtextextended('dns:record-name:' || $1, 0))";
+    sqlx::query(lock)
+        .bind(&query_name)
+        .execute(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(lock, e))?;
+
+    let query = r#"
+        SELECT q_name, q_type, content, domain_id, COALESCE(ttl, 300) AS ttl
+        FROM dns_custom_records
+        WHERE q_name = $1
+        UNION ALL
+        SELECT q_name,
+               COALESCE(q_type, CASE WHEN family(resource_record) = 6 THEN 'AAAA' ELSE 'A' END),
+               host(resource_record),
+               domain_id,
+               COALESCE(ttl, 300)
+        FROM dns_records
+        WHERE q_name = $1
+    "#;
+    sqlx::query_as::<_, DbCustomRecord>(query)
+        .bind(&query_name)
+        .fetch_all(txn)
+        .await
+        .map(|records| records.into_iter().map(|r| r.0).collect())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// All custom records of a domain, for zone transfer. Ordered by name so
+/// records sharing an owner name are sent together.
+pub async fn get_all_custom_records(
+    txn: impl DbReader<'_>,
+    query_name: &str,
+) -> Result<Vec<DbResourceRecord>, DatabaseError> {
+    let domain_name = crate::dns::normalize_domain(query_name);
+    let query = r#"
+        SELECT cr.q_name, cr.q_type, cr.content, cr.domain_id,
+               COALESCE(cr.ttl, 300) as ttl
+        FROM dns_custom_records cr
+        JOIN domains d ON d.id = cr.domain_id
+        WHERE d.name = $1 AND d.deleted IS NULL
+        ORDER BY cr.q_name, cr.q_type
+    "#;
+
+    sqlx::query_as::<_, DbCustomRecord>(query)
+        .bind(domain_name)
+        .fetch_all(txn)
+        .await
+        .map(|records| records.into_iter().map(|r| r.0).collect())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
 #[cfg(test)]
 mod tests {
     use carbide_uuid::instance::InstanceId;
@@ -782,4 +1031,58 @@ mod tests {
             .unwrap();
         assert!(ptrs.is_empty(), "a deleted domain cannot publish PTR");
     }
+
+    #[crate::sqlx_test]
+    async fn custom_records_are_found_by_name_and_by_zone(pool: sqlx::PgPool) {
+        let mut txn = pool.begin().await.unwrap();
+        let zone = domain::persist(
+            NewDomain::new("custom.example.com".to_string()),
+            txn.as_mut(),
+        )
+        .await
+        .unwrap();
+        for (q_type, content) in [
+            ("TXT", "\"v=spf1 -all\""),
+            ("SRV", "10 5 5060 sip.custom.example.com."),
+        ] {
+            super::persist_custom_record(
+                super::NewCustomRecord {
+                    domain_id: zone.id,
+                    // Stored names are normalized to lowercase FQDNs.
+                    q_name: "_Sip._TCP.custom.example.com".to_string(),
+                    q_type: q_type.to_string(),
+                    ttl: None,
+                    content: content.to_string(),
+                },
+                txn.as_mut(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let by_name = super::find_custom_records(txn.as_mut(), "_sip._tcp.custom.example.com.")
+            .await
+            .unwrap();
+        let mut types = by_name
+            .iter()
+            .map(|r| r.q_type.as_str())
+            .collect::<Vec<_>>();
+        types.sort_unstable();
+        assert_eq!(types, ["SRV", "TXT"]);
+        assert!(by_name.iter().all(|r| r.ttl == 300), "TTL defaults to 300");
+
+        let by_zone = super::get_all_custom_records(txn.as_mut(), "custom.example.com.")
+            .await
+            .unwrap();
+        assert_eq!(by_zone.len(), 2);
+
+        domain::delete(zone, txn.as_mut()).await.unwrap();
+        let by_zone = super::get_all_custom_records(txn.as_mut(), "custom.example.com")
+            .await
+            .unwrap();
+        assert!(
+            by_zone.is_empty(),
+            "a deleted domain serves no custom records"
+        );
+    }
 }
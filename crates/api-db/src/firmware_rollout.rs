@@ -0,0 +1,362 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Staged firmware rollouts and the hosts each one has started.
+
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use model::firmware_rollout::{
+    FirmwareRollout, FirmwareRolloutMachine, FirmwareRolloutMachineOutcome, FirmwareRolloutSpec,
+    FirmwareRolloutTarget,
+};
+use sqlx::PgConnection;
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+const ROLLOUT_COLUMNS: &str = "name, target, spec, state, current_wave, wave_started_at, \
+     wave_completed_at, paused_reason, created_at, updated_at";
+
+/// Creates an active rollout starting at its first wave.
+///
+/// Fails with [`DatabaseError::AlreadyFoundError`] if the name is taken or
+/// `target` already has a rollout that has not completed.
+pub async fn create(
+    txn: &mut PgConnection,
+    name: &str,
+    target: FirmwareRolloutTarget,
+    spec: &FirmwareRolloutSpec,
+) -> DatabaseResult<FirmwareRollout> {
+    let query = format!(
+        "INSERT INTO firmware_rollouts (name, target, spec) VALUES ($1, $2, $3)
+         RETURNING {ROLLOUT_COLUMNS}"
+    );
+    match sqlx::query_as(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(name)
+        .bind(target)
+        .bind(sqlx::types::Json(spec))
+        .fetch_one(txn)
+        .await
+    {
+        Ok(rollout) => Ok(rollout),
+        Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => {
+            Err(DatabaseError::AlreadyFoundError {
+                kind: "firmware_rollout",
+                id: if db_err.constraint() == Some("firmware_rollouts_one_live_per_target_idx") {
+                    format!("{target:?}")
+                } else {
+                    name.to_string()
+                },
+            })
+        }
+        Err(e) => Err(DatabaseError::query(&query, e)),
+    }
+}
+
+/// Returns the named rollout, or every rollout when `name` is `None`, newest
+/// first.
+pub async fn find(
+    db: impl DbReader<'_>,
+    name: Option<&str>,
+) -> DatabaseResult<Vec<FirmwareRollout>> {
+    let query = format!(
+        "SELECT {ROLLOUT_COLUMNS} FROM firmware_rollouts
+         WHERE $1::text IS NULL OR name = $1
+         ORDER BY created_at DESC"
+    );
+    sqlx::query_as(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(name)
+        .fetch_all(db)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// Locks and returns the rollout of `target` that has not completed, if any.
+pub async fn find_live_for_update(
+    txn: &mut PgConnection,
+    target: FirmwareRolloutTarget,
+) -> DatabaseResult<Option<FirmwareRollout>> {
+    let query = format!(
+        "SELECT {ROLLOUT_COLUMNS} FROM firmware_rollouts
+         WHERE target = $1 AND state <> 'completed'
+         FOR UPDATE"
+    );
+    sqlx::query_as(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(target)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// Writes the progress fields of `rollout` back.
+pub async fn update_progress(
+    txn: &mut PgConnection,
+    rollout: &FirmwareRollout,
+) -> DatabaseResult<()> {
+    const QUERY: &str = "UPDATE firmware_rollouts SET
+            state = $2,
+            current_wave = $3,
+            wave_started_at = $4,
+            wave_completed_at = $5,
+            paused_reason = $6,
+            updated_at = now()
+        WHERE name = $1";
+    sqlx::query(QUERY)
+        .bind(&rollout.name)
+        .bind(rollout.state)
+        .bind(rollout.current_wave)
+        .bind(rollout.wave_started_at)
+        .bind(rollout.wave_completed_at)
+        .bind(&rollout.paused_reason)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(QUERY, e))?;
+    Ok(())
+}
+
+/// Marks `wave` of an active rollout as out of work, starting its soak. Does
+/// nothing if the rollout moved on or was paused meanwhile.
+pub async fn complete_wave(txn: &mut PgConnection, name: &str, wave: i32) -> DatabaseResult<()> {
+    const QUERY: &str = "UPDATE firmware_rollouts SET wave_completed_at = now(), updated_at = now()
+        WHERE name = $1 AND current_wave = $2 AND state = 'active' AND wave_completed_at IS NULL";
+    sqlx::query(QUERY)
+        .bind(name)
+        .bind(wave)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(QUERY, e))?;
+    Ok(())
+}
+
+/// Pauses an active rollout, keeping `reason` for operators.
+///
+/// Returns `None` if no rollout by that name is active.
+pub async fn pause(
+    txn: &mut PgConnection,
+    name: &str,
+    reason: &str,
+) -> DatabaseResult<Option<FirmwareRollout>> {
+    let query = format!(
+        "UPDATE firmware_rollouts SET state = 'paused', paused_reason = $2, updated_at = now()
+         WHERE name = $1 AND state = 'active'
+         RETURNING {ROLLOUT_COLUMNS}"
+    );
+    sqlx::query_as(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(name)
+        .bind(reason)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// Resumes a paused rollout. Failures already seen in the current wave stop
+/// counting against its threshold.
+///
+/// Returns `None` if no rollout by that name is paused.
+pub async fn resume(txn: &mut PgConnection, name: &str) -> DatabaseResult<Option<FirmwareRollout>> {
+    let query = format!(
+        "UPDATE firmware_rollouts SET
+            state = 'active',
+            paused_reason = NULL,
+            wave_started_at = now(),
+            updated_at = now()
+         WHERE name = $1 AND state = 'paused'
+         RETURNING {ROLLOUT_COLUMNS}"
+    );
+    sqlx::query_as(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(name)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// Deletes a rollout and its host records. Returns whether it existed.
+pub async fn delete(txn: &mut PgConnection, name: &str) -> DatabaseResult<bool> {
+    const QUERY: &str = "DELETE FROM firmware_rollouts WHERE name = $1";
+    let result = sqlx::query(QUERY)
+        .bind(name)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(QUERY, e))?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns every host the rollout has started, oldest first.
+pub async fn find_machines(
+    db: impl DbReader<'_>,
+    name: &str,
+) -> DatabaseResult<Vec<FirmwareRolloutMachine>> {
+    const QUERY: &str = "SELECT machine_id, wave, outcome, started_at, finished_at
+        FROM firmware_rollout_machines
+        WHERE rollout_name = $1
+        ORDER BY started_at, machine_id";
+    sqlx::query_as(QUERY)
+        .bind(name)
+        .fetch_all(db)
+        .await
+        .map_err(|e| DatabaseError::query(QUERY, e))
+}
+
+/// Records hosts the rollout just started updating in `wave`.
+pub async fn record_started(
+    txn: &mut PgConnection,
+    name: &str,
+    wave: i32,
+    machine_ids: &[MachineId],
+) -> DatabaseResult<()> {
+    const QUERY: &str = "INSERT INTO firmware_rollout_machines (rollout_name, machine_id, wave)
+        SELECT $1, machine_id, $2 FROM unnest($3::text[]) AS machine_id
+        ON CONFLICT DO NOTHING";
+    sqlx::query(QUERY)
+        .bind(name)
+        .bind(wave)
+        .bind(
+            machine_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>(),
+        )
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(QUERY, e))?;
+    Ok(())
+}
+
+/// Records how a host's update ended.
+pub async fn record_outcome(
+    txn: &mut PgConnection,
+    name: &str,
+    machine_id: &MachineId,
+    outcome: FirmwareRolloutMachineOutcome,
+    finished_at: DateTime<Utc>,
+) -> DatabaseResult<()> {
+    const QUERY: &str = "UPDATE firmware_rollout_machines SET outcome = $3, finished_at = $4
+        WHERE rollout_name = $1 AND machine_id = $2";
+    sqlx::query(QUERY)
+        .bind(name)
+        .bind(machine_id)
+        .bind(outcome)
+        .bind(finished_at)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(QUERY, e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use model::firmware_rollout::{
+        FirmwareRolloutSpec, FirmwareRolloutState, FirmwareRolloutTarget, RolloutWaveSize,
+    };
+    use sqlx::PgPool;
+
+    use super::*;
+
+    fn spec() -> FirmwareRolloutSpec {
+        FirmwareRolloutSpec {
+            waves: vec![RolloutWaveSize::Count(1), RolloutWaveSize::Percent(100)],
+            max_per_rack: Some(2),
+            max_per_nvlink_domain: None,
+            soak_time: Duration::from_secs(600),
+            max_failed_percent: 0,
+        }
+    }
+
+    #[crate::sqlx_test]
+    async fn one_live_rollout_per_target(pool: PgPool) -> Result<(), Box<dyn std::error::Error>> {
+        let mut txn = pool.begin().await?;
+        let created = create(
+            txn.as_mut(),
+            "bios-2026",
+            FirmwareRolloutTarget::HostFirmware,
+            &spec(),
+        )
+        .await?;
+        assert_eq!(created.state, FirmwareRolloutState::Active);
+        assert_eq!(created.spec, spec());
+
+        let clash = create(
+            txn.as_mut(),
+            "bios-2026-again",
+            FirmwareRolloutTarget::HostFirmware,
+            &spec(),
+        )
+        .await;
+        assert!(matches!(
+            clash,
+            Err(DatabaseError::AlreadyFoundError { .. })
+        ));
+
+        create(
+            txn.as_mut(),
+            "nic-2026",
+            FirmwareRolloutTarget::DpuNicFirmware,
+            &spec(),
+        )
+        .await?;
+        assert_eq!(find(txn.as_mut(), None).await?.len(), 2);
+
+        // A completed rollout no longer holds its target.
+        let mut done = created;
+        done.state = FirmwareRolloutState::Completed;
+        update_progress(txn.as_mut(), &done).await?;
+        assert!(
+            find_live_for_update(txn.as_mut(), FirmwareRolloutTarget::HostFirmware)
+                .await?
+                .is_none()
+        );
+        create(
+            txn.as_mut(),
+            "bios-2027",
+            FirmwareRolloutTarget::HostFirmware,
+            &spec(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    #[crate::sqlx_test]
+    async fn pause_and_resume(pool: PgPool) -> Result<(), Box<dyn std::error::Error>> {
+        let mut txn = pool.begin().await?;
+        let created = create(
+            txn.as_mut(),
+            "bios-2026",
+            FirmwareRolloutTarget::HostFirmware,
+            &spec(),
+        )
+        .await?;
+
+        let paused = pause(txn.as_mut(), "bios-2026", "holiday freeze")
+            .await?
+            .unwrap();
+        assert_eq!(paused.state, FirmwareRolloutState::Paused);
+        assert_eq!(paused.paused_reason.as_deref(), Some("holiday freeze"));
+        assert!(pause(txn.as_mut(), "bios-2026", "again").await?.is_none());
+
+        let resumed = resume(txn.as_mut(), "bios-2026").await?.unwrap();
+        assert_eq!(resumed.state, FirmwareRolloutState::Active);
+        assert_eq!(resumed.paused_reason, None);
+        assert!(resumed.wave_started_at >= created.wave_started_at);
+
+        assert!(delete(txn.as_mut(), "bios-2026").await?);
+        assert!(!delete(txn.as_mut(), "bios-2026").await?);
+
+        Ok(())
+    }
+}
@@ -336,6 +336,34 @@ pub async fn find_id_by_machine_id(
         .map_err(|e| DatabaseError::query(query, e))
 }
 
+/// Returns the host machine of each of `instance_ids`, including instances
+/// that are being terminated. Unknown IDs are skipped.
+pub async fn find_machine_ids_by_instance_ids(
+    db: impl DbReader<'_>,
+    instance_ids: &[InstanceId],
+) -> Result<Vec<(InstanceId, MachineId)>, DatabaseError> {
+    let query = "SELECT id, machine_id FROM instances WHERE id = ANY($1)";
+    sqlx::query_as(query)
+        .bind(instance_ids)
+        .fetch_all(db)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Returns the instance hosted by each of `machine_ids`, including instances
+/// that are being terminated. Machines without an instance are skipped.
+pub async fn find_instance_ids_by_machine_ids(
+    db: impl DbReader<'_>,
+    machine_ids: &[MachineId],
+) -> Result<Vec<(InstanceId, MachineId)>, DatabaseError> {
+    let query = "SELECT id, machine_id FROM instances WHERE machine_id = ANY($1)";
+    sqlx::query_as(query)
+        .bind(machine_ids)
+        .fetch_all(db)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
 pub async fn find_by_machine_id(
     txn: &mut PgConnection,
     machine_id: &MachineId,
@@ -1278,6 +1306,7 @@ mod tests {
                 allow_override: true,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: model::operating_system_definition::UserDataFormat::CloudInit,
                 ipxe_script: Some("#!ipxe boot-from-os-row".to_string()),
                 ipxe_template_id: None,
                 ipxe_parameters: None,
@@ -19,16 +19,20 @@
 #![cfg_attr(test, allow(txn_held_across_await, txn_without_commit))]
 
 pub mod attestation;
+pub mod audit;
 pub mod bmc_metadata;
 pub mod bmc_redfish_session;
 pub mod bmc_suppression;
 pub mod carbide_version;
+pub mod certificate_revocation;
+pub mod component_firmware_update;
 pub mod compute_allocation;
 pub mod credential_rotation;
 pub mod db_read;
 pub mod desired_firmware;
 pub mod dhcp_entry;
 pub mod dhcp_record;
+pub mod disk_sanitization;
 pub mod dns;
 pub mod dpa_interface;
 pub mod dpu_agent_upgrade_policy;
@@ -41,6 +45,7 @@ pub mod expected_switch;
 pub mod explored_endpoints;
 pub mod explored_managed_host;
 pub mod extension_service;
+pub mod firmware_rollout;
 pub mod health_history;
 pub mod health_report;
 pub mod host_firmware_config;
@@ -21,7 +21,7 @@
  */
 
 use carbide_uuid::machine::MachineId;
-use carbide_uuid::measured_boot::MeasurementReportId;
+use carbide_uuid::measured_boot::{MeasurementBundleId, MeasurementReportId};
 use measured_boot::pcr::PcrRegisterValue;
 use measured_boot::records::{MeasurementReportRecord, MeasurementReportValueRecord};
 use sqlx::{PgConnection, Postgres, QueryBuilder};
@@ -250,3 +250,42 @@ pub async fn get_all_measurement_report_value_records(
         .await
         .map_err(|e| e.with_op_name("get_all_measurement_report_value_records"))
 }
+
+/// upsert_measurement_report_event_log stores the raw event log
+/// for a report. A report whose values didn't change gets reused
+/// on the next attestation, so the latest log simply replaces the
+/// previous one.
+pub async fn upsert_measurement_report_event_log(
+    txn: &mut PgConnection,
+    report_id: MeasurementReportId,
+    event_log: &[u8],
+) -> Result<(), DatabaseError> {
+    let query = "INSERT INTO measurement_report_event_logs(report_id, event_log) VALUES($1, $2)
+        ON CONFLICT (report_id) DO UPDATE SET event_log = EXCLUDED.event_log, ts = clock_timestamp()";
+    sqlx::query(query)
+        .bind(report_id)
+        .bind(event_log)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::new("upsert_measurement_report_event_log", e))?;
+    Ok(())
+}
+
+/// get_latest_event_log_for_bundle_id returns the event log of the
+/// most recent report journaled as matching `bundle_id`, which is
+/// what a mismatching report gets diffed against.
+pub async fn get_latest_event_log_for_bundle_id(
+    txn: &mut PgConnection,
+    bundle_id: MeasurementBundleId,
+) -> Result<Option<Vec<u8>>, DatabaseError> {
+    let query = "SELECT l.event_log FROM measurement_report_event_logs l
+        JOIN measurement_journal j ON j.report_id = l.report_id
+        WHERE j.bundle_id = $1
+        ORDER BY j.ts DESC
+        LIMIT 1";
+    sqlx::query_scalar(query)
+        .bind(bundle_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::new("get_latest_event_log_for_bundle_id", e))
+}
@@ -40,9 +40,10 @@ use crate::db_read::DbReader;
 use crate::measured_boot::interface::common;
 use crate::measured_boot::interface::common::pcr_register_values_to_map;
 use crate::measured_boot::interface::report::{
-    delete_report_for_id, delete_report_values_for_id, get_measurement_report_record_by_id,
-    get_measurement_report_values_for_report_id, insert_measurement_report_record,
-    insert_measurement_report_value_records, update_report_tstamp, update_report_values_tstamp,
+    delete_report_for_id, delete_report_values_for_id, get_latest_event_log_for_bundle_id,
+    get_measurement_report_record_by_id, get_measurement_report_values_for_report_id,
+    insert_measurement_report_record, insert_measurement_report_value_records,
+    update_report_tstamp, update_report_values_tstamp, upsert_measurement_report_event_log,
 };
 use crate::measured_boot::interface::site::{
     get_approval_for_machine_id, get_approval_for_profile_id,
@@ -95,6 +96,26 @@ pub async fn get_all_for_machine_id(
     get_measurement_reports_for_machine_id(txn, machine_id).await
 }
 
+/// set_event_log stores the raw TCG2 event log that produced
+/// `report_id`, so it can later serve as a reference when another
+/// report fails to match the same bundle.
+pub async fn set_event_log(
+    txn: &mut PgConnection,
+    report_id: MeasurementReportId,
+    event_log: &[u8],
+) -> DatabaseResult<()> {
+    upsert_measurement_report_event_log(txn, report_id, event_log).await
+}
+
+/// reference_event_log_for_bundle_id returns the event log of the
+/// latest report that matched `bundle_id`, if one was stored.
+pub async fn reference_event_log_for_bundle_id(
+    txn: &mut PgConnection,
+    bundle_id: MeasurementBundleId,
+) -> DatabaseResult<Option<Vec<u8>>> {
+    get_latest_event_log_for_bundle_id(txn, bundle_id).await
+}
+
 pub async fn get_all<DB>(txn: &mut DB) -> DatabaseResult<Vec<MeasurementReport>>
 where
     for<'db> &'db mut DB: DbReader<'db>,
@@ -22,6 +22,7 @@ use model::metadata::Metadata;
 use model::network_security_group::{
     NetworkSecurityGroup, NetworkSecurityGroupAttachments,
     NetworkSecurityGroupPropagationObjectStatus, NetworkSecurityGroupRule,
+    NetworkSecurityGroupRuleNet, ResolvedNetworkSecurityGroupReferences,
 };
 use model::tenant::TenantOrganizationId;
 use sqlx::{PgConnection, Postgres};
@@ -429,7 +430,9 @@ pub async fn update(
                 rules=$4::jsonb,
                 version=$5::varchar,
                 updated_by=$6::varchar,
-                stateful_egress=$10
+                stateful_egress=$10,
+                references_digest=NULL,
+                resolved_references=NULL
             WHERE
                 /*
                     All but the final `AND NOT EXISTS` are here to be defensive.
@@ -470,6 +473,209 @@ pub async fn update(
     }
 }
 
+/// Resolves the reference nets used by the rules of a NetworkSecurityGroup
+/// to the interface addresses of the matching instances.  References are
+/// only ever resolved against instances owned by the tenant that owns the
+/// NetworkSecurityGroup.  Every address is returned as a host prefix.
+///
+/// * `txn`                    - A reference to an active DB transaction
+/// * `tenant_organization_id` - A reference to the TenantOrganizationId that
+///   owns the NetworkSecurityGroup whose references are being resolved
+/// * `references`             - A slice of reference nets to resolve.  Any
+///   explicit prefixes are ignored.
+pub async fn resolve_references(
+    txn: &mut PgConnection,
+    tenant_organization_id: &TenantOrganizationId,
+    references: &[NetworkSecurityGroupRuleNet],
+) -> Result<ResolvedNetworkSecurityGroupReferences, DatabaseError> {
+    let mut resolved = ResolvedNetworkSecurityGroupReferences::new();
+
+    for reference in references {
+        let mut builder = sqlx::QueryBuilder::new(
+            "SELECT DISTINCT a.address FROM instance_addresses a
+                INNER JOIN instances i ON i.id = a.instance_id
+                LEFT JOIN vpcs v ON v.id = a.vpc_id AND v.deleted IS NULL
+            WHERE i.deleted IS NULL AND i.tenant_org = ",
+        );
+        builder.push_bind(tenant_organization_id.to_string());
+
+        match reference {
+            NetworkSecurityGroupRuleNet::Prefix(_) => continue,
+            // An NSG on the instance takes precedence over the NSG on its VPC,
+            // the same way it does when rules are sent to the DPU.
+            NetworkSecurityGroupRuleNet::NetworkSecurityGroupId(id) => {
+                builder.push(
+                    " AND COALESCE(i.network_security_group_id, v.network_security_group_id) = ",
+                );
+                builder.push_bind(id);
+            }
+            NetworkSecurityGroupRuleNet::VpcId(id) => {
+                builder.push(" AND a.vpc_id = ");
+                builder.push_bind(id);
+            }
+            NetworkSecurityGroupRuleNet::LabelSelector(labels) => {
+                builder.push(" AND i.labels @> ");
+                builder.push_bind(sqlx::types::Json(labels));
+                builder.push("::jsonb");
+            }
+        }
+
+        builder.push(" ORDER BY a.address");
+
+        let addresses: Vec<(ipnetwork::IpNetwork,)> = builder
+            .build_query_as()
+            .fetch_all(&mut *txn)
+            .await
+            .map_err(|err| DatabaseError::query(builder.sql(), err))?;
+
+        resolved.insert(
+            reference.clone(),
+            addresses
+                .into_iter()
+                .map(|(address,)| ipnetwork::IpNetwork::from(address.ip()))
+                .collect(),
+        );
+    }
+
+    Ok(resolved)
+}
+
+/// Returns the IDs and last recorded reference digests of all non-deleted
+/// NetworkSecurityGroup records that have at least one rule with a
+/// security-group, VPC or label-selector reference.
+///
+/// * `txn` - A reference to an active DB transaction
+pub async fn find_references_digests(
+    txn: &mut PgConnection,
+) -> Result<Vec<(NetworkSecurityGroupId, Option<String>)>, DatabaseError> {
+    let query = "SELECT id, references_digest FROM network_security_groups
+            WHERE deleted IS NULL
+                AND jsonb_path_exists(rules, '$[*] ? (!(exists(@.src_net.Prefix)) || !(exists(@.dst_net.Prefix)))')
+            ORDER BY id";
+
+    sqlx::query_as(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Records the prefixes the references of a NetworkSecurityGroup currently
+/// resolve to, along with their digest, as the expansion DPUs are given.
+/// If `bump_version` is set, the version of the NetworkSecurityGroup is also
+/// incremented so that DPUs pick up the change and propagation status is
+/// tracked against the new membership.
+///
+/// Returns false if the record was not updated because it was deleted or its
+/// version no longer matches `expected_version`.
+///
+/// * `txn`              - A reference to an active DB transaction
+/// * `id`               - A reference to the NetworkSecurityGroupId to update
+/// * `expected_version` - The version the digest was computed against
+/// * `digest`           - The digest of the resolved references
+/// * `resolved`         - The resolved references
+/// * `bump_version`     - Whether the version should be incremented
+pub async fn record_references_digest(
+    txn: &mut PgConnection,
+    id: &NetworkSecurityGroupId,
+    expected_version: ConfigVersion,
+    digest: &str,
+    resolved: &ResolvedNetworkSecurityGroupReferences,
+    bump_version: bool,
+) -> Result<bool, DatabaseError> {
+    let query = "UPDATE network_security_groups
+            SET
+                references_digest=$1,
+                version=$2::varchar,
+                resolved_references=$5::jsonb
+            WHERE
+                id=$3::varchar
+                AND version=$4::varchar
+                AND deleted IS NULL
+            RETURNING id";
+
+    let version = if bump_version {
+        expected_version.increment()
+    } else {
+        expected_version
+    };
+
+    match sqlx::query_as::<_, (NetworkSecurityGroupId,)>(query)
+        .bind(digest)
+        .bind(version)
+        .bind(id)
+        .bind(expected_version)
+        .bind(sqlx::types::Json(stored_references(resolved)))
+        .fetch_one(txn)
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(sqlx::Error::RowNotFound) => Ok(false),
+        Err(e) => Err(DatabaseError::query(query, e)),
+    }
+}
+
+/// Records the prefixes the references of a NetworkSecurityGroup resolved
+/// to when its rules were written, as the expansion DPUs are given until
+/// the next reference refresh.
+///
+/// * `txn`      - A reference to an active DB transaction
+/// * `id`       - A reference to the NetworkSecurityGroupId to update
+/// * `resolved` - The resolved references
+pub async fn record_resolved_references(
+    txn: &mut PgConnection,
+    id: &NetworkSecurityGroupId,
+    resolved: &ResolvedNetworkSecurityGroupReferences,
+) -> Result<(), DatabaseError> {
+    let query = "UPDATE network_security_groups
+            SET resolved_references=$1::jsonb
+            WHERE id=$2::varchar AND deleted IS NULL";
+
+    sqlx::query(query)
+        .bind(sqlx::types::Json(stored_references(resolved)))
+        .bind(id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(())
+}
+
+/// Returns the expansion of the references of a NetworkSecurityGroup that
+/// DPUs are given.  That is the last recorded expansion that fit in the
+/// expanded rule limit, or, if none was recorded since the rules last
+/// changed, what the references currently resolve to.
+///
+/// * `txn` - A reference to an active DB transaction
+/// * `nsg` - A reference to the NetworkSecurityGroup
+pub async fn find_resolved_references(
+    txn: &mut PgConnection,
+    nsg: &NetworkSecurityGroup,
+) -> Result<ResolvedNetworkSecurityGroupReferences, DatabaseError> {
+    let query = "SELECT resolved_references FROM network_security_groups WHERE id=$1::varchar";
+
+    let stored: Option<(Option<sqlx::types::Json<StoredReferences>>,)> = sqlx::query_as(query)
+        .bind(&nsg.id)
+        .fetch_optional(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    match stored {
+        Some((Some(stored),)) => Ok(stored.0.into_iter().collect()),
+        _ => resolve_references(txn, &nsg.tenant_organization_id, &nsg.references()).await,
+    }
+}
+
+/// Resolved references as stored in the DB.  JSON object keys have to be
+/// strings, so the map is stored as a list of pairs.
+type StoredReferences = Vec<(NetworkSecurityGroupRuleNet, Vec<ipnetwork::IpNetwork>)>;
+
+fn stored_references(resolved: &ResolvedNetworkSecurityGroupReferences) -> StoredReferences {
+    resolved
+        .iter()
+        .map(|(net, prefixes)| (net.clone(), prefixes.clone()))
+        .collect()
+}
+
 /// Soft deletes an instance type by updating the deleted column in the DB.
 /// If the record with that ID is already deleted, nothing changes and Ok(None)
 /// is returned.
@@ -18,7 +18,9 @@
 use carbide_ipxe_renderer::{
     IpxeTemplateArtifact, IpxeTemplateArtifactCacheStrategy, IpxeTemplateParameter,
 };
+use carbide_uuid::instance::InstanceId;
 use chrono::{DateTime, Utc};
+use model::operating_system_definition::UserDataFormat;
 use serde::Deserialize;
 use sqlx::{FromRow, PgConnection};
 use uuid::Uuid;
@@ -101,6 +103,7 @@ impl From<&OperatingSystem> for model::operating_system_definition::OperatingSys
             allow_override: row.allow_override,
             phone_home_enabled: row.phone_home_enabled,
             user_data: row.user_data.clone(),
+            user_data_format: UserDataFormat::from_db_str(&row.user_data_format),
             created: row.created.to_rfc3339(),
             updated: row.updated.to_rfc3339(),
             ipxe_script: row.ipxe_script.clone(),
@@ -126,6 +129,7 @@ pub struct OperatingSystem {
     pub allow_override: bool,
     pub phone_home_enabled: bool,
     pub user_data: Option<String>,
+    pub user_data_format: String,
     pub created: DateTime<Utc>,
     pub updated: DateTime<Utc>,
     pub deleted: Option<DateTime<Utc>>,
@@ -141,7 +145,7 @@ pub async fn get(
     id: Uuid,
 ) -> Result<OperatingSystem, DatabaseError> {
     let query = "SELECT id, name, description, org, type, status, is_active, allow_override,
-        phone_home_enabled, user_data, created, updated, deleted,
+        phone_home_enabled, user_data, user_data_format, created, updated, deleted,
         ipxe_script, ipxe_template_id, ipxe_parameters, ipxe_artifacts, ipxe_definition_hash
         FROM operating_systems WHERE id = $1 AND deleted IS NULL";
     sqlx::query_as::<_, OperatingSystem>(query)
@@ -160,7 +164,7 @@ pub async fn get_many(
         return Ok(Vec::new());
     }
     let query = "SELECT id, name, description, org, type, status, is_active, allow_override,
-        phone_home_enabled, user_data, created, updated, deleted,
+        phone_home_enabled, user_data, user_data_format, created, updated, deleted,
         ipxe_script, ipxe_template_id, ipxe_parameters, ipxe_artifacts, ipxe_definition_hash
         FROM operating_systems WHERE id = ANY($1) AND deleted IS NULL";
     sqlx::query_as::<_, OperatingSystem>(query)
@@ -170,6 +174,23 @@ pub async fn get_many(
         .map_err(|e| DatabaseError::query(query, e))
 }
 
+/// Returns the user data format of the live operating system definition an
+/// instance references, or `None` if it references none.
+pub async fn find_user_data_format_for_instance(
+    txn: impl sqlx::Executor<'_, Database = sqlx::Postgres>,
+    instance_id: InstanceId,
+) -> Result<Option<UserDataFormat>, DatabaseError> {
+    let query = "SELECT o.user_data_format FROM instances i
+        JOIN operating_systems o ON i.operating_system_id = o.id AND o.deleted IS NULL
+        WHERE i.id = $1";
+    let format = sqlx::query_scalar::<_, String>(query)
+        .bind(instance_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(format.as_deref().map(UserDataFormat::from_db_str))
+}
+
 /// Returns only ids for operating systems matching the filter (optional org). Order by name.
 pub async fn list_ids(
     txn: impl sqlx::Executor<'_, Database = sqlx::Postgres>,
@@ -204,6 +225,7 @@ pub struct CreateOperatingSystem {
     pub allow_override: bool,
     pub phone_home_enabled: bool,
     pub user_data: Option<String>,
+    pub user_data_format: UserDataFormat,
     pub ipxe_script: Option<String>,
     pub ipxe_template_id: Option<String>,
     pub ipxe_parameters: Option<serde_json::Value>,
@@ -218,10 +240,11 @@ pub async fn create(
     let row = if let Some(id) = input.id {
         let query = "INSERT INTO operating_systems
             (id, name, description, org, type, status, is_active, allow_override, phone_home_enabled, user_data,
-             ipxe_script, ipxe_template_id, ipxe_parameters, ipxe_artifacts, ipxe_definition_hash)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+             ipxe_script, ipxe_template_id, ipxe_parameters, ipxe_artifacts, ipxe_definition_hash,
+             user_data_format)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
             RETURNING id, name, description, org, type, status, is_active, allow_override,
-            phone_home_enabled, user_data, created, updated, deleted,
+            phone_home_enabled, user_data, user_data_format, created, updated, deleted,
             ipxe_script, ipxe_template_id, ipxe_parameters, ipxe_artifacts, ipxe_definition_hash";
         sqlx::query_as::<_, OperatingSystem>(query)
             .bind(id)
@@ -239,16 +262,18 @@ pub async fn create(
             .bind(input.ipxe_parameters.as_ref().map(sqlx::types::Json))
             .bind(input.ipxe_artifacts.as_ref().map(sqlx::types::Json))
             .bind(&input.ipxe_definition_hash)
+            .bind(input.user_data_format.as_str())
             .fetch_one(txn)
             .await
             .map_err(|e| DatabaseError::query(query, e))?
     } else {
         let query = "INSERT INTO operating_systems
             (name, description, org, type, status, is_active, allow_override, phone_home_enabled, user_data,
-             ipxe_script, ipxe_template_id, ipxe_parameters, ipxe_artifacts, ipxe_definition_hash)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+             ipxe_script, ipxe_template_id, ipxe_parameters, ipxe_artifacts, ipxe_definition_hash,
+             user_data_format)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
             RETURNING id, name, description, org, type, status, is_active, allow_override,
-            phone_home_enabled, user_data, created, updated, deleted,
+            phone_home_enabled, user_data, user_data_format, created, updated, deleted,
             ipxe_script, ipxe_template_id, ipxe_parameters, ipxe_artifacts, ipxe_definition_hash";
         sqlx::query_as::<_, OperatingSystem>(query)
             .bind(&input.name)
@@ -265,6 +290,7 @@ pub async fn create(
             .bind(input.ipxe_parameters.as_ref().map(sqlx::types::Json))
             .bind(input.ipxe_artifacts.as_ref().map(sqlx::types::Json))
             .bind(&input.ipxe_definition_hash)
+            .bind(input.user_data_format.as_str())
             .fetch_one(txn)
             .await
             .map_err(|e| DatabaseError::query(query, e))?
@@ -281,6 +307,7 @@ pub struct UpdateOperatingSystem {
     pub allow_override: Option<bool>,
     pub phone_home_enabled: Option<bool>,
     pub user_data: Option<String>,
+    pub user_data_format: Option<UserDataFormat>,
     pub ipxe_script: Option<String>,
     pub ipxe_template_id: Option<String>,
     pub ipxe_parameters: Option<serde_json::Value>,
@@ -307,6 +334,10 @@ pub async fn update(
         .phone_home_enabled
         .unwrap_or(existing.phone_home_enabled);
     let user_data = input.user_data.as_deref().or(existing.user_data.as_deref());
+    let user_data_format = input
+        .user_data_format
+        .map(|format| format.as_str())
+        .unwrap_or(&existing.user_data_format);
     let ipxe_script = input
         .ipxe_script
         .as_deref()
@@ -337,10 +368,10 @@ pub async fn update(
         name = $1, description = $2, is_active = $3, allow_override = $4,
         phone_home_enabled = $5, user_data = $6, ipxe_script = $7,
         ipxe_template_id = $8, ipxe_parameters = $9, ipxe_artifacts = $10,
-        ipxe_definition_hash = $11, status = $12, updated = NOW()
-        WHERE id = $13 AND deleted IS NULL
+        ipxe_definition_hash = $11, status = $12, user_data_format = $13, updated = NOW()
+        WHERE id = $14 AND deleted IS NULL
         RETURNING id, name, description, org, type, status, is_active, allow_override,
-        phone_home_enabled, user_data, created, updated, deleted,
+        phone_home_enabled, user_data, user_data_format, created, updated, deleted,
         ipxe_script, ipxe_template_id, ipxe_parameters, ipxe_artifacts, ipxe_definition_hash";
     sqlx::query_as::<_, OperatingSystem>(query)
         .bind(name)
@@ -355,6 +386,7 @@ pub async fn update(
         .bind(ipxe_artifacts)
         .bind(ipxe_definition_hash)
         .bind(status)
+        .bind(user_data_format)
         .bind(input.id)
         .fetch_one(txn)
         .await
@@ -432,6 +464,7 @@ mod tests {
                 allow_override: false,
                 phone_home_enabled: false,
                 user_data: None,
+                user_data_format: UserDataFormat::CloudInit,
                 ipxe_script: Some("#!ipxe\nboot".to_string()),
                 ipxe_template_id: None,
                 ipxe_parameters: None,
@@ -14,6 +14,7 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use carbide_uuid::instance_type::InstanceTypeId;
 use carbide_uuid::machine::MachineId;
 use config_version::ConfigVersion;
 use model::power_manager::{PowerOptions, PowerState};
@@ -85,12 +86,15 @@ pub async fn get_by_ids(
 }
 
 pub async fn persist(options: &PowerOptions, txn: &mut PgConnection) -> Result<(), DatabaseError> {
+    // `idle_parked_at` is owned by the idle host power manager while the host is desired
+    // Off; the state handler only clears it once a woken host is seen On.
     let query = "UPDATE power_options SET 
                                     last_fetched_updated_at=$1, last_fetched_next_try_at=$2,
                                     last_fetched_power_state=$3, last_fetched_off_counter=$4,
                                     wait_until_time_before_performing_next_power_action=$5,
-                                    tried_triggering_on_at=$6, tried_triggering_on_counter=$7
-                                WHERE host_id=$8";
+                                    tried_triggering_on_at=$6, tried_triggering_on_counter=$7,
+                                    idle_parked_at=CASE WHEN desired_power_state='on' THEN $8 ELSE idle_parked_at END
+                                WHERE host_id=$9";
 
     sqlx::query(query)
         .bind(options.last_fetched_updated_at)
@@ -100,6 +104,7 @@ pub async fn persist(options: &PowerOptions, txn: &mut PgConnection) -> Result<(
         .bind(options.wait_until_time_before_performing_next_power_action)
         .bind(options.tried_triggering_on_at)
         .bind(options.tried_triggering_on_counter)
+        .bind(options.idle_parked_at)
         .bind(options.host_id)
         .execute(txn)
         .await
@@ -107,3 +112,99 @@ pub async fn persist(options: &PowerOptions, txn: &mut PgConnection) -> Result<(
 
     Ok(())
 }
+
+/// Powers off an idle host on behalf of the idle host power policy: sets the desired
+/// state to Off and marks the host as parked. Returns `None` and changes nothing if the
+/// desired state changed since `current_version` was read, or if an instance has been
+/// created on the host in the meantime.
+pub async fn park_idle_host(
+    host_id: &MachineId,
+    current_version: &ConfigVersion,
+    txn: &mut PgConnection,
+) -> Result<Option<PowerOptions>, DatabaseError> {
+    let query = "UPDATE power_options SET desired_power_state='off', desired_power_state_version=$1, idle_parked_at=NOW()
+                    WHERE host_id=$2 AND desired_power_state_version=$3
+                        AND NOT EXISTS (SELECT 1 FROM instances WHERE machine_id=$2)
+                    RETURNING *";
+
+    let updated_value = sqlx::query_as(query)
+        .bind(current_version.increment())
+        .bind(host_id)
+        .bind(current_version)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(updated_value)
+}
+
+/// Powers a parked host back on on behalf of the idle host power policy. Returns `None`
+/// and changes nothing if the desired state changed since `current_version` was read or
+/// the host is no longer parked, so a concurrent operator change is not overwritten.
+pub async fn wake_idle_host(
+    host_id: &MachineId,
+    current_version: &ConfigVersion,
+    txn: &mut PgConnection,
+) -> Result<Option<PowerOptions>, DatabaseError> {
+    let query = "UPDATE power_options SET desired_power_state='on', desired_power_state_version=$1
+                    WHERE host_id=$2 AND desired_power_state_version=$3
+                        AND idle_parked_at IS NOT NULL
+                    RETURNING *";
+
+    let updated_value = sqlx::query_as(query)
+        .bind(current_version.increment())
+        .bind(host_id)
+        .bind(current_version)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(updated_value)
+}
+
+/// Upper bound of the recorded wake demand of one instance type; far above any fleet.
+const MAX_IDLE_WAKE_DEMAND: u32 = 1_000_000;
+
+/// Records that an allocation of `instance_type_id` was short `hosts` hosts while
+/// hosts of that type were parked. Adds to demand not yet taken by the idle host
+/// power manager.
+pub async fn record_idle_wake_demand(
+    instance_type_id: &InstanceTypeId,
+    hosts: u32,
+    txn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    if hosts == 0 {
+        return Ok(());
+    }
+
+    let query = "INSERT INTO idle_host_wake_demand (instance_type_id, hosts) VALUES ($1, $2)
+                    ON CONFLICT (instance_type_id) DO UPDATE
+                        SET hosts = LEAST(idle_host_wake_demand.hosts + EXCLUDED.hosts, $3),
+                            updated = NOW()";
+
+    sqlx::query(query)
+        .bind(instance_type_id)
+        .bind(hosts.min(MAX_IDLE_WAKE_DEMAND) as i32)
+        .bind(MAX_IDLE_WAKE_DEMAND as i32)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(())
+}
+
+/// Returns and clears the recorded wake demand of an instance type.
+pub async fn take_idle_wake_demand(
+    instance_type_id: &InstanceTypeId,
+    txn: &mut PgConnection,
+) -> Result<u32, DatabaseError> {
+    let query = "DELETE FROM idle_host_wake_demand WHERE instance_type_id=$1 RETURNING hosts";
+
+    let hosts: Option<i32> = sqlx::query_scalar(query)
+        .bind(instance_type_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(hosts.map_or(0, |hosts| hosts.max(0) as u32))
+}
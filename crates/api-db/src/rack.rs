@@ -20,7 +20,9 @@ use config_version::ConfigVersion;
 use health_report::{HealthReport, HealthReportApplyMode};
 use model::controller_outcome::PersistentStateHandlerOutcome;
 use model::metadata::Metadata;
-use model::rack::{FirmwareUpgradeJob, NvosUpdateJob, Rack, RackConfig, RackState};
+use model::rack::{
+    FirmwareUpgradeJob, NvosUpdateJob, Rack, RackConfig, RackPowerBudgetStatus, RackState,
+};
 use sqlx::PgConnection;
 
 use crate::db_read::DbReader;
@@ -297,6 +299,23 @@ pub async fn update_nvos_update_job(
     Ok(())
 }
 
+/// Records the outcome of a rack power budget pass. Passes run continuously,
+/// so this leaves `updated` alone.
+pub async fn update_power_budget(
+    txn: &mut PgConnection,
+    rack_id: &RackId,
+    power_budget: Option<&RackPowerBudgetStatus>,
+) -> DatabaseResult<()> {
+    let query = "UPDATE racks SET power_budget = $1 WHERE id = $2 RETURNING id";
+    sqlx::query_as::<_, (RackId,)>(query)
+        .bind(power_budget.map(sqlx::types::Json))
+        .bind(rack_id)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::new("update_power_budget", e))?;
+    Ok(())
+}
+
 pub async fn final_delete(txn: &mut PgConnection, rack_id: &RackId) -> DatabaseResult<()> {
     let query = "DELETE from racks WHERE id=$1";
     sqlx::query(query)
@@ -17,15 +17,18 @@
 
 use chrono::{DateTime, Utc};
 use config_version::ConfigVersion;
+use model::state_analytics::{StateAnalyticsFilter, StateAnalyticsObjectType, StateSpan};
 use model::state_history::StateHistoryRecord;
 use serde::Serialize;
 use sqlx::postgres::PgRow;
 use sqlx::{FromRow, PgConnection, Row};
 
+use crate::db_read::DbReader;
 use crate::{DatabaseError, DatabaseResult};
 
 #[derive(Debug, Clone)]
 struct DbStateHistoryRecord {
+    id: i64,
     object_id: String,
     state: String,
     state_version: ConfigVersion,
@@ -35,6 +38,7 @@ struct DbStateHistoryRecord {
 impl<'r> FromRow<'r, PgRow> for DbStateHistoryRecord {
     fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
         Ok(Self {
+            id: row.try_get("id")?,
             object_id: row.try_get("object_id")?,
             state: row.try_get("state")?,
             state_version: row.try_get("state_version")?,
@@ -53,6 +57,31 @@ impl From<DbStateHistoryRecord> for StateHistoryRecord {
     }
 }
 
+/// A state history record together with the ID of the object it belongs to.
+#[derive(Debug, Clone)]
+pub struct ObjectStateHistoryRecord {
+    /// The ID of the history record
+    pub history_id: i64,
+    /// The ID of the object that entered the state
+    pub object_id: String,
+    /// The recorded state transition
+    pub record: StateHistoryRecord,
+}
+
+impl From<DbStateHistoryRecord> for ObjectStateHistoryRecord {
+    fn from(record: DbStateHistoryRecord) -> Self {
+        ObjectStateHistoryRecord {
+            history_id: record.id,
+            object_id: record.object_id,
+            record: StateHistoryRecord {
+                state: record.state,
+                state_version: record.state_version,
+                time: Some(record.timestamp),
+            },
+        }
+    }
+}
+
 /// Identifies the table that is used to store state history.
 #[derive(Debug, Copy, Clone)]
 pub enum StateHistoryTableId {
@@ -96,8 +125,9 @@ pub async fn find_by_object_ids(
         return Ok(std::collections::HashMap::new());
     }
 
-    let mut qb =
-        sqlx::QueryBuilder::new("SELECT object_id, state::TEXT, state_version, timestamp FROM ");
+    let mut qb = sqlx::QueryBuilder::new(
+        "SELECT id, object_id, state::TEXT, state_version, timestamp FROM ",
+    );
     qb.push(table_id.sql_table());
     qb.push(" WHERE object_id IN (");
 
@@ -122,6 +152,124 @@ pub async fn find_by_object_ids(
     Ok(histories)
 }
 
+/// Retrieve the state history records that were persisted at or after `since`,
+/// starting with the oldest.
+///
+/// `timestamp` is assigned when the inserting transaction starts, so a record
+/// can become visible after records with a later timestamp. Callers that poll
+/// with this function should overlap consecutive windows by the longest time a
+/// state-persisting transaction stays open.
+///
+/// If `object_ids` is `Some`, only records of these objects are returned.
+/// Records whose ID is in `skip_ids` are not returned, so overlapping polls
+/// only transfer the records they did not see yet.
+pub async fn find_recorded_since(
+    db: impl DbReader<'_>,
+    table_id: StateHistoryTableId,
+    object_ids: Option<&[String]>,
+    since: DateTime<Utc>,
+    skip_ids: &[i64],
+) -> DatabaseResult<Vec<ObjectStateHistoryRecord>> {
+    let mut qb = sqlx::QueryBuilder::new(
+        "SELECT id, object_id, state::TEXT, state_version, timestamp FROM ",
+    );
+    qb.push(table_id.sql_table());
+    qb.push(" WHERE timestamp >= ");
+    qb.push_bind(since);
+    if !skip_ids.is_empty() {
+        qb.push(" AND id <> ALL(");
+        qb.push_bind(skip_ids);
+        qb.push(")");
+    }
+    if let Some(object_ids) = object_ids {
+        qb.push(" AND object_id = ANY(");
+        qb.push_bind(object_ids);
+        qb.push(")");
+    }
+    qb.push(" ORDER BY id ASC");
+
+    let query_results: Vec<DbStateHistoryRecord> = qb
+        .build_query_as()
+        .fetch_all(db)
+        .await
+        .map_err(|e| DatabaseError::query("state_history::find_recorded_since", e))?;
+
+    Ok(query_results.into_iter().map(Into::into).collect())
+}
+
+/// Retrieve the top-level state spans that overlap the window of `filter`,
+/// ordered by object and entry time.
+///
+/// Consecutive records with the same top-level state are merged into one span,
+/// and a span that was still open at `filter.until` has no `next_state`. Only
+/// objects that still exist are included. Each table keeps the latest 250
+/// records per object, so the oldest spans of busy objects may be missing.
+pub async fn find_state_spans(
+    db: impl DbReader<'_>,
+    filter: &StateAnalyticsFilter,
+) -> DatabaseResult<Vec<StateSpan>> {
+    let (table_id, object_table, soft_deleted) = match filter.object_type {
+        StateAnalyticsObjectType::Machine => (StateHistoryTableId::Machine, "machines", false),
+        StateAnalyticsObjectType::Rack => (StateHistoryTableId::Rack, "racks", true),
+        StateAnalyticsObjectType::Switch => (StateHistoryTableId::Switch, "switches", true),
+        StateAnalyticsObjectType::PowerShelf => {
+            (StateHistoryTableId::PowerShelf, "power_shelves", true)
+        }
+    };
+
+    let mut qb = sqlx::QueryBuilder::new(
+        "WITH entries AS (
+            SELECT h.id, h.object_id, h.timestamp,
+                COALESCE(h.state->>'state', h.state::TEXT) AS state,
+                LAG(COALESCE(h.state->>'state', h.state::TEXT))
+                    OVER (PARTITION BY h.object_id ORDER BY h.id) AS previous_state
+            FROM ",
+    );
+    qb.push(table_id.sql_table());
+    qb.push(" h JOIN ");
+    qb.push(object_table);
+    qb.push(" o ON o.id = h.object_id WHERE h.timestamp < ");
+    qb.push_bind(filter.until);
+    if soft_deleted {
+        qb.push(" AND o.deleted IS NULL");
+    }
+    if let Some(sku) = &filter.sku {
+        qb.push(" AND o.hw_sku = ");
+        qb.push_bind(sku);
+    }
+    if let Some(instance_type_id) = &filter.instance_type_id {
+        qb.push(" AND o.instance_type_id = ");
+        qb.push_bind(instance_type_id);
+    }
+    if let Some(rack_id) = &filter.rack_id {
+        qb.push(match filter.object_type {
+            StateAnalyticsObjectType::Rack => " AND o.id = ",
+            _ => " AND o.rack_id = ",
+        });
+        qb.push_bind(rack_id);
+    }
+    qb.push(
+        "
+        ), spans AS (
+            SELECT object_id, state, timestamp AS entered_at,
+                LEAD(state) OVER w AS next_state,
+                LEAD(timestamp) OVER w AS left_at
+            FROM entries
+            WHERE previous_state IS DISTINCT FROM state
+            WINDOW w AS (PARTITION BY object_id ORDER BY id)
+        )
+        SELECT object_id, state, entered_at, next_state, left_at FROM spans
+        WHERE left_at IS NULL OR left_at >= ",
+    );
+    qb.push_bind(filter.since);
+    qb.push(" ORDER BY object_id, entered_at");
+
+    qb.build_query_as()
+        .fetch_all(db)
+        .await
+        .map_err(|e| DatabaseError::query("state_history::find_state_spans", e))
+}
+
 /// Retrieve state history for a single object.
 pub async fn for_object(
     txn: &mut PgConnection,
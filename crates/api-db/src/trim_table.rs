@@ -29,6 +29,16 @@ pub async fn trim_table(
         model::trim_table::TrimTableTarget::MeasuredBoot => {
             let query = "SELECT * FROM measured_boot_reports_keep_limit($1)";
 
+            let val: (i32,) = sqlx::query_as(query)
+                .bind(keep_entries as i32)
+                .fetch_one(txn)
+                .await
+                .map_err(|e| DatabaseError::new(query, e))?;
+            Ok(val.0)
+        }
+        model::trim_table::TrimTableTarget::AuditEvents => {
+            let query = "SELECT * FROM audit_events_keep_limit($1)";
+
             let val: (i32,) = sqlx::query_as(query)
                 .bind(keep_entries as i32)
                 .fetch_one(txn)
@@ -1421,6 +1421,8 @@ where
         hw_mac_address_ranges: None,
         mac_address_pool: None,
         ufm_mock: Default::default(),
+        scenario: None,
+        lifecycle_report: None,
     };
 
     let (provisionable_handles, mat_handle) = api_test_helper::machine_a_tron::run_local(
@@ -186,6 +186,8 @@ async fn run_machine_a_tron_racks_test(
         hw_mac_address_ranges: None,
         mac_address_pool: None,
         ufm_mock: Default::default(),
+        scenario: None,
+        lifecycle_report: None,
     };
 
     let (provisionable_handles, mat_handle) = api_test_helper::machine_a_tron::run_local(
@@ -0,0 +1,90 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Durable records of mutating API calls.
+
+use chrono::{DateTime, Utc};
+
+/// Which front door a call came through.
+///
+/// Mirrors the `audit_event_source` Postgres enum
+/// (`20261016101500_audit_events.sql`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "audit_event_source", rename_all = "snake_case")]
+pub enum AuditEventSource {
+    /// A `Forge` gRPC method.
+    Grpc,
+    /// A state-changing request to the `/admin` web UI.
+    AdminWeb,
+}
+
+/// One audited call, as it is about to be written.
+///
+/// `request_digest` is the same redacted, truncated rendering the request span
+/// carries -- never the raw payload -- so secrets that are kept out of the logs
+/// are kept out of the table too.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NewAuditEvent {
+    pub occurred_at: DateTime<Utc>,
+    pub source: AuditEventSource,
+    /// The gRPC method name, or `"<HTTP method> <route>"` for web actions.
+    pub method: String,
+    /// Each authenticated principal, rendered with `Principal::audit_identity`
+    /// and, for external users, suffixed with the user name.
+    pub principals: Vec<String>,
+    /// Object ids the request named, in the order they appeared.
+    pub target_ids: Vec<String>,
+    pub request_digest: String,
+    /// The gRPC code name (`"Ok"`, `"NotFound"`, ...) or the HTTP status.
+    pub outcome: String,
+    /// The error message, when the call failed.
+    pub outcome_message: Option<String>,
+    pub client_address: Option<String>,
+}
+
+/// A stored audit event.
+#[derive(Clone, Debug, Eq, PartialEq, sqlx::FromRow)]
+pub struct AuditEvent {
+    pub id: i64,
+    pub occurred_at: DateTime<Utc>,
+    pub source: AuditEventSource,
+    pub method: String,
+    pub principals: Vec<String>,
+    pub target_ids: Vec<String>,
+    pub request_digest: String,
+    pub outcome: String,
+    pub outcome_message: Option<String>,
+    pub client_address: Option<String>,
+}
+
+/// Narrows an audit event search. Unset fields match everything.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AuditEventFilter {
+    /// Exact method name.
+    pub method: Option<String>,
+    /// Matches events where any principal contains this substring, so a bare
+    /// user name or group finds the external principals that carry it.
+    pub principal: Option<String>,
+    /// Exact object id among the event's targets.
+    pub target_id: Option<String>,
+    /// Inclusive lower bound on `occurred_at`.
+    pub since: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `occurred_at`.
+    pub until: Option<DateTime<Utc>>,
+    /// Only events with an id below this one, for paging backwards.
+    pub before_id: Option<i64>,
+}
@@ -40,6 +40,14 @@ pub enum FirmwareState {
     Cancelled,
 }
 
+/// A firmware update pushed to one device by a `core` component manager
+/// backend: the bundle version and the BMC tasks applying it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedFirmwareUpdate {
+    pub target_version: String,
+    pub task_ids: Vec<String>,
+}
+
 /// Switch certificate configuration job lifecycle state returned by component-manager backends.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConfigureSwitchCertificateState {
@@ -0,0 +1,44 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Disk sanitization certificates recorded when a machine is deprovisioned.
+
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+
+/// A stored disk sanitization certificate.
+///
+/// The device records themselves are only kept inside `signed_certificate`,
+/// the JWS scout sent, so what is served is always what the machine signed.
+#[derive(Clone, Debug, Eq, PartialEq, sqlx::FromRow)]
+pub struct DiskSanitizationRecord {
+    pub id: uuid::Uuid,
+    pub machine_id: MachineId,
+    /// The instance whose release opened the record. Unset when the machine
+    /// was cleaned up without an instance release, e.g. on a forced
+    /// deprovision.
+    pub instance_id: Option<InstanceId>,
+    pub tenant_organization_id: Option<String>,
+    pub released_at: Option<DateTime<Utc>>,
+    /// When the API received the certificate. Unset while scout has not
+    /// reported cleanup for the release yet.
+    pub sanitized_at: Option<DateTime<Utc>>,
+    /// SPIFFE ID of the client certificate that signed the certificate.
+    pub signer: Option<String>,
+    pub signed_certificate: Option<String>,
+}
@@ -0,0 +1,38 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::domain::DomainId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Record types that are stored in `dns_custom_records` rather than derived
+/// from machine or instance addresses.
+pub const CUSTOM_RECORD_TYPES: [&str; 5] = ["CNAME", "TXT", "SRV", "MX", "NS"];
+
+/// A stored CNAME, TXT, SRV, MX or delegating NS record of a domain.
+/// `content` is the presentation-format RDATA served unchanged, and a missing
+/// `ttl` is served with the default TTL.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CustomRecord {
+    pub id: uuid::Uuid,
+    pub domain_id: DomainId,
+    pub q_name: String,
+    pub q_type: String,
+    pub ttl: Option<u32>,
+    pub content: String,
+    pub created: DateTime<Utc>,
+}
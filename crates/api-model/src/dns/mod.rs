@@ -18,11 +18,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+pub mod custom_record;
 pub mod domain_info;
 pub mod metadata;
 pub mod resource_record;
 pub mod snapshot;
 
+pub use custom_record::CustomRecord;
 pub use domain_info::DomainInfo;
 pub use metadata::DomainMetadata;
 pub use resource_record::ResourceRecord;
@@ -0,0 +1,246 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Staged firmware rollouts: which update module a rollout gates, its wave
+//! schedule, and the progress the machine update manager records.
+
+use std::time::Duration;
+
+use carbide_utils::config::as_std_duration;
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use duration_str::deserialize_duration;
+use serde::{Deserialize, Serialize};
+
+/// The update module a rollout gates.
+///
+/// Mirrors the `firmware_rollout_target` Postgres enum
+/// (`20261016103000_firmware_rollouts.sql`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, sqlx::Type)]
+#[sqlx(type_name = "firmware_rollout_target", rename_all = "snake_case")]
+pub enum FirmwareRolloutTarget {
+    HostFirmware,
+    DpuNicFirmware,
+}
+
+/// Mirrors the `firmware_rollout_state` Postgres enum.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "firmware_rollout_state", rename_all = "snake_case")]
+pub enum FirmwareRolloutState {
+    /// Starting updates wave by wave.
+    Active,
+    /// Halted by an operator or by a wave crossing its failure threshold; no
+    /// new updates start until resumed.
+    Paused,
+    /// Every wave finished. The module updates freely again.
+    Completed,
+}
+
+/// Mirrors the `firmware_rollout_machine_outcome` Postgres enum.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, sqlx::Type)]
+#[sqlx(
+    type_name = "firmware_rollout_machine_outcome",
+    rename_all = "snake_case"
+)]
+pub enum FirmwareRolloutMachineOutcome {
+    InProgress,
+    Succeeded,
+    Failed,
+}
+
+/// How far one wave reaches, counted over the whole rollout so far.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RolloutWaveSize {
+    /// A fixed number of hosts, typically the canary.
+    Count(u32),
+    /// A share of all managed hosts, from 1 to 100.
+    Percent(u8),
+}
+
+/// The schedule and limits of a rollout, stored as JSON.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FirmwareRolloutSpec {
+    /// Cumulative wave sizes: wave `n` may have started at most
+    /// `waves[n]` hosts across the rollout, including earlier waves.
+    pub waves: Vec<RolloutWaveSize>,
+    /// Most hosts of one rack updating at once.
+    pub max_per_rack: Option<u32>,
+    /// Most hosts of one NVLink domain updating at once.
+    pub max_per_nvlink_domain: Option<u32>,
+    /// Wait between one wave finishing and the next one opening.
+    #[serde(
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub soak_time: Duration,
+    /// A wave pauses the rollout once more than this share of the hosts it
+    /// plans to update have failed.
+    pub max_failed_percent: u8,
+}
+
+impl FirmwareRolloutSpec {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.waves.is_empty() {
+            return Err("a rollout needs at least one wave".to_string());
+        }
+        for wave in &self.waves {
+            match wave {
+                RolloutWaveSize::Count(0) => {
+                    return Err("wave counts must be at least 1".to_string());
+                }
+                RolloutWaveSize::Percent(p) if !(1..=100).contains(p) => {
+                    return Err(format!("wave percentage {p} is not between 1 and 100"));
+                }
+                _ => {}
+            }
+        }
+        if self.max_per_rack == Some(0) || self.max_per_nvlink_domain == Some(0) {
+            return Err("concurrency caps must be at least 1".to_string());
+        }
+        if self.max_failed_percent > 100 {
+            return Err(format!(
+                "failure threshold {}% is above 100%",
+                self.max_failed_percent
+            ));
+        }
+        Ok(())
+    }
+
+    /// How many hosts the rollout may have started by the end of `wave`,
+    /// given `host_count` managed hosts. Percentages round up, so a small
+    /// fleet still gets at least one host per wave.
+    pub fn wave_limit(&self, wave: usize, host_count: usize) -> usize {
+        match self.waves.get(wave) {
+            Some(RolloutWaveSize::Count(count)) => *count as usize,
+            Some(RolloutWaveSize::Percent(percent)) => {
+                (host_count * usize::from(*percent)).div_ceil(100)
+            }
+            None => 0,
+        }
+    }
+}
+
+/// A stored rollout.
+#[derive(Clone, Debug, PartialEq, Eq, sqlx::FromRow)]
+pub struct FirmwareRollout {
+    pub name: String,
+    pub target: FirmwareRolloutTarget,
+    #[sqlx(json)]
+    pub spec: FirmwareRolloutSpec,
+    pub state: FirmwareRolloutState,
+    /// Zero-based index into [`FirmwareRolloutSpec::waves`].
+    pub current_wave: i32,
+    /// Failures count against the current wave's threshold from here on.
+    pub wave_started_at: DateTime<Utc>,
+    /// When the current wave ran out of work; the soak runs from here.
+    pub wave_completed_at: Option<DateTime<Utc>>,
+    pub paused_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One host a rollout started updating.
+#[derive(Clone, Debug, PartialEq, Eq, sqlx::FromRow)]
+pub struct FirmwareRolloutMachine {
+    pub machine_id: MachineId,
+    pub wave: i32,
+    pub outcome: FirmwareRolloutMachineOutcome,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::value_scenarios;
+
+    use super::*;
+
+    fn spec(waves: Vec<RolloutWaveSize>) -> FirmwareRolloutSpec {
+        FirmwareRolloutSpec {
+            waves,
+            max_per_rack: None,
+            max_per_nvlink_domain: None,
+            soak_time: Duration::from_secs(3600),
+            max_failed_percent: 10,
+        }
+    }
+
+    #[test]
+    fn wave_limits() {
+        struct Row {
+            wave: usize,
+            hosts: usize,
+        }
+
+        let spec = spec(vec![
+            RolloutWaveSize::Count(1),
+            RolloutWaveSize::Percent(10),
+            RolloutWaveSize::Percent(100),
+        ]);
+        value_scenarios!(run = |Row { wave, hosts }| spec.wave_limit(wave, hosts);
+            "canary" {
+                Row { wave: 0, hosts: 500 } => 1,
+            }
+            "percentages round up" {
+                Row { wave: 1, hosts: 500 } => 50,
+                Row { wave: 1, hosts: 5 } => 1,
+                Row { wave: 2, hosts: 5 } => 5,
+            }
+            "past the last wave" {
+                Row { wave: 3, hosts: 500 } => 0,
+            }
+        );
+    }
+
+    #[test]
+    fn validation() {
+        value_scenarios!(run = |waves| spec(waves).validate().is_ok();
+            "valid" {
+                vec![RolloutWaveSize::Count(1), RolloutWaveSize::Percent(100)] => true,
+            }
+            "invalid" {
+                vec![] => false,
+                vec![RolloutWaveSize::Count(0)] => false,
+                vec![RolloutWaveSize::Percent(0)] => false,
+                vec![RolloutWaveSize::Percent(101)] => false,
+            }
+        );
+    }
+
+    #[test]
+    fn spec_json_round_trips() {
+        let spec = spec(vec![
+            RolloutWaveSize::Count(2),
+            RolloutWaveSize::Percent(50),
+        ]);
+        let json = serde_json::to_value(&spec).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "waves": [{"count": 2}, {"percent": 50}],
+                "max_per_rack": null,
+                "max_per_nvlink_domain": null,
+                "soak_time": "3600s",
+                "max_failed_percent": 10,
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<FirmwareRolloutSpec>(json).unwrap(),
+            spec
+        );
+    }
+}
@@ -36,13 +36,16 @@ use serde::{Deserialize, Serialize};
 pub mod address_selection_strategy;
 pub mod allocation_type;
 pub mod attestation;
+pub mod audit;
 pub mod bmc_info;
 pub mod bmc_redfish_session;
 pub mod bmc_suppression;
+pub mod certificate_revocation;
 pub mod component_manager;
 pub mod compute_allocation;
 pub mod controller_outcome;
 pub mod dhcp_record;
+pub mod disk_sanitization;
 pub mod dns;
 pub mod dpa_interface;
 pub mod dpu_machine_update;
@@ -55,6 +58,7 @@ pub mod expected_rack;
 pub mod expected_switch;
 pub mod extension_service;
 pub mod firmware;
+pub mod firmware_rollout;
 pub mod hardware_info;
 pub mod health;
 pub mod host_machine_update;
@@ -94,6 +98,7 @@ pub mod site_explorer;
 pub mod site_prefix;
 pub mod sku;
 pub mod spx_partition;
+pub mod state_analytics;
 pub mod state_history;
 pub mod storage;
 pub mod switch;
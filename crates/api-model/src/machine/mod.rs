@@ -243,6 +243,8 @@ pub enum NotAllocatableReason {
     MaintenanceMode,
     #[error("A health alert prevents the machine from being allocated: {0:?}")]
     HealthAlert(Box<health_report::HealthProbeAlert>),
+    #[error("the machine is powered off by the idle host power policy")]
+    IdlePowerParked,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -481,6 +483,7 @@ impl ManagedHostStateSnapshot {
     /// - no health alerts which classification `PreventAllocations` to be set
     /// - the machine not to be in Maintenance Mode
     /// - the desired boot-interface generation to have a matching observation
+    /// - the machine not to be powered off by the idle host power policy
     pub fn is_usable_as_instance(&self, allow_unhealthy: bool) -> Result<(), NotAllocatableReason> {
         // TODO: allow other states than Ready when allow_unhealthy=true. Will require changes to state machine (see Matthias).
         if !matches!(self.managed_state, ManagedHostState::Ready) {
@@ -514,6 +517,16 @@ impl ManagedHostStateSnapshot {
             return Err(NotAllocatableReason::NoDpuSnapshots);
         }
 
+        if self
+            .host_snapshot
+            .status
+            .power_options
+            .as_ref()
+            .is_some_and(|power_options| power_options.idle_parked_at.is_some())
+        {
+            return Err(NotAllocatableReason::IdlePowerParked);
+        }
+
         if !allow_unhealthy
             && let Some(alert) = self.aggregate_health.find_alert_by_classification(
                 &health_report::HealthAlertClassification::prevent_allocations(),
@@ -3571,6 +3584,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn idle_parked_host_is_not_allocatable() {
+        use crate::power_manager::{PowerOptions, PowerState};
+
+        let mut snapshot = managed_host_state_snapshot();
+        let now = Utc::now();
+        let mut power_options = PowerOptions {
+            host_id: snapshot.host_snapshot.id,
+            last_fetched_updated_at: now,
+            last_fetched_next_try_at: now,
+            last_fetched_power_state: PowerState::Off,
+            last_fetched_off_counter: 0,
+            desired_power_state_version: ConfigVersion::initial(),
+            desired_power_state: PowerState::Off,
+            wait_until_time_before_performing_next_power_action: now,
+            tried_triggering_on_at: None,
+            tried_triggering_on_counter: 0,
+            idle_parked_at: Some(now),
+        };
+        snapshot.host_snapshot.status.power_options = Some(power_options.clone());
+        assert_eq!(
+            snapshot.is_usable_as_instance(false),
+            Err(NotAllocatableReason::IdlePowerParked)
+        );
+
+        power_options.idle_parked_at = None;
+        snapshot.host_snapshot.status.power_options = Some(power_options);
+        assert_ne!(
+            snapshot.is_usable_as_instance(false),
+            Err(NotAllocatableReason::IdlePowerParked)
+        );
+    }
+
     #[test]
     fn boot_configuring_state_has_stable_state_strings() {
         let state = ManagedHostState::BootConfiguring {
@@ -14,7 +14,7 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 use carbide_uuid::instance::InstanceId;
@@ -160,32 +160,62 @@ impl fmt::Display for NetworkSecurityGroupRuleAction {
 /// NetworkSecurityGroupRuleNet describes a source or
 /// destination network to look for when matching
 /// network traffic. It can be either an explicit prefix
-/// or defined by an object ID.
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+/// or a reference to a set of objects whose addresses
+/// are resolved to prefixes when the rule is delivered
+/// to a DPU.
+///
+/// References are only ever resolved against objects owned
+/// by the tenant that owns the NSG, so allowing a VPC or NSG
+/// never hands part of the tenant's ACL control to another
+/// tenant.  Because the resolved set changes without the
+/// NSG itself being touched, the NSG version is bumped
+/// whenever the resolved set changes so that propagation
+/// status keeps reflecting what DPUs have actually applied.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub enum NetworkSecurityGroupRuleNet {
     Prefix(ipnetwork::IpNetwork),
-    // Not yet supported.  We hide this in the proto spec.
-    // Implementation wouldn't be hard, but it would be hard
-    // to manage operationally because this would allow users
-    // to create a rule that does not exceed any limits but
-    // later silently exceeds limits by adding more prefixes
-    // to a VPC without ever touching the NSG.  It would be
-    // dangerous/irresponsible to simply allow the overflow
-    // (the behavior on the DPU could be undefined), or trim
-    // out anything beyond our limits.
-    // We either need some job checking the state of things
-    // and alerting, or the DPU needs to alert when limits
-    // are exceeded, but the damage is already done by the
-    // time something is alerting.
-    // This also perhaps opens a security issue unless we
-    // restrict the prefixes that a tenant is allowed to add
-    // to a VPC.  Otherwise, If I allow (VPC ID 123), then
-    // it means I allow whatever random prefixes the owner
-    // of that VPC attaches.  It implicitly hands off part of
-    // "my" ACL control over to the other side.
-    // VpcId(VpcId),
+    /// The interface addresses of all instances that have
+    /// the referenced NSG applied, either directly or
+    /// inherited from their VPC.
+    NetworkSecurityGroupId(NetworkSecurityGroupId),
+    /// The interface addresses of all instances in the VPC.
+    VpcId(VpcId),
+    /// The interface addresses of all instances whose labels
+    /// contain every key/value pair of the selector.
+    LabelSelector(BTreeMap<String, String>),
+}
+
+impl NetworkSecurityGroupRuleNet {
+    /// Returns true if the net must be resolved to prefixes
+    /// before it can be sent to a DPU.
+    pub fn is_reference(&self) -> bool {
+        !matches!(self, NetworkSecurityGroupRuleNet::Prefix(_))
+    }
+}
+
+impl fmt::Display for NetworkSecurityGroupRuleNet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkSecurityGroupRuleNet::Prefix(p) => write!(f, "{p}"),
+            NetworkSecurityGroupRuleNet::NetworkSecurityGroupId(id) => write!(f, "nsg:{id}"),
+            NetworkSecurityGroupRuleNet::VpcId(id) => write!(f, "vpc:{id}"),
+            NetworkSecurityGroupRuleNet::LabelSelector(labels) => {
+                let selector = labels
+                    .iter()
+                    .map(|(k, v)| format!("{k}={v}"))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                write!(f, "labels:{selector}")
+            }
+        }
+    }
 }
 
+/// ResolvedNetworkSecurityGroupReferences maps every referencing
+/// rule net of a set of NSGs to the prefixes it currently expands to.
+pub type ResolvedNetworkSecurityGroupReferences =
+    HashMap<NetworkSecurityGroupRuleNet, Vec<ipnetwork::IpNetwork>>;
+
 /* ********************************** */
 /*       NetworkSecurityGroupRule     */
 /* ********************************** */
@@ -230,6 +260,28 @@ pub struct NetworkSecurityGroup {
     pub updated_by: Option<String>,
 }
 
+impl NetworkSecurityGroup {
+    /// Returns the distinct nets referenced by the rules
+    /// of the NSG that need to be resolved to prefixes.
+    pub fn references(&self) -> Vec<NetworkSecurityGroupRuleNet> {
+        rule_references(&self.rules)
+    }
+}
+
+/// Returns the distinct nets referenced by a set of rules
+/// that need to be resolved to prefixes.
+pub fn rule_references(rules: &[NetworkSecurityGroupRule]) -> Vec<NetworkSecurityGroupRuleNet> {
+    let mut references = Vec::new();
+    for rule in rules {
+        for net in [&rule.src_net, &rule.dst_net] {
+            if net.is_reference() && !references.contains(net) {
+                references.push(net.clone());
+            }
+        }
+    }
+    references
+}
+
 /* ******************************************* */
 /*         NetworkSecurityGroupAttachments     */
 /* ******************************************* */
@@ -374,4 +426,31 @@ mod tests {
 
         assert_eq!(rule.priority, 1);
     }
+
+    #[test]
+    fn persisted_rule_nets_round_trip() {
+        let nets = vec![
+            NetworkSecurityGroupRuleNet::Prefix("10.0.0.0/8".parse().unwrap()),
+            NetworkSecurityGroupRuleNet::NetworkSecurityGroupId(
+                "b65b13d6-d81c-11ef-9252-b346dc360bd4".parse().unwrap(),
+            ),
+            NetworkSecurityGroupRuleNet::VpcId(
+                "1ec6e5fa-98c7-4c5a-a9a3-4a1b7c4b0f2d".parse().unwrap(),
+            ),
+            NetworkSecurityGroupRuleNet::LabelSelector(BTreeMap::from([(
+                "role".to_string(),
+                "web".to_string(),
+            )])),
+        ];
+
+        for net in nets {
+            let value = serde_json::to_value(&net).unwrap();
+            let round_tripped: NetworkSecurityGroupRuleNet = serde_json::from_value(value).unwrap();
+            assert_eq!(net, round_tripped);
+        }
+
+        let legacy: NetworkSecurityGroupRuleNet =
+            serde_json::from_value(serde_json::json!({ "Prefix": "0.0.0.0/0" })).unwrap();
+        assert!(!legacy.is_reference());
+    }
 }
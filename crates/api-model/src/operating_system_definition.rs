@@ -28,6 +28,39 @@ pub const OS_TYPE_IPXE: &str = "iPXE";
 /// Database value for the iPXE OS definition (template-based) OS type.
 pub const OS_TYPE_TEMPLATED_IPXE: &str = "ipxe_os_definition";
 
+/// How the `user_data` of an operating system definition is interpreted.
+///
+/// Stored in the `user_data_format` column as [`UserDataFormat::as_str`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UserDataFormat {
+    #[default]
+    CloudInit,
+    /// Ignition config (spec v3) in JSON.
+    Ignition,
+    /// Butane config in YAML; carbide-pxe translates it to Ignition.
+    Butane,
+}
+
+impl UserDataFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CloudInit => "cloud-init",
+            Self::Ignition => "ignition",
+            Self::Butane => "butane",
+        }
+    }
+
+    /// Parses a stored column value. Unknown values fall back to cloud-init,
+    /// which is what every definition used before the column existed.
+    pub fn from_db_str(s: &str) -> Self {
+        match s {
+            "ignition" => Self::Ignition,
+            "butane" => Self::Butane,
+            _ => Self::CloudInit,
+        }
+    }
+}
+
 /// Operating system definition (list/get/create/update response).
 ///
 /// Name matches the RPC message `rpc::forge::OperatingSystem`;
@@ -44,6 +77,7 @@ pub struct OperatingSystem {
     pub allow_override: bool,
     pub phone_home_enabled: bool,
     pub user_data: Option<String>,
+    pub user_data_format: UserDataFormat,
     pub created: String,
     pub updated: String,
     pub ipxe_script: Option<String>,
@@ -90,6 +90,10 @@ pub struct PowerOptions {
     /// Increment it every time you try to power-on the host.
     /// Reset it when host's power state is detected as On.
     pub tried_triggering_on_counter: i32,
+    /// Set while the idle-host power policy keeps the host powered off. The host is not
+    /// allocatable while it is set. Reset it when host's power state is detected as On.
+    #[serde(default)]
+    pub idle_parked_at: Option<DateTime<Utc>>,
 }
 
 /// This function returns updated power options and boolean value which indicates if a power on is
@@ -134,6 +138,7 @@ pub fn update_power_options_for_desired_on_state_on(
     updated_power_options.last_fetched_next_try_at = now + next_try_duration_on_success;
     updated_power_options.tried_triggering_on_counter = 0;
     updated_power_options.last_fetched_off_counter = 0;
+    updated_power_options.idle_parked_at = None;
 }
 
 // Check if DPU sent network observation after power on.
@@ -199,6 +204,7 @@ impl<'r> FromRow<'r, PgRow> for PowerOptions {
         let tried_triggering_on_at: Option<DateTime<Utc>> =
             row.try_get("tried_triggering_on_at").ok();
         let tried_triggering_on_counter = row.try_get("tried_triggering_on_counter")?;
+        let idle_parked_at: Option<DateTime<Utc>> = row.try_get("idle_parked_at").ok();
 
         Ok(Self {
             host_id,
@@ -211,6 +217,7 @@ impl<'r> FromRow<'r, PgRow> for PowerOptions {
             wait_until_time_before_performing_next_power_action,
             tried_triggering_on_at,
             tried_triggering_on_counter,
+            idle_parked_at,
         })
     }
 }
@@ -71,6 +71,8 @@ pub struct Rack {
     pub controller_state_outcome: Option<PersistentStateHandlerOutcome>,
     pub firmware_upgrade_job: Option<FirmwareUpgradeJob>,
     pub nvos_update_job: Option<NvosUpdateJob>,
+    /// Last power budget computed by the rack power budget manager.
+    pub power_budget: Option<RackPowerBudgetStatus>,
     pub health_reports: HealthReportSources,
     pub created: DateTime<Utc>,
     pub updated: DateTime<Utc>,
@@ -137,6 +139,40 @@ impl NvosUpdateJob {
     }
 }
 
+/// RackPowerBudgetStatus is the outcome of the last rack power budget pass:
+/// how much power the rack's power shelves can deliver, how much of it was
+/// handed out as host power limits, and what is left.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RackPowerBudgetStatus {
+    /// Watts available to hosts after PSU losses, the rack limit and the
+    /// profile's reservation are taken into account.
+    pub capacity_watts: u32,
+    /// Sum of the power limits assigned to hosts.
+    pub allocated_watts: u32,
+    /// `capacity_watts - allocated_watts`. Negative when the hosts' minimum
+    /// limits alone exceed the capacity.
+    pub headroom_watts: i64,
+    /// Power shelf PSUs that reported themselves enabled and healthy.
+    pub healthy_power_supplies: u32,
+    /// All PSUs read from the power shelves, healthy or not. Shelves that
+    /// could not be reached contribute none.
+    pub total_power_supplies: u32,
+    /// Power limits currently applied to the rack's hosts.
+    #[serde(default)]
+    pub host_power_limits: Vec<HostPowerLimit>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// HostPowerLimit is the Redfish power limit applied to one host.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostPowerLimit {
+    pub machine_id: MachineId,
+    pub limit_watts: u32,
+    /// When the limit was last written to the host's BMC and read back.
+    #[serde(default)]
+    pub applied_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ResolvedNvosArtifact {
     pub firmware_id: String,
@@ -312,6 +348,11 @@ impl<'r> FromRow<'r, PgRow> for Rack {
             .ok()
             .flatten()
             .map(|j| j.0);
+        let power_budget: Option<RackPowerBudgetStatus> = row
+            .try_get::<Option<sqlx::types::Json<RackPowerBudgetStatus>>, _>("power_budget")
+            .ok()
+            .flatten()
+            .map(|j| j.0);
         Ok(Rack {
             id: row.try_get("id")?,
             rack_profile_id: row.try_get("rack_profile_id")?,
@@ -323,6 +364,7 @@ impl<'r> FromRow<'r, PgRow> for Rack {
             controller_state_outcome: controller_state_outcome.map(|o| o.0),
             firmware_upgrade_job,
             nvos_update_job,
+            power_budget,
             health_reports,
             created: row.try_get("created")?,
             updated: row.try_get("updated")?,
@@ -1091,6 +1133,7 @@ mod tests {
             controller_state_outcome: None,
             firmware_upgrade_job: None,
             nvos_update_job: None,
+            power_budget: None,
             health_reports: Default::default(),
             created: Utc::now(),
             updated: Utc::now(),
@@ -373,6 +373,38 @@ pub struct RackCapabilitiesSet {
     pub power_shelf: RackCapabilityPowerShelf,
 }
 
+/* ********************************** */
+/*          RackPowerBudget           */
+/* ********************************** */
+
+/// RackPowerBudget describes the power envelope of a rack type. The rack
+/// power budget manager combines it with live power shelf telemetry to
+/// decide how much power the rack's hosts may draw.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RackPowerBudget {
+    /// Rated output of a single power shelf PSU in watts. Used for PSUs
+    /// that do not report `PowerCapacityWatts` themselves.
+    pub psu_capacity_watts: u32,
+
+    /// Upper bound on the rack's draw regardless of PSU capacity, e.g. the
+    /// rating of the facility feed.
+    #[serde(default)]
+    pub rack_limit_watts: Option<u32>,
+
+    /// Watts withheld from host allocation for switches, fans and safety
+    /// margin.
+    #[serde(default)]
+    pub reserved_watts: u32,
+
+    /// Lowest power limit assigned to a host, even when the rack budget
+    /// cannot cover it.
+    pub host_min_watts: u32,
+
+    /// Highest power limit assigned to a host.
+    pub host_max_watts: u32,
+}
+
 /* ********************************** */
 /*           RackProfile              */
 /* ********************************** */
@@ -440,6 +472,12 @@ pub struct RackProfile {
     pub attributes: HashMap<String, String>,
 
     pub rack_capabilities: RackCapabilitiesSet,
+
+    /// Power envelope used by the rack power budget manager.
+    ///
+    /// When absent, hosts in racks of this profile are not power capped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_budget: Option<RackPowerBudget>,
 }
 
 /* ********************************** */
@@ -682,6 +720,67 @@ url = "https://firmware.example.invalid/sot/rack.json"
         }
     }
 
+    #[test]
+    fn test_rack_profile_power_budget_toml_deserialization() {
+        let toml_str = r#"
+[NVL72.power_budget]
+psu_capacity_watts = 5500
+rack_limit_watts = 120000
+reserved_watts = 8000
+host_min_watts = 2000
+host_max_watts = 6000
+
+[NVL72.rack_capabilities.compute]
+count = 18
+[NVL72.rack_capabilities.switch]
+count = 9
+[NVL72.rack_capabilities.power_shelf]
+count = 8
+
+[NVL36.power_budget]
+psu_capacity_watts = 3300
+host_min_watts = 1500
+host_max_watts = 5000
+
+[NVL36.rack_capabilities.compute]
+count = 9
+[NVL36.rack_capabilities.switch]
+count = 9
+[NVL36.rack_capabilities.power_shelf]
+count = 2
+
+[Uncapped.rack_capabilities.compute]
+count = 1
+[Uncapped.rack_capabilities.switch]
+count = 0
+[Uncapped.rack_capabilities.power_shelf]
+count = 1
+"#;
+        let config: RackProfileConfig = toml::from_str(toml_str).unwrap();
+
+        assert_eq!(
+            config.get("NVL72").unwrap().power_budget,
+            Some(RackPowerBudget {
+                psu_capacity_watts: 5500,
+                rack_limit_watts: Some(120000),
+                reserved_watts: 8000,
+                host_min_watts: 2000,
+                host_max_watts: 6000,
+            })
+        );
+        assert_eq!(
+            config.get("NVL36").unwrap().power_budget,
+            Some(RackPowerBudget {
+                psu_capacity_watts: 3300,
+                rack_limit_watts: None,
+                reserved_watts: 0,
+                host_min_watts: 1500,
+                host_max_watts: 5000,
+            })
+        );
+        assert_eq!(config.get("Uncapped").unwrap().power_budget, None);
+    }
+
     #[test]
     fn test_rack_profile_config_toml_with_hardware_fields() {
         let toml_str = r#"
@@ -0,0 +1,370 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Fleet-wide time-in-state analytics, computed from the state history tables.
+//!
+//! Analytics work on top-level states (the `state` tag of the serialized
+//! controller state), so sub-state changes inside e.g. `hostinit` extend a
+//! single stay instead of starting a new one.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+/// Stuck objects returned by [`StateDwellAnalytics::compute`], longest stuck
+/// first.
+pub const MAX_STUCK_OBJECTS: usize = 100;
+
+/// Whose state history is analyzed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StateAnalyticsObjectType {
+    Machine,
+    Rack,
+    Switch,
+    PowerShelf,
+}
+
+impl StateAnalyticsObjectType {
+    /// States an object is expected to sit in indefinitely. Objects in these
+    /// states are never reported as stuck.
+    pub fn steady_states(self) -> &'static [&'static str] {
+        match self {
+            StateAnalyticsObjectType::Machine => &["ready", "assigned"],
+            StateAnalyticsObjectType::Rack
+            | StateAnalyticsObjectType::Switch
+            | StateAnalyticsObjectType::PowerShelf => &["ready"],
+        }
+    }
+}
+
+/// Selects the history that analytics are computed from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateAnalyticsFilter {
+    pub object_type: StateAnalyticsObjectType,
+    /// Inclusive start of the window.
+    pub since: DateTime<Utc>,
+    /// Exclusive end of the window.
+    pub until: DateTime<Utc>,
+    /// Exact hardware SKU. Machines only.
+    pub sku: Option<String>,
+    /// Exact rack id; for racks, the rack itself.
+    pub rack_id: Option<String>,
+    /// Exact instance type id. Machines only.
+    pub instance_type_id: Option<String>,
+}
+
+/// One stay of an object in a top-level state.
+#[derive(Clone, Debug, Eq, PartialEq, sqlx::FromRow)]
+pub struct StateSpan {
+    pub object_id: String,
+    pub state: String,
+    pub entered_at: DateTime<Utc>,
+    /// The state entered next, unless the object was still in `state` at the
+    /// end of the window.
+    pub next_state: Option<String>,
+    /// When `next_state` was entered.
+    pub left_at: Option<DateTime<Utc>>,
+}
+
+/// How long objects stayed in one state.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StateDwellStats {
+    pub state: String,
+    /// Stays that ended inside the window; the percentiles cover these.
+    pub completed: u64,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+    /// Objects still in this state at the end of the window.
+    pub in_progress: u64,
+    /// Of those, the ones that have been in it for at least `stuck_after`.
+    pub stuck: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateTransitionCount {
+    pub from_state: String,
+    pub to_state: String,
+    pub count: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StuckObject {
+    pub object_id: String,
+    pub state: String,
+    pub since: DateTime<Utc>,
+}
+
+/// Time-in-state distributions, transition counts and stuck objects for one
+/// window.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateDwellAnalytics {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub object_count: u64,
+    /// Ordered by state name.
+    pub states: Vec<StateDwellStats>,
+    /// Most frequent first.
+    pub transitions: Vec<StateTransitionCount>,
+    /// Longest stuck first, at most [`MAX_STUCK_OBJECTS`].
+    pub stuck_objects: Vec<StuckObject>,
+    pub stuck_objects_truncated: bool,
+}
+
+impl StateDwellAnalytics {
+    /// Aggregates the spans of a window.
+    ///
+    /// Spans that ended before `since` or started at or after `until` are
+    /// ignored. An open span counts as stuck once it has lasted `stuck_after`
+    /// by `until`, unless its state is one of `steady_states`.
+    pub fn compute(
+        spans: &[StateSpan],
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        stuck_after: Duration,
+        steady_states: &[&str],
+    ) -> Self {
+        let mut objects = HashSet::new();
+        let mut dwells: BTreeMap<&str, Vec<Duration>> = BTreeMap::new();
+        let mut open: BTreeMap<&str, (u64, u64)> = BTreeMap::new();
+        let mut transitions: HashMap<(&str, &str), u64> = HashMap::new();
+        let mut stuck_objects = Vec::new();
+
+        for span in spans {
+            if span.entered_at >= until || span.left_at.is_some_and(|left_at| left_at < since) {
+                continue;
+            }
+            objects.insert(span.object_id.as_str());
+            match (&span.next_state, span.left_at) {
+                (Some(next_state), Some(left_at)) if left_at < until => {
+                    dwells
+                        .entry(span.state.as_str())
+                        .or_default()
+                        .push(elapsed(span.entered_at, left_at));
+                    *transitions
+                        .entry((span.state.as_str(), next_state.as_str()))
+                        .or_default() += 1;
+                }
+                _ => {
+                    let (in_progress, stuck) = open.entry(span.state.as_str()).or_default();
+                    *in_progress += 1;
+                    if elapsed(span.entered_at, until) >= stuck_after
+                        && !steady_states.contains(&span.state.as_str())
+                    {
+                        *stuck += 1;
+                        stuck_objects.push(StuckObject {
+                            object_id: span.object_id.clone(),
+                            state: span.state.clone(),
+                            since: span.entered_at,
+                        });
+                    }
+                }
+            }
+        }
+
+        let state_names: BTreeSet<&str> = dwells.keys().chain(open.keys()).copied().collect();
+        let states = state_names
+            .into_iter()
+            .map(|state| {
+                let mut durations = dwells.remove(state).unwrap_or_default();
+                durations.sort_unstable();
+                let (in_progress, stuck) = open.get(state).copied().unwrap_or_default();
+                StateDwellStats {
+                    state: state.to_string(),
+                    completed: durations.len() as u64,
+                    p50: percentile(&durations, 50),
+                    p95: percentile(&durations, 95),
+                    p99: percentile(&durations, 99),
+                    max: durations.last().copied().unwrap_or_default(),
+                    in_progress,
+                    stuck,
+                }
+            })
+            .collect();
+
+        let mut transitions: Vec<_> = transitions
+            .into_iter()
+            .map(|((from_state, to_state), count)| StateTransitionCount {
+                from_state: from_state.to_string(),
+                to_state: to_state.to_string(),
+                count,
+            })
+            .collect();
+        transitions.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.from_state.cmp(&b.from_state))
+                .then_with(|| a.to_state.cmp(&b.to_state))
+        });
+
+        stuck_objects.sort_by(|a, b| {
+            a.since
+                .cmp(&b.since)
+                .then_with(|| a.object_id.cmp(&b.object_id))
+        });
+        let stuck_objects_truncated = stuck_objects.len() > MAX_STUCK_OBJECTS;
+        stuck_objects.truncate(MAX_STUCK_OBJECTS);
+
+        StateDwellAnalytics {
+            since,
+            until,
+            object_count: objects.len() as u64,
+            states,
+            transitions,
+            stuck_objects,
+            stuck_objects_truncated,
+        }
+    }
+}
+
+fn elapsed(from: DateTime<Utc>, to: DateTime<Utc>) -> Duration {
+    (to - from).to_std().unwrap_or_default()
+}
+
+/// Nearest-rank percentile of sorted `durations`; zero when there are none.
+fn percentile(durations: &[Duration], percent: usize) -> Duration {
+    if durations.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (durations.len() * percent).div_ceil(100).max(1);
+    durations[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::value_scenarios;
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        DateTime::UNIX_EPOCH + TimeDelta::minutes(minutes)
+    }
+
+    fn span(object_id: &str, state: &str, entered: i64, left: Option<(&str, i64)>) -> StateSpan {
+        StateSpan {
+            object_id: object_id.to_string(),
+            state: state.to_string(),
+            entered_at: at(entered),
+            next_state: left.map(|(next, _)| next.to_string()),
+            left_at: left.map(|(_, minutes)| at(minutes)),
+        }
+    }
+
+    fn minutes(minutes: u64) -> Duration {
+        Duration::from_secs(minutes * 60)
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let durations: Vec<_> = (1..=10).map(minutes).collect();
+        value_scenarios!(run = |percent| percentile(&durations, percent);
+            "rank rounds up" {
+                50 => minutes(5),
+                95 => minutes(10),
+                1 => minutes(1),
+            }
+        );
+        assert_eq!(percentile(&[], 50), Duration::ZERO);
+    }
+
+    #[test]
+    fn aggregates_dwell_transitions_and_stuck_objects() {
+        let spans = [
+            // Left hostinit inside the window after 20 minutes.
+            span("m1", "hostinit", 10, Some(("ready", 30))),
+            span("m1", "ready", 30, None),
+            // Entered hostinit before the window; the whole stay counts.
+            span("m2", "hostinit", -50, Some(("validation", 10))),
+            // Still validating 110 minutes later: stuck.
+            span("m2", "validation", 10, None),
+            // Steady states are never stuck.
+            span("m3", "assigned", -500, None),
+            // Ended before the window.
+            span("m4", "hostinit", -100, Some(("ready", -10))),
+            // Left after the window ended, so still open at `until`.
+            span("m5", "hostinit", 100, Some(("ready", 200))),
+        ];
+
+        let analytics = StateDwellAnalytics::compute(
+            &spans,
+            at(0),
+            at(120),
+            minutes(30),
+            StateAnalyticsObjectType::Machine.steady_states(),
+        );
+
+        assert_eq!(analytics.object_count, 4);
+        let states: Vec<_> = analytics
+            .states
+            .iter()
+            .map(|s| (s.state.as_str(), s.completed, s.max, s.in_progress, s.stuck))
+            .collect();
+        assert_eq!(
+            states,
+            [
+                ("assigned", 0, Duration::ZERO, 1, 0),
+                ("hostinit", 2, minutes(60), 1, 0),
+                ("ready", 0, Duration::ZERO, 1, 0),
+                ("validation", 0, Duration::ZERO, 1, 1),
+            ]
+        );
+        assert_eq!(analytics.states[1].p50, minutes(20));
+
+        let transitions: Vec<_> = analytics
+            .transitions
+            .iter()
+            .map(|t| (t.from_state.as_str(), t.to_state.as_str(), t.count))
+            .collect();
+        assert_eq!(
+            transitions,
+            [("hostinit", "ready", 1), ("hostinit", "validation", 1)]
+        );
+
+        assert_eq!(
+            analytics.stuck_objects,
+            [StuckObject {
+                object_id: "m2".to_string(),
+                state: "validation".to_string(),
+                since: at(10),
+            }]
+        );
+        assert!(!analytics.stuck_objects_truncated);
+    }
+
+    #[test]
+    fn caps_stuck_objects_longest_first() {
+        let spans: Vec<_> = (0..=MAX_STUCK_OBJECTS as i64)
+            .map(|i| span(&format!("s{i:03}"), "configuring", -i, None))
+            .collect();
+
+        let analytics = StateDwellAnalytics::compute(
+            &spans,
+            at(0),
+            at(60),
+            minutes(30),
+            StateAnalyticsObjectType::Switch.steady_states(),
+        );
+
+        assert_eq!(analytics.states[0].stuck, MAX_STUCK_OBJECTS as u64 + 1);
+        assert_eq!(analytics.stuck_objects.len(), MAX_STUCK_OBJECTS);
+        assert_eq!(analytics.stuck_objects[0].object_id, "s100");
+        assert!(analytics.stuck_objects_truncated);
+    }
+}
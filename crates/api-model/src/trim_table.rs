@@ -18,4 +18,5 @@
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TrimTableTarget {
     MeasuredBoot,
+    AuditEvents,
 }
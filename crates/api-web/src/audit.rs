@@ -0,0 +1,77 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Records state-changing admin web requests in the audit trail.
+//!
+//! Web handlers call [`Api`] in-process, so they never pass the gRPC
+//! listener's audit layer; this middleware audits them at the HTTP level
+//! instead.
+
+use std::sync::Arc;
+
+use axum::extract::{FromRequestParts, MatchedPath, RawPathParams, State as AxumState};
+use axum::middleware::Next;
+use axum::response::Response;
+use carbide_api_core::{AdminWebAction, Api, AuthContext};
+use carbide_authn::middleware::ConnectionAttributes;
+use http::{Method, Request};
+use tonic::service::AxumBody;
+
+/// Audits every request that can change state: anything but a read.
+pub(super) async fn audit_middleware(
+    AxumState(api): AxumState<Arc<Api>>,
+    request: Request<AxumBody>,
+    next: Next,
+) -> Response {
+    if matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    ) {
+        return next.run(request).await;
+    }
+
+    let (mut parts, body) = request.into_parts();
+    let path = parts.uri.path().to_string();
+    // The route template rather than the path, so one action is one method
+    // however many objects it is used on.
+    let route = parts
+        .extensions
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str().to_string())
+        .unwrap_or_else(|| path.clone());
+    let path_params = RawPathParams::from_request_parts(&mut parts, &())
+        .await
+        .map(|params| params.iter().map(|(_, value)| value.to_string()).collect())
+        .unwrap_or_default();
+    let action = AdminWebAction {
+        method: format!("{} {route}", parts.method),
+        path,
+        path_params,
+        principals: parts
+            .extensions
+            .get::<AuthContext>()
+            .map(|auth_context| auth_context.principals.clone())
+            .unwrap_or_default(),
+        client_address: parts
+            .extensions
+            .get::<Arc<ConnectionAttributes>>()
+            .map(|conn_attrs| conn_attrs.peer_address.to_string()),
+    };
+
+    let request = Request::from_parts(parts, body);
+    api.audit_admin_web_action(action, next.run(request)).await
+}
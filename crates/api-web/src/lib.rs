@@ -245,6 +245,7 @@ mod tests;
 
 mod action_status;
 mod attestation;
+mod audit;
 mod auth;
 mod compute_allocation;
 mod configuration;
@@ -908,6 +909,13 @@ fn routes_with_auth_mode(
             .route("/logs", get(logs::page))
             .route("/logs/{source}/stream", get(logs::stream))
             .route("/logs/{source}/history", get(logs::history))
+            // Auditing is innermost, inside web authentication so the OAuth
+            // principal is known, and past admission so shed requests -- which
+            // never ran -- are not recorded.
+            .layer(axum::middleware::from_fn_with_state(
+                api.clone(),
+                audit::audit_middleware,
+            ))
             // Admission is intentionally inside web authentication: OAuth
             // identity is established before fair scheduling classifies the
             // client. Axum runs the later web-auth layer first.
@@ -64,21 +64,10 @@ pub(crate) async fn setup_resources(
     // One vault client serves every credential vault role below.
     let vault_client = create_vault_client(&vault_config)?;
 
-    // Certificate vending is selected independently of the credential store.
-    // SharedVault (the default) reuses `vault_client` (no second client or token
-    // lease); a dedicated cert Vault decouples PKI issuance from credentials and
-    // is fully explicit, never inheriting the credential Vault's env config. The
-    // SPIFFE identity comes from the site-resolved credential Vault config so all
-    // backends mint under the same identity namespace.
+    // Parsed up front so a bad [certificates] section fails the boot before
+    // anything with side effects runs; the provider itself is built below,
+    // once the KMS a local CA unseals its key with exists.
     let cert_config = carbide_config.certificates.to_certificate_config()?;
-    let certificate_provider = create_certificate_provider(
-        &cert_config,
-        &vault_client,
-        SpiffeIdentity {
-            trust_domain: vault_config.spiffe_trust_domain(),
-            machine_base_path: vault_config.spiffe_machine_base_path(),
-        },
-    )?;
 
     let db_pool = connect_postgres(carbide_config).await?;
     let work_lock_manager_handle = work_lock_manager::start(
@@ -193,7 +182,7 @@ pub(crate) async fn setup_resources(
             .as_deref()
             .unwrap_or("vault")
         {
-            "vault" => vault_client,
+            "vault" => vault_client.clone(),
             "memory" => Arc::new(MemoryCredentialStore::default()),
             other => {
                 return Err(eyre::eyre!(
@@ -211,6 +200,24 @@ pub(crate) async fn setup_resources(
         (create_credential_manager_from(store, chain), None)
     };
 
+    // Certificate vending is selected independently of the credential store.
+    // SharedVault (the default) reuses `vault_client` (no second client or token
+    // lease); a dedicated cert Vault decouples PKI issuance from credentials and
+    // is fully explicit, never inheriting the credential Vault's env config. A
+    // local CA unseals its key through the [secrets] KMS. The SPIFFE identity
+    // comes from the site-resolved credential Vault config so all backends mint
+    // under the same identity namespace.
+    let certificate_provider = create_certificate_provider(
+        &cert_config,
+        &vault_client,
+        secrets_context.as_ref().map(|context| context.kms.as_ref()),
+        SpiffeIdentity {
+            trust_domain: vault_config.spiffe_trust_domain(),
+            machine_base_path: vault_config.spiffe_machine_base_path(),
+        },
+    )
+    .await?;
+
     Ok(RuntimeResources {
         credential_manager,
         certificate_provider,
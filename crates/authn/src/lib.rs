@@ -16,6 +16,7 @@
  */
 pub mod config;
 pub mod middleware;
+pub mod revocation;
 pub mod spiffe_id;
 
 #[derive(thiserror::Error, Debug, Clone)]
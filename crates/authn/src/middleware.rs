@@ -25,6 +25,7 @@ use x509_parser::oid_registry;
 use x509_parser::prelude::{FromDer, X509Certificate, X509Name};
 
 use crate::config::{AllowedCertCriteria, CertComponent};
+use crate::revocation::RevocationList;
 use crate::{SpiffeContext, SpiffeError};
 
 // A middleware layer to deal with per-request authentication.
@@ -63,6 +64,9 @@ pub struct CertDescriptionMiddleware<AZ: Authorization> {
     /// migrated to bearer tokens. Scoped to machine certs — service and admin
     /// client certs are unaffected.
     pub machine_certs_enabled: bool,
+    /// Revoked client certificates. A request presenting one gets no
+    /// certificate-derived principal at all, not even `TrustedCertificate`.
+    pub revocation_list: Option<Arc<RevocationList>>,
     _authorization: std::marker::PhantomData<AZ>,
 }
 
@@ -76,6 +80,7 @@ impl<AZ: Authorization> CertDescriptionMiddleware<AZ> {
             extra_allowed_certs,
             bearer_authenticator: None,
             machine_certs_enabled: true,
+            revocation_list: None,
             _authorization: std::marker::PhantomData,
         }
     }
@@ -97,6 +102,14 @@ impl<AZ: Authorization> CertDescriptionMiddleware<AZ> {
         self.machine_certs_enabled = enabled;
         self
     }
+
+    /// Refuses client certificates found in `revocation_list`, which the
+    /// caller keeps up to date.
+    #[must_use]
+    pub fn with_revocation_list(mut self, revocation_list: Arc<RevocationList>) -> Self {
+        self.revocation_list = Some(revocation_list);
+        self
+    }
 }
 
 impl<S, AZ: Authorization> Layer<S> for CertDescriptionMiddleware<AZ> {
@@ -579,11 +592,13 @@ struct ClientCertRejected {
 }
 
 /// Which stage of certificate-to-principal mapping rejected the certificate,
-/// mirroring [`SpiffeError`]'s variants.
+/// mirroring [`SpiffeError`]'s variants, or `Revoked` when the chain held a
+/// certificate on the revocation list.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, carbide_instrument::LabelValue)]
 enum RejectReason {
     Validation,
     Recognition,
+    Revoked,
 }
 
 impl From<&SpiffeError> for RejectReason {
@@ -662,7 +677,21 @@ where
         }
 
         let extensions = request.extensions_mut();
-        if let Some(conn_attrs) = extensions.get::<Arc<ConnectionAttributes>>() {
+        if let Some(conn_attrs) = extensions.get::<Arc<ConnectionAttributes>>()
+            && let Some(revocation_list) = &self.authorization_context.revocation_list
+            && let Some(position) = conn_attrs
+                .peer_certificates
+                .iter()
+                .position(|cert| revocation_list.is_revoked_der(cert.as_ref()))
+        {
+            // A revoked certificate anywhere in the chain voids the whole
+            // chain: nothing is minted from it, `TrustedCertificate` included.
+            carbide_instrument::emit(ClientCertRejected {
+                reason: RejectReason::Revoked,
+                peer_address: conn_attrs.peer_address,
+                error: format!("certificate {position} of the presented chain is revoked"),
+            });
+        } else if let Some(conn_attrs) = extensions.get::<Arc<ConnectionAttributes>>() {
             let peer_certs = &conn_attrs.peer_certificates;
             // rustls presents the end-entity certificate first, intermediates
             // after -- and an intermediate CA certificate never maps to a
@@ -1258,6 +1287,70 @@ mod tests {
         );
     }
 
+    /// A CRL from the issuer of `spiffe_leaf_certificate`'s self-signed
+    /// leaves, revoking `certificate`.
+    fn revocation_list_revoking(certificate: &CertificateDer) -> Arc<RevocationList> {
+        let (_, parsed) = X509Certificate::from_der(certificate.as_ref()).expect("certificate");
+        let issuer = rcgen::Issuer::new(
+            rcgen::CertificateParams::default(),
+            rcgen::KeyPair::generate().expect("key pair"),
+        );
+        let now = time::OffsetDateTime::now_utc();
+        let crl = rcgen::CertificateRevocationListParams {
+            this_update: now,
+            next_update: now + time::Duration::hours(1),
+            crl_number: rcgen::SerialNumber::from(1u64),
+            issuing_distribution_point: None,
+            revoked_certs: vec![rcgen::RevokedCertParams {
+                serial_number: rcgen::SerialNumber::from_slice(parsed.raw_serial()),
+                revocation_time: now,
+                reason_code: None,
+                invalidity_date: None,
+            }],
+            key_identifier_method: rcgen::KeyIdMethod::Sha256,
+        }
+        .signed_by(&issuer)
+        .expect("crl");
+        let list = RevocationList::new();
+        list.update_from_crl(crl.der()).expect("update");
+        Arc::new(list)
+    }
+
+    /// A revoked certificate mints nothing, not even `TrustedCertificate`,
+    /// and counts as a rejection; an unrevoked one from the same issuer is
+    /// unaffected.
+    #[tokio::test]
+    async fn a_revoked_cert_authorizes_nothing() {
+        let revoked = spiffe_leaf_certificate("/carbide-system/sa/test-service");
+        let revocation_list = revocation_list_revoking(&revoked);
+        let metrics = MetricsCapture::start();
+
+        let middleware = CertDescriptionMiddleware::<NoAuthorization>::new(None, spiffe_context())
+            .with_revocation_list(revocation_list.clone());
+        let principals = principals_for_certs(middleware, None, vec![revoked]).await;
+        assert_eq!(principals, Vec::new());
+        assert_eq!(
+            metrics.counter_delta(
+                "carbide_authn_client_cert_rejected_total",
+                &[("reason", "revoked")]
+            ),
+            1.0
+        );
+
+        let middleware = CertDescriptionMiddleware::<NoAuthorization>::new(None, spiffe_context())
+            .with_revocation_list(revocation_list);
+        let principals = principals_for_certs(
+            middleware,
+            None,
+            vec![spiffe_leaf_certificate("/carbide-system/sa/test-service")],
+        )
+        .await;
+        assert!(
+            principals.contains(&Principal::TrustedCertificate),
+            "an unrevoked cert keeps its principals, got {principals:?}"
+        );
+    }
+
     #[tokio::test]
     async fn bearer_ignored_when_no_authenticator_configured() {
         // Without an authenticator installed, an Authorization header is ignored
@@ -0,0 +1,166 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Client-certificate revocation, fed from the certificate provider's CRLs.
+//!
+//! The TLS acceptor only checks that a chain leads to a trusted root; it knows
+//! nothing about revocation. [`RevocationList`] holds the revoked serial
+//! numbers from the latest CRL of each issuer so the authentication middleware
+//! can refuse a revoked certificate before it mints any principal.
+//!
+//! CRL signatures are not verified here: the lists come straight from our own
+//! certificate provider, never from a peer.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+use x509_parser::prelude::{FromDer, X509Certificate};
+use x509_parser::revocation_list::CertificateRevocationList;
+use x509_parser::x509::X509Name;
+
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum RevocationListError {
+    #[error("CRL parse error: {0}")]
+    Parse(String),
+}
+
+#[derive(Debug, Default)]
+pub struct RevocationList {
+    /// Revoked serial numbers (minimal big-endian bytes) by issuer name.
+    revoked: RwLock<HashMap<String, HashSet<Vec<u8>>>>,
+}
+
+impl RevocationList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces everything known about the CRL issuer's revocations with the
+    /// contents of `der_crl`. Returns how many certificates it revokes.
+    pub fn update_from_crl(&self, der_crl: &[u8]) -> Result<usize, RevocationListError> {
+        let (_remainder, crl) = CertificateRevocationList::from_der(der_crl)
+            .map_err(|e| RevocationListError::Parse(e.to_string()))?;
+        let serials: HashSet<Vec<u8>> = crl
+            .iter_revoked_certificates()
+            .map(|revoked| minimal_serial(revoked.raw_serial()))
+            .collect();
+        let count = serials.len();
+        self.revoked
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(issuer_key(crl.issuer()), serials);
+        Ok(count)
+    }
+
+    pub fn is_revoked(&self, certificate: &X509Certificate) -> bool {
+        self.revoked
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&issuer_key(certificate.issuer()))
+            .is_some_and(|serials| serials.contains(&minimal_serial(certificate.raw_serial())))
+    }
+
+    /// Like [`Self::is_revoked`], for a DER certificate. A certificate that
+    /// does not parse is not revoked; SPIFFE validation rejects it anyway.
+    pub fn is_revoked_der(&self, der_certificate: &[u8]) -> bool {
+        X509Certificate::from_der(der_certificate)
+            .is_ok_and(|(_remainder, certificate)| self.is_revoked(&certificate))
+    }
+}
+
+/// Compares names by their RFC 4514 rendering, so a CRL re-encoding its
+/// issuer's attributes with a different string type still matches.
+fn issuer_key(issuer: &X509Name) -> String {
+    issuer.to_string()
+}
+
+fn minimal_serial(raw_serial: &[u8]) -> Vec<u8> {
+    let significant = raw_serial
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(raw_serial.len());
+    raw_serial[significant..].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use rcgen::{
+        BasicConstraints, CertificateParams, CertificateRevocationListParams, IsCa, Issuer,
+        KeyIdMethod, KeyPair, RevokedCertParams, SerialNumber,
+    };
+    use time::OffsetDateTime;
+
+    use super::RevocationList;
+
+    #[test]
+    fn crl_revokes_only_listed_serials_of_its_issuer() {
+        let ca_key = KeyPair::generate().expect("key pair");
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).expect("params");
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let issuer = Issuer::new(ca_params, ca_key);
+
+        let leaf = |serial: u64| {
+            let mut params = CertificateParams::new(Vec::<String>::new()).expect("params");
+            params.serial_number = Some(SerialNumber::from(serial));
+            let key = KeyPair::generate().expect("key pair");
+            params
+                .signed_by(&key, &issuer)
+                .expect("certificate")
+                .der()
+                .to_vec()
+        };
+        let revoked = leaf(7);
+        let valid = leaf(8);
+        let unrelated_key = KeyPair::generate().expect("key pair");
+        let mut unrelated_params = CertificateParams::new(Vec::<String>::new()).expect("params");
+        unrelated_params
+            .distinguished_name
+            .push(rcgen::DnType::CommonName, "elsewhere");
+        unrelated_params.serial_number = Some(SerialNumber::from(7u64));
+        let unrelated = unrelated_params
+            .self_signed(&unrelated_key)
+            .expect("certificate")
+            .der()
+            .to_vec();
+
+        let now = OffsetDateTime::now_utc();
+        let crl = CertificateRevocationListParams {
+            this_update: now,
+            next_update: now + time::Duration::hours(1),
+            crl_number: SerialNumber::from(1u64),
+            issuing_distribution_point: None,
+            revoked_certs: vec![RevokedCertParams {
+                serial_number: SerialNumber::from(7u64),
+                revocation_time: now,
+                reason_code: None,
+                invalidity_date: None,
+            }],
+            key_identifier_method: KeyIdMethod::Sha256,
+        }
+        .signed_by(&issuer)
+        .expect("crl");
+
+        let list = RevocationList::new();
+        assert!(!list.is_revoked_der(&revoked));
+        assert_eq!(list.update_from_crl(crl.der()).expect("update"), 1);
+        assert!(list.is_revoked_der(&revoked));
+        assert!(!list.is_revoked_der(&valid));
+        assert!(!list.is_revoked_der(&unrelated));
+        assert!(!list.is_revoked_der(b"not a certificate"));
+        assert!(list.update_from_crl(b"not a crl").is_err());
+    }
+}
@@ -21,6 +21,7 @@ use crate::redfish;
 use crate::redfish::account_service::AccountServiceState;
 use crate::redfish::chassis::ChassisState;
 use crate::redfish::computer_system::SystemState;
+use crate::redfish::event_service::{EventRecord, EventService};
 use crate::redfish::manager::ManagerState;
 use crate::redfish::session_service::SessionServiceState;
 use crate::redfish::update_service::UpdateServiceState;
@@ -37,6 +38,7 @@ pub struct BmcState {
     pub update_service_state: Arc<UpdateServiceState>,
     pub account_service_state: Arc<AccountServiceState>,
     pub(crate) session_service_state: Arc<SessionServiceState>,
+    pub event_service: Arc<EventService>,
     pub injection: Arc<InjectionStore>,
     pub(crate) callbacks: Option<Arc<dyn crate::Callbacks>>,
     /// Whether this BMC advertises and serves the `/redfish/v1/Systems`
@@ -61,6 +63,10 @@ impl BmcState {
                 // Move any staged firmware versions into the active inventory so
                 // that site-explorer observes the upgraded version after reset.
                 self.update_service_state.apply_staged_firmware();
+                if let Some(system) = self.system_state.controlled_system() {
+                    self.event_service
+                        .publish(EventRecord::powered_on(&system.odata_id()));
+                }
             }
             BmcEvent::BootCompleted => {
                 self.system_state.on_boot_completed();
@@ -294,6 +294,7 @@ impl DellPowerEdgeR750<'_> {
                     chassis_id,
                     Self::sensor_layout(),
                 )),
+                power_control: true,
                 ..redfish::chassis::SingleChassisConfig::defaults()
             }],
         }
@@ -38,6 +38,10 @@ use crate::redfish;
 /// Chassis id reported by a Delta power shelf (matches the scrape).
 const CHASSIS_ID: &str = "chassis";
 
+/// PMC firmware version, reported by both the manager and the firmware
+/// inventory.
+const PMC_FIRMWARE_VERSION: &str = "01.04.01.04";
+
 /// Default per-PSU power states: the six-bay shelf the real scrape reports,
 /// all outputting power.
 pub(crate) const DEFAULT_PSU_POWER: &[bool] = &[true; 6];
@@ -65,7 +69,7 @@ impl DeltaPowerShelf<'_> {
                 ]),
                 host_interfaces: None,
                 serial_interfaces: None,
-                firmware_version: Some("01.04.01.04"),
+                firmware_version: Some(PMC_FIRMWARE_VERSION),
                 oem: None,
             }],
         }
@@ -112,7 +116,13 @@ impl DeltaPowerShelf<'_> {
 
     pub(crate) fn update_service_config(&self) -> redfish::update_service::UpdateServiceConfig {
         redfish::update_service::UpdateServiceConfig {
-            firmware_inventory: vec![],
+            firmware_inventory: vec![
+                redfish::software_inventory::builder(
+                    &redfish::software_inventory::firmware_inventory_resource("PMC"),
+                )
+                .version(PMC_FIRMWARE_VERSION)
+                .build(),
+            ],
             ..Default::default()
         }
     }
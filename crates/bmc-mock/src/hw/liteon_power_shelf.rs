@@ -21,6 +21,10 @@ use mac_address::MacAddress;
 
 use crate::redfish;
 
+/// PMC firmware version, reported by both the manager and the firmware
+/// inventory.
+const PMC_FIRMWARE_VERSION: &str = "r1.3.9";
+
 pub(crate) struct LiteOnPowerShelf<'a> {
     pub(crate) bmc_mac_address: MacAddress,
     pub(crate) product_serial_number: Cow<'a, str>,
@@ -57,7 +61,7 @@ impl LiteOnPowerShelf<'_> {
                 ]),
                 host_interfaces: None,
                 serial_interfaces: None,
-                firmware_version: Some("r1.3.9"),
+                firmware_version: Some(PMC_FIRMWARE_VERSION),
                 oem: None,
             }],
         }
@@ -129,7 +133,13 @@ impl LiteOnPowerShelf<'_> {
 
     pub(crate) fn update_service_config(&self) -> redfish::update_service::UpdateServiceConfig {
         redfish::update_service::UpdateServiceConfig {
-            firmware_inventory: vec![],
+            firmware_inventory: vec![
+                redfish::software_inventory::builder(
+                    &redfish::software_inventory::firmware_inventory_resource("PMC"),
+                )
+                .version(PMC_FIRMWARE_VERSION)
+                .build(),
+            ],
             ..Default::default()
         }
     }
@@ -56,6 +56,7 @@ pub use mock_machine_router::{
     machine_router_with_injection_store,
 };
 pub use rack_info::RackInfo;
+pub use redfish::event_service::{EventRecord, EventService};
 pub use redfish::virtual_media::DeviceConfig as VirtualMediaDeviceConfig;
 
 pub const DUMMY_FACTORY_USERNAME: &str = "root";
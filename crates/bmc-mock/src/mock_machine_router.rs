@@ -22,6 +22,7 @@ use tokio::sync::oneshot;
 use crate::auth_router::Authorizer;
 use crate::bmc_state::BmcState;
 use crate::injection::InjectionStore;
+use crate::redfish::event_service::EventService;
 use crate::redfish::manager::ManagerState;
 use crate::{
     Callbacks, HardwareType, MachineInfo, SystemPowerControl, VirtualMediaDeviceConfig,
@@ -79,17 +80,21 @@ pub fn machine_router(
         mat_host_id,
         redfish_auth,
         Arc::new(InjectionStore::new()),
+        Arc::new(EventService::new()),
         options,
     )
 }
 
-/// Return a machine router backed by a caller-provided injection store.
+/// Return a machine router backed by a caller-provided injection store and
+/// event service, so the caller can change responses and raise events from
+/// outside the Redfish API.
 pub fn machine_router_with_injection_store(
     machine_info: &MachineInfo,
     callbacks: Arc<dyn Callbacks>,
     mat_host_id: String,
     redfish_auth: bool,
     injection: Arc<InjectionStore>,
+    event_service: Arc<EventService>,
 ) -> (Router, BmcState) {
     machine_router_inner(
         machine_info,
@@ -97,6 +102,7 @@ pub fn machine_router_with_injection_store(
         mat_host_id,
         redfish_auth,
         injection,
+        event_service,
         MachineRouterOptions::default(),
     )
 }
@@ -107,6 +113,7 @@ fn machine_router_inner(
     mat_host_id: String,
     redfish_auth: bool,
     injection: Arc<InjectionStore>,
+    event_service: Arc<EventService>,
     options: MachineRouterOptions,
 ) -> (Router, BmcState) {
     let system_config = machine_info.system_config(callbacks.clone());
@@ -124,6 +131,7 @@ fn machine_router_inner(
         .add_routes(crate::redfish::update_service::add_routes)
         .add_routes(crate::redfish::task_service::add_routes)
         .add_routes(crate::redfish::telemetry_service::add_routes)
+        .add_routes(crate::redfish::event_service::add_routes)
         .add_routes(crate::redfish::account_service::add_routes)
         .add_routes(crate::redfish::session_service::add_routes)
         .add_routes(|routes| crate::redfish::computer_system::add_routes(routes, bmc_vendor))
@@ -167,6 +175,7 @@ fn machine_router_inner(
         update_service_state,
         account_service_state,
         session_service_state,
+        event_service,
         injection: injection.clone(),
         callbacks: Some(callbacks.clone()),
         exposes_computer_systems: machine_info.exposes_computer_systems(),
@@ -16,9 +16,11 @@
  */
 
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Mutex;
 
 use axum::Router;
-use axum::extract::{Path, State};
+use axum::extract::{Json, Path, State};
 use axum::response::Response;
 use axum::routing::get;
 use serde_json::json;
@@ -26,6 +28,7 @@ use serde_json::json;
 use crate::bmc_state::BmcState;
 use crate::json::{JsonExt, JsonPatch};
 use crate::redfish::Builder;
+use crate::redfish::event_service::EventRecord;
 use crate::{http, redfish};
 
 pub(super) fn resource<'a>(chassis_id: &'a str) -> redfish::Resource<'a> {
@@ -98,12 +101,16 @@ pub(crate) fn add_routes(r: Router<BmcState>) -> Router<BmcState> {
         )
         .route(
             &redfish::sensor::chassis_resource(CHASSIS_ID, SENSOR_ID).odata_id,
-            get(get_chassis_sensor),
+            get(get_chassis_sensor).patch(patch_chassis_sensor),
         )
         .route(
             &redfish::assembly::chassis_resource(CHASSIS_ID).odata_id,
             get(get_chassis_assembly),
         )
+        .route(
+            &redfish::power::resource(CHASSIS_ID).odata_id,
+            get(get_chassis_power).patch(patch_chassis_power),
+        )
         .route(
             &redfish::power_subsystem::resource(CHASSIS_ID).odata_id,
             get(get_chassis_power_subsystem),
@@ -130,7 +137,7 @@ pub(crate) fn add_routes(r: Router<BmcState>) -> Router<BmcState> {
         )
         .route(
             &redfish::leak_detector::resource(CHASSIS_ID, LEAK_DETECTOR_ID).odata_id,
-            get(get_chassis_leak_detector),
+            get(get_chassis_leak_detector).patch(patch_chassis_leak_detector),
         )
 }
 
@@ -148,6 +155,8 @@ pub(crate) struct SingleChassisConfig {
     pub(crate) chassis_type: Cow<'static, str>,
     pub(crate) assembly: Option<serde_json::Value>,
     pub(crate) power_supplies: Option<Vec<redfish::power_supply::PowerSupply>>,
+    /// Serves the deprecated `Power` resource, whose power limit can be set.
+    pub(crate) power_control: bool,
     pub(crate) oem: Option<serde_json::Value>,
 }
 
@@ -169,6 +178,7 @@ impl SingleChassisConfig {
             leak_detectors: None,
             assembly: None,
             power_supplies: None,
+            power_control: false,
             oem: None,
         }
     }
@@ -205,11 +215,81 @@ impl ChassisState {
 
 pub(crate) struct SingleChassisState {
     pub(crate) config: SingleChassisConfig,
+    /// Readings pinned with a `PATCH` of the sensor. Other sensors report a
+    /// fresh random reading on every read.
+    sensor_readings: Mutex<HashMap<String, f64>>,
+    /// Detector states set with a `PATCH` of the leak detector.
+    leak_detector_states: Mutex<HashMap<String, redfish::resource::Status>>,
+    /// `LimitInWatts` set with a `PATCH` of the `Power` resource.
+    power_limit_watts: Mutex<Option<f64>>,
 }
 
 impl SingleChassisState {
     fn new(config: SingleChassisConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            sensor_readings: Mutex::new(HashMap::new()),
+            leak_detector_states: Mutex::new(HashMap::new()),
+            power_limit_watts: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn sensor_json(&self, sensor: &redfish::sensor::Sensor) -> serde_json::Value {
+        let pinned = self
+            .sensor_readings
+            .lock()
+            .expect("mutex poisoned")
+            .get(sensor.id.as_ref())
+            .copied();
+        match pinned {
+            Some(reading) => sensor.to_json_with_reading(reading),
+            None => sensor.to_json(),
+        }
+    }
+
+    /// Pins a sensor's reading, returning the events for any threshold it
+    /// crossed, or `None` if there is no such sensor.
+    fn set_sensor_reading(&self, sensor_id: &str, reading: f64) -> Option<Vec<EventRecord>> {
+        let sensor = self.find_sensor(sensor_id)?;
+        // Unpinned readings are drawn from within the default thresholds.
+        let previous = self
+            .sensor_readings
+            .lock()
+            .expect("mutex poisoned")
+            .insert(sensor_id.to_string(), reading);
+        let origin = redfish::sensor::chassis_resource(&self.config.id, sensor_id).odata_id;
+        Some(EventRecord::threshold_transition(
+            &origin,
+            sensor_id,
+            previous.and_then(|previous| sensor.breached_threshold(previous)),
+            sensor.breached_threshold(reading),
+        ))
+    }
+
+    /// Sets a leak detector's state, returning the event for the change, if
+    /// any, or `None` if there is no such detector.
+    fn set_leak_detector_state(
+        &self,
+        detector_id: &str,
+        state: redfish::resource::Status,
+    ) -> Option<Option<EventRecord>> {
+        let detector = self.find_leak_detector(detector_id)?;
+        self.leak_detector_states
+            .lock()
+            .expect("mutex poisoned")
+            .insert(detector_id.to_string(), state);
+        let origin = redfish::leak_detector::resource(&self.config.id, detector_id).odata_id;
+        Some(
+            (detector.detector_state != state)
+                .then(|| EventRecord::leak_detector(&origin, detector_id, state)),
+        )
+    }
+
+    fn power_json(&self) -> serde_json::Value {
+        let limit_watts = *self.power_limit_watts.lock().expect("mutex poisoned");
+        redfish::power::builder(&redfish::power::resource(&self.config.id))
+            .power_limit(limit_watts)
+            .build()
     }
 
     pub(crate) fn pcie_devices_resources(&self) -> Vec<redfish::Resource<'static>> {
@@ -250,18 +330,25 @@ impl SingleChassisState {
     }
 
     fn leak_detectors(&self) -> Vec<redfish::leak_detector::LeakDetector> {
-        self.config.leak_detectors.clone().unwrap_or_default()
-    }
-
-    fn find_leak_detector(&self, id: &str) -> Option<&redfish::leak_detector::LeakDetector> {
+        let states = self.leak_detector_states.lock().expect("mutex poisoned");
         self.config
             .leak_detectors
-            .as_ref()
-            .and_then(|leak_detectors| {
-                leak_detectors
-                    .iter()
-                    .find(|detector| detector.id.as_ref() == id)
+            .iter()
+            .flatten()
+            .cloned()
+            .map(|mut detector| {
+                if let Some(state) = states.get(detector.id.as_ref()) {
+                    detector.detector_state = *state;
+                }
+                detector
             })
+            .collect()
+    }
+
+    fn find_leak_detector(&self, id: &str) -> Option<redfish::leak_detector::LeakDetector> {
+        self.leak_detectors()
+            .into_iter()
+            .find(|detector| detector.id.as_ref() == id)
     }
 }
 
@@ -305,6 +392,10 @@ async fn get_chassis(State(state): State<BmcState>, Path(chassis_id): Path<Strin
         .is_some()
         .then_some(redfish::power_subsystem::resource(&chassis_id));
 
+    let power = config
+        .power_control
+        .then_some(redfish::power::resource(&chassis_id));
+
     let thermal_subsystem = config
         .leak_detectors
         .is_some()
@@ -320,6 +411,7 @@ async fn get_chassis(State(state): State<BmcState>, Path(chassis_id): Path<Strin
         .maybe_with(ChassisBuilder::manufacturer, &config.manufacturer)
         .maybe_with(ChassisBuilder::part_number, &config.part_number)
         .maybe_with(ChassisBuilder::sku, &config.sku)
+        .maybe_with(ChassisBuilder::power, &power)
         .maybe_with(ChassisBuilder::power_subsystem, &power_subsystem)
         .maybe_with(ChassisBuilder::thermal_subsystem, &thermal_subsystem)
         .maybe_with(ChassisBuilder::model, &config.model);
@@ -474,8 +566,37 @@ async fn get_chassis_sensor(
     state
         .chassis_state
         .find(&chassis_id)
-        .and_then(|chassis_state| chassis_state.find_sensor(&sensor_id))
-        .map(|sensor| sensor.to_json().into_ok_response())
+        .and_then(|chassis_state| {
+            chassis_state
+                .find_sensor(&sensor_id)
+                .map(|sensor| chassis_state.sensor_json(sensor))
+        })
+        .map(|sensor| sensor.into_ok_response())
+        .unwrap_or_else(http::not_found)
+}
+
+/// Pins a sensor's `Reading`. Real BMCs treat it as read-only; the mock
+/// accepts it so that tests can drive threshold crossings.
+async fn patch_chassis_sensor(
+    State(state): State<BmcState>,
+    Path((chassis_id, sensor_id)): Path<(String, String)>,
+    Json(request): Json<serde_json::Value>,
+) -> Response {
+    let Some(reading) = request.get("Reading").and_then(serde_json::Value::as_f64) else {
+        return http::bad_request("Reading is expected to be a number");
+    };
+    let Some(chassis_state) = state.chassis_state.find(&chassis_id) else {
+        return http::not_found();
+    };
+    let Some(events) = chassis_state.set_sensor_reading(&sensor_id, reading) else {
+        return http::not_found();
+    };
+    events
+        .into_iter()
+        .for_each(|event| state.event_service.publish(event));
+    chassis_state
+        .find_sensor(&sensor_id)
+        .map(|sensor| chassis_state.sensor_json(sensor).into_ok_response())
         .unwrap_or_else(http::not_found)
 }
 
@@ -491,6 +612,42 @@ async fn get_chassis_assembly(
         .unwrap_or_else(http::not_found)
 }
 
+async fn get_chassis_power(
+    State(state): State<BmcState>,
+    Path(chassis_id): Path<String>,
+) -> Response {
+    state
+        .chassis_state
+        .find(&chassis_id)
+        .filter(|chassis_state| chassis_state.config.power_control)
+        .map(|chassis_state| chassis_state.power_json().into_ok_response())
+        .unwrap_or_else(http::not_found)
+}
+
+/// Sets `PowerControl[0].PowerLimit.LimitInWatts`. Other properties of the
+/// request are ignored.
+async fn patch_chassis_power(
+    State(state): State<BmcState>,
+    Path(chassis_id): Path<String>,
+    Json(request): Json<serde_json::Value>,
+) -> Response {
+    let Some(limit_watts) = redfish::power::requested_limit_watts(&request) else {
+        return http::bad_request("PowerControl[0].PowerLimit.LimitInWatts is expected");
+    };
+    let Some(chassis_state) = state
+        .chassis_state
+        .find(&chassis_id)
+        .filter(|chassis_state| chassis_state.config.power_control)
+    else {
+        return http::not_found();
+    };
+    *chassis_state
+        .power_limit_watts
+        .lock()
+        .expect("mutex poisoned") = limit_watts;
+    chassis_state.power_json().into_ok_response()
+}
+
 async fn get_chassis_power_subsystem(
     State(state): State<BmcState>,
     Path(chassis_id): Path<String>,
@@ -616,6 +773,36 @@ async fn get_chassis_leak_detector(
         .unwrap_or_else(http::not_found)
 }
 
+/// Sets a leak detector's `DetectorState`, which the mock accepts so that
+/// tests can trip and clear leaks.
+async fn patch_chassis_leak_detector(
+    State(state): State<BmcState>,
+    Path((chassis_id, leak_detector_id)): Path<(String, String)>,
+    Json(request): Json<serde_json::Value>,
+) -> Response {
+    let Some(detector_state) = request
+        .get("DetectorState")
+        .and_then(serde_json::Value::as_str)
+        .and_then(redfish::resource::Status::from_health)
+    else {
+        return http::bad_request("DetectorState is expected to be OK, Warning or Critical");
+    };
+    let Some(chassis_state) = state.chassis_state.find(&chassis_id) else {
+        return http::not_found();
+    };
+    let Some(event) = chassis_state.set_leak_detector_state(&leak_detector_id, detector_state)
+    else {
+        return http::not_found();
+    };
+    if let Some(event) = event {
+        state.event_service.publish(event);
+    }
+    chassis_state
+        .find_leak_detector(&leak_detector_id)
+        .map(|detector| detector.to_json(&chassis_id).into_ok_response())
+        .unwrap_or_else(http::not_found)
+}
+
 struct ChassisBuilder {
     value: serde_json::Value,
 }
@@ -669,6 +856,10 @@ impl ChassisBuilder {
         self.apply_patch(v.nav_property("Sensors"))
     }
 
+    fn power(self, v: &redfish::Resource<'_>) -> Self {
+        self.apply_patch(v.nav_property("Power"))
+    }
+
     fn power_subsystem(self, v: &redfish::Resource<'_>) -> Self {
         self.apply_patch(v.nav_property("PowerSubsystem"))
     }
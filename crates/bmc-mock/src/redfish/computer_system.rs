@@ -336,6 +336,10 @@ impl SingleSystemState {
         }
     }
 
+    pub(crate) fn odata_id(&self) -> String {
+        resource(&self.config.id).odata_id.into_owned()
+    }
+
     pub(crate) fn on_boot_completed(&self) {
         let mut src = self.boot_source_override.lock().unwrap();
         if src.enabled.as_ref().is_some_and(|v| v == "Once") {
@@ -797,8 +801,18 @@ async fn post_reset_system(
     // introduce a deadlock if the API server holds a lock on the row for this machine
     // while issuing a redfish call, and MachineStateMachine is blocked waiting for the row lock
     // to be released.
+    let prior_power_state = callbacks.get_power_state();
     match callbacks.set_power_state(reset_type) {
-        Ok(_) => json!({}).into_ok_response(),
+        Ok(_) => {
+            state
+                .event_service
+                .publish(redfish::event_service::EventRecord::power_action(
+                    &resource(&system_id).odata_id,
+                    reset_type,
+                    prior_power_state,
+                ));
+            json!({}).into_ok_response()
+        }
         Err(SetSystemPowerError::BadRequest(_)) => StatusCode::BAD_REQUEST.into_response(),
         Err(SetSystemPowerError::CommandSendError(_)) => {
             StatusCode::INTERNAL_SERVER_ERROR.into_response()
@@ -0,0 +1,906 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Redfish EventService.
+//!
+//! Every event the mock raises -- a power action, a sensor crossing one of
+//! its thresholds, a leak detector changing state, or an arbitrary record
+//! submitted through `SubmitTestEvent` or machine-a-tron's control API -- is
+//! fanned out two ways:
+//!
+//! - to every client streaming `ServerSentEventUri`, unfiltered, and
+//! - to each push subscription whose `RegistryPrefixes`, `MessageIds` and
+//!   `OriginResources` filters match, as an HTTP `POST` of the `Event`
+//!   payload to its `Destination`.
+//!
+//! Delivery is best effort, like on a real BMC: a slow SSE client misses the
+//! events that overflow its buffer and a failed push is logged, not retried.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use axum::Router;
+use axum::extract::{Json, Path, State};
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use futures::stream;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::bmc_state::BmcState;
+use crate::json::{JsonExt, JsonPatch};
+use crate::redfish::resource::Status;
+use crate::redfish::sensor::ThresholdBreach;
+use crate::{MockPowerState, SystemPowerControl, http, redfish};
+
+/// How many events an SSE client may fall behind before it starts missing
+/// them.
+const SSE_BUFFER: usize = 256;
+
+/// How long a push delivery may take before it is abandoned.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+const RESOURCE_EVENT_REGISTRY: &str = "ResourceEvent.1.3";
+const ENVIRONMENTAL_REGISTRY: &str = "Environmental.1.0";
+
+pub(crate) fn resource() -> redfish::Resource<'static> {
+    redfish::Resource {
+        odata_id: Cow::Borrowed("/redfish/v1/EventService"),
+        odata_type: Cow::Borrowed("#EventService.v1_10_0.EventService"),
+        id: Cow::Borrowed("EventService"),
+        name: Cow::Borrowed("Event Service"),
+    }
+}
+
+fn subscriptions_collection() -> redfish::Collection<'static> {
+    redfish::Collection {
+        odata_id: Cow::Borrowed("/redfish/v1/EventService/Subscriptions"),
+        odata_type: Cow::Borrowed("#EventDestinationCollection.EventDestinationCollection"),
+        name: Cow::Borrowed("Event Subscriptions Collection"),
+    }
+}
+
+fn subscription_resource(id: impl Display) -> redfish::Resource<'static> {
+    redfish::Resource {
+        odata_id: Cow::Owned(format!("{}/{id}", subscriptions_collection().odata_id)),
+        odata_type: Cow::Borrowed("#EventDestination.v1_13_0.EventDestination"),
+        id: Cow::Owned(id.to_string()),
+        name: Cow::Borrowed("Event Subscription"),
+    }
+}
+
+/// The `Event` payload that carries event `event_id`. Its `Events` members
+/// are addressed within it by their index.
+fn event_resource(event_id: u64) -> redfish::Resource<'static> {
+    redfish::Resource {
+        odata_id: Cow::Owned(format!("{}/Events/{event_id}", resource().odata_id)),
+        odata_type: Cow::Borrowed("#Event.v1_7_0.Event"),
+        id: Cow::Owned(event_id.to_string()),
+        name: Cow::Borrowed("Event Array"),
+    }
+}
+
+fn sse_uri() -> String {
+    format!("{}/SSE", resource().odata_id)
+}
+
+fn submit_test_event_target() -> String {
+    format!(
+        "{}/Actions/EventService.SubmitTestEvent",
+        resource().odata_id
+    )
+}
+
+pub(crate) fn add_routes(r: Router<BmcState>) -> Router<BmcState> {
+    r.route(&resource().odata_id, get(get_event_service))
+        .route(
+            &subscriptions_collection().odata_id,
+            get(get_subscriptions).post(post_subscription),
+        )
+        .route(
+            &subscription_resource("{subscription_id}").odata_id,
+            get(get_subscription).delete(delete_subscription),
+        )
+        .route(&sse_uri(), get(get_sse))
+        .route(&submit_test_event_target(), post(post_submit_test_event))
+}
+
+/// One entry of an `Event` payload's `Events` array.
+///
+/// Deserializes from the `EventService.SubmitTestEvent` action parameters,
+/// so the same body can be posted to the mock BMC or to machine-a-tron's
+/// control API. `OriginOfCondition` is the odata id of the resource the
+/// event is about.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct EventRecord {
+    #[serde(default = "EventRecord::default_event_type")]
+    pub event_type: String,
+    pub message_id: String,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub message_args: Vec<String>,
+    #[serde(default = "EventRecord::default_severity", alias = "Severity")]
+    pub message_severity: String,
+    #[serde(default)]
+    pub origin_of_condition: Option<String>,
+    #[serde(default)]
+    pub oem: Option<serde_json::Value>,
+}
+
+impl EventRecord {
+    fn default_event_type() -> String {
+        "Alert".to_string()
+    }
+
+    fn default_severity() -> String {
+        Status::Ok.as_str().to_string()
+    }
+
+    fn new(
+        message_id: String,
+        severity: Status,
+        origin: &str,
+        message: String,
+        message_args: Vec<String>,
+    ) -> Self {
+        Self {
+            event_type: Self::default_event_type(),
+            message_id,
+            message: Some(message),
+            message_args,
+            message_severity: severity.as_str().to_string(),
+            origin_of_condition: Some(origin.to_string()),
+            oem: None,
+        }
+    }
+
+    /// The event a BMC raises when it accepts a `ComputerSystem.Reset`.
+    ///
+    /// Restarts report only the power-off half; the power-on that follows
+    /// is reported by [`EventRecord::powered_on`] once the host is up.
+    pub(crate) fn power_action(
+        origin: &str,
+        reset_type: SystemPowerControl,
+        prior: MockPowerState,
+    ) -> Self {
+        type C = SystemPowerControl;
+        let powering_on = match reset_type {
+            C::On | C::ForceOn | C::Resume => true,
+            C::PushPowerButton => matches!(prior, MockPowerState::Off),
+            C::GracefulShutdown
+            | C::ForceOff
+            | C::GracefulRestart
+            | C::ForceRestart
+            | C::PowerCycle
+            | C::Nmi
+            | C::Suspend
+            | C::Pause => false,
+        };
+        let (key, verb) = if powering_on {
+            ("ResourcePoweringOn", "powering on")
+        } else {
+            ("ResourcePoweringOff", "powering off")
+        };
+        Self::new(
+            format!("{RESOURCE_EVENT_REGISTRY}.{key}"),
+            Status::Ok,
+            origin,
+            format!("The resource '{origin}' is {verb}."),
+            vec![origin.to_string()],
+        )
+    }
+
+    pub(crate) fn powered_on(origin: &str) -> Self {
+        Self::new(
+            format!("{RESOURCE_EVENT_REGISTRY}.ResourcePoweredOn"),
+            Status::Ok,
+            origin,
+            format!("The resource '{origin}' has powered on."),
+            vec![origin.to_string()],
+        )
+    }
+
+    /// The events for a sensor reading moving from `old` to `new`.
+    ///
+    /// Each severity band is reported as entered and left, so a reading
+    /// that climbs from caution straight into critical clears the warning
+    /// threshold before it exceeds the error one.
+    pub(crate) fn threshold_transition(
+        origin: &str,
+        property: &str,
+        old: Option<ThresholdBreach>,
+        new: Option<ThresholdBreach>,
+    ) -> Vec<Self> {
+        let mut events = Vec::new();
+        if let Some(old) = old
+            && new.is_none_or(|new| new.severity != old.severity)
+        {
+            events.push(Self::threshold(origin, property, old, true));
+        }
+        if let Some(new) = new
+            && old.is_none_or(|old| old.severity != new.severity)
+        {
+            events.push(Self::threshold(origin, property, new, false));
+        }
+        events
+    }
+
+    fn threshold(origin: &str, property: &str, breach: ThresholdBreach, cleared: bool) -> Self {
+        let level = match breach.severity {
+            Status::Critical => "Error",
+            Status::Warning | Status::Ok => "Warning",
+        };
+        let (suffix, verb, severity) = if cleared {
+            ("Cleared", "cleared", Status::Ok)
+        } else {
+            ("Exceeded", "exceeded", breach.severity)
+        };
+        let threshold = breach.threshold.to_string();
+        Self::new(
+            format!("{RESOURCE_EVENT_REGISTRY}.Resource{level}Threshold{suffix}"),
+            severity,
+            origin,
+            format!(
+                "The resource property {property} has {verb} the {} threshold of value {threshold}.",
+                level.to_lowercase()
+            ),
+            vec![property.to_string(), threshold],
+        )
+    }
+
+    pub(crate) fn leak_detector(origin: &str, detector: &str, state: Status) -> Self {
+        let (key, message) = match state {
+            Status::Ok => ("LeakDetectedNormal", "no longer detects a leak"),
+            Status::Warning => ("LeakDetectedWarning", "detected a leak"),
+            Status::Critical => ("LeakDetectedCritical", "detected a critical leak"),
+        };
+        Self::new(
+            format!("{ENVIRONMENTAL_REGISTRY}.{key}"),
+            state,
+            origin,
+            format!("Leak detector {detector} {message}."),
+            vec![detector.to_string()],
+        )
+    }
+
+    fn to_json(&self, event_id: u64, timestamp: &str) -> serde_json::Value {
+        let mut value = json!({
+            "@odata.id": format!("{}#/Events/0", event_resource(event_id).odata_id),
+            "EventType": self.event_type,
+            "EventId": event_id.to_string(),
+            "EventTimestamp": timestamp,
+            "MemberId": "0",
+            "MessageId": self.message_id,
+            "MessageArgs": self.message_args,
+            "MessageSeverity": self.message_severity,
+        });
+        if let Some(message) = &self.message {
+            value["Message"] = json!(message);
+        }
+        if let Some(origin) = &self.origin_of_condition {
+            value["OriginOfCondition"] = json!({ "@odata.id": origin });
+        }
+        if let Some(oem) = &self.oem {
+            value["Oem"] = oem.clone();
+        }
+        value
+    }
+
+    /// `Registry` and `MessageKey` of a `Registry.Major.Minor.MessageKey`
+    /// message id.
+    fn message_id_parts(message_id: &str) -> (&str, &str) {
+        let registry = message_id.split('.').next().unwrap_or(message_id);
+        let key = message_id.rsplit('.').next().unwrap_or(message_id);
+        (registry, key)
+    }
+}
+
+/// A published event: its id and the `Event` payload carrying it.
+type Published = (u64, Arc<serde_json::Value>);
+
+/// Event fan-out for one mock BMC.
+///
+/// Shared with whoever constructs the BMC router, so events can be injected
+/// from outside the Redfish API.
+pub struct EventService {
+    sender: broadcast::Sender<Published>,
+    next_event_id: AtomicU64,
+    next_subscription_id: AtomicU64,
+    subscriptions: Mutex<BTreeMap<u64, Subscription>>,
+    /// Built on the first push delivery; most mocks never get a subscriber.
+    client: OnceLock<Option<reqwest::Client>>,
+}
+
+impl std::fmt::Debug for EventService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventService")
+            .field("next_event_id", &self.next_event_id)
+            .field("subscriptions", &self.subscriptions)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for EventService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventService {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(SSE_BUFFER);
+        Self {
+            sender,
+            next_event_id: AtomicU64::new(0),
+            next_subscription_id: AtomicU64::new(0),
+            subscriptions: Mutex::new(BTreeMap::new()),
+            client: OnceLock::new(),
+        }
+    }
+
+    /// Sends `record` to every SSE client and matching push subscription.
+    pub fn publish(&self, record: EventRecord) {
+        let event_id = self.next_event_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let timestamp = chrono::Utc::now().to_rfc3339();
+        let entry = record.to_json(event_id, &timestamp);
+
+        tracing::debug!(event_id, message_id = %record.message_id, "publishing event");
+        // An error only means no SSE client is connected right now.
+        let _ = self
+            .sender
+            .send((event_id, Arc::new(event_payload(event_id, &entry, None))));
+
+        let subscriptions = self
+            .subscriptions
+            .lock()
+            .expect("mutex poisoned")
+            .values()
+            .filter(|subscription| subscription.matches(&record))
+            .cloned()
+            .collect::<Vec<_>>();
+        for subscription in subscriptions {
+            let payload = event_payload(event_id, &entry, subscription.context.as_deref());
+            self.deliver(subscription.destination, payload);
+        }
+    }
+
+    /// Receives every event published from now on, as an SSE client would.
+    pub fn subscribe_sse(&self) -> broadcast::Receiver<Published> {
+        self.sender.subscribe()
+    }
+
+    fn subscribe(&self, request: SubscriptionRequest) -> Subscription {
+        let id = self.next_subscription_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let subscription = Subscription {
+            id,
+            destination: request.destination,
+            context: request.context,
+            registry_prefixes: request.registry_prefixes,
+            message_ids: request.message_ids,
+            origin_resources: request
+                .origin_resources
+                .into_iter()
+                .map(|origin| origin.odata_id)
+                .collect(),
+        };
+        self.subscriptions
+            .lock()
+            .expect("mutex poisoned")
+            .insert(id, subscription.clone());
+        subscription
+    }
+
+    fn subscriptions(&self) -> Vec<Subscription> {
+        self.subscriptions
+            .lock()
+            .expect("mutex poisoned")
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    fn find_subscription(&self, id: u64) -> Option<Subscription> {
+        self.subscriptions
+            .lock()
+            .expect("mutex poisoned")
+            .get(&id)
+            .cloned()
+    }
+
+    fn unsubscribe(&self, id: u64) -> bool {
+        self.subscriptions
+            .lock()
+            .expect("mutex poisoned")
+            .remove(&id)
+            .is_some()
+    }
+
+    fn deliver(&self, destination: String, payload: serde_json::Value) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            tracing::warn!(destination, "no async runtime to deliver event on");
+            return;
+        };
+        let Some(client) = self
+            .client
+            .get_or_init(|| {
+                reqwest::Client::builder()
+                    .danger_accept_invalid_certs(true)
+                    .timeout(DELIVERY_TIMEOUT)
+                    .build()
+                    .inspect_err(|error| tracing::warn!(%error, "failed to build event client"))
+                    .ok()
+            })
+            .clone()
+        else {
+            return;
+        };
+        runtime.spawn(async move {
+            let result = client
+                .post(&destination)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(payload.to_string())
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status);
+            if let Err(error) = result {
+                tracing::warn!(destination, %error, "failed to deliver event");
+            }
+        });
+    }
+}
+
+fn event_payload(
+    event_id: u64,
+    entry: &serde_json::Value,
+    context: Option<&str>,
+) -> serde_json::Value {
+    let mut payload = json!({
+        "Events": [entry],
+    })
+    .patch(event_resource(event_id));
+    if let Some(context) = context {
+        payload["Context"] = json!(context);
+    }
+    payload
+}
+
+#[derive(Clone, Debug)]
+struct Subscription {
+    id: u64,
+    destination: String,
+    context: Option<String>,
+    registry_prefixes: Vec<String>,
+    message_ids: Vec<String>,
+    origin_resources: Vec<String>,
+}
+
+impl Subscription {
+    /// Empty filters match everything. `MessageIds` entries may omit the
+    /// registry version, as in `ResourceEvent.ResourcePoweredOn`.
+    fn matches(&self, record: &EventRecord) -> bool {
+        let (registry, key) = EventRecord::message_id_parts(&record.message_id);
+        let registry_matches = self.registry_prefixes.is_empty()
+            || self
+                .registry_prefixes
+                .iter()
+                .any(|prefix| prefix == registry);
+        let message_matches = self.message_ids.is_empty()
+            || self.message_ids.iter().any(|message_id| {
+                message_id == &record.message_id
+                    || EventRecord::message_id_parts(message_id) == (registry, key)
+            });
+        let origin_matches = self.origin_resources.is_empty()
+            || record
+                .origin_of_condition
+                .as_ref()
+                .is_some_and(|origin| self.origin_resources.contains(origin));
+        registry_matches && message_matches && origin_matches
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let origin_resources = self
+            .origin_resources
+            .iter()
+            .map(|odata_id| json!({ "@odata.id": odata_id }))
+            .collect::<Vec<_>>();
+        json!({
+            "Destination": self.destination,
+            "Context": self.context,
+            "Protocol": "Redfish",
+            "SubscriptionType": "RedfishEvent",
+            "EventFormatType": "Event",
+            "RegistryPrefixes": self.registry_prefixes,
+            "MessageIds": self.message_ids,
+            "OriginResources": origin_resources,
+        })
+        .patch(subscription_resource(self.id))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SubscriptionRequest {
+    destination: String,
+    #[serde(default)]
+    context: Option<String>,
+    #[serde(default)]
+    protocol: Option<String>,
+    #[serde(default)]
+    subscription_type: Option<String>,
+    #[serde(default)]
+    registry_prefixes: Vec<String>,
+    #[serde(default)]
+    message_ids: Vec<String>,
+    #[serde(default)]
+    origin_resources: Vec<EntityRef>,
+}
+
+impl SubscriptionRequest {
+    fn validate(&self) -> Result<(), &'static str> {
+        if self.protocol.as_deref().is_some_and(|v| v != "Redfish") {
+            return Err("only the Redfish protocol is supported");
+        }
+        if self
+            .subscription_type
+            .as_deref()
+            .is_some_and(|v| v != "RedfishEvent")
+        {
+            return Err("only RedfishEvent subscriptions can be created");
+        }
+        match url::Url::parse(&self.destination) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(()),
+            _ => Err("Destination must be an http or https URI"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EntityRef {
+    #[serde(rename = "@odata.id")]
+    odata_id: String,
+}
+
+async fn get_event_service() -> Response {
+    resource()
+        .json_patch()
+        .patch(json!({
+            "Status": redfish::resource::Status::Ok.into_json(),
+            "ServiceEnabled": true,
+            "DeliveryRetryAttempts": 0,
+            "EventFormatTypes": ["Event"],
+            "ServerSentEventUri": sse_uri(),
+            "SSEFilterPropertiesSupported": {
+                "EventFormatType": false,
+                "MessageId": false,
+                "MetricReportDefinition": false,
+                "OriginResource": false,
+                "RegistryPrefix": false,
+                "ResourceType": false,
+            },
+            "Actions": {
+                "#EventService.SubmitTestEvent": {
+                    "target": submit_test_event_target(),
+                }
+            },
+        }))
+        .patch(subscriptions_collection().nav_property("Subscriptions"))
+        .into_ok_response()
+}
+
+async fn get_subscriptions(State(state): State<BmcState>) -> Response {
+    let members = state
+        .event_service
+        .subscriptions()
+        .iter()
+        .map(|subscription| subscription_resource(subscription.id).entity_ref())
+        .collect::<Vec<_>>();
+    subscriptions_collection()
+        .with_members(&members)
+        .into_ok_response()
+}
+
+async fn post_subscription(
+    State(state): State<BmcState>,
+    Json(request): Json<SubscriptionRequest>,
+) -> Response {
+    if let Err(message) = request.validate() {
+        return http::bad_request(message);
+    }
+    let subscription = state.event_service.subscribe(request);
+    let location = subscription_resource(subscription.id).odata_id.into_owned();
+    let mut response = subscription.to_json().into_response(StatusCode::CREATED);
+    if let Ok(value) = HeaderValue::from_str(&location) {
+        response
+            .headers_mut()
+            .insert(axum::http::header::LOCATION, value);
+    }
+    response
+}
+
+async fn get_subscription(
+    State(state): State<BmcState>,
+    Path(subscription_id): Path<String>,
+) -> Response {
+    subscription_id
+        .parse()
+        .ok()
+        .and_then(|id| state.event_service.find_subscription(id))
+        .map(|subscription| subscription.to_json().into_ok_response())
+        .unwrap_or_else(http::not_found)
+}
+
+async fn delete_subscription(
+    State(state): State<BmcState>,
+    Path(subscription_id): Path<String>,
+) -> Response {
+    let deleted = subscription_id
+        .parse()
+        .is_ok_and(|id| state.event_service.unsubscribe(id));
+    if deleted {
+        http::ok_no_content()
+    } else {
+        http::not_found()
+    }
+}
+
+async fn get_sse(State(state): State<BmcState>) -> Response {
+    let receiver = state.event_service.subscribe_sse();
+    let events = stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok((event_id, payload)) => {
+                    let event = Event::default()
+                        .id(event_id.to_string())
+                        .data(payload.to_string());
+                    return Some((Ok::<_, Infallible>(event), receiver));
+                }
+                // Like a BMC with a full per-client queue: the client just
+                // misses what was overwritten.
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+async fn post_submit_test_event(
+    State(state): State<BmcState>,
+    Json(record): Json<EventRecord>,
+) -> Response {
+    state.event_service.publish(record);
+    http::ok_no_content()
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::{Check, check_values};
+
+    use super::*;
+
+    fn breach(severity: Status, threshold: f64) -> Option<ThresholdBreach> {
+        Some(ThresholdBreach {
+            severity,
+            threshold,
+        })
+    }
+
+    fn message_ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn threshold_transitions_enter_and_leave_severity_bands() {
+        check_values(
+            [
+                Check {
+                    scenario: "unchanged band raises nothing",
+                    input: (breach(Status::Warning, 38.0), breach(Status::Warning, 38.0)),
+                    expect: message_ids(&[]),
+                },
+                Check {
+                    scenario: "normal to caution exceeds the warning threshold",
+                    input: (None, breach(Status::Warning, 38.0)),
+                    expect: message_ids(&["ResourceEvent.1.3.ResourceWarningThresholdExceeded"]),
+                },
+                Check {
+                    scenario: "caution to critical clears warning, then exceeds error",
+                    input: (
+                        breach(Status::Warning, 38.0),
+                        breach(Status::Critical, 40.0),
+                    ),
+                    expect: message_ids(&[
+                        "ResourceEvent.1.3.ResourceWarningThresholdCleared",
+                        "ResourceEvent.1.3.ResourceErrorThresholdExceeded",
+                    ]),
+                },
+                Check {
+                    scenario: "critical back to normal clears the error threshold",
+                    input: (breach(Status::Critical, 40.0), None),
+                    expect: message_ids(&["ResourceEvent.1.3.ResourceErrorThresholdCleared"]),
+                },
+            ],
+            |(old, new)| {
+                EventRecord::threshold_transition(
+                    "/redfish/v1/Chassis/1/Sensors/Temp_1",
+                    "Temp_1",
+                    old,
+                    new,
+                )
+                .into_iter()
+                .map(|event| event.message_id)
+                .collect()
+            },
+        );
+    }
+
+    #[test]
+    fn power_actions_report_powering_on_or_off() {
+        type C = SystemPowerControl;
+        check_values(
+            [
+                Check {
+                    scenario: "power on",
+                    input: (C::On, MockPowerState::Off),
+                    expect: true,
+                },
+                Check {
+                    scenario: "restart reports the power-off half",
+                    input: (C::ForceRestart, MockPowerState::On),
+                    expect: false,
+                },
+                Check {
+                    scenario: "power button on a powered-off host turns it on",
+                    input: (C::PushPowerButton, MockPowerState::Off),
+                    expect: true,
+                },
+                Check {
+                    scenario: "power button on a running host turns it off",
+                    input: (C::PushPowerButton, MockPowerState::On),
+                    expect: false,
+                },
+            ],
+            |(reset_type, prior)| {
+                EventRecord::power_action("/redfish/v1/Systems/1", reset_type, prior).message_id
+                    == "ResourceEvent.1.3.ResourcePoweringOn"
+            },
+        );
+    }
+
+    #[test]
+    fn subscription_filters_match_registry_message_and_origin() {
+        let record = EventRecord::powered_on("/redfish/v1/Systems/1");
+        let subscription =
+            |registry_prefixes: &[&str], message_ids: &[&str], origins: &[&str]| Subscription {
+                id: 1,
+                destination: "http://collector.local/events".to_string(),
+                context: None,
+                registry_prefixes: registry_prefixes.iter().map(ToString::to_string).collect(),
+                message_ids: message_ids.iter().map(ToString::to_string).collect(),
+                origin_resources: origins.iter().map(ToString::to_string).collect(),
+            };
+        check_values(
+            [
+                Check {
+                    scenario: "no filters",
+                    input: subscription(&[], &[], &[]),
+                    expect: true,
+                },
+                Check {
+                    scenario: "matching registry prefix",
+                    input: subscription(&["ResourceEvent"], &[], &[]),
+                    expect: true,
+                },
+                Check {
+                    scenario: "other registry prefix",
+                    input: subscription(&["Environmental"], &[], &[]),
+                    expect: false,
+                },
+                Check {
+                    scenario: "unversioned message id",
+                    input: subscription(&[], &["ResourceEvent.ResourcePoweredOn"], &[]),
+                    expect: true,
+                },
+                Check {
+                    scenario: "other message id",
+                    input: subscription(&[], &["ResourceEvent.1.3.ResourcePoweredOff"], &[]),
+                    expect: false,
+                },
+                Check {
+                    scenario: "other origin",
+                    input: subscription(&[], &[], &["/redfish/v1/Systems/2"]),
+                    expect: false,
+                },
+            ],
+            |subscription| subscription.matches(&record),
+        );
+    }
+
+    #[tokio::test]
+    async fn published_events_reach_sse_clients() {
+        let service = EventService::new();
+        let mut receiver = service.subscribe_sse();
+        service.publish(EventRecord::leak_detector(
+            "/redfish/v1/Chassis/1/ThermalSubsystem/LeakDetection/LeakDetectors/LeakDetector_1",
+            "LeakDetector_1",
+            Status::Critical,
+        ));
+
+        let (event_id, payload) = receiver.recv().await.unwrap();
+        assert_eq!(event_id, 1);
+        assert_eq!(payload["Id"], "1");
+        let entry = &payload["Events"][0];
+        assert_eq!(entry["MessageId"], "Environmental.1.0.LeakDetectedCritical");
+        assert_eq!(entry["MessageSeverity"], "Critical");
+        assert_eq!(
+            entry["OriginOfCondition"]["@odata.id"],
+            "/redfish/v1/Chassis/1/ThermalSubsystem/LeakDetection/LeakDetectors/LeakDetector_1"
+        );
+    }
+
+    #[tokio::test]
+    async fn published_events_parse_as_redfish_event_stream_payloads() {
+        use nv_redfish::core::NavProperty;
+        use nv_redfish::event_service::EventStreamPayload;
+
+        let service = EventService::new();
+        let mut receiver = service.subscribe_sse();
+        service.publish(EventRecord::powered_on("/redfish/v1/Systems/1"));
+
+        let (_, payload) = receiver.recv().await.unwrap();
+        let EventStreamPayload::Event(event) =
+            serde_json::from_value::<EventStreamPayload>((*payload).clone()).unwrap()
+        else {
+            panic!("expected an Event payload, got {payload}");
+        };
+        assert_eq!(event.events.len(), 1);
+        assert!(!matches!(event.events[0], NavProperty::Reference(_)));
+        assert_eq!(
+            event.events[0].odata_id().to_string(),
+            "/redfish/v1/EventService/Events/1#/Events/0"
+        );
+    }
+
+    #[test]
+    fn submit_test_event_parameters_deserialize() {
+        let record: EventRecord = serde_json::from_value(json!({
+            "MessageId": "Example.1.0.Event",
+            "Severity": "Warning",
+            "OriginOfCondition": "/redfish/v1/Systems/1",
+        }))
+        .unwrap();
+        assert_eq!(record.event_type, "Alert");
+        assert_eq!(record.message_severity, "Warning");
+        assert_eq!(
+            record.origin_of_condition.as_deref(),
+            Some("/redfish/v1/Systems/1")
+        );
+    }
+}
@@ -23,6 +23,7 @@ pub(crate) mod chassis;
 mod collection;
 pub(crate) mod computer_system;
 pub(crate) mod ethernet_interface;
+pub(crate) mod event_service;
 pub(crate) mod host_interface;
 pub(crate) mod leak_detector;
 pub(crate) mod log_service;
@@ -33,6 +34,7 @@ pub(crate) mod network_adapter;
 pub(crate) mod network_device_function;
 pub(crate) mod oem;
 pub(crate) mod pcie_device;
+mod power;
 mod power_subsystem;
 pub(crate) mod power_supply;
 pub(crate) mod processor;
@@ -0,0 +1,94 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The deprecated `Power` resource of a chassis. Only its
+//! `PowerControl[0].PowerLimit` is modelled, which is what BMCs still use
+//! for host power capping.
+
+use std::borrow::Cow;
+
+use serde_json::json;
+
+use crate::json::{JsonExt, JsonPatch};
+use crate::redfish;
+use crate::redfish::Builder;
+
+pub(super) fn resource(chassis_id: &str) -> redfish::Resource<'static> {
+    let odata_id = format!("{}/Power", redfish::chassis::resource(chassis_id).odata_id);
+    redfish::Resource {
+        odata_id: Cow::Owned(odata_id),
+        odata_type: Cow::Borrowed("#Power.v1_7_1.Power"),
+        id: Cow::Borrowed("Power"),
+        name: Cow::Borrowed("Power"),
+    }
+}
+
+pub(super) fn builder(resource: &redfish::Resource) -> PowerBuilder {
+    PowerBuilder {
+        odata_id: resource.odata_id.to_string(),
+        value: resource.json_patch(),
+    }
+}
+
+/// Reads `PowerControl[0].PowerLimit.LimitInWatts` from a `PATCH` body. A
+/// `null` limit removes the limit.
+pub(super) fn requested_limit_watts(request: &serde_json::Value) -> Option<Option<f64>> {
+    let limit = request
+        .get("PowerControl")?
+        .get(0)?
+        .get("PowerLimit")?
+        .get("LimitInWatts")?;
+    match limit {
+        serde_json::Value::Null => Some(None),
+        limit => limit.as_f64().map(Some),
+    }
+}
+
+pub(super) struct PowerBuilder {
+    odata_id: String,
+    value: serde_json::Value,
+}
+
+impl Builder for PowerBuilder {
+    fn apply_patch(self, patch: serde_json::Value) -> Self {
+        Self {
+            value: self.value.patch(patch),
+            odata_id: self.odata_id,
+        }
+    }
+}
+
+impl PowerBuilder {
+    pub(super) fn power_limit(self, limit_watts: Option<f64>) -> Self {
+        let odata_id = format!("{}#/PowerControl/0", self.odata_id);
+        self.apply_patch(json!({
+            "PowerControl": [{
+                "@odata.id": odata_id,
+                "MemberId": "0",
+                "Name": "System Power Control",
+                "PowerLimit": {
+                    "LimitInWatts": limit_watts,
+                    "LimitException": "NoAction",
+                },
+            }],
+        }))
+    }
+
+    pub(super) fn build(self) -> serde_json::Value {
+        self.value
+    }
+}
@@ -61,7 +61,7 @@ impl JsonPatch for Resource<'_> {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Status {
     Ok,
     Warning,
@@ -77,6 +77,15 @@ impl Status {
         }
     }
 
+    pub(super) fn from_health(value: &str) -> Option<Self> {
+        match value {
+            "OK" => Some(Self::Ok),
+            "Warning" => Some(Self::Warning),
+            "Critical" => Some(Self::Critical),
+            _ => None,
+        }
+    }
+
     pub(super) fn into_json(self) -> serde_json::Value {
         json!({
             "State": "Enabled",
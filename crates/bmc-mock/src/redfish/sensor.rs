@@ -99,6 +99,31 @@ impl Sensor {
 
         refreshed_builder.build().value
     }
+
+    /// The sensor reporting a fixed `reading`, with its health derived from
+    /// the thresholds as usual.
+    pub(crate) fn to_json_with_reading(&self, reading: f64) -> serde_json::Value {
+        self.builder_with_reading(reading).build().value
+    }
+
+    pub(crate) fn breached_threshold(&self, reading: f64) -> Option<ThresholdBreach> {
+        self.builder_with_reading(reading).breached_threshold()
+    }
+
+    fn builder_with_reading(&self, reading: f64) -> SensorBuilder {
+        SensorBuilder {
+            id: self.id.clone(),
+            value: self.value.clone(),
+        }
+        .reading_f64(reading)
+    }
+}
+
+/// A reading's position beyond one of its sensor's thresholds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ThresholdBreach {
+    pub(crate) severity: redfish::resource::Status,
+    pub(crate) threshold: f64,
 }
 
 pub(crate) struct SensorBuilder {
@@ -141,47 +166,36 @@ impl SensorBuilder {
     }
 
     fn health_status(&self) -> redfish::resource::Status {
-        let Some(reading) = self.reading() else {
-            return redfish::resource::Status::Ok;
-        };
-
-        if let Some(upper_fatal) = self.threshold("UpperFatal")
-            && reading >= upper_fatal
-        {
-            return redfish::resource::Status::Critical;
-        }
-
-        if let Some(lower_fatal) = self.threshold("LowerFatal")
-            && reading <= lower_fatal
-        {
-            return redfish::resource::Status::Critical;
-        }
-
-        if let Some(upper_critical) = self.threshold("UpperCritical")
-            && reading >= upper_critical
-        {
-            return redfish::resource::Status::Critical;
-        }
-
-        if let Some(lower_critical) = self.threshold("LowerCritical")
-            && reading <= lower_critical
-        {
-            return redfish::resource::Status::Critical;
-        }
-
-        if let Some(upper_caution) = self.threshold("UpperCaution")
-            && reading >= upper_caution
-        {
-            return redfish::resource::Status::Warning;
-        }
-
-        if let Some(lower_caution) = self.threshold("LowerCaution")
-            && reading <= lower_caution
-        {
-            return redfish::resource::Status::Warning;
-        }
-
-        redfish::resource::Status::Ok
+        self.breached_threshold()
+            .map_or(redfish::resource::Status::Ok, |breach| breach.severity)
+    }
+
+    /// The most severe threshold the current reading is at or beyond.
+    fn breached_threshold(&self) -> Option<ThresholdBreach> {
+        use redfish::resource::Status;
+        const THRESHOLDS: [(&str, bool, Status); 6] = [
+            ("UpperFatal", true, Status::Critical),
+            ("LowerFatal", false, Status::Critical),
+            ("UpperCritical", true, Status::Critical),
+            ("LowerCritical", false, Status::Critical),
+            ("UpperCaution", true, Status::Warning),
+            ("LowerCaution", false, Status::Warning),
+        ];
+        let reading = self.reading()?;
+        THRESHOLDS
+            .into_iter()
+            .find_map(|(threshold_name, upper, severity)| {
+                let threshold = self.threshold(threshold_name)?;
+                let breached = if upper {
+                    reading >= threshold
+                } else {
+                    reading <= threshold
+                };
+                breached.then_some(ThresholdBreach {
+                    severity,
+                    threshold,
+                })
+            })
     }
 
     pub(crate) fn name(self, value: &str) -> Self {
@@ -510,7 +524,10 @@ fn random_tenths(rng: &mut impl Rng, range: std::ops::RangeInclusive<f64>) -> f6
 mod tests {
     use std::collections::HashMap;
 
-    use super::{Layout, Thresholds, builder, chassis_resource, generate_chassis_sensors};
+    use super::{
+        Layout, ThresholdBreach, Thresholds, builder, chassis_resource, generate_chassis_sensors,
+    };
+    use crate::redfish::resource::Status;
 
     #[test]
     fn generated_sensors_follow_layout_and_ranges() {
@@ -609,4 +626,30 @@ mod tests {
         assert_eq!(json["Status"]["Health"], "Critical");
         assert_eq!(json["Thresholds"]["UpperFatal"]["Reading"], 44.0);
     }
+
+    #[test]
+    fn pinned_reading_reports_the_breached_threshold() {
+        let sensor = builder(&chassis_resource("System.Embedded.1", "Temp_1"))
+            .reading_type("Temperature")
+            .reading_f64(30.0)
+            .thresholds(Thresholds {
+                upper_caution: Some(38.0),
+                upper_critical: Some(40.0),
+                ..Default::default()
+            })
+            .build();
+
+        assert_eq!(sensor.breached_threshold(30.0), None);
+        assert_eq!(
+            sensor.breached_threshold(41.0),
+            Some(ThresholdBreach {
+                severity: Status::Critical,
+                threshold: 40.0,
+            })
+        );
+
+        let json = sensor.to_json_with_reading(39.0);
+        assert_eq!(json["Reading"], 39.0);
+        assert_eq!(json["Status"]["Health"], "Warning");
+    }
 }
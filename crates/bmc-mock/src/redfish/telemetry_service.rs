@@ -26,6 +26,7 @@ use serde_json::json;
 
 use crate::bmc_state::BmcState;
 use crate::json::{JsonExt, JsonPatch};
+use crate::redfish::chassis::SingleChassisState;
 use crate::{http, redfish};
 
 /// Id of the aggregated report the mock publishes.
@@ -214,15 +215,16 @@ fn metric_definition_units(state: &BmcState) -> BTreeMap<String, String> {
         .collect()
 }
 
-fn sensors(state: &BmcState) -> impl Iterator<Item = (&str, &redfish::sensor::Sensor)> {
+fn sensors(
+    state: &BmcState,
+) -> impl Iterator<Item = (&SingleChassisState, &redfish::sensor::Sensor)> {
     state.chassis_state.iter().flat_map(|chassis| {
-        let id = chassis.config.id.as_ref();
         chassis
             .config
             .sensors
             .iter()
             .flatten()
-            .map(move |sensor| (id, sensor))
+            .map(move |sensor| (chassis, sensor))
     })
 }
 
@@ -230,9 +232,9 @@ fn sensor_metric_values<'a>(
     state: &'a BmcState,
     timestamp: &'a str,
 ) -> impl Iterator<Item = serde_json::Value> + 'a {
-    sensors(state).filter_map(move |(chassis_id, sensor)| {
-        let reading = sensor.to_json().get("Reading")?.as_f64()?;
-        let odata_id = redfish::sensor::chassis_resource(chassis_id, &sensor.id).odata_id;
+    sensors(state).filter_map(move |(chassis, sensor)| {
+        let reading = chassis.sensor_json(sensor).get("Reading")?.as_f64()?;
+        let odata_id = redfish::sensor::chassis_resource(&chassis.config.id, &sensor.id).odata_id;
         Some(json!({
             "MetricId": sensor.id,
             "MetricValue": reading.to_string(),
@@ -71,18 +71,24 @@ pub struct TestBmcHandle {
     pub state: BmcState,
 }
 
-async fn test_bmc((router, state): (axum::Router, BmcState)) -> TestBmcHandle {
+fn http_bmc(router: axum::Router) -> Arc<TestBmc> {
     let client = AxumRouterHttpClient::new(router);
     let endpoint = Url::parse("https://bmc-mock.local").expect("valid URL");
     let credentials = BmcCredentials::new("root".to_string(), "password".to_string());
-    let bmc = Arc::new(HttpBmc::new(
+    Arc::new(HttpBmc::new(
         client,
         endpoint,
         credentials,
         CacheSettings::with_capacity(32),
-    ));
+    ))
+}
+
+async fn test_bmc((router, state): (axum::Router, BmcState)) -> TestBmcHandle {
     TestBmcHandle {
-        service_root: nv_redfish::ServiceRoot::new(bmc).await.unwrap().into(),
+        service_root: nv_redfish::ServiceRoot::new(http_bmc(router))
+            .await
+            .unwrap()
+            .into(),
         state,
     }
 }
@@ -116,6 +122,29 @@ pub(super) fn host_info(hw_type: HardwareType) -> MachineInfo {
     ))
 }
 
+/// Builds the host router for `hw_type` with caller-provided callbacks, for
+/// tests that serve the mock over a real listener and need to observe power
+/// commands.
+pub fn host_router(
+    hw_type: HardwareType,
+    callbacks: Arc<dyn Callbacks>,
+) -> (axum::Router, BmcState) {
+    machine_router(
+        &host_info(hw_type),
+        callbacks,
+        "test-host-id".to_string(),
+        false,
+        MachineRouterOptions::default(),
+    )
+}
+
+/// Redfish client of a host mock for `hw_type`, for code under test that
+/// takes a `Bmc` rather than a `ServiceRoot`.
+pub fn host_bmc(hw_type: HardwareType) -> Arc<TestBmc> {
+    let (router, _state) = host_router(hw_type, Arc::new(NoopCallbacks));
+    http_bmc(router)
+}
+
 pub async fn wiwynn_gb200_bmc() -> TestBmcHandle {
     test_bmc(machine_router(
         &host_info(HardwareType::WiwynnGB200Nvl),
@@ -626,6 +655,7 @@ mod test {
             "test-host-id".to_string(),
             false,
             injection.clone(),
+            Arc::new(crate::EventService::new()),
         );
 
         assert!(Arc::ptr_eq(&injection, &state.injection));
@@ -30,7 +30,7 @@ use serde::{Deserialize, Deserializer};
 #[derive(Clone, Default)]
 pub(crate) struct AclConfig {
     // Keys are "users" (ie. service principals), values are a list of AclEntries for authenticating them.
-    config: BTreeMap<String, Vec<AclEntry>>,
+    config: BTreeMap<String, AclRules>,
 }
 
 impl<'de> Deserialize<'de> for AclConfig {
@@ -38,7 +38,7 @@ impl<'de> Deserialize<'de> for AclConfig {
     where
         D: Deserializer<'de>,
     {
-        let config = BTreeMap::<String, Vec<AclEntry>>::deserialize(deserializer)?;
+        let config = BTreeMap::<String, AclRules>::deserialize(deserializer)?;
         Ok(Self { config })
     }
 }
@@ -50,10 +50,37 @@ impl AclConfig {
     /// entry wins. If the principal is unknown or no entry matches, this
     /// returns `false`.
     pub(crate) fn allows(&self, principal: &str, method: &http::Method, path: &str) -> bool {
-        let Some(entries) = self.config.get(principal) else {
-            return false;
-        };
-        entries
+        self.config
+            .get(principal)
+            .is_some_and(|rules| rules.allows(method, path))
+    }
+}
+
+/// An ordered list of [`AclEntry`] values that is not tied to a principal,
+/// for settings that select requests by method and path (such as which
+/// resources the response cache may hold). Same syntax and first-match-wins
+/// evaluation as a principal's list in [`AclConfig`]; an empty list matches
+/// nothing.
+#[derive(Clone, Default)]
+pub(crate) struct AclRules {
+    entries: Vec<AclEntry>,
+}
+
+impl<'de> Deserialize<'de> for AclRules {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let entries = Vec::<AclEntry>::deserialize(deserializer)?;
+        Ok(Self { entries })
+    }
+}
+
+impl AclRules {
+    /// Returns whether the first entry matching `method` and `path` allows
+    /// it, or `false` when no entry matches.
+    pub(crate) fn allows(&self, method: &http::Method, path: &str) -> bool {
+        self.entries
             .iter()
             .find_map(|entry| entry.action_if_matches(method, path))
             .map(|action| action.is_allowed())
@@ -748,6 +775,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn acl_rules_match_without_a_principal() {
+        #[derive(Deserialize)]
+        struct MockConfig {
+            rules: AclRules,
+        }
+
+        let MockConfig { rules } = figment::Figment::new()
+            .merge(Toml::string(
+                r#"rules = ["!GET /redfish/v1/**/LogServices/**", "GET /redfish/v1/**"]"#,
+            ))
+            .extract()
+            .expect("Mock config didn't parse");
+
+        value_scenarios!(
+            run = |(method, path): (http::Method, &str)| rules.allows(&method, path);
+            "first matching entry decides" {
+                (http::Method::GET, "/redfish/v1/Chassis/1") => true,
+                (http::Method::GET, "/redfish/v1/Managers/BMC/LogServices/SEL/Entries") => false,
+            }
+
+            "unmatched requests are not selected" {
+                (http::Method::PATCH, "/redfish/v1/Chassis/1") => false,
+                (http::Method::GET, "/other/stuff") => false,
+            }
+        );
+        assert!(!AclRules::default().allows(&http::Method::GET, "/redfish/v1"));
+    }
+
     #[test]
     fn acl_config_defaults_to_deny() {
         let acls = parse_acl_config(
@@ -28,6 +28,7 @@ use axum::extract::State;
 use axum::middleware::{Next, from_fn_with_state};
 use axum::response::IntoResponse;
 use axum::routing::{any, get};
+use bytes::Bytes;
 use carbide_authn::SpiffeContext;
 use carbide_authn::middleware::{
     AuthContext, Authorization, CertDescriptionMiddleware, ConnectionAttributes, Principal,
@@ -35,6 +36,7 @@ use carbide_authn::middleware::{
 use carbide_instrument::{Event, LabelValue, MetricFamily, emit};
 use carbide_utils::HostPortPair;
 use forge_tls::client_config::ClientCert;
+use futures::StreamExt;
 use http::{HeaderMap, Method, Request, Response, StatusCode, Uri};
 use hyper_util::rt::{TokioExecutor, TokioIo};
 use hyper_util::server::conn::auto;
@@ -55,7 +57,11 @@ use tower_http::add_extension::AddExtensionLayer;
 use trace_propagation::{is_propagated_header, set_span_parent_from_headers};
 use tracing::Instrument;
 
+use crate::coalesce::{
+    BufferedResponse, FetchError, GetCoalescer, GetKey, LiveResponse, UnbufferedResponse,
+};
 use crate::config::{AuthConfig, TlsConfig};
+use crate::governor::{AdmissionError, Governor, GovernorPermit};
 use crate::metrics::{
     AuthContextMissing, MethodLabel, PrincipalAllowListDenied, RequestAclDenied,
     UpstreamRequestCompleted, UpstreamStatus,
@@ -63,6 +69,7 @@ use crate::metrics::{
 
 const TLS_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
 const MAX_BODY_SIZE: usize = 8 * 1024 * 1024; // 8MiB body size limit (matches nginx ingress controller defaults)
+const EVENT_STREAM: &str = "text/event-stream";
 
 #[derive(thiserror::Error, Debug)]
 pub(crate) enum BmcProxyError {
@@ -91,6 +98,8 @@ struct BmcProxyState {
     credential_cache: CredentialCache,
     client_cache: HttpClientCache,
     ip_cache: LookupToIpCache,
+    governor: Arc<Governor>,
+    get_coalescer: Arc<GetCoalescer>,
 }
 
 type CredentialCache = Arc<Mutex<HashMap<IpAddr, BmcCredentials>>>;
@@ -172,6 +181,8 @@ pub(crate) async fn start(
     let api_client = ForgeApiClient::new(&api_config);
 
     let state = BmcProxyState {
+        governor: Arc::new(Governor::new(&config.governor)),
+        get_coalescer: Arc::new(GetCoalescer::new(&config.governor.response_cache)),
         config,
         api_client,
         credential_cache: Default::default(),
@@ -624,6 +635,7 @@ async fn proxy_request_inner(
     if !state.allows(&request) {
         return Ok(error_response((StatusCode::FORBIDDEN, "Forbidden").into()));
     }
+    let principal = fair_share_principal(&request);
     let (parts, body) = request.into_parts();
     let forwarded_target = forwarded_header_value(&parts.headers)
         .map_err(|e| error_response((StatusCode::BAD_REQUEST, e.to_string()).into()))?
@@ -679,6 +691,25 @@ async fn proxy_request_inner(
 
     copy_request_headers(&parts.headers, &mut bmc_client_info.header_map);
 
+    let governor_config = &state.config.governor;
+    let coalesced_get = (parts.method == Method::GET
+        && governor_config.coalesce_gets
+        && !accepts_event_stream(&parts.headers))
+    .then(|| {
+        (
+            GetKey::new(
+                target_ip,
+                path_and_query.as_str(),
+                &bmc_client_info.header_map,
+            ),
+            governor_config
+                .response_cache
+                .paths
+                .allows(&Method::GET, path_and_query.path()),
+        )
+    })
+    .filter(|(key, _)| !state.get_coalescer.is_unbufferable(key));
+
     let body = axum::body::to_bytes(body, MAX_BODY_SIZE)
         .await
         .map_err(|e| error_response((StatusCode::BAD_REQUEST, e.to_string()).into()))?;
@@ -703,25 +734,194 @@ async fn proxy_request_inner(
         upstream_request = upstream_request.body(body);
     }
 
+    if let Some((key, cacheable)) = coalesced_get
+        && let Some(shared_request) = upstream_request.try_clone()
+    {
+        let fetch = fetch_buffered(
+            state.governor.clone(),
+            target_ip,
+            principal.clone(),
+            shared_request,
+            state.credential_cache.clone(),
+        );
+        match state.get_coalescer.get(key, cacheable, fetch).await {
+            Ok(response) => {
+                return Ok(build_response(
+                    response.status,
+                    &response.headers,
+                    Body::from(response.body.clone()),
+                ));
+            }
+            Err(FetchError::Failed(status, message)) => {
+                return Err(error_response((status, message).into()));
+            }
+            Err(FetchError::Unbufferable(live)) => {
+                // Only one caller can stream the response that was fetched;
+                // the rest send their own request below.
+                if let Some(live) = live.take() {
+                    let body = streamed_body(live.prefix, live.response, live.permit);
+                    return Ok(build_response(live.status, &live.headers, body));
+                }
+            }
+        }
+    }
+
+    let permit = state
+        .governor
+        .admit(target_ip, &principal)
+        .await
+        .map_err(|e| error_response(admission_rejected(e).into()))?;
+    let upstream_response = send_upstream(&parts.method, upstream_request)
+        .await
+        .map_err(|e| error_response((StatusCode::BAD_GATEWAY, e.to_string()).into()))?;
+
+    let status = upstream_response.status();
+    let headers = upstream_response.headers().clone();
+    let body = streamed_body(Bytes::new(), upstream_response, permit);
+
+    evict_on_auth_failure(status, target_ip, &state.credential_cache).await;
+    if !matches!(parts.method, Method::GET | Method::HEAD) && status.is_success() {
+        state.get_coalescer.invalidate(target_ip);
+    }
+
+    Ok(build_response(status, &headers, body))
+}
+
+/// Stream an upstream response body, starting with the part of it that was
+/// already read. The BMC is still busy until the body has been sent, so the
+/// slot is only freed once the stream is finished or dropped. An event stream
+/// is the exception: it stays open for as long as its client listens, and
+/// holding a slot that long would take it from every other caller of the BMC.
+fn streamed_body(prefix: Bytes, response: reqwest::Response, permit: GovernorPermit) -> Body {
+    let permit = (!is_event_stream(response.headers())).then_some(permit);
+    let prefix = (!prefix.is_empty()).then_some(Ok(prefix));
+    let chunks = futures::stream::iter(prefix).chain(response.bytes_stream());
+    Body::from_stream(chunks.map(move |chunk| {
+        let _holds_slot = &permit;
+        chunk
+    }))
+}
+
+/// Send a request to a BMC, recording how long it took and how it ended.
+async fn send_upstream(
+    method: &Method,
+    request: reqwest_middleware::RequestBuilder,
+) -> reqwest_middleware::Result<reqwest::Response> {
     let started = Instant::now();
-    let upstream_result = upstream_request.send().await;
+    let upstream_result = request.send().await;
     emit(UpstreamRequestCompleted {
-        method: MethodLabel::from(&parts.method),
+        method: MethodLabel::from(method),
         status: UpstreamStatus::from_result(&upstream_result),
         took: started.elapsed(),
     });
-    let upstream_response = upstream_result
-        .map_err(|e| error_response((StatusCode::BAD_GATEWAY, e.to_string()).into()))?;
+    upstream_result
+}
 
-    let status = upstream_response.status();
-    let headers = upstream_response.headers().clone();
-    let body = Body::from_stream(upstream_response.bytes_stream());
+/// The upstream leg of a coalesced GET: wait for a slot, send, and read the
+/// whole response so every caller sharing it can be answered. An event stream
+/// or a response over the body limit is [`FetchError::Unbufferable`], which
+/// carries the response so it can still be streamed to one caller.
+async fn fetch_buffered(
+    governor: Arc<Governor>,
+    ip: IpAddr,
+    principal: String,
+    request: reqwest_middleware::RequestBuilder,
+    credential_cache: CredentialCache,
+) -> Result<BufferedResponse, FetchError> {
+    let permit = governor
+        .admit(ip, &principal)
+        .await
+        .map_err(admission_rejected)?;
+    let mut response = send_upstream(&Method::GET, request)
+        .await
+        .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+
+    let status = response.status();
+    let headers = response.headers().clone();
+    evict_on_auth_failure(status, ip, &credential_cache).await;
+    if is_event_stream(&headers)
+        || response
+            .content_length()
+            .is_some_and(|length| length > MAX_BODY_SIZE as u64)
+    {
+        return Err(FetchError::Unbufferable(LiveResponse::new(
+            UnbufferedResponse {
+                status,
+                headers,
+                prefix: Bytes::new(),
+                response,
+                permit,
+            },
+        )));
+    }
 
-    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
-        evict_cached_credentials(target_ip, &state.credential_cache).await;
+    let mut body = bytes::BytesMut::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?
+    {
+        body.extend_from_slice(&chunk);
+        if body.len() > MAX_BODY_SIZE {
+            return Err(FetchError::Unbufferable(LiveResponse::new(
+                UnbufferedResponse {
+                    status,
+                    headers,
+                    prefix: body.freeze(),
+                    response,
+                    permit,
+                },
+            )));
+        }
     }
 
-    Ok(build_response(status, &headers, body))
+    Ok(BufferedResponse {
+        status,
+        headers,
+        body: body.freeze(),
+    })
+}
+
+fn admission_rejected(error: AdmissionError) -> FetchError {
+    FetchError::Failed(StatusCode::SERVICE_UNAVAILABLE, error.to_string())
+}
+
+/// Whether a request asks for a Redfish event stream (SSE).
+fn accepts_event_stream(headers: &HeaderMap) -> bool {
+    headers.get_all(http::header::ACCEPT).iter().any(|value| {
+        value
+            .to_str()
+            .is_ok_and(|value| value.contains(EVENT_STREAM))
+    })
+}
+
+/// Whether a response is an event stream (SSE).
+fn is_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with(EVENT_STREAM))
+}
+
+/// The principal a request queues under when a BMC is busy: its first
+/// authenticated principal, or anonymous.
+fn fair_share_principal(request: &Request<Body>) -> String {
+    request
+        .extensions()
+        .get::<AuthContext<()>>()
+        .and_then(|auth_context| auth_context.principals.first())
+        .unwrap_or(&Principal::Anonymous)
+        .as_identifier()
+}
+
+async fn evict_on_auth_failure(
+    status: reqwest::StatusCode,
+    ip: IpAddr,
+    credential_cache: &CredentialCache,
+) {
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        evict_cached_credentials(ip, credential_cache).await;
+    }
 }
 
 async fn ip_for_forwarded_target(
@@ -1183,6 +1383,7 @@ mod tests {
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
     use std::str::FromStr;
     use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     use axum::body::Body;
     use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, Request, StatusCode};
@@ -1206,12 +1407,15 @@ mod tests {
 
     use super::{
         BmcCredentials, BmcProxyState, ConnectionFailReason, CredentialCache, ForwardedTarget,
-        TcpAcceptFailed, TlsCertificateReloadFailed, TlsConnectionFailed,
-        authorize_principal_allow_list, bmc_proxy_request_span, build_authority, build_response,
-        copy_request_headers, create_client, evict_cached_credentials, forwarded_header_value,
-        get_http_client, ip_for_forwarded_target, is_hop_by_hop_header, method_supports_body,
-        parse_forwarded_host_value, request_principal_ids, span_status,
+        MAX_BODY_SIZE, TcpAcceptFailed, TlsCertificateReloadFailed, TlsConnectionFailed,
+        accepts_event_stream, authorize_principal_allow_list, bmc_proxy_request_span,
+        build_authority, build_http_client, build_response, copy_request_headers, create_client,
+        evict_cached_credentials, fetch_buffered, forwarded_header_value, get_http_client,
+        ip_for_forwarded_target, is_event_stream, is_hop_by_hop_header, method_supports_body,
+        parse_forwarded_host_value, request_principal_ids, span_status, streamed_body,
     };
+    use crate::coalesce::{FetchError, GetCoalescer, GetKey};
+    use crate::governor::Governor;
     use crate::metrics::MethodLabel;
 
     const TEST_CONFIG: &str = r#"
@@ -1309,6 +1513,8 @@ mod tests {
             credential_cache: Default::default(),
             client_cache: Default::default(),
             ip_cache: Arc::new(Mutex::new(ip_cache)),
+            governor: Arc::new(Governor::new(&Default::default())),
+            get_coalescer: Arc::new(GetCoalescer::new(&Default::default())),
         }
     }
 
@@ -1628,6 +1834,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn event_streams_are_recognized() {
+        value_scenarios!(
+            run = |(name, value): (HeaderName, &'static str)| {
+                let mut headers = HeaderMap::new();
+                headers.insert(name, HeaderValue::from_static(value));
+                (accepts_event_stream(&headers), is_event_stream(&headers))
+            };
+            "SSE request" {
+                (http::header::ACCEPT, "text/event-stream") => (true, false),
+            }
+
+            "SSE among other accepted types" {
+                (http::header::ACCEPT, "application/json, text/event-stream") => (true, false),
+            }
+
+            "JSON request" {
+                (http::header::ACCEPT, "application/json") => (false, false),
+            }
+
+            "SSE response" {
+                (http::header::CONTENT_TYPE, "text/event-stream; charset=utf-8") => (false, true),
+            }
+
+            "JSON response" {
+                (http::header::CONTENT_TYPE, "application/json") => (false, false),
+            }
+        );
+    }
+
     #[test]
     fn forwarded_host_value_parsing() {
         value_scenarios!(
@@ -1956,6 +2192,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fair_share_principal_is_the_first_authenticated_principal() {
+        value_scenarios!(
+            run = |principals: Option<Vec<Principal>>| {
+                let mut request = Request::new(Body::empty());
+                if let Some(principals) = principals {
+                    request.extensions_mut().insert(AuthContext::<()> {
+                        principals,
+                        authorization: None,
+                    });
+                }
+                fair_share_principal(&request)
+            };
+            "unauthenticated requests share one queue" {
+                None => "anonymous".to_string(),
+                Some(vec![]) => "anonymous".to_string(),
+            }
+
+            "authenticated requests queue under their first principal" {
+                Some(vec![
+                    Principal::SpiffeServiceIdentifier("forge-system/carbide-api".to_string()),
+                    Principal::SpiffeMachineIdentifier("machine-1".to_string()),
+                ]) => "spiffe-service-id/forge-system/carbide-api".to_string(),
+            }
+        );
+    }
+
     /// `BmcProxyState::allows` owns the per-principal ACL boundary. An ordinary
     /// policy rejection moves the denial counter, while a missing `AuthContext`
     /// still rejects the request but moves only the middleware-error counter.
@@ -2327,6 +2590,61 @@ mod tests {
         assert_eq!(body, Bytes::from_static(br#"{"value":"ok"}"#));
     }
 
+    #[tokio::test]
+    async fn an_oversized_coalesced_get_is_streamed_from_one_upstream_call() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let upstream =
+            axum::Router::new().route(
+                "/redfish/v1/LogService/Entries",
+                axum::routing::get({
+                    let calls = calls.clone();
+                    move || async move {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        // Chunked, so the size is only found out while reading.
+                        Body::from_stream(iter((0..=MAX_BODY_SIZE / 1024).map(|_| {
+                            Result::<Bytes, Infallible>::Ok(Bytes::from(vec![b'x'; 1024]))
+                        })))
+                    }
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, upstream).await });
+
+        let state = test_state_with_ip_cache(HashMap::new());
+        let key = GetKey::new(
+            address.ip(),
+            "/redfish/v1/LogService/Entries",
+            &HeaderMap::new(),
+        );
+        let request = build_http_client()
+            .unwrap()
+            .get(format!("http://{address}/redfish/v1/LogService/Entries"));
+        let fetch = fetch_buffered(
+            state.governor.clone(),
+            address.ip(),
+            "principal".to_string(),
+            request,
+            state.credential_cache.clone(),
+        );
+
+        let Err(FetchError::Unbufferable(live)) =
+            state.get_coalescer.get(key.clone(), false, fetch).await
+        else {
+            panic!("an oversized response should not be buffered");
+        };
+        let live = live.take().expect("the live response should be handed out");
+        let body = streamed_body(live.prefix, live.response, live.permit)
+            .collect()
+            .await
+            .unwrap()
+            .to_bytes();
+
+        assert_eq!(body.len(), (MAX_BODY_SIZE / 1024 + 1) * 1024);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(state.get_coalescer.is_unbufferable(&key));
+    }
+
     const TLS_FAILURE_METRIC: &str = "carbide_bmc_proxy_tls_connection_fail_total";
 
     struct TlsFailureInput {
@@ -0,0 +1,505 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! GET coalescing and the short-TTL response cache.
+//!
+//! Health collectors, site-explorer and admin tools routinely read the same
+//! Redfish resource of the same BMC at the same moment. Identical GETs that
+//! overlap share one upstream call, and, for paths configured as cacheable,
+//! a successful response keeps answering for a few seconds afterwards.
+//! Paths whose responses turn out too large to buffer, or to be event
+//! streams, are remembered and no longer coalesced.
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use carbide_instrument::emit;
+use futures::FutureExt;
+use futures::future::{BoxFuture, Shared};
+use http::header::{HeaderName, HeaderValue};
+use http::{HeaderMap, StatusCode};
+
+use crate::config::ResponseCacheConfig;
+use crate::governor::GovernorPermit;
+use crate::metrics::{BmcEndpoint, GetServed, GetSource};
+
+/// How many unbufferable BMC paths are remembered. Once full, the set starts
+/// over, so a misbehaving client cannot grow it without bound.
+const MAX_UNBUFFERABLE_PATHS: usize = 1024;
+
+/// A response read to the end so it can be handed to several callers.
+#[derive(Debug)]
+pub(crate) struct BufferedResponse {
+    pub(crate) status: reqwest::StatusCode,
+    pub(crate) headers: reqwest::header::HeaderMap,
+    pub(crate) body: Bytes,
+}
+
+/// An upstream response that was too large to buffer, or an event stream,
+/// with the part of its body that was already read.
+pub(crate) struct UnbufferedResponse {
+    pub(crate) status: reqwest::StatusCode,
+    pub(crate) headers: reqwest::header::HeaderMap,
+    pub(crate) prefix: Bytes,
+    pub(crate) response: reqwest::Response,
+    /// The governor slot the upstream call holds.
+    pub(crate) permit: GovernorPermit,
+}
+
+/// An [`UnbufferedResponse`] shared by every caller of a coalesced GET. Only
+/// the first caller to [`take`](Self::take) it can stream it.
+#[derive(Clone)]
+pub(crate) struct LiveResponse(Arc<Mutex<Option<UnbufferedResponse>>>);
+
+impl LiveResponse {
+    pub(crate) fn new(response: UnbufferedResponse) -> Self {
+        Self(Arc::new(Mutex::new(Some(response))))
+    }
+
+    pub(crate) fn take(&self) -> Option<UnbufferedResponse> {
+        self.0.lock().expect("live response lock poisoned").take()
+    }
+}
+
+impl std::fmt::Debug for LiveResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LiveResponse").finish_non_exhaustive()
+    }
+}
+
+/// What makes two GETs interchangeable: the same BMC, the same path and
+/// query, and the same headers forwarded upstream (so an `Accept` or
+/// `If-None-Match` difference still gets its own call).
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct GetKey {
+    ip: IpAddr,
+    path_and_query: String,
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+impl GetKey {
+    pub(crate) fn new(ip: IpAddr, path_and_query: &str, forwarded_headers: &HeaderMap) -> Self {
+        let mut headers = forwarded_headers
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect::<Vec<_>>();
+        headers.sort_by(|(a, a_value), (b, b_value)| {
+            (a.as_str(), a_value.as_bytes()).cmp(&(b.as_str(), b_value.as_bytes()))
+        });
+        Self {
+            ip,
+            path_and_query: path_and_query.to_string(),
+            headers,
+        }
+    }
+
+    fn path(&self) -> &str {
+        self.path_and_query
+            .split_once('?')
+            .map_or(self.path_and_query.as_str(), |(path, _)| path)
+    }
+}
+
+/// Why a fetch produced no shared response.
+#[derive(Clone, Debug)]
+pub(crate) enum FetchError {
+    /// The fetch failed; every caller sharing it is answered with this
+    /// status and message.
+    Failed(StatusCode, String),
+    /// The response is an event stream or too large to buffer, so it cannot
+    /// be shared. The first caller streams the response that was already
+    /// fetched; every other caller sends the GET on its own.
+    Unbufferable(LiveResponse),
+}
+
+impl From<(StatusCode, String)> for FetchError {
+    fn from((status, message): (StatusCode, String)) -> Self {
+        Self::Failed(status, message)
+    }
+}
+type FetchResult = Result<Arc<BufferedResponse>, FetchError>;
+type SharedFetch = Shared<BoxFuture<'static, FetchResult>>;
+
+pub(crate) struct GetCoalescer {
+    in_flight: Arc<Mutex<HashMap<GetKey, SharedFetch>>>,
+    cache: Option<Arc<ResponseCache>>,
+    unbufferable: Arc<Mutex<HashSet<(IpAddr, String)>>>,
+}
+
+impl GetCoalescer {
+    pub(crate) fn new(cache_config: &ResponseCacheConfig) -> Self {
+        Self {
+            in_flight: Default::default(),
+            unbufferable: Default::default(),
+            cache: cache_config.enabled.then(|| {
+                Arc::new(ResponseCache {
+                    ttl: cache_config.ttl(),
+                    max_entries: cache_config.max_entries,
+                    entries: Default::default(),
+                })
+            }),
+        }
+    }
+
+    /// Whether an earlier GET of the same BMC path could not be buffered.
+    /// Such GETs are sent on their own instead of being coalesced.
+    pub(crate) fn is_unbufferable(&self, key: &GetKey) -> bool {
+        self.unbufferable
+            .lock()
+            .expect("unbufferable paths lock poisoned")
+            .contains(&(key.ip, key.path().to_string()))
+    }
+
+    /// Serve a GET from the cache, from an identical GET already in flight,
+    /// or by running `fetch`. `fetch` runs in its own task, so the upstream
+    /// call (and the governor slot it holds) finishes even if every caller
+    /// waiting on it goes away.
+    pub(crate) async fn get<F>(&self, key: GetKey, cacheable: bool, fetch: F) -> FetchResult
+    where
+        F: Future<Output = Result<BufferedResponse, FetchError>> + Send + 'static,
+    {
+        let endpoint = BmcEndpoint(key.ip);
+        let cache = self.cache.as_ref().filter(|_| cacheable);
+        if let Some(response) = cache.and_then(|cache| cache.get(&key)) {
+            emit(GetServed {
+                endpoint,
+                source: GetSource::Cache,
+            });
+            return Ok(response);
+        }
+
+        let (shared, source) = {
+            let mut in_flight = self.in_flight.lock().expect("coalescer lock poisoned");
+            match in_flight.get(&key) {
+                Some(shared) => (shared.clone(), GetSource::Coalesced),
+                None => {
+                    let task = tokio::spawn({
+                        let in_flight = self.in_flight.clone();
+                        let unbufferable = self.unbufferable.clone();
+                        let cache = cache.cloned();
+                        let key = key.clone();
+                        async move {
+                            let result = fetch.await.map(Arc::new);
+                            match &result {
+                                Ok(response) if response.status.is_success() => {
+                                    if let Some(cache) = cache {
+                                        cache.insert(key.clone(), response.clone());
+                                    }
+                                }
+                                Err(FetchError::Unbufferable(_)) => {
+                                    let mut unbufferable = unbufferable
+                                        .lock()
+                                        .expect("unbufferable paths lock poisoned");
+                                    if unbufferable.len() >= MAX_UNBUFFERABLE_PATHS {
+                                        unbufferable.clear();
+                                    }
+                                    unbufferable.insert((key.ip, key.path().to_string()));
+                                }
+                                _ => {}
+                            }
+                            in_flight
+                                .lock()
+                                .expect("coalescer lock poisoned")
+                                .remove(&key);
+                            result
+                        }
+                    });
+                    let shared = async move {
+                        task.await.unwrap_or_else(|e| {
+                            Err(FetchError::Failed(
+                                StatusCode::INTERNAL_SERVER_ERROR,
+                                format!("upstream request task failed: {e}"),
+                            ))
+                        })
+                    }
+                    .boxed()
+                    .shared();
+                    in_flight.insert(key, shared.clone());
+                    (shared, GetSource::Upstream)
+                }
+            }
+        };
+
+        emit(GetServed { endpoint, source });
+        shared.await
+    }
+
+    /// Drop every cached response from the BMC at `ip`, after a request that
+    /// may have changed its state.
+    pub(crate) fn invalidate(&self, ip: IpAddr) {
+        if let Some(cache) = &self.cache {
+            cache
+                .entries
+                .lock()
+                .expect("response cache lock poisoned")
+                .retain(|key, _| key.ip != ip);
+        }
+    }
+}
+
+struct ResponseCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<GetKey, (Instant, Arc<BufferedResponse>)>>,
+}
+
+impl ResponseCache {
+    fn get(&self, key: &GetKey) -> Option<Arc<BufferedResponse>> {
+        let mut entries = self.entries.lock().expect("response cache lock poisoned");
+        match entries.get(key) {
+            Some((stored_at, response)) if stored_at.elapsed() < self.ttl => Some(response.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, key: GetKey, response: Arc<BufferedResponse>) {
+        let mut entries = self.entries.lock().expect("response cache lock poisoned");
+        if entries.len() >= self.max_entries {
+            entries.retain(|_, (stored_at, _)| stored_at.elapsed() < self.ttl);
+        }
+        if entries.len() >= self.max_entries {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, (stored_at, _))| *stored_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        if self.max_entries > 0 {
+            entries.insert(key, (Instant::now(), response));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use carbide_instrument::testing::MetricsCapture;
+    use tokio::sync::Notify;
+
+    use super::*;
+
+    const BMC: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 7));
+    const GET_METRIC: &str = "carbide_bmc_proxy_get_requests_total";
+
+    fn cache_config(enabled: bool) -> ResponseCacheConfig {
+        ResponseCacheConfig {
+            enabled,
+            ttl_secs: 60,
+            max_entries: 2,
+            paths: Default::default(),
+        }
+    }
+
+    fn key(path: &str) -> GetKey {
+        GetKey::new(BMC, path, &HeaderMap::new())
+    }
+
+    fn counting_fetch(
+        calls: &Arc<AtomicUsize>,
+        release: Option<Arc<Notify>>,
+        status: reqwest::StatusCode,
+    ) -> impl Future<Output = Result<BufferedResponse, FetchError>> + Send + 'static {
+        let calls = calls.clone();
+        async move {
+            let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if let Some(release) = release {
+                release.notified().await;
+            }
+            Ok(BufferedResponse {
+                status,
+                headers: Default::default(),
+                body: Bytes::from(format!("call {call}")),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn identical_in_flight_gets_share_one_upstream_call() {
+        let coalescer = Arc::new(GetCoalescer::new(&cache_config(false)));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let release = Arc::new(Notify::new());
+        let metrics = MetricsCapture::start();
+
+        let leader = tokio::spawn({
+            let coalescer = coalescer.clone();
+            let fetch = counting_fetch(&calls, Some(release.clone()), reqwest::StatusCode::OK);
+            async move {
+                coalescer
+                    .get(key("/redfish/v1/Chassis"), false, fetch)
+                    .await
+            }
+        });
+        while calls.load(Ordering::SeqCst) == 0 {
+            tokio::task::yield_now().await;
+        }
+        let follower = tokio::spawn({
+            let coalescer = coalescer.clone();
+            let fetch = counting_fetch(&calls, None, reqwest::StatusCode::OK);
+            async move {
+                coalescer
+                    .get(key("/redfish/v1/Chassis"), false, fetch)
+                    .await
+            }
+        });
+        while metrics.counter_delta(
+            GET_METRIC,
+            &[("endpoint", "10.0.0.7"), ("source", "coalesced")],
+        ) == 0.0
+        {
+            tokio::task::yield_now().await;
+        }
+        release.notify_one();
+
+        let leader = leader.await.unwrap().unwrap();
+        let follower = follower.await.unwrap().unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(leader.body, follower.body);
+
+        // Once the call completes, the next GET goes upstream again.
+        let after = coalescer
+            .get(
+                key("/redfish/v1/Chassis"),
+                false,
+                counting_fetch(&calls, None, reqwest::StatusCode::OK),
+            )
+            .await
+            .unwrap();
+        assert_eq!(after.body, Bytes::from("call 2"));
+    }
+
+    #[tokio::test]
+    async fn an_unbufferable_response_is_not_cached_and_its_path_remembered() {
+        let coalescer = GetCoalescer::new(&cache_config(true));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let unbufferable = || {
+            let calls = calls.clone();
+            async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(FetchError::Unbufferable(LiveResponse(Default::default())))
+            }
+        };
+
+        assert!(!coalescer.is_unbufferable(&key("/redfish/v1/EventService/SSE")));
+        for _ in 0..2 {
+            let result = coalescer
+                .get(key("/redfish/v1/EventService/SSE"), true, unbufferable())
+                .await;
+            assert!(matches!(result, Err(FetchError::Unbufferable(_))));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(coalescer.is_unbufferable(&key("/redfish/v1/EventService/SSE?filter=x")));
+        assert!(!coalescer.is_unbufferable(&key("/redfish/v1/EventService")));
+    }
+
+    #[tokio::test]
+    async fn gets_that_differ_do_not_coalesce() {
+        let mut accept_json = HeaderMap::new();
+        accept_json.insert(http::header::ACCEPT, "application/json".parse().unwrap());
+
+        assert_ne!(key("/redfish/v1/Chassis"), key("/redfish/v1/Systems"));
+        assert_ne!(
+            key("/redfish/v1/Chassis"),
+            GetKey::new(BMC, "/redfish/v1/Chassis", &accept_json)
+        );
+        assert_ne!(
+            key("/redfish/v1/Chassis"),
+            GetKey::new(
+                IpAddr::V4(Ipv4Addr::new(10, 0, 0, 8)),
+                "/redfish/v1/Chassis",
+                &HeaderMap::new()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn cacheable_successes_are_served_until_invalidated() {
+        let coalescer = GetCoalescer::new(&cache_config(true));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let get = |path: &'static str, cacheable: bool, status: reqwest::StatusCode| {
+            coalescer.get(key(path), cacheable, counting_fetch(&calls, None, status))
+        };
+
+        get("/redfish/v1/Chassis", true, reqwest::StatusCode::OK)
+            .await
+            .unwrap();
+        let cached = get("/redfish/v1/Chassis", true, reqwest::StatusCode::OK)
+            .await
+            .unwrap();
+        assert_eq!(cached.body, Bytes::from("call 1"));
+
+        // Failures and non-cacheable paths always go upstream.
+        get(
+            "/redfish/v1/Systems",
+            true,
+            reqwest::StatusCode::SERVICE_UNAVAILABLE,
+        )
+        .await
+        .unwrap();
+        get("/redfish/v1/Systems", true, reqwest::StatusCode::OK)
+            .await
+            .unwrap();
+        get("/redfish/v1/Managers", false, reqwest::StatusCode::OK)
+            .await
+            .unwrap();
+        get("/redfish/v1/Managers", false, reqwest::StatusCode::OK)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+
+        coalescer.invalidate(BMC);
+        let refreshed = get("/redfish/v1/Chassis", true, reqwest::StatusCode::OK)
+            .await
+            .unwrap();
+        assert_eq!(refreshed.body, Bytes::from("call 6"));
+    }
+
+    #[test]
+    fn a_full_cache_evicts_its_oldest_entry() {
+        let cache = ResponseCache {
+            ttl: Duration::from_secs(60),
+            max_entries: 2,
+            entries: Default::default(),
+        };
+        let response = Arc::new(BufferedResponse {
+            status: reqwest::StatusCode::OK,
+            headers: Default::default(),
+            body: Bytes::new(),
+        });
+
+        for path in ["/a", "/b", "/c"] {
+            cache.insert(key(path), response.clone());
+            // Keep insertion times distinct so "oldest" is well defined.
+            std::thread::sleep(Duration::from_millis(2));
+        }
+
+        assert!(cache.get(&key("/a")).is_none());
+        assert!(cache.get(&key("/b")).is_some());
+        assert!(cache.get(&key("/c")).is_some());
+    }
+}
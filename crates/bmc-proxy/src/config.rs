@@ -18,6 +18,7 @@
 use std::collections::HashSet;
 use std::net::SocketAddr;
 use std::str::FromStr;
+use std::time::Duration;
 
 use carbide_authn::config::{AllowedCertCriteria, TrustConfig};
 use carbide_utils::HostPortPair;
@@ -26,7 +27,7 @@ use figment::providers::{Env, Format, Toml};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::acl::AclConfig;
+use crate::acl::{AclConfig, AclRules};
 
 #[derive(thiserror::Error, Debug)]
 pub(crate) enum ConfigError {
@@ -57,6 +58,8 @@ pub(crate) struct Config {
     pub(crate) bmc_proxy: Option<HostPortPair>,
     #[serde(default)]
     pub(crate) tracing: TracingConfig,
+    #[serde(default)]
+    pub(crate) governor: GovernorConfig,
 }
 
 /// OpenTelemetry trace export settings for proxied BMC requests.
@@ -72,6 +75,88 @@ pub(crate) struct TracingConfig {
     pub(crate) otlp_endpoint: Option<String>,
 }
 
+/// Limits on what the proxy sends to any one BMC. Many BMCs fall over under
+/// a handful of concurrent sessions, so requests beyond the per-BMC limit wait
+/// their turn, served round-robin across principals so one busy caller cannot
+/// starve the others.
+#[derive(Clone, Deserialize)]
+pub(crate) struct GovernorConfig {
+    /// Upstream requests allowed in flight to one BMC. Default: 4.
+    #[serde(default = "Defaults::max_concurrent_per_bmc")]
+    pub(crate) max_concurrent_per_bmc: usize,
+    /// Requests allowed to wait for one BMC; the proxy answers 503 beyond
+    /// this. Default: 64.
+    #[serde(default = "Defaults::max_queued_per_bmc")]
+    pub(crate) max_queued_per_bmc: usize,
+    /// Longest a request waits for an upstream slot before the proxy answers
+    /// 503. Default: 30.
+    #[serde(default = "Defaults::queue_timeout_secs")]
+    pub(crate) queue_timeout_secs: u64,
+    /// Serve identical concurrent GETs to a BMC from one upstream call.
+    /// Event streams (SSE) and responses over the 8MiB body limit cannot be
+    /// shared: one caller streams the response, and later GETs of the same
+    /// path are sent on their own. Default: true.
+    #[serde(default = "Defaults::coalesce_gets")]
+    pub(crate) coalesce_gets: bool,
+    #[serde(default)]
+    pub(crate) response_cache: ResponseCacheConfig,
+}
+
+impl GovernorConfig {
+    pub(crate) fn queue_timeout(&self) -> Duration {
+        Duration::from_secs(self.queue_timeout_secs)
+    }
+}
+
+impl Default for GovernorConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_per_bmc: Defaults::max_concurrent_per_bmc(),
+            max_queued_per_bmc: Defaults::max_queued_per_bmc(),
+            queue_timeout_secs: Defaults::queue_timeout_secs(),
+            coalesce_gets: Defaults::coalesce_gets(),
+            response_cache: Default::default(),
+        }
+    }
+}
+
+/// A short-lived cache of successful GET responses, for read-only Redfish
+/// resources that several pollers read on overlapping schedules. It only
+/// applies while `coalesce_gets` is on.
+#[derive(Clone, Deserialize)]
+pub(crate) struct ResponseCacheConfig {
+    /// Default: false.
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// How long a cached response is served. Default: 5.
+    #[serde(default = "Defaults::response_cache_ttl_secs")]
+    pub(crate) ttl_secs: u64,
+    /// Cached responses kept across all BMCs. Default: 4096.
+    #[serde(default = "Defaults::response_cache_max_entries")]
+    pub(crate) max_entries: usize,
+    /// Which GET paths may be cached, in `auth.acls` entry syntax. Empty
+    /// caches nothing.
+    #[serde(default)]
+    pub(crate) paths: AclRules,
+}
+
+impl ResponseCacheConfig {
+    pub(crate) fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_secs)
+    }
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_secs: Defaults::response_cache_ttl_secs(),
+            max_entries: Defaults::response_cache_max_entries(),
+            paths: AclRules::default(),
+        }
+    }
+}
+
 struct Defaults;
 
 impl Defaults {
@@ -83,6 +168,30 @@ impl Defaults {
         SocketAddr::from_str("[::]:1080").expect("BUG: default metrics endpoint doesn't parse")
     }
 
+    fn max_concurrent_per_bmc() -> usize {
+        4
+    }
+
+    fn max_queued_per_bmc() -> usize {
+        64
+    }
+
+    fn queue_timeout_secs() -> u64 {
+        30
+    }
+
+    fn coalesce_gets() -> bool {
+        true
+    }
+
+    fn response_cache_ttl_secs() -> u64 {
+        5
+    }
+
+    fn response_cache_max_entries() -> usize {
+        4096
+    }
+
     fn trust_config() -> TrustConfig {
         TrustConfig {
             spiffe_trust_domain: "nico.local".to_string(),
@@ -442,4 +551,82 @@ mod tests {
             }
         );
     }
+    #[derive(Debug, PartialEq)]
+    struct GovernorSummary {
+        max_concurrent_per_bmc: usize,
+        max_queued_per_bmc: usize,
+        queue_timeout: Duration,
+        coalesce_gets: bool,
+        cache_enabled: bool,
+        cache_ttl: Duration,
+        cache_max_entries: usize,
+        caches_chassis: bool,
+        caches_sessions: bool,
+    }
+
+    #[test]
+    fn parses_governor_section() {
+        value_scenarios!(
+            run = |section: &str| {
+                let config = Config::parse(&format!("{section}\n{MINIMAL_TLS}"))
+                    .expect("config parses");
+                let governor = config.governor;
+                let cache = &governor.response_cache;
+                GovernorSummary {
+                    max_concurrent_per_bmc: governor.max_concurrent_per_bmc,
+                    max_queued_per_bmc: governor.max_queued_per_bmc,
+                    queue_timeout: governor.queue_timeout(),
+                    coalesce_gets: governor.coalesce_gets,
+                    cache_enabled: cache.enabled,
+                    cache_ttl: cache.ttl(),
+                    cache_max_entries: cache.max_entries,
+                    caches_chassis: cache
+                        .paths
+                        .allows(&http::Method::GET, "/redfish/v1/Chassis/1"),
+                    caches_sessions: cache
+                        .paths
+                        .allows(&http::Method::GET, "/redfish/v1/SessionService/Sessions"),
+                }
+            };
+            "absent section uses defaults" {
+                "" => GovernorSummary {
+                    max_concurrent_per_bmc: 4,
+                    max_queued_per_bmc: 64,
+                    queue_timeout: Duration::from_secs(30),
+                    coalesce_gets: true,
+                    cache_enabled: false,
+                    cache_ttl: Duration::from_secs(5),
+                    cache_max_entries: 4096,
+                    caches_chassis: false,
+                    caches_sessions: false,
+                },
+            }
+
+            "explicit limits and cached paths" {
+                r#"
+                [governor]
+                max_concurrent_per_bmc = 2
+                max_queued_per_bmc = 8
+                queue_timeout_secs = 10
+                coalesce_gets = false
+
+                [governor.response_cache]
+                enabled = true
+                ttl_secs = 2
+                max_entries = 16
+                paths = ["!GET /redfish/v1/SessionService/**", "GET /redfish/v1/**"]
+                "# => GovernorSummary {
+                    max_concurrent_per_bmc: 2,
+                    max_queued_per_bmc: 8,
+                    queue_timeout: Duration::from_secs(10),
+                    coalesce_gets: false,
+                    cache_enabled: true,
+                    cache_ttl: Duration::from_secs(2),
+                    cache_max_entries: 16,
+                    caches_chassis: true,
+                    caches_sessions: false,
+                },
+            }
+        );
+    }
 }
@@ -0,0 +1,424 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Per-BMC admission control for upstream requests.
+//!
+//! Each BMC gets a fixed number of upstream slots. A request that finds no
+//! free slot waits in that BMC's queue, and freed slots are handed to waiting
+//! principals in turn, so a collector polling hundreds of resources and an
+//! operator's one-off request make progress at the same rate.
+
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use carbide_instrument::emit;
+use tokio::sync::oneshot;
+
+use crate::config::GovernorConfig;
+use crate::metrics::{AdmissionDecided, AdmissionOutcome, BmcEndpoint, QueueDepthChanged};
+
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AdmissionError {
+    #[error("too many requests are already waiting for BMC {0}")]
+    QueueFull(IpAddr),
+    #[error("timed out after {1:?} waiting for a free connection to BMC {0}")]
+    TimedOut(IpAddr, Duration),
+}
+
+pub(crate) struct Governor {
+    max_in_flight: usize,
+    max_queued: usize,
+    queue_timeout: Duration,
+    gates: Mutex<HashMap<IpAddr, Arc<BmcGate>>>,
+}
+
+impl Governor {
+    pub(crate) fn new(config: &GovernorConfig) -> Self {
+        Self {
+            // A zero limit would queue every request until it times out.
+            max_in_flight: config.max_concurrent_per_bmc.max(1),
+            max_queued: config.max_queued_per_bmc,
+            queue_timeout: config.queue_timeout(),
+            gates: Default::default(),
+        }
+    }
+
+    /// Wait for an upstream slot to the BMC at `ip`. The slot is held until
+    /// the returned permit is dropped.
+    pub(crate) async fn admit(
+        &self,
+        ip: IpAddr,
+        principal: &str,
+    ) -> Result<GovernorPermit, AdmissionError> {
+        let gate = self
+            .gates
+            .lock()
+            .expect("governor lock poisoned")
+            .entry(ip)
+            .or_insert_with(|| {
+                Arc::new(BmcGate {
+                    ip,
+                    max_in_flight: self.max_in_flight,
+                    max_queued: self.max_queued,
+                    state: Default::default(),
+                })
+            })
+            .clone();
+        gate.admit(principal, self.queue_timeout).await
+    }
+}
+
+/// Holds one upstream slot to a BMC; dropping it frees the slot for the next
+/// waiting principal.
+pub(crate) struct GovernorPermit {
+    gate: Arc<BmcGate>,
+}
+
+impl Drop for GovernorPermit {
+    fn drop(&mut self) {
+        self.gate.release();
+    }
+}
+
+struct BmcGate {
+    ip: IpAddr,
+    max_in_flight: usize,
+    max_queued: usize,
+    state: Mutex<GateState>,
+}
+
+#[derive(Default)]
+struct GateState {
+    in_flight: usize,
+    queued: usize,
+    next_waiter_id: u64,
+    /// Principals with waiting requests, in the order they are next served.
+    rotation: VecDeque<String>,
+    waiting: HashMap<String, VecDeque<Waiter>>,
+}
+
+struct Waiter {
+    id: u64,
+    /// Receiving on this means the releasing request's slot now belongs to
+    /// the waiter; `in_flight` is not decremented in between.
+    handoff: oneshot::Sender<()>,
+}
+
+impl BmcGate {
+    fn endpoint(&self) -> BmcEndpoint {
+        BmcEndpoint(self.ip)
+    }
+
+    async fn admit(
+        self: Arc<Self>,
+        principal: &str,
+        queue_timeout: Duration,
+    ) -> Result<GovernorPermit, AdmissionError> {
+        let started = Instant::now();
+        let mut ticket = {
+            let mut state = self.state.lock().expect("governor lock poisoned");
+            // Waiting requests go first, or a steady stream of new arrivals
+            // would starve the queue.
+            if state.in_flight < self.max_in_flight && state.queued == 0 {
+                state.in_flight += 1;
+                drop(state);
+                self.decided(AdmissionOutcome::Immediate, Duration::ZERO);
+                return Ok(GovernorPermit { gate: self });
+            }
+            if state.queued >= self.max_queued {
+                drop(state);
+                self.decided(AdmissionOutcome::QueueFull, Duration::ZERO);
+                return Err(AdmissionError::QueueFull(self.ip));
+            }
+
+            let id = state.next_waiter_id;
+            state.next_waiter_id += 1;
+            let (handoff, receiver) = oneshot::channel();
+            if !state.waiting.contains_key(principal) {
+                state.rotation.push_back(principal.to_string());
+            }
+            state
+                .waiting
+                .entry(principal.to_string())
+                .or_default()
+                .push_back(Waiter { id, handoff });
+            state.queued += 1;
+            self.depth_changed(state.queued);
+
+            Ticket {
+                gate: self.clone(),
+                principal: principal.to_string(),
+                id,
+                receiver: Some(receiver),
+            }
+        };
+
+        let receiver = ticket.receiver.as_mut().expect("ticket not yet settled");
+        match tokio::time::timeout(queue_timeout, receiver).await {
+            Ok(Ok(())) => {
+                ticket.receiver = None;
+                self.decided(AdmissionOutcome::Queued, started.elapsed());
+                Ok(GovernorPermit { gate: self })
+            }
+            // The sender is only dropped unsent when the waiter leaves the
+            // queue on its own, which cannot happen while it is awaited here.
+            Ok(Err(_)) | Err(_) => {
+                drop(ticket);
+                self.decided(AdmissionOutcome::TimedOut, started.elapsed());
+                Err(AdmissionError::TimedOut(self.ip, queue_timeout))
+            }
+        }
+    }
+
+    /// Hand the caller's slot to the next principal in rotation, or free it
+    /// when nobody is waiting.
+    fn release(&self) {
+        let mut state = self.state.lock().expect("governor lock poisoned");
+        while let Some(principal) = state.rotation.pop_front() {
+            let Some(queue) = state.waiting.get_mut(&principal) else {
+                continue;
+            };
+            let waiter = queue.pop_front();
+            if queue.is_empty() {
+                state.waiting.remove(&principal);
+            } else {
+                state.rotation.push_back(principal);
+            }
+            let Some(waiter) = waiter else {
+                continue;
+            };
+            state.queued -= 1;
+            self.depth_changed(state.queued);
+            if waiter.handoff.send(()).is_ok() {
+                return;
+            }
+        }
+        state.in_flight -= 1;
+    }
+
+    /// Take a waiter out of the queue because it stopped waiting. Returns
+    /// `false` when it had already been handed a slot.
+    fn withdraw(&self, principal: &str, id: u64) -> bool {
+        let mut state = self.state.lock().expect("governor lock poisoned");
+        let Some(queue) = state.waiting.get_mut(principal) else {
+            return false;
+        };
+        let Some(position) = queue.iter().position(|waiter| waiter.id == id) else {
+            return false;
+        };
+        queue.remove(position);
+        if queue.is_empty() {
+            state.waiting.remove(principal);
+            state.rotation.retain(|waiting| waiting != principal);
+        }
+        state.queued -= 1;
+        self.depth_changed(state.queued);
+        true
+    }
+
+    fn decided(&self, outcome: AdmissionOutcome, waited: Duration) {
+        emit(AdmissionDecided {
+            endpoint: self.endpoint(),
+            outcome,
+            waited,
+        });
+    }
+
+    fn depth_changed(&self, queued: usize) {
+        emit(QueueDepthChanged {
+            endpoint: self.endpoint(),
+            depth: queued as f64,
+        });
+    }
+}
+
+/// A request's place in a BMC queue. Dropping it before the request was
+/// admitted -- a timeout, or the caller disconnecting -- leaves the queue,
+/// and passes on a slot that was handed over in the meantime.
+struct Ticket {
+    gate: Arc<BmcGate>,
+    principal: String,
+    id: u64,
+    receiver: Option<oneshot::Receiver<()>>,
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        let Some(mut receiver) = self.receiver.take() else {
+            return;
+        };
+        if self.gate.withdraw(&self.principal, self.id) {
+            return;
+        }
+        // Handoffs happen under the gate lock, so once `withdraw` found the
+        // waiter gone, the slot is already in the channel.
+        receiver.close();
+        if receiver.try_recv().is_ok() {
+            self.gate.release();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use carbide_instrument::testing::MetricsCapture;
+
+    use super::*;
+
+    const BMC: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 42));
+    const ADMISSION_METRIC: &str = "carbide_bmc_proxy_queue_wait_milliseconds";
+
+    fn governor(max_concurrent_per_bmc: usize, max_queued_per_bmc: usize) -> Governor {
+        Governor::new(&GovernorConfig {
+            max_concurrent_per_bmc,
+            max_queued_per_bmc,
+            queue_timeout_secs: 30,
+            ..Default::default()
+        })
+    }
+
+    fn queued(governor: &Governor) -> usize {
+        governor.gates.lock().unwrap()[&BMC]
+            .state
+            .lock()
+            .unwrap()
+            .queued
+    }
+
+    async fn wait_until_queued(governor: &Governor, count: usize) {
+        while queued(governor) < count {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn requests_beyond_the_limit_wait_for_a_slot() {
+        let governor = Arc::new(governor(1, 8));
+        let first = governor.admit(BMC, "collector").await.unwrap();
+
+        let waiting = tokio::spawn({
+            let governor = governor.clone();
+            async move { governor.admit(BMC, "collector").await.map(|_| ()) }
+        });
+        wait_until_queued(&governor, 1).await;
+        assert!(!waiting.is_finished());
+
+        drop(first);
+        waiting.await.unwrap().unwrap();
+        assert_eq!(queued(&governor), 0);
+    }
+
+    #[tokio::test]
+    async fn freed_slots_rotate_across_principals() {
+        let governor = Arc::new(governor(1, 8));
+        let first = governor.admit(BMC, "collector").await.unwrap();
+
+        let (order_tx, mut order_rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut tasks = Vec::new();
+        // The collector queues three requests before the operator's one
+        // arrives; the operator still goes second.
+        for (position, principal) in ["collector", "collector", "collector", "operator"]
+            .into_iter()
+            .enumerate()
+        {
+            let governor = governor.clone();
+            let order_tx = order_tx.clone();
+            tasks.push(tokio::spawn(async move {
+                let permit = governor.admit(BMC, principal).await.unwrap();
+                order_tx.send(principal).unwrap();
+                tokio::task::yield_now().await;
+                drop(permit);
+            }));
+            wait_until_queued(&governor, position + 1).await;
+        }
+        drop(first);
+        for task in tasks {
+            task.await.unwrap();
+        }
+        drop(order_tx);
+
+        let mut order = Vec::new();
+        while let Some(principal) = order_rx.recv().await {
+            order.push(principal);
+        }
+        assert_eq!(order, ["collector", "operator", "collector", "collector"]);
+    }
+
+    #[tokio::test]
+    async fn a_full_queue_rejects_without_waiting() {
+        let governor = Arc::new(governor(1, 1));
+        let metrics = MetricsCapture::start();
+        let _first = governor.admit(BMC, "collector").await.unwrap();
+        let _waiting = tokio::spawn({
+            let governor = governor.clone();
+            async move { governor.admit(BMC, "collector").await.map(|_| ()) }
+        });
+        wait_until_queued(&governor, 1).await;
+
+        assert_eq!(
+            governor.admit(BMC, "operator").await.err(),
+            Some(AdmissionError::QueueFull(BMC))
+        );
+        let endpoint = BMC.to_string();
+        assert_eq!(
+            metrics.histogram_count_delta(
+                ADMISSION_METRIC,
+                &[("endpoint", endpoint.as_str()), ("outcome", "queue_full")]
+            ),
+            1
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_timed_out_waiter_leaves_the_queue() {
+        let governor = governor(1, 8);
+        let first = governor.admit(BMC, "collector").await.unwrap();
+
+        assert_eq!(
+            governor.admit(BMC, "operator").await.err(),
+            Some(AdmissionError::TimedOut(BMC, Duration::from_secs(30)))
+        );
+        assert_eq!(queued(&governor), 0);
+
+        // The slot still frees normally, and the next request gets it at once.
+        drop(first);
+        let _next = governor.admit(BMC, "operator").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn a_cancelled_waiter_does_not_leak_its_slot() {
+        let governor = Arc::new(governor(1, 8));
+        let first = governor.admit(BMC, "collector").await.unwrap();
+        let waiting = tokio::spawn({
+            let governor = governor.clone();
+            async move { governor.admit(BMC, "operator").await.map(|_| ()) }
+        });
+        wait_until_queued(&governor, 1).await;
+
+        waiting.abort();
+        let _ = waiting.await;
+        drop(first);
+
+        let state = governor.gates.lock().unwrap()[&BMC].clone();
+        let state = state.state.lock().unwrap();
+        assert_eq!((state.in_flight, state.queued), (0, 0));
+    }
+}
@@ -21,7 +21,9 @@ use std::sync::Arc;
 
 mod acl;
 mod bmc_proxy;
+mod coalesce;
 mod config;
+mod governor;
 mod metrics;
 mod net;
 mod setup;
@@ -18,14 +18,17 @@
 //! The bmc-proxy metrics endpoint and the proxy's instrumentation events.
 //! Authorization events keep policy denials separate from missing middleware
 //! context, while each outbound BMC request records its duration and status.
+//! Governor events show, per BMC, how long requests queue for an upstream
+//! slot and how many GETs were served without an upstream call of their own.
 
 use std::io;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::time::Duration;
 
 use carbide_instrument::{Event, LabelValue, MetricFamily};
 use http::Method;
 use metrics_endpoint::{MetricsEndpointConfig, MetricsSetup};
+use opentelemetry::StringValue;
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 
@@ -283,6 +286,103 @@ pub(crate) struct UpstreamRequestCompleted {
     pub(crate) took: Duration,
 }
 
+/// `BmcEndpoint` is the reviewed escape hatch for the governor's `endpoint`
+/// label. The proxy only forwards to BMCs nico-api resolves or credentials, so
+/// the value set is the site's BMC inventory: large, but fixed by the
+/// hardware rather than by traffic. Per-BMC series are the point of these
+/// metrics -- a fragile BMC shows up as its own queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BmcEndpoint(pub(crate) IpAddr);
+
+impl LabelValue for BmcEndpoint {
+    fn label_value(&self) -> StringValue {
+        self.0.to_string().into()
+    }
+}
+
+/// How the governor answered a request for an upstream slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, LabelValue)]
+pub(crate) enum AdmissionOutcome {
+    /// A slot was free and nobody was waiting.
+    Immediate,
+    /// The request waited in the BMC's queue and then got a slot.
+    Queued,
+    /// The BMC's queue was full; the caller got a 503 without waiting.
+    QueueFull,
+    /// The request waited the full queue timeout; the caller got a 503.
+    TimedOut,
+}
+
+/// A request for an upstream slot to one BMC was decided. The observation is
+/// the time spent queued (zero unless the outcome is `queued` or
+/// `timed_out`); the _count series, split by outcome, gives the admission and
+/// rejection rates per BMC. Metric-only, like the upstream request histogram.
+#[derive(Event)]
+#[event(
+    event_name = "bmc_proxy_governor_admission_decided",
+    metric_name = "carbide_bmc_proxy_queue_wait_milliseconds",
+    component = "nico-bmc-proxy",
+    log = off,
+    metric = histogram,
+    describe = "Time requests waited for an upstream slot to a BMC, by BMC endpoint and admission outcome; the _count series gives the admission and rejection rates."
+)]
+pub(crate) struct AdmissionDecided {
+    #[label]
+    pub(crate) endpoint: BmcEndpoint,
+    #[label]
+    pub(crate) outcome: AdmissionOutcome,
+    #[observation]
+    pub(crate) waited: Duration,
+}
+
+/// The number of requests waiting for an upstream slot to one BMC, sampled
+/// whenever a request joins or leaves the queue.
+#[derive(Event)]
+#[event(
+    event_name = "bmc_proxy_governor_queue_depth_changed",
+    metric_name = "carbide_bmc_proxy_queue_depth",
+    component = "nico-bmc-proxy",
+    log = off,
+    metric = gauge,
+    describe = "Number of requests waiting for an upstream slot, by BMC endpoint."
+)]
+pub(crate) struct QueueDepthChanged {
+    #[label]
+    pub(crate) endpoint: BmcEndpoint,
+    #[observation]
+    pub(crate) depth: f64,
+}
+
+/// Where a GET's response came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, LabelValue)]
+pub(crate) enum GetSource {
+    /// This request made the upstream call.
+    Upstream,
+    /// An identical GET was already in flight; this one shared its response.
+    Coalesced,
+    /// A fresh response was in the response cache.
+    Cache,
+}
+
+/// A GET was served, labelled by whether it cost the BMC a call. The ratio of
+/// `coalesced` and `cache` to `upstream` is the load the proxy kept off each
+/// BMC.
+#[derive(Event)]
+#[event(
+    event_name = "bmc_proxy_get_served",
+    metric_name = "carbide_bmc_proxy_get_requests_total",
+    component = "nico-bmc-proxy",
+    log = off,
+    metric = counter,
+    describe = "Number of GET requests served, by BMC endpoint and whether the response came from the BMC, a coalesced in-flight request, or the response cache."
+)]
+pub(crate) struct GetServed {
+    #[label]
+    pub(crate) endpoint: BmcEndpoint,
+    #[label]
+    pub(crate) source: GetSource,
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
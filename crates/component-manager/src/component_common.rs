@@ -3,6 +3,8 @@
 
 //! Shared helpers used across component-manager backends and state controllers.
 
+use model::component_manager::FirmwareState;
+
 // ---------------------------------------------------------------------------
 // Power-state observation
 // ---------------------------------------------------------------------------
@@ -40,6 +42,45 @@ pub fn interpret_power_state_poll<T: ComponentPowerStateResult>(
     }
 }
 
+// ---------------------------------------------------------------------------
+// Firmware job aggregation
+// ---------------------------------------------------------------------------
+
+/// Collapse the states of the jobs making up one device's firmware update into
+/// a single state. Terminal failures win over activity, and activity wins over
+/// unknown, so a device is only `Completed` when every job is.
+pub fn aggregate_firmware_job_states(states: &[FirmwareState]) -> FirmwareState {
+    if states.is_empty() {
+        return FirmwareState::Unknown;
+    }
+    if states.contains(&FirmwareState::Failed) {
+        return FirmwareState::Failed;
+    }
+    if states.contains(&FirmwareState::Cancelled) {
+        return FirmwareState::Cancelled;
+    }
+    if states.contains(&FirmwareState::InProgress) {
+        return FirmwareState::InProgress;
+    }
+    if states.contains(&FirmwareState::Verifying) {
+        return FirmwareState::Verifying;
+    }
+    if states.contains(&FirmwareState::Queued) {
+        return FirmwareState::Queued;
+    }
+    if states.contains(&FirmwareState::Unknown) {
+        return FirmwareState::Unknown;
+    }
+    if states
+        .iter()
+        .all(|state| *state == FirmwareState::Completed)
+    {
+        FirmwareState::Completed
+    } else {
+        FirmwareState::Unknown
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use mac_address::MacAddress;
@@ -732,6 +732,22 @@ pub async fn build_component_manager(
                 config.nvos_password_rotation_enabled,
             ))
         }
+        PowerShelfBackend::Core => {
+            let pool = redfish_pool.clone().ok_or_else(|| {
+                ComponentManagerError::InvalidArgument(
+                    "power_shelf_backend is 'core' but Redfish client pool is not configured"
+                        .into(),
+                )
+            })?;
+            let mut manager = crate::core_power_shelf_manager::CorePowerShelfManager::new(pool);
+            if let Some(bundles) = core_firmware_bundles(config) {
+                manager = manager.with_firmware_bundles(bundles);
+            }
+            if let Some(db) = db.clone() {
+                manager = manager.with_database(db);
+            }
+            Arc::new(manager)
+        }
         PowerShelfBackend::Mock => Arc::new(crate::mock::MockPowerShelfManager),
     };
 
@@ -762,9 +778,14 @@ pub async fn build_component_manager(
                         .into(),
                 )
             })?;
-            Arc::new(crate::core_compute_manager::CoreComputeTrayManager::new(
-                pool,
-            ))
+            let mut manager = crate::core_compute_manager::CoreComputeTrayManager::new(pool);
+            if let Some(bundles) = core_firmware_bundles(config) {
+                manager = manager.with_firmware_bundles(bundles);
+            }
+            if let Some(db) = db.clone() {
+                manager = manager.with_database(db);
+            }
+            Arc::new(manager)
         }
         ComputeBackend::Mock => Arc::new(crate::mock::MockComputeTrayManager),
     };
@@ -779,6 +800,16 @@ pub async fn build_component_manager(
     ))
 }
 
+fn core_firmware_bundles(
+    config: &ComponentManagerConfig,
+) -> Option<crate::core_redfish::FirmwareBundles> {
+    config
+        .core
+        .as_ref()
+        .and_then(|core| core.firmware_directory.as_ref())
+        .map(crate::core_redfish::FirmwareBundles::new)
+}
+
 #[cfg(test)]
 mod tests {
     use async_trait::async_trait;
@@ -1450,6 +1481,22 @@ mod tests {
         assert!(matches!(err, ComponentManagerError::InvalidArgument(_)));
     }
 
+    #[tokio::test]
+    async fn build_core_power_shelf_without_redfish_pool_returns_error() {
+        let config = ComponentManagerConfig {
+            nv_switch_backend: NvSwitchBackend::Mock,
+            power_shelf_backend: PowerShelfBackend::Core,
+            compute_tray_backend: ComputeBackend::Mock,
+            ..Default::default()
+        };
+
+        let err = build_component_manager(&config, Default::default(), None, None, None, None)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ComponentManagerError::InvalidArgument(_)));
+    }
+
     // A config that explicitly selects working switch/power-shelf backends but
     // leaves `compute_tray_backend` at its default (now `Rms`) must not be able
     // to silently come up half-configured: RMS validation rejects missing rack
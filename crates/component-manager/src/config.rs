@@ -22,6 +22,9 @@ pub struct ComponentManagerConfig {
     pub nsm: Option<BackendEndpointConfig>,
     #[serde(default)]
     pub psm: Option<BackendEndpointConfig>,
+    /// Settings shared by the `core` power shelf and compute tray backends.
+    #[serde(default)]
+    pub core: Option<CoreBackendConfig>,
 
     /// When `true`, Switch power control and firmware update calls go
     /// through the switch state controller, instead of being dispatched
@@ -141,6 +144,17 @@ pub struct BackendEndpointConfig {
 /// Follows the same SPIFFE cert convention used by NICo Flow: a directory
 /// containing `ca.crt`, `tls.crt`, and `tls.key`. Alternatively, each
 /// path can be set individually.
+/// Settings for the `core` backends, which talk to power shelf and compute
+/// tray BMCs directly over Redfish.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CoreBackendConfig {
+    /// Directory holding firmware bundles, one subdirectory per version.
+    /// Firmware updates through the `core` backends are rejected when unset.
+    #[serde(default)]
+    pub firmware_directory: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct BackendTlsConfig {
@@ -212,6 +226,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn core_backend_config_deserializes() {
+        let cfg: ComponentManagerConfig = toml::from_str(
+            r#"
+            power_shelf_backend = "core"
+            compute_tray_backend = "core"
+
+            [core]
+            firmware_directory = "/opt/nico/firmware/core"
+            "#,
+        )
+        .expect("component-manager configuration should deserialize");
+
+        assert_eq!(cfg.power_shelf_backend, PowerShelfBackend::Core);
+        assert_eq!(cfg.compute_tray_backend, ComputeBackend::Core);
+        assert_eq!(
+            cfg.core.and_then(|core| core.firmware_directory).as_deref(),
+            Some("/opt/nico/firmware/core")
+        );
+    }
+
     /// One `BackendTlsConfig` worth of path inputs for a resolver table.
     struct Row {
         cert_dir: Option<&'static str>,
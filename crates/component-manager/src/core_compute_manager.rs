@@ -1,33 +1,88 @@
 // SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::net::IpAddr;
 use std::sync::Arc;
 
-use carbide_redfish::libredfish::RedfishClientPool;
+use carbide_redfish::libredfish::{RedfishAuth, RedfishClientPool};
 use carbide_secrets::credentials::Credentials;
-use model::component_manager::{ComputeTrayComponent, PowerAction};
+use libredfish::Redfish;
+use model::component_manager::{ComputeTrayComponent, FirmwareState, PowerAction};
+use sqlx::PgPool;
 
 use crate::compute_tray_manager::{
     Backend, ComputeTrayEndpoint, ComputeTrayFirmwareUpdateStatus, ComputeTrayResult,
     ComputeTrayVendor,
 };
+use crate::core_redfish::{
+    FirmwareBundles, FirmwareUpdateTracker, TrackedFirmwareUpdate, map_power_action,
+    poll_firmware_update, push_images,
+};
 use crate::error::ComponentManagerError;
 
-/// Compute tray manager backend that uses NICo-core's Redfish stack for
-/// power control. Firmware operations are not yet supported.
+/// Compute tray manager backend that uses NICo-core's Redfish stack to talk
+/// to tray BMCs directly. Firmware updates push images from
+/// [`FirmwareBundles`] through the BMC's `UpdateService` and are followed by
+/// polling the resulting tasks.
 pub struct CoreComputeTrayManager {
     redfish_pool: Arc<dyn RedfishClientPool>,
+    firmware_bundles: Option<FirmwareBundles>,
+    firmware_updates: FirmwareUpdateTracker<IpAddr>,
 }
 
 impl std::fmt::Debug for CoreComputeTrayManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("CoreComputeTrayManager").finish()
+        f.debug_struct("CoreComputeTrayManager")
+            .field("firmware_bundles", &self.firmware_bundles)
+            .finish()
     }
 }
 
 impl CoreComputeTrayManager {
     pub fn new(redfish_pool: Arc<dyn RedfishClientPool>) -> Self {
-        Self { redfish_pool }
+        Self {
+            redfish_pool,
+            firmware_bundles: None,
+            firmware_updates: FirmwareUpdateTracker::new("compute_tray"),
+        }
+    }
+
+    /// Stores firmware updates in `database`, so that their status can still
+    /// be followed after a restart.
+    pub fn with_database(mut self, database: PgPool) -> Self {
+        self.firmware_updates = self.firmware_updates.with_database(database);
+        self
+    }
+
+    /// Enables firmware updates from `firmware_bundles`.
+    pub fn with_firmware_bundles(mut self, firmware_bundles: FirmwareBundles) -> Self {
+        self.firmware_bundles = Some(firmware_bundles);
+        self
+    }
+
+    fn firmware_bundles(&self) -> Result<&FirmwareBundles, ComponentManagerError> {
+        self.firmware_bundles.as_ref().ok_or_else(|| {
+            ComponentManagerError::Unsupported(
+                "the core compute tray backend has no firmware_directory configured".into(),
+            )
+        })
+    }
+
+    async fn client(&self, ep: &ComputeTrayEndpoint) -> Result<Box<dyn Redfish>, String> {
+        let Credentials::UsernamePassword {
+            ref username,
+            ref password,
+        } = ep.bmc_credentials;
+
+        self.redfish_pool
+            .create_client(
+                &ep.bmc_ip.to_string(),
+                Some(443),
+                RedfishAuth::Direct(username.clone(), password.clone()),
+                map_vendor(ep.vendor),
+            )
+            .await
+            .map_err(|e| format!("failed to create Redfish client: {e}"))
     }
 }
 
@@ -42,17 +97,6 @@ fn map_vendor(vendor: ComputeTrayVendor) -> Option<libredfish::model::service_ro
     }
 }
 
-fn map_power_action(action: PowerAction) -> libredfish::SystemPowerControl {
-    match action {
-        PowerAction::On => libredfish::SystemPowerControl::On,
-        PowerAction::GracefulShutdown => libredfish::SystemPowerControl::GracefulShutdown,
-        PowerAction::ForceOff => libredfish::SystemPowerControl::ForceOff,
-        PowerAction::GracefulRestart => libredfish::SystemPowerControl::GracefulRestart,
-        PowerAction::ForceRestart => libredfish::SystemPowerControl::ForceRestart,
-        PowerAction::AcPowercycle => libredfish::SystemPowerControl::ACPowercycle,
-    }
-}
-
 #[async_trait::async_trait]
 impl crate::compute_tray_manager::ComputeTrayManager for CoreComputeTrayManager {
     fn name(&self) -> &str {
@@ -72,25 +116,9 @@ impl crate::compute_tray_manager::ComputeTrayManager for CoreComputeTrayManager
         let mut results = Vec::with_capacity(endpoints.len());
 
         for ep in endpoints {
-            let Credentials::UsernamePassword {
-                ref username,
-                ref password,
-            } = ep.bmc_credentials;
-
-            let auth = carbide_redfish::libredfish::RedfishAuth::Direct(
-                username.clone(),
-                password.clone(),
-            );
-            let vendor = map_vendor(ep.vendor);
-
             let outcome = async {
-                let client = self
-                    .redfish_pool
-                    .create_client(&ep.bmc_ip.to_string(), Some(443), auth, vendor)
-                    .await
-                    .map_err(|e| format!("failed to create Redfish client: {e}"))?;
-
-                client
+                self.client(ep)
+                    .await?
                     .power(redfish_action)
                     .await
                     .map_err(|e| format!("Redfish power control failed: {e}"))
@@ -109,38 +137,122 @@ impl crate::compute_tray_manager::ComputeTrayManager for CoreComputeTrayManager
 
     async fn update_firmware(
         &self,
-        _endpoints: &[ComputeTrayEndpoint],
-        _target_version: &str,
-        _components: &[ComputeTrayComponent],
+        endpoints: &[ComputeTrayEndpoint],
+        target_version: &str,
+        components: &[ComputeTrayComponent],
         _options: &crate::types::FirmwareUpdateOptions,
     ) -> Result<Vec<ComputeTrayResult>, ComponentManagerError> {
-        Err(ComponentManagerError::Internal(
-            "firmware update is not supported by the core compute tray backend".into(),
-        ))
+        let images = self
+            .firmware_bundles()?
+            .images(target_version, components)
+            .await?;
+        let mut results = Vec::with_capacity(endpoints.len());
+
+        for ep in endpoints {
+            let outcome =
+                async { push_images(self.client(ep).await?.as_ref(), &images).await }.await;
+
+            match &outcome {
+                Ok(task_ids) => {
+                    self.firmware_updates
+                        .track(
+                            ep.bmc_ip,
+                            TrackedFirmwareUpdate {
+                                target_version: target_version.to_owned(),
+                                task_ids: task_ids.clone(),
+                            },
+                        )
+                        .await
+                }
+                Err(_) => self.firmware_updates.forget(&ep.bmc_ip).await,
+            }
+
+            results.push(ComputeTrayResult {
+                bmc_ip: ep.bmc_ip,
+                success: outcome.is_ok(),
+                error: outcome.err(),
+            });
+        }
+
+        Ok(results)
     }
 
     async fn get_firmware_status(
         &self,
-        _endpoints: &[ComputeTrayEndpoint],
+        endpoints: &[ComputeTrayEndpoint],
     ) -> Result<Vec<ComputeTrayFirmwareUpdateStatus>, ComponentManagerError> {
-        Err(ComponentManagerError::Internal(
-            "firmware status is not supported by the core compute tray backend".into(),
-        ))
+        let mut statuses = Vec::with_capacity(endpoints.len());
+
+        for ep in endpoints {
+            let update = match self.firmware_updates.get(&ep.bmc_ip).await {
+                Ok(Some(update)) => update,
+                Ok(None) => {
+                    statuses.push(ComputeTrayFirmwareUpdateStatus {
+                        bmc_ip: ep.bmc_ip,
+                        state: FirmwareState::Unknown,
+                        target_version: String::new(),
+                        error: Some("no firmware update tracked for this compute tray".into()),
+                    });
+                    continue;
+                }
+                Err(e) => {
+                    statuses.push(ComputeTrayFirmwareUpdateStatus {
+                        bmc_ip: ep.bmc_ip,
+                        state: FirmwareState::Unknown,
+                        target_version: String::new(),
+                        error: Some(e),
+                    });
+                    continue;
+                }
+            };
+
+            let (state, error) = match self.client(ep).await {
+                Ok(client) => poll_firmware_update(client.as_ref(), &update).await,
+                Err(e) => (FirmwareState::Unknown, Some(e)),
+            };
+
+            statuses.push(ComputeTrayFirmwareUpdateStatus {
+                bmc_ip: ep.bmc_ip,
+                state,
+                target_version: update.target_version,
+                error,
+            });
+        }
+
+        Ok(statuses)
     }
 
     async fn list_firmware_bundles(&self) -> Result<Vec<String>, ComponentManagerError> {
-        Err(ComponentManagerError::Internal(
-            "firmware bundles listing is not supported by the core compute tray backend".into(),
-        ))
+        self.firmware_bundles()?.versions().await
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
+    use bmc_mock::HardwareType;
     use carbide_test_support::value_scenarios;
     use libredfish::model::service_root::RedfishVendor;
 
     use super::*;
+    use crate::compute_tray_manager::ComputeTrayManager;
+    use crate::test_support::{firmware_bundle_dir, mock_redfish_bmc};
+    use crate::types::FirmwareUpdateOptions;
+
+    const TRAY_MODELS: [HardwareType; 3] = [
+        HardwareType::WiwynnGB200Nvl,
+        HardwareType::LenovoGB300Nvl,
+        HardwareType::NvidiaDgxGb300,
+    ];
+
+    fn endpoint() -> ComputeTrayEndpoint {
+        ComputeTrayEndpoint {
+            vendor: ComputeTrayVendor::Nvidia,
+            bmc_ip: "127.0.0.1".parse().unwrap(),
+            bmc_credentials: Credentials::new("root", "password"),
+        }
+    }
 
     #[test]
     fn compute_tray_vendor_maps_to_redfish_vendor() {
@@ -158,4 +270,93 @@ mod tests {
             }
         );
     }
+
+    #[tokio::test]
+    async fn power_control_reaches_tray_bmcs() {
+        for hw_type in TRAY_MODELS {
+            let bmc = mock_redfish_bmc(hw_type);
+            let manager = CoreComputeTrayManager::new(bmc.redfish_pool.clone());
+
+            let results = manager
+                .power_control(&[endpoint()], PowerAction::ForceRestart)
+                .await
+                .unwrap();
+
+            assert_eq!(results.len(), 1);
+            assert!(results[0].success, "{hw_type:?}: {:?}", results[0].error);
+            assert!(
+                !bmc.callbacks.power_commands.lock().unwrap().is_empty(),
+                "{hw_type:?} did not receive a power command"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn firmware_update_pushes_bundle_and_follows_task() {
+        for hw_type in TRAY_MODELS {
+            let bmc = mock_redfish_bmc(hw_type);
+            let (_dir, bundles) = firmware_bundle_dir("1.2.3", &["bmc.fwpkg"]).await;
+            let manager = CoreComputeTrayManager::new(bmc.redfish_pool.clone())
+                .with_firmware_bundles(bundles);
+
+            assert_eq!(
+                manager.list_firmware_bundles().await.unwrap(),
+                vec!["1.2.3"]
+            );
+
+            let results = manager
+                .update_firmware(
+                    &[endpoint()],
+                    "1.2.3",
+                    &[ComputeTrayComponent::Bmc],
+                    &FirmwareUpdateOptions::default(),
+                )
+                .await
+                .unwrap();
+            assert!(results[0].success, "{hw_type:?}: {:?}", results[0].error);
+
+            // bmc-mock completes update tasks after a few seconds.
+            let mut status = None;
+            for _ in 0..40 {
+                let statuses = manager.get_firmware_status(&[endpoint()]).await.unwrap();
+                assert_eq!(statuses[0].target_version, "1.2.3");
+                if statuses[0].state == FirmwareState::Completed {
+                    status = statuses.into_iter().next();
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(250)).await;
+            }
+            let status = status.unwrap_or_else(|| panic!("{hw_type:?} update never completed"));
+            assert_eq!(status.error, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn firmware_status_without_tracked_update_is_unknown() {
+        let bmc = mock_redfish_bmc(HardwareType::WiwynnGB200Nvl);
+        let manager = CoreComputeTrayManager::new(bmc.redfish_pool.clone());
+
+        let statuses = manager.get_firmware_status(&[endpoint()]).await.unwrap();
+
+        assert_eq!(statuses[0].state, FirmwareState::Unknown);
+        assert!(statuses[0].error.is_some());
+    }
+
+    #[tokio::test]
+    async fn firmware_update_without_bundles_is_unsupported() {
+        let bmc = mock_redfish_bmc(HardwareType::WiwynnGB200Nvl);
+        let manager = CoreComputeTrayManager::new(bmc.redfish_pool.clone());
+
+        let err = manager
+            .update_firmware(
+                &[endpoint()],
+                "1.2.3",
+                &[ComputeTrayComponent::Bmc],
+                &FirmwareUpdateOptions::default(),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ComponentManagerError::Unsupported(_)));
+    }
 }
@@ -0,0 +1,446 @@
+// SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use carbide_redfish::libredfish::{RedfishAuth, RedfishClientPool};
+use carbide_secrets::credentials::Credentials;
+use libredfish::Redfish;
+use mac_address::MacAddress;
+use model::component_manager::{FirmwareState, PowerAction, PowerShelfComponent};
+use sqlx::PgPool;
+
+use crate::core_redfish::{
+    FirmwareBundles, FirmwareUpdateTracker, TrackedFirmwareUpdate, firmware_inventory_versions,
+    map_power_action, poll_firmware_update, push_images,
+};
+use crate::error::ComponentManagerError;
+use crate::power_shelf_manager::{
+    PowerShelfComponentResult, PowerShelfEndpoint, PowerShelfFirmwareUpdateStatus,
+    PowerShelfFirmwareVersions, PowerShelfManager, PowerShelfPowerStateResult,
+};
+
+/// Power shelf manager backend that uses NICo-core's Redfish stack to talk to
+/// PMCs directly, without PSM or RMS in between.
+///
+/// The PMC vendor is probed from the BMC rather than taken from the endpoint,
+/// since Delta shelves only identify themselves through their chassis.
+pub struct CorePowerShelfManager {
+    redfish_pool: Arc<dyn RedfishClientPool>,
+    firmware_bundles: Option<FirmwareBundles>,
+    firmware_updates: FirmwareUpdateTracker<MacAddress>,
+}
+
+impl std::fmt::Debug for CorePowerShelfManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CorePowerShelfManager")
+            .field("firmware_bundles", &self.firmware_bundles)
+            .finish()
+    }
+}
+
+impl CorePowerShelfManager {
+    pub fn new(redfish_pool: Arc<dyn RedfishClientPool>) -> Self {
+        Self {
+            redfish_pool,
+            firmware_bundles: None,
+            firmware_updates: FirmwareUpdateTracker::new("power_shelf"),
+        }
+    }
+
+    /// Stores firmware updates in `database`, so that their status can still
+    /// be followed after a restart.
+    pub fn with_database(mut self, database: PgPool) -> Self {
+        self.firmware_updates = self.firmware_updates.with_database(database);
+        self
+    }
+
+    /// Enables firmware updates from `firmware_bundles`.
+    pub fn with_firmware_bundles(mut self, firmware_bundles: FirmwareBundles) -> Self {
+        self.firmware_bundles = Some(firmware_bundles);
+        self
+    }
+
+    fn firmware_bundles(&self) -> Result<&FirmwareBundles, ComponentManagerError> {
+        self.firmware_bundles.as_ref().ok_or_else(|| {
+            ComponentManagerError::Unsupported(
+                "the core power shelf backend has no firmware_directory configured".into(),
+            )
+        })
+    }
+
+    async fn client(&self, ep: &PowerShelfEndpoint) -> Result<Box<dyn Redfish>, String> {
+        let host = ep.pmc_ip.to_string();
+        let vendor = self
+            .redfish_pool
+            .probe_bmc_vendor(&host, Some(443), ep.pmc_credentials.clone())
+            .await
+            .map_err(|e| format!("failed to identify PMC vendor: {e}"))?;
+
+        let Credentials::UsernamePassword {
+            ref username,
+            ref password,
+        } = ep.pmc_credentials;
+
+        self.redfish_pool
+            .create_client(
+                &host,
+                Some(443),
+                RedfishAuth::Direct(username.clone(), password.clone()),
+                Some(vendor),
+            )
+            .await
+            .map_err(|e| format!("failed to create Redfish client: {e}"))
+    }
+}
+
+#[async_trait::async_trait]
+impl PowerShelfManager for CorePowerShelfManager {
+    fn name(&self) -> &str {
+        "core"
+    }
+
+    async fn power_control(
+        &self,
+        endpoints: &[PowerShelfEndpoint],
+        action: PowerAction,
+    ) -> Result<Vec<PowerShelfComponentResult>, ComponentManagerError> {
+        let redfish_action = map_power_action(action);
+        let mut results = Vec::with_capacity(endpoints.len());
+
+        for ep in endpoints {
+            let outcome = async {
+                self.client(ep)
+                    .await?
+                    .power(redfish_action)
+                    .await
+                    .map_err(|e| format!("Redfish power control failed: {e}"))
+            }
+            .await;
+
+            results.push(PowerShelfComponentResult {
+                pmc_mac: ep.pmc_mac,
+                success: outcome.is_ok(),
+                error: outcome.err(),
+            });
+        }
+
+        Ok(results)
+    }
+
+    async fn update_firmware(
+        &self,
+        endpoints: &[PowerShelfEndpoint],
+        target_version: &str,
+        components: &[PowerShelfComponent],
+        _options: &crate::types::FirmwareUpdateOptions,
+    ) -> Result<Vec<PowerShelfComponentResult>, ComponentManagerError> {
+        let images = self
+            .firmware_bundles()?
+            .images(target_version, components)
+            .await?;
+        let mut results = Vec::with_capacity(endpoints.len());
+
+        for ep in endpoints {
+            let outcome =
+                async { push_images(self.client(ep).await?.as_ref(), &images).await }.await;
+
+            match &outcome {
+                Ok(task_ids) => {
+                    self.firmware_updates
+                        .track(
+                            ep.pmc_mac,
+                            TrackedFirmwareUpdate {
+                                target_version: target_version.to_owned(),
+                                task_ids: task_ids.clone(),
+                            },
+                        )
+                        .await
+                }
+                Err(_) => self.firmware_updates.forget(&ep.pmc_mac).await,
+            }
+
+            results.push(PowerShelfComponentResult {
+                pmc_mac: ep.pmc_mac,
+                success: outcome.is_ok(),
+                error: outcome.err(),
+            });
+        }
+
+        Ok(results)
+    }
+
+    async fn get_firmware_status(
+        &self,
+        endpoints: &[PowerShelfEndpoint],
+    ) -> Result<Vec<PowerShelfFirmwareUpdateStatus>, ComponentManagerError> {
+        let mut statuses = Vec::with_capacity(endpoints.len());
+
+        for ep in endpoints {
+            let update = match self.firmware_updates.get(&ep.pmc_mac).await {
+                Ok(Some(update)) => update,
+                Ok(None) => {
+                    statuses.push(PowerShelfFirmwareUpdateStatus {
+                        pmc_mac: ep.pmc_mac,
+                        state: FirmwareState::Unknown,
+                        target_version: String::new(),
+                        error: Some("no firmware update tracked for this power shelf".into()),
+                    });
+                    continue;
+                }
+                Err(e) => {
+                    statuses.push(PowerShelfFirmwareUpdateStatus {
+                        pmc_mac: ep.pmc_mac,
+                        state: FirmwareState::Unknown,
+                        target_version: String::new(),
+                        error: Some(e),
+                    });
+                    continue;
+                }
+            };
+
+            let (state, error) = match self.client(ep).await {
+                Ok(client) => poll_firmware_update(client.as_ref(), &update).await,
+                Err(e) => (FirmwareState::Unknown, Some(e)),
+            };
+
+            statuses.push(PowerShelfFirmwareUpdateStatus {
+                pmc_mac: ep.pmc_mac,
+                state,
+                target_version: update.target_version,
+                error,
+            });
+        }
+
+        Ok(statuses)
+    }
+
+    async fn list_firmware(
+        &self,
+        endpoints: &[PowerShelfEndpoint],
+    ) -> Result<Vec<PowerShelfFirmwareVersions>, ComponentManagerError> {
+        let mut results = Vec::with_capacity(endpoints.len());
+
+        for ep in endpoints {
+            let outcome = async {
+                firmware_inventory_versions(self.client(ep).await?.as_ref())
+                    .await
+                    .map_err(|e| format!("Redfish firmware inventory query failed: {e}"))
+            }
+            .await;
+
+            results.push(match outcome {
+                Ok(versions) => PowerShelfFirmwareVersions {
+                    pmc_mac: ep.pmc_mac,
+                    versions,
+                    error: None,
+                },
+                Err(e) => PowerShelfFirmwareVersions {
+                    pmc_mac: ep.pmc_mac,
+                    versions: vec![],
+                    error: Some(e),
+                },
+            });
+        }
+
+        Ok(results)
+    }
+
+    async fn get_power_state(
+        &self,
+        endpoints: &[PowerShelfEndpoint],
+    ) -> Result<Vec<PowerShelfPowerStateResult>, ComponentManagerError> {
+        let mut results = Vec::with_capacity(endpoints.len());
+
+        for ep in endpoints {
+            let outcome = async {
+                self.client(ep)
+                    .await?
+                    .get_power_state()
+                    .await
+                    .map_err(|e| format!("Redfish power state query failed: {e}"))
+            }
+            .await;
+
+            results.push(match outcome {
+                Ok(power_state) => PowerShelfPowerStateResult {
+                    pmc_mac: ep.pmc_mac,
+                    power_state: Some(power_state.to_string().to_lowercase()),
+                    error: None,
+                },
+                Err(e) => PowerShelfPowerStateResult {
+                    pmc_mac: ep.pmc_mac,
+                    power_state: None,
+                    error: Some(e),
+                },
+            });
+        }
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bmc_mock::HardwareType;
+
+    use super::*;
+    use crate::power_shelf_manager::PowerShelfVendor;
+    use crate::test_support::{PS_MAC_1, firmware_bundle_dir, mock_redfish_bmc};
+    use crate::types::FirmwareUpdateOptions;
+
+    fn endpoint() -> PowerShelfEndpoint {
+        PowerShelfEndpoint {
+            pmc_ip: "127.0.0.1".parse().unwrap(),
+            pmc_mac: PS_MAC_1.parse().unwrap(),
+            // Callers always pass the default vendor; the backend must not
+            // rely on it.
+            pmc_vendor: PowerShelfVendor::DEFAULT,
+            pmc_credentials: Credentials::new("root", "password"),
+        }
+    }
+
+    #[tokio::test]
+    async fn list_firmware_reports_pmc_inventory() {
+        for (hw_type, version) in [
+            (HardwareType::LiteOnPowerShelf, "r1.3.9"),
+            (HardwareType::DeltaPowerShelf, "01.04.01.04"),
+        ] {
+            let bmc = mock_redfish_bmc(hw_type);
+            let manager = CorePowerShelfManager::new(bmc.redfish_pool.clone());
+
+            let results = manager.list_firmware(&[endpoint()]).await.unwrap();
+
+            assert_eq!(results[0].pmc_mac, endpoint().pmc_mac);
+            assert_eq!(results[0].error, None, "{hw_type:?}");
+            assert!(
+                results[0].versions.iter().any(|v| v == version),
+                "{hw_type:?}: {:?}",
+                results[0].versions
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn power_control_reaches_liteon_pmc() {
+        let bmc = mock_redfish_bmc(HardwareType::LiteOnPowerShelf);
+        let manager = CorePowerShelfManager::new(bmc.redfish_pool.clone());
+
+        let results = manager
+            .power_control(&[endpoint()], PowerAction::ForceRestart)
+            .await
+            .unwrap();
+
+        assert!(results[0].success, "{:?}", results[0].error);
+        assert!(!bmc.callbacks.power_commands.lock().unwrap().is_empty());
+    }
+
+    // Delta shelves expose no ComputerSystem, so there may be nothing to
+    // reset; whatever happens is reported against the shelf rather than
+    // failing the whole request.
+    #[tokio::test]
+    async fn power_control_reports_delta_outcome_per_shelf() {
+        let bmc = mock_redfish_bmc(HardwareType::DeltaPowerShelf);
+        let manager = CorePowerShelfManager::new(bmc.redfish_pool.clone());
+
+        let results = manager
+            .power_control(&[endpoint()], PowerAction::ForceRestart)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].pmc_mac, endpoint().pmc_mac);
+        assert_eq!(results[0].success, results[0].error.is_none());
+    }
+
+    #[tokio::test]
+    async fn firmware_update_pushes_bundle_and_follows_task() {
+        for hw_type in [
+            HardwareType::LiteOnPowerShelf,
+            HardwareType::DeltaPowerShelf,
+        ] {
+            let bmc = mock_redfish_bmc(hw_type);
+            let (_dir, bundles) = firmware_bundle_dir("2.0.0", &["pmc.bin"]).await;
+            let manager =
+                CorePowerShelfManager::new(bmc.redfish_pool.clone()).with_firmware_bundles(bundles);
+
+            let results = manager
+                .update_firmware(
+                    &[endpoint()],
+                    "2.0.0",
+                    &[PowerShelfComponent::Pmc],
+                    &FirmwareUpdateOptions::default(),
+                )
+                .await
+                .unwrap();
+            assert!(results[0].success, "{hw_type:?}: {:?}", results[0].error);
+
+            // bmc-mock completes update tasks after a few seconds.
+            let mut completed = false;
+            for _ in 0..40 {
+                let statuses = manager.get_firmware_status(&[endpoint()]).await.unwrap();
+                assert_eq!(statuses[0].target_version, "2.0.0");
+                if statuses[0].state == FirmwareState::Completed {
+                    completed = true;
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(250)).await;
+            }
+            assert!(completed, "{hw_type:?} update never completed");
+        }
+    }
+
+    #[carbide_macros::sqlx_test]
+    async fn firmware_update_status_survives_restart(pool: sqlx::PgPool) {
+        let bmc = mock_redfish_bmc(HardwareType::LiteOnPowerShelf);
+        let (_dir, bundles) = firmware_bundle_dir("2.0.0", &["pmc.bin"]).await;
+        let manager = CorePowerShelfManager::new(bmc.redfish_pool.clone())
+            .with_firmware_bundles(bundles)
+            .with_database(pool.clone());
+
+        let results = manager
+            .update_firmware(
+                &[endpoint()],
+                "2.0.0",
+                &[PowerShelfComponent::Pmc],
+                &FirmwareUpdateOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert!(results[0].success, "{:?}", results[0].error);
+
+        // A new manager knows nothing in memory but finds the stored tasks.
+        let restarted =
+            CorePowerShelfManager::new(bmc.redfish_pool.clone()).with_database(pool.clone());
+        let statuses = restarted.get_firmware_status(&[endpoint()]).await.unwrap();
+        assert_eq!(statuses[0].target_version, "2.0.0");
+        assert_ne!(statuses[0].state, FirmwareState::Unknown, "{statuses:?}");
+
+        // Without a database a restart loses the update.
+        let forgetful = CorePowerShelfManager::new(bmc.redfish_pool.clone());
+        let statuses = forgetful.get_firmware_status(&[endpoint()]).await.unwrap();
+        assert_eq!(statuses[0].state, FirmwareState::Unknown);
+    }
+
+    #[tokio::test]
+    async fn firmware_update_rejects_missing_component_image() {
+        let bmc = mock_redfish_bmc(HardwareType::LiteOnPowerShelf);
+        let (_dir, bundles) = firmware_bundle_dir("2.0.0", &["pmc.bin"]).await;
+        let manager =
+            CorePowerShelfManager::new(bmc.redfish_pool.clone()).with_firmware_bundles(bundles);
+
+        let err = manager
+            .update_firmware(
+                &[endpoint()],
+                "2.0.0",
+                &[PowerShelfComponent::Psu],
+                &FirmwareUpdateOptions::default(),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ComponentManagerError::NotFound(_)));
+    }
+}
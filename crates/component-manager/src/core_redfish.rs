@@ -0,0 +1,457 @@
+// SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Redfish plumbing shared by the `core` power shelf and compute tray
+//! backends: power actions, firmware bundles on local disk, `UpdateService`
+//! pushes and task polling.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use libredfish::model::task::TaskState;
+use libredfish::model::update_service::ComponentType;
+use libredfish::{Redfish, RedfishError};
+pub(crate) use model::component_manager::TrackedFirmwareUpdate;
+use model::component_manager::{FirmwareState, PowerAction};
+use sqlx::PgPool;
+
+use crate::component_common::aggregate_firmware_job_states;
+use crate::error::ComponentManagerError;
+
+/// How long a BMC gets to accept a multipart firmware push.
+const FIRMWARE_UPLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+
+pub(crate) fn map_power_action(action: PowerAction) -> libredfish::SystemPowerControl {
+    match action {
+        PowerAction::On => libredfish::SystemPowerControl::On,
+        PowerAction::GracefulShutdown => libredfish::SystemPowerControl::GracefulShutdown,
+        PowerAction::ForceOff => libredfish::SystemPowerControl::ForceOff,
+        PowerAction::GracefulRestart => libredfish::SystemPowerControl::GracefulRestart,
+        PowerAction::ForceRestart => libredfish::SystemPowerControl::ForceRestart,
+        PowerAction::AcPowercycle => libredfish::SystemPowerControl::ACPowercycle,
+    }
+}
+
+/// Firmware bundles served from local disk, one subdirectory per version:
+/// `<directory>/<version>/<image>`.
+///
+/// An image whose file stem names a component (`bmc.fwpkg`, `pmc.bin`, ...)
+/// is pushed when that component is requested. When no components are
+/// requested, every image in the bundle is pushed in file-name order.
+#[derive(Debug, Clone)]
+pub struct FirmwareBundles {
+    directory: PathBuf,
+}
+
+impl FirmwareBundles {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// Versions with a bundle directory, sorted.
+    pub async fn versions(&self) -> Result<Vec<String>, ComponentManagerError> {
+        let mut entries = tokio::fs::read_dir(&self.directory).await.map_err(|e| {
+            ComponentManagerError::Unavailable(format!(
+                "failed to read firmware directory {}: {e}",
+                self.directory.display()
+            ))
+        })?;
+
+        let mut versions = Vec::new();
+        while let Some(entry) = entries.next_entry().await.map_err(|e| {
+            ComponentManagerError::Unavailable(format!(
+                "failed to read firmware directory {}: {e}",
+                self.directory.display()
+            ))
+        })? {
+            if entry.file_type().await.is_ok_and(|t| t.is_dir())
+                && let Some(version) = entry.file_name().to_str()
+            {
+                versions.push(version.to_owned());
+            }
+        }
+        versions.sort();
+        Ok(versions)
+    }
+
+    /// Images to push for `version`, restricted to `components` when any are
+    /// given. Fails before anything is pushed if the bundle is missing or
+    /// lacks an image for a requested component.
+    pub async fn images(
+        &self,
+        version: &str,
+        components: &[impl ToString],
+    ) -> Result<Vec<PathBuf>, ComponentManagerError> {
+        if version.is_empty() || version == "." || version == ".." || version.contains(['/', '\\'])
+        {
+            return Err(ComponentManagerError::InvalidArgument(format!(
+                "invalid firmware version {version:?}"
+            )));
+        }
+
+        let bundle = self.directory.join(version);
+        let mut entries = tokio::fs::read_dir(&bundle).await.map_err(|e| {
+            ComponentManagerError::NotFound(format!("firmware bundle {version}: {e}"))
+        })?;
+
+        let mut images = Vec::new();
+        while let Some(entry) = entries.next_entry().await.map_err(|e| {
+            ComponentManagerError::Unavailable(format!(
+                "failed to read firmware bundle {version}: {e}"
+            ))
+        })? {
+            if entry.file_type().await.is_ok_and(|t| t.is_file()) {
+                images.push(entry.path());
+            }
+        }
+        images.sort();
+
+        if components.is_empty() {
+            if images.is_empty() {
+                return Err(ComponentManagerError::NotFound(format!(
+                    "firmware bundle {version} contains no images"
+                )));
+            }
+            return Ok(images);
+        }
+
+        components
+            .iter()
+            .map(|component| {
+                let component = component.to_string();
+                images
+                    .iter()
+                    .find(|image| {
+                        image_stem(image).is_some_and(|stem| stem.eq_ignore_ascii_case(&component))
+                    })
+                    .cloned()
+                    .ok_or_else(|| {
+                        ComponentManagerError::NotFound(format!(
+                            "firmware bundle {version} has no {component} image"
+                        ))
+                    })
+            })
+            .collect()
+    }
+}
+
+fn image_stem(image: &Path) -> Option<&str> {
+    image.file_stem().and_then(|stem| stem.to_str())
+}
+
+/// The `UpdateService` component an image targets, derived from its name.
+fn image_component_type(image: &Path) -> ComponentType {
+    match image_stem(image).map(str::to_ascii_lowercase).as_deref() {
+        Some("bmc") => ComponentType::BMC,
+        Some("bios" | "uefi") => ComponentType::UEFI,
+        Some("cpld") => ComponentType::CPLDMB,
+        _ => ComponentType::Unknown,
+    }
+}
+
+/// Push `images` one after another through the BMC's `UpdateService` and
+/// return the ids of the tasks tracking them.
+///
+/// Each image goes to the multipart push URI when the BMC supports it, and
+/// falls back to a plain HTTP push otherwise.
+pub(crate) async fn push_images(
+    client: &dyn Redfish,
+    images: &[PathBuf],
+) -> Result<Vec<String>, String> {
+    let mut task_ids = Vec::with_capacity(images.len());
+    for image in images {
+        let task_id = match client
+            .update_firmware_multipart(
+                image,
+                true,
+                FIRMWARE_UPLOAD_TIMEOUT,
+                image_component_type(image),
+            )
+            .await
+        {
+            Ok(task_id) => task_id,
+            Err(RedfishError::NotSupported(_)) => {
+                let file = tokio::fs::File::open(image)
+                    .await
+                    .map_err(|e| format!("failed to open {}: {e}", image.display()))?;
+                client
+                    .update_firmware(file)
+                    .await
+                    .map_err(|e| {
+                        format!("Redfish firmware push of {} failed: {e}", image.display())
+                    })?
+                    .id
+            }
+            Err(e) => {
+                return Err(format!(
+                    "Redfish firmware push of {} failed: {e}",
+                    image.display()
+                ));
+            }
+        };
+        task_ids.push(task_id);
+    }
+    Ok(task_ids)
+}
+
+pub(crate) fn map_task_state(state: Option<&TaskState>) -> FirmwareState {
+    match state {
+        Some(TaskState::New | TaskState::Pending) => FirmwareState::Queued,
+        Some(
+            TaskState::Starting
+            | TaskState::Running
+            | TaskState::Suspended
+            | TaskState::Stopping
+            | TaskState::Service,
+        ) => FirmwareState::InProgress,
+        Some(TaskState::Completed) => FirmwareState::Completed,
+        Some(TaskState::Exception | TaskState::Interrupted | TaskState::Killed) => {
+            FirmwareState::Failed
+        }
+        Some(TaskState::Cancelling | TaskState::Cancelled) => FirmwareState::Cancelled,
+        None => FirmwareState::Unknown,
+    }
+}
+
+/// Firmware updates pushed by a `core` backend, keyed by device, so status
+/// polls know which BMC tasks to follow. With a database the updates are
+/// also stored there, so that they can still be followed after a restart;
+/// failing to store one is logged and leaves it tracked in memory only.
+#[derive(Debug)]
+pub(crate) struct FirmwareUpdateTracker<K> {
+    device_kind: &'static str,
+    database: Option<PgPool>,
+    updates: Mutex<HashMap<K, TrackedFirmwareUpdate>>,
+}
+
+impl<K> FirmwareUpdateTracker<K> {
+    /// `device_kind` tells devices of different backends apart in the
+    /// database.
+    pub(crate) fn new(device_kind: &'static str) -> Self {
+        Self {
+            device_kind,
+            database: None,
+            updates: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn with_database(mut self, database: PgPool) -> Self {
+        self.database = Some(database);
+        self
+    }
+}
+
+impl<K: Eq + Hash + Clone + Display> FirmwareUpdateTracker<K> {
+    pub(crate) async fn track(&self, device: K, update: TrackedFirmwareUpdate) {
+        if let Some(database) = &self.database {
+            let device_id = device.to_string();
+            let result = async {
+                let mut txn = database.begin().await.map_err(|e| e.to_string())?;
+                db::component_firmware_update::upsert(
+                    &mut txn,
+                    self.device_kind,
+                    &device_id,
+                    &update,
+                )
+                .await
+                .map_err(|e| e.to_string())?;
+                txn.commit().await.map_err(|e| e.to_string())
+            }
+            .await;
+            if let Err(error) = result {
+                tracing::warn!(
+                    device_kind = self.device_kind,
+                    device_id,
+                    error,
+                    "Failed to store firmware update; it is lost on restart"
+                );
+            }
+        }
+        self.updates.lock().unwrap().insert(device, update);
+    }
+
+    pub(crate) async fn forget(&self, device: &K) {
+        self.updates.lock().unwrap().remove(device);
+        if let Some(database) = &self.database {
+            let device_id = device.to_string();
+            let result = async {
+                let mut txn = database.begin().await.map_err(|e| e.to_string())?;
+                db::component_firmware_update::delete(&mut txn, self.device_kind, &device_id)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                txn.commit().await.map_err(|e| e.to_string())
+            }
+            .await;
+            if let Err(error) = result {
+                tracing::warn!(
+                    device_kind = self.device_kind,
+                    device_id,
+                    error,
+                    "Failed to remove stored firmware update"
+                );
+            }
+        }
+    }
+
+    /// Returns the update tracked for `device`, falling back to the database
+    /// for updates pushed before a restart.
+    pub(crate) async fn get(&self, device: &K) -> Result<Option<TrackedFirmwareUpdate>, String> {
+        if let Some(update) = self.updates.lock().unwrap().get(device).cloned() {
+            return Ok(Some(update));
+        }
+        let Some(database) = &self.database else {
+            return Ok(None);
+        };
+
+        let update =
+            db::component_firmware_update::find(database, self.device_kind, &device.to_string())
+                .await
+                .map_err(|e| format!("failed to load tracked firmware update: {e}"))?;
+        if let Some(update) = &update {
+            self.updates
+                .lock()
+                .unwrap()
+                .insert(device.clone(), update.clone());
+        }
+        Ok(update)
+    }
+}
+
+/// Poll every task of `update` and fold them into one state. Tasks that end
+/// badly, or cannot be read, contribute their last message to the error.
+pub(crate) async fn poll_firmware_update(
+    client: &dyn Redfish,
+    update: &TrackedFirmwareUpdate,
+) -> (FirmwareState, Option<String>) {
+    let mut states = Vec::with_capacity(update.task_ids.len());
+    let mut errors = Vec::new();
+
+    for task_id in &update.task_ids {
+        match client.get_task(task_id).await {
+            Ok(task) => {
+                let state = map_task_state(task.task_state.as_ref());
+                if matches!(state, FirmwareState::Failed | FirmwareState::Cancelled) {
+                    let message = task
+                        .messages
+                        .last()
+                        .map(|m| m.message.clone())
+                        .unwrap_or_else(|| format!("{state:?}"));
+                    errors.push(format!("task {task_id}: {message}"));
+                }
+                states.push(state);
+            }
+            Err(e) => {
+                states.push(FirmwareState::Unknown);
+                errors.push(format!("failed to read task {task_id}: {e}"));
+            }
+        }
+    }
+
+    (
+        aggregate_firmware_job_states(&states),
+        (!errors.is_empty()).then(|| errors.join("; ")),
+    )
+}
+
+/// Versions reported by the BMC's firmware inventory, in inventory order.
+/// Entries without a version are skipped.
+pub(crate) async fn firmware_inventory_versions(
+    client: &dyn Redfish,
+) -> Result<Vec<String>, RedfishError> {
+    let mut versions = Vec::new();
+    for id in client.get_software_inventories().await? {
+        if let Some(version) = client.get_firmware(&id).await?.version {
+            versions.push(version);
+        }
+    }
+    Ok(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::value_scenarios;
+    use model::component_manager::{ComputeTrayComponent, PowerShelfComponent};
+
+    use super::*;
+    use crate::test_support::firmware_bundle_dir;
+
+    #[test]
+    fn task_state_maps_to_firmware_state() {
+        value_scenarios!(run = |state| map_task_state(state);
+            "not started" {
+                Some(&TaskState::New) => FirmwareState::Queued,
+                Some(&TaskState::Pending) => FirmwareState::Queued,
+            }
+
+            "running" {
+                Some(&TaskState::Starting) => FirmwareState::InProgress,
+                Some(&TaskState::Running) => FirmwareState::InProgress,
+                Some(&TaskState::Stopping) => FirmwareState::InProgress,
+            }
+
+            "terminal" {
+                Some(&TaskState::Completed) => FirmwareState::Completed,
+                Some(&TaskState::Exception) => FirmwareState::Failed,
+                Some(&TaskState::Killed) => FirmwareState::Failed,
+                Some(&TaskState::Cancelled) => FirmwareState::Cancelled,
+            }
+
+            "missing" {
+                None => FirmwareState::Unknown,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn bundle_images_follow_requested_components() {
+        let (_dir, bundles) =
+            firmware_bundle_dir("1.2.3", &["bmc.fwpkg", "bios.fwpkg", "cpld.bin"]).await;
+
+        assert_eq!(bundles.versions().await.unwrap(), vec!["1.2.3"]);
+
+        let images = bundles
+            .images(
+                "1.2.3",
+                &[ComputeTrayComponent::Bios, ComputeTrayComponent::Bmc],
+            )
+            .await
+            .unwrap();
+        let names: Vec<_> = images.iter().filter_map(|p| image_stem(p)).collect();
+        assert_eq!(names, vec!["bios", "bmc"]);
+
+        let all = bundles
+            .images("1.2.3", &[] as &[ComputeTrayComponent])
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn bundle_images_reject_missing_components_and_bad_versions() {
+        let (_dir, bundles) = firmware_bundle_dir("1.2.3", &["pmc.bin"]).await;
+
+        let err = bundles
+            .images("1.2.3", &[PowerShelfComponent::Psu])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ComponentManagerError::NotFound(msg) if msg.contains("PSU")));
+
+        let err = bundles
+            .images("9.9.9", &[PowerShelfComponent::Pmc])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ComponentManagerError::NotFound(_)));
+
+        let err = bundles
+            .images("../1.2.3", &[PowerShelfComponent::Pmc])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ComponentManagerError::InvalidArgument(_)));
+    }
+}
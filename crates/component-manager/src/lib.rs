@@ -6,6 +6,8 @@ pub mod component_manager;
 pub mod compute_tray_manager;
 pub mod config;
 pub mod core_compute_manager;
+pub mod core_power_shelf_manager;
+pub mod core_redfish;
 pub mod error;
 pub mod mock;
 pub mod nsm;
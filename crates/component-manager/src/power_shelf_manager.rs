@@ -16,6 +16,7 @@ use crate::types::FirmwareUpdateOptions;
 #[serde(rename_all = "lowercase")]
 pub enum Backend {
     Psm,
+    Core,
     #[default]
     Rms,
     Mock,
@@ -25,6 +26,7 @@ impl std::fmt::Display for Backend {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Psm => f.write_str("psm"),
+            Self::Core => f.write_str("core"),
             Self::Rms => f.write_str("rms"),
             Self::Mock => f.write_str("mock"),
         }
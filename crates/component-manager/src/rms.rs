@@ -41,6 +41,7 @@ use serde::Deserialize;
 use sqlx::PgPool;
 use tracing::instrument;
 
+use crate::component_common::aggregate_firmware_job_states;
 use crate::compute_tray_manager::{
     Backend as ComputeTrayBackend, ComputeTrayEndpoint, ComputeTrayFirmwareUpdateStatus,
     ComputeTrayManager, ComputeTrayResult,
@@ -434,38 +435,6 @@ fn map_rms_switch_system_image_job_state(state: &str) -> FirmwareState {
     }
 }
 
-fn aggregate_firmware_job_states(states: &[FirmwareState]) -> FirmwareState {
-    if states.is_empty() {
-        return FirmwareState::Unknown;
-    }
-    if states.contains(&FirmwareState::Failed) {
-        return FirmwareState::Failed;
-    }
-    if states.contains(&FirmwareState::Cancelled) {
-        return FirmwareState::Cancelled;
-    }
-    if states.contains(&FirmwareState::InProgress) {
-        return FirmwareState::InProgress;
-    }
-    if states.contains(&FirmwareState::Verifying) {
-        return FirmwareState::Verifying;
-    }
-    if states.contains(&FirmwareState::Queued) {
-        return FirmwareState::Queued;
-    }
-    if states.contains(&FirmwareState::Unknown) {
-        return FirmwareState::Unknown;
-    }
-    if states
-        .iter()
-        .all(|state| *state == FirmwareState::Completed)
-    {
-        FirmwareState::Completed
-    } else {
-        FirmwareState::Unknown
-    }
-}
-
 /// Default BMC HTTPS port used when populating `rms::Endpoint` for power
 /// shelves. Mirrors the value used by `crate::power_shelf_controller::maintenance`.
 const POWER_SHELF_BMC_PORT: u32 = 443;
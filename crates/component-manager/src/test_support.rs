@@ -1,7 +1,17 @@
 // SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::net::IpAddr;
+use std::net::{IpAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+
+use arc_swap::ArcSwap;
+use bmc_mock::{
+    Callbacks, CombinedServer, HardwareType, ListenerOrAddress, MockPowerState,
+    SetSystemPowerError, SystemPowerControl,
+};
+use carbide_redfish::libredfish::RedfishClientPool;
+use carbide_secrets::test_support::credentials::TestCredentialManager;
+use carbide_utils::HostPortPair;
 
 use carbide_uuid::machine::{MachineId, MachineIdSource, MachineInterfaceId, MachineType};
 use carbide_uuid::network::NetworkSegmentId;
@@ -19,6 +29,8 @@ use model::rack::{RackConfig, RackState};
 use model::switch::{NewSwitch, SwitchConfig};
 use sqlx::PgPool;
 
+use crate::core_redfish::FirmwareBundles;
+
 pub(crate) const PS_MAC_1: &str = "AA:BB:CC:DD:EE:01";
 pub(crate) const PS_MAC_2: &str = "AA:BB:CC:DD:EE:02";
 pub(crate) const SW_MAC_1: &str = "AA:BB:CC:DD:FF:01";
@@ -308,3 +320,80 @@ pub(crate) async fn seed_machine(
 
     machine_id
 }
+
+/// Records the power commands a bmc-mock host receives. The host always
+/// reports itself as powered on.
+#[derive(Debug, Default)]
+pub(crate) struct RecordingCallbacks {
+    pub(crate) power_commands: Mutex<Vec<SystemPowerControl>>,
+}
+
+impl Callbacks for RecordingCallbacks {
+    fn get_power_state(&self) -> MockPowerState {
+        MockPowerState::On
+    }
+
+    fn send_power_command(
+        &self,
+        reset_type: SystemPowerControl,
+    ) -> Result<(), SetSystemPowerError> {
+        self.power_commands.lock().unwrap().push(reset_type);
+        Ok(())
+    }
+
+    fn state_refresh_indication(&self) {}
+}
+
+/// A bmc-mock host served over HTTPS on a local port, together with a
+/// libredfish client pool that sends every BMC address to that port.
+pub(crate) struct MockRedfishBmc {
+    pub(crate) redfish_pool: Arc<dyn RedfishClientPool>,
+    pub(crate) callbacks: Arc<RecordingCallbacks>,
+    _server: CombinedServer,
+}
+
+pub(crate) fn mock_redfish_bmc(hw_type: HardwareType) -> MockRedfishBmc {
+    let callbacks = Arc::new(RecordingCallbacks::default());
+    let (router, _state) = bmc_mock::test_support::host_router(hw_type, callbacks.clone());
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = CombinedServer::run_router(
+        "component-manager-core-test",
+        router,
+        Some(ListenerOrAddress::Listener(listener)),
+        bmc_mock::tls::server_config(None::<&str>).unwrap(),
+    );
+
+    let redfish_pool = carbide_redfish::libredfish::new_pool(
+        Arc::new(TestCredentialManager::default()),
+        libredfish::RedfishClientPool::builder()
+            .danger_accept_invalid_certs()
+            .build()
+            .unwrap(),
+        Arc::new(ArcSwap::new(Arc::new(Some(HostPortPair::PortOnly(port))))),
+    );
+
+    MockRedfishBmc {
+        redfish_pool,
+        callbacks,
+        _server: server,
+    }
+}
+
+/// Creates a firmware bundle directory holding `version` with one small
+/// image per entry of `images`.
+pub(crate) async fn firmware_bundle_dir(
+    version: &str,
+    images: &[&str],
+) -> (tempfile::TempDir, FirmwareBundles) {
+    let dir = tempfile::tempdir().unwrap();
+    let bundle = dir.path().join(version);
+    tokio::fs::create_dir(&bundle).await.unwrap();
+    for image in images {
+        tokio::fs::write(bundle.join(image), b"image")
+            .await
+            .unwrap();
+    }
+    let bundles = FirmwareBundles::new(dir.path());
+    (dir, bundles)
+}
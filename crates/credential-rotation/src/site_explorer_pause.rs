@@ -50,7 +50,7 @@ use std::time::Duration;
 use chrono::Utc;
 use db::DatabaseError;
 use mac_address::MacAddress;
-use model::bmc_suppression::{BmcSuppressionSubsystem, NewBmcSuppression};
+use model::bmc_suppression::{BmcSuppression, BmcSuppressionSubsystem, NewBmcSuppression};
 use sqlx::{PgConnection, PgPool};
 
 /// The `reason` rotation flows stamp on the suppressions they own. Deletes are
@@ -118,12 +118,39 @@ pub async fn gate_before_credential_change(
         .await
         .map_err(|e| DatabaseError::query("commit site-explorer pause gate transaction", e))?;
 
+    Ok(decide(macs, &rows, reason))
+}
+
+/// Report what [`gate_before_credential_change`] would decide for `macs`
+/// without creating any suppression. A MAC without a suppression counts as a
+/// freshly requested, unacknowledged one, so the result is
+/// [`GateDecision::Wait`] until site-explorer has acknowledged every MAC.
+pub async fn peek_gate(
+    pool: &PgPool,
+    macs: &[MacAddress],
+    reason: &str,
+) -> Result<GateDecision, DatabaseError> {
+    if macs.is_empty() {
+        return Ok(GateDecision::Proceed);
+    }
+    let rows =
+        db::bmc_suppression::find_many(pool, macs, BmcSuppressionSubsystem::SiteExplorer).await?;
+    if macs
+        .iter()
+        .any(|mac| !rows.iter().any(|row| row.bmc_mac_address == *mac))
+    {
+        return Ok(GateDecision::Wait);
+    }
+    Ok(decide(macs, &rows, reason))
+}
+
+fn decide(macs: &[MacAddress], rows: &[BmcSuppression], reason: &str) -> GateDecision {
     let all_acknowledged = macs.iter().all(|mac| {
         rows.iter()
             .any(|row| row.bmc_mac_address == *mac && row.acknowledged_at.is_some())
     });
     if all_acknowledged {
-        return Ok(GateDecision::Proceed);
+        return GateDecision::Proceed;
     }
 
     // Escape hatch for a disabled/unavailable site-explorer that will never
@@ -149,11 +176,11 @@ pub async fn gate_before_credential_change(
                 "proceeding with BMC credential change without site-explorer acknowledgement: \
                  pause budget exceeded (site-explorer disabled or unavailable?)"
             );
-            return Ok(GateDecision::Proceed);
+            return GateDecision::Proceed;
         }
     }
 
-    Ok(GateDecision::Wait)
+    GateDecision::Wait
 }
 
 /// Delete the suppressions the caller created for `macs` (matching `reason`),
@@ -184,11 +211,11 @@ pub async fn resume_after_credential_change(
 #[cfg(test)]
 mod tests {
     use mac_address::MacAddress;
-    use model::bmc_suppression::{BmcSuppressionSubsystem, NewBmcSuppression};
+    use model::bmc_suppression::{BmcSuppression, BmcSuppressionSubsystem, NewBmcSuppression};
     use sqlx::PgPool;
 
     use super::{
-        GateDecision, ROTATION_SUPPRESSION_REASON, gate_before_credential_change,
+        GateDecision, ROTATION_SUPPRESSION_REASON, gate_before_credential_change, peek_gate,
         resume_after_credential_change,
     };
 
@@ -257,6 +284,41 @@ mod tests {
         );
     }
 
+    #[carbide_macros::sqlx_test]
+    async fn peek_does_not_create_suppressions(pool: PgPool) {
+        let macs = [mac(1)];
+
+        assert_eq!(
+            peek_gate(&pool, &macs, ROTATION_SUPPRESSION_REASON)
+                .await
+                .unwrap(),
+            GateDecision::Wait
+        );
+        assert!(
+            db::bmc_suppression::find_many(&pool, &macs, BmcSuppressionSubsystem::SiteExplorer)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // Once a suppression exists and is acknowledged, the peek agrees with
+        // the gate.
+        gate_before_credential_change(&pool, &macs, ROTATION_SUPPRESSION_REASON)
+            .await
+            .unwrap();
+        let mut txn = pool.begin().await.unwrap();
+        db::bmc_suppression::acknowledge(&mut txn, mac(1), BmcSuppressionSubsystem::SiteExplorer)
+            .await
+            .unwrap();
+        txn.commit().await.unwrap();
+        assert_eq!(
+            peek_gate(&pool, &macs, ROTATION_SUPPRESSION_REASON)
+                .await
+                .unwrap(),
+            GateDecision::Proceed
+        );
+    }
+
     #[carbide_macros::sqlx_test]
     async fn resume_removes_only_reason_owned_suppressions(pool: PgPool) {
         // Rotation owns mac(1); an operator owns mac(2).
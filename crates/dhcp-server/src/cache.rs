@@ -26,7 +26,7 @@ use std::{
 };
 
 use lru::LruCache;
-use rpc::forge::DhcpRecord;
+use rpc::forge::{DhcpDiscovery, DhcpRecord};
 
 /// Data in cache is only valid this long
 const MACHINE_CACHE_TIMEOUT: Duration = Duration::from_secs(60);
@@ -48,14 +48,12 @@ pub(super) struct CacheEntry {
 /// Returns None if we don't have that item in cache, or if we did but
 /// it's no longer valid (e.g. too old).
 pub(super) fn get(
-    mac_address: &str,
+    discovery_request: &DhcpDiscovery,
     link_address: IpAddr,
-    circuit_id: &Option<String>,
-    remote_id: &Option<String>,
     vendor_id: &str,
     cache: &mut LruCache<String, CacheEntry>,
 ) -> Option<CacheEntry> {
-    let key = &key(mac_address, link_address, circuit_id, remote_id, vendor_id);
+    let key = &key(discovery_request, link_address, vendor_id);
     if key.len() < MIN_KEY_LEN {
         tracing::debug!(key = key.as_str(), "Unexpected cache key, skipping");
         return None;
@@ -64,7 +62,10 @@ pub(super) fn get(
         if !entry.has_expired() {
             return Some(entry.clone());
         } else {
-            tracing::debug!(?mac_address, "removed expired cached response");
+            tracing::debug!(
+                mac_address = ?discovery_request.mac_address,
+                "removed expired cached response"
+            );
             let _removed = cache.pop_entry(key);
         }
     }
@@ -73,21 +74,13 @@ pub(super) fn get(
 
 /// Insert or update an item in the cache
 pub(super) fn put(
-    mac_address: &str,
+    discovery_request: &DhcpDiscovery,
     link_address: IpAddr,
-    circuit_id: Option<String>,
-    remote_id: Option<String>,
     vendor_id: &str,
     dhcp_record: DhcpRecord,
     machine_cache: &mut LruCache<String, CacheEntry>,
 ) {
-    let key = key(
-        mac_address,
-        link_address,
-        &circuit_id,
-        &remote_id,
-        vendor_id,
-    );
+    let key = key(discovery_request, link_address, vendor_id);
     let new_entry = CacheEntry {
         timestamp: Instant::now(),
         dhcp_record,
@@ -99,27 +92,32 @@ pub(super) fn put(
 // Internals
 //
 
-// Unique identifier for this entry
-fn key(
-    mac_address: &str,
-    link_address: IpAddr,
-    circuit_id: &Option<String>,
-    remote_id: &Option<String>,
-    vendor_id: &str,
-) -> String {
+// Unique identifier for this entry. The address family and message kind are
+// part of it because the API answers them differently: a DHCPv6 exchange that
+// only reads the record (INFORMATION-REQUEST, CONFIRM, IA_PD-only) allocates
+// nothing, so its reply must not answer a later IA_NA request. DHCPv6 clients
+// without a MAC in their DUID are told apart by the DUID.
+fn key(discovery_request: &DhcpDiscovery, link_address: IpAddr, vendor_id: &str) -> String {
     format!(
-        "{}_{}_{}_{}_{}",
-        mac_address,
+        "{}_{}_{}_{}_{}_{}_{}_{}",
+        discovery_request.mac_address,
         link_address,
-        match circuit_id {
-            Some(cid) => cid.as_str(),
-            None => "",
-        },
-        match remote_id {
-            Some(rid) => rid.as_str(),
-            None => "",
-        },
+        discovery_request.circuit_id.as_deref().unwrap_or_default(),
+        discovery_request.remote_id.as_deref().unwrap_or_default(),
         vendor_id,
+        discovery_request
+            .address_family
+            .map(|family| family.to_string())
+            .unwrap_or_default(),
+        discovery_request
+            .message_kind
+            .map(|kind| kind.to_string())
+            .unwrap_or_default(),
+        discovery_request
+            .duid
+            .as_deref()
+            .map(crate::util::u8_to_mac)
+            .unwrap_or_default(),
     )
 }
 
@@ -128,3 +126,66 @@ impl CacheEntry {
         self.timestamp.elapsed() >= MACHINE_CACHE_TIMEOUT
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use rpc::forge::{AddressFamily, MessageKind};
+
+    use super::*;
+
+    fn v6_discovery(message_kind: MessageKind, duid: &[u8]) -> DhcpDiscovery {
+        DhcpDiscovery {
+            mac_address: "aa:bb:cc:dd:ee:ff".to_string(),
+            relay_address: "2001:db8::1".to_string(),
+            address_family: Some(AddressFamily::V6 as i32),
+            message_kind: Some(message_kind as i32),
+            duid: Some(duid.to_vec()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn replies_are_cached_per_message_kind_and_client() {
+        let mut cache = LruCache::new(NonZeroUsize::new(MACHINE_CACHE_SIZE).unwrap());
+        let link_address: IpAddr = "2001:db8::1".parse().unwrap();
+        let info_request = v6_discovery(MessageKind::V6InfoRequest, &[0, 3, 0, 1, 1, 2]);
+        put(
+            &info_request,
+            link_address,
+            "",
+            DhcpRecord::default(),
+            &mut cache,
+        );
+
+        assert!(get(&info_request, link_address, "", &mut cache).is_some());
+        assert!(
+            get(
+                &v6_discovery(MessageKind::V6Request, &[0, 3, 0, 1, 1, 2]),
+                link_address,
+                "",
+                &mut cache
+            )
+            .is_none(),
+            "an observation-only reply does not answer an address request"
+        );
+        assert!(
+            get(
+                &v6_discovery(MessageKind::V6InfoRequest, &[0, 3, 0, 1, 3, 4]),
+                link_address,
+                "",
+                &mut cache
+            )
+            .is_none(),
+            "another DUID is another client"
+        );
+        let v4_discovery = DhcpDiscovery {
+            address_family: Some(AddressFamily::V4 as i32),
+            message_kind: Some(MessageKind::V4Discover as i32),
+            duid: None,
+            ..info_request
+        };
+        assert!(get(&v4_discovery, link_address, "", &mut cache).is_none());
+    }
+}
@@ -14,7 +14,7 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use std::net::SocketAddrV4;
+use std::net::{SocketAddrV4, SocketAddrV6};
 
 use clap::{Parser, ValueEnum};
 
@@ -32,6 +32,13 @@ pub(super) struct Args {
     )]
     pub(super) listen_addr: SocketAddrV4,
 
+    #[arg(
+        long,
+        help = "UDP address where the DHCPv6 server listens (e.g. [::]:547). \
+                When omitted DHCPv6 is not served."
+    )]
+    pub(super) listen_addr_v6: Option<SocketAddrV6>,
+
     #[arg(
         long,
         help = "UDP destination port for responses to DHCP relays.",
@@ -102,7 +109,7 @@ impl Args {
 
 #[cfg(test)]
 mod tests {
-    use std::net::{Ipv4Addr, SocketAddrV4};
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
 
     use clap::Parser;
 
@@ -115,6 +122,7 @@ mod tests {
             defaults.listen_addr,
             SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 67)
         );
+        assert_eq!(defaults.listen_addr_v6, None);
         assert_eq!(defaults.relay_response_port, 67);
         assert_eq!(defaults.forge_root_ca_path, None);
         assert_eq!(defaults.client_cert_path, None);
@@ -138,6 +146,16 @@ mod tests {
             Args::try_parse_from(["forge-dhcp-server", "--listen-addr", "[::]:6767",]).is_err()
         );
 
+        let v6 =
+            Args::try_parse_from(["forge-dhcp-server", "--listen-addr-v6", "[::]:547"]).unwrap();
+        assert_eq!(
+            v6.listen_addr_v6,
+            Some(SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 547, 0, 0))
+        );
+        assert!(
+            Args::try_parse_from(["forge-dhcp-server", "--listen-addr-v6", "0.0.0.0:547"]).is_err()
+        );
+
         let tls = Args::try_parse_from([
             "forge-dhcp-server",
             "--forge-root-ca-path",
@@ -15,7 +15,7 @@
  * limitations under the License.
  */
 use std::io;
-use std::net::{AddrParseError, Ipv4Addr};
+use std::net::{AddrParseError, IpAddr};
 use std::str::Utf8Error;
 
 use dhcproto::v4::relay::RelayCode;
@@ -39,6 +39,12 @@ pub enum DhcpError {
     #[error("missing message type: {0:?}")]
     UnhandledMessageType(MessageType),
 
+    #[error("missing DHCPv6 option: {0:?}")]
+    MissingV6Option(dhcproto::v6::OptionCode),
+
+    #[error("unhandled DHCPv6 message type: {0:?}")]
+    UnhandledV6MessageType(dhcproto::v6::MessageType),
+
     #[error("DhcpDecline message received for IP: {0}, mac: {1:?}")]
     DhcpDeclineMessage(String, String),
 
@@ -67,7 +73,7 @@ pub enum DhcpError {
     AddressParseError(#[from] AddrParseError),
 
     #[error("non relayed packet received: {0}. dropping!")]
-    NonRelayedPacket(Ipv4Addr),
+    NonRelayedPacket(IpAddr),
 
     #[error("unknown packet: {0}")]
     UnknownPacket(u8),
@@ -108,6 +108,7 @@ impl TryFrom<proto::InterfaceInfoV6> for ModelInterfaceInfoV6 {
         Ok(ModelInterfaceInfoV6 {
             address: i.address.map(|s| s.parse()).transpose()?,
             prefix: i.prefix,
+            delegated_prefix: i.delegated_prefix,
         })
     }
 }
@@ -304,6 +305,7 @@ mod tests {
                     ipv6: Some(proto::InterfaceInfoV6 {
                         address: Some("2001:db8::10".to_string()),
                         prefix: "2001:db8::/64".to_string(),
+                        delegated_prefix: None,
                     }),
                     ..Default::default()
                 } => Yields((None, None, None)),
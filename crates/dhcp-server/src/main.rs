@@ -23,11 +23,12 @@ mod grpc_server;
 mod metrics;
 mod modes;
 mod packet_handler;
+mod packet_handler_v6;
 mod rpc;
 mod util;
 
 use std::error::Error;
-use std::net::SocketAddr;
+use std::net::{SocketAddr, SocketAddrV6};
 use std::sync::Arc;
 
 use ::rpc::forge_tls_client::ForgeClientConfig;
@@ -46,6 +47,7 @@ use metrics_endpoint::{MetricsEndpointConfig, new_metrics_setup, run_metrics_end
 use modes::DhcpMode;
 use modes::controller::Controller;
 use modes::dpu::{Dpu, get_host_config};
+use packet_handler_v6::MINIMUM_DHCPV6_PKT_SIZE;
 use tokio::net::UdpSocket;
 use tokio::sync::Mutex;
 use tokio_util::sync::CancellationToken;
@@ -107,12 +109,20 @@ async fn run_dhcp_server(args: Args, cancel_token: CancellationToken) {
 
     let mut join_handles = vec![];
 
-    // Create a new socket for each interface.
+    // DHCPv6 is served only when a listen address for it is configured.
+    let listen_addresses: Vec<SocketAddr> = std::iter::once(SocketAddr::V4(args.listen_addr))
+        .chain(args.listen_addr_v6.map(SocketAddr::V6))
+        .collect();
+
+    // Create a new socket for each interface and address family.
     // In case of Controller, there will be only 1 interface.
-    for interface in args.interfaces {
+    for (interface, listen_address) in args.interfaces.iter().flat_map(|interface| {
+        listen_addresses
+            .iter()
+            .map(move |listen_address| (interface.clone(), *listen_address))
+    }) {
         let config_ = config__.clone();
         let args_mode = args.mode.clone();
-        let listen_address = args.listen_addr;
         let dhcp_timestamps_ = dhcp_timestamps.clone();
         let rate_limiter = rate_limiter_.clone();
         let cancel = cancel_token.clone();
@@ -189,11 +199,16 @@ async fn run_dhcp_server(args: Args, cancel_token: CancellationToken) {
                         };
 
                         // Not a valid packet.
-                        if len < MINIMUM_DHCP_PKT_SIZE {
+                        let minimum_packet_size = if listen_address.is_ipv4() {
+                            MINIMUM_DHCP_PKT_SIZE
+                        } else {
+                            MINIMUM_DHCPV6_PKT_SIZE
+                        };
+                        if len < minimum_packet_size {
                             emit(DhcpPacketDropped {
                                 reason: DropReason::TooShort,
                                 error: format!(
-                                    "{len} bytes is below the {MINIMUM_DHCP_PKT_SIZE}-byte minimum"
+                                    "{len} bytes is below the {minimum_packet_size}-byte minimum"
                                 ),
                             });
                             continue;
@@ -210,7 +225,7 @@ async fn run_dhcp_server(args: Args, cancel_token: CancellationToken) {
                             process(
                                 addr,
                                 socket,
-                                &buf,
+                                &buf[..len],
                                 config.clone(),
                                 &**handler_,
                                 &iface,
@@ -625,30 +640,121 @@ async fn process(
     machine_cache: &mut Arc<Mutex<LruCache<String, CacheEntry>>>,
     dhcp_timestamps: Arc<Mutex<DhcpTimestamps>>,
 ) {
-    if !addr.is_ipv4() {
-        emit(DhcpPacketDropped {
-            reason: DropReason::NotIpv4,
-            error: format!("source address {addr} is not IPv4"),
-        });
+    let replied = match addr {
+        SocketAddr::V4(_) => {
+            process_v4(
+                addr,
+                socket,
+                buf,
+                &config,
+                handler,
+                circuit_id,
+                machine_cache,
+            )
+            .await
+        }
+        SocketAddr::V6(addr) => {
+            process_v6(
+                addr,
+                socket,
+                buf,
+                &config,
+                handler,
+                circuit_id,
+                machine_cache,
+            )
+            .await
+        }
+    };
+    if !replied {
         return;
     }
 
+    // Tell forge-dpu-agent that an IP has been requested for this interface.
+    if let Some(host_config) = config.host_config {
+        let mut dhcp_timestamps = dhcp_timestamps.lock().await;
+        dhcp_timestamps.add_timestamp(host_config.host_interface_id, Utc::now().to_rfc3339());
+        if let Err(e) = dhcp_timestamps.write() {
+            emit(DhcpTimestampFileFailed::Write {
+                dhcp_timestamps_path: DhcpTimestampsFilePath::HbnTmp.path_str().to_string(),
+                host_interface_id: host_config.host_interface_id.to_string(),
+                error: e.to_string(),
+            });
+        }
+    }
+}
+
+/// Handles one DHCPv4 packet. Returns false when the packet was dropped
+/// before a reply was built.
+async fn process_v4(
+    addr: SocketAddr,
+    socket: Arc<UdpSocket>,
+    buf: &[u8],
+    config: &Config,
+    handler: &dyn DhcpMode,
+    circuit_id: &str,
+    machine_cache: &mut Arc<Mutex<LruCache<String, CacheEntry>>>,
+) -> bool {
     let Some(&bootp_op) = buf.first() else {
         emit(DhcpPacketDropped {
             reason: DropReason::TooShort,
             error: format!("0 bytes is below the {MINIMUM_DHCP_PKT_SIZE}-byte minimum"),
         });
-        return;
+        return false;
     };
 
     // Keep raw source/opcode visibility when validation or decoding fails
     // before the structured request Event can be emitted.
     tracing::debug!(bootp_op, source_address = %addr, "Received DHCP packet");
 
-    let packet = match packet_handler::process_packet(
+    let packet =
+        match packet_handler::process_packet(buf, addr, config, circuit_id, handler, machine_cache)
+            .await
+        {
+            Ok(packet) => packet,
+            Err(err) => {
+                emit(DhcpPacketDropped {
+                    reason: DropReason::from(&err),
+                    error: err.to_string(),
+                });
+                return false;
+            }
+        };
+
+    let dest_address = handler.get_destination_address(&packet);
+    if let Err(err) = packet.send(dest_address, socket).await {
+        emit(DhcpPacketDropped {
+            reason: DropReason::SendFailed,
+            error: err,
+        });
+    }
+    true
+}
+
+/// DHCPv6 counterpart of `process_v4`.
+async fn process_v6(
+    addr: SocketAddrV6,
+    socket: Arc<UdpSocket>,
+    buf: &[u8],
+    config: &Config,
+    handler: &dyn DhcpMode,
+    circuit_id: &str,
+    machine_cache: &mut Arc<Mutex<LruCache<String, CacheEntry>>>,
+) -> bool {
+    let Some(&msg_type) = buf.first() else {
+        emit(DhcpPacketDropped {
+            reason: DropReason::TooShort,
+            error: format!("0 bytes is below the {MINIMUM_DHCPV6_PKT_SIZE}-byte minimum"),
+        });
+        return false;
+    };
+
+    tracing::debug!(msg_type, source_address = %addr, "Received DHCPv6 packet");
+
+    let packet = match packet_handler_v6::process_packet_v6(
         buf,
         addr,
-        &config,
+        config,
         circuit_id,
         handler,
         machine_cache,
@@ -661,30 +767,17 @@ async fn process(
                 reason: DropReason::from(&err),
                 error: err.to_string(),
             });
-            return;
+            return false;
         }
     };
 
-    let dest_address = handler.get_destination_address(&packet);
-    if let Err(err) = packet.send(dest_address, socket).await {
+    if let Err(err) = packet.send(packet.dst_address(), socket).await {
         emit(DhcpPacketDropped {
             reason: DropReason::SendFailed,
             error: err,
         });
     }
-
-    // Tell forge-dpu-agent that an IP has been requested for this interface.
-    if let Some(host_config) = config.host_config {
-        let mut dhcp_timestamps = dhcp_timestamps.lock().await;
-        dhcp_timestamps.add_timestamp(host_config.host_interface_id, Utc::now().to_rfc3339());
-        if let Err(e) = dhcp_timestamps.write() {
-            emit(DhcpTimestampFileFailed::Write {
-                dhcp_timestamps_path: DhcpTimestampsFilePath::HbnTmp.path_str().to_string(),
-                host_interface_id: host_config.host_interface_id.to_string(),
-                error: e.to_string(),
-            });
-        }
-    }
+    true
 }
 
 #[cfg(test)]
@@ -785,6 +878,7 @@ mod test {
         Args {
             interfaces,
             listen_addr: "0.0.0.0:67".parse().unwrap(),
+            listen_addr_v6: None,
             relay_response_port: 67,
             dhcp_config: td.path().join("dhcp.yaml").display().to_string(),
             host_config: Some(td.path().join("host.yaml").display().to_string()),
@@ -955,6 +1049,7 @@ mod test {
         Args {
             interfaces: vec!["eth0".to_string()],
             listen_addr: "0.0.0.0:67".parse().unwrap(),
+            listen_addr_v6: None,
             relay_response_port: 67,
             dhcp_config: base_path.join("conf/conf.yaml").display().to_string(),
             host_config: Some(
@@ -16,17 +16,19 @@
  */
 
 //! Packet-level counters and logs for the DHCP server. Request and reply
-//! Events write INFO records with selected BOOTP header and socket details,
-//! while full packets (including their options) stay at DEBUG for forensics. A
+//! Events write INFO records with selected BOOTP (or DHCPv6) header and socket
+//! details, while full packets (including their options) stay at DEBUG for
+//! forensics. A
 //! drop is the operational error, so its Event also writes the ERROR line --
 //! one declaration moves the counter and logs the reason together.
 //! Timestamp-file failures share a counter by operation while their paths,
 //! host interface, and errors remain log-only diagnostics.
 
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 
 use carbide_instrument::{Event, LabelValue, MetricFamily};
 use dhcproto::v4::MessageType;
+use dhcproto::v6::MessageType as V6MessageType;
 
 use crate::errors::DhcpError;
 
@@ -63,21 +65,58 @@ impl From<MessageType> for MessageTypeLabel {
     }
 }
 
+/// The DHCPv6 message type of a packet, as a bounded metric label. The named
+/// variants are the RFC 8415 client and server messages this server handles;
+/// relay envelopes are unwrapped first, so a request is labelled by the client
+/// message inside it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, LabelValue)]
+pub(super) enum V6MessageTypeLabel {
+    Solicit,
+    Advertise,
+    Request,
+    Confirm,
+    Renew,
+    Rebind,
+    Reply,
+    Release,
+    Decline,
+    InformationRequest,
+    Other,
+}
+
+impl From<V6MessageType> for V6MessageTypeLabel {
+    fn from(message_type: V6MessageType) -> Self {
+        match message_type {
+            V6MessageType::Solicit => Self::Solicit,
+            V6MessageType::Advertise => Self::Advertise,
+            V6MessageType::Request => Self::Request,
+            V6MessageType::Confirm => Self::Confirm,
+            V6MessageType::Renew => Self::Renew,
+            V6MessageType::Rebind => Self::Rebind,
+            V6MessageType::Reply => Self::Reply,
+            V6MessageType::Release => Self::Release,
+            V6MessageType::Decline => Self::Decline,
+            V6MessageType::InformationRequest => Self::InformationRequest,
+            _ => Self::Other,
+        }
+    }
+}
+
 /// Why a packet was dropped, as a bounded metric label: one variant per
 /// [`DhcpError`] variant, plus the drop sites that never construct a
-/// `DhcpError` -- rate limiting, undersized packets, non-IPv4 sources, and
-/// send failures.
+/// `DhcpError` -- rate limiting, undersized packets, and send failures.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, LabelValue)]
 pub(super) enum DropReason {
     RateLimited,
     TooShort,
-    NotIpv4,
     SendFailed,
     IoError,
     ConfigParseFailure,
     MissingArgument,
     MissingOption,
     UnhandledMessageType,
+    MissingV6Option,
+    UnhandledV6MessageType,
     DhcpDeclineMessage,
     MissingRelayCode,
     InvalidInput,
@@ -102,6 +141,8 @@ impl From<&DhcpError> for DropReason {
             DhcpError::MissingArgument(_) => Self::MissingArgument,
             DhcpError::MissingOption(_) => Self::MissingOption,
             DhcpError::UnhandledMessageType(_) => Self::UnhandledMessageType,
+            DhcpError::MissingV6Option(_) => Self::MissingV6Option,
+            DhcpError::UnhandledV6MessageType(_) => Self::UnhandledV6MessageType,
             DhcpError::DhcpDeclineMessage(_, _) => Self::DhcpDeclineMessage,
             DhcpError::MissingRelayCode(_) => Self::MissingRelayCode,
             DhcpError::InvalidInput(_) => Self::InvalidInput,
@@ -139,6 +180,7 @@ pub(crate) enum SocketSetupOperation {
     BindAddress,
     SetBroadcast,
     BindDevice,
+    JoinMulticast,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, LabelValue)]
@@ -251,6 +293,20 @@ pub(crate) enum DhcpSocketSetupFailed {
         #[context]
         error: String,
     },
+
+    #[event(
+        labels(operation = SocketSetupOperation::JoinMulticast),
+        log = info,
+        message = "Socket multicast group join failed"
+    )]
+    JoinMulticast {
+        #[label]
+        next_action: SocketSetupNextAction,
+        #[context(value)]
+        retry: i64,
+        #[context]
+        error: String,
+    },
 }
 
 impl DhcpSocketSetupFailed {
@@ -293,6 +349,11 @@ impl DhcpSocketSetupFailed {
                 retry,
                 error,
             },
+            SocketSetupOperation::JoinMulticast => Self::JoinMulticast {
+                next_action,
+                retry,
+                error,
+            },
         }
     }
 }
@@ -370,7 +431,7 @@ pub(super) struct DhcpRequestReceived {
 }
 
 /// A packet was dropped without a reply reaching the client -- anywhere from
-/// the receive loop (rate limiting, undersized or non-IPv4 packets) through
+/// the receive loop (rate limiting, undersized packets) through
 /// packet processing to the final send.
 #[derive(Event)]
 #[event(
@@ -426,6 +487,61 @@ pub(super) struct DhcpReplySent {
     pub(super) chaddr: String,
 }
 
+/// A DHCPv6 client message was decoded from the wire, directly or from inside
+/// a relay envelope. The transaction and client identity stay on the log line;
+/// the option-rich full message stays at DEBUG.
+#[derive(Event)]
+#[event(
+    event_name = "dhcp_server_v6_request_received",
+    metric_name = "carbide_dhcp_v6_requests_total",
+    component = "nico-dhcp",
+    log = info,
+    metric = counter,
+    message = "Decoded DHCPv6 packet",
+    describe = "Number of DHCPv6 packets received and decoded, by DHCPv6 message type."
+)]
+pub(super) struct DhcpV6RequestReceived {
+    #[label]
+    pub(super) message_type: V6MessageTypeLabel,
+    #[context]
+    pub(super) source_address: SocketAddrV6,
+    #[context(value)]
+    pub(super) transaction_id: i64,
+    /// The relay's link-address; absent when the client is on-link.
+    #[context]
+    pub(super) link_address: Option<Ipv6Addr>,
+    /// Absent when the client sent no Client Identifier option.
+    #[context]
+    pub(super) client_duid: Option<String>,
+}
+
+/// A DHCPv6 reply was sent, labelled by the client-facing message type: an
+/// `advertise` proposes leases, a `reply` commits them or answers a
+/// stateless, confirm, or release exchange. The metric is shared with the Kea
+/// hook so both servers count replies the same way.
+#[derive(Event)]
+#[event(
+    event_name = "dhcp_server_v6_reply_sent",
+    metric_name = "carbide_dhcp_v6_replies_sent_total",
+    component = "nico-dhcp",
+    log = info,
+    metric = counter,
+    message = "Sent DHCPv6 reply",
+    describe = "Number of DHCPv6 replies sent, by response message type."
+)]
+pub(super) struct DhcpV6ReplySent {
+    #[label]
+    pub(super) message_type: V6MessageTypeLabel,
+    #[context]
+    pub(super) destination_address: SocketAddrV6,
+    #[context(value)]
+    pub(super) transaction_id: i64,
+    #[context(value)]
+    pub(super) relayed: bool,
+    #[context(value)]
+    pub(super) client_duid: String,
+}
+
 /// A DHCP timestamp-file operation failed. Each variant is one operation, and
 /// holds only what that path has -- only a write is per-interface, so only it
 /// has a `host_interface_id`.
@@ -490,7 +606,7 @@ mod tests {
 
     use carbide_instrument::emit;
     use carbide_instrument::testing::{CapturedFieldKind, MetricsCapture, capture_logs};
-    use carbide_test_support::{Check, check_values};
+    use carbide_test_support::{Check, check_values, value_scenarios};
     use dhcproto::v4::OptionCode;
     use dhcproto::v4::relay::RelayCode;
 
@@ -1013,6 +1129,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn v6_message_type_label_maps_the_rfc8415_set_and_buckets_the_rest() {
+        value_scenarios!(V6MessageTypeLabel::from:
+            "client messages" {
+                V6MessageType::Solicit => V6MessageTypeLabel::Solicit,
+                V6MessageType::Request => V6MessageTypeLabel::Request,
+                V6MessageType::Confirm => V6MessageTypeLabel::Confirm,
+                V6MessageType::Renew => V6MessageTypeLabel::Renew,
+                V6MessageType::Rebind => V6MessageTypeLabel::Rebind,
+                V6MessageType::Release => V6MessageTypeLabel::Release,
+                V6MessageType::Decline => V6MessageTypeLabel::Decline,
+                V6MessageType::InformationRequest => V6MessageTypeLabel::InformationRequest,
+            }
+
+            "server messages" {
+                V6MessageType::Advertise => V6MessageTypeLabel::Advertise,
+                V6MessageType::Reply => V6MessageTypeLabel::Reply,
+            }
+
+            "relay envelopes and extensions bucket as other" {
+                V6MessageType::RelayForw => V6MessageTypeLabel::Other,
+                V6MessageType::RelayRepl => V6MessageTypeLabel::Other,
+                V6MessageType::Reconfigure => V6MessageTypeLabel::Other,
+            }
+        );
+    }
+
     #[test]
     fn drop_reason_covers_every_dhcp_error_variant() {
         // 0x80 is a lone UTF-8 continuation byte -- the decode failure is the
@@ -1047,6 +1190,16 @@ mod tests {
                     input: DhcpError::UnhandledMessageType(MessageType::Offer),
                     expect: DropReason::UnhandledMessageType,
                 },
+                Check {
+                    scenario: "missing DHCPv6 option",
+                    input: DhcpError::MissingV6Option(dhcproto::v6::OptionCode::ClientId),
+                    expect: DropReason::MissingV6Option,
+                },
+                Check {
+                    scenario: "unhandled DHCPv6 message type",
+                    input: DhcpError::UnhandledV6MessageType(V6MessageType::RelayRepl),
+                    expect: DropReason::UnhandledV6MessageType,
+                },
                 Check {
                     scenario: "decline message",
                     input: DhcpError::DhcpDeclineMessage(
@@ -1103,7 +1256,7 @@ mod tests {
                 },
                 Check {
                     scenario: "non-relayed packet",
-                    input: DhcpError::NonRelayedPacket(Ipv4Addr::new(0, 0, 0, 0)),
+                    input: DhcpError::NonRelayedPacket(Ipv4Addr::new(0, 0, 0, 0).into()),
                     expect: DropReason::NonRelayedPacket,
                 },
                 Check {
@@ -65,10 +65,8 @@ impl DhcpMode for Controller {
         {
             let mut machine_cache = machine_cache.lock().await;
             if let Some(cache_entry) = cache::get(
-                &discovery_request.mac_address,
+                &discovery_request,
                 link_address,
-                &discovery_request.circuit_id,
-                &discovery_request.remote_id,
                 vendor_id,
                 &mut machine_cache,
             ) {
@@ -78,6 +76,7 @@ impl DhcpMode for Controller {
                     circuit_id = ?discovery_request.circuit_id,
                     remote_id = ?discovery_request.remote_id,
                     %vendor_id,
+                    message_kind = ?discovery_request.message_kind,
                     "returning cached response"
                 );
 
@@ -88,10 +87,8 @@ impl DhcpMode for Controller {
         let record = discover_dhcp(discovery_request.clone(), config).await?;
         let mut machine_cache = machine_cache.lock().await;
         cache::put(
-            &discovery_request.mac_address,
+            &discovery_request,
             link_address,
-            discovery_request.circuit_id,
-            discovery_request.remote_id,
             vendor_id,
             record.clone(),
             &mut machine_cache,
@@ -14,16 +14,18 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use carbide_rpc_utils::dhcp::InterfaceInfo;
+use carbide_rpc_utils::dhcp::{InterfaceInfo, InterfaceInfoV6};
 use carbide_uuid::machine::MachineInterfaceId;
+use ipnetwork::Ipv6Network;
 use lru::LruCache;
-use rpc::forge::{DhcpDiscovery, DhcpRecord};
+use rpc::forge::{AddressFamily, DhcpDiscovery, DhcpRecord};
 use tonic::async_trait;
 
 use super::DhcpMode;
 use crate::cache::CacheEntry;
 use crate::errors::DhcpError;
 use crate::packet_handler::DecodedPacket;
+use crate::packet_handler_v6::DecodedPacketV6;
 use crate::{Config, HostConfig};
 
 #[derive(Debug)]
@@ -62,6 +64,38 @@ fn from_host_conf(
     })
 }
 
+fn from_host_conf_v6(
+    value: &InterfaceInfo,
+    interface_id: MachineInterfaceId,
+) -> Result<DhcpRecord, DhcpError> {
+    let InterfaceInfoV6 {
+        address, prefix, ..
+    } = value
+        .ipv6
+        .as_ref()
+        .ok_or_else(|| DhcpError::InvalidInput("IPv6 is not configured".to_string()))?;
+
+    // A SLAAC-only interface has no address to hand out; it still gets the
+    // stateless options, and IA_NA requests are answered with NoAddrsAvail.
+    Ok(DhcpRecord {
+        machine_id: None,
+        machine_interface_id: Some(interface_id),
+        segment_id: None,
+        subdomain_id: None,
+        fqdn: value.fqdn.clone(),
+        mac_address: "dummy".to_string(),
+        address: address
+            .map(|address| address.to_string())
+            .unwrap_or_default(),
+        mtu: 0,
+        prefix: prefix.clone(),
+        gateway: None,
+        booturl: None,
+        last_invalidation_time: None,
+        ntp_servers: vec![],
+    })
+}
+
 #[async_trait]
 impl DhcpMode for Dpu {
     async fn discover_dhcp(
@@ -92,6 +126,9 @@ impl DhcpMode for Dpu {
             ));
         };
 
+        if discovery_request.address_family == Some(AddressFamily::V6 as i32) {
+            return from_host_conf_v6(ip_details, host_config.host_interface_id);
+        }
         from_host_conf(ip_details, host_config.host_interface_id)
     }
 
@@ -100,6 +137,25 @@ impl DhcpMode for Dpu {
         Some(circuit_id.to_string())
     }
 
+    fn get_circuit_id_v6(&self, _packet: &DecodedPacketV6, circuit_id: &str) -> Option<String> {
+        Some(circuit_id.to_string())
+    }
+
+    /// dpu-agent configures the delegated prefix per host interface, next to its IPv6 address.
+    fn get_delegated_prefix(&self, config: &Config, circuit_id: &str) -> Option<Ipv6Network> {
+        config
+            .host_config
+            .as_ref()?
+            .host_ip_addresses
+            .get(circuit_id)?
+            .ipv6
+            .as_ref()?
+            .delegated_prefix
+            .as_deref()?
+            .parse()
+            .ok()
+    }
+
     fn should_be_relayed(&self) -> bool {
         false
     }
@@ -116,6 +172,17 @@ fn validate_host_config(host_config: &HostConfig) -> Result<(), DhcpError> {
                 )));
             }
         }
+
+        if let Some(delegated_prefix) = interface
+            .ipv6
+            .as_ref()
+            .and_then(|ipv6| ipv6.delegated_prefix.as_deref())
+            && delegated_prefix.parse::<Ipv6Network>().is_err()
+        {
+            return Err(DhcpError::InvalidInput(format!(
+                "delegated prefix {delegated_prefix} for {circuit_id} is not an IPv6 network"
+            )));
+        }
     }
 
     Ok(())
@@ -194,6 +261,7 @@ mod tests {
                     ipv6: Some(InterfaceInfoV6 {
                         address: Some("2001:db8::10".parse().unwrap()),
                         prefix: "2001:db8::/64".to_string(),
+                        delegated_prefix: None,
                     }),
                     ..Default::default()
                 } => Fails,
@@ -231,6 +299,7 @@ mod tests {
                     ipv6: Some(InterfaceInfoV6 {
                         address: Some("2001:db8::10".parse().unwrap()),
                         prefix: "2001:db8::/64".to_string(),
+                        delegated_prefix: None,
                     }),
                     ..Default::default()
                 } => Yields(()),
@@ -17,6 +17,7 @@
 use std::net::SocketAddrV4;
 use std::sync::Arc;
 
+use ipnetwork::Ipv6Network;
 use lru::LruCache;
 use rpc::forge::{DhcpDiscovery, DhcpRecord};
 use tokio::sync::Mutex;
@@ -26,6 +27,7 @@ use crate::Config;
 use crate::cache::CacheEntry;
 use crate::errors::DhcpError;
 use crate::packet_handler::{DecodedPacket, Packet};
+use crate::packet_handler_v6::DecodedPacketV6;
 
 pub(super) mod controller;
 pub(super) mod dpu;
@@ -47,6 +49,15 @@ pub(super) trait DhcpMode: Send + Sync + std::fmt::Debug {
     fn get_circuit_id(&self, packet: &DecodedPacket, _circuit_id: &str) -> Option<String> {
         packet.get_circuit_id()
     }
+    /// DHCPv6 counterpart of `get_circuit_id`: relays identify the client link by Interface-ID.
+    fn get_circuit_id_v6(&self, packet: &DecodedPacketV6, _circuit_id: &str) -> Option<String> {
+        packet.get_interface_id()
+    }
+    /// Prefix to delegate over DHCPv6 IA_PD. The API does not delegate prefixes, so by default
+    /// IA_PD requests are answered with NoPrefixAvail.
+    fn get_delegated_prefix(&self, _config: &Config, _circuit_id: &str) -> Option<Ipv6Network> {
+        None
+    }
     /// Should be relayed? A controller mode will accept on relayed packet, while dpu with relay
     /// mode will never get a relayed packet.
     fn should_be_relayed(&self) -> bool {
@@ -126,7 +126,7 @@ impl DecodedPacket {
         // get gi address
         let giaddress = self.packet.giaddr();
         if giaddress.is_broadcast() || giaddress == Ipv4Addr::new(0, 0, 0, 0) {
-            return Err(DhcpError::NonRelayedPacket(giaddress));
+            return Err(DhcpError::NonRelayedPacket(giaddress.into()));
         }
         Ok(())
     }
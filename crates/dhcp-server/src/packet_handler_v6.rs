@@ -0,0 +1,1234 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! DHCPv6 (RFC 8415) packet handling.
+//!
+//! Clients reach the server either directly on the link (DPU mode) or through
+//! one Relay-Forward hop (controller mode). Replies mirror the request: an
+//! ADVERTISE or REPLY goes straight back to an on-link client, or inside a
+//! Relay-Reply to the relay that forwarded it. The server keeps no lease
+//! state of its own; every exchange asks the mode for the interface's record,
+//! exactly like the DHCPv4 path.
+
+use std::net::{IpAddr, Ipv6Addr, SocketAddrV6};
+use std::sync::Arc;
+
+use carbide_instrument::emit;
+use carbide_rpc_utils::dhcp::DhcpConfig;
+use dhcproto::v6::{
+    DhcpOption, DhcpOptions, IAAddr, IANA, IAPD, IAPrefix, Message, MessageType, OptionCode,
+    Status, StatusCode, UnknownOption,
+};
+use dhcproto::{Decodable, Decoder, Encodable, Encoder};
+use ipnetwork::{IpNetwork, Ipv6Network};
+use lru::LruCache;
+use rpc::forge::{AddressFamily, DhcpDiscovery, DhcpRecord, MessageKind};
+use tokio::net::UdpSocket;
+use tokio::sync::Mutex;
+
+use crate::cache::CacheEntry;
+use crate::errors::DhcpError;
+use crate::metrics::{DhcpV6ReplySent, DhcpV6RequestReceived, V6MessageTypeLabel};
+use crate::{Config, DhcpMode, util};
+
+/// Message type and transaction ID; anything shorter cannot be DHCPv6.
+pub(super) const MINIMUM_DHCPV6_PKT_SIZE: usize = 4;
+const DHCPV6_CLIENT_PORT: u16 = 546;
+/// Relay agents listen on the server port for Relay-Reply messages.
+const DHCPV6_SERVER_PORT: u16 = 547;
+const RELAY_FORW: u8 = 12;
+const RELAY_REPL: u8 = 13;
+/// msg-type, hop-count, link-address, peer-address.
+const RELAY_HEADER_LEN: usize = 34;
+const DUID_LLT: u16 = 1;
+const DUID_LL: u16 = 3;
+const DUID_UUID: u16 = 4;
+const HTYPE_ETHERNET: u16 = 1;
+const ETHERNET_MAC_LEN: usize = 6;
+/// RFC 5908 NTP server option and its server-address suboption.
+const OPTION_NTP_SERVER: u16 = 56;
+const NTP_SUBOPTION_SRV_ADDR: u16 = 1;
+
+/// The relay envelope a client message arrived in.
+struct RelayForward {
+    hop_count: u8,
+    link_address: Ipv6Addr,
+    peer_address: Ipv6Addr,
+    interface_id: Option<Vec<u8>>,
+    remote_id: Option<Vec<u8>>,
+    client_link_layer: Option<Vec<u8>>,
+}
+
+pub(super) struct DecodedPacketV6 {
+    message: Message,
+    relay: Option<RelayForward>,
+}
+
+impl DecodedPacketV6 {
+    fn decode(buf: &[u8]) -> Result<Self, DhcpError> {
+        match buf.first().copied() {
+            Some(RELAY_FORW) => Self::decode_relay_forward(buf),
+            Some(RELAY_REPL) => Err(DhcpError::UnhandledV6MessageType(MessageType::RelayRepl)),
+            Some(_) => Ok(Self {
+                message: Message::decode(&mut Decoder::new(buf))?,
+                relay: None,
+            }),
+            None => Err(not_enough_bytes()),
+        }
+    }
+
+    /// `dhcproto` models option 9 as a nested relay message, so the envelope
+    /// itself is walked as raw TLVs and only the client message is decoded.
+    fn decode_relay_forward(buf: &[u8]) -> Result<Self, DhcpError> {
+        if buf.len() < RELAY_HEADER_LEN {
+            return Err(not_enough_bytes());
+        }
+        let (header, options) = buf.split_at(RELAY_HEADER_LEN);
+        let options = raw_options(options)?;
+
+        let mut relay_messages = options
+            .iter()
+            .filter(|(code, _)| *code == u16::from(OptionCode::RelayMsg));
+        let (Some((_, relay_message)), None) = (relay_messages.next(), relay_messages.next())
+        else {
+            return Err(DhcpError::InvalidInput(
+                "Relay-Forward must carry exactly one Relay Message option".to_string(),
+            ));
+        };
+
+        // Segment selection keys on a single relay link-address, so a chain of
+        // relays is refused rather than guessing which hop identifies the link.
+        if matches!(
+            relay_message.first().copied(),
+            Some(RELAY_FORW) | Some(RELAY_REPL)
+        ) {
+            return Err(DhcpError::InvalidInput(
+                "nested DHCPv6 relay is not supported".to_string(),
+            ));
+        }
+
+        let option = |code: OptionCode| {
+            options
+                .iter()
+                .find(|(option_code, _)| *option_code == u16::from(code))
+                .map(|(_, data)| data.to_vec())
+        };
+
+        Ok(Self {
+            message: Message::decode(&mut Decoder::new(relay_message))?,
+            relay: Some(RelayForward {
+                hop_count: header[1],
+                link_address: ipv6_at(header, 2),
+                peer_address: ipv6_at(header, 18),
+                interface_id: option(OptionCode::InterfaceId),
+                remote_id: option(OptionCode::RemoteId),
+                client_link_layer: option(OptionCode::ClientLinklayerAddr),
+            }),
+        })
+    }
+
+    fn is_relayed(&self, source_address: SocketAddrV6) -> Result<(), DhcpError> {
+        if self.relay.is_none() {
+            return Err(DhcpError::NonRelayedPacket(IpAddr::V6(
+                *source_address.ip(),
+            )));
+        }
+        Ok(())
+    }
+
+    fn is_this_for_us(&self, server_duid: &[u8]) -> Result<(), DhcpError> {
+        match self.message.opts().get(OptionCode::ServerId) {
+            Some(DhcpOption::ServerId(duid)) if duid != server_duid => {
+                Err(DhcpError::NotMyPacket(util::u8_to_mac(duid)))
+            }
+            // No identifier sent by client. It can be for us
+            _ => Ok(()),
+        }
+    }
+
+    fn client_duid(&self) -> Result<&[u8], DhcpError> {
+        match self.message.opts().get(OptionCode::ClientId) {
+            Some(DhcpOption::ClientId(duid)) if !duid.is_empty() => Ok(duid.as_slice()),
+            _ => Err(DhcpError::MissingV6Option(OptionCode::ClientId)),
+        }
+    }
+
+    /// The client's MAC. RFC 6939 option 79 names the link the request came
+    /// from, so it wins over a MAC embedded in the DUID.
+    fn get_mac_address(&self) -> Option<String> {
+        let client_link_layer = self
+            .relay
+            .as_ref()
+            .and_then(|relay| relay.client_link_layer.as_deref());
+        client_link_layer
+            .and_then(ethernet_mac)
+            .or_else(|| self.client_duid().ok().and_then(mac_from_duid))
+    }
+
+    pub(super) fn get_interface_id(&self) -> Option<String> {
+        self.relay.as_ref()?.interface_id.as_deref().map(hex_encode)
+    }
+
+    fn get_remote_id(&self) -> Option<String> {
+        self.relay.as_ref()?.remote_id.as_deref().map(hex_encode)
+    }
+
+    fn get_vendor_string(&self) -> Option<String> {
+        match self.message.opts().get(OptionCode::VendorClass) {
+            Some(DhcpOption::VendorClass(vendor)) => vendor
+                .data
+                .iter()
+                .find_map(|value| String::from_utf8(value.clone()).ok()),
+            _ => None,
+        }
+    }
+
+    fn ia_nas(&self) -> impl Iterator<Item = &IANA> {
+        self.message
+            .opts()
+            .get_all(OptionCode::IANA)
+            .into_iter()
+            .flatten()
+            .filter_map(|option| match option {
+                DhcpOption::IANA(ia_na) => Some(ia_na),
+                _ => None,
+            })
+    }
+
+    fn ia_pds(&self) -> impl Iterator<Item = &IAPD> {
+        self.message
+            .opts()
+            .get_all(OptionCode::IAPD)
+            .into_iter()
+            .flatten()
+            .filter_map(|option| match option {
+                DhcpOption::IAPD(ia_pd) => Some(ia_pd),
+                _ => None,
+            })
+    }
+
+    fn requested_addresses(&self) -> Vec<Ipv6Addr> {
+        self.ia_nas().flat_map(ia_addresses).collect()
+    }
+
+    fn has_rapid_commit(&self) -> bool {
+        self.message.opts().get(OptionCode::RapidCommit).is_some()
+    }
+
+    /// The API message kind for a client message that needs the interface's
+    /// record. Only an exchange that asks for an address is stateful; IA_PD-only,
+    /// CONFIRM and INFORMATION-REQUEST exchanges read the record without
+    /// allocating.
+    fn message_kind(&self) -> Result<MessageKind, DhcpError> {
+        let has_ia_na = self.ia_nas().next().is_some();
+        match self.message.msg_type() {
+            // A rapid-commit SOLICIT commits the lease in one round trip.
+            MessageType::Solicit if has_ia_na && self.has_rapid_commit() => {
+                Ok(MessageKind::V6Request)
+            }
+            MessageType::Solicit if has_ia_na => Ok(MessageKind::V6Solicit),
+            MessageType::Request | MessageType::Renew | MessageType::Rebind if has_ia_na => {
+                Ok(MessageKind::V6Request)
+            }
+            MessageType::Solicit
+            | MessageType::Request
+            | MessageType::Renew
+            | MessageType::Rebind
+            | MessageType::Confirm
+            | MessageType::InformationRequest => Ok(MessageKind::V6InfoRequest),
+            message_type => Err(DhcpError::UnhandledV6MessageType(message_type)),
+        }
+    }
+
+    fn get_discovery_request(
+        &self,
+        handler: &dyn DhcpMode,
+        circuit_id: &str,
+        source_address: SocketAddrV6,
+        message_kind: MessageKind,
+    ) -> Result<DhcpDiscovery, DhcpError> {
+        let mac_address = self.get_mac_address();
+        if mac_address.is_none() && handler.should_be_relayed() {
+            return Err(DhcpError::InvalidInput(
+                "DHCPv6 client DUID carries no MAC and the relay sent no client link-layer address"
+                    .to_string(),
+            ));
+        }
+
+        // A relay without a global address leaves link-address unspecified;
+        // its own source address still identifies the segment.
+        let relay_address = match &self.relay {
+            Some(relay) if !relay.link_address.is_unspecified() => relay.link_address,
+            _ => *source_address.ip(),
+        };
+
+        Ok(DhcpDiscovery {
+            mac_address: mac_address.unwrap_or_default(),
+            relay_address: relay_address.to_string(),
+            vendor_string: self.get_vendor_string(),
+            link_address: self
+                .relay
+                .as_ref()
+                .map(|relay| relay.link_address.to_string()),
+            circuit_id: handler.get_circuit_id_v6(self, circuit_id),
+            remote_id: self.get_remote_id(),
+            desired_address: self
+                .requested_addresses()
+                .first()
+                .map(|address| address.to_string()),
+            address_family: Some(AddressFamily::V6 as i32),
+            message_kind: Some(message_kind as i32),
+            duid: Some(self.client_duid()?.to_vec()),
+        })
+    }
+
+    /// On-link clients listen on 546; a relay takes the Relay-Reply on 547.
+    fn decide_dst_address(&self, source_address: SocketAddrV6) -> SocketAddrV6 {
+        let port = match self.relay {
+            Some(_) => DHCPV6_SERVER_PORT,
+            None => DHCPV6_CLIENT_PORT,
+        };
+        SocketAddrV6::new(*source_address.ip(), port, 0, source_address.scope_id())
+    }
+}
+
+pub(super) struct PacketV6 {
+    encoded_packet: Vec<u8>,
+    sent_packet: Message,
+    dst_address: SocketAddrV6,
+    relayed: bool,
+    /// The client-facing reply type (an Advertise or a Reply), never the
+    /// Relay-Reply envelope around it.
+    message_type: V6MessageTypeLabel,
+}
+
+impl PacketV6 {
+    #[cfg(test)]
+    pub(super) fn encoded_packet(&self) -> &Vec<u8> {
+        &self.encoded_packet
+    }
+
+    #[cfg(test)]
+    pub(super) fn message_type(&self) -> V6MessageTypeLabel {
+        self.message_type
+    }
+
+    pub(super) fn dst_address(&self) -> SocketAddrV6 {
+        self.dst_address
+    }
+
+    pub(super) async fn send(
+        &self,
+        dst_address: SocketAddrV6,
+        socket: Arc<UdpSocket>,
+    ) -> Result<(), String> {
+        if let Err(error) = socket.send_to(&self.encoded_packet, dst_address).await {
+            tracing::debug!(
+                packet.send = ?self.sent_packet,
+                destination_address = %dst_address,
+                error = %error,
+                "Failed to send DHCPv6 packet"
+            );
+            return Err(error.to_string());
+        }
+
+        emit(DhcpV6ReplySent {
+            message_type: self.message_type,
+            destination_address: dst_address,
+            transaction_id: transaction_id(&self.sent_packet),
+            relayed: self.relayed,
+            client_duid: match self.sent_packet.opts().get(OptionCode::ClientId) {
+                Some(DhcpOption::ClientId(duid)) => util::u8_to_mac(duid),
+                _ => String::new(),
+            },
+        });
+        tracing::debug!(packet.send = ?self.sent_packet, "Sent DHCPv6 packet");
+
+        Ok(())
+    }
+}
+
+pub(super) async fn process_packet_v6(
+    buf: &[u8],
+    source_address: SocketAddrV6,
+    config: &Config,
+    circuit_id: &str,
+    handler: &dyn DhcpMode,
+    machine_cache: &mut Arc<Mutex<LruCache<String, CacheEntry>>>,
+) -> Result<PacketV6, DhcpError> {
+    let decoded_packet = DecodedPacketV6::decode(buf)?;
+    let msg_type = decoded_packet.message.msg_type();
+
+    emit(DhcpV6RequestReceived {
+        message_type: V6MessageTypeLabel::from(msg_type),
+        source_address,
+        transaction_id: transaction_id(&decoded_packet.message),
+        link_address: decoded_packet
+            .relay
+            .as_ref()
+            .map(|relay| relay.link_address),
+        client_duid: decoded_packet.client_duid().ok().map(util::u8_to_mac),
+    });
+    tracing::debug!(packet.received = ?decoded_packet.message, "Received DHCPv6 packet");
+
+    if handler.should_be_relayed() {
+        decoded_packet.is_relayed(source_address)?;
+    }
+    let server_duid = server_duid(&config.dhcp_config)?;
+    decoded_packet.is_this_for_us(&server_duid)?;
+    let client_duid = decoded_packet.client_duid()?.to_vec();
+
+    let packet = match msg_type {
+        // Leases live in the API, which expires them on its own schedule;
+        // the client only needs to hear that its release was received.
+        MessageType::Release => {
+            let mut msg = reply_header(&decoded_packet, server_duid, client_duid);
+            msg.opts_mut()
+                .insert(status_code(Status::Success, "release acknowledged"));
+            msg
+        }
+        MessageType::Decline => {
+            return Err(DhcpError::DhcpDeclineMessage(
+                decoded_packet
+                    .requested_addresses()
+                    .iter()
+                    .map(|address| address.to_string())
+                    .collect::<Vec<String>>()
+                    .join(","),
+                util::u8_to_mac(&client_duid),
+            ));
+        }
+        _ => {
+            let message_kind = decoded_packet.message_kind()?;
+            let dhcp_response = handler
+                .discover_dhcp(
+                    decoded_packet.get_discovery_request(
+                        handler,
+                        circuit_id,
+                        source_address,
+                        message_kind,
+                    )?,
+                    config,
+                    machine_cache,
+                )
+                .await?;
+            let delegated_prefix = handler
+                .get_circuit_id_v6(&decoded_packet, circuit_id)
+                .and_then(|circuit_id| handler.get_delegated_prefix(config, &circuit_id));
+
+            create_dhcpv6_reply_packet(
+                &decoded_packet,
+                dhcp_response,
+                delegated_prefix,
+                config,
+                server_duid,
+                client_duid,
+            )?
+        }
+    };
+
+    let reply_message_type = V6MessageTypeLabel::from(packet.msg_type());
+
+    let mut encoded_packet = Vec::new();
+    let mut e = Encoder::new(&mut encoded_packet);
+    if let Err(error) = packet.encode(&mut e) {
+        tracing::debug!(
+            packet.encode = ?packet,
+            error = %error,
+            "Failed to encode DHCPv6 packet"
+        );
+        return Err(error.into());
+    }
+    if let Some(relay) = &decoded_packet.relay {
+        encoded_packet = relay_reply(relay, &encoded_packet);
+    }
+
+    Ok(PacketV6 {
+        encoded_packet,
+        sent_packet: packet,
+        dst_address: decoded_packet.decide_dst_address(source_address),
+        relayed: decoded_packet.relay.is_some(),
+        message_type: reply_message_type,
+    })
+}
+
+fn create_dhcpv6_reply_packet(
+    src: &DecodedPacketV6,
+    forge_response: DhcpRecord,
+    delegated_prefix: Option<Ipv6Network>,
+    config: &Config,
+    server_duid: Vec<u8>,
+    client_duid: Vec<u8>,
+) -> Result<Message, DhcpError> {
+    let mut msg = reply_header(src, server_duid, client_duid);
+
+    match src.message.msg_type() {
+        // https://www.rfc-editor.org/rfc/rfc8415#section-18.3.3
+        MessageType::Confirm => {
+            let requested_addresses = src.requested_addresses();
+            if requested_addresses.is_empty() {
+                return Err(DhcpError::InvalidInput(
+                    "CONFIRM carries no addresses to check".to_string(),
+                ));
+            }
+            let prefix = ipv6_prefix(&forge_response.prefix)?;
+            let status = if requested_addresses
+                .iter()
+                .all(|address| prefix.contains(*address))
+            {
+                status_code(Status::Success, "addresses are on-link")
+            } else {
+                status_code(Status::NotOnLink, "addresses are not on-link")
+            };
+            msg.opts_mut().insert(status);
+        }
+        MessageType::InformationRequest => {}
+        _ => {
+            let lifetimes = Lifetimes::from_config(&config.dhcp_config);
+            let allocated_address = if forge_response.address.is_empty() {
+                None
+            } else {
+                Some(forge_response.address.parse::<Ipv6Addr>()?)
+            };
+
+            // The API allocates one address per interface, so only the first
+            // IA_NA (and the first IA_PD) can be satisfied.
+            for (index, ia_na) in src.ia_nas().enumerate() {
+                let address = allocated_address.filter(|_| index == 0);
+                msg.opts_mut()
+                    .insert(DhcpOption::IANA(ia_na_reply(ia_na, address, &lifetimes)));
+            }
+            for (index, ia_pd) in src.ia_pds().enumerate() {
+                let prefix = delegated_prefix.filter(|_| index == 0);
+                msg.opts_mut()
+                    .insert(DhcpOption::IAPD(ia_pd_reply(ia_pd, prefix, &lifetimes)));
+            }
+        }
+    }
+
+    if !config.dhcp_config.carbide_nameservers_v6.is_empty() {
+        msg.opts_mut().insert(DhcpOption::DomainNameServers(
+            config.dhcp_config.carbide_nameservers_v6.clone(),
+        ));
+    }
+    if !config.dhcp_config.carbide_ntpservers_v6.is_empty() {
+        msg.opts_mut()
+            .insert(ntp_servers(&config.dhcp_config.carbide_ntpservers_v6));
+    }
+
+    Ok(msg)
+}
+
+/// ADVERTISE for a SOLICIT (REPLY when the client asked for rapid commit),
+/// REPLY for everything else, with the identifiers every reply carries.
+fn reply_header(src: &DecodedPacketV6, server_duid: Vec<u8>, client_duid: Vec<u8>) -> Message {
+    let rapid_commit = src.message.msg_type() == MessageType::Solicit && src.has_rapid_commit();
+    let reply_message_type = match src.message.msg_type() {
+        MessageType::Solicit if !rapid_commit => MessageType::Advertise,
+        _ => MessageType::Reply,
+    };
+
+    let mut msg = Message::new_with_id(reply_message_type, src.message.xid());
+    msg.opts_mut().insert(DhcpOption::ServerId(server_duid));
+    msg.opts_mut().insert(DhcpOption::ClientId(client_duid));
+    if rapid_commit {
+        msg.opts_mut().insert(DhcpOption::RapidCommit);
+    }
+    msg
+}
+
+/// Lease timers for IA_NA and IA_PD. DHCPv6 lifetimes left unset in the config
+/// fall back to the DHCPv4 lease timers, so existing configs serve both.
+struct Lifetimes {
+    t1: u32,
+    t2: u32,
+    preferred: u32,
+    valid: u32,
+}
+
+impl Lifetimes {
+    fn from_config(config: &DhcpConfig) -> Self {
+        let nonzero_or = |value: u32, default: u32| if value == 0 { default } else { value };
+        let valid = nonzero_or(config.dhcpv6_valid_lifetime_secs, config.lease_time_secs);
+        let preferred = nonzero_or(config.dhcpv6_preferred_lifetime_secs, valid).min(valid);
+        Self {
+            t1: config.renewal_time_secs.min(preferred),
+            t2: config.rebinding_time_secs.min(preferred),
+            preferred,
+            valid,
+        }
+    }
+}
+
+fn ia_na_reply(request: &IANA, address: Option<Ipv6Addr>, lifetimes: &Lifetimes) -> IANA {
+    let mut opts = DhcpOptions::default();
+    let Some(address) = address else {
+        opts.insert(status_code(
+            Status::NoAddrsAvail,
+            "no address is available for this interface",
+        ));
+        return IANA {
+            id: request.id,
+            t1: 0,
+            t2: 0,
+            opts,
+        };
+    };
+
+    // An address the client still holds but no longer owns is returned with
+    // zero lifetimes so it stops using it (RFC 8415 section 18.3.4).
+    for stale in ia_addresses(request).filter(|stale| *stale != address) {
+        opts.insert(DhcpOption::IAAddr(IAAddr {
+            addr: stale,
+            preferred_life: 0,
+            valid_life: 0,
+            opts: Default::default(),
+        }));
+    }
+    opts.insert(DhcpOption::IAAddr(IAAddr {
+        addr: address,
+        preferred_life: lifetimes.preferred,
+        valid_life: lifetimes.valid,
+        opts: Default::default(),
+    }));
+
+    IANA {
+        id: request.id,
+        t1: lifetimes.t1,
+        t2: lifetimes.t2,
+        opts,
+    }
+}
+
+fn ia_pd_reply(request: &IAPD, prefix: Option<Ipv6Network>, lifetimes: &Lifetimes) -> IAPD {
+    let mut opts = DhcpOptions::default();
+    let Some(prefix) = prefix else {
+        opts.insert(status_code(
+            Status::NoPrefixAvail,
+            "no prefix is delegated to this interface",
+        ));
+        return IAPD {
+            id: request.id,
+            t1: 0,
+            t2: 0,
+            opts,
+        };
+    };
+
+    opts.insert(DhcpOption::IAPrefix(IAPrefix {
+        preferred_lifetime: lifetimes.preferred,
+        valid_lifetime: lifetimes.valid,
+        prefix_len: prefix.prefix(),
+        prefix_ip: prefix.network(),
+        opts: Default::default(),
+    }));
+
+    IAPD {
+        id: request.id,
+        t1: lifetimes.t1,
+        t2: lifetimes.t2,
+        opts,
+    }
+}
+
+fn ia_addresses(ia_na: &IANA) -> impl Iterator<Item = Ipv6Addr> + '_ {
+    ia_na.opts.iter().filter_map(|option| match option {
+        DhcpOption::IAAddr(ia_addr) => Some(ia_addr.addr),
+        _ => None,
+    })
+}
+
+fn status_code(status: Status, msg: &str) -> DhcpOption {
+    DhcpOption::StatusCode(StatusCode {
+        status,
+        msg: msg.to_string(),
+    })
+}
+
+fn ntp_servers(servers: &[Ipv6Addr]) -> DhcpOption {
+    let mut data = Vec::with_capacity(servers.len() * 20);
+    for server in servers {
+        data.extend_from_slice(&NTP_SUBOPTION_SRV_ADDR.to_be_bytes());
+        data.extend_from_slice(&16u16.to_be_bytes());
+        data.extend_from_slice(&server.octets());
+    }
+    DhcpOption::Unknown(UnknownOption::new(
+        OptionCode::from(OPTION_NTP_SERVER),
+        data,
+    ))
+}
+
+fn ipv6_prefix(prefix: &str) -> Result<Ipv6Network, DhcpError> {
+    match prefix.parse::<IpNetwork>() {
+        Ok(IpNetwork::V6(prefix)) => Ok(prefix),
+        Ok(IpNetwork::V4(prefix)) => Err(DhcpError::GenericError(format!(
+            "Prefix ({prefix}) is an IPv4 network, which DHCPv6 cannot serve."
+        ))),
+        Err(error) => Err(DhcpError::GenericError(format!(
+            "prefix value in deserialized protobuf is not an IP Network: {error}"
+        ))),
+    }
+}
+
+/// The server's DUID-UUID (RFC 6355). The configured DHCPv6 server address is
+/// already sixteen stable bytes that are unique to this server, so it serves
+/// as the UUID and the identifier survives restarts without extra state.
+fn server_duid(config: &DhcpConfig) -> Result<Vec<u8>, DhcpError> {
+    let server = config.carbide_dhcp_server_v6.ok_or_else(|| {
+        DhcpError::MissingArgument(
+            "carbide_dhcp_server_v6 must be configured to serve DHCPv6".to_string(),
+        )
+    })?;
+    let mut duid = DUID_UUID.to_be_bytes().to_vec();
+    duid.extend_from_slice(&server.octets());
+    Ok(duid)
+}
+
+/// Wrap an encoded reply for the relay that forwarded the request, echoing
+/// the Interface-ID the relay uses to pick the client-facing link.
+fn relay_reply(relay: &RelayForward, reply: &[u8]) -> Vec<u8> {
+    let mut out = vec![RELAY_REPL, relay.hop_count];
+    out.extend_from_slice(&relay.link_address.octets());
+    out.extend_from_slice(&relay.peer_address.octets());
+    if let Some(interface_id) = &relay.interface_id {
+        push_raw_option(&mut out, OptionCode::InterfaceId, interface_id);
+    }
+    push_raw_option(&mut out, OptionCode::RelayMsg, reply);
+    out
+}
+
+fn push_raw_option(out: &mut Vec<u8>, code: OptionCode, data: &[u8]) {
+    out.extend_from_slice(&u16::from(code).to_be_bytes());
+    out.extend_from_slice(&(data.len() as u16).to_be_bytes());
+    out.extend_from_slice(data);
+}
+
+fn raw_options(mut bytes: &[u8]) -> Result<Vec<(u16, &[u8])>, DhcpError> {
+    let mut options = Vec::new();
+    while !bytes.is_empty() {
+        if bytes.len() < 4 {
+            return Err(not_enough_bytes());
+        }
+        let code = u16::from_be_bytes([bytes[0], bytes[1]]);
+        let len = usize::from(u16::from_be_bytes([bytes[2], bytes[3]]));
+        bytes = &bytes[4..];
+        if bytes.len() < len {
+            return Err(not_enough_bytes());
+        }
+        let (data, rest) = bytes.split_at(len);
+        options.push((code, data));
+        bytes = rest;
+    }
+    Ok(options)
+}
+
+fn ipv6_at(bytes: &[u8], offset: usize) -> Ipv6Addr {
+    let mut octets = [0; 16];
+    octets.copy_from_slice(&bytes[offset..offset + 16]);
+    Ipv6Addr::from(octets)
+}
+
+fn not_enough_bytes() -> DhcpError {
+    DhcpError::PacketDecodeFailure(dhcproto::error::DecodeError::NotEnoughBytes)
+}
+
+fn transaction_id(message: &Message) -> i64 {
+    let [a, b, c] = message.xid();
+    i64::from(u32::from_be_bytes([0, a, b, c]))
+}
+
+/// Relay identifiers are hex-encoded the same way as in the Kea hook, so the
+/// API sees one circuit and remote ID whichever server relayed the client.
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Hardware type and address, as carried by DUID-LL and option 79.
+fn ethernet_mac(htype_and_address: &[u8]) -> Option<String> {
+    let (htype, address) = htype_and_address.split_first_chunk::<2>()?;
+    (u16::from_be_bytes(*htype) == HTYPE_ETHERNET && address.len() == ETHERNET_MAC_LEN)
+        .then(|| util::u8_to_mac(address))
+}
+
+fn mac_from_duid(duid: &[u8]) -> Option<String> {
+    let (duid_type, rest) = duid.split_first_chunk::<2>()?;
+    match u16::from_be_bytes(*duid_type) {
+        // DUID-LLT has a four-byte time between hardware type and address.
+        DUID_LLT if rest.len() > 6 => ethernet_mac(&[&rest[..2], &rest[6..]].concat()),
+        DUID_LL => ethernet_mac(rest),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ::rpc::forge_tls_client::ForgeClientConfig;
+    use carbide_rpc_utils::dhcp::{HostConfig, InterfaceInfo, InterfaceInfoV6};
+    use dhcproto::v6::ORO;
+    use tonic::async_trait;
+
+    use super::*;
+    use crate::cache;
+    use crate::modes::dpu::Dpu;
+
+    const SERVER: Ipv6Addr = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0x547);
+    const CLIENT_DUID_LL: &[u8] = &[0, 3, 0, 1, 0x02, 0, 0, 0, 0, 0x01];
+    const RELAY_SOURCE: &str = "[2001:db8:5::1]:547";
+    const LINK_LOCAL_CLIENT: &str = "[fe80::1%2]:546";
+
+    #[derive(Debug)]
+    struct TestRelayed {}
+
+    #[async_trait]
+    impl DhcpMode for TestRelayed {
+        async fn discover_dhcp(
+            &self,
+            discovery_request: DhcpDiscovery,
+            _config: &Config,
+            _machine_cache: &mut Arc<Mutex<LruCache<String, CacheEntry>>>,
+        ) -> Result<DhcpRecord, DhcpError> {
+            assert_eq!(
+                discovery_request.address_family,
+                Some(AddressFamily::V6 as i32)
+            );
+            assert_eq!(discovery_request.mac_address, "02:00:00:00:00:01");
+            assert_eq!(discovery_request.relay_address, "2001:db8:5::1");
+            assert_eq!(
+                discovery_request.circuit_id.as_deref(),
+                Some("657468302e313030")
+            );
+            Ok(DhcpRecord {
+                machine_id: None,
+                machine_interface_id: None,
+                segment_id: None,
+                subdomain_id: None,
+                fqdn: "host.example.com".to_string(),
+                mac_address: discovery_request.mac_address,
+                address: "2001:db8:5::10".to_string(),
+                mtu: 0,
+                prefix: "2001:db8:5::/64".to_string(),
+                gateway: None,
+                booturl: None,
+                last_invalidation_time: None,
+                ntp_servers: vec![],
+            })
+        }
+
+        fn should_be_relayed(&self) -> bool {
+            true
+        }
+    }
+
+    fn test_config() -> Config {
+        let host_config = HostConfig {
+            host_interface_id: "6a3e76cd-e3f3-487a-aea0-04e56b049999".parse().unwrap(),
+            host_ip_addresses: [
+                (
+                    "vlan200".to_string(),
+                    InterfaceInfo {
+                        fqdn: "host.example.com".to_string(),
+                        ipv6: Some(InterfaceInfoV6 {
+                            address: Some("2001:db8:1::10".parse().unwrap()),
+                            prefix: "2001:db8:1::/64".to_string(),
+                            delegated_prefix: Some("2001:db8:100::/56".to_string()),
+                        }),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "vlan300".to_string(),
+                    InterfaceInfo {
+                        fqdn: "slaac.example.com".to_string(),
+                        ipv6: Some(InterfaceInfoV6 {
+                            address: None,
+                            prefix: "2001:db8:3::/64".to_string(),
+                            delegated_prefix: None,
+                        }),
+                        ..Default::default()
+                    },
+                ),
+            ]
+            .into(),
+        };
+
+        Config {
+            dhcp_config: DhcpConfig {
+                carbide_nameservers_v6: vec!["2001:db8::53".parse().unwrap()],
+                carbide_ntpservers_v6: vec!["2001:db8::123".parse().unwrap()],
+                carbide_dhcp_server_v6: Some(SERVER),
+                dhcpv6_preferred_lifetime_secs: 1800,
+                dhcpv6_valid_lifetime_secs: 3600,
+                ..Default::default()
+            },
+            host_config: Some(host_config),
+            relay_response_port: 547,
+            forge_client_config: ForgeClientConfig::new("/dev/null".to_string(), None),
+        }
+    }
+
+    fn client_message(message_type: MessageType, ia_na: bool, ia_pd: bool) -> Message {
+        let mut message = Message::new_with_id(message_type, [0x0a, 0x0b, 0x0c]);
+        message
+            .opts_mut()
+            .insert(DhcpOption::ClientId(CLIENT_DUID_LL.to_vec()));
+        message.opts_mut().insert(DhcpOption::ORO(ORO {
+            opts: vec![OptionCode::DomainNameServers],
+        }));
+        if ia_na {
+            message.opts_mut().insert(DhcpOption::IANA(IANA {
+                id: 7,
+                t1: 0,
+                t2: 0,
+                opts: Default::default(),
+            }));
+        }
+        if ia_pd {
+            message.opts_mut().insert(DhcpOption::IAPD(IAPD {
+                id: 9,
+                t1: 0,
+                t2: 0,
+                opts: Default::default(),
+            }));
+        }
+        message
+    }
+
+    fn encode(message: &Message) -> Vec<u8> {
+        let mut out = Vec::new();
+        message.encode(&mut Encoder::new(&mut out)).unwrap();
+        out
+    }
+
+    fn relay_forward(inner: &[u8]) -> Vec<u8> {
+        let mut out = vec![RELAY_FORW, 0];
+        out.extend_from_slice(&"2001:db8:5::1".parse::<Ipv6Addr>().unwrap().octets());
+        out.extend_from_slice(&"fe80::1".parse::<Ipv6Addr>().unwrap().octets());
+        push_raw_option(&mut out, OptionCode::InterfaceId, b"eth0.100");
+        push_raw_option(&mut out, OptionCode::RelayMsg, inner);
+        out
+    }
+
+    async fn process(
+        buf: &[u8],
+        source_address: &str,
+        circuit_id: &str,
+        handler: &dyn DhcpMode,
+    ) -> Result<PacketV6, DhcpError> {
+        let mut machine_cache = Arc::new(Mutex::new(LruCache::new(
+            std::num::NonZeroUsize::new(cache::MACHINE_CACHE_SIZE).unwrap(),
+        )));
+        process_packet_v6(
+            buf,
+            SocketAddrV6::from_str(source_address).unwrap(),
+            &test_config(),
+            circuit_id,
+            handler,
+            &mut machine_cache,
+        )
+        .await
+    }
+
+    fn decode_reply(packet: &PacketV6) -> Message {
+        Message::decode(&mut Decoder::new(packet.encoded_packet())).unwrap()
+    }
+
+    fn ia_na_of(reply: &Message) -> &IANA {
+        match reply.opts().get(OptionCode::IANA) {
+            Some(DhcpOption::IANA(ia_na)) => ia_na,
+            other => panic!("expected IA_NA, got {other:?}"),
+        }
+    }
+
+    fn ia_pd_of(reply: &Message) -> &IAPD {
+        match reply.opts().get(OptionCode::IAPD) {
+            Some(DhcpOption::IAPD(ia_pd)) => ia_pd,
+            other => panic!("expected IA_PD, got {other:?}"),
+        }
+    }
+
+    fn status_of(opts: &DhcpOptions) -> Option<Status> {
+        match opts.get(OptionCode::StatusCode) {
+            Some(DhcpOption::StatusCode(status)) => Some(status.status),
+            _ => None,
+        }
+    }
+
+    /// A DPU-attached host solicits on-link: it is advertised its configured
+    /// address and delegated prefix, with the server's DUID and lifetimes.
+    #[tokio::test]
+    async fn on_link_solicit_is_advertised_address_and_prefix() {
+        let solicit = encode(&client_message(MessageType::Solicit, true, true));
+
+        let packet = process(&solicit, LINK_LOCAL_CLIENT, "vlan200", &Dpu {})
+            .await
+            .unwrap();
+
+        assert_eq!(packet.message_type(), V6MessageTypeLabel::Advertise);
+        assert_eq!(
+            packet.dst_address(),
+            SocketAddrV6::from_str(LINK_LOCAL_CLIENT).unwrap()
+        );
+        let reply = decode_reply(&packet);
+        assert_eq!(reply.msg_type(), MessageType::Advertise);
+        assert_eq!(reply.xid(), [0x0a, 0x0b, 0x0c]);
+        assert_eq!(
+            reply.opts().get(OptionCode::ServerId),
+            Some(&DhcpOption::ServerId(
+                server_duid(&test_config().dhcp_config).unwrap()
+            ))
+        );
+        assert_eq!(
+            reply.opts().get(OptionCode::ClientId),
+            Some(&DhcpOption::ClientId(CLIENT_DUID_LL.to_vec()))
+        );
+        assert_eq!(
+            reply.opts().get(OptionCode::DomainNameServers),
+            Some(&DhcpOption::DomainNameServers(vec![
+                "2001:db8::53".parse().unwrap()
+            ]))
+        );
+
+        let ia_na = ia_na_of(&reply);
+        assert_eq!(ia_na.id, 7);
+        assert_eq!(
+            ia_addresses(ia_na).collect::<Vec<_>>(),
+            vec!["2001:db8:1::10".parse::<Ipv6Addr>().unwrap()]
+        );
+        match ia_na.opts.get(OptionCode::IAAddr) {
+            Some(DhcpOption::IAAddr(ia_addr)) => {
+                assert_eq!((ia_addr.preferred_life, ia_addr.valid_life), (1800, 3600));
+            }
+            other => panic!("expected IA Address, got {other:?}"),
+        }
+
+        let ia_pd = ia_pd_of(&reply);
+        assert_eq!(ia_pd.id, 9);
+        match ia_pd.opts.get(OptionCode::IAPrefix) {
+            Some(DhcpOption::IAPrefix(prefix)) => {
+                assert_eq!(
+                    prefix.prefix_ip,
+                    "2001:db8:100::".parse::<Ipv6Addr>().unwrap()
+                );
+                assert_eq!(prefix.prefix_len, 56);
+            }
+            other => panic!("expected IA Prefix, got {other:?}"),
+        }
+    }
+
+    /// A SLAAC-only interface has no address or prefix to hand out, so each
+    /// IA carries its own status rather than the exchange failing.
+    #[tokio::test]
+    async fn request_without_configured_address_reports_ia_status() {
+        let request = encode(&client_message(MessageType::Request, true, true));
+
+        let packet = process(&request, LINK_LOCAL_CLIENT, "vlan300", &Dpu {})
+            .await
+            .unwrap();
+
+        assert_eq!(packet.message_type(), V6MessageTypeLabel::Reply);
+        let reply = decode_reply(&packet);
+        assert_eq!(
+            status_of(&ia_na_of(&reply).opts),
+            Some(Status::NoAddrsAvail)
+        );
+        assert_eq!(
+            status_of(&ia_pd_of(&reply).opts),
+            Some(Status::NoPrefixAvail)
+        );
+    }
+
+    /// A renewing client whose address moved keeps hearing about the old one
+    /// with zero lifetimes next to the current allocation.
+    #[tokio::test]
+    async fn renew_of_a_stale_address_expires_it() {
+        let mut renew = client_message(MessageType::Renew, false, false);
+        let mut ia_na = IANA {
+            id: 7,
+            t1: 0,
+            t2: 0,
+            opts: Default::default(),
+        };
+        ia_na.opts.insert(DhcpOption::IAAddr(IAAddr {
+            addr: "2001:db8:1::99".parse().unwrap(),
+            preferred_life: 0,
+            valid_life: 0,
+            opts: Default::default(),
+        }));
+        renew.opts_mut().insert(DhcpOption::IANA(ia_na));
+
+        let packet = process(&encode(&renew), LINK_LOCAL_CLIENT, "vlan200", &Dpu {})
+            .await
+            .unwrap();
+
+        let reply = decode_reply(&packet);
+        let lifetimes = ia_na_of(&reply)
+            .opts
+            .iter()
+            .filter_map(|option| match option {
+                DhcpOption::IAAddr(ia_addr) => Some((ia_addr.addr.to_string(), ia_addr.valid_life)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(lifetimes.contains(&("2001:db8:1::99".to_string(), 0)));
+        assert!(lifetimes.contains(&("2001:db8:1::10".to_string(), 3600)));
+    }
+
+    /// A relayed REQUEST is answered inside a Relay-Reply that echoes the
+    /// relay's addresses and Interface-ID and goes back to the relay.
+    #[tokio::test]
+    async fn relayed_request_is_answered_with_relay_reply() {
+        let request = relay_forward(&encode(&client_message(MessageType::Request, true, false)));
+
+        let packet = process(&request, RELAY_SOURCE, "eth0", &TestRelayed {})
+            .await
+            .unwrap();
+
+        assert_eq!(packet.message_type(), V6MessageTypeLabel::Reply);
+        assert_eq!(
+            packet.dst_address(),
+            SocketAddrV6::from_str("[2001:db8:5::1]:547").unwrap()
+        );
+        let encoded = packet.encoded_packet();
+        assert_eq!(encoded[0], RELAY_REPL);
+        assert_eq!(
+            ipv6_at(encoded, 2),
+            "2001:db8:5::1".parse::<Ipv6Addr>().unwrap()
+        );
+        assert_eq!(ipv6_at(encoded, 18), "fe80::1".parse::<Ipv6Addr>().unwrap());
+        let options = raw_options(&encoded[RELAY_HEADER_LEN..]).unwrap();
+        assert_eq!(
+            options[0],
+            (u16::from(OptionCode::InterfaceId), b"eth0.100".as_slice())
+        );
+        let (_, inner) = options[1];
+        let reply = Message::decode(&mut Decoder::new(inner)).unwrap();
+        assert_eq!(reply.msg_type(), MessageType::Reply);
+        assert_eq!(
+            ia_addresses(ia_na_of(&reply)).collect::<Vec<_>>(),
+            vec!["2001:db8:5::10".parse::<Ipv6Addr>().unwrap()]
+        );
+        // The API does not delegate prefixes, and this client did not ask.
+        assert!(reply.opts().get(OptionCode::IAPD).is_none());
+    }
+
+    #[tokio::test]
+    async fn controller_mode_requires_a_relay() {
+        let solicit = encode(&client_message(MessageType::Solicit, true, false));
+
+        let result = process(&solicit, LINK_LOCAL_CLIENT, "eth0", &TestRelayed {}).await;
+
+        assert!(matches!(result, Err(DhcpError::NonRelayedPacket(_))));
+    }
+
+    #[tokio::test]
+    async fn rejects_nested_relays_and_other_servers() {
+        let inner = relay_forward(&encode(&client_message(MessageType::Solicit, true, false)));
+        let nested = relay_forward(&inner);
+        assert!(matches!(
+            process(&nested, RELAY_SOURCE, "eth0", &TestRelayed {}).await,
+            Err(DhcpError::InvalidInput(_))
+        ));
+
+        let mut request = client_message(MessageType::Request, true, false);
+        request
+            .opts_mut()
+            .insert(DhcpOption::ServerId(vec![0, 3, 0, 1, 2, 0, 0, 0, 0, 9]));
+        assert!(matches!(
+            process(&encode(&request), LINK_LOCAL_CLIENT, "vlan200", &Dpu {}).await,
+            Err(DhcpError::NotMyPacket(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn confirm_checks_addresses_against_the_link_prefix() {
+        for (address, expected) in [
+            ("2001:db8:1::10", Status::Success),
+            ("2001:db8:2::10", Status::NotOnLink),
+        ] {
+            let mut confirm = client_message(MessageType::Confirm, false, false);
+            let mut ia_na = IANA {
+                id: 7,
+                t1: 0,
+                t2: 0,
+                opts: Default::default(),
+            };
+            ia_na.opts.insert(DhcpOption::IAAddr(IAAddr {
+                addr: address.parse().unwrap(),
+                preferred_life: 0,
+                valid_life: 0,
+                opts: Default::default(),
+            }));
+            confirm.opts_mut().insert(DhcpOption::IANA(ia_na));
+
+            let packet = process(&encode(&confirm), LINK_LOCAL_CLIENT, "vlan200", &Dpu {})
+                .await
+                .unwrap();
+
+            let reply = decode_reply(&packet);
+            assert_eq!(status_of(reply.opts()), Some(expected), "{address}");
+            assert!(reply.opts().get(OptionCode::IANA).is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn rapid_commit_solicit_gets_a_reply() {
+        let mut solicit = client_message(MessageType::Solicit, true, false);
+        solicit.opts_mut().insert(DhcpOption::RapidCommit);
+
+        let packet = process(&encode(&solicit), LINK_LOCAL_CLIENT, "vlan200", &Dpu {})
+            .await
+            .unwrap();
+
+        let reply = decode_reply(&packet);
+        assert_eq!(reply.msg_type(), MessageType::Reply);
+        assert!(reply.opts().get(OptionCode::RapidCommit).is_some());
+    }
+
+    #[test]
+    fn extracts_client_mac_from_duid_and_option79() {
+        assert_eq!(
+            mac_from_duid(CLIENT_DUID_LL).as_deref(),
+            Some("02:00:00:00:00:01")
+        );
+        assert_eq!(
+            mac_from_duid(&[0, 1, 0, 1, 0xaa, 0xbb, 0xcc, 0xdd, 0x02, 0, 0, 0, 0, 0x02]).as_deref(),
+            Some("02:00:00:00:00:02")
+        );
+        assert_eq!(mac_from_duid(&[0, 4, 1, 2, 3, 4]), None);
+        assert_eq!(
+            ethernet_mac(&[0, 1, 0x02, 0xaa, 0xbb, 0xcc, 0xdd, 0xee]).as_deref(),
+            Some("02:aa:bb:cc:dd:ee")
+        );
+        assert_eq!(ethernet_mac(&[0, 6, 1, 2, 3, 4, 5, 6]), None);
+    }
+
+    #[test]
+    fn unset_dhcpv6_lifetimes_fall_back_to_the_dhcpv4_timers() {
+        let lifetimes = Lifetimes::from_config(&DhcpConfig::default());
+        assert_eq!(
+            (
+                lifetimes.t1,
+                lifetimes.t2,
+                lifetimes.preferred,
+                lifetimes.valid
+            ),
+            (3600, 432000, 604800, 604800)
+        );
+    }
+}
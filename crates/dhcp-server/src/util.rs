@@ -14,6 +14,8 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::net::{Ipv6Addr, SocketAddr};
+
 use carbide_dhcp_common::{MachineArchitecture, VendorClass};
 use carbide_instrument::emit;
 use rpc::forge::DhcpRecord;
@@ -27,12 +29,14 @@ use crate::metrics::{
 
 const SOCKET_SETUP_ATTEMPTS: i32 = 10;
 const INTERFACE_BIND_ATTEMPTS: i32 = 10;
+/// All_DHCP_Relay_Agents_and_Servers (RFC 8415 section 7.1).
+const ALL_DHCP_RELAY_AGENTS_AND_SERVERS: Ipv6Addr = Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 1, 2);
 
 fn open_configured_socket(
-    listen_address: core::net::SocketAddrV4,
+    listen_address: SocketAddr,
 ) -> Result<socket2::Socket, (SocketSetupOperation, std::io::Error)> {
     let socket = socket2::Socket::new(
-        socket2::Domain::IPV4,
+        socket2::Domain::for_address(listen_address),
         socket2::Type::DGRAM,
         Some(socket2::Protocol::UDP),
     )
@@ -48,13 +52,23 @@ fn open_configured_socket(
         .bind(&listen_address.into())
         .map_err(|error| (SocketSetupOperation::BindAddress, error))?;
     // SO_BROADCAST permits sends to broadcast addresses; receiving does not require it.
-    socket
-        .set_broadcast(true)
-        .map_err(|error| (SocketSetupOperation::SetBroadcast, error))?;
+    // DHCPv6 has no broadcast, so the option only applies to IPv4 sockets.
+    if listen_address.is_ipv4() {
+        socket
+            .set_broadcast(true)
+            .map_err(|error| (SocketSetupOperation::SetBroadcast, error))?;
+    }
 
     Ok(socket)
 }
 
+/// On-link DHCPv6 clients multicast to ff02::1:2 instead of addressing the
+/// server, so an IPv6 socket joins that group on its interface.
+fn join_dhcpv6_multicast(socket: &socket2::Socket, interface: &str) -> std::io::Result<()> {
+    let interface_index = nix::net::if_::if_nametoindex(interface)?;
+    socket.join_multicast_v6(&ALL_DHCP_RELAY_AGENTS_AND_SERVERS, interface_index)
+}
+
 fn socket_setup_next_action(retry: i32) -> SocketSetupNextAction {
     if retry + 1 == SOCKET_SETUP_ATTEMPTS {
         SocketSetupNextAction::Panic
@@ -122,10 +136,7 @@ pub(super) fn machine_get_filename(
 }
 
 /// Create a UDP socket and set non_blocking, broadcast and other options flag on it.
-pub(super) async fn get_socket(
-    listen_address: core::net::SocketAddrV4,
-    interface: String,
-) -> UdpSocket {
+pub(super) async fn get_socket(listen_address: SocketAddr, interface: String) -> UdpSocket {
     for retry in 0..SOCKET_SETUP_ATTEMPTS {
         // Create a socket2 socket because std and Tokio sockets do not expose
         // the options that must be set before binding.
@@ -161,6 +172,19 @@ pub(super) async fn get_socket(
             panic!("Cannot bind interface {interface}.");
         }
 
+        if listen_address.is_ipv6()
+            && let Err(error) = join_dhcpv6_multicast(&socket, &interface)
+        {
+            emit(DhcpSocketSetupFailed::new(
+                SocketSetupOperation::JoinMulticast,
+                socket_setup_next_action(retry),
+                retry,
+                error.to_string(),
+            ));
+            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+            continue;
+        }
+
         // Now create tokio UDPSocket from socket2, which has all needed advanced options set.
         return UdpSocket::from_std(socket.into()).unwrap();
     }
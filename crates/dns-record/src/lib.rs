@@ -22,6 +22,7 @@ use serde::{Deserialize, Serialize};
 use tracing::debug;
 
 pub mod constants;
+pub mod rdata;
 
 /// Wrapper type for time intervals in seconds
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
@@ -72,6 +73,7 @@ pub enum DnsResourceRecordType {
     MX,
     TXT,
     PTR,
+    SRV,
 }
 
 impl Display for DnsResourceRecordType {
@@ -85,6 +87,7 @@ impl Display for DnsResourceRecordType {
             DnsResourceRecordType::MX => constants::DNS_TYPE_MX,
             DnsResourceRecordType::TXT => constants::DNS_TYPE_TXT,
             DnsResourceRecordType::PTR => constants::DNS_TYPE_PTR,
+            DnsResourceRecordType::SRV => constants::DNS_TYPE_SRV,
         };
         write!(f, "{record_type}")
     }
@@ -229,6 +232,7 @@ impl TryFrom<&str> for DnsResourceRecordType {
             constants::DNS_TYPE_MX => Ok(DnsResourceRecordType::MX),
             constants::DNS_TYPE_TXT => Ok(DnsResourceRecordType::TXT),
             constants::DNS_TYPE_PTR => Ok(DnsResourceRecordType::PTR),
+            constants::DNS_TYPE_SRV => Ok(DnsResourceRecordType::SRV),
             _ => Err(format!("RecordType {value} not implement")),
         }
     }
@@ -247,6 +251,7 @@ impl TryFrom<String> for DnsResourceRecordType {
             constants::DNS_TYPE_MX => Ok(DnsResourceRecordType::MX),
             constants::DNS_TYPE_TXT => Ok(DnsResourceRecordType::TXT),
             constants::DNS_TYPE_PTR => Ok(DnsResourceRecordType::PTR),
+            constants::DNS_TYPE_SRV => Ok(DnsResourceRecordType::SRV),
             _ => Err(format!("RecordType {value} not implement")),
         }
     }
@@ -263,6 +268,7 @@ impl From<DnsResourceRecordType> for String {
             DnsResourceRecordType::MX => constants::DNS_TYPE_MX.to_string(),
             DnsResourceRecordType::TXT => constants::DNS_TYPE_TXT.to_string(),
             DnsResourceRecordType::PTR => constants::DNS_TYPE_PTR.to_string(),
+            DnsResourceRecordType::SRV => constants::DNS_TYPE_SRV.to_string(),
         }
     }
 }
@@ -502,6 +508,11 @@ mod tests {
                     display: "PTR".to_string(),
                     string: "PTR".to_string(),
                 }),
+                RecordTypeInput::Owned("SRV") => Yields(RecordTypeSummary {
+                    record_type: DnsResourceRecordType::SRV,
+                    display: "SRV".to_string(),
+                    string: "SRV".to_string(),
+                }),
             }
 
             "unknown record types" {
@@ -0,0 +1,219 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Parsing of record content in presentation format, shared by carbide-api,
+//! which validates custom records before storing them, and carbide-dns, which
+//! serves them.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use hickory_proto::rr::rdata::{CNAME, MX, NS, PTR, SOA, SRV, TXT};
+use hickory_proto::rr::{Name, RData};
+
+use crate::DnsResourceRecordType;
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("{qtype} content {content:?} is invalid: {reason}")]
+pub struct RdataParseError {
+    pub qtype: DnsResourceRecordType,
+    pub content: String,
+    pub reason: String,
+}
+
+/// Builds the hickory `RData` for a record type from its `content`.
+pub fn parse_rdata(qtype: DnsResourceRecordType, content: &str) -> Result<RData, RdataParseError> {
+    let invalid = |reason: &dyn std::fmt::Display| RdataParseError {
+        qtype,
+        content: content.to_string(),
+        reason: reason.to_string(),
+    };
+    let name = |content: &str| content.parse::<Name>().map_err(|e| invalid(&e));
+    match qtype {
+        DnsResourceRecordType::A => content
+            .parse::<Ipv4Addr>()
+            .map(|ip| RData::A(ip.into()))
+            .map_err(|e| invalid(&e)),
+        DnsResourceRecordType::AAAA => content
+            .parse::<Ipv6Addr>()
+            .map(|ip| RData::AAAA(ip.into()))
+            .map_err(|e| invalid(&e)),
+        // The content is the target FQDN; PTR is a name, unlike the address-valued
+        // A/AAAA records.
+        DnsResourceRecordType::PTR => name(content).map(|name| RData::PTR(PTR(name))),
+        DnsResourceRecordType::CNAME => name(content).map(|name| RData::CNAME(CNAME(name))),
+        DnsResourceRecordType::NS => name(content).map(|name| RData::NS(NS(name))),
+        // The remaining types carry several fields in presentation format.
+        DnsResourceRecordType::MX => parse_mx(content)
+            .map(RData::MX)
+            .ok_or_else(|| invalid(&"expected `preference exchange`")),
+        DnsResourceRecordType::TXT => parse_txt(content)
+            .map(RData::TXT)
+            .ok_or_else(|| invalid(&"expected quoted character strings")),
+        DnsResourceRecordType::SRV => parse_srv(content)
+            .map(RData::SRV)
+            .ok_or_else(|| invalid(&"expected `priority weight port target`")),
+        DnsResourceRecordType::SOA => parse_soa(content)
+            .map(RData::SOA)
+            .ok_or_else(|| invalid(&"expected `mname rname serial refresh retry expire minimum`")),
+    }
+}
+
+/// `preference exchange`, e.g. `10 mail.example.com.`.
+fn parse_mx(content: &str) -> Option<MX> {
+    let [preference, exchange] = content.split_whitespace().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    Some(MX::new(preference.parse().ok()?, exchange.parse().ok()?))
+}
+
+/// `priority weight port target`, e.g. `10 5 5060 sip.example.com.`.
+fn parse_srv(content: &str) -> Option<SRV> {
+    let [priority, weight, port, target] = content.split_whitespace().collect::<Vec<_>>()[..]
+    else {
+        return None;
+    };
+    Some(SRV::new(
+        priority.parse().ok()?,
+        weight.parse().ok()?,
+        port.parse().ok()?,
+        target.parse().ok()?,
+    ))
+}
+
+/// `mname rname serial refresh retry expire minimum`, the form
+/// [`crate::SoaRecord`] renders.
+fn parse_soa(content: &str) -> Option<SOA> {
+    let [mname, rname, serial, refresh, retry, expire, minimum] =
+        content.split_whitespace().collect::<Vec<_>>()[..]
+    else {
+        return None;
+    };
+    Some(SOA::new(
+        mname.parse().ok()?,
+        rname.parse().ok()?,
+        serial.parse().ok()?,
+        refresh.parse().ok()?,
+        retry.parse().ok()?,
+        expire.parse().ok()?,
+        minimum.parse().ok()?,
+    ))
+}
+
+/// One or more double-quoted character strings (`"a" "b"`), with `\"` and `\\`
+/// escapes; unquoted content is taken as a single string. An unterminated
+/// quote does not parse.
+fn parse_txt(content: &str) -> Option<TXT> {
+    let content = content.trim();
+    if !content.starts_with('"') {
+        return Some(TXT::new(vec![content.to_string()]));
+    }
+
+    let mut strings = Vec::new();
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => continue,
+            '"' => {
+                let mut current = String::new();
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => current.push(chars.next()?),
+                        c => current.push(c),
+                    }
+                }
+                strings.push(current);
+            }
+            _ => return None,
+        }
+    }
+    Some(TXT::new(strings))
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::value_scenarios;
+
+    use super::*;
+
+    #[test]
+    fn parse_rdata_builds_supported_types_and_rejects_unparseable() {
+        value_scenarios!(
+            run = |(qtype, content): (DnsResourceRecordType, &str)| parse_rdata(qtype, content).ok();
+            "supported types build the matching RData" {
+                (DnsResourceRecordType::A, "192.0.2.1")
+                    => Some(RData::A(Ipv4Addr::new(192, 0, 2, 1).into())),
+                (DnsResourceRecordType::AAAA, "fd00::1")
+                    => Some(RData::AAAA("fd00::1".parse::<Ipv6Addr>().unwrap().into())),
+                // A PTR's content is the target FQDN, which round-trips into the RData.
+                (DnsResourceRecordType::PTR, "host.example.com.")
+                    => Some(RData::PTR(PTR("host.example.com.".parse::<Name>().unwrap()))),
+            }
+            "name-valued types parse their target" {
+                (DnsResourceRecordType::CNAME, "host.example.com.")
+                    => Some(RData::CNAME(CNAME("host.example.com.".parse::<Name>().unwrap()))),
+                (DnsResourceRecordType::NS, "ns1.example.com.")
+                    => Some(RData::NS(NS("ns1.example.com.".parse::<Name>().unwrap()))),
+            }
+            "multi-field types parse their presentation format" {
+                (DnsResourceRecordType::MX, "10 mail.example.com.")
+                    => Some(RData::MX(MX::new(10, "mail.example.com.".parse().unwrap()))),
+                (DnsResourceRecordType::SRV, "10 5 5060 sip.example.com.")
+                    => Some(RData::SRV(SRV::new(10, 5, 5060, "sip.example.com.".parse().unwrap()))),
+                // The form `SoaRecord` renders.
+                (
+                    DnsResourceRecordType::SOA,
+                    "ns1.example.com. hostmaster.example.com. 2024110401 3600 600 604800 3600",
+                ) => Some(RData::SOA(SOA::new(
+                    "ns1.example.com.".parse().unwrap(),
+                    "hostmaster.example.com.".parse().unwrap(),
+                    2024110401,
+                    3600,
+                    600,
+                    604800,
+                    3600,
+                ))),
+                (DnsResourceRecordType::TXT, r#""v=spf1 -all" "second \"quoted\"""#)
+                    => Some(RData::TXT(TXT::new(vec![
+                        "v=spf1 -all".to_string(),
+                        r#"second "quoted""#.to_string(),
+                    ]))),
+                (DnsResourceRecordType::TXT, "unquoted text")
+                    => Some(RData::TXT(TXT::new(vec!["unquoted text".to_string()]))),
+            }
+            "unparseable content is rejected rather than panicked on" {
+                (DnsResourceRecordType::A, "not-an-ip") => None,
+                (DnsResourceRecordType::AAAA, "192.0.2.1") => None,
+                // A label is at most 63 characters.
+                (DnsResourceRecordType::CNAME, "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.example.com.") => None,
+                (DnsResourceRecordType::SRV, "10 5 sip.example.com.") => None,
+                (DnsResourceRecordType::MX, "mail.example.com. 10") => None,
+                (DnsResourceRecordType::SOA, "unused") => None,
+                (DnsResourceRecordType::TXT, r#""unterminated"#) => None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_errors_name_the_type_and_expected_form() {
+        let error = parse_rdata(DnsResourceRecordType::MX, "mail.example.com. 10").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"MX content "mail.example.com. 10" is invalid: expected `preference exchange`"#
+        );
+    }
+}
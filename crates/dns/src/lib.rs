@@ -18,9 +18,12 @@
 //! Carbide DNS Server
 //!
 //! Listens directly on a DNS port (UDP/TCP) and resolves queries by forwarding
-//! them to carbide-api via the `lookup_record` RPC.
+//! them to carbide-api via the `lookup_record` RPC. Zone transfers (AXFR/IXFR)
+//! are served over TCP from the `get_all_records_for_domain` RPC, to clients the
+//! domain's `allow_axfr_from` metadata lists.
 
 use std::iter;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -28,18 +31,21 @@ use carbide_instrument::{Event, LabelValue, emit};
 use dns_record::DnsResourceRecordType;
 use eyre::Report;
 use hickory_resolver::proto::op::ResponseCode;
-use hickory_resolver::proto::rr::rdata::PTR;
 use hickory_resolver::proto::rr::{DNSClass, Name, RData, RecordType};
 use hickory_server::net::runtime::Time;
 use hickory_server::proto::op::Metadata;
 use hickory_server::proto::rr::Record;
 use hickory_server::server::{Request, RequestHandler, ResponseHandler, ResponseInfo};
 use hickory_server::zone_handler::MessageResponseBuilder;
+use ipnetwork::IpNetwork;
 use metrics_endpoint::{MetricsEndpointConfig, new_metrics_setup, run_metrics_endpoint};
 use opentelemetry::StringValue;
 use opentelemetry::metrics::{Counter, Meter, ObservableGauge};
 use rpc::forge_tls_client::{ApiConfig, ForgeClientT, ForgeTlsClient};
-use rpc::protos::dns::DnsResourceRecordLookupRequest;
+use rpc::protos::dns::{
+    DnsResourceRecord, DnsResourceRecordLookupRequest, DomainMetadataRequest,
+    GetAllRecordsForDomainRequest,
+};
 use tokio::net::{TcpListener, UdpSocket};
 use tokio::sync::Mutex;
 use tracing::{Instrument, error, info, warn};
@@ -50,6 +56,10 @@ mod negative_cache;
 use crate::config::Config;
 use crate::negative_cache::{CacheKey, NegativeCache};
 
+/// Records per message of a zone transfer. Kept well under what fits the 64KiB
+/// TCP message limit with long owner names, even before name compression.
+const ZONE_TRANSFER_RECORDS_PER_MESSAGE: usize = 100;
+
 struct DnsMetrics {
     negative_cache_eviction: Counter<u64>,
     // Observable gauge of current cache occupancy: its callback reads the
@@ -128,41 +138,44 @@ fn classify_failure(code: tonic::Code) -> NegativeClassification {
     NegativeClassification { code, transient }
 }
 
-/// Builds the hickory `RData` for a supported record type from the API's string
+/// Builds the hickory `RData` for a record type from the API's string
 /// `content`, logging and dropping the record when the content does not parse.
-/// `handle_request` only dispatches the supported types here, so any other qtype
-/// yields `None`.
 fn content_to_rdata(qtype: DnsResourceRecordType, content: &str) -> Option<RData> {
-    match qtype {
-        DnsResourceRecordType::A => match content.parse::<std::net::Ipv4Addr>() {
-            Ok(ip) => Some(RData::A(ip.into())),
-            Err(e) => {
-                warn!(%content, error = %e, "Failed to parse IPv4 address");
-                None
-            }
-        },
-        DnsResourceRecordType::AAAA => match content.parse::<std::net::Ipv6Addr>() {
-            Ok(ip) => Some(RData::AAAA(ip.into())),
-            Err(e) => {
-                warn!(%content, error = %e, "Failed to parse IPv6 address");
-                None
-            }
-        },
-        // The content is the target FQDN; PTR is a name, unlike the address-valued
-        // A/AAAA records.
-        DnsResourceRecordType::PTR => match content.parse::<Name>() {
-            Ok(name) => Some(RData::PTR(PTR(name))),
+    dns_record::rdata::parse_rdata(qtype, content)
+        .inspect_err(|error| warn!(%error, "Failed to parse record content"))
+        .ok()
+}
+
+/// Builds an `IN`-class record. hickory infers the record type from the rdata;
+/// the class is set explicitly since `from_rdata` defaults it.
+fn in_record(name: Name, ttl: u32, rdata: RData) -> Record {
+    let mut record = Record::from_rdata(name, ttl, rdata);
+    record.dns_class = DNSClass::IN;
+    record
+}
+
+/// Whether `client` may transfer a zone whose metadata lists `allow_axfr_from`:
+/// addresses or CIDR ranges, one per entry or comma-separated. An empty list
+/// allows nobody.
+fn transfer_allowed(allow_axfr_from: &[String], client: IpAddr) -> bool {
+    // The server listens dual-stack, so IPv4 clients arrive IPv4-mapped.
+    let client = client.to_canonical();
+    allow_axfr_from
+        .iter()
+        .flat_map(|entry| entry.split(','))
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| match entry.parse::<IpNetwork>() {
+            Ok(network) => network.contains(client),
             Err(e) => {
-                warn!(%content, error = %e, "Failed to parse PTR target name");
-                None
+                warn!(entry, error = %e, "Ignoring unparseable allow_axfr_from entry");
+                false
             }
-        },
-        _ => None,
-    }
+        })
 }
 
 /// The query-type metric label, bounded by construction: the types the server
-/// resolves each get their own value, and everything else — the types answered
+/// resolves or transfers each get their own value, and everything else — the types answered
 /// with NotImp, and requests whose question section the handler cannot read
 /// (zero or multiple questions) — collapses into `Other`. Wire-level garbage
 /// never reaches the handler, so it is out of scope for these counters.
@@ -171,6 +184,14 @@ enum Qtype {
     A,
     Aaaa,
     Ptr,
+    Cname,
+    Ns,
+    Soa,
+    Mx,
+    Txt,
+    Srv,
+    Axfr,
+    Ixfr,
     Other,
 }
 
@@ -180,6 +201,14 @@ impl From<RecordType> for Qtype {
             RecordType::A => Qtype::A,
             RecordType::AAAA => Qtype::Aaaa,
             RecordType::PTR => Qtype::Ptr,
+            RecordType::CNAME => Qtype::Cname,
+            RecordType::NS => Qtype::Ns,
+            RecordType::SOA => Qtype::Soa,
+            RecordType::MX => Qtype::Mx,
+            RecordType::TXT => Qtype::Txt,
+            RecordType::SRV => Qtype::Srv,
+            RecordType::AXFR => Qtype::Axfr,
+            RecordType::IXFR => Qtype::Ixfr,
             _ => Qtype::Other,
         }
     }
@@ -380,16 +409,60 @@ impl RequestHandler for DnsServer {
         async move {
             let start = Instant::now();
 
-            // Only handle types that DnsResourceRecordType supports and that we can build
-            // RData for; return NotImp for everything else. Currently A, AAAA, and PTR
-            // are supported; add arms here as the API and RData parsing are extended.
+            // A zone transfer answers with the whole zone rather than one RRset,
+            // and is never negatively cached.
+            if matches!(qtype, RecordType::AXFR | RecordType::IXFR) {
+                let (response_code, records) = self
+                    .transfer_zone(
+                        &Name::from(request_info.query.name()),
+                        qtype,
+                        request_info.src,
+                        request_info.protocol.is_datagram(),
+                    )
+                    .await;
+
+                let duration = start.elapsed();
+                emit(DnsRequestCompleted {
+                    qtype: qtype_label,
+                    rcode: Rcode::from(response_code),
+                    response_code: format!("{response_code:?}"),
+                    record_count: i64::try_from(records.len()).unwrap_or(i64::MAX),
+                    duration_milliseconds: duration.as_millis() as f64,
+                    took: duration,
+                });
+
+                // A zone may not fit one message; over TCP the transfer is a
+                // sequence of messages, each answering part of the zone.
+                let chunks = if records.is_empty() {
+                    vec![&records[..]]
+                } else {
+                    records.chunks(ZONE_TRANSFER_RECORDS_PER_MESSAGE).collect()
+                };
+                let mut response_info = None;
+                for chunk in chunks {
+                    let mut response_header =
+                        Metadata::response_from_request(request_info.metadata);
+                    response_header.response_code = response_code;
+                    let message = MessageResponseBuilder::from_message_request(request).build(
+                        response_header,
+                        chunk.iter(),
+                        iter::empty(),
+                        iter::empty(),
+                        iter::empty(),
+                    );
+                    response_info = Some(response_handle.send_response(message).await.unwrap());
+                }
+                emit(DnsResponseSent {
+                    rcode: Rcode::from(response_code),
+                });
+                return response_info.expect("a zone transfer sends at least one message");
+            }
+
+            // Only handle types that DnsResourceRecordType supports; return NotImp
+            // for everything else.
             let dns_qtype = match DnsResourceRecordType::try_from(qtype.to_string().as_str()) {
-                Ok(
-                    t @ (DnsResourceRecordType::A
-                    | DnsResourceRecordType::AAAA
-                    | DnsResourceRecordType::PTR),
-                ) => t,
-                _ => {
+                Ok(t) => t,
+                Err(_) => {
                     warn!(%qname, %qtype, "Unsupported query type");
                     let response = MessageResponseBuilder::from_message_request(request);
                     let response_info = response_handle
@@ -566,18 +639,27 @@ impl DnsServer {
             "API lookup completed"
         );
 
+        // The API returns all record types for the qname; keep only the requested
+        // type. A name with none of that type but a CNAME answers with the CNAME,
+        // which the client follows to its target.
+        let record_type =
+            |r: &DnsResourceRecord| DnsResourceRecordType::try_from(r.qtype.as_str()).ok();
+        let answer_type = if response
+            .records
+            .iter()
+            .any(|r| record_type(r) == Some(qtype))
+        {
+            qtype
+        } else {
+            DnsResourceRecordType::CNAME
+        };
         let records = response
             .records
             .into_iter()
-            // The API returns all record types for the qname; keep only the requested type.
-            .filter(|r| DnsResourceRecordType::try_from(r.qtype.as_str()).ok() == Some(qtype))
+            .filter(|r| record_type(r) == Some(answer_type))
             .filter_map(|r| {
-                let rdata = content_to_rdata(qtype, &r.content)?;
-                // hickory infers the record type from the rdata; set the class
-                // explicitly since `from_rdata` defaults it.
-                let mut record = Record::from_rdata(record_name.clone(), r.ttl, rdata);
-                record.dns_class = DNSClass::IN;
-                Some(record)
+                let rdata = content_to_rdata(answer_type, &r.content)?;
+                Some(in_record(record_name.clone(), r.ttl, rdata))
             })
             .collect::<Vec<_>>();
 
@@ -596,6 +678,111 @@ impl DnsServer {
         Ok(records)
     }
 
+    /// Answers an AXFR or IXFR for `zone`: its SOA, every record, then the SOA
+    /// again. IXFR is answered with a full transfer (RFC 1995 section 4), since
+    /// carbide-api keeps no per-serial history to build an incremental one from.
+    ///
+    /// AXFR is only served over TCP. An IXFR over UDP is answered with the SOA
+    /// alone, which tells the secondary to retry over TCP.
+    async fn transfer_zone(
+        &self,
+        zone: &Name,
+        qtype: RecordType,
+        src: SocketAddr,
+        over_udp: bool,
+    ) -> (ResponseCode, Vec<Record>) {
+        if over_udp && qtype == RecordType::AXFR {
+            warn!(%src, "Refusing AXFR over UDP");
+            return (ResponseCode::Refused, vec![]);
+        }
+
+        let client = {
+            let guard = self.forge_client.lock().await;
+            guard.clone()
+        };
+        let result = match tokio::time::timeout(
+            self.upstream_lookup_timeout,
+            Self::retrieve_zone(client, zone, src.ip()),
+        )
+        .await
+        {
+            Ok(inner) => inner,
+            Err(_elapsed) => Err(tonic::Status::deadline_exceeded(format!(
+                "upstream zone transfer exceeded {}s",
+                self.upstream_lookup_timeout.as_secs()
+            ))),
+        };
+
+        match result {
+            Ok((soa, _)) if over_udp => (ResponseCode::NoError, vec![soa]),
+            Ok((soa, records)) => {
+                info!(record_count = records.len(), "Zone transfer succeeded");
+                let mut transfer = Vec::with_capacity(records.len() + 2);
+                transfer.push(soa.clone());
+                transfer.extend(records);
+                transfer.push(soa);
+                (ResponseCode::NoError, transfer)
+            }
+            Err(e) => {
+                let NegativeClassification { code, .. } = classify_failure(e.code());
+                warn!(%src, error = %e, response_code = %code, "Zone transfer failed");
+                (code, vec![])
+            }
+        }
+    }
+
+    /// Fetches a zone for transfer to `client`, returning its SOA and the
+    /// remaining records. The client must be listed in the domain's
+    /// `allow_axfr_from` metadata.
+    #[tracing::instrument(level = "debug", skip_all, fields(zone = %zone))]
+    async fn retrieve_zone(
+        mut forge_client: ForgeClientT,
+        zone: &Name,
+        client: IpAddr,
+    ) -> Result<(Record, Vec<Record>), tonic::Status> {
+        let metadata = forge_client
+            .get_all_domain_metadata(tonic::Request::new(DomainMetadataRequest {
+                domain: zone.to_string(),
+            }))
+            .await?
+            .into_inner()
+            .result
+            .unwrap_or_default();
+        if !transfer_allowed(&metadata.allow_axfr_from, client) {
+            return Err(tonic::Status::permission_denied(format!(
+                "{client} may not transfer {zone}"
+            )));
+        }
+
+        let response = forge_client
+            .get_all_records_for_domain(tonic::Request::new(GetAllRecordsForDomainRequest {
+                name: zone.to_string(),
+            }))
+            .await?
+            .into_inner();
+
+        let mut records = response.result.into_iter().filter_map(|r| {
+            let qtype = DnsResourceRecordType::try_from(r.qtype.as_str()).ok()?;
+            let name = match r.qname.parse::<Name>() {
+                Ok(name) => name,
+                Err(e) => {
+                    warn!(qname = %r.qname, error = %e, "Failed to parse record name");
+                    return None;
+                }
+            };
+            let rdata = content_to_rdata(qtype, &r.content)?;
+            Some((qtype, in_record(name, r.ttl, rdata)))
+        });
+
+        // The API lists the SOA first; without one there is no zone to transfer.
+        let Some((DnsResourceRecordType::SOA, soa)) = records.next() else {
+            return Err(tonic::Status::internal(format!("zone {zone} has no SOA")));
+        };
+        let records = records.map(|(_, record)| record).collect();
+
+        Ok((soa, records))
+    }
+
     pub async fn run(config: Config) -> Result<(), Report> {
         let listen = config.listen_address;
 
@@ -739,28 +926,33 @@ mod tests {
     }
 
     #[test]
-    fn content_to_rdata_builds_supported_types_and_drops_unparseable() {
-        use std::net::{Ipv4Addr, Ipv6Addr};
-
+    fn transfer_is_allowed_only_from_listed_clients() {
         use carbide_test_support::value_scenarios;
 
+        let allow_axfr_from = |entries: &[&str]| {
+            entries
+                .iter()
+                .map(|entry| entry.to_string())
+                .collect::<Vec<_>>()
+        };
+
         value_scenarios!(
-            run = |(qtype, content): (DnsResourceRecordType, &str)| content_to_rdata(qtype, content);
-            "supported types build the matching RData" {
-                (DnsResourceRecordType::A, "192.0.2.1")
-                    => Some(RData::A(Ipv4Addr::new(192, 0, 2, 1).into())),
-                (DnsResourceRecordType::AAAA, "fd00::1")
-                    => Some(RData::AAAA("fd00::1".parse::<Ipv6Addr>().unwrap().into())),
-                // A PTR's content is the target FQDN, which round-trips into the RData.
-                (DnsResourceRecordType::PTR, "host.example.com.")
-                    => Some(RData::PTR(PTR("host.example.com.".parse::<Name>().unwrap()))),
-            }
-            "unparseable content is dropped rather than panicked on" {
-                (DnsResourceRecordType::A, "not-an-ip") => None,
-                (DnsResourceRecordType::AAAA, "192.0.2.1") => None,
+            run = |(entries, client): (&[&str], &str)| {
+                transfer_allowed(&allow_axfr_from(entries), client.parse().unwrap())
+            };
+            "listed addresses and ranges may transfer" {
+                (&["192.0.2.10"][..], "192.0.2.10") => true,
+                (&["192.0.2.0/24"][..], "192.0.2.77") => true,
+                (&["2001:db8::/64"][..], "2001:db8::53") => true,
+                // Entries may also arrive comma-separated in one string.
+                (&["198.51.100.1, 192.0.2.0/24"][..], "192.0.2.1") => true,
+                // IPv4 clients of the dual-stack listener arrive IPv4-mapped.
+                (&["192.0.2.0/24"][..], "::ffff:192.0.2.1") => true,
             }
-            "a type the gate never dispatches here yields nothing" {
-                (DnsResourceRecordType::SOA, "unused") => None,
+            "anyone else is refused" {
+                (&[][..], "192.0.2.10") => false,
+                (&["192.0.2.0/24"][..], "198.51.100.1") => false,
+                (&["not-an-address", "192.0.2.10"][..], "192.0.2.11") => false,
             }
         );
     }
@@ -775,12 +967,20 @@ mod tests {
                 RecordType::A => Qtype::A,
                 RecordType::AAAA => Qtype::Aaaa,
                 RecordType::PTR => Qtype::Ptr,
+                RecordType::CNAME => Qtype::Cname,
+                RecordType::NS => Qtype::Ns,
+                RecordType::SOA => Qtype::Soa,
+                RecordType::MX => Qtype::Mx,
+                RecordType::TXT => Qtype::Txt,
+                RecordType::SRV => Qtype::Srv,
+            }
+            "zone transfers" {
+                RecordType::AXFR => Qtype::Axfr,
+                RecordType::IXFR => Qtype::Ixfr,
             }
             "everything else collapses into Other" {
-                RecordType::MX => Qtype::Other,
-                RecordType::SOA => Qtype::Other,
-                RecordType::TXT => Qtype::Other,
                 RecordType::ANY => Qtype::Other,
+                RecordType::CAA => Qtype::Other,
             }
         );
     }
@@ -56,6 +56,10 @@ pub struct Config {
     /// Total number of shards in the StatefulSet
     pub shards_count: usize,
 
+    /// Runtime shard membership. When enabled, `shard` and `shards_count`
+    /// are ignored and replicas can be added or removed without a restart.
+    pub shard_membership: Configurable<ShardMembershipConfig>,
+
     /// Maximum cache size per BMC, uses etags
     pub cache_size: usize,
 
@@ -85,6 +89,7 @@ impl Default for Config {
             metrics: MetricsConfig::default(),
             shard: 0,
             shards_count: 1,
+            shard_membership: Configurable::Disabled,
             cache_size: 100,
             bmc_request_concurrency: DEFAULT_BMC_REQUEST_CONCURRENCY,
             endpoint_discovery_interval: Duration::from_secs(300),
@@ -93,6 +98,62 @@ impl Default for Config {
     }
 }
 
+/// Shard membership discovered from the endpoints of a Kubernetes Service
+/// that selects every health replica.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShardMembershipConfig {
+    /// Name of the Service whose EndpointSlices list the replicas.
+    pub service: String,
+
+    /// Namespace of the Service. Defaults to the namespace of the client
+    /// credentials, i.e. the pod's own namespace when running in-cluster.
+    pub namespace: Option<String>,
+
+    /// This replica's name in the Service endpoints. Defaults to `HOSTNAME`,
+    /// which Kubernetes sets to the pod name.
+    pub member: Option<String>,
+
+    /// How often membership is refreshed; bounds how long a failed replica's
+    /// endpoints go unmonitored.
+    #[serde(with = "humantime_serde")]
+    pub refresh_interval: Duration,
+}
+
+impl Default for ShardMembershipConfig {
+    fn default() -> Self {
+        Self {
+            service: String::new(),
+            namespace: None,
+            member: None,
+            refresh_interval: Duration::from_secs(15),
+        }
+    }
+}
+
+impl ShardMembershipConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.service.is_empty() {
+            return Err("shard_membership.service must not be empty".to_string());
+        }
+        if self.refresh_interval.is_zero() {
+            return Err("shard_membership.refresh_interval must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// This replica's member name, from configuration or the environment.
+    pub fn member_name(&self) -> Result<String, String> {
+        self.member
+            .clone()
+            .or_else(|| std::env::var("HOSTNAME").ok())
+            .filter(|member| !member.is_empty())
+            .ok_or_else(|| {
+                "shard_membership.member is not set and HOSTNAME is unavailable".to_string()
+            })
+    }
+}
+
 /// Configuration for where BMC endpoints are discovered from.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -1996,6 +2057,10 @@ impl Config {
             ));
         }
 
+        if let Configurable::Enabled(membership) = &self.shard_membership {
+            membership.validate()?;
+        }
+
         if self.endpoint_discovery_interval.is_zero() {
             return Err("endpoint_discovery_interval must be greater than 0".to_string());
         }
@@ -2738,6 +2803,28 @@ username = "root"
                     config.shards_count = 3;
                 }) => FailsWith("shard (5) must be less than shards_count (3)".to_string()),
 
+                config_with(|config| {
+                    config.shard_membership = Configurable::Enabled(ShardMembershipConfig {
+                        service: "carbide-hw-health".to_string(),
+                        ..ShardMembershipConfig::default()
+                    });
+                }) => Yields(()),
+
+                config_with(|config| {
+                    config.shard_membership =
+                        Configurable::Enabled(ShardMembershipConfig::default());
+                }) => FailsWith("shard_membership.service must not be empty".to_string()),
+
+                config_with(|config| {
+                    config.shard_membership = Configurable::Enabled(ShardMembershipConfig {
+                        service: "carbide-hw-health".to_string(),
+                        refresh_interval: Duration::ZERO,
+                        ..ShardMembershipConfig::default()
+                    });
+                }) => FailsWith(
+                    "shard_membership.refresh_interval must be greater than 0".to_string()
+                ),
+
                 config_with(|config| {
                     config.endpoint_discovery_interval = Duration::ZERO;
                 }) => FailsWith(
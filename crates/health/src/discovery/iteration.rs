@@ -382,18 +382,10 @@ mod tests {
             DiscoveryLoopContext::new(Arc::new(NoopLimiter), metrics_manager, Arc::new(config))
                 .expect("discovery context should start");
 
-        let stats = run_discovery_iteration(
-            source,
-            &ShardManager {
-                shard: 0,
-                shards_count: 1,
-            },
-            &mut ctx,
-            None,
-            "test",
-        )
-        .await
-        .expect("discovery iteration should succeed");
+        let stats =
+            run_discovery_iteration(source, &ShardManager::fixed(0, 1), &mut ctx, None, "test")
+                .await
+                .expect("discovery iteration should succeed");
 
         assert_eq!(stats.discovered_endpoints, 2);
         assert_eq!(stats.sharded_endpoints, 2);
@@ -503,10 +495,7 @@ mod tests {
         let iteration = tokio::spawn(async move {
             let (result, logs) = capture_logs_async(run_discovery_iteration(
                 source,
-                &ShardManager {
-                    shard: 0,
-                    shards_count: 1,
-                },
+                &ShardManager::fixed(0, 1),
                 &mut ctx,
                 Some(iteration_sink),
                 "test",
@@ -22,6 +22,7 @@ mod iteration;
 mod reachability;
 mod spawn;
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
@@ -29,6 +30,7 @@ use carbide_utils::managed_loop::{self, LoopManager};
 pub use context::DiscoveryLoopContext;
 pub(crate) use context::load_nmxc_schema_override;
 pub use iteration::run_discovery_iteration;
+use tokio::sync::Notify;
 
 use crate::HealthError;
 use crate::collectors::{BackoffConfig, ExponentialBackoff};
@@ -51,11 +53,13 @@ pub(crate) trait DiscoveryIteration: Send {
 /// counted by the shared managed-loop event; a failed pass writes that
 /// event's WARN line and schedules a capped exponential backoff instead of
 /// ending the loop, and the next success resets the backoff and returns the
-/// cadence to `interval`.
+/// cadence to `interval`. A notification on `wake` starts the next pass
+/// early, e.g. when shard membership changes.
 pub(crate) async fn run_discovery_loop(
     interval: Duration,
     backoff_config: BackoffConfig,
     mut iteration: impl DiscoveryIteration,
+    wake: Arc<Notify>,
 ) {
     let mut backoff = ExponentialBackoff::new(&backoff_config);
     loop {
@@ -68,7 +72,10 @@ pub(crate) async fn run_discovery_loop(
             }
             Err(_) => backoff.next_delay(),
         };
-        tokio::time::sleep(delay).await;
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = wake.notified() => {}
+        }
     }
 }
 
@@ -127,6 +134,7 @@ mod tests {
                 calls: 0,
                 times: times_tx,
             },
+            Arc::new(Notify::new()),
         ));
 
         let mut times = Vec::new();
@@ -171,4 +179,31 @@ mod tests {
             2.0
         );
     }
+
+    /// A wake-up starts the next pass right away instead of after the full
+    /// discovery interval.
+    #[tokio::test(start_paused = true)]
+    async fn discovery_loop_runs_early_when_woken() {
+        let (times_tx, mut times_rx) = tokio::sync::mpsc::unbounded_channel();
+        let wake = Arc::new(Notify::new());
+
+        let loop_task = tokio::spawn(run_discovery_loop(
+            Duration::from_secs(300),
+            BackoffConfig::default(),
+            ScriptedIteration {
+                script: vec![true],
+                calls: 0,
+                times: times_tx,
+            },
+            wake.clone(),
+        ));
+
+        let first = times_rx.recv().await.expect("loop keeps iterating");
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        wake.notify_one();
+        let second = times_rx.recv().await.expect("loop keeps iterating");
+        loop_task.abort();
+
+        assert_eq!(second - first, Duration::from_secs(10));
+    }
 }
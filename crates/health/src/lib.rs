@@ -22,6 +22,7 @@ use nv_redfish::bmc_http::reqwest::{
     BmcError, Client as ReqwestClient, ClientParams as ReqwestClientParams,
 };
 use prometheus::{Gauge, GaugeVec, Opts};
+use tokio::sync::Notify;
 
 pub mod api_client;
 pub mod bmc;
@@ -53,7 +54,7 @@ use crate::processor::{
     BmcIntrusionEventProcessor, EventProcessingPipeline, EventProcessor, HealthReportProcessor,
     LeakEventProcessor, RackLeakProcessor,
 };
-use crate::sharding::ShardManager;
+use crate::sharding::{KubernetesMembership, MembershipSource, ShardManager};
 use crate::sink::event_mapper::{OpenBmcEventMapper, RedfishEventMapper};
 use crate::sink::{
     CompositeDataSink, DataSink, HealthReportSink, LogFileSink, OtlpSink,
@@ -115,6 +116,10 @@ pub enum HealthError {
     /// Client TLS material could not be read, validated, or applied.
     #[error("TLS profile error: {0}")]
     Tls(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+    /// Shard membership could not be discovered.
+    #[error("shard membership error: {0}")]
+    Membership(#[source] Box<dyn std::error::Error + Send + Sync>),
 }
 
 impl From<String> for HealthError {
@@ -310,7 +315,7 @@ fn build_data_sink(
 /// plus the gauge updates its stats feed.
 struct ServiceDiscoveryIteration {
     endpoint_source: Arc<dyn EndpointSource>,
-    shard_manager: ShardManager,
+    shard_manager: Arc<ShardManager>,
     ctx: DiscoveryLoopContext,
     data_sink: Option<Arc<dyn DataSink>>,
     config: Arc<Config>,
@@ -427,14 +432,30 @@ pub async fn run_service(config: Config) -> Result<(), HealthError> {
 
     let data_sink = build_data_sink(&config, metrics_manager.clone())?;
 
+    let discovery_wake = Arc::new(Notify::new());
+    let shard_manager = match &config.shard_membership {
+        Configurable::Enabled(membership) => {
+            let shard_manager = Arc::new(ShardManager::new(membership.member_name()?));
+            let source: Arc<dyn MembershipSource> =
+                Arc::new(KubernetesMembership::new(membership).await?);
+            // Start from the current membership instead of claiming every
+            // endpoint while the first poll is pending.
+            shard_manager.set_members(source.members().await?);
+            tokio::spawn(sharding::run_membership_loop(
+                source,
+                shard_manager.clone(),
+                membership.refresh_interval,
+                discovery_wake.clone(),
+            ));
+            shard_manager
+        }
+        Configurable::Disabled => Arc::new(ShardManager::fixed(config.shard, config.shards_count)),
+    };
+
     let config_arc = Arc::new(config);
 
     let join_discovery: tokio::task::JoinHandle<()> = tokio::spawn({
         let config = config_arc.clone();
-        let shard_manager = ShardManager {
-            shard: config.shard,
-            shards_count: config.shards_count,
-        };
         let limiter: Arc<dyn RateLimiter> =
             if let Configurable::Enabled(rate_limit) = &config.rate_limit {
                 Arc::new(BucketLimiter::new(
@@ -465,7 +486,12 @@ pub async fn run_service(config: Config) -> Result<(), HealthError> {
             active_endpoints_gauge: active_endpoints_gauge.clone(),
         };
 
-        discovery::run_discovery_loop(interval, BackoffConfig::default(), iteration)
+        discovery::run_discovery_loop(
+            interval,
+            BackoffConfig::default(),
+            iteration,
+            discovery_wake,
+        )
     });
 
     tokio::select! {
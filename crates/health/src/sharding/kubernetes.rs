@@ -0,0 +1,143 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Shard membership from the EndpointSlices of a Kubernetes Service.
+//!
+//! Every health replica backs the same (typically headless) Service. A replica
+//! is a member while its endpoint is ready, so a crashed or failing pod drops
+//! out as soon as Kubernetes notices and its endpoints are taken over on the
+//! next membership poll.
+
+use async_trait::async_trait;
+use k8s_openapi::api::discovery::v1::EndpointSlice;
+use kube::api::ListParams;
+use kube::{Api, Client};
+
+use super::MembershipSource;
+use crate::HealthError;
+use crate::config::ShardMembershipConfig;
+
+/// Label Kubernetes puts on every EndpointSlice it manages for a Service.
+const SERVICE_NAME_LABEL: &str = "kubernetes.io/service-name";
+
+pub struct KubernetesMembership {
+    api: Api<EndpointSlice>,
+    selector: String,
+}
+
+impl KubernetesMembership {
+    /// Connects with the in-cluster (or kubeconfig) credentials.
+    pub async fn new(config: &ShardMembershipConfig) -> Result<Self, HealthError> {
+        let client = Client::try_default()
+            .await
+            .map_err(|e| HealthError::Membership(Box::new(e)))?;
+        let api = match &config.namespace {
+            Some(namespace) => Api::namespaced(client, namespace),
+            None => Api::default_namespaced(client),
+        };
+        Ok(Self {
+            api,
+            selector: format!("{SERVICE_NAME_LABEL}={}", config.service),
+        })
+    }
+}
+
+#[async_trait]
+impl MembershipSource for KubernetesMembership {
+    async fn members(&self) -> Result<Vec<String>, HealthError> {
+        let slices = self
+            .api
+            .list(&ListParams::default().labels(&self.selector))
+            .await
+            .map_err(|e| HealthError::Membership(Box::new(e)))?;
+        Ok(ready_members(&slices.items))
+    }
+}
+
+/// Pod names (or hostnames, for endpoints without a pod) of the ready
+/// endpoints. Kubernetes defines an unset `ready` condition as ready.
+fn ready_members(slices: &[EndpointSlice]) -> Vec<String> {
+    slices
+        .iter()
+        .flat_map(|slice| &slice.endpoints)
+        .filter(|endpoint| {
+            endpoint
+                .conditions
+                .as_ref()
+                .and_then(|conditions| conditions.ready)
+                .unwrap_or(true)
+        })
+        .filter_map(|endpoint| {
+            endpoint
+                .target_ref
+                .as_ref()
+                .and_then(|target| target.name.clone())
+                .or_else(|| endpoint.hostname.clone())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::api::core::v1::ObjectReference;
+    use k8s_openapi::api::discovery::v1::{Endpoint, EndpointConditions};
+
+    use super::*;
+
+    fn endpoint(pod: Option<&str>, hostname: Option<&str>, ready: Option<bool>) -> Endpoint {
+        Endpoint {
+            addresses: vec!["10.0.0.1".to_string()],
+            conditions: Some(EndpointConditions {
+                ready,
+                ..Default::default()
+            }),
+            hostname: hostname.map(str::to_string),
+            target_ref: pod.map(|name| ObjectReference {
+                kind: Some("Pod".to_string()),
+                name: Some(name.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ready_members_skip_endpoints_that_are_not_ready() {
+        let slices = vec![
+            EndpointSlice {
+                endpoints: vec![
+                    endpoint(Some("health-0"), None, Some(true)),
+                    endpoint(Some("health-1"), None, Some(false)),
+                ],
+                ..Default::default()
+            },
+            EndpointSlice {
+                endpoints: vec![
+                    endpoint(Some("health-2"), None, None),
+                    endpoint(None, Some("health-3"), Some(true)),
+                    endpoint(None, None, Some(true)),
+                ],
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(
+            ready_members(&slices),
+            vec!["health-0", "health-2", "health-3"]
+        );
+    }
+}
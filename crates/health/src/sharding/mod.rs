@@ -0,0 +1,355 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Assignment of BMC endpoints to health service replicas.
+//!
+//! Endpoints are spread with rendezvous (highest random weight) hashing: every
+//! replica scores each endpoint key and the highest score wins. A membership
+//! change only moves the keys won by the replica that joined or left, so the
+//! remaining collectors keep running instead of cold-starting elsewhere.
+//!
+//! Membership is either fixed by configuration (replica ordinals of a
+//! StatefulSet) or discovered at runtime through a [`MembershipSource`] that
+//! [`run_membership_loop`] polls.
+
+mod kubernetes;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use arc_swap::ArcSwap;
+use async_trait::async_trait;
+pub use kubernetes::KubernetesMembership;
+use tokio::sync::Notify;
+
+use crate::HealthError;
+use crate::endpoint::BmcEndpoint;
+
+pub struct ShardManager {
+    /// This replica's name within `members`.
+    member: String,
+    /// Sorted, deduplicated, and always containing `member`.
+    members: ArcSwap<Vec<String>>,
+}
+
+impl ShardManager {
+    /// A replica that starts out alone, until [`Self::set_members`] tells it
+    /// about its peers.
+    pub fn new(member: impl Into<String>) -> Self {
+        let member = member.into();
+        Self {
+            members: ArcSwap::from_pointee(vec![member.clone()]),
+            member,
+        }
+    }
+
+    /// Replica `shard` of a fixed set of `shards_count` replicas, named by
+    /// their ordinals.
+    pub fn fixed(shard: usize, shards_count: usize) -> Self {
+        let manager = Self::new(shard.to_string());
+        manager.set_members((0..shards_count).map(|ordinal| ordinal.to_string()));
+        manager
+    }
+
+    pub fn member(&self) -> &str {
+        &self.member
+    }
+
+    pub fn members(&self) -> Arc<Vec<String>> {
+        self.members.load_full()
+    }
+
+    /// Replaces the replica set and reports whether it changed.
+    ///
+    /// This replica is always kept as a member: it is evidently alive, and
+    /// dropping it would leave its endpoints unmonitored until the source
+    /// catches up.
+    pub fn set_members(&self, members: impl IntoIterator<Item = String>) -> bool {
+        let mut members: Vec<String> = members.into_iter().collect();
+        members.push(self.member.clone());
+        members.sort();
+        members.dedup();
+        if **self.members.load() == members {
+            return false;
+        }
+        self.members.store(Arc::new(members));
+        true
+    }
+
+    /// Check if this shard should monitor a BMC endpoint.
+    pub fn should_monitor(&self, endpoint: &BmcEndpoint) -> bool {
+        self.should_monitor_key(&endpoint.hash_key())
+    }
+
+    pub fn should_monitor_key(&self, key: &str) -> bool {
+        let members = self.members.load();
+        if members.len() == 1 {
+            return true;
+        }
+        owner(&members, key) == Some(self.member.as_str())
+    }
+}
+
+/// The member with the highest score for `key`; ties go to the smaller name.
+fn owner<'a>(members: &'a [String], key: &str) -> Option<&'a str> {
+    members
+        .iter()
+        .map(|member| (score(member, key), member))
+        .max_by(|(left_score, left), (right_score, right)| {
+            left_score.cmp(right_score).then_with(|| right.cmp(left))
+        })
+        .map(|(_, member)| member.as_str())
+}
+
+/// FNV-1a 64-bit over `member` and `key`, finalized with the splitmix64 mixer
+/// so that names differing in a single byte still get independent scores.
+fn score(member: &str, key: &str) -> u64 {
+    const FNV_PRIME: u64 = 1099511628211;
+    const FNV_OFFSET_BASIS: u64 = 14695981039346656037;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    // The separator keeps ("ab", "c") and ("a", "bc") apart.
+    for byte in member.bytes().chain([0xff]).chain(key.bytes()) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d049bb133111eb);
+    hash ^ (hash >> 31)
+}
+
+/// Where the set of live replicas comes from.
+#[async_trait]
+pub trait MembershipSource: Send + Sync {
+    /// Names of the replicas currently able to monitor endpoints.
+    async fn members(&self) -> Result<Vec<String>, HealthError>;
+}
+
+/// Polls `source` every `interval` and applies the result to `manager`.
+///
+/// `changed` is notified whenever membership changes, so that discovery can
+/// take over a failed replica's endpoints without waiting for its next pass.
+/// A failed poll keeps the last known membership.
+pub async fn run_membership_loop(
+    source: Arc<dyn MembershipSource>,
+    manager: Arc<ShardManager>,
+    interval: Duration,
+    changed: Arc<Notify>,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        match source.members().await {
+            Ok(members) => {
+                if manager.set_members(members) {
+                    tracing::info!(
+                        member = manager.member(),
+                        members = ?manager.members(),
+                        "Shard membership changed"
+                    );
+                    changed.notify_one();
+                }
+            }
+            Err(error) => {
+                tracing::warn!(?error, "Could not refresh shard membership");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use carbide_uuid::rack::RackId;
+    use mac_address::MacAddress;
+
+    use super::*;
+    use crate::endpoint::test_support::endpoint_with_creds;
+    use crate::endpoint::{BmcAddr, BmcCredentials};
+
+    fn endpoint(mac: &str, rack: Option<&str>) -> BmcEndpoint {
+        endpoint_with_creds(
+            BmcAddr {
+                ip: "10.0.0.1".parse().unwrap(),
+                port: Some(443),
+                mac: MacAddress::from_str(mac).unwrap(),
+            },
+            BmcCredentials::UsernamePassword {
+                username: "admin".into(),
+                password: None,
+            },
+            None,
+            rack.map(RackId::new),
+        )
+    }
+
+    #[test]
+    fn test_single_shard() {
+        let manager = ShardManager::fixed(0, 1);
+        assert!(manager.should_monitor(&endpoint("42:9e:b1:bd:9d:dd", None)));
+    }
+
+    #[test]
+    fn test_consistent_hashing() {
+        let ep1 = endpoint("42:9e:b1:bd:9d:dd", None);
+        let ep2 = endpoint("42:9e:b2:bd:9d:dd", None);
+
+        let managers: Vec<_> = (0..3).map(|shard| ShardManager::fixed(shard, 3)).collect();
+
+        for (label, ep) in [("ep1", &ep1), ("ep2", &ep2)] {
+            let count = managers.iter().filter(|m| m.should_monitor(ep)).count();
+            assert_eq!(count, 1, "{label} should be monitored by exactly one shard");
+        }
+    }
+
+    #[test]
+    fn test_should_monitor_key_distribution() {
+        for key in ["AA:BB:CC:DD:EE:FF", "11:22:33:44:55:66"] {
+            let count = (0..3)
+                .map(|shard| ShardManager::fixed(shard, 3))
+                .filter(|m| m.should_monitor_key(key))
+                .count();
+            assert_eq!(
+                count, 1,
+                "Key {key} should be assigned to exactly one shard"
+            );
+        }
+    }
+
+    #[test]
+    fn test_same_rack_id_same_shard() {
+        let ep_a = endpoint("42:9e:b1:bd:9d:dd", Some("rack-7"));
+        let ep_b = endpoint("42:9e:b2:bd:9d:dd", Some("rack-7"));
+
+        let managers: Vec<_> = (0..3).map(|shard| ShardManager::fixed(shard, 3)).collect();
+
+        let shard_a = managers
+            .iter()
+            .position(|m| m.should_monitor(&ep_a))
+            .expect("should be assigned");
+        let shard_b = managers
+            .iter()
+            .position(|m| m.should_monitor(&ep_b))
+            .expect("should be assigned");
+
+        assert_eq!(
+            shard_a, shard_b,
+            "endpoints with the same rack_id should land on the same shard"
+        );
+    }
+
+    fn keys() -> Vec<String> {
+        (0..1000)
+            .map(|n| format!("02:00:00:00:{:02x}:{:02x}", n / 256, n % 256))
+            .collect()
+    }
+
+    fn assignments(members: &[String], keys: &[String]) -> Vec<String> {
+        keys.iter()
+            .map(|key| owner(members, key).unwrap().to_string())
+            .collect()
+    }
+
+    fn names(members: &[&str]) -> Vec<String> {
+        members.iter().map(|member| member.to_string()).collect()
+    }
+
+    #[test]
+    fn test_scaling_up_only_moves_keys_to_the_new_shard() {
+        let keys = keys();
+        let before = assignments(&names(&["0", "1", "2"]), &keys);
+        let after = assignments(&names(&["0", "1", "2", "3"]), &keys);
+
+        let moved: Vec<_> = before
+            .iter()
+            .zip(&after)
+            .filter(|(before, after)| before != after)
+            .collect();
+        assert!(moved.iter().all(|(_, after)| after.as_str() == "3"));
+        // Roughly a quarter of the keys belong to the new shard.
+        assert!(
+            (150..350).contains(&moved.len()),
+            "{} keys moved",
+            moved.len()
+        );
+    }
+
+    #[test]
+    fn test_failed_member_keys_are_spread_over_survivors() {
+        let keys = keys();
+        let before = assignments(&names(&["health-0", "health-1", "health-2"]), &keys);
+        let after = assignments(&names(&["health-0", "health-2"]), &keys);
+
+        for (before, after) in before.iter().zip(&after) {
+            if before != "health-1" {
+                assert_eq!(before, after, "keys of surviving members must stay put");
+            }
+        }
+        let taken_over = |member: &str| {
+            before
+                .iter()
+                .zip(&after)
+                .filter(|(before, after)| before.as_str() == "health-1" && after.as_str() == member)
+                .count()
+        };
+        assert!(taken_over("health-0") > 0);
+        assert!(taken_over("health-2") > 0);
+    }
+
+    #[test]
+    fn test_set_members_keeps_self_and_reports_changes() {
+        let manager = ShardManager::new("health-1");
+        assert!(manager.should_monitor_key("any"));
+
+        assert!(manager.set_members(names(&["health-2", "health-0"])));
+        assert_eq!(
+            *manager.members(),
+            names(&["health-0", "health-1", "health-2"])
+        );
+        assert!(!manager.set_members(names(&["health-0", "health-1", "health-2"])));
+
+        assert!(manager.set_members(Vec::new()));
+        assert_eq!(*manager.members(), names(&["health-1"]));
+    }
+
+    #[test]
+    fn test_members_agree_on_a_single_owner() {
+        let members = names(&["health-0", "health-1", "health-2"]);
+        let managers: Vec<_> = members
+            .iter()
+            .map(|member| {
+                let manager = ShardManager::new(member.clone());
+                manager.set_members(members.clone());
+                manager
+            })
+            .collect();
+
+        for key in keys() {
+            let count = managers
+                .iter()
+                .filter(|m| m.should_monitor_key(&key))
+                .count();
+            assert_eq!(
+                count, 1,
+                "Key {key} should be assigned to exactly one shard"
+            );
+        }
+    }
+}
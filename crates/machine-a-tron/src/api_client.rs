@@ -14,6 +14,7 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, Ordering};
 
 use base64::prelude::*;
@@ -30,8 +31,9 @@ use rpc::forge::instance_operating_system_config::Variant;
 use rpc::forge::machine_cleanup_info::CleanupStepResult;
 use rpc::forge::{
     ConfigSetting, ExpectedInterface, ExpectedMachine, ExpectedPowerShelf, ExpectedRack,
-    ExpectedRackRequest, ExpectedSwitch, InlineIpxe, InstanceOperatingSystemConfig,
-    MachinesByIdsRequest, SetDynamicConfigRequest, VpcVirtualizationType,
+    ExpectedRackRequest, ExpectedSwitch, InlineIpxe, InstanceOperatingSystemConfig, MachineEvent,
+    MachineStateHistoriesRequest, MachinesByIdsRequest, SetDynamicConfigRequest,
+    VpcVirtualizationType,
 };
 use rpc::protos::forge_api_client::ForgeApiClient;
 
@@ -176,6 +178,24 @@ impl ApiClient {
         Ok(out.machines)
     }
 
+    /// Returns the state history of each machine, oldest record first, keyed by machine ID.
+    pub async fn find_machine_state_histories(
+        &self,
+        machine_ids: Vec<MachineId>,
+    ) -> ClientApiResult<HashMap<String, Vec<MachineEvent>>> {
+        let out = self
+            .0
+            .find_machine_state_histories(MachineStateHistoriesRequest { machine_ids })
+            .await
+            .map_err(ClientApiError::InvocationError)?;
+
+        Ok(out
+            .histories
+            .into_iter()
+            .map(|(machine_id, history)| (machine_id, history.records))
+            .collect())
+    }
+
     pub async fn record_dpu_network_status(
         &self,
         DpuNetworkStatusArgs {
@@ -509,11 +529,12 @@ impl ApiClient {
         &self,
         machine_id: &MachineId,
         validation_id: &MachineValidationId,
+        error: Option<String>,
     ) -> ClientApiResult<()> {
         self.0
             .machine_validation_completed(rpc::forge::MachineValidationCompletedRequest {
                 machine_id: Some(*machine_id),
-                machine_validation_error: None,
+                machine_validation_error: error,
                 validation_id: Some(*validation_id),
             })
             .await
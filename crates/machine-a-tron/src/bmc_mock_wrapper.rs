@@ -23,7 +23,8 @@ use axum::Router;
 use bmc_mock::injection::InjectionStore;
 use bmc_mock::ipmi_sim::{IpmiEndpoint, IpmiSimConfig, IpmiSimHandle};
 use bmc_mock::{
-    BmcState, Callbacks, CombinedServer, HostnameQuerying, ListenerOrAddress, MachineInfo,
+    BmcState, Callbacks, CombinedServer, EventService, HostnameQuerying, ListenerOrAddress,
+    MachineInfo,
 };
 use carbide_ipmi::DEFAULT_IPMI_PORT;
 use tokio::sync::RwLock;
@@ -56,6 +57,7 @@ impl BmcMockWrapper {
         hostname: Arc<dyn HostnameQuerying>,
         host_id: Uuid,
         injection: Arc<InjectionStore>,
+        events: Arc<EventService>,
     ) -> Self {
         let (bmc_mock_router, bmc_mock_state) = bmc_mock::machine_router_with_injection_store(
             machine_info,
@@ -63,6 +65,7 @@ impl BmcMockWrapper {
             host_id.to_string(),
             true,
             injection,
+            events,
         );
 
         BmcMockWrapper {
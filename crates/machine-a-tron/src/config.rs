@@ -42,8 +42,10 @@ use uuid::Uuid;
 use crate::BmcRegistrationMode;
 use crate::api_client::ApiClient;
 use crate::api_throttler::ApiThrottler;
+use crate::lifecycle_report::LifecycleReportConfig;
 use crate::machine_state_machine::OsImage;
 use crate::rack::{RackMemberRegistration, RackRegistration};
+use crate::scenario::ScenarioConfig;
 
 #[derive(Parser, Debug, Serialize, Deserialize)]
 #[clap(name = "machine-sim")]
@@ -555,6 +557,14 @@ pub struct MachineATronConfig {
     /// when its explicit `enabled` flag is set.
     #[serde(default)]
     pub ufm_mock: Option<UfmMockConfig>,
+
+    /// Optional time-scripted faults to inject into the simulated devices once they start.
+    #[serde(default)]
+    pub scenario: Option<ScenarioConfig>,
+
+    /// Optional report of how long hosts dwell in each state, written when machine-a-tron quits.
+    #[serde(default)]
+    pub lifecycle_report: Option<LifecycleReportConfig>,
 }
 
 impl MachineATronConfig {
@@ -599,6 +609,10 @@ impl MachineATronConfig {
             bmc_mock::ipmi_sim::validate_executable()?;
         }
 
+        if let Some(lifecycle_report) = self.lifecycle_report.as_ref() {
+            lifecycle_report.validate()?;
+        }
+
         let mut simulated_rack_ids = BTreeSet::new();
         for (rack_group, rack) in &self.racks {
             eyre::ensure!(!rack_group.is_empty(), "rack group name cannot be empty");
@@ -693,6 +707,10 @@ impl MachineATronConfig {
             });
         }
 
+        if let Some(scenario) = self.scenario.as_ref() {
+            scenario.validate(&machines)?;
+        }
+
         Ok(ResolvedDeviceConfigs { machines, racks })
     }
 
@@ -948,7 +966,7 @@ impl MachineATronContext {
     }
 }
 
-fn as_std_duration<S>(d: &std::time::Duration, serializer: S) -> Result<S::Ok, S::Error>
+pub(crate) fn as_std_duration<S>(d: &std::time::Duration, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -1610,6 +1628,124 @@ server_address = "127.0.0.1:6767""#,
         );
     }
 
+    #[test]
+    fn scenario_steps_are_validated() {
+        fn with_step(step: &str) -> MachineATronConfig {
+            let mut config = rack_config();
+            config.scenario = Some(
+                toml::from_str(&format!("[[steps]]\n{step}")).expect("Could not parse scenario"),
+            );
+            config
+        }
+
+        check_cases(
+            [
+                Case {
+                    scenario: "host fault in a known section",
+                    input: with_step(
+                        r#"
+at = "1m"
+for = "5m"
+target = { kind = "host", section = "config", count = 2 }
+fault = { type = "fail_validation" }
+"#,
+                    ),
+                    expect: Yields(()),
+                },
+                Case {
+                    scenario: "power loss in a known rack",
+                    input: with_step(
+                        r#"
+at = "1m"
+target = { kind = "power_shelf", rack_id = "rack-001" }
+fault = { type = "power_loss" }
+"#,
+                    ),
+                    expect: Yields(()),
+                },
+                Case {
+                    scenario: "scout crash on a DPU",
+                    input: with_step(
+                        r#"
+at = "1m"
+target = { kind = "dpu" }
+fault = { type = "crash_scout", step = "discovery" }
+"#,
+                    ),
+                    expect: Fails,
+                },
+                Case {
+                    scenario: "discovery stall on a switch",
+                    input: with_step(
+                        r#"
+at = "1m"
+target = { kind = "switch" }
+fault = { type = "stall_discovery" }
+"#,
+                    ),
+                    expect: Fails,
+                },
+                Case {
+                    scenario: "unknown section",
+                    input: with_step(
+                        r#"
+at = "1m"
+target = { kind = "host", section = "missing" }
+fault = { type = "power_loss" }
+"#,
+                    ),
+                    expect: Fails,
+                },
+                Case {
+                    scenario: "unknown rack",
+                    input: with_step(
+                        r#"
+at = "1m"
+target = { kind = "host", rack_id = "rack-404" }
+fault = { type = "power_loss" }
+"#,
+                    ),
+                    expect: Fails,
+                },
+                Case {
+                    scenario: "zero count",
+                    input: with_step(
+                        r#"
+at = "1m"
+target = { kind = "host", count = 0 }
+fault = { type = "power_loss" }
+"#,
+                    ),
+                    expect: Fails,
+                },
+                Case {
+                    scenario: "zero duration",
+                    input: with_step(
+                        r#"
+at = "1m"
+for = "0s"
+target = { kind = "host" }
+fault = { type = "power_loss" }
+"#,
+                    ),
+                    expect: Fails,
+                },
+                Case {
+                    scenario: "invalid HTTP status",
+                    input: with_step(
+                        r#"
+at = "1m"
+target = { kind = "host" }
+fault = { type = "bmc_unreachable", status = 42 }
+"#,
+                    ),
+                    expect: Fails,
+                },
+            ],
+            |config| config.resolved_device_configs().map(drop).map_err(drop),
+        );
+    }
+
     #[test]
     fn missing_host_inband_relay_warning_selection() {
         let host_inband = Ipv4Addr::new(192, 168, 177, 1);
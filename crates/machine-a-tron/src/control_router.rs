@@ -20,10 +20,10 @@ use axum::body::Body;
 use axum::extract::{Path, Request, State};
 use axum::http::StatusCode;
 use axum::response::{Html, IntoResponse, Response};
-use axum::routing::{any, get};
+use axum::routing::{any, get, post};
 use axum::{Json, Router};
-use bmc_mock::HardwareType;
 use bmc_mock::injection::{InjectionStore, Rule, RuleId};
+use bmc_mock::{EventRecord, HardwareType};
 use carbide_uuid::rack::RackId;
 use chrono::{SecondsFormat, Utc};
 use tower::Service;
@@ -50,6 +50,7 @@ pub fn append(router: Option<Router>, control_state: ControlState) -> Router {
             "/machines/{id}/bmc/injection/rules/{rule_id}",
             axum::routing::delete(delete_bmc_injection_rule),
         )
+        .route("/machines/{id}/bmc/events", post(post_bmc_event))
         .route("/{*all}", any(process))
         .with_state(ControlRouter {
             inner: router,
@@ -277,6 +278,19 @@ async fn delete_bmc_injection_rule(
     }
 }
 
+/// Publishes an arbitrary Redfish event from the device's BMC, as if the BMC had raised it.
+async fn post_bmc_event(
+    State(state): State<ControlRouter>,
+    Path(id): Path<String>,
+    Json(record): Json<EventRecord>,
+) -> Response {
+    let Some(events) = state.control_state.simulators.find_event_service(&id) else {
+        return device_not_found();
+    };
+    events.publish(record);
+    StatusCode::NO_CONTENT.into_response()
+}
+
 fn list_rules(device: &InjectionStore) -> Vec<Rule> {
     device
         .list()
@@ -669,6 +683,55 @@ mod tests {
         assert!(host.bmc_injection_store().list().is_empty());
     }
 
+    #[tokio::test]
+    async fn bmc_events_require_known_device() {
+        let router = append(None, control_state(Vec::new()));
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/machines/unknown/bmc/events")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"MessageId":"ResourceEvent.1.3.ResourceChanged"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn bmc_events_are_published_by_the_device_bmc() {
+        let host = DeviceHandle::for_control_test(Vec::new(), None);
+        let mut events = host.bmc_event_service().subscribe_sse();
+        let router = append(None, control_state(vec![host.clone()]));
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(format!("/machines/{}/bmc/events", host.mat_id()))
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"MessageId":"ResourceEvent.1.3.ResourceErrorsDetected","Severity":"Critical"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let (_, payload) = events.try_recv().unwrap();
+        assert_eq!(
+            payload["Events"][0]["MessageId"],
+            "ResourceEvent.1.3.ResourceErrorsDetected"
+        );
+        assert_eq!(payload["Events"][0]["MessageSeverity"], "Critical");
+    }
+
     #[tokio::test]
     async fn unmatched_paths_forward_to_inner_router() {
         let router = append(
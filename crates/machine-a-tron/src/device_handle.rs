@@ -18,8 +18,8 @@ use std::net::Ipv4Addr;
 use std::sync::Arc;
 use std::time::Duration;
 
-use bmc_mock::HostMachineInfo;
 use bmc_mock::injection::InjectionStore;
+use bmc_mock::{EventService, HostMachineInfo, SystemPowerControl};
 use carbide_uuid::machine::MachineId;
 use tokio::sync::mpsc;
 use uuid::Uuid;
@@ -28,6 +28,7 @@ use crate::api_client::ApiClient;
 use crate::dpu_machine::DpuMachineHandle;
 use crate::host_machine::MachineHandle;
 use crate::power_shelf_simulator::PowerShelfHandle;
+use crate::scenario::MachineFaults;
 use crate::status::{DeviceKind, DeviceStatus, DeviceStatusConfig};
 use crate::switch_simulator::SwitchHandle;
 use crate::tui::UiUpdate;
@@ -199,6 +200,30 @@ impl DeviceHandle {
         }
     }
 
+    /// Faults injected into the host's agent. Switches and power shelves run no agent.
+    pub(crate) fn machine_faults(&self) -> Option<Arc<MachineFaults>> {
+        match &self.0 {
+            DeviceHandleInner::Machine(handle) => Some(handle.machine_faults()),
+            DeviceHandleInner::Switch(_) | DeviceHandleInner::PowerShelf(_) => None,
+        }
+    }
+
+    pub(crate) fn set_system_power(&self, request: SystemPowerControl) -> eyre::Result<()> {
+        match &self.0 {
+            DeviceHandleInner::Machine(handle) => handle.set_system_power(request),
+            DeviceHandleInner::Switch(handle) => handle.set_system_power(request),
+            DeviceHandleInner::PowerShelf(handle) => handle.set_system_power(request),
+        }
+    }
+
+    pub(crate) fn bmc_event_service(&self) -> Arc<EventService> {
+        match &self.0 {
+            DeviceHandleInner::Machine(handle) => handle.bmc_event_service(),
+            DeviceHandleInner::Switch(handle) => handle.bmc_event_service(),
+            DeviceHandleInner::PowerShelf(handle) => handle.bmc_event_service(),
+        }
+    }
+
     pub fn abort(&self) {
         match &self.0 {
             DeviceHandleInner::Machine(handle) => handle.abort(),
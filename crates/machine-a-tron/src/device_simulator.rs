@@ -16,6 +16,7 @@
  */
 use std::sync::Arc;
 
+use bmc_mock::EventService;
 use bmc_mock::injection::InjectionStore;
 use tokio::sync::mpsc;
 use uuid::Uuid;
@@ -61,6 +62,10 @@ pub trait SimulatorLifecycle {
     fn bmc_injection_store(&self) -> Arc<InjectionStore> {
         self.handle().bmc_injection_store()
     }
+
+    fn bmc_event_service(&self) -> Arc<EventService> {
+        self.handle().bmc_event_service()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -21,8 +21,8 @@ use std::time::{Duration, Instant};
 use bmc_mock::injection::InjectionStore;
 use bmc_mock::mac_address_pool::MacAddressPool;
 use bmc_mock::{
-    BmcCommand, DpuMachineInfo, DpuSettings, HardwareType, MachineInfo, SetSystemPowerResult,
-    SystemPowerControl,
+    BmcCommand, DpuMachineInfo, DpuSettings, EventService, HardwareType, MachineInfo,
+    SetSystemPowerResult, SystemPowerControl,
 };
 use carbide_uuid::machine::MachineId;
 use eyre::Context;
@@ -36,6 +36,7 @@ use crate::config::{MachineATronContext, PersistedDpuMachine};
 use crate::dhcp_wrapper::{DhcpRelayResult, DhcpResponseInfo, DpuDhcpRelay, DpuDhcpRelayServer};
 use crate::host_machine::HandleMessageResult;
 use crate::machine_state_machine::{LiveState, MachineStateMachine, OsImage, PersistedMachine};
+use crate::scenario::MachineFaults;
 use crate::status::{BmcStatus, DeviceKind, DeviceStatus, DeviceStatusConfig, EndpointStatus};
 use crate::tui::HostDetails;
 use crate::{MachineConfig, saturating_add_duration_to_instant};
@@ -180,6 +181,8 @@ impl DpuMachine {
         let dpu_index = self.dpu_index;
         let live_state = self.state_machine.live_state.clone();
         let bmc_injection = self.state_machine.bmc_injection_store();
+        let bmc_events = self.state_machine.bmc_event_service();
+        let faults = self.state_machine.machine_faults();
         let join_handle = tokio::task::Builder::new()
             .name(&format!("DPU {}", self.mat_id))
             .spawn({
@@ -203,6 +206,8 @@ impl DpuMachine {
             dpu_info,
             dpu_index,
             bmc_injection,
+            bmc_events,
+            faults,
             join_handle: Mutex::new(Some(join_handle)),
         }))
     }
@@ -347,6 +352,8 @@ struct DpuMachineActor {
     dpu_info: DpuMachineInfo,
     dpu_index: u8,
     bmc_injection: Arc<InjectionStore>,
+    bmc_events: Arc<EventService>,
+    faults: Arc<MachineFaults>,
     join_handle: Mutex<Option<JoinHandle<()>>>,
 }
 
@@ -372,6 +379,14 @@ impl DpuMachineHandle {
         self.0.bmc_injection.clone()
     }
 
+    pub(crate) fn bmc_event_service(&self) -> Arc<EventService> {
+        self.0.bmc_events.clone()
+    }
+
+    pub(crate) fn machine_faults(&self) -> Arc<MachineFaults> {
+        self.0.faults.clone()
+    }
+
     #[cfg(test)]
     pub(crate) fn for_control_test(mat_id: Uuid, observed_machine_id: Option<MachineId>) -> Self {
         let (message_tx, _message_rx) = mpsc::unbounded_channel();
@@ -394,6 +409,8 @@ impl DpuMachineHandle {
             },
             dpu_index: 0,
             bmc_injection: Arc::new(InjectionStore::new()),
+            bmc_events: Arc::new(EventService::new()),
+            faults: Arc::default(),
             join_handle: Mutex::new(None),
         }))
     }
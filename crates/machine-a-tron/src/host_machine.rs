@@ -22,8 +22,8 @@ use std::time::{Duration, Instant};
 use bmc_mock::injection::InjectionStore;
 use bmc_mock::mac_address_pool::{MacAddressPool, PoolConfig as MacAddressPoolConfig};
 use bmc_mock::{
-    BmcCommand, HostFirmwareVersions, HostMachineInfo, MachineInfo, SetSystemPowerResult,
-    SystemPowerControl,
+    BmcCommand, EventService, HostFirmwareVersions, HostMachineInfo, MachineInfo,
+    SetSystemPowerResult, SystemPowerControl,
 };
 use carbide_utils::test_support::certs::create_random_self_signed_cert;
 use carbide_uuid::machine::MachineId;
@@ -39,6 +39,7 @@ use crate::config::{self, MachineATronContext, MachineConfig, PersistedDevice};
 use crate::dhcp_wrapper::{DhcpRelayResult, DhcpResponseInfo, DpuDhcpRelay};
 use crate::dpu_machine::{DpuMachine, DpuMachineHandle};
 use crate::machine_state_machine::{LiveState, MachineStateMachine, PersistedMachine};
+use crate::scenario::MachineFaults;
 use crate::status::{
     BmcStatus, DeviceKind, DeviceStatus, DeviceStatusConfig, EndpointStatus, InfinibandPortStatus,
 };
@@ -312,6 +313,8 @@ impl HostMachine {
         let dpus = self.dpus.clone();
         let machine_config_section = self.machine_config_section.clone();
         let bmc_injection = self.state_machine.bmc_injection_store();
+        let bmc_events = self.state_machine.bmc_event_service();
+        let faults = self.state_machine.machine_faults();
 
         if !paused {
             self.resume_dpus();
@@ -339,6 +342,8 @@ impl HostMachine {
             dpus,
             machine_config_section,
             bmc_injection,
+            bmc_events,
+            faults,
 
             join_handle: Mutex::new(Some(join_handle)),
         }))
@@ -477,6 +482,12 @@ impl HostMachine {
                 self.live_state.write().unwrap().api_state = api_state;
                 HandleMessageResult::ContinuePolling
             }
+            HostMachineMessage::SetSystemPower(request) => {
+                _ = self.set_system_power(request).inspect_err(
+                    |e| tracing::warn!(error = %e, ?request, "Could not change host system power"),
+                );
+                HandleMessageResult::ProcessStateNow
+            }
         }
     }
 
@@ -600,6 +611,7 @@ enum HostMachineMessage {
     AttachToUI(Option<mpsc::Sender<UiUpdate>>),
     SetPaused(bool),
     SetApiState(String),
+    SetSystemPower(SystemPowerControl),
 }
 
 #[derive(Debug)]
@@ -612,6 +624,8 @@ struct HostMachineActor {
     dpus: Vec<DpuMachineHandle>,
     machine_config_section: String,
     bmc_injection: Arc<InjectionStore>,
+    bmc_events: Arc<EventService>,
+    faults: Arc<MachineFaults>,
 }
 
 #[derive(Debug, Clone)]
@@ -660,6 +674,8 @@ impl MachineHandle {
             dpus,
             machine_config_section: machine_config_section.to_string(),
             bmc_injection: Arc::new(InjectionStore::new()),
+            bmc_events: Arc::new(EventService::new()),
+            faults: Arc::default(),
         }))
     }
 
@@ -689,6 +705,22 @@ impl MachineHandle {
         self.0.bmc_injection.clone()
     }
 
+    pub(crate) fn bmc_event_service(&self) -> Arc<EventService> {
+        self.0.bmc_events.clone()
+    }
+
+    pub(crate) fn machine_faults(&self) -> Arc<MachineFaults> {
+        self.0.faults.clone()
+    }
+
+    /// Changes the host's power as if its BMC received a Redfish reset.
+    pub(crate) fn set_system_power(&self, request: SystemPowerControl) -> eyre::Result<()> {
+        self.0
+            .message_tx
+            .send(HostMachineMessage::SetSystemPower(request))?;
+        Ok(())
+    }
+
     pub(super) async fn wait_until_machine_up_with_api_state(
         &self,
         state: &str,
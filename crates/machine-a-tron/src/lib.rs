@@ -27,6 +27,7 @@ mod dhcp_wrapper_udp;
 mod discovery_info;
 mod dpu_machine;
 mod host_machine;
+mod lifecycle_report;
 mod machine_a_tron;
 mod machine_fsm;
 mod machine_state_machine;
@@ -35,6 +36,7 @@ mod mock_ssh_server;
 mod power_shelf_fsm;
 mod power_shelf_simulator;
 mod rack;
+mod scenario;
 mod simulator_registry;
 mod status;
 mod subnet;
@@ -60,6 +62,9 @@ pub use device_simulator::{
 };
 pub use dhcp_wrapper::{DhcpClient, UdpDhcpService};
 pub use dpu_machine::DpuMachineHandle;
+pub use lifecycle_report::{
+    DwellStats, LifecycleReport, LifecycleReportConfig, LifecycleSpan, StateEntry, Transition,
+};
 pub use machine_a_tron::{AppEvent, MachineATron};
 pub use machine_state_machine::BmcRegistrationMode;
 pub use mock_ssh_server::{
@@ -67,6 +72,7 @@ pub use mock_ssh_server::{
     spawn as spawn_mock_ssh_server,
 };
 pub use rack::{RackMemberStatus, RackStatus, RacksStatusResponse};
+pub use scenario::{DeviceSelector, Fault, ScenarioConfig, ScenarioStep, ScoutStep, TargetKind};
 pub use simulator_registry::SimulatorRegistry;
 pub use status::{
     DeviceKind, DeviceStatus, DeviceStatusConfig, DevicesStatusResponse, InfinibandPortStatus,
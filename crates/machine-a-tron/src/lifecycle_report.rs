@@ -0,0 +1,675 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Lifecycle benchmark reports.
+//!
+//! While machine-a-tron runs, the recorder polls `FindMachineStateHistories` for every host it
+//! simulates and keeps each host's `ManagedHostState` timeline. When machine-a-tron quits, it
+//! writes a JSON report with dwell-time percentiles per state, the configured spans and the
+//! slowest transitions, plus a plain-text summary next to it. A report from an earlier run can be
+//! given as a baseline, and the summary then shows how each percentile moved.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use duration_str::deserialize_duration;
+use eyre::Context;
+use rpc::forge::MachineEvent;
+use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+use crate::api_client::ApiClient;
+use crate::config::as_std_duration;
+use crate::device_simulator::SimulatorLifecycle;
+use crate::simulator_registry::SimulatorRegistry;
+use crate::status::DeviceKind;
+
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LifecycleReportConfig {
+    /// Where to write the JSON report. The summary is written next to it with a `.txt` extension.
+    pub path: PathBuf,
+    /// How often to fetch state histories while the simulation runs.
+    #[serde(
+        default = "default_poll_interval",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub poll_interval: Duration,
+    /// How many of the slowest transitions to list.
+    #[serde(default = "default_slowest_transitions")]
+    pub slowest_transitions: usize,
+    /// Multi-state spans to measure. Defaults to `created` to `ready`, and `assigned` through
+    /// `waitingforcleanup`.
+    #[serde(default = "default_spans")]
+    pub spans: Vec<LifecycleSpan>,
+    /// A report from an earlier run to compare this run against.
+    #[serde(default)]
+    pub baseline: Option<PathBuf>,
+}
+
+/// A span starts when a host enters `from`. It ends when the host next enters `to`, or when it
+/// next leaves `through`. Exactly one of `to` and `through` must be set.
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LifecycleSpan {
+    pub name: String,
+    pub from: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub through: Option<String>,
+}
+
+impl LifecycleReportConfig {
+    pub(crate) fn validate(&self) -> eyre::Result<()> {
+        eyre::ensure!(
+            !self.poll_interval.is_zero(),
+            "lifecycle_report.poll_interval must be longer than zero"
+        );
+        for span in &self.spans {
+            eyre::ensure!(
+                span.to.is_some() != span.through.is_some(),
+                "lifecycle_report span {} must set exactly one of to and through",
+                span.name
+            );
+        }
+        Ok(())
+    }
+
+    fn summary_path(&self) -> PathBuf {
+        self.path.with_extension("txt")
+    }
+}
+
+fn default_poll_interval() -> Duration {
+    Duration::from_secs(30)
+}
+
+fn default_slowest_transitions() -> usize {
+    10
+}
+
+fn default_spans() -> Vec<LifecycleSpan> {
+    vec![
+        LifecycleSpan {
+            name: "created_to_ready".to_string(),
+            from: "created".to_string(),
+            to: Some("ready".to_string()),
+            through: None,
+        },
+        LifecycleSpan {
+            name: "assigned_through_cleanup".to_string(),
+            from: "assigned".to_string(),
+            to: None,
+            through: Some("waitingforcleanup".to_string()),
+        },
+    ]
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct LifecycleReport {
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    /// Dwell time per `ManagedHostState`, keyed by the state's serialized name.
+    pub states: BTreeMap<String, DwellStats>,
+    pub spans: BTreeMap<String, DwellStats>,
+    pub slowest_transitions: Vec<Transition>,
+    /// Each host's state timeline during the run, keyed by machine ID.
+    pub timelines: BTreeMap<String, Vec<StateEntry>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct StateEntry {
+    pub state: String,
+    pub entered_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Transition {
+    pub machine_id: String,
+    pub from: String,
+    pub to: String,
+    pub entered_at: DateTime<Utc>,
+    pub dwell_seconds: f64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+pub struct DwellStats {
+    pub count: usize,
+    pub p50_seconds: f64,
+    pub p95_seconds: f64,
+    pub p99_seconds: f64,
+    pub max_seconds: f64,
+}
+
+impl DwellStats {
+    fn from_seconds(mut seconds: Vec<f64>) -> Option<Self> {
+        if seconds.is_empty() {
+            return None;
+        }
+        seconds.sort_by(f64::total_cmp);
+        Some(Self {
+            count: seconds.len(),
+            p50_seconds: percentile(&seconds, 50),
+            p95_seconds: percentile(&seconds, 95),
+            p99_seconds: percentile(&seconds, 99),
+            max_seconds: seconds[seconds.len() - 1],
+        })
+    }
+}
+
+/// Nearest-rank percentile of already sorted, non-empty samples.
+fn percentile(sorted: &[f64], percent: usize) -> f64 {
+    let rank = (sorted.len() * percent).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// The top-level `ManagedHostState` of a history record, e.g. `hostinit` for any `HostInit`
+/// sub-state. Records that are not JSON are reported as-is.
+fn state_name(event: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(event)
+        .ok()
+        .and_then(|value| value.get("state")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| event.to_string())
+}
+
+/// Turns a host's history records into top-level state entries, oldest first. Sub-state changes
+/// within one top-level state do not start a new entry.
+fn timeline(records: &BTreeMap<(DateTime<Utc>, String), String>) -> Vec<StateEntry> {
+    let mut entries: Vec<StateEntry> = Vec::new();
+    for ((entered_at, _version), event) in records {
+        let state = state_name(event);
+        if entries.last().is_some_and(|last| last.state == state) {
+            continue;
+        }
+        entries.push(StateEntry {
+            state,
+            entered_at: *entered_at,
+        });
+    }
+    entries
+}
+
+fn seconds_between(start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+    (end - start).num_milliseconds() as f64 / 1000.0
+}
+
+impl LifecycleReport {
+    fn build(
+        config: &LifecycleReportConfig,
+        started_at: DateTime<Utc>,
+        finished_at: DateTime<Utc>,
+        timelines: BTreeMap<String, Vec<StateEntry>>,
+    ) -> Self {
+        // Only count dwells that began during this run; hosts restored from persisted devices
+        // carry history from earlier runs.
+        let mut transitions = timelines
+            .iter()
+            .flat_map(|(machine_id, entries)| {
+                entries.windows(2).map(move |pair| Transition {
+                    machine_id: machine_id.clone(),
+                    from: pair[0].state.clone(),
+                    to: pair[1].state.clone(),
+                    entered_at: pair[0].entered_at,
+                    dwell_seconds: seconds_between(pair[0].entered_at, pair[1].entered_at),
+                })
+            })
+            .filter(|transition| transition.entered_at >= started_at)
+            .collect::<Vec<_>>();
+
+        let mut dwells: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+        for transition in &transitions {
+            dwells
+                .entry(transition.from.clone())
+                .or_default()
+                .push(transition.dwell_seconds);
+        }
+        let states = dwells
+            .into_iter()
+            .filter_map(|(state, seconds)| Some((state, DwellStats::from_seconds(seconds)?)))
+            .collect();
+
+        let spans = config
+            .spans
+            .iter()
+            .filter_map(|span| {
+                let seconds = timelines
+                    .values()
+                    .flat_map(|entries| span_durations(span, entries, started_at))
+                    .collect();
+                Some((span.name.clone(), DwellStats::from_seconds(seconds)?))
+            })
+            .collect();
+
+        transitions.sort_by(|a, b| b.dwell_seconds.total_cmp(&a.dwell_seconds));
+        transitions.truncate(config.slowest_transitions);
+
+        Self {
+            started_at,
+            finished_at,
+            states,
+            spans,
+            slowest_transitions: transitions,
+            timelines,
+        }
+    }
+
+    /// A plain-text rendering of the report, with percentile changes against `baseline` when
+    /// one is given.
+    fn summary(&self, baseline: Option<&LifecycleReport>) -> String {
+        let mut out = String::new();
+        _ = writeln!(
+            out,
+            "Lifecycle report: {} hosts, {} to {}",
+            self.timelines.len(),
+            self.started_at.to_rfc3339(),
+            self.finished_at.to_rfc3339()
+        );
+        for (title, stats, baseline_stats) in [
+            (
+                "State dwell times",
+                &self.states,
+                baseline.map(|baseline| &baseline.states),
+            ),
+            (
+                "Spans",
+                &self.spans,
+                baseline.map(|baseline| &baseline.spans),
+            ),
+        ] {
+            _ = writeln!(out, "\n{title} (seconds):");
+            _ = writeln!(
+                out,
+                "  {:<28} {:>6} {:>10} {:>10} {:>10} {:>10}",
+                "name", "count", "p50", "p95", "p99", "max"
+            );
+            for (name, stats) in stats {
+                _ = writeln!(
+                    out,
+                    "  {:<28} {:>6} {:>10.1} {:>10.1} {:>10.1} {:>10.1}",
+                    name,
+                    stats.count,
+                    stats.p50_seconds,
+                    stats.p95_seconds,
+                    stats.p99_seconds,
+                    stats.max_seconds
+                );
+                if let Some(before) = baseline_stats.and_then(|baseline| baseline.get(name)) {
+                    _ = writeln!(
+                        out,
+                        "  {:<28} {:>6} {:>10} {:>10} {:>10} {:>10}",
+                        "  vs baseline",
+                        "",
+                        change(before.p50_seconds, stats.p50_seconds),
+                        change(before.p95_seconds, stats.p95_seconds),
+                        change(before.p99_seconds, stats.p99_seconds),
+                        change(before.max_seconds, stats.max_seconds)
+                    );
+                }
+            }
+        }
+        _ = writeln!(out, "\nSlowest transitions:");
+        for transition in &self.slowest_transitions {
+            _ = writeln!(
+                out,
+                "  {:>10.1}s  {} -> {}  {} at {}",
+                transition.dwell_seconds,
+                transition.from,
+                transition.to,
+                transition.machine_id,
+                transition.entered_at.to_rfc3339()
+            );
+        }
+        out
+    }
+
+    fn write(&self, config: &LifecycleReportConfig) -> eyre::Result<()> {
+        let baseline = config
+            .baseline
+            .as_deref()
+            .map(LifecycleReport::read)
+            .transpose()?;
+        std::fs::write(&config.path, serde_json::to_vec_pretty(self)?)
+            .wrap_err_with(|| format!("could not write {}", config.path.display()))?;
+        let summary = self.summary(baseline.as_ref());
+        let summary_path = config.summary_path();
+        std::fs::write(&summary_path, &summary)
+            .wrap_err_with(|| format!("could not write {}", summary_path.display()))?;
+        tracing::info!(path = %config.path.display(), "wrote lifecycle report\n{summary}");
+        Ok(())
+    }
+
+    fn read(path: &Path) -> eyre::Result<Self> {
+        let contents = std::fs::read(path)
+            .wrap_err_with(|| format!("could not read baseline {}", path.display()))?;
+        serde_json::from_slice(&contents)
+            .wrap_err_with(|| format!("could not parse baseline {}", path.display()))
+    }
+}
+
+/// The relative change from `before` to `after`, e.g. `+12.5%`.
+fn change(before: f64, after: f64) -> String {
+    if before == 0.0 {
+        return "n/a".to_string();
+    }
+    format!("{:+.1}%", (after - before) / before * 100.0)
+}
+
+/// How long each pass through `span` took in one host's timeline.
+fn span_durations(
+    span: &LifecycleSpan,
+    entries: &[StateEntry],
+    started_at: DateTime<Utc>,
+) -> Vec<f64> {
+    let mut durations = Vec::new();
+    let mut index = 0;
+    while let Some(start) = entries[index..]
+        .iter()
+        .position(|entry| entry.state == span.from && entry.entered_at >= started_at)
+        .map(|offset| index + offset)
+    {
+        let end = match (&span.to, &span.through) {
+            (Some(to), _) => entries[start + 1..]
+                .iter()
+                .position(|entry| &entry.state == to)
+                .map(|offset| start + 1 + offset),
+            (None, Some(through)) => entries[start..]
+                .iter()
+                .position(|entry| &entry.state == through)
+                .map(|offset| start + offset + 1)
+                .filter(|end| *end < entries.len()),
+            (None, None) => None,
+        };
+        let Some(end) = end else {
+            break;
+        };
+        durations.push(seconds_between(
+            entries[start].entered_at,
+            entries[end].entered_at,
+        ));
+        index = end;
+    }
+    durations
+}
+
+/// Polls state histories for the simulated hosts and writes the report when stopped.
+pub(crate) struct LifecycleRecorder {
+    config: LifecycleReportConfig,
+    api_client: ApiClient,
+    simulators: SimulatorRegistry,
+    started_at: DateTime<Utc>,
+    histories: HashMap<String, BTreeMap<(DateTime<Utc>, String), String>>,
+}
+
+pub(crate) struct LifecycleRecorderHandle {
+    stop_tx: oneshot::Sender<()>,
+    join_handle: JoinHandle<eyre::Result<()>>,
+}
+
+impl LifecycleRecorderHandle {
+    /// Fetches the final histories and writes the report.
+    pub(crate) async fn finish(self) -> eyre::Result<()> {
+        _ = self.stop_tx.send(());
+        self.join_handle.await?
+    }
+}
+
+impl LifecycleRecorder {
+    pub(crate) fn new(
+        config: LifecycleReportConfig,
+        api_client: ApiClient,
+        simulators: SimulatorRegistry,
+    ) -> Self {
+        Self {
+            config,
+            api_client,
+            simulators,
+            started_at: Utc::now(),
+            histories: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn spawn(self) -> LifecycleRecorderHandle {
+        let (stop_tx, stop_rx) = oneshot::channel();
+        LifecycleRecorderHandle {
+            stop_tx,
+            join_handle: tokio::spawn(self.run(stop_rx)),
+        }
+    }
+
+    async fn run(mut self, mut stop_rx: oneshot::Receiver<()>) -> eyre::Result<()> {
+        let mut interval = tokio::time::interval(self.config.poll_interval);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => self.poll().await,
+                _ = &mut stop_rx => break,
+            }
+        }
+        self.poll().await;
+
+        let timelines = self
+            .histories
+            .iter()
+            .map(|(machine_id, records)| (machine_id.clone(), timeline(records)))
+            .collect();
+        LifecycleReport::build(&self.config, self.started_at, Utc::now(), timelines)
+            .write(&self.config)
+    }
+
+    async fn poll(&mut self) {
+        let machine_ids = self
+            .simulators
+            .devices()
+            .iter()
+            .map(SimulatorLifecycle::handle)
+            .filter(|handle| handle.kind() == DeviceKind::Machine)
+            .filter_map(|handle| handle.observed_machine_id())
+            .collect::<Vec<MachineId>>();
+
+        // Max of 100 machine IDs at a time
+        for chunk in machine_ids.chunks(100) {
+            let histories = match self
+                .api_client
+                .find_machine_state_histories(chunk.to_vec())
+                .await
+            {
+                Ok(histories) => histories,
+                Err(e) => {
+                    tracing::warn!(error = %e, "API failure getting machine state histories");
+                    continue;
+                }
+            };
+            for (machine_id, records) in histories {
+                let known = self.histories.entry(machine_id).or_default();
+                for MachineEvent {
+                    event,
+                    version,
+                    time,
+                } in records
+                {
+                    let Some(time) = time.and_then(|time| DateTime::<Utc>::try_from(time).ok())
+                    else {
+                        continue;
+                    };
+                    known.insert((time, version), event);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::value_scenarios;
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000 + seconds, 0).unwrap()
+    }
+
+    fn entries(states: &[(&str, i64)]) -> Vec<StateEntry> {
+        states
+            .iter()
+            .map(|(state, seconds)| StateEntry {
+                state: state.to_string(),
+                entered_at: at(*seconds),
+            })
+            .collect()
+    }
+
+    fn config() -> LifecycleReportConfig {
+        toml::from_str(r#"path = "/tmp/lifecycle.json""#).expect("config parses")
+    }
+
+    #[test]
+    fn state_names_are_top_level_states() {
+        value_scenarios!(state_name:
+            "managed host states" {
+                r#"{"state":"ready"}"# => "ready".to_string(),
+                r#"{"state":"hostinit","machine_state":{"state":"waitingfordiscovery"}}"# => "hostinit".to_string(),
+            }
+            "unparsable records" {
+                "legacy" => "legacy".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let samples = (1..=200).map(f64::from).collect::<Vec<_>>();
+        value_scenarios!(run = |percent| percentile(&samples, percent);
+            "ranks" {
+                50 => 100.0,
+                95 => 190.0,
+                99 => 198.0,
+                100 => 200.0,
+            }
+        );
+        assert_eq!(percentile(&[7.0], 99), 7.0);
+    }
+
+    #[test]
+    fn timeline_merges_sub_states() {
+        let records = BTreeMap::from([
+            (
+                (at(0), "V1".to_string()),
+                r#"{"state":"created"}"#.to_string(),
+            ),
+            (
+                (at(10), "V2".to_string()),
+                r#"{"state":"hostinit","machine_state":{"state":"a"}}"#.to_string(),
+            ),
+            (
+                (at(20), "V3".to_string()),
+                r#"{"state":"hostinit","machine_state":{"state":"b"}}"#.to_string(),
+            ),
+            (
+                (at(45), "V4".to_string()),
+                r#"{"state":"ready"}"#.to_string(),
+            ),
+        ]);
+
+        assert_eq!(
+            timeline(&records),
+            entries(&[("created", 0), ("hostinit", 10), ("ready", 45)])
+        );
+    }
+
+    #[test]
+    fn spans_end_on_entering_to_or_leaving_through() {
+        let timeline = entries(&[
+            ("created", 0),
+            ("hostinit", 10),
+            ("ready", 60),
+            ("assigned", 100),
+            ("waitingforcleanup", 160),
+            ("validation", 200),
+            ("ready", 230),
+            ("assigned", 300),
+            ("waitingforcleanup", 330),
+        ]);
+        let [created_to_ready, assigned_through_cleanup] = default_spans().try_into().unwrap();
+
+        assert_eq!(
+            span_durations(&created_to_ready, &timeline, at(0)),
+            vec![60.0]
+        );
+        // The second assignment is still cleaning up, so it is not counted.
+        assert_eq!(
+            span_durations(&assigned_through_cleanup, &timeline, at(0)),
+            vec![100.0]
+        );
+        assert_eq!(
+            span_durations(&created_to_ready, &timeline, at(1)),
+            Vec::<f64>::new()
+        );
+    }
+
+    #[test]
+    fn report_counts_dwells_started_during_the_run() {
+        let mut config = config();
+        config.slowest_transitions = 2;
+        let timelines = BTreeMap::from([
+            (
+                "host-a".to_string(),
+                entries(&[("created", -50), ("hostinit", 10), ("ready", 40)]),
+            ),
+            (
+                "host-b".to_string(),
+                entries(&[("created", 0), ("hostinit", 5), ("ready", 95)]),
+            ),
+        ]);
+
+        let report = LifecycleReport::build(&config, at(0), at(100), timelines);
+
+        assert_eq!(
+            report.states.keys().collect::<Vec<_>>(),
+            ["created", "hostinit"]
+        );
+        assert_eq!(report.states["created"].count, 1);
+        assert_eq!(report.states["hostinit"].count, 2);
+        assert_eq!(report.states["hostinit"].max_seconds, 90.0);
+        assert_eq!(report.spans["created_to_ready"].count, 1);
+        assert_eq!(
+            report
+                .slowest_transitions
+                .iter()
+                .map(|transition| (transition.machine_id.as_str(), transition.dwell_seconds))
+                .collect::<Vec<_>>(),
+            [("host-b", 90.0), ("host-a", 30.0)]
+        );
+
+        let summary = report.summary(Some(&report));
+        assert!(summary.contains("vs baseline"), "{summary}");
+    }
+
+    #[test]
+    fn spans_need_exactly_one_end() {
+        let mut config = config();
+        assert!(config.validate().is_ok());
+        config.spans[0].through = Some("ready".to_string());
+        assert!(config.validate().is_err());
+    }
+}
@@ -31,8 +31,10 @@ use crate::device_simulator::{
     DeviceSimulator, MachineSimulator, PowerShelfSimulator, SimulatorLifecycle, SwitchSimulator,
 };
 use crate::host_machine::HostMachine;
+use crate::lifecycle_report::LifecycleRecorder;
 use crate::machine_utils::get_next_free_machine;
 use crate::power_shelf_simulator::PowerShelfActor;
+use crate::scenario::ScenarioRunner;
 use crate::simulator_registry::SimulatorRegistry;
 use crate::status::DeviceKind;
 use crate::subnet::Subnet;
@@ -431,12 +433,45 @@ impl MachineATron {
             simulator.resume()?;
         }
 
+        let scenario_task = match self.app_context.app_config.scenario.as_ref() {
+            Some(scenario) => {
+                let machines = self
+                    .app_context
+                    .app_config
+                    .resolved_device_configs()?
+                    .machines;
+                Some(ScenarioRunner::new(scenario, &simulators, &machines).spawn())
+            }
+            None => None,
+        };
+        let mut lifecycle_recorder =
+            self.app_context
+                .app_config
+                .lifecycle_report
+                .clone()
+                .map(|config| {
+                    LifecycleRecorder::new(
+                        config,
+                        self.app_context.api_client(),
+                        simulators.clone(),
+                    )
+                    .spawn()
+                });
+
         tracing::info!("Machine construction complete");
 
         while let Some(msg) = app_rx.recv().await {
             match msg {
                 AppEvent::Quit => {
                     tracing::info!("quit");
+                    if let Some(scenario_task) = scenario_task.as_ref() {
+                        scenario_task.abort();
+                    }
+                    if let Some(lifecycle_recorder) = lifecycle_recorder.take() {
+                        _ = lifecycle_recorder.finish().await.inspect_err(
+                            |e| tracing::error!(error = %e, "Could not write lifecycle report"),
+                        );
+                    }
                     let cleanup_on_quit = self.app_context.app_config.cleanup_on_quit;
                     let persisted_devices =
                         try_join_all(simulators.devices().iter().cloned().map(|simulator| {
@@ -288,6 +288,8 @@ pub(super) enum Event {
     InitialDiscoveryCompleted,
     AgentControlCompleted,
     MachineNotFound,
+    /// The agent process died; the OS stays up with nothing running until reboot.
+    AgentCrashed,
     NetworkObservationCompleted,
     DpuFlippedToNicMode,
 }
@@ -383,7 +385,7 @@ impl ScoutFsm {
                 Self::PollingLoop,
                 vec![Action::AgentControlRequest(OsImage::Scout)],
             ),
-            Event::MachineNotFound => (Self::FailedAndWaitForReboot, vec![]),
+            Event::MachineNotFound | Event::AgentCrashed => (Self::FailedAndWaitForReboot, vec![]),
             _ => (self, vec![]),
         }
     }
@@ -396,7 +398,7 @@ impl ScoutFsm {
             Event::TimerAlert(Timer::ScoutAgentControlPoll) => {
                 (self, vec![Action::AgentControlRequest(OsImage::Scout)])
             }
-            Event::MachineNotFound => (Self::FailedAndWaitForReboot, vec![]),
+            Event::MachineNotFound | Event::AgentCrashed => (Self::FailedAndWaitForReboot, vec![]),
             _ => (self, vec![]),
         }
     }
@@ -505,4 +507,17 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn scout_crash_waits_for_reboot() {
+        for scout_fsm in [ScoutFsm::Discovery, ScoutFsm::PollingLoop] {
+            let (os_fsm, actions) = OsFsm::Scout(scout_fsm).event(Event::AgentCrashed);
+
+            assert!(
+                os_fsm.is_awaiting_reboot(),
+                "scout crash in {scout_fsm:?} should wait for reboot"
+            );
+            assert!(actions.is_empty(), "scout crash in {scout_fsm:?} acted");
+        }
+    }
 }
@@ -24,8 +24,8 @@ use std::time::Duration;
 use bmc_mock::injection::InjectionStore;
 use bmc_mock::ipmi_sim::IpmiEndpoint;
 use bmc_mock::{
-    BmcCommand, BmcEvent, BmcState, BootOptionKind, Callbacks, HostnameQuerying, MachineInfo,
-    MockPowerState, POWER_CYCLE_DELAY, SetSystemPowerError, SetSystemPowerResult,
+    BmcCommand, BmcEvent, BmcState, BootOptionKind, Callbacks, EventService, HostnameQuerying,
+    MachineInfo, MockPowerState, POWER_CYCLE_DELAY, SetSystemPowerError, SetSystemPowerResult,
     SystemPowerControl,
 };
 use carbide_network::virtualization::build_dual_stack_list;
@@ -50,6 +50,7 @@ use crate::machine_state_machine::MachineStateError::MissingMachineId;
 use crate::machine_utils::{
     PxeError, PxeResponse, forge_agent_control, get_validation_id, send_pxe_boot_request,
 };
+use crate::scenario::{MachineFaults, ScoutStep};
 use crate::{Guid, InfinibandPortState, PersistedDevice, PersistedDpuMachine};
 
 type DpuDhcpRelayHandle = oneshot::Sender<()>;
@@ -162,6 +163,8 @@ pub(super) struct MachineStateMachine {
     bmc_mock: Option<Arc<BmcMockWrapperHandle>>,
     bmc_state: Option<BmcState>,
     bmc_injection: Arc<InjectionStore>,
+    bmc_events: Arc<EventService>,
+    faults: Arc<MachineFaults>,
     power_cycle_deadline: Option<Instant>,
     machine_on_deadline: Option<Instant>,
     agent_polling_deadline: Option<(Instant, Timer)>,
@@ -365,6 +368,8 @@ impl MachineStateMachine {
             bmc_mock: None,
             bmc_state: None,
             bmc_injection: Arc::new(InjectionStore::new()),
+            bmc_events: Arc::new(EventService::new()),
+            faults: Arc::default(),
             power_cycle_deadline: None,
             machine_on_deadline: None,
             agent_polling_deadline: None,
@@ -410,6 +415,8 @@ impl MachineStateMachine {
             bmc_mock: None,
             bmc_state: None,
             bmc_injection: Arc::new(InjectionStore::new()),
+            bmc_events: Arc::new(EventService::new()),
+            faults: Arc::default(),
             machine_dhcp_info: None,
             dhcp_retry: DhcpRetryState::default(),
             machine_discovery_result: None,
@@ -586,6 +593,17 @@ impl MachineStateMachine {
                     }
                 }
                 FsmAction::InitialDiscoveryRequest(os_image) => {
+                    if self.faults.discovery_stalled() {
+                        return Some(self.config.discovery_retry_interval);
+                    }
+                    if *os_image == OsImage::Scout
+                        && self.faults.scout_crashes_at(ScoutStep::Discovery)
+                    {
+                        tracing::warn!("scout crashed before initial discovery");
+                        self.actions.pop_front();
+                        self.fsm_event(Event::AgentCrashed);
+                        continue;
+                    }
                     match self.initial_discovery_request(*os_image).await {
                         Ok(None) => {
                             self.actions.pop_front();
@@ -611,6 +629,11 @@ impl MachineStateMachine {
                             self.actions.pop_front();
                             self.fsm_event(Event::MachineNotFound)
                         }
+                        Err(MachineStateError::AgentCrashed(step)) => {
+                            tracing::warn!(?step, "scout crashed during agent control");
+                            self.actions.pop_front();
+                            self.fsm_event(Event::AgentCrashed)
+                        }
                         Err(_) => return Some(self.config.run_interval_working),
                     }
                 }
@@ -904,6 +927,10 @@ impl MachineStateMachine {
             .and_then(|result| result.machine_id)
             .ok_or(MissingMachineId)?;
 
+        if os_image == OsImage::Scout && self.faults.scout_crashes_at(ScoutStep::AgentControl) {
+            return Err(MachineStateError::AgentCrashed(ScoutStep::AgentControl));
+        }
+
         // Ask the API server what to do next
         let start = Instant::now();
         let Some(control_response) = forge_agent_control(&self.app_context, machine_id).await
@@ -919,14 +946,24 @@ impl MachineStateMachine {
         match &control_response.action {
             Some(Action::Discovery(_)) => self.send_discovery_complete(&machine_id).await?,
             Some(Action::MachineValidation(_)) if os_image == OsImage::Scout => {
+                if self.faults.scout_crashes_at(ScoutStep::Validation) {
+                    return Err(MachineStateError::AgentCrashed(ScoutStep::Validation));
+                }
                 if let Some(validation_id) = get_validation_id(&control_response) {
                     self.app_context
                         .api_client()
-                        .machine_validation_complete(&machine_id, &validation_id)
+                        .machine_validation_complete(
+                            &machine_id,
+                            &validation_id,
+                            self.faults.validation_failure(),
+                        )
                         .await?;
                 }
             }
             Some(Action::Reset(_)) if os_image == OsImage::Scout => {
+                if self.faults.scout_crashes_at(ScoutStep::Cleanup) {
+                    return Err(MachineStateError::AgentCrashed(ScoutStep::Cleanup));
+                }
                 tracing::debug!("Got Reset action in scout image, sending cleanup_complete");
                 // Wait a bit before confirming the cleanup in order to mimic real
                 // cleanup and give the tests a higher chance to observe teh cleanup state
@@ -1248,6 +1285,14 @@ impl MachineStateMachine {
         self.bmc_injection.clone()
     }
 
+    pub(crate) fn machine_faults(&self) -> Arc<MachineFaults> {
+        self.faults.clone()
+    }
+
+    pub(crate) fn bmc_event_service(&self) -> Arc<EventService> {
+        self.bmc_events.clone()
+    }
+
     pub(super) fn booted_os(&self) -> MaybeOsImage {
         MaybeOsImage(self.fsm.booted_os())
     }
@@ -1266,6 +1311,7 @@ impl MachineStateMachine {
             Arc::new(LiveStateHostnameQuery(self.live_state.clone())),
             self.mat_host_id,
             self.bmc_injection.clone(),
+            self.bmc_events.clone(),
         );
 
         let pw_override = match &self.machine_info {
@@ -1405,6 +1451,8 @@ pub(super) enum MachineStateError {
     WrongOsForMachine(String),
     #[error("machine not found: {0}")]
     MachineNotFound(MachineId),
+    #[error("scout crashed at {0:?}")]
+    AgentCrashed(ScoutStep),
 }
 impl From<tonic::Status> for MachineStateError {
     fn from(err: tonic::Status) -> Self {
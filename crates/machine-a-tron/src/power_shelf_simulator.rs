@@ -24,8 +24,8 @@ use bmc_mock::injection::InjectionStore;
 use bmc_mock::ipmi_sim::IpmiEndpoint;
 use bmc_mock::mac_address_pool::{MacAddressPool, PoolConfig as MacAddressPoolConfig};
 use bmc_mock::{
-    BmcCommand, Callbacks, HardwareType, HostMachineInfo, HostnameQuerying, MachineInfo,
-    MockPowerState, POWER_CYCLE_DELAY, SetSystemPowerError, SetSystemPowerResult,
+    BmcCommand, Callbacks, EventService, HardwareType, HostMachineInfo, HostnameQuerying,
+    MachineInfo, MockPowerState, POWER_CYCLE_DELAY, SetSystemPowerError, SetSystemPowerResult,
     SystemPowerControl,
 };
 use tokio::sync::mpsc;
@@ -110,6 +110,7 @@ pub(crate) struct PowerShelfActor {
     config: Arc<MachineConfig>,
     live_state: Arc<RwLock<PowerShelfLiveState>>,
     bmc_injection: Arc<InjectionStore>,
+    bmc_events: Arc<EventService>,
     _bmc_mock: Option<Arc<BmcMockWrapperHandle>>,
     bmc_dhcp_info: Option<DhcpResponseInfo>,
     fsm: PowerShelfFsm,
@@ -138,6 +139,7 @@ impl PowerShelfActor {
             config,
             live_state: Arc::new(RwLock::new(PowerShelfLiveState::new(&fsm))),
             bmc_injection: Arc::new(InjectionStore::new()),
+            bmc_events: Arc::new(EventService::new()),
             _bmc_mock: None,
             bmc_dhcp_info: None,
             fsm,
@@ -177,6 +179,7 @@ impl PowerShelfActor {
             config,
             live_state: Arc::new(RwLock::new(PowerShelfLiveState::new(&fsm))),
             bmc_injection: Arc::new(InjectionStore::new()),
+            bmc_events: Arc::new(EventService::new()),
             _bmc_mock: None,
             bmc_dhcp_info: None,
             fsm,
@@ -193,6 +196,7 @@ impl PowerShelfActor {
         let host_info = self.host_info.clone();
         let machine_config_section = self.machine_config_section.clone();
         let bmc_injection = self.bmc_injection.clone();
+        let bmc_events = self.bmc_events.clone();
         let (actor, mailbox) = Actor::new(self, PowerShelfMessage::Run);
 
         let join_handle = tokio::task::Builder::new()
@@ -208,6 +212,7 @@ impl PowerShelfActor {
             host_info,
             machine_config_section,
             bmc_injection,
+            bmc_events,
         }))
     }
 
@@ -330,6 +335,7 @@ impl PowerShelfActor {
             Arc::new(PowerShelfHostname),
             self.mat_id,
             self.bmc_injection.clone(),
+            self.bmc_events.clone(),
         );
         if self.host_info.hw_type != HardwareType::LiteOnPowerShelf
             && let Some(password) = self.app_context.app_config.host_bmc_password.as_deref()
@@ -465,6 +471,7 @@ struct PowerShelfActorHandle {
     host_info: HostMachineInfo,
     machine_config_section: String,
     bmc_injection: Arc<InjectionStore>,
+    bmc_events: Arc<EventService>,
 }
 
 #[derive(Debug, Clone)]
@@ -492,6 +499,17 @@ impl PowerShelfHandle {
         Ok(())
     }
 
+    /// Changes the device's power as if its BMC received a Redfish reset.
+    pub(crate) fn set_system_power(&self, request: SystemPowerControl) -> eyre::Result<()> {
+        self.0
+            .mailbox
+            .send(PowerShelfMessage::Bmc(BmcCommand::SetSystemPower {
+                request,
+                reply: None,
+            }))?;
+        Ok(())
+    }
+
     pub(crate) fn host_info(&self) -> &HostMachineInfo {
         &self.0.host_info
     }
@@ -549,6 +567,10 @@ impl PowerShelfHandle {
         self.0.bmc_injection.clone()
     }
 
+    pub(crate) fn bmc_event_service(&self) -> Arc<EventService> {
+        self.0.bmc_events.clone()
+    }
+
     pub(crate) fn abort(&self) {
         if let Some(join_handle) = self.0.join_handle.lock().unwrap().take() {
             join_handle.abort();
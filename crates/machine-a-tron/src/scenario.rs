@@ -0,0 +1,624 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Declarative, time-scripted fault injection for a simulated fleet.
+//!
+//! A scenario is an ordered list of steps. Each step waits until `at` after the
+//! devices start, applies a [`Fault`] to the devices its [`DeviceSelector`]
+//! picks, and optionally lifts it again `for` later. Faults reach the devices
+//! through the same paths real failures would:
+//!
+//! - Agent faults (discovery stalls, failed validation, scout crashes) are
+//!   flags in the machine's [`MachineFaults`], which the machine state machine
+//!   checks before running the matching FSM action.
+//! - BMC faults are rules in the device's bmc-mock [`InjectionStore`].
+//! - Power faults are delivered like a Redfish reset, so they flow through
+//!   `machine_fsm`, `switch_fsm` or `power_shelf_fsm` as usual.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use bmc_mock::SystemPowerControl;
+use bmc_mock::injection::{Action as InjectionAction, InjectionStore, Rule, RuleId, Selector};
+use carbide_uuid::rack::RackId;
+use duration_str::deserialize_duration;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::config::{MachineConfig, as_std_duration};
+use crate::device_simulator::SimulatorLifecycle;
+use crate::simulator_registry::SimulatorRegistry;
+use crate::status::DeviceKind;
+use crate::{DeviceHandle, DpuMachineHandle};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioConfig {
+    #[serde(default)]
+    pub steps: Vec<ScenarioStep>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioStep {
+    /// Offset from the moment the simulated devices start.
+    #[serde(
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub at: Duration,
+    /// How long the fault lasts. When omitted, it lasts until machine-a-tron exits.
+    #[serde(
+        rename = "for",
+        default,
+        deserialize_with = "deserialize_option_duration",
+        serialize_with = "serialize_option_duration",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub lasts: Option<Duration>,
+    pub target: DeviceSelector,
+    pub fault: Fault,
+}
+
+/// Picks the devices a step applies to. Devices are taken in the order
+/// machine-a-tron created them, so a `count` selects the same devices on
+/// every run with the same config.
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DeviceSelector {
+    pub kind: TargetKind,
+    /// Only devices created from this `machines` section.
+    #[serde(default)]
+    pub section: Option<String>,
+    /// Only devices in this simulated rack.
+    #[serde(default)]
+    pub rack_id: Option<RackId>,
+    /// At most this many of the matching devices. Default: all of them.
+    #[serde(default)]
+    pub count: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetKind {
+    Host,
+    /// The DPUs of the matching hosts.
+    Dpu,
+    Switch,
+    PowerShelf,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Fault {
+    /// The machine's agent keeps retrying initial discovery without ever
+    /// reaching the API, so the machine never finishes discovery.
+    StallDiscovery,
+    /// Scout reports every machine validation run as failed.
+    FailValidation {
+        #[serde(default = "default_validation_failure")]
+        message: String,
+    },
+    /// Scout crashes when it reaches `step`. The host stays powered on with no
+    /// agent running until something reboots it.
+    CrashScout { step: ScoutStep },
+    /// The BMC answers requests matching `glob` with `status` instead of
+    /// serving them.
+    BmcUnreachable {
+        #[serde(default = "default_unreachable_status")]
+        status: u16,
+        #[serde(default = "default_unreachable_glob")]
+        glob: String,
+    },
+    /// The device loses power, as if its feed dropped. Power returns when the
+    /// fault is lifted.
+    PowerLoss,
+}
+
+/// Where in its run scout crashes under [`Fault::CrashScout`].
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoutStep {
+    /// Before reporting initial discovery.
+    Discovery,
+    /// Before polling the API for its next action.
+    AgentControl,
+    /// On being told to run machine validation.
+    Validation,
+    /// On being told to clean up the machine.
+    Cleanup,
+}
+
+impl Fault {
+    fn applies_to(&self, kind: TargetKind) -> bool {
+        match self {
+            Self::StallDiscovery => matches!(kind, TargetKind::Host | TargetKind::Dpu),
+            Self::FailValidation { .. } | Self::CrashScout { .. } => kind == TargetKind::Host,
+            Self::BmcUnreachable { .. } | Self::PowerLoss => true,
+        }
+    }
+}
+
+impl ScenarioConfig {
+    /// Checks the steps against the resolved `machines` sections, including
+    /// the ones generated for racks.
+    pub(crate) fn validate(
+        &self,
+        machines: &BTreeMap<String, Arc<MachineConfig>>,
+    ) -> eyre::Result<()> {
+        for (index, step) in self.steps.iter().enumerate() {
+            let target = &step.target;
+            eyre::ensure!(
+                step.fault.applies_to(target.kind),
+                "scenario.steps[{index}]: fault {:?} cannot target {:?} devices",
+                step.fault,
+                target.kind
+            );
+            if let Some(section) = target.section.as_ref() {
+                eyre::ensure!(
+                    machines.contains_key(section),
+                    "scenario.steps[{index}]: no machines section named {section}"
+                );
+            }
+            if let Some(rack_id) = target.rack_id.as_ref() {
+                eyre::ensure!(
+                    machines
+                        .values()
+                        .any(|machine| machine.rack_id.as_ref() == Some(rack_id)),
+                    "scenario.steps[{index}]: no simulated devices are in rack {rack_id}"
+                );
+            }
+            eyre::ensure!(
+                target.count != Some(0),
+                "scenario.steps[{index}]: target.count must be at least 1"
+            );
+            eyre::ensure!(
+                step.lasts.is_none_or(|lasts| !lasts.is_zero()),
+                "scenario.steps[{index}]: for must be longer than zero"
+            );
+            if let Fault::BmcUnreachable { status, .. } = &step.fault {
+                eyre::ensure!(
+                    (100..=599).contains(status),
+                    "scenario.steps[{index}]: {status} is not an HTTP status code"
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Faults currently applied to one simulated machine. Shared between the
+/// machine's state machine, which checks it before acting, and the scenario
+/// runner, which sets and clears it.
+#[derive(Debug, Default)]
+pub(crate) struct MachineFaults(RwLock<ActiveMachineFaults>);
+
+#[derive(Debug, Default)]
+struct ActiveMachineFaults {
+    discovery_stalled: bool,
+    validation_failure: Option<String>,
+    scout_crash: Option<ScoutStep>,
+}
+
+impl MachineFaults {
+    pub(crate) fn discovery_stalled(&self) -> bool {
+        self.0.read().unwrap().discovery_stalled
+    }
+
+    /// The error scout reports for machine validation, if validation should fail.
+    pub(crate) fn validation_failure(&self) -> Option<String> {
+        self.0.read().unwrap().validation_failure.clone()
+    }
+
+    pub(crate) fn scout_crashes_at(&self, step: ScoutStep) -> bool {
+        self.0.read().unwrap().scout_crash == Some(step)
+    }
+
+    fn set(&self, fault: &Fault, active: bool) {
+        let mut faults = self.0.write().unwrap();
+        match fault {
+            Fault::StallDiscovery => faults.discovery_stalled = active,
+            Fault::FailValidation { message } => {
+                faults.validation_failure = active.then(|| message.clone())
+            }
+            Fault::CrashScout { step } => faults.scout_crash = active.then_some(*step),
+            Fault::BmcUnreachable { .. } | Fault::PowerLoss => {}
+        }
+    }
+}
+
+/// Plays a scenario's steps against the running simulators.
+pub(crate) struct ScenarioRunner {
+    steps: Vec<(ScenarioStep, Vec<FaultTarget>)>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Transition {
+    Apply,
+    Lift,
+}
+
+impl ScenarioRunner {
+    /// Resolves every step's targets up front, so a step and its lift always
+    /// touch the same devices.
+    pub(crate) fn new(
+        scenario: &ScenarioConfig,
+        simulators: &SimulatorRegistry,
+        machines: &BTreeMap<String, Arc<MachineConfig>>,
+    ) -> Self {
+        let steps = scenario
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| {
+                let targets = select_targets(&step.target, simulators, machines);
+                if targets.is_empty() {
+                    tracing::warn!(
+                        step = index,
+                        selector = ?step.target,
+                        "scenario step matches no simulated devices"
+                    );
+                }
+                (step.clone(), targets)
+            })
+            .collect();
+        Self { steps }
+    }
+
+    pub(crate) fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        let started = Instant::now();
+        for (offset, index, transition) in timeline(self.steps.iter().map(|(step, _)| step)) {
+            tokio::time::sleep_until(started + offset).await;
+            let (step, targets) = &self.steps[index];
+            tracing::info!(
+                step = index,
+                ?transition,
+                fault = ?step.fault,
+                devices = targets.len(),
+                "scenario step"
+            );
+            for target in targets {
+                target.set_fault(index, &step.fault, transition == Transition::Apply);
+            }
+        }
+        tracing::info!("scenario complete");
+    }
+}
+
+/// When each step is applied and lifted, in the order they happen. Events at
+/// the same moment keep step order, so a later step can override an earlier one.
+fn timeline<'a>(
+    steps: impl Iterator<Item = &'a ScenarioStep>,
+) -> Vec<(Duration, usize, Transition)> {
+    let mut events = steps
+        .enumerate()
+        .flat_map(|(index, step)| {
+            std::iter::once((step.at, index, Transition::Apply)).chain(
+                step.lasts
+                    .map(|lasts| (step.at.saturating_add(lasts), index, Transition::Lift)),
+            )
+        })
+        .collect::<Vec<_>>();
+    events.sort_by_key(|(offset, _, _)| *offset);
+    events
+}
+
+fn select_targets(
+    selector: &DeviceSelector,
+    simulators: &SimulatorRegistry,
+    machines: &BTreeMap<String, Arc<MachineConfig>>,
+) -> Vec<FaultTarget> {
+    let handles = simulators
+        .devices()
+        .iter()
+        .map(SimulatorLifecycle::handle)
+        .filter(|handle| {
+            selector
+                .section
+                .as_deref()
+                .is_none_or(|section| handle.machine_config_section() == section)
+        })
+        .filter(|handle| {
+            selector.rack_id.as_ref().is_none_or(|rack_id| {
+                machines
+                    .get(handle.machine_config_section())
+                    .and_then(|machine| machine.rack_id.as_ref())
+                    == Some(rack_id)
+            })
+        });
+    let count = selector.count.unwrap_or(usize::MAX);
+    match selector.kind {
+        TargetKind::Host => handles
+            .filter(|handle| handle.kind() == DeviceKind::Machine)
+            .take(count)
+            .cloned()
+            .map(FaultTarget::Device)
+            .collect(),
+        TargetKind::Dpu => handles
+            .filter(|handle| handle.kind() == DeviceKind::Machine)
+            .flat_map(|handle| handle.dpus().iter().cloned())
+            .take(count)
+            .map(FaultTarget::Dpu)
+            .collect(),
+        TargetKind::Switch => handles
+            .filter(|handle| handle.kind() == DeviceKind::Switch)
+            .take(count)
+            .cloned()
+            .map(FaultTarget::Device)
+            .collect(),
+        TargetKind::PowerShelf => handles
+            .filter(|handle| handle.kind() == DeviceKind::PowerShelf)
+            .take(count)
+            .cloned()
+            .map(FaultTarget::Device)
+            .collect(),
+    }
+}
+
+enum FaultTarget {
+    Device(DeviceHandle),
+    Dpu(DpuMachineHandle),
+}
+
+impl FaultTarget {
+    fn mat_id(&self) -> Uuid {
+        match self {
+            Self::Device(handle) => handle.mat_id(),
+            Self::Dpu(handle) => handle.mat_id(),
+        }
+    }
+
+    fn machine_faults(&self) -> Option<Arc<MachineFaults>> {
+        match self {
+            Self::Device(handle) => handle.machine_faults(),
+            Self::Dpu(handle) => Some(handle.machine_faults()),
+        }
+    }
+
+    fn bmc_injection_store(&self) -> Arc<InjectionStore> {
+        match self {
+            Self::Device(handle) => handle.bmc_injection_store(),
+            Self::Dpu(handle) => handle.bmc_injection_store(),
+        }
+    }
+
+    fn set_system_power(&self, request: SystemPowerControl) -> eyre::Result<()> {
+        match self {
+            Self::Device(handle) => handle.set_system_power(request),
+            Self::Dpu(handle) => handle.set_system_power(request),
+        }
+    }
+
+    fn set_fault(&self, index: usize, fault: &Fault, active: bool) {
+        match fault {
+            Fault::StallDiscovery | Fault::FailValidation { .. } | Fault::CrashScout { .. } => {
+                if let Some(faults) = self.machine_faults() {
+                    faults.set(fault, active);
+                }
+            }
+            Fault::BmcUnreachable { status, glob } => {
+                let store = self.bmc_injection_store();
+                let id = RuleId::from(format!("scenario-step-{index}").as_str());
+                if active {
+                    store.upsert(Rule {
+                        id,
+                        selector: Selector::Path {
+                            method: None,
+                            glob: glob.clone(),
+                        },
+                        action: InjectionAction::Status(*status),
+                        remaining: None,
+                    });
+                } else {
+                    store.delete(&id);
+                }
+            }
+            Fault::PowerLoss => {
+                let request = if active {
+                    SystemPowerControl::ForceOff
+                } else {
+                    SystemPowerControl::On
+                };
+                _ = self.set_system_power(request).inspect_err(|error| {
+                    tracing::warn!(
+                        mat_id = %self.mat_id(),
+                        %error,
+                        "could not deliver scenario power change"
+                    )
+                });
+            }
+        }
+    }
+}
+
+fn default_validation_failure() -> String {
+    "machine validation failed (injected by machine-a-tron scenario)".to_string()
+}
+
+fn default_unreachable_status() -> u16 {
+    503
+}
+
+fn default_unreachable_glob() -> String {
+    "/**".to_string()
+}
+
+fn deserialize_option_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| duration_str::parse(&value).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+fn serialize_option_duration<S>(value: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(duration) => as_std_duration(duration, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> ScenarioConfig {
+        toml::from_str(source).expect("scenario parses")
+    }
+
+    #[test]
+    fn parses_steps() {
+        let scenario = parse(
+            r#"
+            [[steps]]
+            at = "2m"
+            for = "30s"
+            target = { kind = "host", section = "hosts", count = 3 }
+            fault = { type = "crash_scout", step = "validation" }
+
+            [[steps]]
+            at = "5m"
+            target = { kind = "power_shelf", rack_id = "rack-001" }
+            fault = { type = "power_loss" }
+
+            [[steps]]
+            at = "90s"
+            target = { kind = "dpu" }
+            fault = { type = "bmc_unreachable", glob = "/redfish/v1/UpdateService/**" }
+            "#,
+        );
+
+        assert_eq!(
+            scenario.steps,
+            vec![
+                ScenarioStep {
+                    at: Duration::from_secs(120),
+                    lasts: Some(Duration::from_secs(30)),
+                    target: DeviceSelector {
+                        kind: TargetKind::Host,
+                        section: Some("hosts".to_string()),
+                        rack_id: None,
+                        count: Some(3),
+                    },
+                    fault: Fault::CrashScout {
+                        step: ScoutStep::Validation,
+                    },
+                },
+                ScenarioStep {
+                    at: Duration::from_secs(300),
+                    lasts: None,
+                    target: DeviceSelector {
+                        kind: TargetKind::PowerShelf,
+                        section: None,
+                        rack_id: Some(RackId::new("rack-001")),
+                        count: None,
+                    },
+                    fault: Fault::PowerLoss,
+                },
+                ScenarioStep {
+                    at: Duration::from_secs(90),
+                    lasts: None,
+                    target: DeviceSelector {
+                        kind: TargetKind::Dpu,
+                        section: None,
+                        rack_id: None,
+                        count: None,
+                    },
+                    fault: Fault::BmcUnreachable {
+                        status: 503,
+                        glob: "/redfish/v1/UpdateService/**".to_string(),
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn timeline_orders_applies_and_lifts() {
+        let scenario = parse(
+            r#"
+            [[steps]]
+            at = "60s"
+            for = "60s"
+            target = { kind = "host" }
+            fault = { type = "stall_discovery" }
+
+            [[steps]]
+            at = "30s"
+            for = "90s"
+            target = { kind = "dpu" }
+            fault = { type = "stall_discovery" }
+
+            [[steps]]
+            at = "90s"
+            target = { kind = "switch" }
+            fault = { type = "power_loss" }
+            "#,
+        );
+
+        assert_eq!(
+            timeline(scenario.steps.iter()),
+            vec![
+                (Duration::from_secs(30), 1, Transition::Apply),
+                (Duration::from_secs(60), 0, Transition::Apply),
+                (Duration::from_secs(90), 2, Transition::Apply),
+                (Duration::from_secs(120), 0, Transition::Lift),
+                (Duration::from_secs(120), 1, Transition::Lift),
+            ]
+        );
+    }
+
+    #[test]
+    fn machine_faults_follow_apply_and_lift() {
+        let faults = MachineFaults::default();
+        let crash = Fault::CrashScout {
+            step: ScoutStep::Cleanup,
+        };
+        let fail = Fault::FailValidation {
+            message: "dimm 3 failed".to_string(),
+        };
+
+        faults.set(&Fault::StallDiscovery, true);
+        faults.set(&crash, true);
+        faults.set(&fail, true);
+        assert!(faults.discovery_stalled());
+        assert!(faults.scout_crashes_at(ScoutStep::Cleanup));
+        assert!(!faults.scout_crashes_at(ScoutStep::Discovery));
+        assert_eq!(
+            faults.validation_failure().as_deref(),
+            Some("dimm 3 failed")
+        );
+
+        faults.set(&Fault::StallDiscovery, false);
+        faults.set(&crash, false);
+        faults.set(&fail, false);
+        assert!(!faults.discovery_stalled());
+        assert!(!faults.scout_crashes_at(ScoutStep::Cleanup));
+        assert_eq!(faults.validation_failure(), None);
+    }
+}
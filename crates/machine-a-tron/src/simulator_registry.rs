@@ -17,18 +17,19 @@
 use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
+use bmc_mock::EventService;
 use bmc_mock::injection::InjectionStore;
 use carbide_uuid::machine::MachineId;
 use carbide_uuid::rack::RackId;
 use uuid::Uuid;
 
-use crate::DeviceHandle;
 use crate::device_simulator::{DeviceSimulator, SimulatorLifecycle};
 use crate::rack::{
     RackInstance, RackMemberRef, RackMemberStatus, RackRegistration, RackStatus,
     RacksStatusResponse,
 };
 use crate::status::DeviceStatusConfig;
+use crate::{DeviceHandle, DpuMachineHandle};
 
 #[derive(Debug, Clone)]
 pub struct SimulatorRegistry {
@@ -199,9 +200,32 @@ impl SimulatorRegistry {
     }
 
     pub fn find_injection_store(&self, id: &str) -> Option<Arc<InjectionStore>> {
+        self.find_bmc(
+            id,
+            DeviceHandle::bmc_injection_store,
+            DpuMachineHandle::bmc_injection_store,
+        )
+    }
+
+    /// Finds the Redfish event service of the BMC identified by `id`, which is either a
+    /// machine-a-tron device or DPU UUID or an observed machine ID.
+    pub fn find_event_service(&self, id: &str) -> Option<Arc<EventService>> {
+        self.find_bmc(
+            id,
+            DeviceHandle::bmc_event_service,
+            DpuMachineHandle::bmc_event_service,
+        )
+    }
+
+    fn find_bmc<T>(
+        &self,
+        id: &str,
+        device_bmc: impl Fn(&DeviceHandle) -> T,
+        dpu_bmc: impl Fn(&DpuMachineHandle) -> T,
+    ) -> Option<T> {
         if let Ok(mat_id) = Uuid::parse_str(id) {
             if let Some(device) = self.get(mat_id) {
-                return Some(device.bmc_injection_store());
+                return Some(device_bmc(device.handle()));
             }
             for device in self.devices() {
                 let Some(machine) = device.machine() else {
@@ -213,7 +237,7 @@ impl SimulatorRegistry {
                     .iter()
                     .find(|dpu| dpu.mat_id() == mat_id)
                 {
-                    return Some(dpu.bmc_injection_store());
+                    return Some(dpu_bmc(dpu));
                 }
             }
         }
@@ -224,7 +248,7 @@ impl SimulatorRegistry {
                 continue;
             };
             if machine.handle().observed_machine_id().as_ref() == Some(&machine_id) {
-                return Some(machine.bmc_injection_store());
+                return Some(device_bmc(machine.handle()));
             }
             if let Some(dpu) = machine
                 .handle()
@@ -232,7 +256,7 @@ impl SimulatorRegistry {
                 .iter()
                 .find(|dpu| dpu.observed_machine_id().as_ref() == Some(&machine_id))
             {
-                return Some(dpu.bmc_injection_store());
+                return Some(dpu_bmc(dpu));
             }
         }
         None
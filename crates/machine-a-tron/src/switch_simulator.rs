@@ -24,8 +24,9 @@ use bmc_mock::injection::InjectionStore;
 use bmc_mock::ipmi_sim::IpmiEndpoint;
 use bmc_mock::mac_address_pool::{MacAddressPool, PoolConfig as MacAddressPoolConfig};
 use bmc_mock::{
-    BmcCommand, Callbacks, HostMachineInfo, HostnameQuerying, MachineInfo, MockPowerState,
-    POWER_CYCLE_DELAY, SetSystemPowerError, SetSystemPowerResult, SystemPowerControl,
+    BmcCommand, Callbacks, EventService, HostMachineInfo, HostnameQuerying, MachineInfo,
+    MockPowerState, POWER_CYCLE_DELAY, SetSystemPowerError, SetSystemPowerResult,
+    SystemPowerControl,
 };
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
@@ -111,6 +112,7 @@ pub(crate) struct SwitchActor {
     config: Arc<MachineConfig>,
     live_state: Arc<RwLock<SwitchLiveState>>,
     bmc_injection: Arc<InjectionStore>,
+    bmc_events: Arc<EventService>,
     _bmc_mock: Option<Arc<BmcMockWrapperHandle>>,
     bmc_dhcp_info: Option<DhcpResponseInfo>,
     fsm: SwitchFsm,
@@ -140,6 +142,7 @@ impl SwitchActor {
             config,
             live_state: Arc::new(RwLock::new(SwitchLiveState::new(&fsm))),
             bmc_injection: Arc::new(InjectionStore::new()),
+            bmc_events: Arc::new(EventService::new()),
             _bmc_mock: None,
             bmc_dhcp_info: None,
             fsm,
@@ -179,6 +182,7 @@ impl SwitchActor {
             config,
             live_state: Arc::new(RwLock::new(SwitchLiveState::new(&fsm))),
             bmc_injection: Arc::new(InjectionStore::new()),
+            bmc_events: Arc::new(EventService::new()),
             _bmc_mock: None,
             bmc_dhcp_info: None,
             fsm,
@@ -195,6 +199,7 @@ impl SwitchActor {
         let host_info = self.host_info.clone();
         let machine_config_section = self.machine_config_section.clone();
         let bmc_injection = self.bmc_injection.clone();
+        let bmc_events = self.bmc_events.clone();
         let (actor, mailbox) = Actor::new(self, SwitchMessage::Run);
 
         let join_handle = tokio::task::Builder::new()
@@ -210,6 +215,7 @@ impl SwitchActor {
             host_info,
             machine_config_section,
             bmc_injection,
+            bmc_events,
         }))
     }
 
@@ -368,6 +374,7 @@ impl SwitchActor {
             Arc::new(SwitchHostname),
             self.mat_id,
             self.bmc_injection.clone(),
+            self.bmc_events.clone(),
         );
         if let Some(password) = self.app_context.app_config.host_bmc_password.as_deref() {
             bmc_mock
@@ -501,6 +508,7 @@ struct SwitchActorHandle {
     host_info: HostMachineInfo,
     machine_config_section: String,
     bmc_injection: Arc<InjectionStore>,
+    bmc_events: Arc<EventService>,
 }
 
 #[derive(Debug, Clone)]
@@ -528,6 +536,17 @@ impl SwitchHandle {
         Ok(())
     }
 
+    /// Changes the device's power as if its BMC received a Redfish reset.
+    pub(crate) fn set_system_power(&self, request: SystemPowerControl) -> eyre::Result<()> {
+        self.0
+            .mailbox
+            .send(SwitchMessage::Bmc(BmcCommand::SetSystemPower {
+                request,
+                reply: None,
+            }))?;
+        Ok(())
+    }
+
     pub(crate) fn host_info(&self) -> &HostMachineInfo {
         &self.0.host_info
     }
@@ -585,6 +604,10 @@ impl SwitchHandle {
         self.0.bmc_injection.clone()
     }
 
+    pub(crate) fn bmc_event_service(&self) -> Arc<EventService> {
+        self.0.bmc_events.clone()
+    }
+
     pub(crate) fn abort(&self) {
         if let Some(join_handle) = self.0.join_handle.lock().unwrap().take() {
             join_handle.abort();
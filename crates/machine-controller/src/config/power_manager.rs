@@ -15,6 +15,8 @@
  * limitations under the License.
  */
 
+use std::collections::HashMap;
+
 use carbide_utils::config::as_duration;
 use chrono::Duration;
 use duration_str::deserialize_duration_chrono;
@@ -56,6 +58,72 @@ pub struct PowerManagerOptions {
         serialize_with = "as_duration"
     )]
     pub wait_duration_until_host_reboot: chrono::TimeDelta,
+    /// Powers off Ready hosts that stay unallocated, keeping a warm pool
+    /// of powered-on hosts per instance type.
+    #[serde(default)]
+    pub idle_host_policy: IdleHostPowerPolicy,
+}
+
+/// Idle-host power policy. Ready hosts without an instance that have been
+/// idle for longer than their instance type's `idle_timeout` are powered
+/// off ("parked"), except for `warm_pool_size` hosts that stay on. Parked
+/// hosts are not allocatable; they are powered back on whenever the warm
+/// pool drops below its size or allocations fail for want of them, and
+/// re-validated before returning to Ready.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IdleHostPowerPolicy {
+    /// Enables the policy. Requires `power_manager_options.enabled`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Interval between two passes over the idle hosts.
+    /// Default is 1 minute.
+    #[serde(
+        default = "default_idle_host_run_interval",
+        deserialize_with = "deserialize_duration_chrono",
+        serialize_with = "as_duration"
+    )]
+    pub run_interval: chrono::TimeDelta,
+    /// Run on-demand machine validation on hosts that are powered back
+    /// on, before they become allocatable again.
+    /// Default is true.
+    #[serde(default = "default_revalidate_on_wake")]
+    pub revalidate_on_wake: bool,
+    /// Policy per instance type ID. Hosts of instance types not listed
+    /// here are never powered off.
+    #[serde(default)]
+    pub instance_types: HashMap<String, InstanceTypeIdlePolicy>,
+}
+
+impl Default for IdleHostPowerPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            run_interval: default_idle_host_run_interval(),
+            revalidate_on_wake: default_revalidate_on_wake(),
+            instance_types: HashMap::new(),
+        }
+    }
+}
+
+/// Idle-host power policy of a single instance type.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InstanceTypeIdlePolicy {
+    /// Time a host must have been Ready without an instance before it may
+    /// be powered off.
+    #[serde(
+        deserialize_with = "deserialize_duration_chrono",
+        serialize_with = "as_duration"
+    )]
+    pub idle_timeout: chrono::TimeDelta,
+    /// Number of allocatable hosts that are kept powered on.
+    #[serde(default)]
+    pub warm_pool_size: u32,
+    /// Estimated power draw of an idle, powered-on host of this type. Only
+    /// used to report the power saved by parked hosts.
+    #[serde(default)]
+    pub idle_watts: u32,
 }
 
 pub fn default_power_options() -> PowerManagerOptions {
@@ -64,6 +132,7 @@ pub fn default_power_options() -> PowerManagerOptions {
         next_try_duration_on_success: default_next_duration_success(),
         next_try_duration_on_failure: default_next_duration_failure(),
         wait_duration_until_host_reboot: default_wait_duration_next_reboot(),
+        idle_host_policy: IdleHostPowerPolicy::default(),
     }
 }
 
@@ -79,6 +148,14 @@ pub fn default_wait_duration_next_reboot() -> Duration {
     Duration::minutes(15)
 }
 
+pub fn default_idle_host_run_interval() -> Duration {
+    Duration::minutes(1)
+}
+
+fn default_revalidate_on_wake() -> bool {
+    true
+}
+
 #[cfg(test)]
 mod test {
     use figment::Figment;
@@ -133,4 +210,36 @@ next_try_duration_on_success = "3m"
             power_config.wait_duration_until_host_reboot
         );
     }
+
+    #[test]
+    fn test_idle_host_policy() {
+        let toml = r#"
+enabled = true
+
+[idle_host_policy]
+enabled = true
+
+[idle_host_policy.instance_types.gpu-large]
+idle_timeout = "2h"
+warm_pool_size = 4
+idle_watts = 900
+"#;
+
+        let power_config: PowerManagerOptions =
+            Figment::new().merge(Toml::string(toml)).extract().unwrap();
+
+        let policy = power_config.idle_host_policy;
+        assert!(policy.enabled);
+        assert!(policy.revalidate_on_wake);
+        assert_eq!(Duration::minutes(1), policy.run_interval);
+        let gpu_large = &policy.instance_types["gpu-large"];
+        assert_eq!(Duration::hours(2), gpu_large.idle_timeout);
+        assert_eq!(4, gpu_large.warm_pool_size);
+        assert_eq!(900, gpu_large.idle_watts);
+
+        let power_config: PowerManagerOptions =
+            Figment::new().merge(Toml::string("")).extract().unwrap();
+        assert!(!power_config.idle_host_policy.enabled);
+        assert!(power_config.idle_host_policy.instance_types.is_empty());
+    }
 }
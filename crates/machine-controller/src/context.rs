@@ -27,6 +27,7 @@ use db::db_read::PgPoolReader;
 use libredfish::Redfish;
 use model::machine::Machine;
 use sqlx::PgPool;
+use state_controller::explain::SideEffectRecorder;
 use state_controller::state_handler::{StateHandlerContextObjects, StateHandlerError};
 
 use crate::config::MachineStateHandlerSiteConfig;
@@ -73,6 +74,10 @@ pub struct MachineStateHandlerServices {
     /// Trait/association info gauges for the per-object metrics endpoint,
     /// present when per-object state metrics are enabled for machines.
     pub per_object_info: Option<MachinePerObjectInfo>,
+    /// Set while the handler explains its next step instead of taking it.
+    /// Transactions the handler commits on its own are rolled back and
+    /// recorded here instead. See [`crate::dry_run`].
+    pub dry_run: Option<SideEffectRecorder>,
 }
 
 impl MachineStateHandlerServices {
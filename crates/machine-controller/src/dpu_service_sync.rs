@@ -31,8 +31,10 @@ use model::machine::Machine;
 use model::machine_pending_action::MachinePendingActionActor;
 use model::machine_pending_action::MachinePendingActionKind::DpuServiceSync;
 use sqlx::PgPool;
+use state_controller::explain::SideEffectRecorder;
 
 use crate::dpf::DpfOperations;
+use crate::dry_run::commit_unless_dry_run;
 
 /// What to do when the host turns out to be assigned.
 ///
@@ -85,6 +87,10 @@ pub enum ReleaseOutcome {
 /// the `Ready` arm, while the admin API calls it regardless of state, which is
 /// what lets an operator rescue a host that can never reach `Ready` on its own.
 ///
+///
+/// In a `dry_run`, completing the pending action is rolled back and recorded
+/// instead; the caller passes a recording `dpf_sdk` for the release itself.
+///
 /// [`ManagedHostState`]: model::machine::ManagedHostState
 pub async fn release_hold_if_dpus_are_current(
     dpf_sdk: &dyn DpfOperations,
@@ -93,6 +99,7 @@ pub async fn release_hold_if_dpus_are_current(
     dpus: &[Machine],
     tenant_policy: TenantPolicy,
     actor: MachinePendingActionActor,
+    dry_run: Option<&SideEffectRecorder>,
 ) -> ReleaseOutcome {
     let Some(node_id) = host.dpf_id() else {
         return ReleaseOutcome::Failed {
@@ -182,18 +189,25 @@ pub async fn release_hold_if_dpus_are_current(
     // Failing to record the completion is the safe direction: the action stays
     // outstanding and a later pass releases an already-released hold, which is a
     // no-op.
-    let mut conn = match db_pool.acquire().await {
-        Ok(conn) => conn,
+    let mut txn = match db_pool.begin().await {
+        Ok(txn) => txn,
         Err(error) => {
             return ReleaseOutcome::Failed {
                 reason: format!(
-                    "released the hold but could not acquire a database connection to record it: {error}"
+                    "released the hold but could not start a transaction to record it: {error}"
                 ),
             };
         }
     };
     if let Err(error) =
-        db::machine_pending_action::complete(&mut conn, &host.id, DpuServiceSync, actor).await
+        db::machine_pending_action::complete(&mut txn, &host.id, DpuServiceSync, actor).await
+    {
+        return ReleaseOutcome::Failed {
+            reason: format!("released the hold but could not record it as completed: {error}"),
+        };
+    }
+    if let Err(error) =
+        commit_unless_dry_run(txn, dry_run, "complete pending DPU service sync", &host.id).await
     {
         return ReleaseOutcome::Failed {
             reason: format!("released the hold but could not record it as completed: {error}"),
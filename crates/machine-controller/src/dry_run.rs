@@ -0,0 +1,484 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Recording stand-ins for the machine state handler's external services,
+//! used to explain what the handler would do next without doing it.
+//!
+//! Each stand-in wraps the real client so that reads still return live data,
+//! and records writes into a [`SideEffectRecorder`] instead of sending them.
+//! [`MachineStateHandlerServices::dry_run`] and [`MachineStateHandler::dry_run`]
+//! swap them in, and [`MachineStateExplainer`] runs the handler with the
+//! result.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use carbide_credential_rotation::site_explorer_pause::{self, GateDecision};
+use carbide_dpf::types::{DpuServiceVersion, HostDpfSnapshot, ServiceTemplateVersion};
+use carbide_dpf::{DpfError, DpuDeploymentType, DpuDeviceInfo, DpuNodeInfo, DpuPhase};
+use carbide_ipmi::IPMITool;
+use carbide_redfish::libredfish::dry_run::DryRunRedfishClientPool;
+use carbide_secrets::SecretsError;
+use carbide_secrets::credentials::{
+    CompositeCredentialManager, CredentialKey, CredentialManager, CredentialWriter, Credentials,
+};
+use carbide_uuid::machine::MachineId;
+use component_manager::compute_tray_manager::{
+    Backend, ComputeTrayEndpoint, ComputeTrayFirmwareUpdateStatus, ComputeTrayManager,
+    ComputeTrayResult,
+};
+use component_manager::error::ComponentManagerError;
+use component_manager::types::FirmwareUpdateOptions;
+use mac_address::MacAddress;
+use model::component_manager::{ComputeTrayComponent, PowerAction};
+use model::dpu_machine_update::OutdatedDpfDpu;
+use model::machine::{Machine, ManagedHostState};
+use sqlx::PgTransaction;
+use state_controller::explain::{
+    DATABASE_SERVICE, Explanation, SideEffect, SideEffectRecorder, explain_object,
+};
+use state_controller::state_handler::StateHandlerError;
+
+use crate::context::MachineStateHandlerServices;
+use crate::dpf::DpfOperations;
+use crate::handler::MachineStateHandler;
+use crate::io::MachineStateControllerIO;
+
+const IPMI_SERVICE: &str = "ipmi";
+const CREDENTIALS_SERVICE: &str = "credentials";
+const COMPONENT_MANAGER_SERVICE: &str = "component_manager";
+const DPF_SERVICE: &str = "dpf";
+
+impl MachineStateHandlerServices {
+    /// Returns a copy of these services whose external clients record into
+    /// `recorder` instead of changing anything.
+    ///
+    /// The database pool is shared, since the explaining caller rolls back
+    /// the transaction the handler returns. Transactions the handler commits
+    /// on its own go through [`commit_unless_dry_run`].
+    pub fn dry_run(&self, recorder: &SideEffectRecorder) -> Self {
+        let component_manager = self.component_manager.as_ref().map(|component_manager| {
+            Arc::new(component_manager::component_manager::ComponentManager {
+                compute_tray: Arc::new(DryRunComputeTray {
+                    inner: component_manager.compute_tray.clone(),
+                    recorder: recorder.clone(),
+                }),
+                ..component_manager.as_ref().clone()
+            })
+        });
+        let credential_manager: Arc<dyn CredentialManager> =
+            Arc::new(CompositeCredentialManager::new(
+                self.credential_manager.clone(),
+                DryRunCredentialWriter {
+                    inner: self.credential_manager.clone(),
+                    recorder: recorder.clone(),
+                },
+            ));
+
+        Self {
+            redfish_client_pool: Arc::new(DryRunRedfishClientPool::new(
+                self.redfish_client_pool.clone(),
+                recorder.clone(),
+            )),
+            ipmi_tool: Arc::new(DryRunIpmiTool {
+                recorder: recorder.clone(),
+            }),
+            component_manager,
+            credential_manager,
+            dry_run: Some(recorder.clone()),
+            ..self.clone()
+        }
+    }
+}
+
+/// Wraps a DPF client so that it records into `recorder` instead of changing
+/// anything. Used by [`MachineStateHandler::dry_run`].
+pub(crate) fn dpf(
+    inner: Arc<dyn DpfOperations>,
+    recorder: &SideEffectRecorder,
+) -> Arc<dyn DpfOperations> {
+    Arc::new(DryRunDpf {
+        inner,
+        recorder: recorder.clone(),
+    })
+}
+
+/// Commits a transaction that the state handler commits on its own instead
+/// of returning it with the outcome. In a dry run it is rolled back and
+/// `operation` is recorded instead.
+pub(crate) async fn commit_unless_dry_run(
+    txn: PgTransaction<'_>,
+    dry_run: Option<&SideEffectRecorder>,
+    operation: &str,
+    machine_id: &MachineId,
+) -> Result<(), StateHandlerError> {
+    match dry_run {
+        Some(recorder) => {
+            txn.rollback().await?;
+            recorder.record(SideEffect::new(
+                DATABASE_SERVICE,
+                operation,
+                machine_id.to_string(),
+            ));
+        }
+        None => txn.commit().await?,
+    }
+    Ok(())
+}
+
+/// Suppresses site-explorer for `macs` and reports whether credentials may be
+/// changed, as [`site_explorer_pause::gate_before_credential_change`] does. In
+/// a dry run the suppressions are recorded instead of created, and the
+/// decision is made from the suppressions that already exist.
+pub(crate) async fn gate_before_credential_change(
+    services: &MachineStateHandlerServices,
+    macs: &[MacAddress],
+    reason: &str,
+) -> Result<GateDecision, StateHandlerError> {
+    let Some(recorder) = services.dry_run.as_ref() else {
+        return Ok(site_explorer_pause::gate_before_credential_change(
+            &services.db_pool,
+            macs,
+            reason,
+        )
+        .await?);
+    };
+    for mac in macs {
+        recorder.record(SideEffect::new(
+            DATABASE_SERVICE,
+            "suppress site-explorer",
+            mac.to_string(),
+        ));
+    }
+    Ok(site_explorer_pause::peek_gate(&services.db_pool, macs, reason).await?)
+}
+
+/// Explains what the machine state handler would do next for one machine,
+/// using the handler, services and IO the machine state controller runs with.
+pub struct MachineStateExplainer {
+    handler: Arc<MachineStateHandler>,
+    services: Arc<MachineStateHandlerServices>,
+    io: Arc<MachineStateControllerIO>,
+}
+
+impl MachineStateExplainer {
+    pub fn new(
+        handler: Arc<MachineStateHandler>,
+        services: Arc<MachineStateHandlerServices>,
+        io: Arc<MachineStateControllerIO>,
+    ) -> Self {
+        Self {
+            handler,
+            services,
+            io,
+        }
+    }
+
+    /// Runs the state handler once on the current state of `machine_id`
+    /// with all of its external services recording instead of acting.
+    pub async fn explain(
+        &self,
+        machine_id: &MachineId,
+    ) -> Result<Explanation<ManagedHostState>, StateHandlerError> {
+        let recorder = SideEffectRecorder::new();
+        let handler = self.handler.dry_run(&recorder);
+        let mut services = self.services.dry_run(&recorder);
+        let db_pool = services.db_pool.clone();
+        explain_object(
+            &db_pool,
+            self.io.as_ref(),
+            &handler,
+            machine_id,
+            &mut services,
+            &recorder,
+        )
+        .await
+    }
+}
+
+struct DryRunIpmiTool {
+    recorder: SideEffectRecorder,
+}
+
+#[async_trait]
+impl IPMITool for DryRunIpmiTool {
+    async fn bmc_cold_reset(
+        &self,
+        bmc_address: SocketAddr,
+        _credential_key: &CredentialKey,
+    ) -> Result<(), eyre::Report> {
+        self.recorder.record(SideEffect::new(
+            IPMI_SERVICE,
+            "bmc_cold_reset",
+            bmc_address.to_string(),
+        ));
+        Ok(())
+    }
+
+    async fn restart(
+        &self,
+        _machine_id: &MachineId,
+        bmc_address: SocketAddr,
+        legacy_boot: bool,
+        _credential_key: &CredentialKey,
+    ) -> Result<(), eyre::Report> {
+        let effect = SideEffect::new(IPMI_SERVICE, "restart", bmc_address.to_string());
+        self.recorder.record(if legacy_boot {
+            effect.with_details("legacy boot")
+        } else {
+            effect
+        });
+        Ok(())
+    }
+}
+
+/// Records credential writes by key; the credentials themselves are never
+/// recorded.
+struct DryRunCredentialWriter {
+    inner: Arc<dyn CredentialManager>,
+    recorder: SideEffectRecorder,
+}
+
+impl DryRunCredentialWriter {
+    fn record(&self, operation: &str, key: &CredentialKey) {
+        self.recorder.record(SideEffect::new(
+            CREDENTIALS_SERVICE,
+            operation,
+            key.to_key_str(),
+        ));
+    }
+}
+
+#[async_trait]
+impl CredentialWriter for DryRunCredentialWriter {
+    async fn get_credentials_from_writer(
+        &self,
+        key: &CredentialKey,
+    ) -> Result<Option<Credentials>, SecretsError> {
+        self.inner.get_credentials_from_writer(key).await
+    }
+
+    async fn set_credentials(
+        &self,
+        key: &CredentialKey,
+        _credentials: &Credentials,
+    ) -> Result<(), SecretsError> {
+        self.record("set_credentials", key);
+        Ok(())
+    }
+
+    async fn create_credentials(
+        &self,
+        key: &CredentialKey,
+        _credentials: &Credentials,
+    ) -> Result<(), SecretsError> {
+        self.record("create_credentials", key);
+        Ok(())
+    }
+
+    async fn delete_credentials(&self, key: &CredentialKey) -> Result<(), SecretsError> {
+        self.record("delete_credentials", key);
+        Ok(())
+    }
+}
+
+/// Records compute tray power and firmware operations, and reports them as
+/// successful for every endpoint.
+#[derive(Debug)]
+struct DryRunComputeTray {
+    inner: Arc<dyn ComputeTrayManager>,
+    recorder: SideEffectRecorder,
+}
+
+impl DryRunComputeTray {
+    fn record(
+        &self,
+        operation: &str,
+        endpoints: &[ComputeTrayEndpoint],
+        details: String,
+    ) -> Vec<ComputeTrayResult> {
+        endpoints
+            .iter()
+            .map(|endpoint| {
+                self.recorder.record(
+                    SideEffect::new(
+                        COMPONENT_MANAGER_SERVICE,
+                        operation,
+                        endpoint.bmc_ip.to_string(),
+                    )
+                    .with_details(details.clone()),
+                );
+                ComputeTrayResult {
+                    bmc_ip: endpoint.bmc_ip,
+                    success: true,
+                    error: None,
+                }
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ComputeTrayManager for DryRunComputeTray {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn backend(&self) -> Backend {
+        self.inner.backend()
+    }
+
+    async fn power_control(
+        &self,
+        endpoints: &[ComputeTrayEndpoint],
+        action: PowerAction,
+    ) -> Result<Vec<ComputeTrayResult>, ComponentManagerError> {
+        Ok(self.record("power_control", endpoints, format!("{action:?}")))
+    }
+
+    async fn update_firmware(
+        &self,
+        endpoints: &[ComputeTrayEndpoint],
+        target_version: &str,
+        _components: &[ComputeTrayComponent],
+        _options: &FirmwareUpdateOptions,
+    ) -> Result<Vec<ComputeTrayResult>, ComponentManagerError> {
+        Ok(self.record("update_firmware", endpoints, target_version.to_string()))
+    }
+
+    async fn get_firmware_status(
+        &self,
+        endpoints: &[ComputeTrayEndpoint],
+    ) -> Result<Vec<ComputeTrayFirmwareUpdateStatus>, ComponentManagerError> {
+        self.inner.get_firmware_status(endpoints).await
+    }
+
+    async fn list_firmware_bundles(&self) -> Result<Vec<String>, ComponentManagerError> {
+        self.inner.list_firmware_bundles().await
+    }
+}
+
+/// Records DPF custom resource changes; lookups go to the real DPF SDK.
+#[derive(Debug)]
+struct DryRunDpf {
+    inner: Arc<dyn DpfOperations>,
+    recorder: SideEffectRecorder,
+}
+
+impl DryRunDpf {
+    fn record(&self, operation: &str, target: impl Into<String>) {
+        self.recorder
+            .record(SideEffect::new(DPF_SERVICE, operation, target));
+    }
+}
+
+#[async_trait]
+impl DpfOperations for DryRunDpf {
+    async fn register_dpu_device(&self, info: DpuDeviceInfo) -> Result<(), DpfError> {
+        self.record("register_dpu_device", info.device_id);
+        Ok(())
+    }
+
+    async fn register_dpu_node(&self, info: DpuNodeInfo) -> Result<(), DpfError> {
+        self.record("register_dpu_node", info.node_id);
+        Ok(())
+    }
+
+    async fn release_maintenance_hold(&self, node_name: &str) -> Result<(), DpfError> {
+        self.record("release_maintenance_hold", node_name);
+        Ok(())
+    }
+
+    async fn reprovision_dpu(
+        &self,
+        dpu_device_name: &str,
+        node_name: &str,
+    ) -> Result<(), DpfError> {
+        self.record("reprovision_dpu", format!("{node_name}/{dpu_device_name}"));
+        Ok(())
+    }
+
+    async fn force_delete_host(
+        &self,
+        node_id: &str,
+        _dpu_device_names: &[String],
+    ) -> Result<(), DpfError> {
+        self.record("force_delete_host", node_id);
+        Ok(())
+    }
+
+    async fn get_dpu_phase(
+        &self,
+        dpu_device_name: &str,
+        node_name: &str,
+    ) -> Result<DpuPhase, DpfError> {
+        self.inner.get_dpu_phase(dpu_device_name, node_name).await
+    }
+
+    async fn is_reboot_required(&self, node_name: &str) -> Result<bool, DpfError> {
+        self.inner.is_reboot_required(node_name).await
+    }
+
+    async fn reboot_complete(&self, node_name: &str) -> Result<(), DpfError> {
+        self.record("reboot_complete", node_name);
+        Ok(())
+    }
+
+    fn deployment_type_for_dpu(
+        &self,
+        dpu: &Machine,
+        astra_nics: bool,
+    ) -> Result<DpuDeploymentType, DpfError> {
+        self.inner.deployment_type_for_dpu(dpu, astra_nics)
+    }
+
+    async fn verify_node_labels(
+        &self,
+        node_name: &str,
+        deployment_type: DpuDeploymentType,
+    ) -> Result<bool, DpfError> {
+        self.inner
+            .verify_node_labels(node_name, deployment_type)
+            .await
+    }
+
+    async fn snapshot_host(&self, node_name: &str) -> Result<HostDpfSnapshot, DpfError> {
+        self.inner.snapshot_host(node_name).await
+    }
+
+    async fn list_service_template_versions(
+        &self,
+    ) -> Result<Vec<ServiceTemplateVersion>, DpfError> {
+        self.inner.list_service_template_versions().await
+    }
+
+    async fn get_service_versions_for_dpu(
+        &self,
+        dpu_name: &str,
+    ) -> Result<Vec<DpuServiceVersion>, DpfError> {
+        self.inner.get_service_versions_for_dpu(dpu_name).await
+    }
+
+    async fn find_outdated_dpus_dpf(&self) -> Result<Vec<OutdatedDpfDpu>, DpfError> {
+        self.inner.find_outdated_dpus_dpf().await
+    }
+
+    async fn is_dpu_outdated(&self, dpu_name: &str) -> Result<bool, DpfError> {
+        self.inner.is_dpu_outdated(dpu_name).await
+    }
+}
@@ -94,6 +94,7 @@ use model::resource_pool::common::CommonPools;
 use model::site_explorer::ExploredEndpoint;
 use sku::{handle_bom_validation_requested, handle_bom_validation_state};
 use sqlx::PgConnection;
+use state_controller::explain::SideEffectRecorder;
 use state_controller::state_handler::{
     StateHandler, StateHandlerContext, StateHandlerError, StateHandlerOutcome,
 };
@@ -109,6 +110,7 @@ use crate::config::{
 };
 use crate::context::{MachineStateHandlerContextObjects, MachineStateHandlerServices};
 use crate::dpf::DpfOperations;
+use crate::dry_run;
 use crate::handler::firmware_artifact::ResolvedFirmwareArtifactSource;
 use crate::health_report::{
     create_host_update_health_report_dpufw, create_host_update_health_report_hostfw,
@@ -468,6 +470,20 @@ impl MachineStateHandlerBuilder {
 }
 
 impl MachineStateHandler {
+    /// Returns a copy of this handler whose DPF client records into
+    /// `recorder` instead of changing anything. See [`crate::dry_run`].
+    pub fn dry_run(&self, recorder: &SideEffectRecorder) -> Self {
+        let dpf_sdk = self
+            .dpu_handler
+            .dpf_sdk
+            .clone()
+            .map(|dpf_sdk| dry_run::dpf(dpf_sdk, recorder));
+        let mut handler = self.clone();
+        handler.dpu_handler.dpf_sdk = dpf_sdk.clone();
+        handler.instance_handler.dpf_sdk = dpf_sdk;
+        handler
+    }
+
     fn new(builder: MachineStateHandlerBuilder) -> Self {
         let host_upgrade = Arc::new(HostUpgradeState {
             parsed_hosts: Arc::new(builder.hardware_models.clone().unwrap_or_default()),
@@ -1166,8 +1182,8 @@ impl MachineStateHandler {
                     .map(|e| e.device_mac)
                     .collect();
                 if matches!(
-                    site_explorer_pause::gate_before_credential_change(
-                        &ctx.services.db_pool,
+                    dry_run::gate_before_credential_change(
+                        ctx.services,
                         &bmc_macs,
                         site_explorer_pause::ROTATION_SUPPRESSION_REASON,
                     )
@@ -1379,7 +1395,7 @@ impl MachineStateHandler {
                                 ctx,
                             )
                             .await?;
-                            return Ok(StateHandlerOutcome::wait(status.status));
+                            return Ok(status.wait());
                         }
 
                         // Reboot host
@@ -2705,7 +2721,13 @@ impl StateHandler for MachineStateHandler {
         if let Some(power_options) = power_options {
             let mut txn = power_options_pool.begin().await?;
             db::power_options::persist(&power_options, &mut txn).await?;
-            txn.commit().await?;
+            dry_run::commit_unless_dry_run(
+                txn,
+                ctx.services.dry_run.as_ref(),
+                "persist power options",
+                host_machine_id,
+            )
+            .await?;
         }
 
         result
@@ -3203,7 +3225,13 @@ async fn handle_dpu_reprovision(
                 .unwrap_or(state.host_snapshot.state.version.timestamp());
 
             if wait(&basetime, reachability_params.power_down_wait) {
-                return Ok(StateHandlerOutcome::do_nothing());
+                return Ok(StateHandlerOutcome::wait_until(
+                    format!(
+                        "Waiting for power_down_wait ({}) to elapse before checking that host {} is powered off",
+                        reachability_params.power_down_wait, state.host_snapshot.id
+                    ),
+                    basetime + reachability_params.power_down_wait,
+                ));
             }
 
             let redfish_client = ctx
@@ -3452,13 +3480,17 @@ async fn handle_dpu_reprovision(
                 UnlockHostState::WaitForUefiBoot => {
                     let entered_at = state.host_snapshot.state.version.timestamp();
                     if wait(&entered_at, reachability_params.uefi_boot_wait) {
-                        return Ok(StateHandlerOutcome::wait(format!(
-                            "Waiting for UEFI boot to complete on {} after post-unlock reboot; \
-                             wait duration: {}, will proceed after {}",
-                            state.host_snapshot.id,
-                            reachability_params.uefi_boot_wait,
-                            entered_at + reachability_params.uefi_boot_wait,
-                        )));
+                        let proceed_at = entered_at + reachability_params.uefi_boot_wait;
+                        return Ok(StateHandlerOutcome::wait_until(
+                            format!(
+                                "Waiting for UEFI boot to complete on {} after post-unlock reboot; \
+                                 wait duration: {}, will proceed after {}",
+                                state.host_snapshot.id,
+                                reachability_params.uefi_boot_wait,
+                                proceed_at,
+                            ),
+                            proceed_at,
+                        ));
                     }
 
                     ReprovisionState::CheckHostBootConfigAfterHostReboot
@@ -4490,10 +4522,13 @@ impl DpuMachineStateHandler {
                     .unwrap_or(state.host_snapshot.state.version.timestamp());
 
                 if wait(&basetime, self.reachability_params.power_down_wait) {
-                    return Ok(StateHandlerOutcome::wait(format!(
-                        "Waiting for power_down_wait ({}m) to elapse before powering on host",
-                        self.reachability_params.power_down_wait.num_minutes(),
-                    )));
+                    return Ok(StateHandlerOutcome::wait_until(
+                        format!(
+                            "Waiting for power_down_wait ({}m) to elapse before powering on host",
+                            self.reachability_params.power_down_wait.num_minutes(),
+                        ),
+                        basetime + self.reachability_params.power_down_wait,
+                    ));
                 }
 
                 handler_host_power_control(state, ctx, SystemPowerControl::On).await?;
@@ -5215,8 +5250,21 @@ fn get_reboot_cycle(
 
 #[derive(Debug)]
 pub struct RebootStatus {
-    increase_retry_count: bool, // the vague previous return value
-    status: String,             // what we did or are waiting for
+    increase_retry_count: bool,        // the vague previous return value
+    status: String,                    // what we did or are waiting for
+    recheck_at: Option<DateTime<Utc>>, // when a timer we are waiting for expires
+}
+
+impl RebootStatus {
+    /// Waits for the reboot to make progress. If only a timer is pending,
+    /// the host is not handled again before it expires.
+    #[track_caller]
+    fn wait<S>(self) -> StateHandlerOutcome<S> {
+        match self.recheck_at {
+            Some(recheck_at) => StateHandlerOutcome::wait_until(self.status, recheck_at),
+            None => StateHandlerOutcome::wait(self.status),
+        }
+    }
 }
 
 /// Outcome of set_host_boot_order function.
@@ -5386,12 +5434,11 @@ pub async fn trigger_reboot_if_needed_with_location(
             &last_reboot_requested.time,
             reachability_params.power_down_wait,
         ) {
+            let recheck_at = last_reboot_requested.time + reachability_params.power_down_wait;
             return Ok(RebootStatus {
                 increase_retry_count: false,
-                status: format!(
-                    "Waiting for host to power off. Next check at {}",
-                    last_reboot_requested.time + reachability_params.power_down_wait
-                ),
+                status: format!("Waiting for host to power off. Next check at {recheck_at}"),
+                recheck_at: Some(recheck_at),
             });
         }
 
@@ -5424,6 +5471,7 @@ pub async fn trigger_reboot_if_needed_with_location(
         return Ok(RebootStatus {
             increase_retry_count: false,
             status: format!("Set power state to {action} using Redfish API"),
+            recheck_at: None,
         });
     }
 
@@ -5439,6 +5487,7 @@ pub async fn trigger_reboot_if_needed_with_location(
                 "Not trying to reboot {} since health override is set to prevent reboot.",
                 target.id
             ),
+            recheck_at: None,
         });
     }
 
@@ -5548,11 +5597,13 @@ pub async fn trigger_reboot_if_needed_with_location(
             Ok(RebootStatus {
                 increase_retry_count: true,
                 status,
+                recheck_at: None,
             })
         } else {
             Ok(RebootStatus {
                 increase_retry_count: false,
                 status: format!("Will attempt next reboot at {next_potential_reboot_time}"),
+                recheck_at: None,
             })
         }
     } else {
@@ -6156,10 +6207,13 @@ async fn handle_ready_boot_config(
                 UnlockHostState::WaitForUefiBoot => {
                     let entered_at = mh_snapshot.host_snapshot.state.version.timestamp();
                     if wait(&entered_at, reachability_params.uefi_boot_wait) {
-                        return Ok(StateHandlerOutcome::wait(format!(
-                            "Waiting for UEFI boot to complete on {} after post-unlock reboot",
-                            mh_snapshot.host_snapshot.id
-                        )));
+                        return Ok(StateHandlerOutcome::wait_until(
+                            format!(
+                                "Waiting for UEFI boot to complete on {} after post-unlock reboot",
+                                mh_snapshot.host_snapshot.id
+                            ),
+                            entered_at + reachability_params.uefi_boot_wait,
+                        ));
                     }
                     ReadyBootConfigState::CheckHostConfig
                 }
@@ -7284,7 +7338,7 @@ impl StateHandler for HostMachineStateHandler {
                             ctx,
                         )
                         .await?;
-                        return Ok(StateHandlerOutcome::wait(status.status));
+                        return Ok(status.wait());
                     }
 
                     Ok(StateHandlerOutcome::transition(
@@ -7406,14 +7460,14 @@ impl StateHandler for HostMachineStateHandler {
                             // Lets wait for some time before checking if DPU is up or not.
                             // Waiting is needed because DPU takes some time to go down. If we check DPU
                             // reachability before it goes down, it will give us wrong result.
-                            if wait(
-                                &mh_snapshot.host_snapshot.state.version.timestamp(),
-                                self.host_handler_params.reachability_params.dpu_wait_time,
-                            ) {
-                                Ok(StateHandlerOutcome::wait(format!(
-                                    "Forced wait of {} for DPU to power down",
-                                    self.host_handler_params.reachability_params.dpu_wait_time
-                                )))
+                            let entered_at = mh_snapshot.host_snapshot.state.version.timestamp();
+                            let dpu_wait_time =
+                                self.host_handler_params.reachability_params.dpu_wait_time;
+                            if wait(&entered_at, dpu_wait_time) {
+                                Ok(StateHandlerOutcome::wait_until(
+                                    format!("Forced wait of {dpu_wait_time} for DPU to power down"),
+                                    entered_at + dpu_wait_time,
+                                ))
                             } else {
                                 let next_state = ManagedHostState::HostInit {
                                     machine_state: MachineState::WaitingForLockdown {
@@ -7579,10 +7633,14 @@ impl StateHandler for HostMachineStateHandler {
                             ctx,
                         )
                         .await?;
-                        Ok(StateHandlerOutcome::wait(format!(
-                            "Waiting for scout to call RebootCompleted grpc. {}",
-                            status.status
-                        )))
+                        Ok(RebootStatus {
+                            status: format!(
+                                "Waiting for scout to call RebootCompleted grpc. {}",
+                                status.status
+                            ),
+                            ..status
+                        }
+                        .wait())
                     }
                 }
             }
@@ -8175,7 +8233,7 @@ impl StateHandler for InstanceStateHandler {
                             };
                             StateHandlerOutcome::transition(next_state)
                         } else {
-                            StateHandlerOutcome::wait(status.status)
+                            status.wait()
                         };
                         return Ok(st);
                     }
@@ -8460,6 +8518,16 @@ impl StateHandler for InstanceStateHandler {
 
                     // Delete from database now. Once done, reboot and move to next state.
                     let mut txn = ctx.services.db_pool.begin().await?;
+                    // The instance row is gone by the time scout reports cleanup, so
+                    // remember whose disks it is about to sanitize.
+                    db::disk_sanitization::record_release(
+                        &mut txn,
+                        &instance.machine_id,
+                        instance.id,
+                        instance.config.tenant.tenant_organization_id.as_str(),
+                    )
+                    .await
+                    .map_err(|err| StateHandlerError::GenericError(err.into()))?;
                     db::instance::delete(instance.id, &mut txn)
                         .await
                         .map_err(|err| StateHandlerError::GenericError(err.into()))?;
@@ -11884,21 +11952,19 @@ async fn handle_boss_job_failure(
                 .map(|x| x.time)
                 .unwrap_or(mh_snapshot.host_snapshot.state.version.timestamp());
 
-            if wait(
-                &basetime,
-                ctx.services
-                    .site_config
-                    .machine_state_controller
-                    .power_down_wait,
-            ) {
-                return Ok(StateHandlerOutcome::wait(format!(
-                    "waiting for {} to power down; power_down_wait: {}",
-                    mh_snapshot.host_snapshot.id,
-                    ctx.services
-                        .site_config
-                        .machine_state_controller
-                        .power_down_wait
-                )));
+            let power_down_wait = ctx
+                .services
+                .site_config
+                .machine_state_controller
+                .power_down_wait;
+            if wait(&basetime, power_down_wait) {
+                return Ok(StateHandlerOutcome::wait_until(
+                    format!(
+                        "waiting for {} to power down; power_down_wait: {power_down_wait}",
+                        mh_snapshot.host_snapshot.id,
+                    ),
+                    basetime + power_down_wait,
+                ));
             }
 
             if current_power_state != libredfish::PowerState::On {
@@ -12547,10 +12613,13 @@ async fn handle_instance_host_platform_config(
                 .unwrap_or(mh_snapshot.host_snapshot.state.version.timestamp());
 
             if wait(&basetime, reachability_params.power_down_wait) {
-                return Ok(StateHandlerOutcome::wait(format!(
-                    "waiting for power-down grace period before powering on {}; power_down_wait: {}",
-                    mh_snapshot.host_snapshot.id, reachability_params.power_down_wait
-                )));
+                return Ok(StateHandlerOutcome::wait_until(
+                    format!(
+                        "waiting for power-down grace period before powering on {}; power_down_wait: {}",
+                        mh_snapshot.host_snapshot.id, reachability_params.power_down_wait
+                    ),
+                    basetime + reachability_params.power_down_wait,
+                ));
             }
 
             if power_state == PowerState::On {
@@ -12691,13 +12760,17 @@ async fn handle_instance_host_platform_config(
                 UnlockHostState::WaitForUefiBoot => {
                     let entered_at = mh_snapshot.host_snapshot.state.version.timestamp();
                     if wait(&entered_at, reachability_params.uefi_boot_wait) {
-                        return Ok(StateHandlerOutcome::wait(format!(
-                            "Waiting for UEFI boot to complete on {} after post-unlock reboot; \
-                             wait duration: {}, will proceed after {}",
-                            mh_snapshot.host_snapshot.id,
-                            reachability_params.uefi_boot_wait,
-                            entered_at + reachability_params.uefi_boot_wait,
-                        )));
+                        let proceed_at = entered_at + reachability_params.uefi_boot_wait;
+                        return Ok(StateHandlerOutcome::wait_until(
+                            format!(
+                                "Waiting for UEFI boot to complete on {} after post-unlock reboot; \
+                                 wait duration: {}, will proceed after {}",
+                                mh_snapshot.host_snapshot.id,
+                                reachability_params.uefi_boot_wait,
+                                proceed_at,
+                            ),
+                            proceed_at,
+                        ));
                     }
 
                     InstanceState::HostPlatformConfiguration {
@@ -12965,6 +13038,7 @@ async fn set_host_boot_order(
                         RebootStatus {
                             increase_retry_count: true,
                             status: "Restarted host".to_string(),
+                            recheck_at: None,
                         }
                     } else {
                         trigger_reboot_if_needed(
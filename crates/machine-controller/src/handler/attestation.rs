@@ -33,11 +33,13 @@ use model::machine::{
     ManagedHostStateSnapshot, SpdmMeasuringState, StateMachineArea,
 };
 use sqlx::PgPool;
+use state_controller::explain::SideEffectRecorder;
 use state_controller::state_handler::{
     StateHandlerContext, StateHandlerError, StateHandlerOutcome,
 };
 
 use crate::context::MachineStateHandlerContextObjects;
+use crate::dry_run::commit_unless_dry_run;
 use crate::handler::MachineStateHandlerServices;
 
 const PRODUCT_GB200: &str = "GB200 NVL";
@@ -49,6 +51,7 @@ pub async fn trigger_attestation(
     bmc_info: &BmcInfo,
     machine_id: &MachineId,
     redfish_timeout_duration: std::time::Duration,
+    dry_run: Option<&SideEffectRecorder>,
 ) -> Result<u64, StateHandlerError> {
     // retrieve bmc info for a machine and create redfish client
     // get service root
@@ -140,7 +143,7 @@ pub async fn trigger_attestation(
     )
     .await?;
 
-    txn.commit().await?;
+    commit_unless_dry_run(txn, dry_run, "insert device attestations", machine_id).await?;
 
     tracing::info!(
         %machine_id,
@@ -310,6 +313,7 @@ pub(crate) async fn handle_spdm_trigger_state(
         &mh_snapshot.host_snapshot.status.bmc_info,
         host_machine_id,
         std::time::Duration::MAX,
+        services.dry_run.as_ref(),
     )
     .await?;
 
@@ -126,6 +126,7 @@ pub(super) async fn configure_host_bios(
                 RebootStatus {
                     increase_retry_count: true,
                     status: "Restarted host".to_string(),
+                    recheck_at: None,
                 }
             } else {
                 trigger_reboot_if_needed(
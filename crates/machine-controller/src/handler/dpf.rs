@@ -332,12 +332,11 @@ async fn handle_dpf_handle_reboot(
 
     // Due to enqueue based event handling, On is triggered immedietely after Off while chassis is
     // not able to process Off completely. So this time delay is needed.
-    if super::wait(
-        &state.host_snapshot.state.version.timestamp(),
-        power_down_wait,
-    ) {
-        return Ok(StateHandlerOutcome::wait(
+    let entered_at = state.host_snapshot.state.version.timestamp();
+    if super::wait(&entered_at, power_down_wait) {
+        return Ok(StateHandlerOutcome::wait_until(
             "waiting for power transition delay".into(),
+            entered_at + power_down_wait,
         ));
     }
 
@@ -52,6 +52,7 @@ use state_controller::state_handler::{
 use crate::context::MachineStateHandlerContextObjects;
 use crate::dpf::DpfOperations;
 use crate::dpu_service_sync::{ReleaseOutcome, TenantPolicy};
+use crate::dry_run::commit_unless_dry_run;
 
 /// Performs the DPU-side work a host owes, recorded as a pending action while
 /// the host was busy elsewhere.
@@ -108,6 +109,7 @@ pub(super) async fn handle_pending_dpu_actions(
         // hold.
         TenantPolicy::RefuseIfAssigned,
         MachinePendingActionActor::Automatic,
+        ctx.services.dry_run.as_ref(),
     )
     .await;
 
@@ -160,15 +162,23 @@ pub(super) async fn complete_pending_sync(
     ctx: &mut StateHandlerContext<'_, MachineStateHandlerContextObjects>,
     host: &Machine,
 ) -> Result<bool, StateHandlerError> {
-    let mut conn = ctx.services.db_pool.acquire().await?;
+    let mut txn = ctx.services.db_pool.begin().await?;
     // Both callers are carbide acting on its own: this handler having confirmed
     // the DPUs are current, and the provisioning path having released the hold
     // itself. An operator-driven release records itself separately.
-    Ok(db::machine_pending_action::complete(
-        &mut conn,
+    let completed = db::machine_pending_action::complete(
+        &mut txn,
         &host.id,
         DpuServiceSync,
         MachinePendingActionActor::Automatic,
     )
-    .await?)
+    .await?;
+    commit_unless_dry_run(
+        txn,
+        ctx.services.dry_run.as_ref(),
+        "complete pending DPU service sync",
+        &host.id,
+    )
+    .await?;
+    Ok(completed)
 }
@@ -51,6 +51,7 @@ use state_controller::state_handler::{
 
 use super::{current_site_uefi_target, handler_restart_dpu, resolve_site_uefi_credentials};
 use crate::context::{MachineStateHandlerContextObjects, MachineStateHandlerServices};
+use crate::dry_run;
 
 /// `true` when this DPU's UEFI credential lags the staged site-wide `dpu_uefi`
 /// target and is not quarantined. A DPU with no BMC MAC (untrackable) or no
@@ -262,9 +263,9 @@ pub(crate) async fn handle_rotating_dpu_uefi(
     // Stage the target before dispatch (crash-safe), in its own short
     // transaction so no lock is held across the Redfish round-trip.
     {
-        let mut conn = db_pool.acquire().await?;
+        let mut txn = db_pool.begin().await?;
         db::credential_rotation::mark_device_rotating_to_version(
-            &mut conn,
+            &mut txn,
             dpu_bmc_mac,
             DpuUefi,
             target as i32,
@@ -273,6 +274,13 @@ pub(crate) async fn handle_rotating_dpu_uefi(
         .map_err(|e| {
             StateHandlerError::GenericError(eyre!("stage dpu uefi rotating_to_version: {e}"))
         })?;
+        dry_run::commit_unless_dry_run(
+            txn,
+            ctx.services.dry_run.as_ref(),
+            "stage dpu UEFI rotating_to_version",
+            &dpu.id,
+        )
+        .await?;
     }
 
     match ctx
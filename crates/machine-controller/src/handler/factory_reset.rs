@@ -78,6 +78,7 @@ use carbide_credential_rotation::site_explorer_pause::{self, GateDecision};
 use carbide_redfish::libredfish::RedfishAuth;
 use carbide_redfish::libredfish::error::state_handler_redfish_error as redfish_error;
 use carbide_secrets::credentials::{BmcCredentialType, CredentialKey, Credentials};
+use carbide_uuid::machine::MachineId;
 use eyre::eyre;
 use libredfish::RedfishError;
 use libredfish::model::service_root::RedfishVendor;
@@ -91,6 +92,7 @@ use state_controller::state_handler::{
 };
 
 use crate::context::MachineStateHandlerContextObjects;
+use crate::dry_run;
 
 /// The `reason` this flow stamps on the site-explorer suppression it owns.
 /// Deletes in [`remove_suppression`] are scoped to it (via the shared
@@ -327,11 +329,18 @@ async fn read_per_device_bmc_cred(
 /// already invalidates sessions), mirroring `rotate_bmc`.
 async fn flush_bmc_session(
     ctx: &mut StateHandlerContext<'_, MachineStateHandlerContextObjects>,
+    host_id: &MachineId,
     host_bmc_mac: MacAddress,
 ) -> Result<(), StateHandlerError> {
-    let mut conn = ctx.services.db_pool.acquire().await?;
-    db::bmc_redfish_session::delete_by_mac(&mut conn, host_bmc_mac).await?;
-    Ok(())
+    let mut txn = ctx.services.db_pool.begin().await?;
+    db::bmc_redfish_session::delete_by_mac(&mut txn, host_bmc_mac).await?;
+    dry_run::commit_unless_dry_run(
+        txn,
+        ctx.services.dry_run.as_ref(),
+        "flush BMC Redfish session",
+        host_id,
+    )
+    .await
 }
 
 /// Backoff before the next factory-credential login probe, derived from the
@@ -419,8 +428,8 @@ async fn suppress_exploration(
 ) -> Result<StateHandlerOutcome<ManagedHostState>, StateHandlerError> {
     let host_bmc_mac = require_bmc_mac(mh_snapshot)?;
 
-    match site_explorer_pause::gate_before_credential_change(
-        &ctx.services.db_pool,
+    match dry_run::gate_before_credential_change(
+        ctx.services,
         &[host_bmc_mac],
         FACTORY_RESET_SUPPRESSION_REASON,
     )
@@ -486,9 +495,10 @@ async fn wait_for_bmc(
     let settle = chrono::Duration::seconds(BMC_POST_RESET_SETTLE_SECS);
     let entered_at = mh_snapshot.host_snapshot.state.version.timestamp();
     if super::wait(&entered_at, settle) {
-        return Ok(StateHandlerOutcome::wait(format!(
-            "settling {settle} before probing BMC {host_bmc_mac} after factory reset"
-        )));
+        return Ok(StateHandlerOutcome::wait_until(
+            format!("settling {settle} before probing BMC {host_bmc_mac} after factory reset"),
+            entered_at + settle,
+        ));
     }
 
     let (host, port) = bmc_host_port(mh_snapshot)?;
@@ -563,8 +573,12 @@ async fn restore_credentials(
 
     // Login backoff gate derived from the state version timestamp.
     let entered_at = mh_snapshot.host_snapshot.state.version.timestamp();
-    if super::wait(&entered_at, verify_backoff(retry_count)) {
-        return Ok(StateHandlerOutcome::do_nothing());
+    let backoff = verify_backoff(retry_count);
+    if super::wait(&entered_at, backoff) {
+        return Ok(StateHandlerOutcome::wait_until(
+            format!("backing off {backoff} before verifying BMC credentials"),
+            entered_at + backoff,
+        ));
     }
 
     let (host, port) = bmc_host_port(mh_snapshot)?;
@@ -642,7 +656,7 @@ async fn restore_credentials(
         .await
     {
         Ok(()) => {
-            flush_bmc_session(ctx, host_bmc_mac).await?;
+            flush_bmc_session(ctx, &mh_snapshot.host_snapshot.id, host_bmc_mac).await?;
             tracing::info!(
                 %host_bmc_mac,
                 "restored host BMC to its previous per-device credential after factory reset"
@@ -661,7 +675,7 @@ async fn restore_credentials(
                 .bmc_credentials_valid(&host, port, per_device)
                 .await?
             {
-                flush_bmc_session(ctx, host_bmc_mac).await?;
+                flush_bmc_session(ctx, &mh_snapshot.host_snapshot.id, host_bmc_mac).await?;
                 tracing::warn!(
                     %host_bmc_mac,
                     "password change reported an error but the per-device credential is already valid; treating BMC as already restored (prior attempt succeeded before advancing)"
@@ -555,6 +555,7 @@ mod tests {
                 Duration::from_secs(60),
             ),
             per_object_info: None,
+            dry_run: None,
             bmc_rotation_gate: carbide_credential_rotation::RotationGate::new_for_family(
                 db::credential_rotation::CredentialRotationType::Bmc,
             ),
@@ -56,6 +56,7 @@ use state_controller::state_handler::{
 
 use super::{current_site_uefi_target, handler_host_power_control, resolve_site_uefi_credentials};
 use crate::context::{MachineStateHandlerContextObjects, MachineStateHandlerServices};
+use crate::dry_run;
 
 /// Whether a Ready host should enter `ManagedHostState::RotatingHostUefi` now.
 ///
@@ -315,9 +316,9 @@ async fn set_rotating_host_uefi_password(
     // Stage the target before dispatch (crash-safe), in its own short
     // transaction so no lock is held across the Redfish round-trip.
     {
-        let mut conn = db_pool.acquire().await?;
+        let mut txn = db_pool.begin().await?;
         db::credential_rotation::mark_device_rotating_to_version(
-            &mut conn,
+            &mut txn,
             host_bmc_mac,
             HostUefi,
             target as i32,
@@ -326,6 +327,13 @@ async fn set_rotating_host_uefi_password(
         .map_err(|e| {
             StateHandlerError::GenericError(eyre!("stage host uefi rotating_to_version: {e}"))
         })?;
+        dry_run::commit_unless_dry_run(
+            txn,
+            ctx.services.dry_run.as_ref(),
+            "stage host UEFI rotating_to_version",
+            &state.host_snapshot.id,
+        )
+        .await?;
     }
 
     match ctx
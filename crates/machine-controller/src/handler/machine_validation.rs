@@ -298,7 +298,7 @@ pub(crate) async fn handle_machine_validation_state(
                     ctx,
                 )
                 .await?;
-                return Ok(StateHandlerOutcome::wait(status.status));
+                return Ok(status.wait());
             }
             if !host_handler_params.machine_validation_config.enabled {
                 return skip_machine_validation(ctx, id, mh_snapshot).await;
@@ -455,14 +455,15 @@ pub(crate) async fn handle_machine_validation_state(
                 }
                 UnlockHostState::WaitForUefiBoot => {
                     let entered_at = mh_snapshot.host_snapshot.state.version.timestamp();
-                    if wait(
-                        &entered_at,
-                        host_handler_params.reachability_params.uefi_boot_wait,
-                    ) {
-                        return Ok(StateHandlerOutcome::wait(format!(
-                            "Waiting for UEFI boot to complete on {} after post-unlock reboot",
-                            mh_snapshot.host_snapshot.id
-                        )));
+                    let uefi_boot_wait = host_handler_params.reachability_params.uefi_boot_wait;
+                    if wait(&entered_at, uefi_boot_wait) {
+                        return Ok(StateHandlerOutcome::wait_until(
+                            format!(
+                                "Waiting for UEFI boot to complete on {} after post-unlock reboot",
+                                mh_snapshot.host_snapshot.id
+                            ),
+                            entered_at + uefi_boot_wait,
+                        ));
                     }
                     MachineValidatingState::CheckBootConfigForRepair {
                         validation_id: *validation_id,
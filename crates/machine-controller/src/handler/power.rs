@@ -88,12 +88,19 @@ pub(super) async fn handle_power_desired_on(
             now + power_options_config.next_try_duration_on_success;
         match power_state {
             UsablePowerState::Usable(PowerState::Off) => {
+                // A host woken by the idle host power policy was powered off by carbide
+                // itself, so there is no need to wait for the Off state to settle.
+                let last_fetched_off_counter = if current_power_options.idle_parked_at.is_some() {
+                    current_power_options.last_fetched_off_counter.max(2)
+                } else {
+                    current_power_options.last_fetched_off_counter
+                };
                 let (ret_val, try_power_on) = get_updated_power_options_for_desired_on_state_off(
                     updated_power_options,
                     power_options_config.next_try_duration_on_failure,
                     power_options_config.wait_duration_until_host_reboot,
                     now,
-                    current_power_options.last_fetched_off_counter,
+                    last_fetched_off_counter,
                 );
                 if try_power_on {
                     // Try power on here.
@@ -187,6 +194,24 @@ pub(super) async fn get_updated_power_options_desired_off(
                     "Desired state is Off and actual state is Off.".to_string()
                 };
                 if let PowerState::On = power_state {
+                    if current_power_options.idle_parked_at.is_some() {
+                        // The idle host power policy parked this host; power it off. This is
+                        // retried on every poll until the host is seen Off.
+                        handler_host_power_control(
+                            mh_snapshot,
+                            ctx,
+                            SystemPowerControl::GracefulShutdown,
+                        )
+                        .await?;
+                        return Ok(PowerHandlingOutcome::new(
+                            Some(updated_power_options),
+                            false,
+                            Some(
+                                "Powering off host parked by the idle host power policy."
+                                    .to_string(),
+                            ),
+                        ));
+                    }
                     tracing::warn!(reason = %cause, "Skipping host power handling");
                 }
                 return Ok(PowerHandlingOutcome::new(
@@ -38,6 +38,7 @@ use carbide_credential_rotation::{BmcEndpoint, BmcRotationTick, RotateOutcome, r
 use carbide_uuid::machine::MachineId;
 use model::machine::{Machine, ManagedHostStateSnapshot};
 use sqlx::PgConnection;
+use state_controller::explain::{DATABASE_SERVICE, SideEffect};
 use state_controller::state_handler::StateHandlerError;
 
 use crate::context::MachineStateHandlerServices;
@@ -196,6 +197,20 @@ async fn rotate_endpoint(
     // engine's probe, which runs inside the same quarantine-on-failure envelope
     // as the rotation and reuses its credential candidates.
     let target = endpoint.into_target_probing_vendor();
+    // The engine keeps its bookkeeping (quarantine, versions, sessions) in
+    // transactions of its own, so a dry run records the rotation instead.
+    if let Some(recorder) = services.dry_run.as_ref() {
+        recorder.record(SideEffect::new(
+            DATABASE_SERVICE,
+            if force {
+                "force-rotate BMC credential"
+            } else {
+                "rotate BMC credential"
+            },
+            target.device_mac.to_string(),
+        ));
+        return BmcRotationTick::Settled;
+    }
     match rotate_bmc(
         &services.db_pool,
         services.credential_manager.as_ref(),
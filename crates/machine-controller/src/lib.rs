@@ -35,6 +35,7 @@ pub mod config;
 pub mod context;
 pub mod dpf;
 pub mod dpu_service_sync;
+pub mod dry_run;
 pub mod handler;
 pub mod health_report;
 pub mod io;
@@ -216,4 +216,50 @@ impl WriteOp for MachineWriteOp {
         };
         Ok(())
     }
+
+    fn describe(&self) -> String {
+        use MachineWriteOp::*;
+        match self {
+            UpdateRebootRequestedTime { mode, time, .. } => {
+                format!("update reboot requested time ({mode:?} at {time})")
+            }
+            PersistMachineHealthHistory { .. } => "persist machine health history".to_string(),
+            ResetHostReprovisioningRequest { clear_reset, .. } => {
+                format!("reset host reprovisioning request (clear_reset: {clear_reset})")
+            }
+            UpdateDpuReprovisionStartTime { time, .. } => {
+                format!("update DPU reprovision start time ({time})")
+            }
+            UpdateHostReprovisionStartTime { time, .. } => {
+                format!("update host reprovision start time ({time})")
+            }
+            ClearFailureDetails { .. } => "clear failure details".to_string(),
+            UpdateRestartVerificationStatus {
+                verified, attempts, ..
+            } => format!(
+                "update restart verification status (verified: {verified:?}, attempts: {attempts})"
+            ),
+            UpdateFirmwareVersionByMachineId {
+                bmc_version,
+                bios_version,
+                ..
+            } => format!("update firmware versions (BMC {bmc_version}, BIOS {bios_version})"),
+            SetTopologyUpdateNeeded { value, .. } => {
+                format!("set topology update needed ({value})")
+            }
+            SetCustomPxeRebootRequested { requested, .. } => {
+                format!("set custom PXE reboot requested ({requested})")
+            }
+            InsertMachineHealthReport { mode, .. } => {
+                format!("insert machine health report ({mode:?})")
+            }
+            ReExploreIfVersionMatches { address, .. } => {
+                format!("re-explore endpoint {address}")
+            }
+            UseCustomIpxeOnNextBoot {
+                boot_with_custom_ipxe,
+                ..
+            } => format!("use custom iPXE on next boot ({boot_with_custom_ipxe})"),
+        }
+    }
 }
@@ -185,6 +185,7 @@ impl EnvBuilder {
             ),
             per_object_metrics_registry,
             per_object_info: None,
+            dry_run: None,
         };
         let machine_controller = StateController::<MachineStateControllerIO>::builder()
             .database(pool, api.work_lock_manager_handle())
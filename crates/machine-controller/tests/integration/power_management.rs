@@ -513,3 +513,73 @@ async fn queued_power_off_runs_when_power_manager_gates_desired_off(
 
     Ok(())
 }
+
+#[sqlx_test]
+async fn idle_parked_host_is_powered_off_and_woken(
+    pool: PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let TestContext { mut env, mh } = TestContext::init(pool).await;
+    let bmc_access_info = mh.bmc_access_info().await;
+
+    // Park the host the way the idle host power manager does.
+    let mut txn = env.test_harness.db_txn().await;
+    let power_options = db::power_options::get_all(&mut txn).await?;
+    let parked = db::power_options::park_idle_host(
+        &mh.host.id,
+        &power_options[0].desired_power_state_version,
+        &mut txn,
+    )
+    .await?
+    .expect("host without instance should be parked");
+    assert_eq!(parked.desired_power_state, PowerState::Off);
+    assert!(parked.idle_parked_at.is_some());
+    txn.commit().await?;
+
+    // The power manager shuts the parked host down.
+    mh.set_next_power_poll_now().await;
+    let redfish_timepoint = env.redfish_sim.timepoint();
+    env.run_single_iteration().await;
+    assert_eq!(
+        env.redfish_sim
+            .actions_since(&redfish_timepoint)
+            .for_host(&bmc_access_info.host),
+        vec![RedfishSimAction::Power(
+            libredfish::SystemPowerControl::GracefulShutdown,
+        )],
+    );
+    let sim = env.redfish_sim.client_by_info(&bmc_access_info).await?;
+    assert_eq!(sim.get_power_state().await?, libredfish::PowerState::Off);
+
+    // Once woken, the host is powered on without waiting for further Off polls.
+    let mut txn = env.test_harness.db_txn().await;
+    db::power_options::update_desired_state(
+        &mh.host.id,
+        PowerState::On,
+        &parked.desired_power_state_version,
+        &mut txn,
+    )
+    .await?;
+    txn.commit().await?;
+
+    mh.set_next_power_poll_now().await;
+    let redfish_timepoint = env.redfish_sim.timepoint();
+    env.run_single_iteration().await;
+    assert_eq!(
+        env.redfish_sim
+            .actions_since(&redfish_timepoint)
+            .for_host(&bmc_access_info.host),
+        vec![RedfishSimAction::Power(libredfish::SystemPowerControl::On)],
+    );
+
+    // The parked marker is cleared once the host is seen On.
+    mh.set_next_power_poll_now().await;
+    env.run_single_iteration().await;
+    let mut txn = env.test_harness.db_txn().await;
+    let power_options = db::power_options::get_all(&mut txn).await?;
+    assert_eq!(power_options[0].desired_power_state, PowerState::On);
+    assert_eq!(power_options[0].last_fetched_power_state, PowerState::On);
+    assert!(power_options[0].idle_parked_at.is_none());
+    txn.rollback().await?;
+
+    Ok(())
+}
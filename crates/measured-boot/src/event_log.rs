@@ -0,0 +1,1643 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/*!
+ *  Parsing and replay of TCG2 crypto-agile TPM event logs (the format the
+ *  kernel exposes at /sys/kernel/security/tpm0/binary_bios_measurements).
+ *
+ *  The final PCR values in a MeasurementReport only say *that* something
+ *  changed. Replaying the event log recomputes those PCRs from the individual
+ *  measurements, which lets us (a) check the log actually accounts for the
+ *  quoted values, and (b) point at the specific bootloader, kernel or
+ *  command line measurement that differs from a known-good log.
+ *
+ *  Layout reference: TCG PC Client Platform Firmware Profile, section 10.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde::Serialize;
+use sha2::Digest;
+
+use crate::pcr::PcrRegisterValue;
+
+/// Signature of the Spec ID event that opens a crypto-agile log.
+const SPEC_ID_EVENT03_SIGNATURE: &[u8; 16] = b"Spec ID Event03\0";
+
+/// Signature of the EV_NO_ACTION event recording the locality the
+/// platform started from, which seeds the initial value of PCR 0.
+const STARTUP_LOCALITY_SIGNATURE: &[u8; 16] = b"StartupLocality\0";
+
+/// Size of the SHA-1 digest in the legacy TCG_PCR_EVENT header event.
+const LEGACY_DIGEST_SIZE: usize = 20;
+
+/// EV_EVENT_TAG identifiers the Linux EFI stub measures into PCR 9.
+const LINUX_LOAD_OPTIONS_TAG: u32 = 0x8F3B_22EC;
+const LINUX_INITRD_TAG: u32 = 0x8F3B_22ED;
+
+/// HashAlgorithm is a PCR bank algorithm we know how to replay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// from_tpm_alg_id maps a TPM_ALG_ID to a HashAlgorithm, if
+    /// it is one we support.
+    pub fn from_tpm_alg_id(alg_id: u16) -> Option<Self> {
+        match alg_id {
+            0x0004 => Some(Self::Sha1),
+            0x000B => Some(Self::Sha256),
+            0x000C => Some(Self::Sha384),
+            0x000D => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+
+    pub fn tpm_alg_id(self) -> u16 {
+        match self {
+            Self::Sha1 => 0x0004,
+            Self::Sha256 => 0x000B,
+            Self::Sha384 => 0x000C,
+            Self::Sha512 => 0x000D,
+        }
+    }
+
+    /// from_digest_size picks the algorithm for a quoted PCR value
+    /// based on its length, since MeasurementReport values don't
+    /// carry their bank.
+    pub fn from_digest_size(size: usize) -> Option<Self> {
+        match size {
+            20 => Some(Self::Sha1),
+            32 => Some(Self::Sha256),
+            48 => Some(Self::Sha384),
+            64 => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+
+    pub fn digest_size(self) -> usize {
+        match self {
+            Self::Sha1 => 20,
+            Self::Sha256 => 32,
+            Self::Sha384 => 48,
+            Self::Sha512 => 64,
+        }
+    }
+
+    /// extend computes H(pcr || digest), which is what the TPM
+    /// does on every PCR_Extend.
+    fn extend(self, pcr: &[u8], digest: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha1 => sha1::Sha1::new()
+                .chain_update(pcr)
+                .chain_update(digest)
+                .finalize()
+                .to_vec(),
+            Self::Sha256 => sha2::Sha256::new()
+                .chain_update(pcr)
+                .chain_update(digest)
+                .finalize()
+                .to_vec(),
+            Self::Sha384 => sha2::Sha384::new()
+                .chain_update(pcr)
+                .chain_update(digest)
+                .finalize()
+                .to_vec(),
+            Self::Sha512 => sha2::Sha512::new()
+                .chain_update(pcr)
+                .chain_update(digest)
+                .finalize()
+                .to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Sha1 => "sha1",
+            Self::Sha256 => "sha256",
+            Self::Sha384 => "sha384",
+            Self::Sha512 => "sha512",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// EventType is the TCG event type of a single log entry. Types
+/// we don't recognize are kept as Unknown so the log can still be
+/// replayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum EventType {
+    PrebootCert,
+    PostCode,
+    NoAction,
+    Separator,
+    Action,
+    EventTag,
+    SCrtmContents,
+    SCrtmVersion,
+    CpuMicrocode,
+    PlatformConfigFlags,
+    TableOfDevices,
+    CompactHash,
+    Ipl,
+    IplPartitionData,
+    NonhostCode,
+    NonhostConfig,
+    NonhostInfo,
+    OmitBootDeviceEvents,
+    EfiVariableDriverConfig,
+    EfiVariableBoot,
+    EfiBootServicesApplication,
+    EfiBootServicesDriver,
+    EfiRuntimeServicesDriver,
+    EfiGptEvent,
+    EfiAction,
+    EfiPlatformFirmwareBlob,
+    EfiHandoffTables,
+    EfiPlatformFirmwareBlob2,
+    EfiHandoffTables2,
+    EfiVariableBoot2,
+    EfiHcrtmEvent,
+    EfiVariableAuthority,
+    EfiSpdmFirmwareBlob,
+    EfiSpdmFirmwareConfig,
+    Unknown(u32),
+}
+
+const EVENT_TYPES: &[(u32, EventType, &str)] = &[
+    (0x0000_0000, EventType::PrebootCert, "EV_PREBOOT_CERT"),
+    (0x0000_0001, EventType::PostCode, "EV_POST_CODE"),
+    (0x0000_0003, EventType::NoAction, "EV_NO_ACTION"),
+    (0x0000_0004, EventType::Separator, "EV_SEPARATOR"),
+    (0x0000_0005, EventType::Action, "EV_ACTION"),
+    (0x0000_0006, EventType::EventTag, "EV_EVENT_TAG"),
+    (0x0000_0007, EventType::SCrtmContents, "EV_S_CRTM_CONTENTS"),
+    (0x0000_0008, EventType::SCrtmVersion, "EV_S_CRTM_VERSION"),
+    (0x0000_0009, EventType::CpuMicrocode, "EV_CPU_MICROCODE"),
+    (
+        0x0000_000A,
+        EventType::PlatformConfigFlags,
+        "EV_PLATFORM_CONFIG_FLAGS",
+    ),
+    (
+        0x0000_000B,
+        EventType::TableOfDevices,
+        "EV_TABLE_OF_DEVICES",
+    ),
+    (0x0000_000C, EventType::CompactHash, "EV_COMPACT_HASH"),
+    (0x0000_000D, EventType::Ipl, "EV_IPL"),
+    (
+        0x0000_000E,
+        EventType::IplPartitionData,
+        "EV_IPL_PARTITION_DATA",
+    ),
+    (0x0000_000F, EventType::NonhostCode, "EV_NONHOST_CODE"),
+    (0x0000_0010, EventType::NonhostConfig, "EV_NONHOST_CONFIG"),
+    (0x0000_0011, EventType::NonhostInfo, "EV_NONHOST_INFO"),
+    (
+        0x0000_0012,
+        EventType::OmitBootDeviceEvents,
+        "EV_OMIT_BOOT_DEVICE_EVENTS",
+    ),
+    (
+        0x8000_0001,
+        EventType::EfiVariableDriverConfig,
+        "EV_EFI_VARIABLE_DRIVER_CONFIG",
+    ),
+    (
+        0x8000_0002,
+        EventType::EfiVariableBoot,
+        "EV_EFI_VARIABLE_BOOT",
+    ),
+    (
+        0x8000_0003,
+        EventType::EfiBootServicesApplication,
+        "EV_EFI_BOOT_SERVICES_APPLICATION",
+    ),
+    (
+        0x8000_0004,
+        EventType::EfiBootServicesDriver,
+        "EV_EFI_BOOT_SERVICES_DRIVER",
+    ),
+    (
+        0x8000_0005,
+        EventType::EfiRuntimeServicesDriver,
+        "EV_EFI_RUNTIME_SERVICES_DRIVER",
+    ),
+    (0x8000_0006, EventType::EfiGptEvent, "EV_EFI_GPT_EVENT"),
+    (0x8000_0007, EventType::EfiAction, "EV_EFI_ACTION"),
+    (
+        0x8000_0008,
+        EventType::EfiPlatformFirmwareBlob,
+        "EV_EFI_PLATFORM_FIRMWARE_BLOB",
+    ),
+    (
+        0x8000_0009,
+        EventType::EfiHandoffTables,
+        "EV_EFI_HANDOFF_TABLES",
+    ),
+    (
+        0x8000_000A,
+        EventType::EfiPlatformFirmwareBlob2,
+        "EV_EFI_PLATFORM_FIRMWARE_BLOB2",
+    ),
+    (
+        0x8000_000B,
+        EventType::EfiHandoffTables2,
+        "EV_EFI_HANDOFF_TABLES2",
+    ),
+    (
+        0x8000_000C,
+        EventType::EfiVariableBoot2,
+        "EV_EFI_VARIABLE_BOOT2",
+    ),
+    (0x8000_0010, EventType::EfiHcrtmEvent, "EV_EFI_HCRTM_EVENT"),
+    (
+        0x8000_00E0,
+        EventType::EfiVariableAuthority,
+        "EV_EFI_VARIABLE_AUTHORITY",
+    ),
+    (
+        0x8000_00E1,
+        EventType::EfiSpdmFirmwareBlob,
+        "EV_EFI_SPDM_FIRMWARE_BLOB",
+    ),
+    (
+        0x8000_00E2,
+        EventType::EfiSpdmFirmwareConfig,
+        "EV_EFI_SPDM_FIRMWARE_CONFIG",
+    ),
+];
+
+impl From<u32> for EventType {
+    fn from(code: u32) -> Self {
+        EVENT_TYPES
+            .iter()
+            .find(|(known, _, _)| *known == code)
+            .map(|(_, event_type, _)| *event_type)
+            .unwrap_or(EventType::Unknown(code))
+    }
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Self::Unknown(code) = self {
+            return write!(f, "EV_UNKNOWN({code:#010x})");
+        }
+        let name = EVENT_TYPES
+            .iter()
+            .find(|(_, event_type, _)| event_type == self)
+            .map(|(_, _, name)| *name)
+            .unwrap_or("EV_UNKNOWN");
+        write!(f, "{name}")
+    }
+}
+
+/// BootComponent is a coarse, operator-facing classification of
+/// what an event measured, so a mismatch can be reported as "the
+/// kernel changed" rather than "event #87 in PCR 9 changed".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum BootComponent {
+    Firmware,
+    PlatformConfiguration,
+    SecureBootPolicy,
+    BootConfiguration,
+    Bootloader,
+    BootloaderConfiguration,
+    Kernel,
+    KernelCommandLine,
+    Initrd,
+    Separator,
+    Other,
+}
+
+impl fmt::Display for BootComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Firmware => "firmware",
+            Self::PlatformConfiguration => "platform configuration",
+            Self::SecureBootPolicy => "secure boot policy",
+            Self::BootConfiguration => "boot configuration",
+            Self::Bootloader => "bootloader",
+            Self::BootloaderConfiguration => "bootloader configuration",
+            Self::Kernel => "kernel",
+            Self::KernelCommandLine => "kernel command line",
+            Self::Initrd => "initrd",
+            Self::Separator => "separator",
+            Self::Other => "other",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// EventDigest is one bank's digest of a single event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EventDigest {
+    pub algorithm_id: u16,
+    pub digest: Vec<u8>,
+}
+
+/// TcgEvent is a single TCG_PCR_EVENT2 entry from the log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TcgEvent {
+    /// sequence is the position of the event in the log, counting
+    /// the Spec ID header event as 0.
+    pub sequence: usize,
+    pub pcr_index: u32,
+    pub event_type: EventType,
+    pub digests: Vec<EventDigest>,
+    pub data: Vec<u8>,
+}
+
+impl TcgEvent {
+    pub fn digest(&self, algorithm: HashAlgorithm) -> Option<&[u8]> {
+        self.digests
+            .iter()
+            .find(|digest| digest.algorithm_id == algorithm.tpm_alg_id())
+            .map(|digest| digest.digest.as_slice())
+    }
+
+    /// is_extended is false for events that are informational only
+    /// and never reached the TPM.
+    pub fn is_extended(&self) -> bool {
+        self.event_type != EventType::NoAction
+    }
+
+    pub fn component(&self) -> BootComponent {
+        match self.event_type {
+            EventType::Separator => BootComponent::Separator,
+            EventType::SCrtmContents
+            | EventType::SCrtmVersion
+            | EventType::PostCode
+            | EventType::CpuMicrocode
+            | EventType::NonhostCode
+            | EventType::NonhostConfig
+            | EventType::NonhostInfo
+            | EventType::EfiPlatformFirmwareBlob
+            | EventType::EfiPlatformFirmwareBlob2
+            | EventType::EfiBootServicesDriver
+            | EventType::EfiRuntimeServicesDriver
+            | EventType::EfiHcrtmEvent
+            | EventType::EfiSpdmFirmwareBlob
+            | EventType::EfiSpdmFirmwareConfig => BootComponent::Firmware,
+            EventType::PlatformConfigFlags
+            | EventType::TableOfDevices
+            | EventType::EfiHandoffTables
+            | EventType::EfiHandoffTables2
+            | EventType::EfiGptEvent => BootComponent::PlatformConfiguration,
+            EventType::EfiVariableDriverConfig | EventType::EfiVariableAuthority => {
+                BootComponent::SecureBootPolicy
+            }
+            EventType::EfiVariableBoot | EventType::EfiVariableBoot2 => {
+                BootComponent::BootConfiguration
+            }
+            EventType::EfiBootServicesApplication => {
+                if looks_like_kernel(&self.description()) {
+                    BootComponent::Kernel
+                } else {
+                    BootComponent::Bootloader
+                }
+            }
+            EventType::EventTag => match tagged_event_id(&self.data) {
+                Some(LINUX_INITRD_TAG) => BootComponent::Initrd,
+                Some(LINUX_LOAD_OPTIONS_TAG) => BootComponent::KernelCommandLine,
+                _ => BootComponent::Other,
+            },
+            EventType::Ipl => self.ipl_component(),
+            _ => BootComponent::Other,
+        }
+    }
+
+    /// ipl_component classifies EV_IPL events, which is how GRUB and
+    /// systemd-stub measure the files and commands they act on:
+    /// commands go to PCR 8, files to PCR 9, UKI sections to PCR 11,
+    /// and systemd-stub puts the kernel command line into PCR 12.
+    fn ipl_component(&self) -> BootComponent {
+        let text = self.description();
+        match self.pcr_index {
+            8 if text.starts_with("kernel_cmdline:") || text.starts_with("grub_kernel_cmdline") => {
+                BootComponent::KernelCommandLine
+            }
+            8 => BootComponent::BootloaderConfiguration,
+            9 if looks_like_initrd(&text) => BootComponent::Initrd,
+            9 if looks_like_kernel(&text) => BootComponent::Kernel,
+            9 => BootComponent::BootloaderConfiguration,
+            11 => match text.as_str() {
+                ".linux" => BootComponent::Kernel,
+                ".cmdline" => BootComponent::KernelCommandLine,
+                ".initrd" => BootComponent::Initrd,
+                _ => BootComponent::Other,
+            },
+            12 => BootComponent::KernelCommandLine,
+            _ => BootComponent::Other,
+        }
+    }
+
+    /// description renders the event data into something an operator
+    /// can read: the image path for loaded EFI applications, the
+    /// variable name for EFI variables, and the text for the various
+    /// string-carrying event types.
+    pub fn description(&self) -> String {
+        match self.event_type {
+            EventType::EfiBootServicesApplication
+            | EventType::EfiBootServicesDriver
+            | EventType::EfiRuntimeServicesDriver => {
+                image_load_path(&self.data).unwrap_or_else(|| String::from("<unnamed image>"))
+            }
+            EventType::EfiVariableDriverConfig
+            | EventType::EfiVariableBoot
+            | EventType::EfiVariableBoot2
+            | EventType::EfiVariableAuthority => {
+                efi_variable_name(&self.data).unwrap_or_else(|| String::from("<unnamed variable>"))
+            }
+            EventType::Separator => match self.data.as_slice() {
+                [0xFF, 0xFF, 0xFF, 0xFF] => String::from("error separator"),
+                _ => String::from("separator"),
+            },
+            EventType::EventTag => match tagged_event_id(&self.data) {
+                Some(LINUX_INITRD_TAG) => String::from("Linux initrd"),
+                Some(LINUX_LOAD_OPTIONS_TAG) => String::from("LOADED_IMAGE::LoadOptions"),
+                Some(id) => format!("tagged event {id:#010x}"),
+                None => String::from("<malformed tagged event>"),
+            },
+            EventType::Ipl
+            | EventType::Action
+            | EventType::EfiAction
+            | EventType::SCrtmVersion
+            | EventType::PostCode
+            | EventType::CompactHash
+            | EventType::PlatformConfigFlags => decode_text(&self.data),
+            _ => format!("{} bytes", self.data.len()),
+        }
+    }
+
+    pub fn summary(&self, algorithm: HashAlgorithm) -> EventSummary {
+        EventSummary {
+            sequence: self.sequence,
+            pcr_index: self.pcr_index,
+            event_type: self.event_type,
+            component: self.component(),
+            description: self.description(),
+            digest: self.digest(algorithm).map(hex::encode),
+        }
+    }
+}
+
+/// EventSummary is the rendered, serializable view of a TcgEvent in
+/// a single bank, used in diffs and mismatch explanations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EventSummary {
+    pub sequence: usize,
+    pub pcr_index: u32,
+    pub event_type: EventType,
+    pub component: BootComponent,
+    pub description: String,
+    pub digest: Option<String>,
+}
+
+impl fmt::Display for EventSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} PCR {} {} {}: {} [{}]",
+            self.sequence,
+            self.pcr_index,
+            self.event_type,
+            self.component,
+            self.description,
+            self.digest.as_deref().unwrap_or("<no digest>"),
+        )
+    }
+}
+
+/// SpecIdAlgorithm is one entry of the digest size table from the
+/// Spec ID header event, which is what tells the parser how many
+/// bytes each bank's digest takes up in the events that follow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SpecIdAlgorithm {
+    pub algorithm_id: u16,
+    pub digest_size: u16,
+}
+
+/// EventLog is a parsed TCG2 crypto-agile event log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EventLog {
+    pub algorithms: Vec<SpecIdAlgorithm>,
+    pub events: Vec<TcgEvent>,
+}
+
+impl EventLog {
+    /// parse parses a binary crypto-agile event log. Legacy SHA-1
+    /// only logs (TPM 1.2 format) are rejected.
+    pub fn parse(bytes: &[u8]) -> super::Result<Self> {
+        let mut reader = Reader::new(bytes);
+
+        // The first event is always in the legacy TCG_PCR_EVENT
+        // format, and carries the Spec ID event as its data.
+        let _pcr_index = reader.u32()?;
+        let event_type = EventType::from(reader.u32()?);
+        reader.take(LEGACY_DIGEST_SIZE)?;
+        let spec_id_size = reader.u32()? as usize;
+        let spec_id = reader.take(spec_id_size)?;
+        if event_type != EventType::NoAction {
+            return Err(event_log_error(format!(
+                "first event is {event_type}, expected EV_NO_ACTION"
+            )));
+        }
+        let algorithms = parse_spec_id_event(spec_id)?;
+
+        let mut events = Vec::new();
+        while !reader.is_padding() {
+            let sequence = events.len() + 1;
+            let pcr_index = reader.u32()?;
+            let event_type = EventType::from(reader.u32()?);
+            let digest_count = reader.u32()?;
+            let mut digests = Vec::new();
+            for _ in 0..digest_count {
+                let algorithm_id = reader.u16()?;
+                let digest_size = algorithms
+                    .iter()
+                    .find(|algorithm| algorithm.algorithm_id == algorithm_id)
+                    .map(|algorithm| algorithm.digest_size as usize)
+                    .ok_or_else(|| {
+                        event_log_error(format!(
+                            "event #{sequence} uses algorithm {algorithm_id:#06x} which is not in the Spec ID event"
+                        ))
+                    })?;
+                digests.push(EventDigest {
+                    algorithm_id,
+                    digest: reader.take(digest_size)?.to_vec(),
+                });
+            }
+            let data_size = reader.u32()? as usize;
+            let data = reader.take(data_size)?.to_vec();
+            events.push(TcgEvent {
+                sequence,
+                pcr_index,
+                event_type,
+                digests,
+                data,
+            });
+        }
+
+        Ok(Self { algorithms, events })
+    }
+
+    /// banks returns the PCR banks this log carries digests for that
+    /// we know how to replay.
+    pub fn banks(&self) -> Vec<HashAlgorithm> {
+        self.algorithms
+            .iter()
+            .filter_map(|algorithm| HashAlgorithm::from_tpm_alg_id(algorithm.algorithm_id))
+            .collect()
+    }
+
+    pub fn events_for_pcr(&self, pcr_index: u32) -> impl Iterator<Item = &TcgEvent> {
+        self.events
+            .iter()
+            .filter(move |event| event.pcr_index == pcr_index && event.is_extended())
+    }
+
+    /// replay recomputes every PCR the log touches in the given bank,
+    /// starting from the reset value (all zeros, except PCR 0 which
+    /// picks up the startup locality when the log records one).
+    pub fn replay(&self, algorithm: HashAlgorithm) -> super::Result<BTreeMap<u32, Vec<u8>>> {
+        let mut pcrs: BTreeMap<u32, Vec<u8>> = BTreeMap::new();
+        let reset_value = vec![0u8; algorithm.digest_size()];
+
+        for event in &self.events {
+            if !event.is_extended() {
+                if let Some(locality) = startup_locality(&event.data) {
+                    let mut pcr0 = reset_value.clone();
+                    if let Some(last) = pcr0.last_mut() {
+                        *last = locality;
+                    }
+                    pcrs.insert(0, pcr0);
+                }
+                continue;
+            }
+
+            let digest = event.digest(algorithm).ok_or_else(|| {
+                event_log_error(format!(
+                    "event #{} has no {algorithm} digest",
+                    event.sequence
+                ))
+            })?;
+            let current = pcrs
+                .entry(event.pcr_index)
+                .or_insert_with(|| reset_value.clone());
+            *current = algorithm.extend(current, digest);
+        }
+
+        Ok(pcrs)
+    }
+
+    /// verify_replay replays the log in the bank matching the quoted
+    /// values and compares each quoted PCR against the replayed one. A
+    /// log that doesn't reproduce the quote can't be used to explain
+    /// it, so callers should treat any mismatch as "the log is not
+    /// trustworthy" rather than as an attestation failure by itself.
+    pub fn verify_replay(&self, quoted: &[PcrRegisterValue]) -> super::Result<ReplayVerification> {
+        let algorithm = algorithm_for_values(quoted)?;
+        let replayed = self.replay(algorithm)?;
+
+        let mut verification = ReplayVerification {
+            algorithm,
+            matched: Vec::new(),
+            mismatched: Vec::new(),
+            unlogged: Vec::new(),
+        };
+        for value in quoted {
+            let quoted_hex = normalize_hex(&value.sha_any);
+            let Some(replayed_value) = u32::try_from(value.pcr_register)
+                .ok()
+                .and_then(|index| replayed.get(&index))
+            else {
+                // Never-extended PCRs read as all zeros; anything else
+                // was extended by something that didn't log to this
+                // log (e.g. the OS).
+                if quoted_hex.bytes().any(|c| c != b'0') {
+                    verification.unlogged.push(value.pcr_register);
+                }
+                continue;
+            };
+            let replayed_hex = hex::encode(replayed_value);
+            if replayed_hex == quoted_hex {
+                verification.matched.push(value.pcr_register);
+            } else {
+                verification.mismatched.push(PcrValueMismatch {
+                    pcr_register: value.pcr_register,
+                    expected: quoted_hex,
+                    actual: replayed_hex,
+                });
+            }
+        }
+
+        Ok(verification)
+    }
+
+    /// diff compares the events this log extended into `pcr_index`
+    /// against the ones `reference` extended into the same PCR. Events
+    /// are aligned on their digests, and an unmatched event on each
+    /// side at the same position is reported as a single Changed diff.
+    pub fn diff_pcr(
+        &self,
+        reference: &EventLog,
+        pcr_index: u32,
+        algorithm: HashAlgorithm,
+    ) -> Vec<EventDiff> {
+        let expected: Vec<EventSummary> = reference
+            .events_for_pcr(pcr_index)
+            .map(|event| event.summary(algorithm))
+            .collect();
+        let actual: Vec<EventSummary> = self
+            .events_for_pcr(pcr_index)
+            .map(|event| event.summary(algorithm))
+            .collect();
+
+        diff_summaries(pcr_index, expected, actual)
+    }
+
+    /// diff compares every PCR either log touches. See diff_pcr.
+    pub fn diff(&self, reference: &EventLog, algorithm: HashAlgorithm) -> Vec<EventDiff> {
+        let pcrs: BTreeSet<u32> = self
+            .events
+            .iter()
+            .chain(reference.events.iter())
+            .filter(|event| event.is_extended())
+            .map(|event| event.pcr_index)
+            .collect();
+        pcrs.into_iter()
+            .flat_map(|pcr_index| self.diff_pcr(reference, pcr_index, algorithm))
+            .collect()
+    }
+
+    /// explain_mismatch takes the values a machine reported and the
+    /// values of the bundle it was expected to match, and for every
+    /// PCR that differs, works out which events are responsible. With
+    /// a `reference` log (one that produced the bundle's values), that
+    /// is a per-event diff; without one, it is the list of events this
+    /// log extended into the PCR.
+    pub fn explain_mismatch(
+        &self,
+        expected: &[PcrRegisterValue],
+        actual: &[PcrRegisterValue],
+        reference: Option<&EventLog>,
+    ) -> super::Result<Vec<PcrMismatch>> {
+        let algorithm = algorithm_for_values(expected)?;
+        let actual_by_register: BTreeMap<i16, String> = actual
+            .iter()
+            .map(|value| (value.pcr_register, normalize_hex(&value.sha_any)))
+            .collect();
+
+        let mut mismatches = Vec::new();
+        for expected_value in expected {
+            let expected_hex = normalize_hex(&expected_value.sha_any);
+            let actual_hex = actual_by_register
+                .get(&expected_value.pcr_register)
+                .cloned()
+                .unwrap_or_default();
+            if expected_hex == actual_hex {
+                continue;
+            }
+
+            let Ok(pcr_index) = u32::try_from(expected_value.pcr_register) else {
+                continue;
+            };
+            let (diffs, events) = match reference {
+                Some(reference) => (self.diff_pcr(reference, pcr_index, algorithm), Vec::new()),
+                None => (
+                    Vec::new(),
+                    self.events_for_pcr(pcr_index)
+                        .map(|event| event.summary(algorithm))
+                        .collect(),
+                ),
+            };
+            mismatches.push(PcrMismatch {
+                pcr_register: expected_value.pcr_register,
+                expected: expected_hex,
+                actual: actual_hex,
+                diffs,
+                events,
+            });
+        }
+
+        Ok(mismatches)
+    }
+}
+
+impl fmt::Display for EventLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let banks = self.banks();
+        let algorithm = banks
+            .iter()
+            .copied()
+            .find(|bank| *bank == HashAlgorithm::Sha256)
+            .or_else(|| banks.first().copied())
+            .unwrap_or(HashAlgorithm::Sha256);
+        write!(
+            f,
+            "TCG2 event log ({} events, banks: {})",
+            self.events.len(),
+            banks
+                .iter()
+                .map(|bank| bank.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        )?;
+        for event in self.events.iter().filter(|event| event.is_extended()) {
+            write!(f, "\n{}", event.summary(algorithm))?;
+        }
+        Ok(())
+    }
+}
+
+/// ReplayVerification is the outcome of replaying a log against the
+/// PCR values from a quote.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReplayVerification {
+    pub algorithm: HashAlgorithm,
+    pub matched: Vec<i16>,
+    /// mismatched uses `expected` for the quoted value and `actual`
+    /// for the value replayed from the log.
+    pub mismatched: Vec<PcrValueMismatch>,
+    /// unlogged are quoted PCRs with a non-reset value that the log
+    /// never touches.
+    pub unlogged: Vec<i16>,
+}
+
+impl ReplayVerification {
+    pub fn is_consistent(&self) -> bool {
+        self.mismatched.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PcrValueMismatch {
+    pub pcr_register: i16,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for PcrValueMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "PCR {}: expected {}, got {}",
+            self.pcr_register, self.expected, self.actual
+        )
+    }
+}
+
+/// PcrMismatch explains a single PCR where a MeasurementReport
+/// differs from a bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PcrMismatch {
+    pub pcr_register: i16,
+    pub expected: String,
+    pub actual: String,
+    /// diffs are the per-event differences against the reference
+    /// log, when one was available.
+    pub diffs: Vec<EventDiff>,
+    /// events are the measurements this machine's log extended into
+    /// the PCR, listed when there was no reference log to diff against.
+    pub events: Vec<EventSummary>,
+}
+
+impl fmt::Display for PcrMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "PCR {}: expected {}, got {}",
+            self.pcr_register, self.expected, self.actual
+        )?;
+        for diff in &self.diffs {
+            write!(f, "\n  {diff}")?;
+        }
+        for event in &self.events {
+            write!(f, "\n  {event}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum EventDiffKind {
+    Changed,
+    Added,
+    Removed,
+}
+
+impl fmt::Display for EventDiffKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Changed => "changed",
+            Self::Added => "added",
+            Self::Removed => "removed",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// EventDiff is a single event-level difference between a machine's
+/// log (`actual`) and a reference log (`expected`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EventDiff {
+    pub pcr_index: u32,
+    pub kind: EventDiffKind,
+    pub expected: Option<EventSummary>,
+    pub actual: Option<EventSummary>,
+}
+
+impl EventDiff {
+    pub fn component(&self) -> BootComponent {
+        self.actual
+            .as_ref()
+            .or(self.expected.as_ref())
+            .map(|summary| summary.component)
+            .unwrap_or(BootComponent::Other)
+    }
+}
+
+impl fmt::Display for EventDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "PCR {} {} {}",
+            self.pcr_index,
+            self.component(),
+            self.kind
+        )?;
+        match (&self.expected, &self.actual) {
+            (Some(expected), Some(actual)) => {
+                if expected.description == actual.description {
+                    write!(f, ": {}", actual.description)?;
+                } else {
+                    write!(f, ": {} -> {}", expected.description, actual.description)?;
+                }
+                write!(
+                    f,
+                    " ({} -> {})",
+                    expected.digest.as_deref().unwrap_or("<no digest>"),
+                    actual.digest.as_deref().unwrap_or("<no digest>")
+                )
+            }
+            (Some(summary), None) | (None, Some(summary)) => write!(
+                f,
+                ": {} ({})",
+                summary.description,
+                summary.digest.as_deref().unwrap_or("<no digest>")
+            ),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// diff_summaries aligns two event lists for the same PCR using a
+/// longest-common-subsequence over their digests, then walks the gaps
+/// between the aligned events, pairing up removed and added events
+/// as changes.
+fn diff_summaries(
+    pcr_index: u32,
+    expected: Vec<EventSummary>,
+    actual: Vec<EventSummary>,
+) -> Vec<EventDiff> {
+    let (n, m) = (expected.len(), actual.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if expected[i].digest == actual[j].digest {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diffs = Vec::new();
+    let mut removed: Vec<EventSummary> = Vec::new();
+    let mut added: Vec<EventSummary> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && expected[i].digest == actual[j].digest {
+            flush_gap(pcr_index, &mut removed, &mut added, &mut diffs);
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            added.push(actual[j].clone());
+            j += 1;
+        } else {
+            removed.push(expected[i].clone());
+            i += 1;
+        }
+    }
+    flush_gap(pcr_index, &mut removed, &mut added, &mut diffs);
+
+    diffs
+}
+
+fn flush_gap(
+    pcr_index: u32,
+    removed: &mut Vec<EventSummary>,
+    added: &mut Vec<EventSummary>,
+    diffs: &mut Vec<EventDiff>,
+) {
+    let mut removed = std::mem::take(removed).into_iter();
+    let mut added = std::mem::take(added).into_iter();
+    loop {
+        let (kind, expected, actual) = match (removed.next(), added.next()) {
+            (Some(expected), Some(actual)) => {
+                (EventDiffKind::Changed, Some(expected), Some(actual))
+            }
+            (Some(expected), None) => (EventDiffKind::Removed, Some(expected), None),
+            (None, Some(actual)) => (EventDiffKind::Added, None, Some(actual)),
+            (None, None) => break,
+        };
+        diffs.push(EventDiff {
+            pcr_index,
+            kind,
+            expected,
+            actual,
+        });
+    }
+}
+
+fn event_log_error(message: String) -> super::Error {
+    super::Error::EventLog(message)
+}
+
+fn algorithm_for_values(values: &[PcrRegisterValue]) -> super::Result<HashAlgorithm> {
+    let size = values
+        .first()
+        .map(|value| normalize_hex(&value.sha_any).len() / 2)
+        .ok_or_else(|| event_log_error(String::from("no PCR values to compare against")))?;
+    HashAlgorithm::from_digest_size(size)
+        .ok_or_else(|| event_log_error(format!("no PCR bank has {size} byte digests")))
+}
+
+fn normalize_hex(value: &str) -> String {
+    value.trim_start_matches("0x").to_ascii_lowercase()
+}
+
+fn parse_spec_id_event(data: &[u8]) -> super::Result<Vec<SpecIdAlgorithm>> {
+    let mut reader = Reader::new(data);
+    if reader.take(SPEC_ID_EVENT03_SIGNATURE.len())? != SPEC_ID_EVENT03_SIGNATURE {
+        return Err(event_log_error(String::from(
+            "not a crypto-agile event log (missing Spec ID Event03 signature)",
+        )));
+    }
+    // platformClass, specVersionMinor, specVersionMajor,
+    // specErrata, uintnSize.
+    reader.take(8)?;
+    let algorithm_count = reader.u32()?;
+    let mut algorithms = Vec::new();
+    for _ in 0..algorithm_count {
+        algorithms.push(SpecIdAlgorithm {
+            algorithm_id: reader.u16()?,
+            digest_size: reader.u16()?,
+        });
+    }
+    Ok(algorithms)
+}
+
+fn startup_locality(data: &[u8]) -> Option<u8> {
+    data.strip_prefix(STARTUP_LOCALITY_SIGNATURE.as_slice())
+        .and_then(|rest| rest.first().copied())
+}
+
+fn tagged_event_id(data: &[u8]) -> Option<u32> {
+    Reader::new(data).u32().ok()
+}
+
+/// image_load_path pulls the file path out of a UEFI_IMAGE_LOAD_EVENT,
+/// which is four UINT64s followed by an EFI device path.
+fn image_load_path(data: &[u8]) -> Option<String> {
+    let mut reader = Reader::new(data);
+    reader.take(24).ok()?;
+    let device_path_length = usize::try_from(reader.u64().ok()?).ok()?;
+    let device_path = reader.take(device_path_length).ok()?;
+    device_path_file(device_path)
+}
+
+/// device_path_file walks an EFI device path and joins its media file
+/// path nodes (type 0x04, subtype 0x04).
+fn device_path_file(device_path: &[u8]) -> Option<String> {
+    let mut reader = Reader::new(device_path);
+    let mut parts = Vec::new();
+    while let (Ok(node_type), Ok(sub_type)) = (reader.u8(), reader.u8()) {
+        let length = reader.u16().ok()? as usize;
+        let body = reader.take(length.checked_sub(4)?).ok()?;
+        match (node_type, sub_type) {
+            (0x7F, _) => break,
+            (0x04, 0x04) => parts.push(decode_utf16(body)),
+            _ => {}
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join(""))
+}
+
+/// efi_variable_name reads the name out of a UEFI_VARIABLE_DATA
+/// struct. Boot#### variables also get the description of the load
+/// option appended, since "Boot0003" on its own isn't very helpful.
+fn efi_variable_name(data: &[u8]) -> Option<String> {
+    let mut reader = Reader::new(data);
+    reader.take(16).ok()?;
+    let name_length = usize::try_from(reader.u64().ok()?).ok()?;
+    let data_length = usize::try_from(reader.u64().ok()?).ok()?;
+    let name = decode_utf16(reader.take(name_length.checked_mul(2)?).ok()?);
+    let value = reader.take(data_length).ok()?;
+
+    let is_boot_option = name.len() == 8
+        && name.starts_with("Boot")
+        && name[4..].chars().all(|c| c.is_ascii_hexdigit());
+    if is_boot_option && let Some(description) = load_option_description(value) {
+        return Some(format!("{name}: {description}"));
+    }
+    Some(name)
+}
+
+/// load_option_description reads the description out of an
+/// EFI_LOAD_OPTION: a UINT32 of attributes, a UINT16 file path list
+/// length, then a NUL-terminated UTF-16 description.
+fn load_option_description(value: &[u8]) -> Option<String> {
+    let description = value.get(6..)?;
+    let end = description
+        .chunks_exact(2)
+        .position(|unit| unit == [0, 0])
+        .map(|units| units * 2)
+        .unwrap_or(description.len());
+    let description = decode_utf16(&description[..end]);
+    (!description.is_empty()).then_some(description)
+}
+
+fn decode_utf16(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+/// decode_text handles event data that is text, which depending on
+/// who did the measuring is either UTF-8/ASCII or UTF-16LE.
+fn decode_text(data: &[u8]) -> String {
+    let looks_utf16 = data.len() >= 2
+        && data.len().is_multiple_of(2)
+        && data.chunks_exact(2).all(|unit| unit[1] == 0);
+    let text = if looks_utf16 {
+        decode_utf16(data)
+    } else {
+        String::from_utf8_lossy(data).to_string()
+    };
+    text.trim_end_matches('\0')
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+fn file_name(path: &str) -> String {
+    path.rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(path)
+        .to_ascii_lowercase()
+}
+
+fn looks_like_kernel(path: &str) -> bool {
+    let name = file_name(path);
+    ["vmlinuz", "vmlinux", "bzimage", "image", "linux"]
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+fn looks_like_initrd(path: &str) -> bool {
+    let name = file_name(path);
+    name.contains("initrd") || name.contains("initramfs")
+}
+
+/// Reader is a little-endian cursor over the raw log bytes.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> super::Result<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| {
+                event_log_error(format!(
+                    "truncated event log: wanted {len} bytes at offset {}, have {}",
+                    self.offset,
+                    self.bytes.len() - self.offset
+                ))
+            })?;
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> super::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> super::Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> super::Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> super::Result<u64> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    /// is_padding is true once the rest of the buffer is empty or just
+    /// filler. Logs read out of ACPI tables are the full size of the
+    /// reserved area, and the unused tail is either zeros or 0xFF.
+    fn is_padding(&self) -> bool {
+        let rest = &self.bytes[self.offset..];
+        rest.iter().all(|byte| *byte == 0x00) || rest.iter().all(|byte| *byte == 0xFF)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::Outcome::{Fails, Yields};
+    use carbide_test_support::{Case, Check, check_cases, check_values};
+
+    use super::*;
+
+    /// LogBuilder assembles a binary crypto-agile log with SHA-1 and
+    /// SHA-256 banks, where each event's digests are the real hashes of
+    /// `measured` so replays can be checked against a reference.
+    struct LogBuilder {
+        bytes: Vec<u8>,
+    }
+
+    impl LogBuilder {
+        fn new() -> Self {
+            let mut spec_id = SPEC_ID_EVENT03_SIGNATURE.to_vec();
+            spec_id.extend_from_slice(&0u32.to_le_bytes()); // platformClass
+            spec_id.extend_from_slice(&[0, 2, 0, 2]); // minor, major, errata, uintnSize
+            spec_id.extend_from_slice(&2u32.to_le_bytes());
+            for algorithm in [HashAlgorithm::Sha1, HashAlgorithm::Sha256] {
+                spec_id.extend_from_slice(&algorithm.tpm_alg_id().to_le_bytes());
+                spec_id.extend_from_slice(&(algorithm.digest_size() as u16).to_le_bytes());
+            }
+            spec_id.push(0); // vendorInfoSize
+
+            let mut bytes = Vec::new();
+            bytes.extend_from_slice(&0u32.to_le_bytes());
+            bytes.extend_from_slice(&3u32.to_le_bytes());
+            bytes.extend_from_slice(&[0u8; LEGACY_DIGEST_SIZE]);
+            bytes.extend_from_slice(&(spec_id.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&spec_id);
+            Self { bytes }
+        }
+
+        fn event(mut self, pcr_index: u32, event_type: u32, measured: &[u8], data: &[u8]) -> Self {
+            self.bytes.extend_from_slice(&pcr_index.to_le_bytes());
+            self.bytes.extend_from_slice(&event_type.to_le_bytes());
+            self.bytes.extend_from_slice(&2u32.to_le_bytes());
+            self.bytes
+                .extend_from_slice(&HashAlgorithm::Sha1.tpm_alg_id().to_le_bytes());
+            self.bytes.extend_from_slice(&sha1::Sha1::digest(measured));
+            self.bytes
+                .extend_from_slice(&HashAlgorithm::Sha256.tpm_alg_id().to_le_bytes());
+            self.bytes
+                .extend_from_slice(&sha2::Sha256::digest(measured));
+            self.bytes
+                .extend_from_slice(&(data.len() as u32).to_le_bytes());
+            self.bytes.extend_from_slice(data);
+            self
+        }
+
+        fn build(self) -> Vec<u8> {
+            self.bytes
+        }
+    }
+
+    fn utf16(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    fn image_load_event(path: &str) -> Vec<u8> {
+        let mut file_node = vec![0x04, 0x04];
+        let name = utf16(&format!("{path}\0"));
+        file_node.extend_from_slice(&((name.len() + 4) as u16).to_le_bytes());
+        file_node.extend_from_slice(&name);
+        file_node.extend_from_slice(&[0x7F, 0xFF, 0x04, 0x00]);
+
+        let mut data = vec![0u8; 24];
+        data.extend_from_slice(&(file_node.len() as u64).to_le_bytes());
+        data.extend_from_slice(&file_node);
+        data
+    }
+
+    const EV_SEPARATOR: u32 = 0x0000_0004;
+    const EV_IPL: u32 = 0x0000_000D;
+    const EV_S_CRTM_VERSION: u32 = 0x0000_0008;
+    const EV_EFI_BOOT_SERVICES_APPLICATION: u32 = 0x8000_0003;
+
+    /// boot_log is a small but representative GRUB boot: firmware
+    /// version, separators, shim and grub in PCR 4, the kernel command
+    /// line in PCR 8 and the kernel file in PCR 9.
+    fn boot_log(kernel: &[u8], cmdline: &str) -> Vec<u8> {
+        let cmdline_text = format!("kernel_cmdline: {cmdline}");
+        LogBuilder::new()
+            .event(0, EV_S_CRTM_VERSION, b"1.2.3", &utf16("1.2.3"))
+            .event(0, EV_SEPARATOR, &[0; 4], &[0; 4])
+            .event(
+                4,
+                EV_EFI_BOOT_SERVICES_APPLICATION,
+                b"shim",
+                &image_load_event("\\EFI\\BOOT\\shimx64.efi"),
+            )
+            .event(
+                4,
+                EV_EFI_BOOT_SERVICES_APPLICATION,
+                b"grub",
+                &image_load_event("\\EFI\\ubuntu\\grubx64.efi"),
+            )
+            .event(
+                8,
+                EV_IPL,
+                cmdline_text.as_bytes(),
+                format!("{cmdline_text}\0").as_bytes(),
+            )
+            .event(9, EV_IPL, kernel, b"/boot/vmlinuz-6.8.0\0")
+            .build()
+    }
+
+    fn expected_pcr(measured: &[&[u8]]) -> String {
+        let mut pcr = vec![0u8; 32];
+        for data in measured {
+            pcr = HashAlgorithm::Sha256.extend(&pcr, &sha2::Sha256::digest(data));
+        }
+        hex::encode(pcr)
+    }
+
+    fn quoted(values: &[(i16, String)]) -> Vec<PcrRegisterValue> {
+        values
+            .iter()
+            .map(|(pcr_register, sha_any)| PcrRegisterValue {
+                pcr_register: *pcr_register,
+                sha_any: sha_any.clone(),
+            })
+            .collect()
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct ParsedSummary {
+        banks: Vec<HashAlgorithm>,
+        events: Vec<(u32, EventType, BootComponent, String)>,
+    }
+
+    fn boot_log_summary() -> ParsedSummary {
+        ParsedSummary {
+            banks: vec![HashAlgorithm::Sha1, HashAlgorithm::Sha256],
+            events: vec![
+                (
+                    0,
+                    EventType::SCrtmVersion,
+                    BootComponent::Firmware,
+                    "1.2.3".to_string(),
+                ),
+                (
+                    0,
+                    EventType::Separator,
+                    BootComponent::Separator,
+                    "separator".to_string(),
+                ),
+                (
+                    4,
+                    EventType::EfiBootServicesApplication,
+                    BootComponent::Bootloader,
+                    "\\EFI\\BOOT\\shimx64.efi".to_string(),
+                ),
+                (
+                    4,
+                    EventType::EfiBootServicesApplication,
+                    BootComponent::Bootloader,
+                    "\\EFI\\ubuntu\\grubx64.efi".to_string(),
+                ),
+                (
+                    8,
+                    EventType::Ipl,
+                    BootComponent::KernelCommandLine,
+                    "kernel_cmdline: root=/dev/sda1".to_string(),
+                ),
+                (
+                    9,
+                    EventType::Ipl,
+                    BootComponent::Kernel,
+                    "/boot/vmlinuz-6.8.0".to_string(),
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn parse_cases() {
+        let mut padded = boot_log(b"kernel", "root=/dev/sda1");
+        padded.extend_from_slice(&[0xFF; 64]);
+        let mut truncated = boot_log(b"kernel", "root=/dev/sda1");
+        truncated.truncate(truncated.len() - 3);
+        let mut legacy = boot_log(b"kernel", "root=/dev/sda1");
+        legacy[32 + 14] = b'0';
+
+        check_cases(
+            [
+                Case {
+                    scenario: "well-formed log",
+                    input: boot_log(b"kernel", "root=/dev/sda1"),
+                    expect: Yields(boot_log_summary()),
+                },
+                Case {
+                    scenario: "trailing 0xFF padding is ignored",
+                    input: padded,
+                    expect: Yields(boot_log_summary()),
+                },
+                Case {
+                    scenario: "truncated event is rejected",
+                    input: truncated,
+                    expect: Fails,
+                },
+                Case {
+                    scenario: "legacy SHA-1 log is rejected",
+                    input: legacy,
+                    expect: Fails,
+                },
+                Case {
+                    scenario: "empty input is rejected",
+                    input: Vec::new(),
+                    expect: Fails,
+                },
+            ],
+            |bytes| {
+                EventLog::parse(&bytes)
+                    .map(|log| ParsedSummary {
+                        banks: log.banks(),
+                        events: log
+                            .events
+                            .iter()
+                            .map(|event| {
+                                (
+                                    event.pcr_index,
+                                    event.event_type,
+                                    event.component(),
+                                    event.description(),
+                                )
+                            })
+                            .collect(),
+                    })
+                    .map_err(drop)
+            },
+        );
+    }
+
+    #[test]
+    fn verify_replay_cases() {
+        let log = EventLog::parse(&boot_log(b"kernel", "root=/dev/sda1")).unwrap();
+        let pcr0 = expected_pcr(&[b"1.2.3", &[0; 4]]);
+        let pcr4 = expected_pcr(&[b"shim", b"grub"]);
+        let pcr9 = expected_pcr(&[b"kernel"]);
+
+        check_values(
+            [
+                Check {
+                    scenario: "replay reproduces the quote",
+                    input: quoted(&[(0, pcr0.clone()), (4, pcr4.clone()), (9, pcr9.clone())]),
+                    expect: (true, vec![0, 4, 9], Vec::new()),
+                },
+                Check {
+                    scenario: "quoted value the log can't account for",
+                    input: quoted(&[(0, pcr0.clone()), (4, pcr9)]),
+                    expect: (false, vec![0], Vec::new()),
+                },
+                Check {
+                    scenario: "reset and OS-extended PCRs outside the log",
+                    input: quoted(&[(0, pcr0), (7, "0".repeat(64)), (10, pcr4)]),
+                    expect: (true, vec![0], vec![10]),
+                },
+            ],
+            |values| {
+                let verification = log.verify_replay(&values).unwrap();
+                assert_eq!(verification.algorithm, HashAlgorithm::Sha256);
+                (
+                    verification.is_consistent(),
+                    verification.matched,
+                    verification.unlogged,
+                )
+            },
+        );
+    }
+
+    #[test]
+    fn startup_locality_seeds_pcr0() {
+        let mut locality = STARTUP_LOCALITY_SIGNATURE.to_vec();
+        locality.push(3);
+        let log = EventLog::parse(
+            &LogBuilder::new()
+                .event(0, 0x0000_0003, b"", &locality)
+                .event(0, EV_SEPARATOR, &[0; 4], &[0; 4])
+                .build(),
+        )
+        .unwrap();
+
+        let mut seed = vec![0u8; 32];
+        seed[31] = 3;
+        let expected = HashAlgorithm::Sha256.extend(&seed, &sha2::Sha256::digest([0u8; 4]));
+        assert_eq!(
+            log.replay(HashAlgorithm::Sha256).unwrap().get(&0),
+            Some(&expected)
+        );
+    }
+
+    #[test]
+    fn explain_mismatch_cases() {
+        let good = EventLog::parse(&boot_log(b"kernel-a", "root=/dev/sda1")).unwrap();
+        let bad = EventLog::parse(&boot_log(b"kernel-b", "root=/dev/sda1 debug")).unwrap();
+        let bundle = quoted(&[
+            (4, expected_pcr(&[b"shim", b"grub"])),
+            (8, expected_pcr(&[b"kernel_cmdline: root=/dev/sda1"])),
+            (9, expected_pcr(&[b"kernel-a"])),
+        ]);
+        let report = quoted(&[
+            (4, expected_pcr(&[b"shim", b"grub"])),
+            (8, expected_pcr(&[b"kernel_cmdline: root=/dev/sda1 debug"])),
+            (9, expected_pcr(&[b"kernel-b"])),
+        ]);
+
+        check_values(
+            [
+                Check {
+                    scenario: "reference log pins the change to the event",
+                    input: Some(&good),
+                    expect: vec![
+                        (
+                            8,
+                            vec![(EventDiffKind::Changed, BootComponent::KernelCommandLine)],
+                            0,
+                        ),
+                        (9, vec![(EventDiffKind::Changed, BootComponent::Kernel)], 0),
+                    ],
+                },
+                Check {
+                    scenario: "without a reference log the contributing events are listed",
+                    input: None,
+                    expect: vec![(8, Vec::new(), 1), (9, Vec::new(), 1)],
+                },
+            ],
+            |reference| {
+                bad.explain_mismatch(&bundle, &report, reference)
+                    .unwrap()
+                    .into_iter()
+                    .map(|mismatch| {
+                        (
+                            mismatch.pcr_register,
+                            mismatch
+                                .diffs
+                                .iter()
+                                .map(|diff| (diff.kind, diff.component()))
+                                .collect::<Vec<_>>(),
+                            mismatch.events.len(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            },
+        );
+    }
+
+    #[test]
+    fn diff_aligns_inserted_and_removed_events() {
+        let reference = EventLog::parse(
+            &LogBuilder::new()
+                .event(
+                    4,
+                    EV_EFI_BOOT_SERVICES_APPLICATION,
+                    b"shim",
+                    &image_load_event("\\shim.efi"),
+                )
+                .event(
+                    4,
+                    EV_EFI_BOOT_SERVICES_APPLICATION,
+                    b"grub",
+                    &image_load_event("\\grub.efi"),
+                )
+                .build(),
+        )
+        .unwrap();
+        let actual = EventLog::parse(
+            &LogBuilder::new()
+                .event(
+                    4,
+                    EV_EFI_BOOT_SERVICES_APPLICATION,
+                    b"shim",
+                    &image_load_event("\\shim.efi"),
+                )
+                .event(
+                    4,
+                    EV_EFI_BOOT_SERVICES_APPLICATION,
+                    b"mok",
+                    &image_load_event("\\mm.efi"),
+                )
+                .event(
+                    4,
+                    EV_EFI_BOOT_SERVICES_APPLICATION,
+                    b"grub",
+                    &image_load_event("\\grub.efi"),
+                )
+                .build(),
+        )
+        .unwrap();
+
+        let diffs = actual.diff(&reference, HashAlgorithm::Sha256);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].kind, EventDiffKind::Added);
+        assert_eq!(
+            diffs[0].to_string(),
+            format!(
+                "PCR 4 bootloader added: \\mm.efi ({})",
+                hex::encode(sha2::Sha256::digest(b"mok"))
+            )
+        );
+        assert!(reference.diff(&reference, HashAlgorithm::Sha256).is_empty());
+    }
+}
@@ -19,6 +19,7 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 
 pub mod bundle;
+pub mod event_log;
 pub mod journal;
 pub mod machine;
 pub mod pcr;
@@ -33,6 +34,8 @@ pub enum Error {
     Parse(String),
     #[error("{0}")]
     RpcConversion(String),
+    #[error("event log: {0}")]
+    EventLog(String),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
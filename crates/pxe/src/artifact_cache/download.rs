@@ -0,0 +1,290 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::path::Path;
+
+use ::rpc::forge as rpc;
+use reqwest::header::{AUTHORIZATION, HeaderValue};
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
+
+use super::expected_sha256;
+use crate::metrics::ArtifactDownloadOutcome;
+
+/// A failed download: the bounded cause for the metric label, plus the
+/// detail for the log line.
+#[derive(Debug)]
+pub(super) struct DownloadError {
+    pub(super) outcome: ArtifactDownloadOutcome,
+    pub(super) message: String,
+}
+
+fn fail(outcome: ArtifactDownloadOutcome, message: impl Into<String>) -> DownloadError {
+    DownloadError {
+        outcome,
+        message: message.into(),
+    }
+}
+
+/// The URL as it may be logged: everything after `?` is dropped, so a
+/// presigned or tokenized artifact URL never lands its credentials in the
+/// log line.
+pub(super) fn loggable_url(url: &str) -> String {
+    url.split('?').next().unwrap_or(url).to_string()
+}
+
+/// Downloads `artifact` to `staged`, hashing as it streams, and verifies the
+/// result against the artifact's declared SHA-256 if it has one. Transfers
+/// that would exceed `max_bytes` are cut off early. On error `staged` is
+/// removed.
+pub(super) async fn download(
+    client: &reqwest::Client,
+    artifact: &rpc::IpxeTemplateArtifact,
+    staged: &Path,
+    max_bytes: u64,
+) -> Result<u64, DownloadError> {
+    let result = download_to(client, artifact, staged, max_bytes).await;
+    if result.is_err() {
+        tokio::fs::remove_file(staged).await.ok();
+    }
+    result
+}
+
+async fn download_to(
+    client: &reqwest::Client,
+    artifact: &rpc::IpxeTemplateArtifact,
+    staged: &Path,
+    max_bytes: u64,
+) -> Result<u64, DownloadError> {
+    let url = loggable_url(&artifact.url);
+    let request = authorize(client.get(&artifact.url), artifact)?;
+
+    let mut response = request.send().await.map_err(|err| {
+        fail(
+            ArtifactDownloadOutcome::Fetch,
+            format!("unable to fetch {url}: {err}"),
+        )
+    })?;
+    if !response.status().is_success() {
+        return Err(fail(
+            ArtifactDownloadOutcome::Status,
+            format!("fetching {url} returned {}", response.status()),
+        ));
+    }
+    if let Some(length) = response.content_length()
+        && length > max_bytes
+    {
+        return Err(fail(
+            ArtifactDownloadOutcome::TooLarge,
+            format!("{url} is {length} bytes, larger than the {max_bytes} byte cache"),
+        ));
+    }
+
+    let mut file = tokio::fs::File::create(staged).await.map_err(|err| {
+        fail(
+            ArtifactDownloadOutcome::Io,
+            format!("unable to create {}: {err}", staged.display()),
+        )
+    })?;
+    let mut hasher = Sha256::new();
+    let mut written: u64 = 0;
+    loop {
+        let chunk = response.chunk().await.map_err(|err| {
+            fail(
+                ArtifactDownloadOutcome::Transfer,
+                format!("transfer of {url} broke off: {err}"),
+            )
+        })?;
+        let Some(chunk) = chunk else {
+            break;
+        };
+        written += chunk.len() as u64;
+        if written > max_bytes {
+            return Err(fail(
+                ArtifactDownloadOutcome::TooLarge,
+                format!("{url} exceeded the {max_bytes} byte cache mid-transfer"),
+            ));
+        }
+        hasher.update(&chunk);
+        file.write_all(&chunk).await.map_err(|err| {
+            fail(
+                ArtifactDownloadOutcome::Io,
+                format!("unable to write {}: {err}", staged.display()),
+            )
+        })?;
+    }
+    file.sync_all().await.map_err(|err| {
+        fail(
+            ArtifactDownloadOutcome::Io,
+            format!("unable to flush {}: {err}", staged.display()),
+        )
+    })?;
+
+    if let Some(expected) = expected_sha256(artifact) {
+        let actual = hex::encode(hasher.finalize());
+        if actual != expected {
+            return Err(fail(
+                ArtifactDownloadOutcome::Checksum,
+                format!("checksum mismatch for {url}: expected {expected} downloaded {actual}"),
+            ));
+        }
+    }
+
+    Ok(written)
+}
+
+/// Applies the artifact's credentials. `Bearer` tokens are sent as-is;
+/// `Basic` tokens are the already-encoded `user:password` credentials.
+fn authorize(
+    request: reqwest::RequestBuilder,
+    artifact: &rpc::IpxeTemplateArtifact,
+) -> Result<reqwest::RequestBuilder, DownloadError> {
+    let auth_type = artifact
+        .auth_type
+        .as_deref()
+        .map(|auth_type| auth_type.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    let token = artifact.auth_token.as_deref().filter(|t| !t.is_empty());
+
+    match (auth_type.as_str(), token) {
+        ("" | "none", _) => Ok(request),
+        ("bearer", Some(token)) => Ok(request.bearer_auth(token)),
+        ("basic", Some(token)) => {
+            let mut value = HeaderValue::from_str(&format!("Basic {token}")).map_err(|_| {
+                fail(
+                    ArtifactDownloadOutcome::Fetch,
+                    "basic auth token is not a valid header value",
+                )
+            })?;
+            value.set_sensitive(true);
+            Ok(request.header(AUTHORIZATION, value))
+        }
+        ("bearer" | "basic", None) => Err(fail(
+            ArtifactDownloadOutcome::Fetch,
+            format!("auth type {auth_type} needs an auth token"),
+        )),
+        (other, _) => Err(fail(
+            ArtifactDownloadOutcome::Fetch,
+            format!("unsupported auth type {other}"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::Router;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::get;
+
+    use super::*;
+
+    const BODY: &[u8] = b"test";
+    const BODY_SHA: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    /// Serves `BODY` at `/vmlinuz` to requests carrying `Bearer secret`.
+    async fn serve() -> String {
+        async fn vmlinuz(headers: HeaderMap) -> Result<&'static [u8], StatusCode> {
+            match headers.get("authorization") {
+                Some(value) if value == "Bearer secret" => Ok(BODY),
+                _ => Err(StatusCode::UNAUTHORIZED),
+            }
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, Router::new().route("/vmlinuz", get(vmlinuz)))
+                .await
+                .unwrap();
+        });
+        format!("http://{addr}/vmlinuz")
+    }
+
+    fn artifact(url: &str, sha: &str, token: Option<&str>) -> rpc::IpxeTemplateArtifact {
+        rpc::IpxeTemplateArtifact {
+            name: "kernel".to_string(),
+            url: url.to_string(),
+            sha: Some(sha.to_string()),
+            auth_type: token.map(|_| "Bearer".to_string()),
+            auth_token: token.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn downloads_and_verifies_authorized_artifacts() {
+        let url = serve().await;
+        let dir = tempfile::tempdir().unwrap();
+        let staged = dir.path().join("kernel.download");
+        let client = reqwest::Client::new();
+
+        let size = download(
+            &client,
+            &artifact(&url, BODY_SHA, Some("secret")),
+            &staged,
+            1024,
+        )
+        .await
+        .unwrap();
+        assert_eq!(size, BODY.len() as u64);
+        assert_eq!(std::fs::read(&staged).unwrap(), BODY);
+    }
+
+    #[tokio::test]
+    async fn failed_downloads_report_their_cause_and_leave_nothing_behind() {
+        let url = serve().await;
+        let dir = tempfile::tempdir().unwrap();
+        let staged = dir.path().join("kernel.download");
+        let client = reqwest::Client::new();
+        let wrong_sha = "0".repeat(64);
+
+        for (scenario, artifact, max_bytes, outcome) in [
+            (
+                "missing credentials",
+                artifact(&url, BODY_SHA, None),
+                1024,
+                ArtifactDownloadOutcome::Status,
+            ),
+            (
+                "checksum mismatch",
+                artifact(&url, &wrong_sha, Some("secret")),
+                1024,
+                ArtifactDownloadOutcome::Checksum,
+            ),
+            (
+                "larger than the cache",
+                artifact(&url, BODY_SHA, Some("secret")),
+                2,
+                ArtifactDownloadOutcome::TooLarge,
+            ),
+        ] {
+            let err = download(&client, &artifact, &staged, max_bytes)
+                .await
+                .unwrap_err();
+            assert_eq!(err.outcome, outcome, "{scenario}: {}", err.message);
+            assert!(!staged.exists(), "{scenario}: staged file left behind");
+        }
+    }
+
+    #[test]
+    fn loggable_url_drops_the_query() {
+        assert_eq!(
+            loggable_url("https://images.example.com/vmlinuz?X-Amz-Signature=secret"),
+            "https://images.example.com/vmlinuz"
+        );
+    }
+}
@@ -0,0 +1,148 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Built-in cache for the remote artifacts (kernels, initrds, ISOs, ...) of
+//! templated iPXE operating systems.
+//!
+//! The sync loop periodically asks carbide-api for every OS definition's
+//! cachable artifacts, downloads the `CACHE_AS_NEEDED` and `CACHED_ONLY` ones
+//! into the [`ArtifactStore`], and reports the resulting `cached_url` back
+//! through `UpdateOperatingSystemCachableIpxeTemplateArtifacts`. The
+//! artifacts are served from `/api/v0/artifacts/{key}` by
+//! [`crate::routes::artifacts`].
+
+use ::rpc::forge as rpc;
+use sha2::{Digest, Sha256};
+
+mod download;
+mod store;
+mod sync;
+
+pub(crate) use store::ArtifactStore;
+pub(crate) use sync::spawn_sync_loop;
+
+/// Where cached artifacts are served from, relative to `static_pxe_url`.
+pub(crate) const ROUTE_PREFIX: &str = "/api/v0/artifacts";
+
+/// The artifact's expected SHA-256 as lowercase hex, if it declares a usable
+/// one. An optional `sha256:` prefix is accepted, matching how OS image
+/// digests are written.
+pub(crate) fn expected_sha256(artifact: &rpc::IpxeTemplateArtifact) -> Option<String> {
+    let sha = artifact.sha.as_deref()?.trim();
+    let sha = sha.strip_prefix("sha256:").unwrap_or(sha);
+    let sha = sha.to_ascii_lowercase();
+    is_cache_key(&sha).then_some(sha)
+}
+
+/// The name an artifact is stored and served under: its SHA-256 when it
+/// declares one, so OS definitions sharing a kernel share one file, and
+/// otherwise a hash of its name and URL -- the same rule
+/// `fabricate_cached_urls` in ipxe-renderer uses.
+pub(crate) fn cache_key(artifact: &rpc::IpxeTemplateArtifact) -> String {
+    expected_sha256(artifact).unwrap_or_else(|| {
+        let mut hasher = Sha256::new();
+        hasher.update(artifact.name.as_bytes());
+        hasher.update(artifact.url.as_bytes());
+        hex::encode(hasher.finalize())
+    })
+}
+
+/// Whether `key` has the shape of a cache key (64 lowercase hex digits).
+/// Doubles as path validation for the serving route.
+pub(crate) fn is_cache_key(key: &str) -> bool {
+    key.len() == 64
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+/// The URL carbide-api hands to booting machines for a cached artifact.
+pub(crate) fn cached_url(static_pxe_url: &str, key: &str) -> String {
+    format!(
+        "{}{ROUTE_PREFIX}/{key}",
+        static_pxe_url.trim_end_matches('/')
+    )
+}
+
+/// Whether the artifact is one the cache should hold.
+fn is_cachable(artifact: &rpc::IpxeTemplateArtifact) -> bool {
+    matches!(
+        artifact.cache_strategy(),
+        rpc::IpxeTemplateArtifactCacheStrategy::CacheAsNeeded
+            | rpc::IpxeTemplateArtifactCacheStrategy::CachedOnly
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::value_scenarios;
+
+    use super::*;
+
+    fn artifact(sha: Option<&str>) -> rpc::IpxeTemplateArtifact {
+        rpc::IpxeTemplateArtifact {
+            name: "kernel".to_string(),
+            url: "https://images.example.com/vmlinuz".to_string(),
+            sha: sha.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    const SHA: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+    /// SHA-256 of "kernel" followed by "https://images.example.com/vmlinuz".
+    const NAME_AND_URL_HASH: &str =
+        "9986612fce35e4734911bff813e5cefb600d2b0b322499cac0c40f24c12af53e";
+
+    #[test]
+    fn cache_key_prefers_the_declared_sha() {
+        value_scenarios!(
+            run = |sha: Option<&'static str>| cache_key(&artifact(sha));
+            "declared sha" {
+                Some(SHA) => SHA.to_string(),
+                Some("sha256:9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08") => SHA.to_string(),
+            }
+            "no usable sha falls back to name and url" {
+                None => NAME_AND_URL_HASH.to_string(),
+                Some("md5:d41d8cd98f00b204e9800998ecf8427e") => NAME_AND_URL_HASH.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn cache_keys_are_path_safe() {
+        value_scenarios!(
+            run = is_cache_key;
+            "valid" {
+                SHA => true,
+            }
+            "invalid" {
+                "" => false,
+                "../etc/passwd" => false,
+                "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08" => false,
+                "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a0" => false,
+            }
+        );
+    }
+
+    #[test]
+    fn cached_url_joins_the_static_pxe_url() {
+        assert_eq!(
+            cached_url("http://carbide-pxe.forge/", SHA),
+            format!("http://carbide-pxe.forge/api/v0/artifacts/{SHA}")
+        );
+    }
+}
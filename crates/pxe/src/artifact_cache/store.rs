@@ -0,0 +1,383 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use super::is_cache_key;
+
+/// Suffix for files still being downloaded. They live next to the finished
+/// artifacts so publishing is a same-filesystem rename, and they are never
+/// indexed or served.
+const STAGING_SUFFIX: &str = ".download";
+
+/// The on-disk half of the artifact cache: one file per cache key in a flat
+/// directory, plus an in-memory index that tracks sizes and last access for
+/// LRU eviction.
+///
+/// Clones share one index. Access times are only kept in memory; after a
+/// restart the index is seeded from file modification times, so the order
+/// falls back to download order until artifacts are served again.
+#[derive(Clone, Debug)]
+pub(crate) struct ArtifactStore {
+    directory: PathBuf,
+    max_bytes: u64,
+    index: Arc<Mutex<StoreIndex>>,
+}
+
+#[derive(Debug, Default)]
+struct StoreIndex {
+    entries: HashMap<String, StoreEntry>,
+    total_bytes: u64,
+}
+
+#[derive(Debug)]
+struct StoreEntry {
+    size: u64,
+    last_access: SystemTime,
+}
+
+/// Why a downloaded artifact could not be kept.
+#[derive(Debug, PartialEq)]
+pub(crate) enum CommitError {
+    /// The artifact alone is larger than the cache.
+    TooLarge {
+        size: u64,
+        max_bytes: u64,
+    },
+    /// Evicting every unpinned artifact still would not make room.
+    NoRoom {
+        size: u64,
+        pinned_bytes: u64,
+    },
+    Io(String),
+}
+
+impl std::fmt::Display for CommitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooLarge { size, max_bytes } => write!(
+                f,
+                "artifact is {size} bytes, larger than the {max_bytes} byte cache"
+            ),
+            Self::NoRoom { size, pinned_bytes } => write!(
+                f,
+                "no room for a {size} byte artifact: {pinned_bytes} bytes are pinned by CACHED_ONLY artifacts"
+            ),
+            Self::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl ArtifactStore {
+    /// Opens (creating if needed) the cache directory, discards staging files
+    /// left by an interrupted download, and indexes the artifacts already on
+    /// disk. Anything over `max_bytes` is trimmed on the next commit.
+    pub(crate) fn open(directory: impl Into<PathBuf>, max_bytes: u64) -> std::io::Result<Self> {
+        let directory = directory.into();
+        std::fs::create_dir_all(&directory)?;
+
+        let mut index = StoreIndex::default();
+        for entry in std::fs::read_dir(&directory)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+            if name.ends_with(STAGING_SUFFIX) {
+                if let Err(err) = std::fs::remove_file(entry.path()) {
+                    tracing::warn!(
+                        file = %entry.path().display(),
+                        error = %err,
+                        "unable to remove stale artifact download"
+                    );
+                }
+                continue;
+            }
+            if !is_cache_key(&name) {
+                continue;
+            }
+            index.total_bytes += metadata.len();
+            index.entries.insert(
+                name,
+                StoreEntry {
+                    size: metadata.len(),
+                    last_access: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                },
+            );
+        }
+
+        tracing::info!(
+            directory = %directory.display(),
+            artifacts = index.entries.len(),
+            bytes = index.total_bytes,
+            max_bytes,
+            "opened boot artifact cache"
+        );
+
+        Ok(Self {
+            directory,
+            max_bytes,
+            index: Arc::new(Mutex::new(index)),
+        })
+    }
+
+    pub(crate) fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// Where a finished artifact lives.
+    pub(crate) fn path(&self, key: &str) -> PathBuf {
+        self.directory.join(key)
+    }
+
+    /// Where an artifact is written while it downloads.
+    pub(crate) fn staging_path(&self, key: &str) -> PathBuf {
+        self.directory.join(format!("{key}{STAGING_SUFFIX}"))
+    }
+
+    pub(crate) fn contains(&self, key: &str) -> bool {
+        self.index.lock().unwrap().entries.contains_key(key)
+    }
+
+    /// Returns the artifact's path for serving and marks it as just used.
+    pub(crate) fn lookup(&self, key: &str) -> Option<PathBuf> {
+        let mut index = self.index.lock().unwrap();
+        let entry = index.entries.get_mut(key)?;
+        entry.last_access = SystemTime::now();
+        Some(self.path(key))
+    }
+
+    /// Publishes a verified download under `key` and evicts least recently
+    /// used artifacts until the cache fits in `max_bytes` again. Keys in
+    /// `pinned` are never evicted. Returns the evicted keys.
+    ///
+    /// On error the staged file is removed and the cache is left as it was.
+    pub(crate) fn commit(
+        &self,
+        key: &str,
+        staged: &Path,
+        pinned: &HashSet<String>,
+    ) -> Result<Vec<String>, CommitError> {
+        let size = match std::fs::metadata(staged) {
+            Ok(metadata) => metadata.len(),
+            Err(err) => {
+                std::fs::remove_file(staged).ok();
+                return Err(CommitError::Io(format!(
+                    "unable to stat {}: {err}",
+                    staged.display()
+                )));
+            }
+        };
+
+        let mut index = self.index.lock().unwrap();
+
+        let victims = match plan_eviction(&index, key, size, self.max_bytes, pinned) {
+            Ok(victims) => victims,
+            Err(err) => {
+                std::fs::remove_file(staged).ok();
+                return Err(err);
+            }
+        };
+
+        if let Err(err) = std::fs::rename(staged, self.path(key)) {
+            std::fs::remove_file(staged).ok();
+            return Err(CommitError::Io(format!(
+                "unable to rename {} into place: {err}",
+                staged.display()
+            )));
+        }
+
+        if let Some(previous) = index.entries.insert(
+            key.to_string(),
+            StoreEntry {
+                size,
+                last_access: SystemTime::now(),
+            },
+        ) {
+            index.total_bytes -= previous.size;
+        }
+        index.total_bytes += size;
+
+        for victim in &victims {
+            if let Some(entry) = index.entries.remove(victim) {
+                index.total_bytes -= entry.size;
+            }
+            if let Err(err) = std::fs::remove_file(self.path(victim)) {
+                tracing::warn!(
+                    key = %victim,
+                    error = %err,
+                    "unable to remove evicted artifact"
+                );
+            }
+        }
+
+        Ok(victims)
+    }
+}
+
+/// Picks the least recently used unpinned artifacts to drop so an artifact
+/// of `size` bytes fits under `max_bytes`. `key` itself never counts as a
+/// victim: if it is already cached, its old size is simply replaced.
+fn plan_eviction(
+    index: &StoreIndex,
+    key: &str,
+    size: u64,
+    max_bytes: u64,
+    pinned: &HashSet<String>,
+) -> Result<Vec<String>, CommitError> {
+    if size > max_bytes {
+        return Err(CommitError::TooLarge { size, max_bytes });
+    }
+
+    let mut candidates: Vec<_> = index
+        .entries
+        .iter()
+        .filter(|(candidate, _)| candidate.as_str() != key)
+        .collect();
+    let mut used: u64 = candidates.iter().map(|(_, entry)| entry.size).sum();
+
+    candidates.retain(|(candidate, _)| !pinned.contains(*candidate));
+    candidates.sort_by_key(|(candidate, entry)| (entry.last_access, (*candidate).clone()));
+
+    let mut victims = Vec::new();
+    for (candidate, entry) in candidates {
+        if used + size <= max_bytes {
+            break;
+        }
+        used -= entry.size;
+        victims.push(candidate.clone());
+    }
+
+    if used + size > max_bytes {
+        return Err(CommitError::NoRoom {
+            size,
+            pinned_bytes: used,
+        });
+    }
+
+    Ok(victims)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn key(c: char) -> String {
+        std::iter::repeat_n(c, 64).collect()
+    }
+
+    fn stage(store: &ArtifactStore, key: &str, size: usize) -> PathBuf {
+        let staged = store.staging_path(key);
+        std::fs::write(&staged, vec![0u8; size]).unwrap();
+        staged
+    }
+
+    fn commit(store: &ArtifactStore, key: &str, size: usize, pinned: &[&str]) -> Vec<String> {
+        let staged = stage(store, key, size);
+        let pinned = pinned.iter().map(|k| k.to_string()).collect();
+        store.commit(key, &staged, &pinned).unwrap()
+    }
+
+    #[test]
+    fn evicts_least_recently_used_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ArtifactStore::open(dir.path(), 30).unwrap();
+        let (a, b, c, d) = (key('a'), key('b'), key('c'), key('d'));
+
+        assert!(commit(&store, &a, 10, &[]).is_empty());
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(commit(&store, &b, 10, &[]).is_empty());
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(commit(&store, &c, 10, &[]).is_empty());
+        std::thread::sleep(Duration::from_millis(5));
+
+        // Serving `a` makes `b` the oldest.
+        assert!(store.lookup(&a).is_some());
+        assert_eq!(commit(&store, &d, 10, &[]), vec![b.clone()]);
+
+        assert!(store.contains(&a));
+        assert!(!store.contains(&b));
+        assert!(!store.path(&b).exists());
+        assert!(store.path(&d).exists());
+    }
+
+    #[test]
+    fn pinned_artifacts_are_never_evicted() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ArtifactStore::open(dir.path(), 20).unwrap();
+        let (a, b, c) = (key('a'), key('b'), key('c'));
+
+        commit(&store, &a, 10, &[]);
+        std::thread::sleep(Duration::from_millis(5));
+        commit(&store, &b, 10, &[]);
+
+        assert_eq!(commit(&store, &c, 10, &[&a]), vec![b.clone()]);
+        assert!(store.contains(&a));
+
+        let staged = stage(&store, &b, 10);
+        let pinned = [a.clone(), c.clone()].into_iter().collect();
+        assert_eq!(
+            store.commit(&b, &staged, &pinned),
+            Err(CommitError::NoRoom {
+                size: 10,
+                pinned_bytes: 20
+            })
+        );
+        assert!(!staged.exists());
+        assert!(!store.contains(&b));
+    }
+
+    #[test]
+    fn rejects_artifacts_larger_than_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ArtifactStore::open(dir.path(), 5).unwrap();
+        let a = key('a');
+
+        let staged = stage(&store, &a, 10);
+        assert_eq!(
+            store.commit(&a, &staged, &HashSet::new()),
+            Err(CommitError::TooLarge {
+                size: 10,
+                max_bytes: 5
+            })
+        );
+        assert!(!staged.exists());
+        assert!(!store.contains(&a));
+    }
+
+    #[test]
+    fn reopening_indexes_artifacts_and_drops_staging_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ArtifactStore::open(dir.path(), 100).unwrap();
+        let (a, b) = (key('a'), key('b'));
+        commit(&store, &a, 10, &[]);
+        stage(&store, &b, 10);
+        std::fs::write(dir.path().join("not-an-artifact"), b"x").unwrap();
+
+        let reopened = ArtifactStore::open(dir.path(), 100).unwrap();
+        assert!(reopened.contains(&a));
+        assert!(!reopened.contains(&b));
+        assert!(!reopened.staging_path(&b).exists());
+        assert!(dir.path().join("not-an-artifact").exists());
+    }
+}
@@ -0,0 +1,409 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{BTreeMap, HashSet};
+use std::time::Instant;
+
+use ::rpc::forge as rpc;
+use ::rpc::forge_tls_client::{self, ApiConfig, ForgeClientConfig};
+use carbide_instrument::emit;
+use carbide_uuid::operating_system::OperatingSystemId;
+use forge_tls::client_config::ClientCert;
+use tokio::time::MissedTickBehavior;
+
+use super::download::{DownloadError, download, loggable_url};
+use super::store::CommitError;
+use super::{ArtifactStore, cache_key, cached_url, is_cachable};
+use crate::config::RuntimeConfig;
+use crate::metrics::{ArtifactDownloadFinished, ArtifactDownloadOutcome, ArtifactEvicted};
+
+/// Starts the loop that keeps the cache and carbide-api's `cached_url`s in
+/// step: every `artifact_cache_sync_interval` it downloads whatever cachable
+/// artifacts are missing and reports each OS definition's `cached_url`s
+/// (set, or cleared after eviction) back to the API.
+pub(crate) fn spawn_sync_loop(store: ArtifactStore, config: RuntimeConfig) {
+    tokio::spawn(async move {
+        let http = reqwest::Client::new();
+        let mut interval = tokio::time::interval(config.artifact_cache_sync_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(err) = sync_once(&store, &http, &config).await {
+                tracing::warn!(error = %err, "boot artifact cache sync failed");
+            }
+        }
+    });
+}
+
+/// Every OS definition's artifacts, as carbide-api returned them.
+type Catalog = Vec<(OperatingSystemId, Vec<rpc::IpxeTemplateArtifact>)>;
+
+async fn sync_once(
+    store: &ArtifactStore,
+    http: &reqwest::Client,
+    config: &RuntimeConfig,
+) -> Result<(), String> {
+    let client_config = ForgeClientConfig::new(
+        config.forge_root_ca_path.clone(),
+        Some(ClientCert {
+            cert_path: config.server_cert_path.clone(),
+            key_path: config.server_key_path.clone(),
+        }),
+    );
+    let api_config = ApiConfig::new(&config.internal_api_url, &client_config);
+    let mut api = forge_tls_client::ForgeTlsClient::retry_build(&api_config)
+        .await
+        .map_err(|err| format!("unable to connect to carbide-api: {err}"))?;
+
+    let ids = api
+        .find_operating_system_ids(rpc::OperatingSystemSearchFilter::default())
+        .await
+        .map_err(|status| format!("unable to list operating systems: {status}"))?
+        .into_inner()
+        .ids;
+
+    let mut catalog = Catalog::new();
+    for id in ids {
+        match api
+            .get_operating_system_cachable_ipxe_template_artifacts(
+                rpc::GetOperatingSystemCachableIpxeTemplateArtifactsRequest { id: Some(id) },
+            )
+            .await
+        {
+            Ok(response) => catalog.push((id, response.into_inner().artifacts)),
+            // Deleted since it was listed.
+            Err(status) if status.code() == tonic::Code::NotFound => {}
+            Err(status) => {
+                tracing::warn!(
+                    operating_system_id = %id,
+                    error = %status,
+                    "unable to fetch operating system artifacts"
+                );
+            }
+        }
+    }
+
+    let own_prefix = cached_url(&config.static_pxe_url, "");
+    let wanted = wanted_artifacts(&catalog, &own_prefix);
+    let pinned: HashSet<String> = wanted
+        .iter()
+        .filter(|(_, (_, pinned))| *pinned)
+        .map(|(key, _)| key.clone())
+        .collect();
+
+    // CACHED_ONLY artifacts first: their OS definitions cannot boot at all
+    // until they are cached.
+    let mut missing: Vec<_> = wanted
+        .iter()
+        .filter(|(key, _)| !store.contains(key))
+        .collect();
+    missing.sort_by_key(|(_, (_, pinned))| !*pinned);
+    for (key, (artifact, _)) in missing {
+        fetch(store, http, key, artifact, &pinned).await;
+    }
+
+    for (id, artifacts) in &catalog {
+        let Some(updates) = plan_updates(
+            artifacts,
+            &own_prefix,
+            |key| store.contains(key),
+            |key| cached_url(&config.static_pxe_url, key),
+        ) else {
+            continue;
+        };
+        if let Err(status) = api
+            .update_operating_system_cachable_ipxe_template_artifacts(
+                rpc::UpdateOperatingSystemIpxeTemplateArtifactRequest {
+                    id: Some(*id),
+                    updates,
+                },
+            )
+            .await
+        {
+            tracing::warn!(
+                operating_system_id = %id,
+                error = %status,
+                "unable to report cached artifact URLs"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Downloads one artifact into the store, records the attempt, and reports
+/// whatever the commit evicted.
+async fn fetch(
+    store: &ArtifactStore,
+    http: &reqwest::Client,
+    key: &str,
+    artifact: &rpc::IpxeTemplateArtifact,
+    pinned: &HashSet<String>,
+) {
+    let started = Instant::now();
+    let staged = store.staging_path(key);
+    let result = match download(http, artifact, &staged, store.max_bytes()).await {
+        Ok(_) => store
+            .commit(key, &staged, pinned)
+            .map_err(|err| DownloadError {
+                outcome: match err {
+                    CommitError::TooLarge { .. } | CommitError::NoRoom { .. } => {
+                        ArtifactDownloadOutcome::TooLarge
+                    }
+                    CommitError::Io(_) => ArtifactDownloadOutcome::Io,
+                },
+                message: err.to_string(),
+            }),
+        Err(err) => Err(err),
+    };
+
+    let outcome = match &result {
+        Ok(_) => ArtifactDownloadOutcome::Ok,
+        Err(err) => err.outcome,
+    };
+    emit(ArtifactDownloadFinished {
+        outcome,
+        took: started.elapsed(),
+    });
+
+    match result {
+        Ok(evicted) => {
+            tracing::info!(
+                key,
+                name = %artifact.name,
+                url = %loggable_url(&artifact.url),
+                "cached boot artifact"
+            );
+            for key in evicted {
+                emit(ArtifactEvicted { key });
+            }
+        }
+        Err(err) => {
+            tracing::warn!(
+                key,
+                name = %artifact.name,
+                error = %err.message,
+                "unable to cache boot artifact"
+            );
+        }
+    }
+}
+
+/// The artifacts the cache should hold, by cache key, with whether any OS
+/// definition needs it as `CACHED_ONLY` (which pins it against eviction).
+/// Artifacts whose `cached_url` points somewhere other than this cache
+/// belong to another cacher and are left alone.
+fn wanted_artifacts<'a>(
+    catalog: &'a Catalog,
+    own_prefix: &str,
+) -> BTreeMap<String, (&'a rpc::IpxeTemplateArtifact, bool)> {
+    let mut wanted = BTreeMap::new();
+    for artifact in catalog.iter().flat_map(|(_, artifacts)| artifacts) {
+        if !is_cachable(artifact) || !is_ours(artifact, own_prefix) {
+            continue;
+        }
+        let pinned =
+            artifact.cache_strategy() == rpc::IpxeTemplateArtifactCacheStrategy::CachedOnly;
+        wanted
+            .entry(cache_key(artifact))
+            .and_modify(|(_, already_pinned)| *already_pinned |= pinned)
+            .or_insert((artifact, pinned));
+    }
+    wanted
+}
+
+fn is_ours(artifact: &rpc::IpxeTemplateArtifact, own_prefix: &str) -> bool {
+    match artifact.cached_url.as_deref() {
+        None | Some("") => true,
+        Some(url) => url.starts_with(own_prefix),
+    }
+}
+
+/// The `cached_url` updates that bring one OS definition in line with the
+/// cache, or `None` when it already is.
+///
+/// carbide-api matches updates to artifacts by name, in order, so when
+/// anything changes every artifact gets an entry: ours with the URL the
+/// cache can serve (or none), the rest with their current value.
+fn plan_updates(
+    artifacts: &[rpc::IpxeTemplateArtifact],
+    own_prefix: &str,
+    is_cached: impl Fn(&str) -> bool,
+    url_for: impl Fn(&str) -> String,
+) -> Option<Vec<rpc::IpxeTemplateArtifactUpdateRequest>> {
+    let mut changed = false;
+    let updates = artifacts
+        .iter()
+        .map(|artifact| {
+            let current = artifact.cached_url.clone().filter(|url| !url.is_empty());
+            let cached_url = if is_cachable(artifact) && is_ours(artifact, own_prefix) {
+                let key = cache_key(artifact);
+                is_cached(&key).then(|| url_for(&key))
+            } else {
+                current.clone()
+            };
+            changed |= cached_url != current;
+            rpc::IpxeTemplateArtifactUpdateRequest {
+                name: artifact.name.clone(),
+                cached_url,
+            }
+        })
+        .collect();
+    changed.then_some(updates)
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::value_scenarios;
+
+    use super::*;
+
+    const OWN_PREFIX: &str = "http://carbide-pxe.forge/api/v0/artifacts/";
+    const KERNEL_SHA: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+    const INITRD_SHA: &str = "60303ae22b998861bce3b28f33eec1be758a213c86c93c076dbe9f558c11c752";
+
+    fn artifact(
+        name: &str,
+        sha: &str,
+        strategy: rpc::IpxeTemplateArtifactCacheStrategy,
+        cached_url: Option<&str>,
+    ) -> rpc::IpxeTemplateArtifact {
+        rpc::IpxeTemplateArtifact {
+            name: name.to_string(),
+            url: format!("https://images.example.com/{name}"),
+            sha: Some(sha.to_string()),
+            cache_strategy: strategy as i32,
+            cached_url: cached_url.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn own_url(key: &str) -> String {
+        format!("{OWN_PREFIX}{key}")
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    enum Case {
+        /// Kernel is cached but the API does not know yet.
+        NewlyCached,
+        /// The API already has the right URL.
+        InSync,
+        /// Kernel was evicted; its URL must be cleared.
+        Evicted,
+        /// Kernel's URL was set by some other cacher.
+        ForeignUrl,
+        /// Remote-only artifacts are never touched.
+        RemoteOnly,
+    }
+
+    fn updates_for(case: Case) -> Option<Vec<(String, Option<String>)>> {
+        use rpc::IpxeTemplateArtifactCacheStrategy::*;
+
+        let (kernel, kernel_cached) = match case {
+            Case::NewlyCached => (artifact("kernel", KERNEL_SHA, CachedOnly, None), true),
+            Case::InSync => (
+                artifact("kernel", KERNEL_SHA, CachedOnly, Some(&own_url(KERNEL_SHA))),
+                true,
+            ),
+            Case::Evicted => (
+                artifact(
+                    "kernel",
+                    KERNEL_SHA,
+                    CacheAsNeeded,
+                    Some(&own_url(KERNEL_SHA)),
+                ),
+                false,
+            ),
+            Case::ForeignUrl => (
+                artifact(
+                    "kernel",
+                    KERNEL_SHA,
+                    CacheAsNeeded,
+                    Some("http://mirror.example.com/vmlinuz"),
+                ),
+                true,
+            ),
+            Case::RemoteOnly => (artifact("kernel", KERNEL_SHA, RemoteOnly, None), true),
+        };
+        let artifacts = vec![
+            kernel,
+            artifact("initrd", INITRD_SHA, LocalOnly, Some("http://local/initrd")),
+        ];
+
+        plan_updates(
+            &artifacts,
+            OWN_PREFIX,
+            |key| key == KERNEL_SHA && kernel_cached,
+            own_url,
+        )
+        .map(|updates| {
+            updates
+                .into_iter()
+                .map(|update| (update.name, update.cached_url))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn plans_cached_url_updates() {
+        let initrd = (
+            "initrd".to_string(),
+            Some("http://local/initrd".to_string()),
+        );
+
+        value_scenarios!(
+            run = updates_for;
+            "reports changes with every artifact in order" {
+                Case::NewlyCached => Some(vec![
+                    ("kernel".to_string(), Some(own_url(KERNEL_SHA))),
+                    initrd.clone(),
+                ]),
+                Case::Evicted => Some(vec![("kernel".to_string(), None), initrd.clone()]),
+            }
+            "leaves everything else alone" {
+                Case::InSync => None,
+                Case::ForeignUrl => None,
+                Case::RemoteOnly => None,
+            }
+        );
+    }
+
+    #[test]
+    fn wanted_artifacts_dedupe_by_key_and_pin_cached_only() {
+        use rpc::IpxeTemplateArtifactCacheStrategy::*;
+
+        let catalog: Catalog = vec![
+            (
+                OperatingSystemId::from(uuid::Uuid::new_v4()),
+                vec![
+                    artifact("kernel", KERNEL_SHA, CacheAsNeeded, None),
+                    artifact("initrd", INITRD_SHA, RemoteOnly, None),
+                ],
+            ),
+            (
+                OperatingSystemId::from(uuid::Uuid::new_v4()),
+                vec![artifact("vmlinuz", KERNEL_SHA, CachedOnly, None)],
+            ),
+        ];
+
+        let wanted: Vec<_> = wanted_artifacts(&catalog, OWN_PREFIX)
+            .into_iter()
+            .map(|(key, (_, pinned))| (key, pinned))
+            .collect();
+        assert_eq!(wanted, vec![(KERNEL_SHA.to_string(), true)]);
+    }
+}
@@ -22,6 +22,7 @@ use rpc::forge::CloudInitInstructions;
 use serde::{Deserialize, Serialize};
 use tera::Tera;
 
+use crate::artifact_cache::ArtifactStore;
 use crate::config::RuntimeConfig;
 use crate::extractors::machine_architecture;
 // use crate::middleware::metrics::RequestMetrics;
@@ -54,6 +55,8 @@ pub(crate) struct AppState {
     /// framework's events record; `/metrics` renders it alongside the
     /// `metrics-exporter-prometheus` recorder above.
     pub(super) otel_registry: prometheus::Registry,
+    /// The boot artifact cache, when `artifact_cache_directory` is configured.
+    pub(super) artifact_store: Option<ArtifactStore>,
 }
 
 /// An [`AppState`] for handler tests: an empty template engine, a local
@@ -76,8 +79,12 @@ pub(crate) fn test_app_state() -> AppState {
             bind_address: "0.0.0.0".parse().unwrap(),
             bind_port: 8080,
             template_directory: String::new(),
+            artifact_cache_directory: None,
+            artifact_cache_max_bytes: 0,
+            artifact_cache_sync_interval: std::time::Duration::from_secs(60),
         },
         prometheus_handle: PrometheusBuilder::new().build_recorder().handle(),
         otel_registry: prometheus::Registry::new(),
+        artifact_store: None,
     }
 }
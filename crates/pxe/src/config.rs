@@ -16,6 +16,11 @@
  */
 use std::env;
 use std::net::IpAddr;
+use std::time::Duration;
+
+/// Default ceiling for the boot artifact cache: 100 GiB.
+const DEFAULT_ARTIFACT_CACHE_MAX_BYTES: u64 = 100 * 1024 * 1024 * 1024;
+const DEFAULT_ARTIFACT_CACHE_SYNC_INTERVAL_SECONDS: u64 = 60;
 
 #[derive(Clone, Debug)]
 pub(crate) struct RuntimeConfig {
@@ -32,6 +37,15 @@ pub(crate) struct RuntimeConfig {
     pub(super) bind_address: IpAddr,
     pub(super) bind_port: u16,
     pub(super) template_directory: String,
+    /// Where the built-in boot artifact cache keeps its files. The cache, its
+    /// download route and its sync loop are all off when this is unset.
+    pub(super) artifact_cache_directory: Option<String>,
+    /// Size ceiling for the cache directory; least recently served artifacts
+    /// are evicted to stay under it.
+    pub(super) artifact_cache_max_bytes: u64,
+    /// How often the cache asks carbide-api for cachable artifacts and
+    /// reports `cached_url` changes back.
+    pub(super) artifact_cache_sync_interval: Duration,
 }
 
 impl RuntimeConfig {
@@ -70,6 +84,29 @@ impl RuntimeConfig {
                 .map_err(|_| "not a parsable bind port for runtime config?".to_string())?,
             template_directory: env::var("CARBIDE_PXE_TEMPLATE_DIRECTORY")
                 .unwrap_or_else(|_| "/opt/carbide/pxe/templates".to_string()),
+            artifact_cache_directory: env::var("CARBIDE_PXE_ARTIFACT_CACHE_DIRECTORY")
+                .ok()
+                .filter(|path| !path.is_empty()),
+            artifact_cache_max_bytes: match env::var("CARBIDE_PXE_ARTIFACT_CACHE_MAX_BYTES") {
+                Ok(value) => value.parse::<u64>().map_err(|_| {
+                    "not a parsable artifact cache size for runtime config?".to_string()
+                })?,
+                Err(_) => DEFAULT_ARTIFACT_CACHE_MAX_BYTES,
+            },
+            artifact_cache_sync_interval: match env::var(
+                "CARBIDE_PXE_ARTIFACT_CACHE_SYNC_INTERVAL_SECONDS",
+            ) {
+                Ok(value) => value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|seconds| *seconds > 0)
+                    .map(Duration::from_secs)
+                    .ok_or_else(|| {
+                        "not a parsable artifact cache sync interval for runtime config?"
+                            .to_string()
+                    })?,
+                Err(_) => Duration::from_secs(DEFAULT_ARTIFACT_CACHE_SYNC_INTERVAL_SECONDS),
+            },
         };
 
         Ok(this)
@@ -99,6 +136,9 @@ mod tests {
         "PXE_BIND_ADDRESS",
         "PXE_BIND_PORT",
         "CARBIDE_PXE_TEMPLATE_DIRECTORY",
+        "CARBIDE_PXE_ARTIFACT_CACHE_DIRECTORY",
+        "CARBIDE_PXE_ARTIFACT_CACHE_MAX_BYTES",
+        "CARBIDE_PXE_ARTIFACT_CACHE_SYNC_INTERVAL_SECONDS",
     ];
 
     #[derive(Debug)]
@@ -119,6 +159,9 @@ mod tests {
         bind_address: IpAddr,
         bind_port: u16,
         template_directory: String,
+        artifact_cache_directory: Option<String>,
+        artifact_cache_max_bytes: u64,
+        artifact_cache_sync_interval: Duration,
     }
 
     struct EnvSnapshot {
@@ -177,6 +220,9 @@ mod tests {
             bind_address: config.bind_address,
             bind_port: config.bind_port,
             template_directory: config.template_directory,
+            artifact_cache_directory: config.artifact_cache_directory,
+            artifact_cache_max_bytes: config.artifact_cache_max_bytes,
+            artifact_cache_sync_interval: config.artifact_cache_sync_interval,
         }
     }
 
@@ -198,6 +244,10 @@ mod tests {
             "Could not extract FORGE_CLIENT_KEY_PATH from environment" => "missing-client-key",
             "not a parsable bind address for runtime config?" => "bad-bind-address",
             "not a parsable bind port for runtime config?" => "bad-bind-port",
+            "not a parsable artifact cache size for runtime config?" => "bad-artifact-cache-size",
+            "not a parsable artifact cache sync interval for runtime config?" => {
+                "bad-artifact-cache-sync-interval"
+            }
             other => panic!("unmapped runtime config error: {other:?}"),
         }
     }
@@ -228,6 +278,9 @@ mod tests {
                     bind_address: "0.0.0.0".parse().unwrap(),
                     bind_port: 8080,
                     template_directory: "/opt/carbide/pxe/templates".to_string(),
+                    artifact_cache_directory: None,
+                    artifact_cache_max_bytes: DEFAULT_ARTIFACT_CACHE_MAX_BYTES,
+                    artifact_cache_sync_interval: Duration::from_secs(60),
                 }),
 
                 ConfigEnv {
@@ -249,6 +302,9 @@ mod tests {
                     bind_address: "0.0.0.0".parse().unwrap(),
                     bind_port: 8080,
                     template_directory: "/opt/carbide/pxe/templates".to_string(),
+                    artifact_cache_directory: None,
+                    artifact_cache_max_bytes: DEFAULT_ARTIFACT_CACHE_MAX_BYTES,
+                    artifact_cache_sync_interval: Duration::from_secs(60),
                 }),
             }
 
@@ -266,6 +322,9 @@ mod tests {
                         ("PXE_BIND_ADDRESS", "127.0.0.1"),
                         ("PXE_BIND_PORT", "9090"),
                         ("CARBIDE_PXE_TEMPLATE_DIRECTORY", "/templates"),
+                        ("CARBIDE_PXE_ARTIFACT_CACHE_DIRECTORY", "/var/cache/pxe"),
+                        ("CARBIDE_PXE_ARTIFACT_CACHE_MAX_BYTES", "1048576"),
+                        ("CARBIDE_PXE_ARTIFACT_CACHE_SYNC_INTERVAL_SECONDS", "15"),
                     ],
                 } => Yields(RuntimeConfigSummary {
                     internal_api_url: "https://internal.example.com".to_string(),
@@ -279,6 +338,9 @@ mod tests {
                     bind_address: "127.0.0.1".parse().unwrap(),
                     bind_port: 9090,
                     template_directory: "/templates".to_string(),
+                    artifact_cache_directory: Some("/var/cache/pxe".to_string()),
+                    artifact_cache_max_bytes: 1048576,
+                    artifact_cache_sync_interval: Duration::from_secs(15),
                 }),
             }
 
@@ -313,6 +375,25 @@ mod tests {
                     ],
                 } => FailsWith("bad-bind-port"),
             }
+
+            "invalid artifact cache settings" {
+                ConfigEnv {
+                    vars: &[
+                        ("FORGE_ROOT_CAFILE_PATH", "/certs/root.pem"),
+                        ("FORGE_CLIENT_CERT_PATH", "/certs/client.pem"),
+                        ("FORGE_CLIENT_KEY_PATH", "/certs/client.key"),
+                        ("CARBIDE_PXE_ARTIFACT_CACHE_MAX_BYTES", "10G"),
+                    ],
+                } => FailsWith("bad-artifact-cache-size"),
+                ConfigEnv {
+                    vars: &[
+                        ("FORGE_ROOT_CAFILE_PATH", "/certs/root.pem"),
+                        ("FORGE_CLIENT_CERT_PATH", "/certs/client.pem"),
+                        ("FORGE_CLIENT_KEY_PATH", "/certs/client.key"),
+                        ("CARBIDE_PXE_ARTIFACT_CACHE_SYNC_INTERVAL_SECONDS", "0"),
+                    ],
+                } => FailsWith("bad-artifact-cache-sync-interval"),
+            }
         );
     }
 }
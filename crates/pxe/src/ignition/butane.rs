@@ -0,0 +1,327 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Butane to Ignition translation for the `fcos` and `flatcar` variants.
+//!
+//! Butane is Ignition with snake_case keys, a `variant`/`version` header,
+//! and some sugar. The structural part translates mechanically; inline
+//! resource contents become data URLs. Sugar that needs local files
+//! (`local`, `trees`) or expands into generated units (`boot_device`,
+//! `with_mount_unit`, `grub`) is rejected: tenants who need it can compile
+//! with `butane` themselves and store the Ignition config instead.
+
+use base64::Engine as _;
+use serde_json::{Map, Value};
+
+/// Butane keys carbide-pxe does not translate, with the reason given to
+/// the tenant.
+const UNSUPPORTED_KEYS: &[(&str, &str)] = &[
+    ("local", "references a local file"),
+    ("contents_local", "references a local file"),
+    ("trees", "references a local directory"),
+    (
+        "boot_device",
+        "is sugar that expands into partitions and units",
+    ),
+    ("with_mount_unit", "is sugar that expands into mount units"),
+    ("grub", "is sugar that expands into files"),
+];
+
+/// The Ignition spec version each supported Butane variant and version
+/// compiles to.
+fn ignition_version(variant: &str, version: &str) -> Option<&'static str> {
+    match (variant, version) {
+        ("fcos", "1.0.0") => Some("3.0.0"),
+        ("fcos", "1.1.0") => Some("3.1.0"),
+        ("fcos", "1.2.0" | "1.3.0") => Some("3.2.0"),
+        ("fcos", "1.4.0") => Some("3.3.0"),
+        ("fcos", "1.5.0") => Some("3.4.0"),
+        ("fcos", "1.6.0") => Some("3.5.0"),
+        ("flatcar", "1.0.0") => Some("3.3.0"),
+        ("flatcar", "1.1.0") => Some("3.4.0"),
+        _ => None,
+    }
+}
+
+/// Translates a Butane YAML document into an Ignition config.
+pub(super) fn translate(butane: &str) -> Result<Value, String> {
+    let document: Value =
+        serde_yaml::from_str(butane).map_err(|err| format!("invalid Butane YAML: {err}"))?;
+    let Value::Object(mut document) = document else {
+        return Err("a Butane config must be a YAML mapping".to_string());
+    };
+
+    let header = |document: &mut Map<String, Value>, key: &str| match document.remove(key) {
+        Some(Value::String(value)) => Ok(value),
+        Some(_) => Err(format!("Butane `{key}` must be a string")),
+        None => Err(format!("Butane config is missing `{key}`")),
+    };
+    let variant = header(&mut document, "variant")?;
+    let version = header(&mut document, "version")?;
+    let spec_version = ignition_version(&variant, &version)
+        .ok_or_else(|| format!("unsupported Butane variant and version: {variant} {version}"))?;
+
+    let Value::Object(mut config) = translate_value(Value::Object(document), "")? else {
+        unreachable!("translating a mapping yields a mapping");
+    };
+    let ignition = config
+        .entry("ignition")
+        .or_insert_with(|| Value::Object(Map::new()));
+    let Value::Object(ignition) = ignition else {
+        return Err("Butane `ignition` must be a mapping".to_string());
+    };
+    ignition.insert(
+        "version".to_string(),
+        Value::String(spec_version.to_string()),
+    );
+
+    Ok(Value::Object(config))
+}
+
+fn translate_value(value: Value, path: &str) -> Result<Value, String> {
+    match value {
+        Value::Object(object) => translate_object(object, path).map(Value::Object),
+        Value::Array(items) => items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| translate_value(item, &format!("{path}[{i}]")))
+            .collect::<Result<_, _>>()
+            .map(Value::Array),
+        scalar => Ok(scalar),
+    }
+}
+
+fn translate_object(object: Map<String, Value>, path: &str) -> Result<Map<String, Value>, String> {
+    let mut translated = Map::new();
+    for (key, value) in object {
+        let key_path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+        if let Some((_, reason)) = UNSUPPORTED_KEYS.iter().find(|(k, _)| *k == key) {
+            return Err(format!(
+                "{key_path} {reason}, which carbide-pxe cannot translate; compile the config with butane and store it as Ignition"
+            ));
+        }
+
+        if key == "inline" {
+            let Value::String(contents) = value else {
+                return Err(format!("{key_path} must be a string"));
+            };
+            if translated.contains_key("source") {
+                return Err(format!("{path} sets both inline and source"));
+            }
+            translated.insert("source".to_string(), Value::String(data_url(&contents)));
+            continue;
+        }
+        if key == "source" && translated.contains_key("source") {
+            return Err(format!("{path} sets both inline and source"));
+        }
+
+        if key == "mode" {
+            translated.insert(key, file_mode(value, &key_path)?);
+            continue;
+        }
+
+        translated.insert(camel_case(&key), translate_value(value, &key_path)?);
+    }
+    Ok(translated)
+}
+
+/// Butane reads `mode: 0644` as octal, the way YAML 1.1 did. serde_yaml
+/// follows YAML 1.2 and hands back the string `"0644"`, so leading-zero
+/// modes are converted here. `0o644` and plain decimal already arrive as
+/// numbers.
+fn file_mode(value: Value, path: &str) -> Result<Value, String> {
+    match value {
+        Value::String(mode) if mode.starts_with('0') => u32::from_str_radix(&mode, 8)
+            .map(Value::from)
+            .map_err(|_| format!("{path} is not an octal file mode: {mode}")),
+        Value::Number(mode) => Ok(Value::Number(mode)),
+        _ => Err(format!("{path} must be a number")),
+    }
+}
+
+/// Encodes inline contents as the data URL Ignition expects in `source`.
+pub(super) fn data_url(contents: &str) -> String {
+    format!(
+        "data:;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(contents)
+    )
+}
+
+/// Converts a Butane key to its Ignition spelling: `ssh_authorized_keys`
+/// becomes `sshAuthorizedKeys`, and the `mib` unit suffix becomes `MiB`.
+fn camel_case(key: &str) -> String {
+    let mut parts = key.split('_');
+    let mut camel = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        if part == "mib" {
+            camel.push_str("MiB");
+            continue;
+        }
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.push_str(chars.as_str());
+        }
+    }
+    camel
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::value_scenarios;
+    use serde_json::json;
+
+    use super::*;
+
+    fn translate_err(butane: &str) -> String {
+        translate(butane).unwrap_err()
+    }
+
+    #[test]
+    fn butane_keys_become_ignition_keys() {
+        value_scenarios!(
+            run = camel_case;
+            "keys" {
+                "ssh_authorized_keys" => "sshAuthorizedKeys".to_string(),
+                "size_mib" => "sizeMiB".to_string(),
+                "start_mib" => "startMiB".to_string(),
+                "type_guid" => "typeGuid".to_string(),
+                "name" => "name".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn translates_a_flatcar_config() {
+        let butane = r#"
+variant: flatcar
+version: 1.1.0
+passwd:
+  users:
+    - name: core
+      ssh_authorized_keys:
+        - ssh-ed25519 AAAA tenant
+storage:
+  files:
+    - path: /etc/motd
+      mode: 0644
+      contents:
+        inline: hello
+systemd:
+  units:
+    - name: app.service
+      enabled: true
+      contents: |
+        [Service]
+        ExecStart=/usr/bin/true
+"#;
+        assert_eq!(
+            translate(butane).unwrap(),
+            json!({
+                "ignition": {"version": "3.4.0"},
+                "passwd": {"users": [{
+                    "name": "core",
+                    "sshAuthorizedKeys": ["ssh-ed25519 AAAA tenant"],
+                }]},
+                "storage": {"files": [{
+                    "path": "/etc/motd",
+                    "mode": 420,
+                    "contents": {"source": "data:;base64,aGVsbG8="},
+                }]},
+                "systemd": {"units": [{
+                    "name": "app.service",
+                    "enabled": true,
+                    "contents": "[Service]\nExecStart=/usr/bin/true\n",
+                }]},
+            })
+        );
+    }
+
+    #[test]
+    fn leading_zero_modes_are_octal() {
+        value_scenarios!(
+            run = |mode: Value| file_mode(mode, "mode");
+            "octal strings" {
+                json!("0644") => Ok(json!(420)),
+                json!("0755") => Ok(json!(493)),
+                json!("0999") => Err("mode is not an octal file mode: 0999".to_string()),
+            }
+            "numbers" {
+                json!(420) => Ok(json!(420)),
+            }
+            "other values" {
+                json!(true) => Err("mode must be a number".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn keeps_ignition_settings_and_sets_the_spec_version() {
+        let butane = r#"
+variant: fcos
+version: 1.5.0
+ignition:
+  config:
+    merge:
+      - inline: '{"ignition":{"version":"3.4.0"}}'
+"#;
+        assert_eq!(
+            translate(butane).unwrap(),
+            json!({
+                "ignition": {
+                    "version": "3.4.0",
+                    "config": {"merge": [{
+                        "source": data_url(r#"{"ignition":{"version":"3.4.0"}}"#),
+                    }]},
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_configs_it_cannot_translate() {
+        assert_eq!(
+            translate_err("variant: fcos\nversion: 9.9.9\n"),
+            "unsupported Butane variant and version: fcos 9.9.9"
+        );
+        assert_eq!(
+            translate_err("version: 1.0.0\n"),
+            "Butane config is missing `variant`"
+        );
+        assert!(
+            translate_err(
+                "variant: flatcar\nversion: 1.0.0\nstorage:\n  files:\n    - path: /a\n      contents:\n        local: a.txt\n"
+            )
+            .starts_with("storage.files[0].contents.local references a local file")
+        );
+        assert!(
+            translate_err("variant: fcos\nversion: 1.4.0\nboot_device:\n  mirror: {}\n")
+                .starts_with("boot_device is sugar")
+        );
+        assert_eq!(
+            translate_err(
+                "variant: fcos\nversion: 1.4.0\nstorage:\n  files:\n    - path: /a\n      contents:\n        source: https://example.com/a\n        inline: a\n"
+            ),
+            "storage.files[0].contents sets both inline and source"
+        );
+        assert!(translate_err("- not a mapping\n").starts_with("a Butane config must be"));
+    }
+}
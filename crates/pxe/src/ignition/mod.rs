@@ -0,0 +1,307 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Renders the Ignition config served to Flatcar and Fedora CoreOS
+//! instances: the tenant's Ignition or Butane user data, with the
+//! network and SSH key fragments NICo provides merged in.
+
+use rpc::forge::UserDataFormat;
+use serde_json::{Map, Value, json};
+
+mod butane;
+
+/// Spec version of the config served when the OS definition declares an
+/// Ignition format but has no user data. 3.3.0 is the oldest spec every
+/// supported Flatcar and Fedora CoreOS release accepts.
+const DEFAULT_SPEC_VERSION: &str = "3.3.0";
+
+/// The user every Flatcar and Fedora CoreOS image ships with, and the one
+/// tenant SSH keys are installed for.
+const DEFAULT_USER: &str = "core";
+
+/// Where the default network fragment is written.
+const NETWORK_FRAGMENT_PATH: &str = "/etc/systemd/network/50-nico-dhcp.network";
+
+/// The Ignition counterpart of the cloud-init routes' default
+/// network-config: DHCP on every interface under both the predictable
+/// ("en*") and legacy ("eth*") naming conventions, so multi-NIC hosts come
+/// up with working networking on every port.
+const NETWORK_FRAGMENT: &str = "[Match]
+Name=en* eth*
+
+[Network]
+DHCP=yes
+";
+
+/// Directories whose files mean the tenant configures networking
+/// themselves, in which case the default network fragment is left out.
+const TENANT_NETWORK_DIRS: &[&str] = &[
+    "/etc/systemd/network/",
+    "/etc/NetworkManager/system-connections/",
+];
+
+/// Renders the Ignition config for `user_data` in `format`, merged with
+/// the NICo fragments. Fails on cloud-init user data, on Ignition configs
+/// that are not spec 3, and on Butane the translator does not handle.
+pub(crate) fn render(
+    user_data: Option<&str>,
+    format: UserDataFormat,
+    ssh_authorized_keys: &[String],
+) -> Result<Value, String> {
+    let user_data = user_data.filter(|user_data| !user_data.trim().is_empty());
+    let mut config = match (format, user_data) {
+        (UserDataFormat::CloudInit, _) => {
+            return Err("the operating system's user data is cloud-init, not Ignition".to_string());
+        }
+        (_, None) => json!({ "ignition": { "version": DEFAULT_SPEC_VERSION } }),
+        (UserDataFormat::Ignition, Some(user_data)) => parse_ignition(user_data)?,
+        (UserDataFormat::Butane, Some(user_data)) => butane::translate(user_data)?,
+    };
+
+    merge_network_fragment(&mut config)?;
+    merge_ssh_authorized_keys(&mut config, ssh_authorized_keys)?;
+    Ok(config)
+}
+
+fn parse_ignition(user_data: &str) -> Result<Value, String> {
+    let config: Value =
+        serde_json::from_str(user_data).map_err(|err| format!("invalid Ignition JSON: {err}"))?;
+    let version = config
+        .pointer("/ignition/version")
+        .and_then(Value::as_str)
+        .ok_or("Ignition config is missing ignition.version")?;
+    if !version.starts_with("3.") {
+        return Err(format!(
+            "Ignition spec {version} is not supported; use spec 3"
+        ));
+    }
+    Ok(config)
+}
+
+/// Returns the object at `key` in `object`, creating an empty one if it is
+/// missing. `path` names the field in errors.
+fn object_field<'a>(
+    object: &'a mut Map<String, Value>,
+    key: &str,
+    path: &str,
+) -> Result<&'a mut Map<String, Value>, String> {
+    object
+        .entry(key)
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .ok_or_else(|| format!("Ignition {path} must be an object"))
+}
+
+/// Returns the array at `key` in `object`, creating an empty one if it is
+/// missing. `path` names the field in errors.
+fn array_field<'a>(
+    object: &'a mut Map<String, Value>,
+    key: &str,
+    path: &str,
+) -> Result<&'a mut Vec<Value>, String> {
+    object
+        .entry(key)
+        .or_insert_with(|| Value::Array(vec![]))
+        .as_array_mut()
+        .ok_or_else(|| format!("Ignition {path} must be an array"))
+}
+
+fn root(config: &mut Value) -> Result<&mut Map<String, Value>, String> {
+    config
+        .as_object_mut()
+        .ok_or_else(|| "an Ignition config must be a JSON object".to_string())
+}
+
+/// Adds the default DHCP network file unless the tenant's config already
+/// writes networkd or NetworkManager configuration.
+fn merge_network_fragment(config: &mut Value) -> Result<(), String> {
+    let storage = object_field(root(config)?, "storage", "storage")?;
+    let files = array_field(storage, "files", "storage.files")?;
+
+    let tenant_configures_network = files.iter().any(|file| {
+        file.get("path")
+            .and_then(Value::as_str)
+            .is_some_and(|path| TENANT_NETWORK_DIRS.iter().any(|dir| path.starts_with(dir)))
+    });
+    if !tenant_configures_network {
+        files.push(json!({
+            "path": NETWORK_FRAGMENT_PATH,
+            "mode": 0o644,
+            "contents": { "source": butane::data_url(NETWORK_FRAGMENT) },
+        }));
+    }
+    Ok(())
+}
+
+/// Adds the instance's tenant SSH keys to the `core` user, creating the
+/// user entry if the tenant's config does not have one. Keys the config
+/// already lists are not repeated.
+fn merge_ssh_authorized_keys(config: &mut Value, keys: &[String]) -> Result<(), String> {
+    if keys.is_empty() {
+        return Ok(());
+    }
+    let passwd = object_field(root(config)?, "passwd", "passwd")?;
+    let users = array_field(passwd, "users", "passwd.users")?;
+
+    let position = users
+        .iter()
+        .position(|user| user.get("name").and_then(Value::as_str) == Some(DEFAULT_USER));
+    let user = match position {
+        Some(position) => &mut users[position],
+        None => {
+            users.push(json!({ "name": DEFAULT_USER }));
+            users.last_mut().expect("just pushed")
+        }
+    };
+    let user = user
+        .as_object_mut()
+        .ok_or("Ignition passwd.users entries must be objects")?;
+    let authorized_keys = array_field(user, "sshAuthorizedKeys", "passwd.users.sshAuthorizedKeys")?;
+
+    for key in keys {
+        if !authorized_keys
+            .iter()
+            .any(|existing| existing == key.as_str())
+        {
+            authorized_keys.push(Value::String(key.clone()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::value_scenarios;
+
+    use super::*;
+
+    const KEY: &str = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAINICo tenant@example";
+
+    fn network_fragment() -> Value {
+        json!({
+            "path": NETWORK_FRAGMENT_PATH,
+            "mode": 420,
+            "contents": { "source": butane::data_url(NETWORK_FRAGMENT) },
+        })
+    }
+
+    #[test]
+    fn renders_ignition_user_data_with_nico_fragments() {
+        let user_data = json!({
+            "ignition": { "version": "3.4.0" },
+            "passwd": { "users": [
+                { "name": "core", "sshAuthorizedKeys": [KEY] },
+                { "name": "ops" },
+            ]},
+            "storage": { "files": [{ "path": "/etc/motd" }] },
+        })
+        .to_string();
+        let other_key = "ssh-rsa AAAAB3NzaC1yc2E other".to_string();
+
+        assert_eq!(
+            render(
+                Some(&user_data),
+                UserDataFormat::Ignition,
+                &[KEY.to_string(), other_key.clone()],
+            )
+            .unwrap(),
+            json!({
+                "ignition": { "version": "3.4.0" },
+                "passwd": { "users": [
+                    { "name": "core", "sshAuthorizedKeys": [KEY, other_key] },
+                    { "name": "ops" },
+                ]},
+                "storage": { "files": [{ "path": "/etc/motd" }, network_fragment()] },
+            })
+        );
+    }
+
+    #[test]
+    fn renders_butane_user_data_with_nico_fragments() {
+        let user_data = "variant: fcos\nversion: 1.4.0\n";
+        assert_eq!(
+            render(Some(user_data), UserDataFormat::Butane, &[KEY.to_string()]).unwrap(),
+            json!({
+                "ignition": { "version": "3.3.0" },
+                "passwd": { "users": [{ "name": "core", "sshAuthorizedKeys": [KEY] }] },
+                "storage": { "files": [network_fragment()] },
+            })
+        );
+    }
+
+    #[test]
+    fn tenant_network_files_replace_the_default_fragment() {
+        value_scenarios!(
+            run = |path: &str| {
+                let user_data = json!({
+                    "ignition": { "version": "3.3.0" },
+                    "storage": { "files": [{ "path": path }] },
+                })
+                .to_string();
+                let config = render(Some(&user_data), UserDataFormat::Ignition, &[]).unwrap();
+                config["storage"]["files"].as_array().unwrap().len()
+            };
+            "tenant configures networking" {
+                "/etc/systemd/network/10-static.network" => 1,
+                "/etc/NetworkManager/system-connections/eth0.nmconnection" => 1,
+            }
+            "tenant leaves networking alone" {
+                "/etc/hostname" => 2,
+            }
+        );
+    }
+
+    #[test]
+    fn renders_a_base_config_without_user_data() {
+        value_scenarios!(
+            run = |user_data: Option<&str>| render(user_data, UserDataFormat::Butane, &[]);
+            "no user data" {
+                None => Ok(json!({
+                    "ignition": { "version": DEFAULT_SPEC_VERSION },
+                    "storage": { "files": [network_fragment()] },
+                })),
+                Some("  \n") => Ok(json!({
+                    "ignition": { "version": DEFAULT_SPEC_VERSION },
+                    "storage": { "files": [network_fragment()] },
+                })),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_user_data_it_cannot_serve() {
+        value_scenarios!(
+            run = |(user_data, format): (&str, UserDataFormat)| render(Some(user_data), format, &[]);
+            "not Ignition" {
+                ("#cloud-config\n", UserDataFormat::CloudInit) => Err(
+                    "the operating system's user data is cloud-init, not Ignition".to_string()
+                ),
+            }
+            "bad Ignition" {
+                (r#"{"ignition":{"version":"2.2.0"}}"#, UserDataFormat::Ignition) => Err(
+                    "Ignition spec 2.2.0 is not supported; use spec 3".to_string()
+                ),
+                ("{}", UserDataFormat::Ignition) => Err(
+                    "Ignition config is missing ignition.version".to_string()
+                ),
+                (r#"{"ignition":{"version":"3.3.0"},"storage":[]}"#, UserDataFormat::Ignition) => Err(
+                    "Ignition storage must be an object".to_string()
+                ),
+            }
+        );
+    }
+}
@@ -34,9 +34,11 @@ use tracing_subscriber::EnvFilter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 
+mod artifact_cache;
 mod common;
 mod config;
 mod extractors;
+mod ignition;
 mod metrics;
 mod middleware;
 mod routes;
@@ -101,11 +103,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .expect("unable to build templating engine?");
     let socket_addr = SocketAddr::new(runtime_config.bind_address, runtime_config.bind_port);
 
+    let artifact_store = match &runtime_config.artifact_cache_directory {
+        Some(directory) => {
+            let store = artifact_cache::ArtifactStore::open(
+                directory,
+                runtime_config.artifact_cache_max_bytes,
+            )
+            .expect("unable to open boot artifact cache?");
+            artifact_cache::spawn_sync_loop(store.clone(), runtime_config.clone());
+            Some(store)
+        }
+        None => None,
+    };
+
     let app_state = AppState {
         engine: Engine::from(tera),
         runtime_config,
         prometheus_handle,
         otel_registry: otel_metrics.registry.clone(),
+        artifact_store,
     };
 
     let app = Router::new()
@@ -127,7 +143,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // we'd have to see if it's actually worthwhile in a real load test scenario
         .merge(routes::ipxe::get_router("/api/v0/pxe"))
         .merge(routes::cloud_init::get_router("/api/v0/cloud-init"))
+        .merge(routes::ignition::get_router("/api/v0/ignition"))
         .merge(routes::tls::get_router("/api/v0/tls"))
+        .merge(routes::artifacts::get_router(artifact_cache::ROUTE_PREFIX))
         .route_layer(axum::middleware::from_fn(middleware::logging::logger))
         .layer(map_response(middleware::fix_content_length_header))
         .layer(middleware::metrics::MetricLayer::default())
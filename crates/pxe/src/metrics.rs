@@ -65,19 +65,23 @@ pub(crate) fn setup_prometheus() -> PrometheusHandle {
 }
 
 /// The boot-path endpoint an outcome describes, as a bounded metric label:
-/// the two iPXE script routes plus the cloud-init route family
-/// (user-data, meta-data, vendor-data).
+/// the two iPXE script routes, the cloud-init route family (user-data,
+/// meta-data, vendor-data), and the Ignition config route.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, LabelValue)]
 pub(crate) enum BootEndpoint {
     Whoami,
     Boot,
     CloudInit,
+    Ignition,
 }
 
-/// How a boot-path request resolved, as a bounded metric label. Every
-/// non-`Ok` variant is a response the machine receives as an error script
-/// or generic error template over HTTP 200 -- this label is what makes
-/// those outcomes visible, since the status-code metrics cannot see them.
+/// How a boot-path request resolved, as a bounded metric label. On the
+/// iPXE and cloud-init routes every non-`Ok` variant is a response the
+/// machine receives as an error script or generic error template over
+/// HTTP 200 -- this label is what makes those outcomes visible, since the
+/// status-code metrics cannot see them. The Ignition route has no error
+/// document to fall back to and answers with a real 4xx/5xx, but still
+/// records its reason here so the label pair stays comparable per endpoint.
 /// Requests rejected before a handler runs (a malformed `buildarch`, an
 /// upstream failure inside the `Machine` extractor) return real 4xx codes
 /// the `http_*` metrics already count; only `architecture_not_found` is
@@ -127,7 +131,7 @@ pub(crate) struct PxeBootOutcome {
     pub(super) reason: OutcomeReason,
 }
 
-// Both failure Events write the same counter as `PxeBootOutcome`. Keep the
+// The failure Events write the same counter as `PxeBootOutcome`. Keep the
 // metric kind, description, and label keys identical so OpenTelemetry sees
 // one instrument while each route keeps its existing message.
 
@@ -167,6 +171,78 @@ pub(crate) struct PxeCustomIpxeFetchFailed {
     pub(super) error: String,
 }
 
+/// `PxeIgnitionRequestFailed` records an Ignition config request answered
+/// with an HTTP error instead of a config.
+#[derive(Event)]
+#[event(
+    event_name = "pxe_ignition_request_failed",
+    metric_family = PxeBootOutcomes,
+    log = error,
+    message = "ignition config could not be served"
+)]
+pub(crate) struct PxeIgnitionRequestFailed {
+    #[label]
+    pub(super) endpoint: BootEndpoint,
+    #[label]
+    pub(super) reason: OutcomeReason,
+    #[context]
+    pub(super) error: String,
+}
+
+/// How a boot artifact cache download ended, as a bounded metric label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, LabelValue)]
+pub(crate) enum ArtifactDownloadOutcome {
+    /// Downloaded, verified, and committed to the cache.
+    Ok,
+    /// The request never produced a response, or the artifact's auth type
+    /// is not one the cache can send.
+    Fetch,
+    /// The server answered, but with a non-success HTTP status.
+    Status,
+    /// The response body broke off mid-transfer.
+    Transfer,
+    /// The artifact does not fit in the cache, even after eviction.
+    TooLarge,
+    /// The downloaded artifact failed SHA-256 verification.
+    Checksum,
+    /// Local filesystem trouble writing or publishing the artifact.
+    Io,
+}
+
+/// One boot artifact download by the artifact cache. Metric-only: the sync
+/// loop logs each attempt itself, at a level that depends on the outcome.
+#[derive(Event)]
+#[event(
+    event_name = "pxe_artifact_download_finished",
+    metric_name = "carbide_pxe_artifact_download_duration_seconds",
+    component = "carbide-pxe",
+    log = off,
+    metric = histogram,
+    describe = "Duration of boot artifact cache downloads, by outcome; the _count series, split by outcome, is the download and failure rate."
+)]
+pub(crate) struct ArtifactDownloadFinished {
+    #[label]
+    pub(crate) outcome: ArtifactDownloadOutcome,
+    #[observation]
+    pub(crate) took: Duration,
+}
+
+/// The artifact cache dropped an artifact to stay under its size limit.
+#[derive(Event)]
+#[event(
+    event_name = "pxe_artifact_evicted",
+    metric_name = "carbide_pxe_artifact_cache_evictions_total",
+    component = "carbide-pxe",
+    log = info,
+    metric = counter,
+    message = "evicted boot artifact from cache",
+    describe = "Number of boot artifacts evicted from the carbide-pxe artifact cache to stay under its size limit."
+)]
+pub(crate) struct ArtifactEvicted {
+    #[context]
+    pub(crate) key: String,
+}
+
 #[cfg(test)]
 mod tests {
     use carbide_instrument::emit;
@@ -198,6 +274,11 @@ mod tests {
                     input: BootEndpoint::CloudInit.label_value(),
                     expect: "cloud_init".to_string(),
                 },
+                Check {
+                    scenario: "ignition endpoint",
+                    input: BootEndpoint::Ignition.label_value(),
+                    expect: "ignition".to_string(),
+                },
                 Check {
                     scenario: "ok",
                     input: OutcomeReason::Ok.label_value(),
@@ -233,6 +314,11 @@ mod tests {
                     input: OutcomeReason::MetadataNotFound.label_value(),
                     expect: "metadata_not_found".to_string(),
                 },
+                Check {
+                    scenario: "artifact too large",
+                    input: ArtifactDownloadOutcome::TooLarge.label_value(),
+                    expect: "too_large".to_string(),
+                },
             ],
             |value| value.to_string(),
         );
@@ -301,6 +387,7 @@ mod tests {
     enum FailureEvent {
         CloudInit,
         CustomIpxe,
+        Ignition,
     }
 
     #[derive(Debug, PartialEq)]
@@ -353,6 +440,21 @@ mod tests {
                         counter_delta: 1.0,
                     },
                 },
+                Check {
+                    scenario: "ignition config error",
+                    input: FailureEvent::Ignition,
+                    expect: FailureRecord {
+                        metadata_name: "pxe_ignition_request_failed".to_string(),
+                        level: tracing::Level::ERROR,
+                        message: "ignition config could not be served".to_string(),
+                        event_name: Some("pxe_ignition_request_failed".to_string()),
+                        metric_name: Some(BOOT_OUTCOMES_METRIC.to_string()),
+                        endpoint: Some("ignition".to_string()),
+                        reason: Some("instructions_invalid".to_string()),
+                        error: Some("invalid Ignition JSON".to_string()),
+                        counter_delta: 1.0,
+                    },
+                },
             ],
             |failure| {
                 let metrics = MetricsCapture::start();
@@ -381,6 +483,18 @@ mod tests {
                         });
                         (endpoint, reason, logs)
                     }
+                    FailureEvent::Ignition => {
+                        let endpoint = BootEndpoint::Ignition;
+                        let reason = OutcomeReason::InstructionsInvalid;
+                        let logs = capture_logs(|| {
+                            emit(PxeIgnitionRequestFailed {
+                                endpoint,
+                                reason,
+                                error: "invalid Ignition JSON".to_string(),
+                            });
+                        });
+                        (endpoint, reason, logs)
+                    }
                 };
 
                 assert_eq!(logs.len(), 1, "one emit must produce one log record");
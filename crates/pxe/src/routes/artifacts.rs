@@ -0,0 +1,142 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use axum::Router;
+use axum::body::Body;
+use axum::extract::{Path, Request, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use tower_http::services::ServeFile;
+
+use crate::artifact_cache::is_cache_key;
+use crate::common::AppState;
+
+/// Serves a cached boot artifact. `ServeFile` handles `Range` and
+/// conditional requests, so interrupted iPXE and BMC virtual-media transfers
+/// can resume. Serving an artifact also marks it as recently used.
+async fn artifact(
+    Path(key): Path<String>,
+    headers: HeaderMap,
+    state: State<AppState>,
+) -> impl IntoResponse {
+    let Some(path) = state
+        .artifact_store
+        .as_ref()
+        .filter(|_| is_cache_key(&key))
+        .and_then(|store| store.lookup(&key))
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let mut req = Request::new(Body::empty());
+    *req.headers_mut() = headers;
+
+    match ServeFile::new_with_mime(&path, &mime::APPLICATION_OCTET_STREAM)
+        .try_call(req)
+        .await
+    {
+        Ok(response) => response.into_response(),
+        Err(err) => {
+            tracing::error!(error = %err, key, "error reading cached artifact");
+            Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("error reading cached artifact?"))
+                .unwrap()
+                .into_response()
+        }
+    }
+}
+
+pub(crate) fn get_router(path_prefix: &str) -> Router<AppState> {
+    Router::new().route(format!("{path_prefix}/{{key}}").as_str(), get(artifact))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use axum::body::to_bytes;
+    use axum::http::{Request, header};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::artifact_cache::ArtifactStore;
+    use crate::common::test_app_state;
+
+    const KEY: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    async fn get_artifact(
+        state: AppState,
+        key: &str,
+        range: Option<&str>,
+    ) -> (StatusCode, Vec<u8>) {
+        let mut request = Request::builder().uri(format!("/api/v0/artifacts/{key}"));
+        if let Some(range) = range {
+            request = request.header(header::RANGE, range);
+        }
+        let response = get_router("/api/v0/artifacts")
+            .with_state(state)
+            .oneshot(request.body(Body::empty()).expect("build request"))
+            .await
+            .expect("serve artifact");
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read response body");
+        (status, body.to_vec())
+    }
+
+    #[tokio::test]
+    async fn serves_cached_artifacts_with_range_support() {
+        let dir = tempfile::tempdir().expect("create cache directory");
+        let store = ArtifactStore::open(dir.path(), 1024).expect("open store");
+        let staged = store.staging_path(KEY);
+        std::fs::write(&staged, b"kernel bytes").expect("stage artifact");
+        store
+            .commit(KEY, &staged, &HashSet::new())
+            .expect("commit artifact");
+
+        let mut state = test_app_state();
+        state.artifact_store = Some(store);
+
+        assert_eq!(
+            get_artifact(state.clone(), KEY, None).await,
+            (StatusCode::OK, b"kernel bytes".to_vec())
+        );
+        assert_eq!(
+            get_artifact(state.clone(), KEY, Some("bytes=7-11")).await,
+            (StatusCode::PARTIAL_CONTENT, b"bytes".to_vec())
+        );
+        assert_eq!(
+            get_artifact(state.clone(), &"0".repeat(64), None).await.0,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            get_artifact(state, "..%2F..%2Fetc%2Fpasswd", None).await.0,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn returns_not_found_when_the_cache_is_disabled() {
+        assert_eq!(
+            get_artifact(test_app_state(), KEY, None).await.0,
+            StatusCode::NOT_FOUND
+        );
+    }
+}
@@ -0,0 +1,180 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use carbide_instrument::emit;
+use rpc::forge::UserDataFormat;
+
+use crate::common::{AppState, Machine};
+use crate::ignition;
+use crate::metrics::{BootEndpoint, OutcomeReason, PxeBootOutcome, PxeIgnitionRequestFailed};
+
+/// Ignition fetches its config once, on first boot, and halts the boot if
+/// the fetch fails. Unlike the cloud-init routes there is no error template
+/// to fall back to, so failures are real HTTP errors: a 404 when the
+/// machine's OS definition does not use Ignition, a 500 when its user data
+/// cannot be rendered.
+fn fail(status: StatusCode, reason: OutcomeReason, error: String) -> Response {
+    emit(PxeIgnitionRequestFailed {
+        endpoint: BootEndpoint::Ignition,
+        reason,
+        error,
+    });
+    status.into_response()
+}
+
+/// Serves the Ignition config for an instance whose operating system
+/// definition declares Ignition or Butane user data.
+async fn config(machine: Machine, _state: State<AppState>) -> Response {
+    let instructions = machine.instructions;
+    let format = match UserDataFormat::try_from(instructions.user_data_format) {
+        Ok(UserDataFormat::CloudInit) => {
+            return fail(
+                StatusCode::NOT_FOUND,
+                OutcomeReason::InstructionsEmpty,
+                "the machine's operating system does not use Ignition".to_string(),
+            );
+        }
+        Ok(format) => format,
+        Err(_) => {
+            return fail(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                OutcomeReason::InstructionsInvalid,
+                format!(
+                    "unknown user data format value {}",
+                    instructions.user_data_format
+                ),
+            );
+        }
+    };
+
+    match ignition::render(
+        instructions.custom_cloud_init.as_deref(),
+        format,
+        &instructions.ssh_authorized_keys,
+    ) {
+        Ok(config) => {
+            emit(PxeBootOutcome {
+                endpoint: BootEndpoint::Ignition,
+                reason: OutcomeReason::Ok,
+            });
+            Json(config).into_response()
+        }
+        Err(error) => fail(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            OutcomeReason::InstructionsInvalid,
+            error,
+        ),
+    }
+}
+
+/// Builds the PXE service's route table for the Ignition endpoint served
+/// under `path_prefix`: `config`, the URL passed to the OS as
+/// `ignition.config.url`.
+pub(crate) fn get_router(path_prefix: &str) -> Router<AppState> {
+    Router::new().route(
+        format!("{}/{}", path_prefix, "config").as_str(),
+        get(config),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+    use axum::http::header;
+    use carbide_instrument::testing::MetricsCapture;
+    use rpc::forge;
+
+    use super::*;
+    use crate::common::test_app_state;
+
+    async fn request(instructions: forge::CloudInitInstructions) -> Response {
+        config(Machine { instructions }, State(test_app_state())).await
+    }
+
+    fn outcomes(metrics: &MetricsCapture, reason: &str) -> f64 {
+        metrics.counter_delta(
+            "carbide_pxe_boot_outcomes_total",
+            &[("endpoint", "ignition"), ("reason", reason)],
+        )
+    }
+
+    /// A Butane OS definition is served as translated Ignition JSON, with
+    /// the tenant's keys merged in, and counts as a served outcome.
+    #[tokio::test]
+    async fn butane_user_data_is_served_as_ignition() {
+        let metrics = MetricsCapture::start();
+
+        let response = request(forge::CloudInitInstructions {
+            custom_cloud_init: Some("variant: flatcar\nversion: 1.0.0\n".to_string()),
+            user_data_format: UserDataFormat::Butane as i32,
+            ssh_authorized_keys: vec!["ssh-ed25519 AAAA tenant".to_string()],
+            ..Default::default()
+        })
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            mime::APPLICATION_JSON.as_ref()
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let config: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(config["ignition"]["version"], "3.3.0");
+        assert_eq!(
+            config["passwd"]["users"][0]["sshAuthorizedKeys"][0],
+            "ssh-ed25519 AAAA tenant"
+        );
+        assert_eq!(outcomes(&metrics, "ok"), 1.0);
+    }
+
+    /// A machine whose OS definition uses cloud-init has no Ignition config.
+    #[tokio::test]
+    async fn cloud_init_user_data_is_not_found() {
+        let metrics = MetricsCapture::start();
+
+        let response = request(forge::CloudInitInstructions {
+            custom_cloud_init: Some("#cloud-config".to_string()),
+            ..Default::default()
+        })
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(outcomes(&metrics, "instructions_empty"), 1.0);
+    }
+
+    /// User data that does not render fails the request instead of handing
+    /// Ignition a config it would reject anyway.
+    #[tokio::test]
+    async fn invalid_user_data_is_a_server_error() {
+        let metrics = MetricsCapture::start();
+
+        let response = request(forge::CloudInitInstructions {
+            custom_cloud_init: Some("{not json".to_string()),
+            user_data_format: UserDataFormat::Ignition as i32,
+            ..Default::default()
+        })
+        .await;
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(outcomes(&metrics, "instructions_invalid"), 1.0);
+    }
+}
@@ -0,0 +1,451 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Redfish clients for state handler dry runs.
+//!
+//! [`DryRunRedfishClientPool`] wraps a real pool and hands out clients that
+//! still read from the BMC, so the handler decides on real data, but record
+//! every call that would change something into a [`SideEffectRecorder`]
+//! instead of sending it. Recorded calls succeed without a job or task to
+//! follow: `Option<String>` job IDs come back as `None`, and calls that
+//! return a [`Task`] get a placeholder with the ID [`DRY_RUN_TASK_ID`].
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use carbide_secrets::credentials::CredentialReader;
+use libredfish::model::account_service::ManagerAccount;
+use libredfish::model::certificate::Certificate;
+use libredfish::model::component_integrity::{CaCertificate, ComponentIntegrities, Evidence};
+use libredfish::model::oem::nvidia_dpu::{HostPrivilegeLevel, NicMode};
+use libredfish::model::power::Power;
+use libredfish::model::secure_boot::SecureBoot;
+use libredfish::model::sel::LogEntry;
+use libredfish::model::sensor::GPUSensors;
+use libredfish::model::service_root::{RedfishVendor, ServiceRoot};
+use libredfish::model::software_inventory::SoftwareInventory;
+use libredfish::model::storage::Drives;
+use libredfish::model::task::Task;
+use libredfish::model::thermal::Thermal;
+use libredfish::model::update_service::{ComponentType, TransferProtocolType, UpdateService};
+use libredfish::model::{BootOption, ComputerSystem, Manager, ODataId, ODataLinks};
+use libredfish::{
+    Assembly, BiosProfileType, BiosProfileVendor, Boot, BootInterfaceRef, BootOptions,
+    BootOverride, Chassis, Collection, EnabledDisabled, EthernetInterface, JobState,
+    MachineSetupStatus, NetworkAdapter, NetworkDeviceFunction, NetworkPort, PCIeDevice, PowerState,
+    Redfish, RedfishError, RedfishFuture, Resource, RoleId, Status, SystemPowerControl,
+};
+use state_controller::explain::{SideEffect, SideEffectRecorder};
+
+use crate::libredfish::{RedfishAuth, RedfishClientCreationError, RedfishClientPool};
+
+/// The `service` recorded for Redfish side effects.
+pub const REDFISH_SERVICE: &str = "redfish";
+
+/// The ID of the placeholder [`Task`] returned by recorded calls.
+pub const DRY_RUN_TASK_ID: &str = "dry-run";
+
+/// A [`RedfishClientPool`] whose clients record changes instead of making
+/// them.
+pub struct DryRunRedfishClientPool {
+    inner: std::sync::Arc<dyn RedfishClientPool>,
+    recorder: SideEffectRecorder,
+}
+
+impl DryRunRedfishClientPool {
+    pub fn new(inner: std::sync::Arc<dyn RedfishClientPool>, recorder: SideEffectRecorder) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+#[async_trait]
+impl RedfishClientPool for DryRunRedfishClientPool {
+    async fn create_client(
+        &self,
+        host: &str,
+        port: Option<u16>,
+        auth: RedfishAuth,
+        vendor: Option<RedfishVendor>,
+    ) -> Result<Box<dyn Redfish>, RedfishClientCreationError> {
+        let inner = self.inner.create_client(host, port, auth, vendor).await?;
+        let target = match port {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        };
+        Ok(Box::new(DryRunRedfish {
+            inner,
+            target,
+            recorder: self.recorder.clone(),
+        }))
+    }
+
+    fn credential_reader(&self) -> &dyn CredentialReader {
+        self.inner.credential_reader()
+    }
+}
+
+struct DryRunRedfish {
+    inner: Box<dyn Redfish>,
+    target: String,
+    recorder: SideEffectRecorder,
+}
+
+impl DryRunRedfish {
+    fn record(&self, operation: &str, details: Option<String>) {
+        let effect = SideEffect::new(REDFISH_SERVICE, operation, self.target.clone());
+        self.recorder.record(match details {
+            Some(details) => effect.with_details(details),
+            None => effect,
+        });
+    }
+}
+
+fn dry_run_task() -> Task {
+    Task {
+        odata: ODataLinks {
+            odata_context: None,
+            odata_id: format!("/redfish/v1/TaskService/Tasks/{DRY_RUN_TASK_ID}"),
+            odata_type: "#Task.v1_4_3.Task".to_string(),
+            odata_etag: None,
+            links: None,
+        },
+        id: DRY_RUN_TASK_ID.to_string(),
+        messages: Vec::new(),
+        name: None,
+        task_state: None,
+        task_status: None,
+        task_monitor: None,
+        percent_complete: None,
+    }
+}
+
+/// Generates the read-only trait methods, which pass straight through to the
+/// inner client. Entries are written like those of `delegate_with_red`.
+macro_rules! delegate {
+    ($(
+        fn $method:ident<$lt:lifetime>(
+            & $selflt:lifetime self
+            $(, $arg:ident : $ty:ty )* $(,)?
+        ) -> $ok:ty;
+    )+) => {
+        $(
+            fn $method<$lt>(
+                & $selflt self
+                $(, $arg : $ty )*
+            ) -> RedfishFuture<$lt, Result<$ok, RedfishError>> {
+                self.inner.$method($( $arg ),*)
+            }
+        )+
+    };
+}
+
+/// Generates the trait methods that change something on the BMC. Each one
+/// records the method's name and returns the value after `=` without calling
+/// the inner client. Arguments are not recorded: some of them are passwords.
+macro_rules! record {
+    ($(
+        fn $method:ident<$lt:lifetime>(
+            & $selflt:lifetime self
+            $(, $arg:ident : $ty:ty )* $(,)?
+        ) -> $ok:ty = $value:expr;
+    )+) => {
+        $(
+            fn $method<$lt>(
+                & $selflt self
+                $(, $arg : $ty )*
+            ) -> RedfishFuture<$lt, Result<$ok, RedfishError>> {
+                $( let _ = $arg; )*
+                self.record(stringify!($method), None);
+                Box::pin(std::future::ready(Ok($value)))
+            }
+        )+
+    };
+}
+
+impl Redfish for DryRunRedfish {
+    delegate! {
+        fn get_accounts<'a>(&'a self) -> Vec<ManagerAccount>;
+        fn get_firmware<'a>(&'a self, id: &'a str) -> SoftwareInventory;
+        fn get_software_inventories<'a>(&'a self) -> Vec<String>;
+        fn get_tasks<'a>(&'a self) -> Vec<String>;
+        fn get_task<'a>(&'a self, id: &'a str) -> Task;
+        fn get_power_state<'a>(&'a self) -> PowerState;
+        fn get_service_root<'a>(&'a self) -> ServiceRoot;
+        fn get_systems<'a>(&'a self) -> Vec<String>;
+        fn get_system<'a>(&'a self) -> ComputerSystem;
+        fn get_managers<'a>(&'a self) -> Vec<String>;
+        fn get_manager<'a>(&'a self) -> Manager;
+        fn get_secure_boot<'a>(&'a self) -> SecureBoot;
+        fn get_secure_boot_certificate<'a>(
+            &'a self,
+            database_id: &'a str,
+            certificate_id: &'a str,
+        ) -> Certificate;
+        fn get_secure_boot_certificates<'a>(&'a self, database_id: &'a str) -> Vec<String>;
+        fn get_power_metrics<'a>(&'a self) -> Power;
+        fn get_thermal_metrics<'a>(&'a self) -> Thermal;
+        fn get_gpu_sensors<'a>(&'a self) -> Vec<GPUSensors>;
+        fn get_system_event_log<'a>(&'a self) -> Vec<LogEntry>;
+        fn get_bmc_event_log<'a>(
+            &'a self,
+            from: Option<chrono::DateTime<chrono::Utc>>,
+        ) -> Vec<LogEntry>;
+        fn get_drives_metrics<'a>(&'a self) -> Vec<Drives>;
+        fn machine_setup_status<'a>(
+            &'a self,
+            boot_interface: Option<BootInterfaceRef<'a>>,
+        ) -> MachineSetupStatus;
+        fn is_bios_setup<'a>(&'a self, boot_interface: Option<BootInterfaceRef<'a>>) -> bool;
+        fn lockdown_status<'a>(&'a self) -> Status;
+        fn serial_console_status<'a>(&'a self) -> Status;
+        fn get_boot_options<'a>(&'a self) -> BootOptions;
+        fn get_boot_option<'a>(&'a self, option_id: &'a str) -> BootOption;
+        fn pcie_devices<'a>(&'a self) -> Vec<PCIeDevice>;
+        fn bios<'a>(&'a self) -> HashMap<String, serde_json::Value>;
+        fn pending<'a>(&'a self) -> HashMap<String, serde_json::Value>;
+        fn get_network_device_functions<'a>(&'a self, chassis_id: &'a str) -> Vec<String>;
+        fn get_network_device_function<'a>(
+            &'a self,
+            chassis_id: &'a str,
+            id: &'a str,
+            port: Option<&'a str>,
+        ) -> NetworkDeviceFunction;
+        fn get_chassis_all<'a>(&'a self) -> Vec<String>;
+        fn get_chassis<'a>(&'a self, id: &'a str) -> Chassis;
+        fn get_chassis_assembly<'a>(&'a self, chassis_id: &'a str) -> Assembly;
+        fn get_chassis_network_adapters<'a>(&'a self, chassis_id: &'a str) -> Vec<String>;
+        fn get_chassis_network_adapter<'a>(
+            &'a self,
+            chassis_id: &'a str,
+            id: &'a str,
+        ) -> NetworkAdapter;
+        fn get_base_network_adapters<'a>(&'a self, system_id: &'a str) -> Vec<String>;
+        fn get_base_network_adapter<'a>(
+            &'a self,
+            system_id: &'a str,
+            id: &'a str,
+        ) -> NetworkAdapter;
+        fn get_ports<'a>(
+            &'a self,
+            chassis_id: &'a str,
+            network_adapter: &'a str,
+        ) -> Vec<String>;
+        fn get_port<'a>(
+            &'a self,
+            chassis_id: &'a str,
+            network_adapter: &'a str,
+            id: &'a str,
+        ) -> NetworkPort;
+        fn get_manager_ethernet_interfaces<'a>(&'a self) -> Vec<String>;
+        fn get_manager_ethernet_interface<'a>(&'a self, id: &'a str) -> EthernetInterface;
+        fn get_system_ethernet_interfaces<'a>(&'a self) -> Vec<String>;
+        fn get_system_ethernet_interface<'a>(&'a self, id: &'a str) -> EthernetInterface;
+        fn get_job_state<'a>(&'a self, job_id: &'a str) -> JobState;
+        fn get_resource<'a>(&'a self, id: ODataId) -> Resource;
+        fn get_collection<'a>(&'a self, id: ODataId) -> Collection;
+        fn get_update_service<'a>(&'a self) -> UpdateService;
+        fn get_base_mac_address<'a>(&'a self) -> Option<String>;
+        fn is_ipmi_over_lan_enabled<'a>(&'a self) -> bool;
+        fn get_nic_mode<'a>(&'a self) -> Option<NicMode>;
+        fn is_infinite_boot_enabled<'a>(&'a self) -> Option<bool>;
+        fn get_host_rshim<'a>(&'a self) -> Option<EnabledDisabled>;
+        fn get_boss_controller<'a>(&'a self) -> Option<String>;
+        fn is_boot_order_setup<'a>(&'a self, boot_interface: BootInterfaceRef<'a>) -> bool;
+        fn get_component_integrities<'a>(&'a self) -> ComponentIntegrities;
+        fn get_firmware_for_component<'a>(
+            &'a self,
+            component_integrity_id: &'a str,
+        ) -> SoftwareInventory;
+        fn get_component_ca_certificate<'a>(&'a self, url: &'a str) -> CaCertificate;
+        fn get_evidence<'a>(&'a self, url: &'a str) -> Evidence;
+    }
+
+    record! {
+        fn change_username<'a>(&'a self, old_name: &'a str, new_name: &'a str) -> () = ();
+        fn change_password<'a>(&'a self, username: &'a str, new_pass: &'a str) -> () = ();
+        fn change_password_by_id<'a>(&'a self, account_id: &'a str, new_pass: &'a str) -> () = ();
+        fn create_user<'a>(
+            &'a self,
+            username: &'a str,
+            password: &'a str,
+            role_id: RoleId,
+        ) -> () = ();
+        fn delete_user<'a>(&'a self, username: &'a str) -> () = ();
+        fn change_uefi_password<'a>(
+            &'a self,
+            current_uefi_password: &'a str,
+            new_uefi_password: &'a str,
+        ) -> Option<String> = None;
+        fn clear_uefi_password<'a>(
+            &'a self,
+            current_uefi_password: &'a str,
+        ) -> Option<String> = None;
+        fn disable_secure_boot<'a>(&'a self) -> () = ();
+        fn enable_secure_boot<'a>(&'a self) -> () = ();
+        fn add_secure_boot_certificate<'a>(
+            &'a self,
+            pem_cert: &'a str,
+            database_id: &'a str,
+        ) -> Task = dry_run_task();
+        fn bmc_reset<'a>(&'a self) -> () = ();
+        fn bmc_reset_to_defaults<'a>(&'a self) -> () = ();
+        fn machine_setup<'a>(
+            &'a self,
+            boot_interface: Option<BootInterfaceRef<'a>>,
+            bios_profiles: &'a BiosProfileVendor,
+            selected_profile: BiosProfileType,
+            oem_manager_profiles: &'a BiosProfileVendor,
+        ) -> Option<String> = None;
+        fn set_machine_password_policy<'a>(&'a self) -> () = ();
+        fn lockdown<'a>(&'a self, target: EnabledDisabled) -> () = ();
+        fn setup_serial_console<'a>(&'a self) -> () = ();
+        fn boot_once<'a>(&'a self, target: Boot) -> () = ();
+        fn boot_first<'a>(&'a self, target: Boot) -> () = ();
+        fn set_boot_override<'a>(&'a self, settings: BootOverride) -> Option<String> = None;
+        fn change_boot_order<'a>(&'a self, boot_array: Vec<String>) -> () = ();
+        fn clear_tpm<'a>(&'a self) -> () = ();
+        fn update_firmware<'a>(&'a self, filename: tokio::fs::File) -> Task = dry_run_task();
+        fn update_firmware_multipart<'a>(
+            &'a self,
+            firmware: &'a Path,
+            reboot: bool,
+            timeout: Duration,
+            component_type: ComponentType,
+        ) -> String = DRY_RUN_TASK_ID.to_string();
+        fn update_firmware_simple_update<'a>(
+            &'a self,
+            image_uri: &'a str,
+            targets: Vec<String>,
+            transfer_protocol: TransferProtocolType,
+        ) -> Task = dry_run_task();
+        fn set_bios<'a>(&'a self, values: HashMap<String, serde_json::Value>) -> () = ();
+        fn reset_bios<'a>(&'a self) -> () = ();
+        fn clear_pending<'a>(&'a self) -> () = ();
+        fn set_boot_order_dpu_first<'a>(
+            &'a self,
+            boot_interface: BootInterfaceRef<'a>,
+        ) -> Option<String> = None;
+        fn lockdown_bmc<'a>(&'a self, target: EnabledDisabled) -> () = ();
+        fn enable_ipmi_over_lan<'a>(&'a self, target: EnabledDisabled) -> () = ();
+        fn enable_rshim_bmc<'a>(&'a self) -> () = ();
+        fn clear_nvram<'a>(&'a self) -> () = ();
+        fn set_nic_mode<'a>(&'a self, mode: NicMode) -> () = ();
+        fn enable_infinite_boot<'a>(&'a self) -> () = ();
+        fn set_host_rshim<'a>(&'a self, enabled: EnabledDisabled) -> () = ();
+        fn set_idrac_lockdown<'a>(&'a self, enabled: EnabledDisabled) -> () = ();
+        fn decommission_storage_controller<'a>(
+            &'a self,
+            controller_id: &'a str,
+        ) -> Option<String> = None;
+        fn create_storage_volume<'a>(
+            &'a self,
+            controller_id: &'a str,
+            volume_name: &'a str,
+        ) -> Option<String> = None;
+        fn trigger_evidence_collection<'a>(
+            &'a self,
+            url: &'a str,
+            nonce: &'a str,
+        ) -> Task = dry_run_task();
+        fn set_host_privilege_level<'a>(&'a self, level: HostPrivilegeLevel) -> () = ();
+        fn set_utc_timezone<'a>(&'a self) -> () = ();
+        fn set_ntp_servers<'a>(&'a self, servers: &'a [String]) -> () = ();
+    }
+
+    // MARK: - Power control
+    //
+    // Recorded with the action, which is what a reader of the explanation
+    // most wants to know.
+
+    fn power<'a>(
+        &'a self,
+        action: SystemPowerControl,
+    ) -> RedfishFuture<'a, Result<(), RedfishError>> {
+        self.record("power", Some(action.to_string()));
+        Box::pin(std::future::ready(Ok(())))
+    }
+
+    fn chassis_reset<'a>(
+        &'a self,
+        chassis_id: &'a str,
+        reset_type: SystemPowerControl,
+    ) -> RedfishFuture<'a, Result<(), RedfishError>> {
+        self.record(
+            "chassis_reset",
+            Some(format!("{reset_type} on chassis {chassis_id}")),
+        );
+        Box::pin(std::future::ready(Ok(())))
+    }
+
+    fn ac_powercycle_supported_by_power(&self) -> bool {
+        self.inner.ac_powercycle_supported_by_power()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use carbide_secrets::credentials::{CredentialKey, CredentialType};
+
+    use super::*;
+    use crate::libredfish::test_support::RedfishSim;
+
+    #[tokio::test]
+    async fn changes_are_recorded_and_not_sent() {
+        let sim = Arc::new(RedfishSim::default());
+        let recorder = SideEffectRecorder::new();
+        let pool = DryRunRedfishClientPool::new(sim.clone(), recorder.clone());
+        let client = pool
+            .create_client(
+                "10.0.0.1",
+                Some(443),
+                RedfishAuth::Key(CredentialKey::HostRedfish {
+                    credential_type: CredentialType::SiteDefault,
+                }),
+                None,
+            )
+            .await
+            .expect("sim client");
+
+        assert_eq!(
+            client.get_power_state().await.expect("sim power state"),
+            PowerState::On,
+        );
+        client
+            .power(SystemPowerControl::ForceOff)
+            .await
+            .expect("recorded power off");
+        client.clear_tpm().await.expect("recorded TPM clear");
+
+        // The BMC never saw the power off
+        assert_eq!(
+            client.get_power_state().await.expect("sim power state"),
+            PowerState::On,
+        );
+        assert_eq!(
+            recorder.take(),
+            vec![
+                SideEffect::new(REDFISH_SERVICE, "power", "10.0.0.1:443")
+                    .with_details(SystemPowerControl::ForceOff.to_string()),
+                SideEffect::new(REDFISH_SERVICE, "clear_tpm", "10.0.0.1:443"),
+            ]
+        );
+    }
+}
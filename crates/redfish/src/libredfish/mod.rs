@@ -21,6 +21,7 @@ mod instrumented;
 pub mod auth;
 pub mod conv;
 pub mod dpu_bios;
+pub mod dry_run;
 pub mod error;
 #[cfg(feature = "test-support")]
 pub mod test_support;
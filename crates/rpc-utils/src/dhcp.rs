@@ -142,6 +142,10 @@ pub struct InterfaceInfoV6 {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub address: Option<Ipv6Addr>,
     pub prefix: String,
+    /// Prefix delegated to the host over DHCPv6 IA_PD, for hosts that route
+    /// their own IPv6 subnets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegated_prefix: Option<String>,
 }
 
 impl HostConfig {
@@ -783,6 +787,7 @@ mod tests {
             ipv6: Some(InterfaceInfoV6 {
                 address: Some("2001:db8::10".parse().unwrap()),
                 prefix: "2001:db8::/64".to_string(),
+                delegated_prefix: Some("2001:db8:1::/56".to_string()),
             }),
         };
 
@@ -84,6 +84,7 @@ pub mod network;
 pub mod node_jwt;
 pub mod node_token_socket;
 pub mod protos;
+pub mod sanitization_certificate;
 pub mod secrets;
 mod site_explorer_report;
 pub mod utils;
@@ -0,0 +1,58 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use model::audit::{AuditEvent, AuditEventSource};
+
+use crate as rpc;
+
+impl From<AuditEventSource> for rpc::forge::AuditEventSource {
+    fn from(source: AuditEventSource) -> Self {
+        match source {
+            AuditEventSource::Grpc => rpc::forge::AuditEventSource::Grpc,
+            AuditEventSource::AdminWeb => rpc::forge::AuditEventSource::AdminWeb,
+        }
+    }
+}
+
+impl From<AuditEvent> for rpc::forge::AuditEvent {
+    fn from(event: AuditEvent) -> Self {
+        let AuditEvent {
+            id,
+            occurred_at,
+            source,
+            method,
+            principals,
+            target_ids,
+            request_digest,
+            outcome,
+            outcome_message,
+            client_address,
+        } = event;
+        rpc::forge::AuditEvent {
+            id,
+            occurred_at: Some(occurred_at.into()),
+            source: rpc::forge::AuditEventSource::from(source).into(),
+            method,
+            principals,
+            target_ids,
+            request_digest,
+            outcome,
+            outcome_message,
+            client_address,
+        }
+    }
+}
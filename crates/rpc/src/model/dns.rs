@@ -18,7 +18,7 @@
 use chrono::{DateTime, Utc};
 use dns_record::SoaRecord;
 use model::dns::domain_info::DomainInfo;
-use model::dns::{Domain, DomainMetadata, NewDomain, SoaSnapshot};
+use model::dns::{CustomRecord, Domain, DomainMetadata, NewDomain, SoaSnapshot};
 
 use crate as rpc;
 use crate::errors::RpcDataConversionError;
@@ -144,3 +144,19 @@ impl From<DomainMetadata> for rpc::protos::dns::Metadata {
         }
     }
 }
+
+impl From<CustomRecord> for rpc::protos::dns::DnsCustomRecord {
+    fn from(record: CustomRecord) -> Self {
+        rpc::protos::dns::DnsCustomRecord {
+            id: Some(rpc::common::Uuid {
+                value: record.id.to_string(),
+            }),
+            domain_id: Some(record.domain_id),
+            qname: record.q_name,
+            qtype: record.q_type,
+            ttl: record.ttl,
+            content: record.content,
+            created: Some(record.created.into()),
+        }
+    }
+}
@@ -0,0 +1,143 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use model::firmware_rollout::{
+    FirmwareRollout, FirmwareRolloutMachine, FirmwareRolloutMachineOutcome, FirmwareRolloutSpec,
+    FirmwareRolloutState, FirmwareRolloutTarget, RolloutWaveSize,
+};
+
+use crate as rpc;
+use crate::errors::RpcDataConversionError;
+use crate::forge::firmware_rollout_wave::Size;
+
+impl From<FirmwareRolloutTarget> for rpc::forge::FirmwareRolloutTarget {
+    fn from(target: FirmwareRolloutTarget) -> Self {
+        match target {
+            FirmwareRolloutTarget::HostFirmware => rpc::forge::FirmwareRolloutTarget::HostFirmware,
+            FirmwareRolloutTarget::DpuNicFirmware => {
+                rpc::forge::FirmwareRolloutTarget::DpuNicFirmware
+            }
+        }
+    }
+}
+
+impl TryFrom<rpc::forge::FirmwareRolloutTarget> for FirmwareRolloutTarget {
+    type Error = RpcDataConversionError;
+
+    fn try_from(target: rpc::forge::FirmwareRolloutTarget) -> Result<Self, Self::Error> {
+        match target {
+            rpc::forge::FirmwareRolloutTarget::Unspecified => {
+                Err(RpcDataConversionError::MissingArgument("target"))
+            }
+            rpc::forge::FirmwareRolloutTarget::HostFirmware => {
+                Ok(FirmwareRolloutTarget::HostFirmware)
+            }
+            rpc::forge::FirmwareRolloutTarget::DpuNicFirmware => {
+                Ok(FirmwareRolloutTarget::DpuNicFirmware)
+            }
+        }
+    }
+}
+
+impl From<FirmwareRolloutState> for rpc::forge::FirmwareRolloutState {
+    fn from(state: FirmwareRolloutState) -> Self {
+        match state {
+            FirmwareRolloutState::Active => rpc::forge::FirmwareRolloutState::Active,
+            FirmwareRolloutState::Paused => rpc::forge::FirmwareRolloutState::Paused,
+            FirmwareRolloutState::Completed => rpc::forge::FirmwareRolloutState::Completed,
+        }
+    }
+}
+
+impl From<FirmwareRolloutSpec> for rpc::forge::FirmwareRolloutSpec {
+    fn from(spec: FirmwareRolloutSpec) -> Self {
+        rpc::forge::FirmwareRolloutSpec {
+            waves: spec
+                .waves
+                .into_iter()
+                .map(|wave| rpc::forge::FirmwareRolloutWave {
+                    size: Some(match wave {
+                        RolloutWaveSize::Count(count) => Size::Count(count),
+                        RolloutWaveSize::Percent(percent) => Size::Percent(percent.into()),
+                    }),
+                })
+                .collect(),
+            max_per_rack: spec.max_per_rack,
+            max_per_nvlink_domain: spec.max_per_nvlink_domain,
+            soak_time: Some(spec.soak_time.into()),
+            max_failed_percent: spec.max_failed_percent.into(),
+        }
+    }
+}
+
+impl TryFrom<rpc::forge::FirmwareRolloutSpec> for FirmwareRolloutSpec {
+    type Error = RpcDataConversionError;
+
+    fn try_from(spec: rpc::forge::FirmwareRolloutSpec) -> Result<Self, Self::Error> {
+        let percent = |field: &str, value: u32| {
+            u8::try_from(value)
+                .map_err(|_| RpcDataConversionError::InvalidValue(field.into(), value.to_string()))
+        };
+        let waves = spec
+            .waves
+            .into_iter()
+            .map(|wave| match wave.size {
+                Some(Size::Count(count)) => Ok(RolloutWaveSize::Count(count)),
+                Some(Size::Percent(value)) => {
+                    Ok(RolloutWaveSize::Percent(percent("percent", value)?))
+                }
+                None => Err(RpcDataConversionError::MissingArgument("waves.size")),
+            })
+            .collect::<Result<_, _>>()?;
+        let soak_time = spec
+            .soak_time
+            .map(std::time::Duration::try_from)
+            .transpose()
+            .map_err(|e| RpcDataConversionError::InvalidValue("soak_time".into(), e.to_string()))?
+            .unwrap_or_default();
+
+        Ok(FirmwareRolloutSpec {
+            waves,
+            max_per_rack: spec.max_per_rack,
+            max_per_nvlink_domain: spec.max_per_nvlink_domain,
+            soak_time,
+            max_failed_percent: percent("max_failed_percent", spec.max_failed_percent)?,
+        })
+    }
+}
+
+impl From<(FirmwareRollout, Vec<FirmwareRolloutMachine>)> for rpc::forge::FirmwareRollout {
+    fn from((rollout, machines): (FirmwareRollout, Vec<FirmwareRolloutMachine>)) -> Self {
+        let with_outcome =
+            |outcome| machines.iter().filter(|m| m.outcome == outcome).count() as u32;
+        rpc::forge::FirmwareRollout {
+            hosts_started: machines.len() as u32,
+            hosts_succeeded: with_outcome(FirmwareRolloutMachineOutcome::Succeeded),
+            hosts_failed: with_outcome(FirmwareRolloutMachineOutcome::Failed),
+            name: rollout.name,
+            target: rpc::forge::FirmwareRolloutTarget::from(rollout.target).into(),
+            spec: Some(rollout.spec.into()),
+            state: rpc::forge::FirmwareRolloutState::from(rollout.state).into(),
+            current_wave: rollout.current_wave.max(0) as u32,
+            wave_started_at: Some(rollout.wave_started_at.into()),
+            wave_completed_at: rollout.wave_completed_at.map(Into::into),
+            paused_reason: rollout.paused_reason,
+            created_at: Some(rollout.created_at.into()),
+            updated_at: Some(rollout.updated_at.into()),
+        }
+    }
+}
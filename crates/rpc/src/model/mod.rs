@@ -20,6 +20,7 @@
 
 pub mod allocation_type;
 pub mod attestation;
+pub mod audit;
 pub mod bmc_info;
 pub mod compute_allocation;
 pub mod controller_outcome;
@@ -33,6 +34,7 @@ pub mod expected_rack;
 pub mod expected_switch;
 pub mod extension_service;
 pub mod firmware;
+pub mod firmware_rollout;
 pub mod hardware_info;
 pub mod health;
 pub mod ib_partition;
@@ -63,6 +65,7 @@ pub mod site_explorer;
 pub mod site_prefix;
 pub mod sku;
 pub mod spx_partition;
+pub mod state_analytics;
 pub mod state_history;
 pub mod storage;
 pub mod switch;
@@ -24,10 +24,32 @@
 use carbide_ipxe_renderer::IpxeTemplateArtifactCacheStrategy;
 use carbide_uuid::ipxe_template::IpxeTemplateId;
 use carbide_uuid::operating_system::OperatingSystemId;
-use model::operating_system_definition::{OS_TYPE_IPXE, OS_TYPE_TEMPLATED_IPXE, OperatingSystem};
+use model::operating_system_definition::{
+    OS_TYPE_IPXE, OS_TYPE_TEMPLATED_IPXE, OperatingSystem, UserDataFormat,
+};
 
 use crate::forge as forgerpc;
 
+impl From<UserDataFormat> for forgerpc::UserDataFormat {
+    fn from(format: UserDataFormat) -> Self {
+        match format {
+            UserDataFormat::CloudInit => Self::CloudInit,
+            UserDataFormat::Ignition => Self::Ignition,
+            UserDataFormat::Butane => Self::Butane,
+        }
+    }
+}
+
+impl From<forgerpc::UserDataFormat> for UserDataFormat {
+    fn from(format: forgerpc::UserDataFormat) -> Self {
+        match format {
+            forgerpc::UserDataFormat::CloudInit => Self::CloudInit,
+            forgerpc::UserDataFormat::Ignition => Self::Ignition,
+            forgerpc::UserDataFormat::Butane => Self::Butane,
+        }
+    }
+}
+
 impl From<OperatingSystem> for forgerpc::OperatingSystem {
     fn from(m: OperatingSystem) -> Self {
         let os_type = match m.type_.as_str() {
@@ -50,6 +72,7 @@ impl From<OperatingSystem> for forgerpc::OperatingSystem {
             allow_override: m.allow_override,
             phone_home_enabled: m.phone_home_enabled,
             user_data: m.user_data,
+            user_data_format: forgerpc::UserDataFormat::from(m.user_data_format) as i32,
             created: m.created,
             updated: m.updated,
             ipxe_script: m.ipxe_script,
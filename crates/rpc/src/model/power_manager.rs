@@ -57,6 +57,7 @@ impl From<PowerOptions> for rpc::forge::PowerOptions {
                     .wait_until_time_before_performing_next_power_action
                     .into(),
             ),
+            idle_parked_at: value.idle_parked_at.map(|x| x.into()),
         }
     }
 }
@@ -15,7 +15,9 @@
  * limitations under the License.
  */
 
-use model::rack::{Rack, RackSearchFilter, derive_rack_aggregate_health};
+use model::rack::{
+    HostPowerLimit, Rack, RackPowerBudgetStatus, RackSearchFilter, derive_rack_aggregate_health,
+};
 
 use crate as rpc;
 use crate::Timestamp;
@@ -56,11 +58,39 @@ impl From<Rack> for rpc::forge::Rack {
                 health: Some(health.into()),
                 health_sources,
                 lifecycle: Some(lifecycle),
+                power_budget: value.power_budget.map(Into::into),
             }),
         }
     }
 }
 
+impl From<RackPowerBudgetStatus> for rpc::forge::RackPowerBudgetStatus {
+    fn from(value: RackPowerBudgetStatus) -> Self {
+        rpc::forge::RackPowerBudgetStatus {
+            capacity_watts: value.capacity_watts,
+            allocated_watts: value.allocated_watts,
+            headroom_watts: value.headroom_watts,
+            healthy_power_supplies: value.healthy_power_supplies,
+            total_power_supplies: value.total_power_supplies,
+            host_power_limits: value
+                .host_power_limits
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            updated_at: Some(Timestamp::from(value.updated_at)),
+        }
+    }
+}
+
+impl From<HostPowerLimit> for rpc::forge::HostPowerLimit {
+    fn from(value: HostPowerLimit) -> Self {
+        rpc::forge::HostPowerLimit {
+            machine_id: Some(value.machine_id),
+            limit_watts: value.limit_watts,
+        }
+    }
+}
+
 impl From<rpc::forge::RackSearchFilter> for RackSearchFilter {
     fn from(filter: rpc::forge::RackSearchFilter) -> Self {
         RackSearchFilter {
@@ -433,6 +433,7 @@ mod tests {
                 },
             },
             attributes: Default::default(),
+            power_budget: None,
         };
 
         let proto: rpc::forge::RackProfile = (&profile).into();
@@ -514,6 +515,7 @@ mod tests {
                 },
             },
             attributes: Default::default(),
+            power_budget: None,
         };
 
         let configured: rpc::forge::ConfiguredRackProfile = ("NVL72_GB300", &profile).into();
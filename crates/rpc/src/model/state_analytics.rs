@@ -0,0 +1,95 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use model::state_analytics::{
+    StateAnalyticsObjectType, StateDwellAnalytics, StateDwellStats, StateTransitionCount,
+    StuckObject,
+};
+
+use crate as rpc;
+use crate::errors::RpcDataConversionError;
+
+impl TryFrom<rpc::forge::StateAnalyticsObjectType> for StateAnalyticsObjectType {
+    type Error = RpcDataConversionError;
+
+    fn try_from(object_type: rpc::forge::StateAnalyticsObjectType) -> Result<Self, Self::Error> {
+        match object_type {
+            rpc::forge::StateAnalyticsObjectType::Unspecified => {
+                Err(RpcDataConversionError::MissingArgument("object_type"))
+            }
+            rpc::forge::StateAnalyticsObjectType::Machine => Ok(StateAnalyticsObjectType::Machine),
+            rpc::forge::StateAnalyticsObjectType::Rack => Ok(StateAnalyticsObjectType::Rack),
+            rpc::forge::StateAnalyticsObjectType::Switch => Ok(StateAnalyticsObjectType::Switch),
+            rpc::forge::StateAnalyticsObjectType::PowerShelf => {
+                Ok(StateAnalyticsObjectType::PowerShelf)
+            }
+        }
+    }
+}
+
+impl From<StateDwellStats> for rpc::forge::StateDwellStats {
+    fn from(stats: StateDwellStats) -> Self {
+        rpc::forge::StateDwellStats {
+            state: stats.state,
+            completed: stats.completed,
+            p50: Some(stats.p50.into()),
+            p95: Some(stats.p95.into()),
+            p99: Some(stats.p99.into()),
+            max: Some(stats.max.into()),
+            in_progress: stats.in_progress,
+            stuck: stats.stuck,
+        }
+    }
+}
+
+impl From<StateTransitionCount> for rpc::forge::StateTransitionCount {
+    fn from(transition: StateTransitionCount) -> Self {
+        rpc::forge::StateTransitionCount {
+            from_state: transition.from_state,
+            to_state: transition.to_state,
+            count: transition.count,
+        }
+    }
+}
+
+impl From<StuckObject> for rpc::forge::StuckObject {
+    fn from(object: StuckObject) -> Self {
+        rpc::forge::StuckObject {
+            object_id: object.object_id,
+            state: object.state,
+            since: Some(object.since.into()),
+        }
+    }
+}
+
+impl From<StateDwellAnalytics> for rpc::forge::StateDwellAnalytics {
+    fn from(analytics: StateDwellAnalytics) -> Self {
+        rpc::forge::StateDwellAnalytics {
+            since: Some(analytics.since.into()),
+            until: Some(analytics.until.into()),
+            object_count: analytics.object_count,
+            states: analytics.states.into_iter().map(Into::into).collect(),
+            transitions: analytics.transitions.into_iter().map(Into::into).collect(),
+            stuck_objects: analytics
+                .stuck_objects
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            stuck_objects_truncated: analytics.stuck_objects_truncated,
+        }
+    }
+}
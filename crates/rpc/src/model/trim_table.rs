@@ -23,6 +23,7 @@ impl From<rpc::forge::TrimTableTarget> for TrimTableTarget {
     fn from(target: rpc::forge::TrimTableTarget) -> Self {
         match target {
             rpc::forge::TrimTableTarget::MeasuredBoot => TrimTableTarget::MeasuredBoot,
+            rpc::forge::TrimTableTarget::AuditEvents => TrimTableTarget::AuditEvents,
         }
     }
 }
@@ -30,6 +30,11 @@
 //! if the cert/key files are missing or unreadable (e.g. before first
 //! registration), requests simply carry no bearer header and the channel's
 //! mTLS client cert remains the only credential.
+//!
+//! The same key also signs documents that must stay verifiable after the
+//! request that delivered them, such as scout's disk sanitization
+//! certificates; see [`NodeJwtMinter::sign_document`] and
+//! [`verify_document`].
 
 use std::io::Cursor;
 use std::sync::{Arc, RwLock};
@@ -37,9 +42,10 @@ use std::task::{Context, Poll};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use data_encoding::BASE64;
-use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use p256::pkcs8::{EncodePrivateKey, LineEnding};
-use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use tower::Service;
 use x509_parser::prelude::{FromDer, GeneralName, X509Certificate};
 
@@ -68,6 +74,8 @@ pub enum NodeJwtError {
     KeyCertMismatch,
     #[error("JWT signing failed: {0}")]
     Sign(#[from] jsonwebtoken::errors::Error),
+    #[error("signed document is not valid: {0}")]
+    BadDocument(String),
     #[error("system clock is before the UNIX epoch")]
     Clock,
 }
@@ -171,6 +179,50 @@ impl NodeJwtMinter {
     }
 
     fn mint(&self, now: u64) -> Result<CachedToken, NodeJwtError> {
+        let identity = self.signing_identity()?;
+        let mut header = identity.header();
+        header.typ = Some("JWT".to_string());
+
+        let expires_at = now + NODE_JWT_TTL_SECS;
+        let claims = NodeClaims {
+            sub: &identity.sub,
+            aud: NODE_JWT_AUDIENCE,
+            iat: now,
+            exp: expires_at,
+        };
+        let token = jsonwebtoken::encode(&header, &claims, &ec_encoding_key(&identity.secret)?)?;
+        Ok(CachedToken { token, expires_at })
+    }
+
+    /// Signs `document` as a compact JWS with the same key and `x5c` chain
+    /// node tokens carry, for records that have to stay verifiable after the
+    /// request that delivered them. The payload is the document's own fields
+    /// plus `sub` and `iat`. There is no `exp` or `aud`: the signature is
+    /// evidence checked later, not a credential. `typ` names the kind of
+    /// document so that one kind can never be passed off as another, and so
+    /// none of them is ever accepted as a bearer token.
+    pub fn sign_document<T: Serialize>(
+        &self,
+        typ: &str,
+        document: &T,
+    ) -> Result<String, NodeJwtError> {
+        let identity = self.signing_identity()?;
+        let mut header = identity.header();
+        header.typ = Some(typ.to_string());
+
+        let claims = DocumentClaims {
+            sub: identity.sub.clone(),
+            iat: unix_now()?,
+            document,
+        };
+        Ok(jsonwebtoken::encode(
+            &header,
+            &claims,
+            &ec_encoding_key(&identity.secret)?,
+        )?)
+    }
+
+    fn signing_identity(&self) -> Result<SigningIdentity, NodeJwtError> {
         let cert_pem = std::fs::read(&self.cert_path)?;
         let key_pem = std::fs::read_to_string(&self.key_path)?;
 
@@ -191,20 +243,106 @@ impl NodeJwtMinter {
             return Err(NodeJwtError::KeyCertMismatch);
         }
 
+        Ok(SigningIdentity {
+            x5c: chain.iter().map(|c| BASE64.encode(c.as_ref())).collect(),
+            sub,
+            secret,
+        })
+    }
+}
+
+/// The node's client certificate chain and the key it certifies, checked to
+/// belong together.
+struct SigningIdentity {
+    x5c: Vec<String>,
+    sub: String,
+    secret: p256::SecretKey,
+}
+
+impl SigningIdentity {
+    fn header(&self) -> Header {
         let mut header = Header::new(Algorithm::ES256);
-        header.typ = Some("JWT".to_string());
-        header.x5c = Some(chain.iter().map(|c| BASE64.encode(c.as_ref())).collect());
+        header.x5c = Some(self.x5c.clone());
+        header
+    }
+}
 
-        let expires_at = now + NODE_JWT_TTL_SECS;
-        let claims = NodeClaims {
-            sub: &sub,
-            aud: NODE_JWT_AUDIENCE,
-            iat: now,
-            exp: expires_at,
-        };
-        let token = jsonwebtoken::encode(&header, &claims, &ec_encoding_key(&secret)?)?;
-        Ok(CachedToken { token, expires_at })
+/// Payload of a document signed with [`NodeJwtMinter::sign_document`].
+#[derive(Debug, Serialize, Deserialize)]
+struct DocumentClaims<T> {
+    sub: String,
+    iat: u64,
+    #[serde(flatten)]
+    document: T,
+}
+
+/// A document whose signature [`verify_document`] checked.
+#[derive(Debug)]
+pub struct VerifiedDocument<T> {
+    /// The SPIFFE URI of the certificate that signed the document.
+    pub signer: String,
+    /// When the node signed the document, in unix seconds.
+    pub signed_at: u64,
+    pub document: T,
+}
+
+/// Verifies a document signed with [`NodeJwtMinter::sign_document`]: the
+/// `typ`, the ES256 signature against the leaf certificate in `x5c`, and that
+/// `sub` is that certificate's SPIFFE URI.
+///
+/// The chain itself is not checked against a trust anchor here. Whoever
+/// relies on the document does that against the root CA they trust, which is
+/// why the chain stays in the JWS rather than being stripped on receipt.
+pub fn verify_document<T: DeserializeOwned>(
+    token: &str,
+    typ: &str,
+) -> Result<VerifiedDocument<T>, NodeJwtError> {
+    let bad_document = |e: jsonwebtoken::errors::Error| NodeJwtError::BadDocument(e.to_string());
+    let header = jsonwebtoken::decode_header(token).map_err(bad_document)?;
+    if header.alg != Algorithm::ES256 {
+        return Err(NodeJwtError::BadDocument(format!(
+            "unexpected algorithm {:?}; only ES256 is accepted",
+            header.alg
+        )));
+    }
+    if header.typ.as_deref() != Some(typ) {
+        return Err(NodeJwtError::BadDocument(format!(
+            "document type is {:?}, expected {typ}",
+            header.typ
+        )));
     }
+    let chain = header
+        .x5c_der()
+        .map_err(bad_document)?
+        .filter(|chain| !chain.is_empty())
+        .ok_or(NodeJwtError::NoCertificate)?;
+    let leaf = &chain[0];
+    let signer = spiffe_uri_from_cert(leaf)?;
+    let (_, cert) = X509Certificate::from_der(leaf)
+        .map_err(|e| NodeJwtError::BadCertificate(format!("X.509 parse error: {e}")))?;
+
+    let mut validation = Validation::new(Algorithm::ES256);
+    validation.required_spec_claims.clear();
+    validation.validate_exp = false;
+    validation.validate_aud = false;
+    let claims = jsonwebtoken::decode::<DocumentClaims<T>>(
+        token,
+        &DecodingKey::from_ec_der(&cert.public_key().subject_public_key.data),
+        &validation,
+    )
+    .map_err(bad_document)?
+    .claims;
+    if claims.sub != signer {
+        return Err(NodeJwtError::BadDocument(
+            "document `sub` does not match the certificate's SPIFFE URI".to_string(),
+        ));
+    }
+
+    Ok(VerifiedDocument {
+        signer,
+        signed_at: claims.iat,
+        document: claims.document,
+    })
 }
 
 fn unix_now() -> Result<u64, NodeJwtError> {
@@ -0,0 +1,279 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Disk sanitization certificates: scout's per-device record of how it
+//! sanitized a machine's storage during cleanup, following NIST SP 800-88.
+//!
+//! Scout signs the record with its client certificate key through
+//! [`NodeJwtMinter::sign_document`] and sends it with `CleanupMachineCompleted`.
+//! The API verifies and stores the JWS as signed, so a tenant can be handed
+//! proof that does not depend on trusting the API's database.
+
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::forge;
+use crate::node_jwt::{NodeJwtError, NodeJwtMinter, VerifiedDocument, verify_document};
+
+/// JWS `typ` of a signed sanitization certificate.
+pub const SANITIZATION_CERTIFICATE_TYPE: &str = "nico-disk-sanitization+jwt";
+
+/// The guideline the recorded methods and verification follow.
+pub const NIST_SP_800_88: &str = "NIST SP 800-88 Rev. 1";
+
+/// The signed payload: every storage device scout sanitized on one cleanup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SanitizationCertificate {
+    pub machine_id: MachineId,
+    pub standard: String,
+    pub devices: Vec<DeviceSanitization>,
+}
+
+/// One device's sanitization, identified the way the drive reports itself
+/// so the record can be matched to the physical device.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceSanitization {
+    pub device: String,
+    pub media: StorageMedia,
+    pub serial_number: String,
+    pub model: String,
+    pub firmware_revision: String,
+    pub capacity_bytes: u64,
+    pub method: SanitizationMethod,
+    pub technique: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub succeeded: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub verification: SampledVerification,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageMedia {
+    Nvme,
+    Sata,
+    Sas,
+}
+
+/// NIST SP 800-88 sanitization categories. Destroy is physical and never
+/// something scout does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SanitizationMethod {
+    /// Logical overwrite or removal, protecting against simple recovery.
+    Clear,
+    /// Cryptographic or firmware erase, protecting against laboratory
+    /// recovery.
+    Purge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationVerdict {
+    NotPerformed,
+    Passed,
+    Failed,
+}
+
+/// The sampled read-back verification NIST SP 800-88 asks for after a
+/// purge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SampledVerification {
+    pub verdict: VerificationVerdict,
+    pub samples: u32,
+    pub changed: u32,
+    pub unchanged: u32,
+    pub inconclusive: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl SanitizationCertificate {
+    /// Signs the certificate with the node's client certificate key.
+    pub fn sign(&self, minter: &NodeJwtMinter) -> Result<String, NodeJwtError> {
+        minter.sign_document(SANITIZATION_CERTIFICATE_TYPE, self)
+    }
+
+    /// Verifies a signed certificate. See [`verify_document`] for what is
+    /// and is not checked.
+    pub fn verify(signed: &str) -> Result<VerifiedDocument<Self>, NodeJwtError> {
+        verify_document(signed, SANITIZATION_CERTIFICATE_TYPE)
+    }
+}
+
+impl From<StorageMedia> for forge::DiskSanitizationMedia {
+    fn from(media: StorageMedia) -> Self {
+        match media {
+            StorageMedia::Nvme => Self::Nvme,
+            StorageMedia::Sata => Self::Sata,
+            StorageMedia::Sas => Self::Sas,
+        }
+    }
+}
+
+impl From<SanitizationMethod> for forge::DiskSanitizationMethod {
+    fn from(method: SanitizationMethod) -> Self {
+        match method {
+            SanitizationMethod::Clear => Self::Clear,
+            SanitizationMethod::Purge => Self::Purge,
+        }
+    }
+}
+
+impl From<VerificationVerdict> for forge::DiskSanitizationVerdict {
+    fn from(verdict: VerificationVerdict) -> Self {
+        match verdict {
+            VerificationVerdict::NotPerformed => Self::NotPerformed,
+            VerificationVerdict::Passed => Self::Passed,
+            VerificationVerdict::Failed => Self::Failed,
+        }
+    }
+}
+
+impl From<SampledVerification> for forge::DiskSanitizationVerification {
+    fn from(verification: SampledVerification) -> Self {
+        Self {
+            verdict: forge::DiskSanitizationVerdict::from(verification.verdict) as i32,
+            samples: verification.samples,
+            changed: verification.changed,
+            unchanged: verification.unchanged,
+            inconclusive: verification.inconclusive,
+            note: verification.note,
+        }
+    }
+}
+
+impl From<DeviceSanitization> for forge::DiskSanitizationDevice {
+    fn from(device: DeviceSanitization) -> Self {
+        Self {
+            device: device.device,
+            media: forge::DiskSanitizationMedia::from(device.media) as i32,
+            serial_number: device.serial_number,
+            model: device.model,
+            firmware_revision: device.firmware_revision,
+            capacity_bytes: device.capacity_bytes,
+            method: forge::DiskSanitizationMethod::from(device.method) as i32,
+            technique: device.technique,
+            started_at: Some(device.started_at.into()),
+            finished_at: Some(device.finished_at.into()),
+            succeeded: device.succeeded,
+            error: device.error,
+            verification: Some(device.verification.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    const SPIFFE_URI: &str = "spiffe://forge.local/forge-system/machine/fm100xtest";
+
+    fn write_temp(dir: &tempfile::TempDir, name: &str, contents: &str) -> String {
+        let path = dir.path().join(name);
+        std::fs::write(&path, contents).expect("write");
+        path.to_string_lossy().into_owned()
+    }
+
+    fn minter(dir: &tempfile::TempDir) -> std::sync::Arc<NodeJwtMinter> {
+        let mut params = rcgen::CertificateParams::default();
+        params.subject_alt_names = vec![rcgen::SanType::URI(
+            rcgen::string::Ia5String::try_from(SPIFFE_URI.to_string()).expect("uri"),
+        )];
+        let key = rcgen::KeyPair::generate().expect("key pair");
+        let cert = params.self_signed(&key).expect("certificate");
+        NodeJwtMinter::new(
+            write_temp(dir, "cert.pem", &cert.pem()),
+            write_temp(dir, "key.pem", &key.serialize_pem()),
+        )
+    }
+
+    fn certificate() -> SanitizationCertificate {
+        let at = DateTime::from_timestamp(1_800_000_000, 0).expect("timestamp");
+        SanitizationCertificate {
+            machine_id: MachineId::from_str(
+                "fm100htjtiaehv1n5vh67tbmqq4eabcjdng40f7jupsadbedhruh6rag1l0",
+            )
+            .expect("machine id"),
+            standard: NIST_SP_800_88.to_string(),
+            devices: vec![DeviceSanitization {
+                device: "/dev/nvme0".to_string(),
+                media: StorageMedia::Nvme,
+                serial_number: "S5XYNA0R000001".to_string(),
+                model: "SAMSUNG MZWLR3T8HBLS".to_string(),
+                firmware_revision: "MPK7525Q".to_string(),
+                capacity_bytes: 3_840_755_982_336,
+                method: SanitizationMethod::Purge,
+                technique: "nvme format --ses=2 (cryptographic erase)".to_string(),
+                started_at: at,
+                finished_at: at,
+                succeeded: true,
+                error: None,
+                verification: SampledVerification {
+                    verdict: VerificationVerdict::Passed,
+                    samples: 16,
+                    changed: 12,
+                    unchanged: 0,
+                    inconclusive: 4,
+                    note: None,
+                },
+            }],
+        }
+    }
+
+    #[test]
+    fn signed_certificates_verify_against_the_signing_certificate() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let signed = certificate().sign(&minter(&dir)).expect("signs");
+
+        let verified = SanitizationCertificate::verify(&signed).expect("verifies");
+        assert_eq!(verified.signer, SPIFFE_URI);
+        assert_eq!(verified.document, certificate());
+    }
+
+    /// Changing a single byte of the payload, or presenting a node token as
+    /// a certificate, must fail verification.
+    #[test]
+    fn tampered_or_mistyped_documents_are_rejected() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let minter = minter(&dir);
+        let signed = certificate().sign(&minter).expect("signs");
+
+        let mut parts: Vec<String> = signed.split('.').map(str::to_string).collect();
+        let mut payload = data_encoding::BASE64URL_NOPAD
+            .decode(parts[1].as_bytes())
+            .expect("payload decodes");
+        let position = payload
+            .windows(b"S5XYNA0R000001".len())
+            .position(|window| window == b"S5XYNA0R000001")
+            .expect("serial in payload");
+        payload[position] = b'X';
+        parts[1] = data_encoding::BASE64URL_NOPAD.encode(&payload);
+        assert!(SanitizationCertificate::verify(&parts.join(".")).is_err());
+
+        let token = minter.current().expect("token minted");
+        assert!(matches!(
+            SanitizationCertificate::verify(&token),
+            Err(NodeJwtError::BadDocument(_))
+        ));
+    }
+}
@@ -16,7 +16,6 @@
  */
 
 use std::ffi::CString;
-use std::process::Command;
 use std::str::FromStr;
 use std::vec::Vec;
 
@@ -342,29 +341,22 @@ pub(super) fn create_quote_request(
     Ok(request)
 }
 
-pub(super) fn get_tpm_eventlog() -> Option<Vec<u8>> {
-    let output_res = Command::new("sh")
-        .arg("-c")
-        .arg("tpm2_eventlog /sys/kernel/security/tpm0/binary_bios_measurements")
-        .output();
+/// Where the kernel exposes the firmware's TCG2 event log.
+const TPM_EVENTLOG_PATH: &str = "/sys/kernel/security/tpm0/binary_bios_measurements";
 
-    let output = match output_res {
-        Ok(output) => output,
+/// get_tpm_eventlog reads the raw binary event log, which carbide-api
+/// parses and replays against the quoted PCR values.
+pub(super) fn get_tpm_eventlog() -> Option<Vec<u8>> {
+    match std::fs::read(TPM_EVENTLOG_PATH) {
+        Ok(event_log) => Some(event_log),
         Err(e) => {
-            tracing::error!(error = %e, "Could not retrieve TPM event log");
-            return None;
+            tracing::error!(
+                error = %e,
+                path = TPM_EVENTLOG_PATH,
+                "Could not retrieve TPM event log",
+            );
+            None
         }
-    };
-
-    if !output.status.success() {
-        tracing::error!(
-            stderr = %String::from_utf8(output.stderr)
-                .unwrap_or("<could not parse stderr log>".to_string()),
-            "Error retrieving TPM event log",
-        );
-        None
-    } else {
-        Some(output.stdout)
     }
 }
 
@@ -15,5 +15,6 @@
  * limitations under the License.
  */
 mod cmdrun;
+mod sanitization;
 mod scrabbing;
 pub(super) use scrabbing::{run, run_no_api};
@@ -0,0 +1,392 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The per-device records scout puts in the disk sanitization certificate
+//! it signs at the end of cleanup, and the sampled read-back verification
+//! behind each record's verdict.
+//!
+//! Verification reads a handful of blocks at spread offsets before and after
+//! a device is sanitized and compares them by digest. Only the digests are
+//! kept, and only the counts leave the machine. A block that held data and
+//! reads back identical means the sanitization did not reach it.
+
+use std::fs;
+use std::os::unix::fs::{FileExt, OpenOptionsExt};
+use std::path::Path;
+use std::time::Duration;
+
+use ::rpc::sanitization_certificate::{
+    DeviceSanitization, SampledVerification, SanitizationMethod, StorageMedia, VerificationVerdict,
+};
+use chrono::{DateTime, Utc};
+use scout::CarbideClientError;
+use sha2::{Digest, Sha256};
+
+/// Blocks sampled per device.
+const SAMPLE_COUNT: u64 = 16;
+
+/// Size of each sampled block. Also the alignment O_DIRECT reads need, which
+/// covers both 512-byte and 4 KiB logical block sizes.
+const SAMPLE_SIZE: usize = 4096;
+
+/// How long to wait for a recreated NVMe namespace's block device to show up
+/// after `attach-ns` before giving up on reading it back.
+const NAMESPACE_APPEAR_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What one sampled block read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum Sample {
+    Unreadable,
+    /// All zeroes, which says nothing about whether the block was reached.
+    Blank,
+    /// SHA-256 of the block.
+    Data(Vec<u8>),
+}
+
+/// Builds a device's record while it is sanitized.
+pub(super) struct DeviceRecord {
+    device: String,
+    media: StorageMedia,
+    serial_number: String,
+    model: String,
+    firmware_revision: String,
+    capacity_bytes: u64,
+    method: SanitizationMethod,
+    technique: String,
+    started_at: DateTime<Utc>,
+    /// The block device that was sampled, the offsets, and what they read
+    /// before sanitization.
+    samples: Option<(String, Vec<u64>, Vec<Sample>)>,
+    /// Why read-back verification cannot be done, when it cannot.
+    verification_note: Option<String>,
+}
+
+impl DeviceRecord {
+    pub(super) fn new(device: &str, media: StorageMedia) -> Self {
+        Self {
+            device: device.to_string(),
+            media,
+            serial_number: String::new(),
+            model: String::new(),
+            firmware_revision: String::new(),
+            capacity_bytes: 0,
+            method: SanitizationMethod::Purge,
+            technique: String::new(),
+            started_at: Utc::now(),
+            samples: None,
+            verification_note: Some("sanitization did not start".to_string()),
+        }
+    }
+
+    /// Records how the device identifies itself.
+    pub(super) fn identify(
+        &mut self,
+        serial_number: &str,
+        model: &str,
+        firmware_revision: &str,
+        capacity_bytes: u64,
+    ) {
+        self.serial_number = serial_number.trim().to_string();
+        self.model = model.trim().to_string();
+        self.firmware_revision = firmware_revision.trim().to_string();
+        self.capacity_bytes = capacity_bytes;
+    }
+
+    /// Records the NIST SP 800-88 method and the command that carried it out.
+    pub(super) fn sanitized_with(&mut self, method: SanitizationMethod, technique: &str) {
+        self.method = method;
+        self.technique = technique.to_string();
+    }
+
+    /// Samples `block_device` before it is sanitized, so it can be read back
+    /// in [`finish`](Self::finish).
+    pub(super) async fn sample_before(&mut self, block_device: &str) {
+        let Some(size) = block_device_size(block_device) else {
+            self.skip_verification(&format!("{block_device} has no readable size"));
+            return;
+        };
+        let offsets = sample_offsets(size);
+        let path = block_device.to_string();
+        let before = read_samples_blocking(path.clone(), offsets.clone()).await;
+        self.samples = Some((path, offsets, before));
+        self.verification_note = None;
+    }
+
+    /// Records why the device cannot be read back.
+    pub(super) fn skip_verification(&mut self, note: &str) {
+        self.samples = None;
+        self.verification_note = Some(note.to_string());
+    }
+
+    /// Reads the sampled blocks back and completes the record.
+    ///
+    /// A sanitization that left a sampled block unchanged did not work, so
+    /// a failed verification turns a successful `result` into an error.
+    pub(super) async fn finish(
+        self,
+        result: &mut Result<(), CarbideClientError>,
+    ) -> DeviceSanitization {
+        let verification = match (&result, self.samples) {
+            (Err(_), _) => not_performed("sanitization failed"),
+            (Ok(()), None) => not_performed(
+                self.verification_note
+                    .as_deref()
+                    .unwrap_or("the device was not sampled"),
+            ),
+            (Ok(()), Some((path, offsets, before))) => {
+                if wait_for_block_device(&path).await {
+                    let after = read_samples_blocking(path, offsets).await;
+                    compare(&before, &after)
+                } else {
+                    not_performed(&format!("{path} did not reappear after sanitization"))
+                }
+            }
+        };
+
+        if verification.verdict == VerificationVerdict::Failed {
+            *result = Err(CarbideClientError::GenericError(format!(
+                "Device {}: {} of {} sampled blocks read back unchanged after sanitization",
+                self.device, verification.unchanged, verification.samples
+            )));
+        }
+
+        DeviceSanitization {
+            device: self.device,
+            media: self.media,
+            serial_number: self.serial_number,
+            model: self.model,
+            firmware_revision: self.firmware_revision,
+            capacity_bytes: self.capacity_bytes,
+            method: self.method,
+            technique: self.technique,
+            started_at: self.started_at,
+            finished_at: Utc::now(),
+            succeeded: result.is_ok(),
+            error: result.as_ref().err().map(|error| error.to_string()),
+            verification,
+        }
+    }
+}
+
+fn not_performed(note: &str) -> SampledVerification {
+    SampledVerification {
+        verdict: VerificationVerdict::NotPerformed,
+        samples: 0,
+        changed: 0,
+        unchanged: 0,
+        inconclusive: 0,
+        note: Some(note.to_string()),
+    }
+}
+
+/// Compares the samples read before and after sanitization at the same
+/// offsets.
+pub(super) fn compare(before: &[Sample], after: &[Sample]) -> SampledVerification {
+    let mut verification = SampledVerification {
+        verdict: VerificationVerdict::NotPerformed,
+        samples: before.len() as u32,
+        changed: 0,
+        unchanged: 0,
+        inconclusive: 0,
+        note: None,
+    };
+    for (before, after) in before.iter().zip(after) {
+        match (before, after) {
+            (Sample::Data(before), Sample::Data(after)) if before == after => {
+                verification.unchanged += 1
+            }
+            (Sample::Data(_), Sample::Data(_) | Sample::Blank) => verification.changed += 1,
+            _ => verification.inconclusive += 1,
+        }
+    }
+
+    if verification.unchanged > 0 {
+        verification.verdict = VerificationVerdict::Failed;
+    } else if verification.changed > 0 {
+        verification.verdict = VerificationVerdict::Passed;
+    } else {
+        verification.note =
+            Some("no sampled block held readable data before sanitization".to_string());
+    }
+    verification
+}
+
+/// [`SAMPLE_COUNT`] block-aligned offsets spread evenly from the first to
+/// the last full block of a device of `size_bytes`.
+pub(super) fn sample_offsets(size_bytes: u64) -> Vec<u64> {
+    let blocks = size_bytes / SAMPLE_SIZE as u64;
+    if blocks == 0 {
+        return vec![];
+    }
+    let last_block = blocks - 1;
+    let mut offsets: Vec<u64> = (0..SAMPLE_COUNT)
+        .map(|i| i * last_block / (SAMPLE_COUNT - 1) * SAMPLE_SIZE as u64)
+        .collect();
+    offsets.dedup();
+    offsets
+}
+
+/// The size of a block device, from sysfs.
+fn block_device_size(block_device: &str) -> Option<u64> {
+    let name = block_device.trim_start_matches("/dev/");
+    let sectors: u64 = fs::read_to_string(format!("/sys/class/block/{name}/size"))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    // sysfs reports the size in 512-byte sectors regardless of the device's
+    // logical block size.
+    Some(sectors * 512)
+}
+
+async fn wait_for_block_device(path: &str) -> bool {
+    let deadline = tokio::time::Instant::now() + NAMESPACE_APPEAR_TIMEOUT;
+    while !Path::new(path).exists() {
+        if tokio::time::Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    true
+}
+
+async fn read_samples_blocking(path: String, offsets: Vec<u64>) -> Vec<Sample> {
+    let count = offsets.len();
+    tokio::task::spawn_blocking(move || read_samples(&path, &offsets))
+        .await
+        .unwrap_or_else(|_| vec![Sample::Unreadable; count])
+}
+
+/// Reads the blocks at `offsets` with O_DIRECT, so the read after
+/// sanitization comes from the device and not from the page cache the read
+/// before it filled.
+fn read_samples(path: &str, offsets: &[u64]) -> Vec<Sample> {
+    let file = match fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_DIRECT)
+        .open(path)
+    {
+        Ok(file) => file,
+        Err(error) => {
+            tracing::debug!(device = path, %error, "Cannot open device for sampling");
+            return vec![Sample::Unreadable; offsets.len()];
+        }
+    };
+
+    // O_DIRECT needs a buffer aligned to the logical block size.
+    let mut buffer = vec![0u8; SAMPLE_SIZE * 2];
+    let start = buffer.as_ptr().align_offset(SAMPLE_SIZE);
+    let block = &mut buffer[start..start + SAMPLE_SIZE];
+    offsets
+        .iter()
+        .map(|offset| match file.read_exact_at(block, *offset) {
+            Ok(()) if block.iter().all(|byte| *byte == 0) => Sample::Blank,
+            Ok(()) => Sample::Data(Sha256::digest(&*block).to_vec()),
+            Err(_) => Sample::Unreadable,
+        })
+        .collect()
+}
+
+/// Extracts the unit serial number from a SCSI VPD page 0x80, as exposed in
+/// sysfs as `device/vpd_pg80`. SATA disks behind libata expose the ATA serial
+/// number the same way.
+pub(super) fn parse_vpd_serial(page: &[u8]) -> Option<String> {
+    if page.len() < 4 || page[1] != 0x80 {
+        return None;
+    }
+    let length = usize::from(page[3]);
+    let serial = page.get(4..4 + length)?;
+    let serial = String::from_utf8_lossy(serial).trim().to_string();
+    (!serial.is_empty()).then_some(serial)
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::value_scenarios;
+
+    use super::*;
+
+    fn data(byte: u8) -> Sample {
+        Sample::Data(vec![byte])
+    }
+
+    #[test]
+    fn read_back_verdicts() {
+        value_scenarios!(
+            run = |(before, after): (Vec<Sample>, Vec<Sample>)| {
+                let verification = compare(&before, &after);
+                (
+                    verification.verdict,
+                    verification.changed,
+                    verification.unchanged,
+                    verification.inconclusive,
+                )
+            };
+            "data was reached" {
+                (vec![data(1), data(2)], vec![data(3), Sample::Blank]) =>
+                    (VerificationVerdict::Passed, 2, 0, 0),
+                (vec![data(1), Sample::Blank], vec![Sample::Blank, data(9)]) =>
+                    (VerificationVerdict::Passed, 1, 0, 1),
+            }
+            "data survived" {
+                (vec![data(1), data(2)], vec![data(1), Sample::Blank]) =>
+                    (VerificationVerdict::Failed, 1, 1, 0),
+            }
+            "nothing to conclude from" {
+                (vec![Sample::Blank, Sample::Unreadable], vec![Sample::Blank, data(1)]) =>
+                    (VerificationVerdict::NotPerformed, 0, 0, 2),
+                (vec![data(1)], vec![Sample::Unreadable]) =>
+                    (VerificationVerdict::NotPerformed, 0, 0, 1),
+            }
+        );
+    }
+
+    #[test]
+    fn sample_offsets_span_the_device() {
+        value_scenarios!(
+            run = |size: u64| {
+                let offsets = sample_offsets(size);
+                (offsets.len(), offsets.first().copied(), offsets.last().copied())
+            };
+            "large devices" {
+                1_000_204_886_016 => (16, Some(0), Some(1_000_204_881_920)),
+            }
+            "small devices" {
+                3 * 4096 => (3, Some(0), Some(2 * 4096)),
+                4095 => (0, None, None),
+            }
+        );
+    }
+
+    #[test]
+    fn vpd_serial_numbers() {
+        value_scenarios!(
+            run = |page: Vec<u8>| parse_vpd_serial(&page);
+            "serial pages" {
+                [&[0x00, 0x80, 0x00, 0x0a][..], b"  WD-WX1234"].concat() =>
+                    Some("WD-WX123".to_string()),
+                [&[0x00, 0x80, 0x00, 0x08][..], b"ZA1B2C3D"].concat() =>
+                    Some("ZA1B2C3D".to_string()),
+            }
+            "unusable pages" {
+                vec![0x00, 0x83, 0x00, 0x00] => None,
+                vec![0x00, 0x80, 0x00, 0x04, b' ', b' ', b' ', b' '] => None,
+                vec![0x00, 0x80, 0x00, 0x10, b'A'] => None,
+            }
+        );
+    }
+}
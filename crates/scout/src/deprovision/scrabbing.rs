@@ -18,6 +18,10 @@ use std::fs;
 use std::str::FromStr;
 
 use ::rpc::forge as rpc;
+use ::rpc::node_jwt::NodeJwtMinter;
+use ::rpc::sanitization_certificate::{
+    DeviceSanitization, NIST_SP_800_88, SanitizationCertificate, SanitizationMethod, StorageMedia,
+};
 use carbide_host_support::hardware_enumeration::discovery_ibs;
 use carbide_instrument::emit;
 use carbide_uuid::machine::MachineId;
@@ -29,6 +33,7 @@ use tracing::Instrument;
 use crate::cfg::Options;
 use crate::client::create_forge_client;
 use crate::deprovision::cmdrun;
+use crate::deprovision::sanitization::{self, DeviceRecord};
 use crate::metrics::{ScoutStorageDeviceCleanup, StorageDeviceType};
 use crate::{CarbideClientResult, IN_QEMU_VM, platform};
 
@@ -237,10 +242,19 @@ async fn get_best_lba_format(nvmename: &str) -> Result<(u8, u64), CarbideClientE
     }
 }
 
-async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
+async fn clean_this_nvme(
+    nvmename: &String,
+    record: &mut DeviceRecord,
+) -> Result<(), CarbideClientError> {
     tracing::debug!(device = %nvmename, "Cleaning NVMe device");
 
     let nvme_drive_params = get_nvme_params(nvmename).await?;
+    record.identify(
+        &nvme_drive_params.sn,
+        &nvme_drive_params.mn,
+        &nvme_drive_params.fr,
+        nvme_drive_params.tnvmcap,
+    );
 
     let namespaces_supported = nvme_drive_params.oacs & 0x8 == 0x8;
 
@@ -269,6 +283,11 @@ async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
         })?;
 
         tracing::info!(program = lenovo_mnv_cli_prog, "Using Lenovo mnv_cli",);
+        record.sanitized_with(
+            SanitizationMethod::Purge,
+            "mnv_cli passthru Format NVM with SES=1 (user data erase) on both RAID kit drives",
+        );
+        record.skip_verification("the drives are behind the RAID kit and cannot be read directly");
 
         let vd_out = cmdrun::run_prog(lenovo_mnv_cli_prog, ["info", "-o", "vd", "-i", "0"]).await?;
 
@@ -316,8 +335,16 @@ async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
         )
         .await?;
     } else {
+        record.sanitized_with(
+            SanitizationMethod::Purge,
+            "nvme format --ses=2 (cryptographic erase)",
+        );
+        // The first namespace is read back after it is recreated below.
+        record.sample_before(&format!("{nvmename}n1")).await;
+
         // list all namespaces
         let nvmens_output = cmdrun::run_prog(NVME_CLI_PROG, ["list-ns", nvmename, "-a"]).await?;
+        let mut format_failed = false;
 
         // iterate over namespaces
         for nsline in nvmens_output.lines() {
@@ -337,6 +364,7 @@ async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
                     if namespaces_supported {
                         // format can fail if there is a wrong params for namespace. We delete it anyway.
                         tracing::debug!(error = %e, "NVMe format error");
+                        format_failed = true;
                     } else {
                         return Err(e);
                     }
@@ -348,6 +376,20 @@ async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
             }
         }
 
+        if format_failed {
+            // Deleting a namespace only unmaps its blocks, so without the
+            // crypto erase this is at most a Clear.
+            record.sanitized_with(
+                SanitizationMethod::Clear,
+                "namespaces deleted and recreated; nvme format --ses=2 failed",
+            );
+        } else if namespaces_supported {
+            record.sanitized_with(
+                SanitizationMethod::Purge,
+                "nvme format --ses=2 (cryptographic erase); namespaces deleted and recreated",
+            );
+        }
+
         if namespaces_supported {
             let (flbas_index, sector_size) = get_best_lba_format(nvmename).await?;
             let sectors = nvme_drive_params.tnvmcap / sector_size;
@@ -408,7 +450,7 @@ struct CleanupFailure {
     error: CarbideClientError,
 }
 
-async fn all_nvme_cleanup() -> Result<(), CarbideClientError> {
+async fn all_nvme_cleanup() -> (Result<(), CarbideClientError>, Vec<DeviceSanitization>) {
     let mut nvme_devicepaths: Vec<String> = Vec::new();
     if let Ok(paths) = fs::read_dir("/dev") {
         for entry in paths {
@@ -430,7 +472,7 @@ async fn all_nvme_cleanup() -> Result<(), CarbideClientError> {
     let device_count = nvme_devicepaths.len();
     if device_count == 0 {
         tracing::info!("No NVMe devices found to clean");
-        return Ok(());
+        return (Ok(()), vec![]);
     }
 
     tracing::info!(device_count, "Starting NVMe cleanup");
@@ -448,7 +490,9 @@ async fn all_nvme_cleanup() -> Result<(), CarbideClientError> {
                     let device_start = std::time::Instant::now();
 
                     tracing::info!("Starting cleanup");
-                    let result = clean_this_nvme(&nvmename).await;
+                    let mut record = DeviceRecord::new(&nvmename, StorageMedia::Nvme);
+                    let mut result = clean_this_nvme(&nvmename, &mut record).await;
+                    let sanitization = record.finish(&mut result).await;
                     let duration = device_start.elapsed();
 
                     emit(ScoutStorageDeviceCleanup::from_result(
@@ -456,14 +500,12 @@ async fn all_nvme_cleanup() -> Result<(), CarbideClientError> {
                         duration,
                         &result,
                     ));
-                    match result {
-                        Ok(()) => Ok(()),
-                        Err(error) => Err(CleanupFailure {
-                            device,
-                            duration,
-                            error,
-                        }),
-                    }
+                    let result = result.map_err(|error| CleanupFailure {
+                        device,
+                        duration,
+                        error,
+                    });
+                    (result, sanitization)
                 }
                 .instrument(span),
             )
@@ -477,9 +519,11 @@ async fn all_nvme_cleanup() -> Result<(), CarbideClientError> {
     // Collect and categorize results
     let mut errors: Vec<String> = Vec::new();
     let mut success_count = 0;
+    let mut sanitizations = Vec::with_capacity(device_count);
 
     for join_result in results {
-        let cleanup_result = join_result.expect("nvme cleanup task panicked");
+        let (cleanup_result, sanitization) = join_result.expect("nvme cleanup task panicked");
+        sanitizations.push(sanitization);
         match cleanup_result {
             Ok(()) => success_count += 1,
             Err(failure) => errors.push(format!(
@@ -498,10 +542,13 @@ async fn all_nvme_cleanup() -> Result<(), CarbideClientError> {
     );
 
     if !errors.is_empty() {
-        return Err(CarbideClientError::GenericError(errors.join("\n")));
+        return (
+            Err(CarbideClientError::GenericError(errors.join("\n"))),
+            sanitizations,
+        );
     }
 
-    Ok(())
+    (Ok(()), sanitizations)
 }
 
 fn hdparm_has_security_section(output: &str) -> bool {
@@ -679,24 +726,62 @@ fn block_device_cleanup_skip_reason(devname: &str) -> Option<BlockDeviceCleanupS
     )
 }
 
-async fn clean_this_block_device(devpath: &str) -> Result<(), CarbideClientError> {
+async fn clean_this_block_device(
+    devpath: &str,
+    record: &mut DeviceRecord,
+) -> Result<(), CarbideClientError> {
     let devname = devpath.trim_start_matches("/dev/");
+    record.identify(
+        &block_device_serial_number(devname).unwrap_or_default(),
+        &read_block_sysfs_attr(devname, "device/model").unwrap_or_default(),
+        &read_block_sysfs_attr(devname, "device/rev").unwrap_or_default(),
+        read_block_sysfs_attr(devname, "size")
+            .and_then(|sectors| sectors.parse::<u64>().ok())
+            .map_or(0, |sectors| sectors * 512),
+    );
+    record.sample_before(devpath).await;
+
     if is_sata_device(devname) {
         tracing::info!(
             device = devpath,
             "Detected SATA device; using ATA Secure Erase",
         );
+        record.sanitized_with(
+            SanitizationMethod::Purge,
+            "hdparm --security-erase-enhanced (ATA enhanced secure erase)",
+        );
         try_ata_secure_erase(devpath).await
     } else {
         tracing::info!(
             device = devpath,
             "Detected SAS/SCSI device; using SCSI Sanitize",
         );
+        record.sanitized_with(
+            SanitizationMethod::Purge,
+            "sg_sanitize --crypto (SCSI SANITIZE cryptographic erase)",
+        );
         try_scsi_sanitize(devpath).await
     }
 }
 
-async fn all_hdd_cleanup() -> Result<(), CarbideClientError> {
+/// Reads the unit serial number the kernel exposes for SCSI and libata
+/// disks.
+fn block_device_serial_number(devname: &str) -> Option<String> {
+    fs::read(format!("/sys/block/{devname}/device/vpd_pg80"))
+        .ok()
+        .and_then(|page| sanitization::parse_vpd_serial(&page))
+}
+
+/// The media type recorded for a SATA or SAS/SCSI disk.
+fn block_device_media(devname: &str) -> StorageMedia {
+    if is_sata_device(devname) {
+        StorageMedia::Sata
+    } else {
+        StorageMedia::Sas
+    }
+}
+
+async fn all_hdd_cleanup() -> (Result<(), CarbideClientError>, Vec<DeviceSanitization>) {
     let mut block_devicepaths: Vec<String> = Vec::new();
     if let Ok(entries) = fs::read_dir("/sys/block") {
         for entry in entries {
@@ -724,7 +809,7 @@ async fn all_hdd_cleanup() -> Result<(), CarbideClientError> {
     let device_count = block_devicepaths.len();
     if device_count == 0 {
         tracing::info!("No SATA/SAS block devices found to clean");
-        return Ok(());
+        return (Ok(()), vec![]);
     }
 
     tracing::info!(device_count, "Starting HDD/SAS cleanup");
@@ -741,7 +826,10 @@ async fn all_hdd_cleanup() -> Result<(), CarbideClientError> {
                     let device_start = std::time::Instant::now();
 
                     tracing::info!("Starting cleanup");
-                    let result = clean_this_block_device(&devpath).await;
+                    let media = block_device_media(devpath.trim_start_matches("/dev/"));
+                    let mut record = DeviceRecord::new(&devpath, media);
+                    let mut result = clean_this_block_device(&devpath, &mut record).await;
+                    let sanitization = record.finish(&mut result).await;
                     let duration = device_start.elapsed();
 
                     emit(ScoutStorageDeviceCleanup::from_result(
@@ -749,14 +837,12 @@ async fn all_hdd_cleanup() -> Result<(), CarbideClientError> {
                         duration,
                         &result,
                     ));
-                    match result {
-                        Ok(()) => Ok(()),
-                        Err(error) => Err(CleanupFailure {
-                            device,
-                            duration,
-                            error,
-                        }),
-                    }
+                    let result = result.map_err(|error| CleanupFailure {
+                        device,
+                        duration,
+                        error,
+                    });
+                    (result, sanitization)
                 }
                 .instrument(span),
             )
@@ -768,9 +854,11 @@ async fn all_hdd_cleanup() -> Result<(), CarbideClientError> {
 
     let mut errors: Vec<String> = Vec::new();
     let mut success_count = 0;
+    let mut sanitizations = Vec::with_capacity(device_count);
 
     for join_result in results {
-        let cleanup_result = join_result.expect("hdd cleanup task panicked");
+        let (cleanup_result, sanitization) = join_result.expect("hdd cleanup task panicked");
+        sanitizations.push(sanitization);
         match cleanup_result {
             Ok(()) => success_count += 1,
             Err(failure) => errors.push(format!(
@@ -789,10 +877,13 @@ async fn all_hdd_cleanup() -> Result<(), CarbideClientError> {
     );
 
     if !errors.is_empty() {
-        return Err(CarbideClientError::GenericError(errors.join("\n")));
+        return (
+            Err(CarbideClientError::GenericError(errors.join("\n"))),
+            sanitizations,
+        );
     }
 
-    Ok(())
+    (Ok(()), sanitizations)
 }
 
 // #[derive(Debug)]
@@ -1013,7 +1104,33 @@ async fn reset_ib_devices() -> Result<(), CarbideClientError> {
     set_ib_link_up().await
 }
 
-async fn do_cleanup(machine_id: &MachineId) -> CarbideClientResult<rpc::MachineCleanupInfo> {
+/// Signs the disk sanitization certificate for `devices` with the machine's
+/// client certificate key. Returns an empty string, which the API reads as
+/// no certificate, when the key cannot sign.
+fn sign_sanitization_certificate(
+    config: &Options,
+    machine_id: &MachineId,
+    devices: Vec<DeviceSanitization>,
+) -> String {
+    let certificate = SanitizationCertificate {
+        machine_id: *machine_id,
+        standard: NIST_SP_800_88.to_string(),
+        devices,
+    };
+    let minter = NodeJwtMinter::new(config.client_cert.clone(), config.client_key.clone());
+    match certificate.sign(&minter) {
+        Ok(signed) => signed,
+        Err(error) => {
+            tracing::error!(%error, "Could not sign the disk sanitization certificate");
+            String::new()
+        }
+    }
+}
+
+async fn do_cleanup(
+    config: &Options,
+    machine_id: &MachineId,
+) -> CarbideClientResult<rpc::MachineCleanupInfo> {
     let mut cleanup_result = rpc::MachineCleanupInfo {
         machine_id: Some(*machine_id),
         nvme: None,
@@ -1022,6 +1139,7 @@ async fn do_cleanup(machine_id: &MachineId) -> CarbideClientResult<rpc::MachineC
         ib: None,
         hdd: None,
         result: rpc::machine_cleanup_info::CleanupResult::Ok as _,
+        sanitization_certificate: String::new(),
     };
 
     // do nvme/hdd cleanup only if stdin is /dev/null. This is because we afraid to cleanup someone's drives.
@@ -1031,7 +1149,13 @@ async fn do_cleanup(machine_id: &MachineId) -> CarbideClientResult<rpc::MachineC
     };
 
     if stdin_link == "/dev/null" {
-        let (nvme_result, hdd_result) = tokio::join!(all_nvme_cleanup(), all_hdd_cleanup());
+        let ((nvme_result, nvme_devices), (hdd_result, hdd_devices)) =
+            tokio::join!(all_nvme_cleanup(), all_hdd_cleanup());
+        cleanup_result.sanitization_certificate = sign_sanitization_certificate(
+            config,
+            machine_id,
+            nvme_devices.into_iter().chain(hdd_devices).collect(),
+        );
 
         match nvme_result {
             Ok(_) => {
@@ -1138,7 +1262,7 @@ pub(crate) async fn run(config: &Options, machine_id: &MachineId) -> CarbideClie
         return Ok(());
     }
     tracing::info!("Machine cleanup starting, we are running on a host.");
-    let info = do_cleanup(machine_id).await?;
+    let info = do_cleanup(config, machine_id).await?;
     let mut client = create_forge_client(config).await?;
     let request = tonic::Request::new(info);
     client.cleanup_machine_completed(request).await?;
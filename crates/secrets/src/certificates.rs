@@ -14,7 +14,11 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::time::Duration;
+
 use async_trait::async_trait;
+use eyre::eyre;
+use rand::RngExt;
 
 use crate::SecretsError;
 
@@ -33,4 +37,108 @@ pub trait CertificateProvider: Send + Sync {
         alt_names: Option<String>,
         ttl: Option<String>,
     ) -> Result<Certificate, SecretsError>;
+
+    /// A DER-encoded certificate revocation list of `revocations`, signed by
+    /// this provider, if it signs CRLs itself. Vault serves its CRL from the
+    /// PKI mount, so the default is `None`.
+    async fn revocation_list(
+        &self,
+        _revocations: &[Revocation],
+    ) -> Result<Option<Vec<u8>>, SecretsError> {
+        Ok(None)
+    }
+}
+
+/// One revoked certificate, as listed in a CRL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revocation {
+    /// Lowercase hex serial number, without separators.
+    pub serial_number: String,
+    /// Seconds since the Unix epoch.
+    pub revoked_at: i64,
+}
+
+/// The lifetime of a certificate requested without a `ttl`: a baseline skew of
+/// between 60 - 100% of 30 days, so that not all boxes will renew (or expire)
+/// at the same time.
+pub(crate) fn default_ttl() -> Duration {
+    let max_hours = 720; // 24 * 30
+    let min_hours = 432; // 24 * 30 * 0.6
+    let hours: u64 = rand::rng().random_range(min_hours..max_hours);
+    Duration::from_secs(hours * 3600)
+}
+
+/// Parses a requested `ttl` in Vault's duration format: a number with an
+/// optional `s`, `m`, `h` or `d` suffix, seconds when there is none.
+pub(crate) fn parse_ttl(ttl: &str) -> Result<Duration, SecretsError> {
+    let invalid = || SecretsError::GenericError(eyre!("invalid certificate ttl {ttl:?}"));
+    let trimmed = ttl.trim();
+    let (value, unit) = trimmed.split_at(
+        trimmed
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(trimmed.len()),
+    );
+    let value: u64 = value.parse().map_err(|_| invalid())?;
+    let unit_seconds = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    match value.checked_mul(unit_seconds) {
+        Some(0) | None => Err(invalid()),
+        Some(seconds) => Ok(Duration::from_secs(seconds)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_test_support::{Check, check_values};
+
+    use super::*;
+
+    #[test]
+    fn ttls_parse_in_vault_duration_format() {
+        check_values(
+            [
+                Check {
+                    scenario: "bare number is seconds",
+                    input: "90",
+                    expect: Some(Duration::from_secs(90)),
+                },
+                Check {
+                    scenario: "minutes",
+                    input: "15m",
+                    expect: Some(Duration::from_secs(15 * 60)),
+                },
+                Check {
+                    scenario: "hours",
+                    input: "720h",
+                    expect: Some(Duration::from_secs(720 * 3600)),
+                },
+                Check {
+                    scenario: "days",
+                    input: "30d",
+                    expect: Some(Duration::from_secs(30 * 86400)),
+                },
+                Check {
+                    scenario: "zero is rejected",
+                    input: "0h",
+                    expect: None,
+                },
+                Check {
+                    scenario: "unknown unit is rejected",
+                    input: "2w",
+                    expect: None,
+                },
+                Check {
+                    scenario: "missing number is rejected",
+                    input: "h",
+                    expect: None,
+                },
+            ],
+            |ttl| parse_ttl(ttl).ok(),
+        );
+    }
 }
@@ -27,7 +27,6 @@ use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use carbide_instrument::{Event, LabelValue, MetricFamily, emit};
 use eyre::{ContextCompat, WrapErr, eyre};
 use opentelemetry::StringValue;
-use rand::RngExt;
 use tokio::sync::mpsc::{Receiver, Sender};
 use tokio::time::sleep;
 use vaultrs::api::kv2::requests::SetSecretRequestOptions;
@@ -39,7 +38,7 @@ use vaultrs::error::ClientError;
 use vaultrs::{kv2, pki};
 
 use crate::SecretsError;
-use crate::certificates::{Certificate, CertificateProvider};
+use crate::certificates::{Certificate, CertificateProvider, default_ttl};
 use crate::credentials::{
     CredentialKey, CredentialManager, CredentialReader, CredentialWriter, Credentials,
 };
@@ -1050,16 +1049,10 @@ impl VaultTask<Certificate> for GetCertificateHelper {
             &self.unique_identifier,
         );
 
-        let ttl = if let Some(ttl) = self.ttl.clone() {
-            ttl
-        } else {
-            // this is to setup a baseline skew of between 60 - 100% of 30 days,
-            // so that not all boxes will renew (or expire) at the same time.
-            let max_hours = 720; // 24 * 30
-            let min_hours = 432; // 24 * 30 * 0.6
-            let mut rng = rand::rng();
-            format!("{}h", rng.random_range(min_hours..max_hours))
-        };
+        let ttl = self
+            .ttl
+            .clone()
+            .unwrap_or_else(|| format!("{}h", default_ttl().as_secs() / 3600));
 
         let mut certificate_request_builder = GenerateCertificateRequest::builder();
         certificate_request_builder
@@ -17,6 +17,8 @@
 use std::fmt::Display;
 use std::sync::Arc;
 
+use carbide_kms_provider::KmsBackend;
+
 pub use crate::chained_reader::ChainedCredentialReader;
 /// Direct vault access for the narrow cases that need it: `CertificateProvider`
 /// (PKI), and the Transit KMS provider, which builds its own raw vault client
@@ -26,6 +28,7 @@ pub use crate::forge_vault::{
     DedicatedVaultConfig, ForgeVaultClient, SpiffeIdentity, VaultConfig,
     create_dedicated_vault_client, create_raw_vault_client_settings, create_vault_client,
 };
+pub use crate::local_ca::{LocalCaConfig, LocalCertificateAuthority};
 pub use crate::local_credentials::{
     CredentialSnapshot, EnvCredentialsConfig, FileCredentialsConfig, MachineIdentityConfig,
     UsernamePassword,
@@ -36,6 +39,7 @@ pub mod chained_reader;
 pub mod credentials;
 pub mod forge_vault;
 pub mod key_encryption;
+pub mod local_ca;
 pub mod local_credentials;
 pub mod memory_credentials;
 
@@ -61,8 +65,8 @@ pub struct CredentialConfig {
 ///
 /// Certificate vending is independent of the credential store: this lets the
 /// API issue PKI certificates from a different Vault than the one backing
-/// credentials — or, in future, from a non-Vault CA — without disturbing
-/// credential storage.
+/// credentials — or from the built-in local CA — without disturbing credential
+/// storage.
 #[derive(Default, Debug, Clone)]
 pub struct CertificateConfig {
     pub backend: CertBackend,
@@ -70,13 +74,8 @@ pub struct CertificateConfig {
 
 /// Backend used to issue certificates.
 ///
-/// Today both variants are Vault-backed. The enum exists so additional backends
-/// (e.g. an in-process CA whose key lives in a Kubernetes Secret) can be added
-/// without touching the call sites that consume [`CertificateProvider`].
-// The shared `Vault` suffix is intentional: both current variants are Vault
-// backends, distinguished by whether the client is shared with the credential
-// store. The lint resolves once a non-Vault backend is added.
-#[allow(clippy::enum_variant_names)]
+/// Call sites consume [`CertificateProvider`] and never see which backend was
+/// chosen.
 #[derive(Default, Debug, Clone)]
 pub enum CertBackend {
     /// Reuse the credential store's Vault client — one client, one token lease.
@@ -89,6 +88,9 @@ pub enum CertBackend {
     /// config fails fast instead of silently re-pointing at the credential
     /// Vault.
     DedicatedVault(DedicatedVaultConfig),
+    /// Issue certificates in-process from an intermediate CA whose key is
+    /// sealed through the KMS. For sites without a Vault PKI engine.
+    LocalCa(LocalCaConfig),
 }
 
 /// Builds the certificate provider selected by `config`.
@@ -97,10 +99,12 @@ pub enum CertBackend {
 /// for [`CertBackend::SharedVault`] so no second client or token lease is
 /// created in the common case. `spiffe` is the site's SPIFFE identity; a
 /// dedicated Vault issues certs under the same identity namespace as the rest
-/// of the deployment.
-pub fn create_certificate_provider(
+/// of the deployment. `kms` unseals the local CA's key and is required for
+/// [`CertBackend::LocalCa`].
+pub async fn create_certificate_provider(
     config: &CertificateConfig,
     shared_vault: &Arc<ForgeVaultClient>,
+    kms: Option<&dyn KmsBackend>,
     spiffe: SpiffeIdentity,
 ) -> eyre::Result<Arc<dyn CertificateProvider>> {
     match &config.backend {
@@ -113,6 +117,13 @@ pub fn create_certificate_provider(
                 create_dedicated_vault_client(dedicated, spiffe)?;
             Ok(provider)
         }
+        CertBackend::LocalCa(local_ca) => {
+            let kms = kms.ok_or_else(|| {
+                eyre::eyre!("the local CA certificate backend needs a configured KMS")
+            })?;
+            let provider = LocalCertificateAuthority::open(local_ca, kms, spiffe).await?;
+            Ok(Arc::new(provider))
+        }
     }
 }
 
@@ -0,0 +1,521 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! An in-process certificate authority, for sites without Vault's PKI engine.
+//!
+//! The site provisions an intermediate CA certificate and its private key.
+//! The key is stored sealed: encrypted under a fresh data encryption key that
+//! a [`KmsBackend`] wraps, so the file on disk is useless without the KMS.
+//! Leaf certificates carry the same SPIFFE URI SAN and honour the same
+//! `alt_names`/`ttl` requests as the ones Vault issues.
+//!
+//! The CA does not store revocations: [`CertificateProvider::revocation_list`]
+//! signs a CRL of whichever revocations the caller keeps.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use carbide_kms_provider::{EncryptedDek, KmsBackend};
+use eyre::{WrapErr, eyre};
+use rcgen::string::Ia5String;
+use rcgen::{
+    CertificateParams, CertificateRevocationListParams, DistinguishedName, ExtendedKeyUsagePurpose,
+    IsCa, Issuer, KeyIdMethod, KeyPair, KeyUsagePurpose, RevokedCertParams, SanType, SerialNumber,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use zeroize::Zeroizing;
+
+use crate::SecretsError;
+use crate::certificates::{Certificate, CertificateProvider, Revocation, default_ttl, parse_ttl};
+use crate::forge_vault::{SpiffeIdentity, machine_spiffe_uri};
+
+/// Associated data binding a sealed key's ciphertext to its purpose.
+const SEALED_KEY_AAD: &[u8] = b"local_ca/intermediate_key";
+
+/// Leaves are backdated by this much to tolerate clock skew between the CA
+/// and the parties verifying them.
+const NOT_BEFORE_SKEW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub struct LocalCaConfig {
+    /// PEM intermediate CA certificate, optionally followed by the rest of
+    /// its chain.
+    pub ca_cert_path: PathBuf,
+    /// The CA private key, sealed with [`SealedKey::seal`].
+    pub sealed_key_path: PathBuf,
+    /// A plaintext PKCS#8 PEM key to seal into `sealed_key_path` on first
+    /// start, when no sealed key exists yet. Delete it once sealed.
+    pub import_key_path: Option<PathBuf>,
+    /// KEK that seals an imported key.
+    pub kek_id: String,
+    /// How long a generated CRL stays valid (its `nextUpdate`).
+    pub crl_validity: Duration,
+}
+
+/// A private key encrypted under a KMS-wrapped data encryption key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealedKey {
+    pub kek_id: String,
+    pub encrypted_dek: Vec<u8>,
+    pub dek_nonce: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+    pub nonce: Vec<u8>,
+}
+
+impl SealedKey {
+    pub async fn seal(
+        kms: &dyn KmsBackend,
+        kek_id: &str,
+        key_pem: &str,
+    ) -> Result<Self, SecretsError> {
+        let (dek, wrapped_dek) = kms
+            .generate_and_wrap_dek(kek_id)
+            .await
+            .map_err(|e| eyre!("failed to wrap local CA key: {e}"))?;
+        let (ciphertext, nonce) =
+            carbide_kms_provider::crypto::encrypt(&dek, key_pem.as_bytes(), SEALED_KEY_AAD)
+                .map_err(|e| eyre!("failed to seal local CA key: {e}"))?;
+        Ok(Self {
+            kek_id: kek_id.to_string(),
+            encrypted_dek: wrapped_dek.ciphertext,
+            dek_nonce: wrapped_dek.nonce,
+            ciphertext,
+            nonce,
+        })
+    }
+
+    pub async fn unseal(&self, kms: &dyn KmsBackend) -> Result<Zeroizing<String>, SecretsError> {
+        let dek = kms
+            .decrypt_dek(
+                &self.kek_id,
+                &EncryptedDek {
+                    ciphertext: self.encrypted_dek.clone(),
+                    nonce: self.dek_nonce.clone(),
+                },
+            )
+            .await
+            .map_err(|e| eyre!("failed to unwrap local CA key: {e}"))?;
+        let key_pem = Zeroizing::new(
+            carbide_kms_provider::crypto::decrypt(
+                &dek,
+                &self.nonce,
+                &self.ciphertext,
+                SEALED_KEY_AAD,
+            )
+            .map_err(|e| eyre!("failed to unseal local CA key: {e}"))?,
+        );
+        let key_pem =
+            std::str::from_utf8(&key_pem).map_err(|_| eyre!("sealed local CA key is not PEM"))?;
+        Ok(Zeroizing::new(key_pem.to_string()))
+    }
+}
+
+pub struct LocalCertificateAuthority {
+    issuer: Issuer<'static, KeyPair>,
+    /// The issuing certificate alone, returned as a leaf's `issuing_ca`.
+    issuing_ca_pem: String,
+    ca_not_after: OffsetDateTime,
+    spiffe: SpiffeIdentity,
+    crl_validity: Duration,
+}
+
+impl std::fmt::Debug for LocalCertificateAuthority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalCertificateAuthority")
+            .field("ca_not_after", &self.ca_not_after)
+            .field("spiffe", &self.spiffe)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LocalCertificateAuthority {
+    /// Loads the CA certificate and unseals its key, first sealing
+    /// `import_key_path` if no sealed key exists yet.
+    pub async fn open(
+        config: &LocalCaConfig,
+        kms: &dyn KmsBackend,
+        spiffe: SpiffeIdentity,
+    ) -> Result<Self, SecretsError> {
+        let ca_pem = tokio::fs::read_to_string(&config.ca_cert_path)
+            .await
+            .wrap_err_with(|| format!("failed to read {}", config.ca_cert_path.display()))?;
+
+        let sealed = match read_json::<SealedKey>(&config.sealed_key_path).await? {
+            Some(sealed) => sealed,
+            None => {
+                let import_path = config.import_key_path.as_ref().ok_or_else(|| {
+                    eyre!(
+                        "no sealed local CA key at {} and no import_key_path to seal one from",
+                        config.sealed_key_path.display()
+                    )
+                })?;
+                let key_pem = Zeroizing::new(
+                    tokio::fs::read_to_string(import_path)
+                        .await
+                        .wrap_err_with(|| format!("failed to read {}", import_path.display()))?,
+                );
+                let sealed = SealedKey::seal(kms, &config.kek_id, &key_pem).await?;
+                write_json(&config.sealed_key_path, &sealed).await?;
+                tracing::warn!(
+                    sealed_key_path = %config.sealed_key_path.display(),
+                    import_key_path = %import_path.display(),
+                    "Sealed the local CA key; delete the plaintext import key"
+                );
+                sealed
+            }
+        };
+        let key_pem = sealed.unseal(kms).await?;
+
+        Self::new(&ca_pem, &key_pem, spiffe, config.crl_validity)
+    }
+
+    fn new(
+        ca_pem: &str,
+        key_pem: &str,
+        spiffe: SpiffeIdentity,
+        crl_validity: Duration,
+    ) -> Result<Self, SecretsError> {
+        let key = KeyPair::from_pem(key_pem).map_err(|e| eyre!("invalid local CA key: {e}"))?;
+        let params = CertificateParams::from_ca_cert_pem(ca_pem)
+            .map_err(|e| eyre!("invalid local CA certificate: {e}"))?;
+        if !matches!(params.is_ca, IsCa::Ca(_)) {
+            return Err(eyre!("local CA certificate is not a CA certificate").into());
+        }
+        let ca_not_after = params.not_after;
+        let issuing_ca_pem = first_pem_block(ca_pem)
+            .ok_or_else(|| eyre!("local CA certificate file holds no certificate"))?;
+
+        Ok(Self {
+            issuer: Issuer::new(params, key),
+            issuing_ca_pem,
+            ca_not_after,
+            spiffe,
+            crl_validity,
+        })
+    }
+
+    fn issue(
+        &self,
+        unique_identifier: &str,
+        alt_names: Option<&str>,
+        ttl: Duration,
+    ) -> Result<Certificate, SecretsError> {
+        let spiffe_id = machine_spiffe_uri(
+            &self.spiffe.trust_domain,
+            &self.spiffe.machine_base_path,
+            unique_identifier,
+        );
+
+        let mut params = CertificateParams::default();
+        params.distinguished_name = DistinguishedName::new();
+        params.subject_alt_names = vec![SanType::URI(
+            Ia5String::try_from(spiffe_id).map_err(|e| eyre!("invalid SPIFFE ID: {e}"))?,
+        )];
+        for name in alt_names
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            params.subject_alt_names.push(SanType::DnsName(
+                Ia5String::try_from(name).map_err(|e| eyre!("invalid alt name {name:?}: {e}"))?,
+            ));
+        }
+
+        let now = OffsetDateTime::now_utc();
+        params.not_before = now - NOT_BEFORE_SKEW;
+        // Like Vault, never outlive the issuing CA.
+        params.not_after = (now + ttl).min(self.ca_not_after);
+        params.serial_number = Some(random_serial_number());
+        params.is_ca = IsCa::ExplicitNoCa;
+        params.key_usages = vec![
+            KeyUsagePurpose::DigitalSignature,
+            KeyUsagePurpose::KeyAgreement,
+        ];
+        params.extended_key_usages = vec![
+            ExtendedKeyUsagePurpose::ServerAuth,
+            ExtendedKeyUsagePurpose::ClientAuth,
+        ];
+        params.use_authority_key_identifier_extension = true;
+
+        let key = KeyPair::generate().map_err(|e| eyre!("failed to generate key: {e}"))?;
+        let certificate = params
+            .signed_by(&key, &self.issuer)
+            .map_err(|e| eyre!("failed to sign certificate: {e}"))?;
+
+        Ok(Certificate {
+            issuing_ca: self.issuing_ca_pem.clone().into_bytes(),
+            public_key: certificate.pem().into_bytes(),
+            private_key: key.serialize_pem().into_bytes(),
+        })
+    }
+
+    fn sign_crl(&self, revocations: &[Revocation]) -> Result<Vec<u8>, SecretsError> {
+        let now = OffsetDateTime::now_utc();
+        let revoked_certs = revocations
+            .iter()
+            .map(|revocation| {
+                let serial = hex::decode(&revocation.serial_number).map_err(|_| {
+                    eyre!(
+                        "invalid revoked serial number {:?}",
+                        revocation.serial_number
+                    )
+                })?;
+                Ok(RevokedCertParams {
+                    serial_number: SerialNumber::from_slice(&serial),
+                    revocation_time: OffsetDateTime::from_unix_timestamp(revocation.revoked_at)
+                        .map_err(|e| eyre!("invalid revocation time: {e}"))?,
+                    reason_code: None,
+                    invalidity_date: None,
+                })
+            })
+            .collect::<Result<Vec<_>, SecretsError>>()?;
+
+        let params = CertificateRevocationListParams {
+            this_update: now,
+            next_update: now + self.crl_validity,
+            // Seconds keep the number increasing across replicas and restarts.
+            crl_number: SerialNumber::from(now.unix_timestamp().max(0) as u64),
+            issuing_distribution_point: None,
+            revoked_certs,
+            key_identifier_method: KeyIdMethod::Sha256,
+        };
+        let crl = params
+            .signed_by(&self.issuer)
+            .map_err(|e| eyre!("failed to sign CRL: {e}"))?;
+        Ok(crl.der().to_vec())
+    }
+}
+
+#[async_trait]
+impl CertificateProvider for LocalCertificateAuthority {
+    async fn get_certificate(
+        &self,
+        unique_identifier: &str,
+        alt_names: Option<String>,
+        ttl: Option<String>,
+    ) -> Result<Certificate, SecretsError> {
+        let ttl = match ttl {
+            Some(ttl) => parse_ttl(&ttl)?,
+            None => default_ttl(),
+        };
+        self.issue(unique_identifier, alt_names.as_deref(), ttl)
+    }
+
+    async fn revocation_list(
+        &self,
+        revocations: &[Revocation],
+    ) -> Result<Option<Vec<u8>>, SecretsError> {
+        self.sign_crl(revocations).map(Some)
+    }
+}
+
+/// A positive 128-bit serial number, as RFC 5280 requires and Vault issues.
+fn random_serial_number() -> SerialNumber {
+    let mut serial: [u8; 16] = rand::random();
+    serial[0] &= 0x7f;
+    SerialNumber::from_slice(&serial)
+}
+
+/// Canonical form of a hex certificate serial number: lowercase, without
+/// separators or leading zero bytes.
+pub fn normalize_serial(serial_number: &str) -> Result<String, SecretsError> {
+    let digits: String = serial_number
+        .chars()
+        .filter(|c| !matches!(c, ':' | '-' | ' '))
+        .collect();
+    let bytes =
+        hex::decode(&digits).map_err(|_| eyre!("invalid serial number {serial_number:?}"))?;
+    // Certificates encode serials as minimal DER integers.
+    let significant = bytes
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(bytes.len());
+    if significant == bytes.len() {
+        return Err(eyre!("invalid serial number {serial_number:?}").into());
+    }
+    Ok(hex::encode(&bytes[significant..]))
+}
+
+fn first_pem_block(pem: &str) -> Option<String> {
+    const END: &str = "-----END CERTIFICATE-----";
+    let start = pem.find("-----BEGIN CERTIFICATE-----")?;
+    let end = pem[start..].find(END)? + start + END.len();
+    Some(format!("{}\n", &pem[start..end]))
+}
+
+async fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Option<T>, SecretsError> {
+    match tokio::fs::read(path).await {
+        Ok(contents) => Ok(Some(
+            serde_json::from_slice(&contents)
+                .wrap_err_with(|| format!("failed to parse {}", path.display()))?,
+        )),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(eyre!("failed to read {}: {error}", path.display()).into()),
+    }
+}
+
+/// Writes through a temporary file, so readers never see a partial file.
+async fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), SecretsError> {
+    let contents = serde_json::to_vec_pretty(value).map_err(|e| eyre!(e))?;
+    let temporary = path.with_extension(format!(
+        "tmp.{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()
+    ));
+    tokio::fs::write(&temporary, contents)
+        .await
+        .wrap_err_with(|| format!("failed to write {}", temporary.display()))?;
+    tokio::fs::rename(&temporary, path)
+        .await
+        .wrap_err_with(|| format!("failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use carbide_kms_provider::IntegratedKmsProvider;
+    use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair, SanType};
+    use x509_parser::prelude::{FromDer, X509Certificate};
+    use x509_parser::revocation_list::CertificateRevocationList;
+
+    use super::{LocalCaConfig, LocalCertificateAuthority, SealedKey, normalize_serial};
+    use crate::certificates::{CertificateProvider, Revocation};
+    use crate::forge_vault::SpiffeIdentity;
+
+    const KEK_ID: &str = "local-ca-kek";
+
+    fn kms() -> IntegratedKmsProvider {
+        IntegratedKmsProvider::new(HashMap::from([(KEK_ID.to_string(), [7u8; 32])]))
+    }
+
+    fn test_spiffe() -> SpiffeIdentity {
+        SpiffeIdentity {
+            trust_domain: "nico.local".to_string(),
+            machine_base_path: "/forge-system/machine/".to_string(),
+        }
+    }
+
+    /// Writes a fresh intermediate CA and its plaintext key into `dir`.
+    fn provision(dir: &std::path::Path) -> LocalCaConfig {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = params.self_signed(&key).unwrap();
+        std::fs::write(dir.join("ca.pem"), ca.pem()).unwrap();
+        std::fs::write(dir.join("ca.key"), key.serialize_pem()).unwrap();
+        LocalCaConfig {
+            ca_cert_path: dir.join("ca.pem"),
+            sealed_key_path: dir.join("ca.key.sealed"),
+            import_key_path: Some(dir.join("ca.key")),
+            kek_id: KEK_ID.to_string(),
+            crl_validity: Duration::from_secs(3600),
+        }
+    }
+
+    #[tokio::test]
+    async fn sealed_key_round_trips_only_through_its_kms() {
+        let sealed = SealedKey::seal(&kms(), KEK_ID, "key material")
+            .await
+            .unwrap();
+        assert!(!sealed.ciphertext.is_empty());
+        assert_eq!(*sealed.unseal(&kms()).await.unwrap(), "key material");
+
+        let other = IntegratedKmsProvider::new(HashMap::from([(KEK_ID.to_string(), [8u8; 32])]));
+        assert!(sealed.unseal(&other).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn issues_spiffe_leaves_from_an_imported_key() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let config = provision(dir.path());
+        let ca = LocalCertificateAuthority::open(&config, &kms(), test_spiffe())
+            .await
+            .unwrap();
+        assert!(config.sealed_key_path.exists());
+
+        let cert = ca
+            .get_certificate(
+                "fm100ht",
+                Some("host.example, bmc.example".to_string()),
+                Some("2h".to_string()),
+            )
+            .await
+            .unwrap();
+        let leaf =
+            CertificateParams::from_ca_cert_pem(std::str::from_utf8(&cert.public_key).unwrap())
+                .unwrap();
+        let uri = |value: &str| SanType::URI(value.try_into().unwrap());
+        let dns = |value: &str| SanType::DnsName(value.try_into().unwrap());
+        assert_eq!(
+            leaf.subject_alt_names,
+            vec![
+                uri("spiffe://nico.local/forge-system/machine/fm100ht"),
+                dns("host.example"),
+                dns("bmc.example"),
+            ]
+        );
+        let lifetime = leaf.not_after - leaf.not_before;
+        assert!(lifetime <= time::Duration::hours(2) + time::Duration::minutes(1));
+        assert_eq!(
+            cert.issuing_ca,
+            std::fs::read(&config.ca_cert_path).unwrap()
+        );
+
+        // A second start unseals the stored key instead of reimporting it.
+        std::fs::remove_file(config.import_key_path.as_ref().unwrap()).unwrap();
+        LocalCertificateAuthority::open(&config, &kms(), test_spiffe())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn revoked_serials_appear_in_the_crl() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let ca = LocalCertificateAuthority::open(&provision(dir.path()), &kms(), test_spiffe())
+            .await
+            .unwrap();
+        let cert = ca.get_certificate("fm100ht", None, None).await.unwrap();
+        let pem = x509_parser::pem::parse_x509_pem(&cert.public_key)
+            .unwrap()
+            .1;
+        let (_, leaf) = X509Certificate::from_der(&pem.contents).unwrap();
+        let serial = leaf.raw_serial_as_string();
+
+        assert!(normalize_serial("not hex").is_err());
+        let revocations = [Revocation {
+            serial_number: normalize_serial(&serial).unwrap(),
+            revoked_at: 1_700_000_000,
+        }];
+
+        let der = ca.revocation_list(&revocations).await.unwrap().unwrap();
+        let (_, crl) = CertificateRevocationList::from_der(&der).unwrap();
+        let revoked: Vec<_> = crl
+            .iter_revoked_certificates()
+            .map(|revoked| revoked.raw_serial().to_vec())
+            .collect();
+        assert_eq!(revoked, vec![leaf.raw_serial().to_vec()]);
+        assert_eq!(crl.issuer(), leaf.issuer());
+    }
+}
@@ -57,6 +57,16 @@ pub struct IterationConfig {
     pub metric_hold_time: std::time::Duration,
 }
 
+impl IterationConfig {
+    /// The longest time an object that waits for a deadline is kept out of
+    /// state handling. Objects need to be handled more often than
+    /// `metric_hold_time`, or they would drop out of the state metrics while
+    /// waiting.
+    pub fn max_recheck_delay(&self) -> Duration {
+        self.metric_hold_time / 2
+    }
+}
+
 impl Default for IterationConfig {
     fn default() -> Self {
         Self {
@@ -29,6 +29,7 @@ mod builder;
 pub mod db;
 mod enqueuer;
 pub use enqueuer::Enqueuer;
+mod notification_listener;
 
 pub mod periodic_enqueuer;
 pub mod processor;
@@ -95,6 +96,7 @@ impl<'r> FromRow<'r, PgRow> for QueuedObject {
 pub struct StateController<IO: StateControllerIO> {
     enqueuer: PeriodicEnqueuer<IO>,
     processor: processor::StateProcessor<IO>,
+    notification_listener: notification_listener::NotificationListener<IO>,
 }
 
 impl<IO: StateControllerIO> StateController<IO> {
@@ -15,16 +15,18 @@
  * limitations under the License.
  */
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use db::work_lock_manager::WorkLockManagerHandle;
 use opentelemetry::metrics::Meter;
+use tokio::sync::Notify;
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 
 use crate::config::IterationConfig;
 use crate::controller::StateController;
+use crate::controller::notification_listener::NotificationListener;
 use crate::controller::periodic_enqueuer::{EnqueuerMetricsEmitter, PeriodicEnqueuer};
 use crate::controller::processor::{ProcessorMetricsEmitter, StateProcessor};
 use crate::io::StateControllerIO;
@@ -133,6 +135,14 @@ impl<IO: StateControllerIO> Builder<IO> {
                 build_or_spawn.controller_name
             ))
             .spawn(async move { build_or_spawn.controller.processor.run().await })?;
+
+        join_set
+            .build_task()
+            .name(&format!(
+                "state_controller_notification_listener {}",
+                build_or_spawn.controller_name
+            ))
+            .spawn(async move { build_or_spawn.controller.notification_listener.run().await })?;
         Ok(())
     }
 
@@ -199,6 +209,14 @@ impl<IO: StateControllerIO> Builder<IO> {
         let processor_metric_emitter =
             meter.map(|meter| ProcessorMetricsEmitter::new(&controller_name, &meter));
 
+        let wake = Arc::new(Notify::new());
+        let notification_listener = NotificationListener::<IO> {
+            pool: database.clone(),
+            wake: wake.clone(),
+            cancel_token: cancel_token.clone(),
+            _phantom_io: std::marker::PhantomData,
+        };
+
         let processor = StateProcessor::<IO> {
             pool: database,
             cancel_token,
@@ -213,6 +231,8 @@ impl<IO: StateControllerIO> Builder<IO> {
             object_tasks: JoinSet::new(),
             completed_objects: HashSet::new(),
             requeue_objects: HashSet::new(),
+            deferred_objects: HashMap::new(),
+            wake,
             object_metrics: Default::default(),
             last_log_time: std::time::Instant::now(),
             stats_since_last_log: Default::default(),
@@ -223,6 +243,7 @@ impl<IO: StateControllerIO> Builder<IO> {
         let controller = StateController::<IO> {
             processor,
             enqueuer,
+            notification_listener,
         };
 
         Ok(BuildOrSpawn {
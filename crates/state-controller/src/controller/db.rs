@@ -17,6 +17,7 @@
 
 //! Database access methods used in the StateController framework
 
+use chrono::{DateTime, Utc};
 use db::work_lock_manager::{AcquireLockError, WorkLockManagerHandle};
 use db::{BIND_LIMIT, DatabaseError};
 use sqlx::{PgConnection, PgPool};
@@ -205,10 +206,13 @@ pub async fn acquire_queued_objects(
             SELECT object_id FROM ",
     );
     query.push(table_id);
-    query.push(" WHERE (processed_by IS NULL OR processing_started_at + ");
+    query.push(
+        " WHERE ((processed_by IS NULL AND (run_after IS NULL OR run_after <= now()))
+            OR (processed_by IS NOT NULL AND processing_started_at + ",
+    );
     query.push_bind(max_outdated);
     query.push(
-        "::interval < now())
+        "::interval < now()))
             ORDER BY processing_started_at ASC
             FOR UPDATE SKIP LOCKED
             LIMIT ",
@@ -219,7 +223,7 @@ pub async fn acquire_queued_objects(
     query.push(" SET processed_by=");
     query.push_bind(processor_id);
     query.push(
-        ", processing_started_at=now() WHERE object_id in (SELECT object_id FROM dequeued_ids) RETURNING *",
+        ", processing_started_at=now(), run_after=NULL, notified=false WHERE object_id in (SELECT object_id FROM dequeued_ids) RETURNING *",
     );
 
     let result = query
@@ -231,6 +235,10 @@ pub async fn acquire_queued_objects(
     Ok(result)
 }
 
+/// Removes the entries of objects which have been processed by `processor_id`.
+/// Objects which were requested again via [`request_objects`] while being
+/// processed are released for another run instead of being deleted, so the
+/// request is not lost.
 pub async fn delete_queued_objects(
     txn: &mut PgConnection,
     table_id: &str,
@@ -245,6 +253,26 @@ pub async fn delete_queued_objects(
 
     let mut num_deleted = 0;
     for queued_objects in object_ids.chunks(OBJECTS_PER_QUERY) {
+        let mut builder = sqlx::QueryBuilder::new("UPDATE ");
+        builder.push(table_id);
+        builder.push(
+            " SET processed_by = NULL, processing_started_at = now(), notified = false
+            WHERE notified AND object_id IN(",
+        );
+        let mut separated = builder.separated(", ");
+        for object_id in queued_objects.iter() {
+            separated.push_bind(object_id);
+        }
+        builder.push(") AND processed_by = ");
+        builder.push_bind(processor_id);
+
+        let result = builder
+            .build()
+            .execute(&mut *txn)
+            .await
+            .map_err(|e| DatabaseError::new("StateController::release_queued_objects", e))?;
+        num_deleted += result.rows_affected();
+
         let mut builder = sqlx::QueryBuilder::new("DELETE FROM ");
         builder.push(table_id);
         builder.push(" WHERE object_id IN(");
@@ -266,3 +294,116 @@ pub async fn delete_queued_objects(
 
     Ok(num_deleted as usize)
 }
+
+/// Releases objects which have been processed by `processor_id` and keeps them
+/// queued until the given time, so neither the periodic enqueuer nor any
+/// processor picks them up before it. Objects which were requested again via
+/// [`request_objects`] while being processed are released immediately.
+pub async fn defer_queued_objects(
+    txn: &mut PgConnection,
+    table_id: &str,
+    deferred_objects: &[(String, DateTime<Utc>)],
+    processor_id: &str,
+) -> Result<usize, DatabaseError> {
+    if deferred_objects.is_empty() {
+        return Ok(0);
+    }
+    let (object_ids, run_after): (Vec<&str>, Vec<DateTime<Utc>>) = deferred_objects
+        .iter()
+        .map(|(object_id, run_after)| (object_id.as_str(), *run_after))
+        .unzip();
+
+    let mut builder = sqlx::QueryBuilder::new("UPDATE ");
+    builder.push(table_id);
+    builder.push(
+        " AS q SET processed_by = NULL, processing_started_at = now(),
+            run_after = CASE WHEN q.notified THEN NULL ELSE d.run_after END,
+            notified = false
+        FROM UNNEST(",
+    );
+    builder.push_bind(object_ids);
+    builder.push("::text[], ");
+    builder.push_bind(run_after);
+    builder.push(
+        "::timestamptz[]) AS d(object_id, run_after)
+        WHERE q.object_id = d.object_id AND q.processed_by = ",
+    );
+    builder.push_bind(processor_id);
+
+    let result = builder
+        .build()
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::new("StateController::defer_queued_objects", e))?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// Requests state handling for objects whose inputs changed.
+///
+/// Unlike [`queue_objects`], this brings objects that have been deferred until
+/// a later time forward, and marks objects that are currently being processed
+/// so that they are handled once more after the current run. Returns the
+/// amount of objects for which a new run was requested.
+pub async fn request_objects(
+    txn: &mut PgConnection,
+    table_id: &str,
+    object_ids: &[String],
+) -> Result<usize, DatabaseError> {
+    // See `queue_objects` for why the IDs are sorted
+    let mut sorted = object_ids.to_vec();
+    sorted.sort();
+    const OBJECTS_PER_QUERY: usize = BIND_LIMIT / 32;
+
+    let mut num_requested = 0;
+    for object_ids in sorted.chunks(OBJECTS_PER_QUERY) {
+        let mut builder = sqlx::QueryBuilder::new("INSERT INTO ");
+        builder.push(table_id);
+        builder.push(" AS q (object_id)");
+        builder.push_values(object_ids, |mut b, object_id| {
+            b.push_bind(object_id);
+        });
+        builder.push(
+            " ON CONFLICT (object_id) DO UPDATE
+            SET run_after = NULL, notified = q.processed_by IS NOT NULL
+            WHERE q.run_after IS NOT NULL OR (q.processed_by IS NOT NULL AND NOT q.notified)",
+        );
+
+        let result = builder
+            .build()
+            .execute(&mut *txn)
+            .await
+            .map_err(|e| DatabaseError::new("StateController::request_objects", e))?;
+        num_requested += result.rows_affected() as usize;
+    }
+
+    Ok(num_requested)
+}
+
+/// The channel on which changes to objects of the state controller that uses
+/// the queued objects table `table_id` are announced.
+pub fn notification_channel(table_id: &str) -> String {
+    format!("{table_id}_changed")
+}
+
+/// Announces that objects have been requested for state handling, so that
+/// state processors dispatch them without waiting for their next poll.
+///
+/// Notifications are only delivered once the transaction commits.
+pub async fn notify_requested_objects(
+    txn: &mut PgConnection,
+    table_id: &str,
+    object_ids: &[String],
+) -> Result<(), DatabaseError> {
+    if object_ids.is_empty() {
+        return Ok(());
+    }
+    let query = "SELECT pg_notify($1, object_id) FROM UNNEST($2::text[]) AS object_id";
+    sqlx::query(query)
+        .bind(notification_channel(table_id))
+        .bind(object_ids)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
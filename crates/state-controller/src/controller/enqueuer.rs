@@ -16,6 +16,7 @@
  */
 
 use ::db::DatabaseError;
+use sqlx::PgConnection;
 
 use super::db;
 use crate::io::StateControllerIO;
@@ -36,16 +37,29 @@ impl<IO: StateControllerIO> Enqueuer<IO> {
     }
 
     /// Requests state handling for the given object
+    ///
+    /// This also applies to objects whose state handler is waiting for a
+    /// deadline. State processors are notified and dispatch the object without
+    /// waiting for their next poll.
     pub async fn enqueue_object(&self, object_id: &IO::ObjectId) -> Result<bool, DatabaseError> {
         let mut conn = self.pool.acquire().await.map_err(DatabaseError::acquire)?;
 
-        let num_enqueued = db::queue_objects(
-            &mut conn,
-            IO::DB_QUEUED_OBJECTS_TABLE_NAME,
-            &[object_id.to_string()],
-        )
-        .await?;
+        Self::enqueue_object_in_txn(&mut conn, object_id).await
+    }
+
+    /// Requests state handling for the given object as part of the caller's
+    /// transaction, e.g. the one that writes the change the state handler
+    /// needs to react to. State processors are only notified once the
+    /// transaction commits, so the handler is guaranteed to observe the change.
+    pub async fn enqueue_object_in_txn(
+        txn: &mut PgConnection,
+        object_id: &IO::ObjectId,
+    ) -> Result<bool, DatabaseError> {
+        let object_ids = [object_id.to_string()];
+        let num_requested =
+            db::request_objects(txn, IO::DB_QUEUED_OBJECTS_TABLE_NAME, &object_ids).await?;
+        db::notify_requested_objects(txn, IO::DB_QUEUED_OBJECTS_TABLE_NAME, &object_ids).await?;
 
-        Ok(num_enqueued == 1)
+        Ok(num_requested == 1)
     }
 }
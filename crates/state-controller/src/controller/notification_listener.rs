@@ -0,0 +1,92 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use sqlx::postgres::PgListener;
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+
+use super::db;
+use crate::io::StateControllerIO;
+
+/// How long to wait before trying to listen again after the connection failed
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Listens for the Postgres notifications sent when objects are requested
+/// through an [`Enqueuer`](super::Enqueuer), and wakes the state processor so
+/// that it dispatches them right away.
+///
+/// Notifications only shorten the time until objects are dispatched: the
+/// requests are stored in the queued objects table, and the processor still
+/// polls it. Notifications that are missed while the connection is down
+/// therefore only delay processing.
+pub(super) struct NotificationListener<IO: StateControllerIO> {
+    pub(super) pool: sqlx::PgPool,
+    pub(super) wake: Arc<Notify>,
+    pub(super) cancel_token: CancellationToken,
+    pub(super) _phantom_io: std::marker::PhantomData<IO>,
+}
+
+impl<IO: StateControllerIO> NotificationListener<IO> {
+    /// Listens for notifications until the cancel token is cancelled
+    pub(super) async fn run(self) {
+        let channel = db::notification_channel(IO::DB_QUEUED_OBJECTS_TABLE_NAME);
+
+        while !self.cancel_token.is_cancelled() {
+            if let Err(e) = self.listen(&channel).await {
+                tracing::warn!(
+                    controller = IO::LOG_SPAN_CONTROLLER_NAME,
+                    channel,
+                    error = %e,
+                    "Failed to listen for object notifications"
+                );
+                // Requests made in the meantime are not lost, but the processor
+                // only sees them on its next poll.
+                self.wake.notify_one();
+                self.cancel_token
+                    .run_until_cancelled(tokio::time::sleep(RETRY_INTERVAL))
+                    .await;
+            }
+        }
+
+        tracing::info!(
+            controller = IO::LOG_SPAN_CONTROLLER_NAME,
+            "NotificationListener stop was requested"
+        );
+    }
+
+    async fn listen(&self, channel: &str) -> Result<(), sqlx::Error> {
+        let mut listener = PgListener::connect_with(&self.pool).await?;
+        listener.listen(channel).await?;
+
+        loop {
+            let Some(notification) = self.cancel_token.run_until_cancelled(listener.recv()).await
+            else {
+                return Ok(());
+            };
+            let notification = notification?;
+            tracing::trace!(
+                controller = IO::LOG_SPAN_CONTROLLER_NAME,
+                object_id = notification.payload(),
+                "Object was requested for state handling"
+            );
+            self.wake.notify_one();
+        }
+    }
+}
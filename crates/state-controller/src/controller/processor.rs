@@ -20,12 +20,14 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use ::db::{DatabaseError, Transaction};
+use chrono::{DateTime, Utc};
 use config_version::Versioned;
 use model::controller_outcome::PersistentStateHandlerOutcome;
 use opentelemetry::KeyValue;
 use opentelemetry::metrics::{Counter, Histogram, Meter};
 use rand::RngExt;
 use sqlx_query_tracing::SqlxQueryDataAggregation;
+use tokio::sync::Notify;
 use tokio::task::{JoinError, JoinSet};
 use tokio_util::sync::CancellationToken;
 use tracing::Instrument;
@@ -81,6 +83,12 @@ pub(super) struct StateProcessor<IO: StateControllerIO> {
     /// Objects for which another object handling task should be queued since
     /// the state handler returned `Transition`
     pub(super) requeue_objects: HashSet<IO::ObjectId>,
+    /// Completed objects whose state handler asked to wait until a certain
+    /// time. Their queue entries are kept until then instead of being deleted.
+    pub(super) deferred_objects: HashMap<IO::ObjectId, DateTime<Utc>>,
+    /// Signaled when objects have been requested for state handling, to
+    /// dispatch them without waiting for the end of the dispatch interval.
+    pub(super) wake: Arc<Notify>,
     /// The last time a log message had been emitted
     pub(super) last_log_time: std::time::Instant,
     /// The last time aggregate metrics had been emitted
@@ -94,6 +102,8 @@ pub(super) struct StateProcessor<IO: StateControllerIO> {
 pub(super) struct ObjectHandlingTaskResult<IO: StateControllerIO> {
     object_id: IO::ObjectId,
     metrics: ObjectHandlerMetrics<IO>,
+    /// The time until which the state handler asked to wait
+    recheck_at: Option<DateTime<Utc>>,
 }
 
 pub(super) struct CollectedMetrics<IO: StateControllerIO> {
@@ -138,6 +148,8 @@ pub(super) struct StatsSinceLastLog {
     num_deleted_queued_objects: usize,
     /// The amount of objects which have been queued again for statehandling
     num_requeued_objects: usize,
+    /// The amount of objects which have been kept queued until a later time
+    num_deferred_objects: usize,
     /// The aggregated sqlx metrics at the last time logs had been emitted
     db_query_metrics: SqlxQueryDataAggregation,
 }
@@ -176,9 +188,15 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
 
             // The iteration might not have used up all of dispatch_interval in case
             // all dispatched tasks finished earlier. In this case we wait the configured
-            // time before the next dispatch.
+            // time before the next dispatch, unless objects get requested in the meantime.
+            let wake = self.wake.clone();
             self.cancel_token
-                .run_until_cancelled(tokio::time::sleep_until(next_dispatch_at.into()))
+                .run_until_cancelled(async {
+                    tokio::select! {
+                        _ = tokio::time::sleep_until(next_dispatch_at.into()) => {}
+                        _ = wake.notified() => {}
+                    }
+                })
                 .await;
         }
 
@@ -323,6 +341,7 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
             completed_task_count = stats.num_completed_tasks,
             dispatched_task_count = stats.num_dispatched_tasks,
             requeued_object_count = stats.num_requeued_objects,
+            deferred_object_count = stats.num_deferred_objects,
             errored_task_count = stats.num_errored_tasks,
             sql_query_count = db_metrics_since_last_query.num_queries,
             sql_affected_row_count = db_metrics_since_last_query.total_rows_affected,
@@ -470,7 +489,7 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
             .name(&format!("state_processor {object_id}"))
             .spawn(
                 async move {
-                    let metrics = process_object(
+                    let (metrics, recheck_at) = process_object(
                         cloned_object_id.clone(),
                         pool,
                         services,
@@ -486,6 +505,7 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
                     ObjectHandlingTaskResult {
                         object_id: cloned_object_id,
                         metrics,
+                        recheck_at,
                     }
                 }
                 .in_current_span(),
@@ -501,8 +521,14 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
         let object_ids: Vec<String> = self
             .completed_objects
             .iter()
+            .filter(|id| !self.deferred_objects.contains_key(id))
             .map(|id| id.to_string())
             .collect();
+        let deferred_objects: Vec<(String, DateTime<Utc>)> = self
+            .deferred_objects
+            .iter()
+            .map(|(id, recheck_at)| (id.to_string(), *recheck_at))
+            .collect();
         let queue_objects: Vec<String> = self
             .requeue_objects
             .iter()
@@ -517,6 +543,13 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
             &self.processor_id,
         )
         .await?;
+        let num_deferred = db::defer_queued_objects(
+            &mut txn,
+            IO::DB_QUEUED_OBJECTS_TABLE_NAME,
+            &deferred_objects,
+            &self.processor_id,
+        )
+        .await?;
         let num_requeued =
             db::queue_objects(&mut txn, IO::DB_QUEUED_OBJECTS_TABLE_NAME, &queue_objects).await?;
         txn.commit().await?;
@@ -525,14 +558,20 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
             object_ids.len() == num_deleted,
             "BUG: Not all objects have been deleted from the database after cleanup"
         );
+        test_assert!(
+            deferred_objects.len() == num_deferred,
+            "BUG: Not all deferred objects have been released after cleanup"
+        );
 
         self.stats_since_last_log.num_deleted_queued_objects += num_deleted;
         self.stats_since_last_log.num_requeued_objects += num_requeued;
+        self.stats_since_last_log.num_deferred_objects += num_deferred;
         if let Some(emitter) = &self.metric_emitter {
             emitter.requeued_tasks_counter.add(num_requeued as u64, &[]);
         }
         self.completed_objects.clear();
         self.requeue_objects.clear();
+        self.deferred_objects.clear();
         Ok(())
     }
 
@@ -555,6 +594,17 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
                 || task_result.metrics.common.transition_conflict)
         {
             self.requeue_objects.insert(task_result.object_id.clone());
+        } else if let Some(recheck_at) = task_result.recheck_at {
+            // Deferring for longer than the metric hold time would evict the
+            // object from the state metrics while it is waiting.
+            let now = Utc::now();
+            let latest = now
+                + chrono::Duration::from_std(self.iteration_config.max_recheck_delay())
+                    .unwrap_or_default();
+            if recheck_at > now {
+                self.deferred_objects
+                    .insert(task_result.object_id.clone(), recheck_at.min(latest));
+            }
         }
 
         self.stats_since_last_log.num_completed_tasks += 1;
@@ -640,10 +690,14 @@ async fn process_object<IO: StateControllerIO>(
     metrics_emitter: Option<Arc<StateProcessorMetricEmitter<IO>>>,
     state_change_emitter: Arc<StateChangeEmitter<IO::ObjectId, IO::ControllerState>>,
     per_object_state: Option<PerObjectStateRecorder>,
-) -> ObjectHandlerMetrics<IO> {
+) -> (ObjectHandlerMetrics<IO>, Option<DateTime<Utc>>) {
     let mut metrics = ObjectHandlerMetrics::<IO>::default();
 
     let start = Instant::now();
+    // The version persisted with `metrics.common.next_state`, for state change hooks
+    let mut next_state_version = None;
+    // The time until which a committed `Wait` outcome asked to wait
+    let mut recheck_at = None;
 
     // Note that this inner async block is required to be able to use
     // the ? operator in the inner block, and then return a `Result`
@@ -711,6 +765,7 @@ async fn process_object<IO: StateControllerIO>(
         };
 
         let mut next_state = None;
+        let mut next_state_entered_version = None;
         let mut next_state_entered_at = None;
         let mut next_state_sla = None;
         if let Ok(StateHandlerOutcome::Transition {
@@ -723,6 +778,7 @@ async fn process_object<IO: StateControllerIO>(
                 tracing::warn!(next_state = ?next, %object_id, "Transition to current state");
             }
             let new_version = controller_state.version.increment();
+            next_state_entered_version = Some(new_version);
             next_state_entered_at = Some(new_version.timestamp());
             // Resolve the SLA of the state being entered, so the committing
             // iteration publishes it without a one-iteration gap.
@@ -750,12 +806,17 @@ async fn process_object<IO: StateControllerIO>(
                 tracing::info!(state=?next, %object_id, "Transition skipped: state version changed concurrently");
                 metrics.common.transition_conflict = true;
                 next_state = None;
+                next_state_entered_version = None;
                 next_state_entered_at = None;
                 next_state_sla = None;
             }
         }
 
         let is_success = handler_outcome.is_ok();
+        let requested_recheck_at = handler_outcome
+            .as_ref()
+            .ok()
+            .and_then(StateHandlerOutcome::recheck_at);
 
         // If the state handler neither transitioned nor returned no error,
         // but the object is stuck in the state for longer than the defined SLA,
@@ -783,6 +844,7 @@ async fn process_object<IO: StateControllerIO>(
             }
 
             txn.commit().await.map_err(StateHandlerError::from)?;
+            recheck_at = requested_recheck_at;
         } else if !matches!(handler_outcome, Ok(StateHandlerOutcome::Deleted { .. })) {
             // Whatever is the reason, outcome must be stored in db.
             let _ = txn.rollback().await;
@@ -796,6 +858,7 @@ async fn process_object<IO: StateControllerIO>(
         // committed and we are sure we reached the next state
         metrics.common.next_state = next_state;
         if metrics.common.next_state.is_some() {
+            next_state_version = next_state_entered_version;
             metrics.common.state_entered_at = next_state_entered_at;
             metrics.common.sla = next_state_sla;
         }
@@ -806,12 +869,15 @@ async fn process_object<IO: StateControllerIO>(
     metrics.common.handler_latency = start.elapsed();
 
     // Emit the state changed event to registered hooks
-    if let Some(next_state) = &metrics.common.next_state {
+    if let (Some(next_state), Some(state_version)) =
+        (&metrics.common.next_state, next_state_version)
+    {
         state_change_emitter.emit(StateChangeEvent {
             object_id: &object_id,
             #[cfg(any(test, feature = "test-support"))]
             previous_state: metrics.common.initial_state.as_ref(),
             new_state: next_state,
+            state_version,
             timestamp: chrono::Utc::now(),
         });
     }
@@ -905,7 +971,7 @@ async fn process_object<IO: StateControllerIO>(
         }
     }
 
-    metrics
+    (metrics, recheck_at)
 }
 
 #[derive(Debug)]
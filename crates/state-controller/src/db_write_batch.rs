@@ -54,6 +54,12 @@ pub trait WriteOp: Send {
         self: Box<Self>,
         txn: &'a mut PgTransaction<'t>,
     ) -> Result<(), StateHandlerError>;
+
+    /// A short description of the write, shown when a batch is explained
+    /// instead of applied.
+    fn describe(&self) -> String {
+        "deferred database write".to_string()
+    }
 }
 
 impl std::fmt::Debug for DbWriteBatch {
@@ -90,6 +96,11 @@ impl DbWriteBatch {
         self.writes.push(Box::new(op));
     }
 
+    /// Describes the pending writes in the order they would be applied.
+    pub fn describe(&self) -> Vec<String> {
+        self.writes.iter().map(|w| w.describe()).collect()
+    }
+
     pub async fn apply_all(self, txn: &mut PgTransaction<'_>) -> Result<(), StateHandlerError> {
         for w in self.writes {
             w.apply(txn).await?;
@@ -0,0 +1,279 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Dry runs of a single state handler iteration.
+//!
+//! [`explain_object`] loads an object the way the processor does and runs the
+//! state handler on it once, but persists nothing: the transaction the handler
+//! returns is rolled back, and the [`DbWriteBatch`] is described instead of
+//! applied. External systems are kept out by handing the handler services
+//! whose clients record what they would have done into a
+//! [`SideEffectRecorder`] instead of doing it. Building those services is up
+//! to the caller, since only it knows what its services hold.
+
+use std::panic::Location;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use config_version::ConfigVersion;
+use sqlx::PgPool;
+
+use crate::db_write_batch::DbWriteBatch;
+use crate::io::StateControllerIO;
+use crate::state_handler::{
+    StateHandler, StateHandlerContext, StateHandlerContextObjects, StateHandlerError,
+    StateHandlerOutcome,
+};
+
+/// The `service` of side effects on the state controller's own database.
+pub const DATABASE_SERVICE: &str = "database";
+
+/// Something a state handler would have done outside of its own process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SideEffect {
+    /// The system the side effect targets, e.g. `redfish` or `database`
+    pub service: &'static str,
+    /// What would have been done, usually the name of the client method
+    pub operation: String,
+    /// What it would have been done to, e.g. a BMC address. Empty if the
+    /// service has only one target.
+    pub target: String,
+    /// Arguments worth showing, if any
+    pub details: Option<String>,
+}
+
+impl SideEffect {
+    pub fn new(
+        service: &'static str,
+        operation: impl Into<String>,
+        target: impl Into<String>,
+    ) -> Self {
+        Self {
+            service,
+            operation: operation.into(),
+            target: target.into(),
+            details: None,
+        }
+    }
+
+    pub fn with_details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+}
+
+/// Collects the side effects of a dry run. Clones share the same list, so
+/// every recording client built for one dry run can hold its own clone.
+#[derive(Debug, Clone, Default)]
+pub struct SideEffectRecorder {
+    effects: Arc<Mutex<Vec<SideEffect>>>,
+}
+
+impl SideEffectRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, effect: SideEffect) {
+        self.effects
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(effect);
+    }
+
+    /// Returns the side effects recorded so far, in the order they happened,
+    /// and clears the list.
+    pub fn take(&self) -> Vec<SideEffect> {
+        std::mem::take(
+            &mut *self
+                .effects
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        )
+    }
+}
+
+/// The outcome of a dry run, without the transaction the handler returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExplainedOutcome<S> {
+    Wait {
+        reason: String,
+        recheck_at: Option<DateTime<Utc>>,
+        source_ref: &'static Location<'static>,
+    },
+    Transition {
+        next_state: S,
+        source_ref: &'static Location<'static>,
+    },
+    DoNothing {
+        source_ref: &'static Location<'static>,
+    },
+    Deleted {
+        source_ref: &'static Location<'static>,
+    },
+}
+
+impl<S> ExplainedOutcome<S> {
+    /// Where in the state handler the outcome was produced.
+    pub fn source_ref(&self) -> &'static Location<'static> {
+        match self {
+            ExplainedOutcome::Wait { source_ref, .. }
+            | ExplainedOutcome::Transition { source_ref, .. }
+            | ExplainedOutcome::DoNothing { source_ref }
+            | ExplainedOutcome::Deleted { source_ref } => source_ref,
+        }
+    }
+}
+
+impl<S> From<StateHandlerOutcome<S>> for ExplainedOutcome<S> {
+    fn from(outcome: StateHandlerOutcome<S>) -> Self {
+        match outcome {
+            StateHandlerOutcome::Wait {
+                reason,
+                recheck_at,
+                source_ref,
+                txn: _,
+            } => ExplainedOutcome::Wait {
+                reason,
+                recheck_at,
+                source_ref,
+            },
+            StateHandlerOutcome::Transition {
+                next_state,
+                source_ref,
+                txn: _,
+            } => ExplainedOutcome::Transition {
+                next_state,
+                source_ref,
+            },
+            StateHandlerOutcome::DoNothing { source_ref, txn: _ } => {
+                ExplainedOutcome::DoNothing { source_ref }
+            }
+            StateHandlerOutcome::Deleted {
+                _source_ref,
+                txn: _,
+            } => ExplainedOutcome::Deleted {
+                source_ref: _source_ref,
+            },
+        }
+    }
+}
+
+/// What the next iteration of a state handler would do to one object.
+#[derive(Debug)]
+pub struct Explanation<S> {
+    /// The controller state the handler ran against
+    pub controller_state: S,
+    pub state_version: ConfigVersion,
+    /// Whether the object has been in its state for longer than its SLA. The
+    /// processor turns `Wait` and `DoNothing` outcomes into errors if so.
+    pub time_in_state_above_sla: bool,
+    pub outcome: Result<ExplainedOutcome<S>, StateHandlerError>,
+    /// Everything the handler would have changed, in the order it would have
+    /// happened
+    pub side_effects: Vec<SideEffect>,
+}
+
+/// Runs `handler` once on the current state of `object_id` without
+/// persisting anything.
+///
+/// `services` should be built so that their external clients record into
+/// `recorder` instead of acting; see the [module documentation](self). Writes
+/// the handler makes in the transaction it returns are rolled back, and
+/// writes it defers to the [`DbWriteBatch`] are never applied. Both are
+/// reported as side effects on [`DATABASE_SERVICE`].
+pub async fn explain_object<IO: StateControllerIO>(
+    pool: &PgPool,
+    io: &IO,
+    handler: &dyn StateHandler<
+        State = IO::State,
+        ControllerState = IO::ControllerState,
+        ContextObjects = IO::ContextObjects,
+        ObjectId = IO::ObjectId,
+    >,
+    object_id: &IO::ObjectId,
+    services: &mut <IO::ContextObjects as StateHandlerContextObjects>::Services,
+    recorder: &SideEffectRecorder,
+) -> Result<Explanation<IO::ControllerState>, StateHandlerError> {
+    let mut txn = pool.begin().await?;
+    let mut snapshot = io
+        .load_object_state(&mut txn, object_id)
+        .await?
+        .ok_or_else(|| StateHandlerError::MissingData {
+            object_id: object_id.to_string(),
+            missing: "object_state",
+        })?;
+    let controller_state = io
+        .load_controller_state(&mut txn, object_id, &snapshot)
+        .await?;
+    let state_sla = io.state_sla(&controller_state, &snapshot);
+    txn.rollback().await?;
+
+    let mut metrics = <IO::ContextObjects as StateHandlerContextObjects>::ObjectMetrics::default();
+    let mut pending_db_writes = DbWriteBatch::new();
+    let mut ctx = StateHandlerContext {
+        services,
+        metrics: &mut metrics,
+        pending_db_writes: &mut pending_db_writes,
+    };
+    let handler_output = handler
+        .handle_object_state(object_id, &mut snapshot, &controller_state.value, &mut ctx)
+        .await;
+
+    let outcome = match handler_output {
+        Ok(mut outcome) => {
+            if let Some(txn) = outcome.take_transaction() {
+                txn.rollback().await?;
+                recorder.record(
+                    SideEffect::new(
+                        DATABASE_SERVICE,
+                        "commit handler transaction",
+                        object_id.to_string(),
+                    )
+                    .with_details("statements the handler ran in the transaction were rolled back"),
+                );
+            }
+            for write in pending_db_writes.describe() {
+                recorder.record(SideEffect::new(
+                    DATABASE_SERVICE,
+                    write,
+                    object_id.to_string(),
+                ));
+            }
+            if let StateHandlerOutcome::Transition { next_state, .. } = &outcome {
+                recorder.record(
+                    SideEffect::new(
+                        DATABASE_SERVICE,
+                        "persist controller state",
+                        object_id.to_string(),
+                    )
+                    .with_details(format!("{next_state:?}")),
+                );
+            }
+            Ok(outcome.into())
+        }
+        Err(e) => Err(e),
+    };
+
+    Ok(Explanation {
+        controller_state: controller_state.value,
+        state_version: controller_state.version,
+        time_in_state_above_sla: state_sla.time_in_state_above_sla,
+        outcome,
+        side_effects: recorder.take(),
+    })
+}
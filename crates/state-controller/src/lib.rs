@@ -50,6 +50,7 @@ macro_rules! test_assert {
 pub mod config;
 pub mod controller;
 pub mod db_write_batch;
+pub mod explain;
 pub mod io;
 pub mod metrics;
 pub mod per_object;
@@ -18,6 +18,7 @@
 //! Generic state change emitter for broadcasting state transitions to registered hooks.
 
 use chrono::{DateTime, Utc};
+use config_version::ConfigVersion;
 
 /// Event emitted when a state transition occurs.
 ///
@@ -33,6 +34,8 @@ pub struct StateChangeEvent<'a, Id, S> {
     pub previous_state: Option<&'a S>,
     /// The new state after the transition.
     pub new_state: &'a S,
+    /// The version that was persisted together with `new_state`.
+    pub state_version: ConfigVersion,
     /// Timestamp when the state change occurred.
     pub timestamp: DateTime<Utc>,
 }
@@ -135,6 +138,7 @@ mod tests {
             object_id: &id,
             previous_state: None,
             new_state: &state,
+            state_version: ConfigVersion::initial(),
             timestamp: Utc::now(),
         });
     }
@@ -17,6 +17,7 @@
 use std::panic::Location;
 
 use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
 use db::DatabaseError;
 use model::controller_outcome::PersistentStateHandlerOutcome;
 use model::machine::ManagedHostState;
@@ -154,6 +155,11 @@ pub enum StateHandlerOutcome<S> {
     Wait {
         /// The reason we're waiting
         reason: String,
+        /// When the condition being waited on is time-based, the earliest time
+        /// at which re-running the handler can make progress. The object is
+        /// left out of periodic enqueuing until then, unless it is explicitly
+        /// enqueued in the meantime.
+        recheck_at: Option<DateTime<Utc>>,
         source_ref: &'static Location<'static>,
         txn: Option<PgTransaction<'static>>,
     },
@@ -189,10 +195,12 @@ impl<S> StateHandlerOutcome<S> {
         match self {
             Self::Wait {
                 reason,
+                recheck_at,
                 source_ref,
                 txn: _,
             } => Self::Wait {
                 reason,
+                recheck_at,
                 source_ref,
                 txn: transaction,
             },
@@ -240,11 +248,34 @@ impl<S> StateHandlerOutcome<S> {
     pub fn wait(reason: String) -> Self {
         StateHandlerOutcome::Wait {
             reason,
+            recheck_at: None,
             source_ref: Location::caller(),
             txn: None,
         }
     }
 
+    /// Waits for a condition that can not change before `recheck_at`, e.g. a
+    /// timer. The object is not handled again before that time unless it is
+    /// explicitly enqueued.
+    #[track_caller]
+    pub fn wait_until(reason: String, recheck_at: DateTime<Utc>) -> Self {
+        StateHandlerOutcome::Wait {
+            reason,
+            recheck_at: Some(recheck_at),
+            source_ref: Location::caller(),
+            txn: None,
+        }
+    }
+
+    /// Returns the time before which the object does not need to be handled
+    /// again, if the handler asked to wait until a certain time.
+    pub fn recheck_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            StateHandlerOutcome::Wait { recheck_at, .. } => *recheck_at,
+            _ => None,
+        }
+    }
+
     #[track_caller]
     pub fn deleted() -> Self {
         StateHandlerOutcome::Deleted {
@@ -512,6 +543,14 @@ mod tests {
         assert_eq!(source_ref.line(), line!() - 4);
         assert_eq!(source_ref.file(), file!());
 
+        let StateHandlerOutcome::<String>::Wait { source_ref, .. } =
+            StateHandlerOutcome::wait_until("reason".into(), Utc::now())
+        else {
+            unreachable!()
+        };
+        assert_eq!(source_ref.line(), line!() - 4);
+        assert_eq!(source_ref.file(), file!());
+
         let StateHandlerOutcome::<String>::Transition { source_ref, .. } =
             StateHandlerOutcome::transition("next".into())
         else {
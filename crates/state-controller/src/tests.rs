@@ -35,6 +35,8 @@ use tokio_util::sync::CancellationToken;
 
 use crate::config::IterationConfig;
 use crate::controller::{self, Enqueuer, QueuedObject, StateController};
+use crate::db_write_batch::WriteOp;
+use crate::explain::{ExplainedOutcome, SideEffect, SideEffectRecorder, explain_object};
 use crate::io::StateControllerIO;
 use crate::metrics::NoopMetricsEmitter;
 use crate::state_change_emitter::{StateChangeEmitterBuilder, StateChangeEvent, StateChangeHook};
@@ -472,7 +474,9 @@ async fn create_test_state_controller_tables(pool: &sqlx::PgPool) {
         "CREATE TABLE test_state_controller_queued_objects(
         object_id VARCHAR PRIMARY KEY,
         processed_by TEXT NULL,
-        processing_started_at timestamptz NOT NULL DEFAULT NOW()
+        processing_started_at timestamptz NOT NULL DEFAULT NOW(),
+        run_after timestamptz NULL,
+        notified boolean NOT NULL DEFAULT false
     );",
     )
     .execute(&mut *txn)
@@ -1205,6 +1209,7 @@ struct CapturedStateChange {
     object_id: String,
     previous_state: Option<TestObjectControllerState>,
     new_state: TestObjectControllerState,
+    state_version: ConfigVersion,
 }
 
 /// A hook that sends events through a channel for deterministic test verification
@@ -1228,6 +1233,7 @@ impl StateChangeHook<String, TestObjectControllerState> for ChannelHook {
             object_id: event.object_id.clone(),
             previous_state: event.previous_state.cloned(),
             new_state: event.new_state.clone(),
+            state_version: event.state_version,
         };
         let _ = self.sender.send(captured);
     }
@@ -1288,6 +1294,10 @@ async fn test_state_change_emitter_emits_events_on_transitions(
     assert_eq!(event2.object_id, obj.id);
     assert_eq!(event2.previous_state, Some(TestObjectControllerState::B));
     assert_eq!(event2.new_state, TestObjectControllerState::C);
+    assert_eq!(
+        event2.state_version.version_nr(),
+        event1.state_version.version_nr() + 1
+    );
 
     // Run third iteration: C -> do_nothing (no transition, no event)
     controller.run_single_iteration().await;
@@ -1883,3 +1893,342 @@ async fn test_lock_loss_requeues_without_publishing_the_transition(
 
     Ok(())
 }
+
+/// A state handler that waits on a timer that expires far in the future
+#[derive(Debug, Default, Clone)]
+struct TestTimerStateHandler {
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl StateHandler for TestTimerStateHandler {
+    type State = TestObject;
+    type ControllerState = TestObjectControllerState;
+    type ObjectId = String;
+    type ContextObjects = TestStateControllerContextObjects;
+
+    async fn handle_object_state(
+        &self,
+        _object_id: &String,
+        _state: &mut TestObject,
+        _controller_state: &Self::ControllerState,
+        _ctx: &mut StateHandlerContext<Self::ContextObjects>,
+    ) -> Result<StateHandlerOutcome<Self::ControllerState>, StateHandlerError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(StateHandlerOutcome::wait_until(
+            "Waiting for timer".to_string(),
+            chrono::Utc::now() + chrono::Duration::hours(1),
+        ))
+    }
+}
+
+#[carbide_macros::sqlx_test]
+async fn test_wait_until_skips_periodic_enqueuing(pool: sqlx::PgPool) -> eyre::Result<()> {
+    create_test_state_controller_tables(&pool).await;
+    let mut join_set = JoinSet::new();
+    let work_lock_manager_handle =
+        db::work_lock_manager::start(&mut join_set, pool.clone(), Default::default()).await?;
+
+    let mut txn = pool.begin().await?;
+    create_test_object("test-obj-1".to_string(), &mut txn).await;
+    txn.commit().await?;
+
+    let handler = TestTimerStateHandler::default();
+    let mut controller = StateController::<TestStateControllerIO>::builder()
+        .iteration_config(IterationConfig {
+            iteration_time: Duration::from_millis(50),
+            processor_dispatch_interval: Duration::from_millis(50),
+            ..Default::default()
+        })
+        .database(pool.clone(), work_lock_manager_handle)
+        .processor_id(uuid::Uuid::new_v4().to_string())
+        .services(Arc::new(()))
+        .state_handler(Arc::new(handler.clone()))
+        .build_for_manual_iterations(CancellationToken::new())?;
+
+    controller.run_single_iteration().await;
+    assert_eq!(handler.calls.load(Ordering::SeqCst), 1);
+
+    // The object stays queued until the deadline, which the periodic
+    // enqueuer does not override
+    controller.run_single_iteration().await;
+    controller.run_single_iteration().await;
+    assert_eq!(handler.calls.load(Ordering::SeqCst), 1);
+
+    let mut txn = pool.begin().await?;
+    let queued = controller::db::fetch_queued_objects(
+        &mut txn,
+        TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
+    )
+    .await?;
+    txn.commit().await?;
+    assert_eq!(
+        queued,
+        vec![QueuedObject {
+            object_id: "test-obj-1".to_string(),
+            processed_by: None,
+        }]
+    );
+
+    // The deadline is only capped by the metric hold time
+    let run_after: chrono::DateTime<chrono::Utc> = sqlx::query_scalar(
+        "SELECT run_after FROM test_state_controller_queued_objects WHERE object_id = 'test-obj-1'",
+    )
+    .fetch_one(&pool)
+    .await?;
+    let max_delay =
+        chrono::Duration::from_std(IterationConfig::default().max_recheck_delay()).unwrap();
+    assert!(run_after <= chrono::Utc::now() + max_delay);
+
+    // Explicitly requesting the object handles it before the deadline
+    let enqueuer = Enqueuer::<TestStateControllerIO>::new(pool.clone());
+    assert!(enqueuer.enqueue_object(&"test-obj-1".to_string()).await?);
+    controller.run_single_iteration().await;
+    assert_eq!(handler.calls.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
+
+#[carbide_macros::sqlx_test]
+async fn test_request_while_processing_is_not_lost(pool: sqlx::PgPool) -> eyre::Result<()> {
+    create_test_state_controller_tables(&pool).await;
+    let table_id = TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME;
+    let processor_id = "000000000001";
+    let object_ids = ["0".to_string(), "1".to_string()];
+
+    let mut txn = pool.begin().await?;
+    controller::db::queue_objects(&mut txn, table_id, &object_ids).await?;
+    let acquired = controller::db::acquire_queued_objects(
+        &mut txn,
+        table_id,
+        2,
+        processor_id,
+        Duration::from_secs(60),
+    )
+    .await?;
+    assert_eq!(acquired.len(), 2);
+
+    // "0" changes while it is processed. Requesting it again is recorded once.
+    assert_eq!(
+        controller::db::request_objects(&mut txn, table_id, &object_ids[..1]).await?,
+        1
+    );
+    assert_eq!(
+        controller::db::request_objects(&mut txn, table_id, &object_ids[..1]).await?,
+        0
+    );
+
+    // "0" is released for another run, even if its handler asked to wait
+    assert_eq!(
+        controller::db::defer_queued_objects(
+            &mut txn,
+            table_id,
+            &[(
+                "0".to_string(),
+                chrono::Utc::now() + chrono::Duration::hours(1)
+            )],
+            processor_id,
+        )
+        .await?,
+        1
+    );
+    assert_eq!(
+        controller::db::delete_queued_objects(&mut txn, table_id, &object_ids[1..], processor_id)
+            .await?,
+        1
+    );
+
+    let acquired = controller::db::acquire_queued_objects(
+        &mut txn,
+        table_id,
+        2,
+        processor_id,
+        Duration::from_secs(60),
+    )
+    .await?;
+    assert_eq!(
+        acquired,
+        vec![QueuedObject {
+            object_id: "0".to_string(),
+            processed_by: Some(processor_id.to_string()),
+        }]
+    );
+    txn.commit().await?;
+
+    Ok(())
+}
+
+#[carbide_macros::sqlx_test]
+async fn test_enqueued_objects_wake_up_the_processor(pool: sqlx::PgPool) -> eyre::Result<()> {
+    create_test_state_controller_tables(&pool).await;
+    let mut join_set = JoinSet::new();
+    let cancel_token = CancellationToken::new();
+    let work_lock_manager_handle =
+        db::work_lock_manager::start(&mut join_set, pool.clone(), Default::default()).await?;
+
+    let mut txn = pool.begin().await?;
+    create_test_object("test-obj-1".to_string(), &mut txn).await;
+    txn.commit().await?;
+
+    // Neither the periodic enqueuer nor the processor poll would run again
+    // during the test
+    let handler = TestTimerStateHandler::default();
+    StateController::<TestStateControllerIO>::builder()
+        .iteration_config(IterationConfig {
+            iteration_time: Duration::from_secs(3600),
+            processor_dispatch_interval: Duration::from_secs(3600),
+            ..Default::default()
+        })
+        .database(pool.clone(), work_lock_manager_handle)
+        .processor_id(uuid::Uuid::new_v4().to_string())
+        .services(Arc::new(()))
+        .state_handler(Arc::new(handler.clone()))
+        .build_and_spawn(&mut join_set, cancel_token.clone())?;
+
+    // Let the initial iterations of the enqueuer and processor finish
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let enqueuer = Enqueuer::<TestStateControllerIO>::new(pool.clone());
+    let calls_before = handler.calls.load(Ordering::SeqCst);
+    let deadline = std::time::Instant::now() + Duration::from_secs(10);
+    // The listener might not be listening yet, so keep requesting the object
+    while handler.calls.load(Ordering::SeqCst) == calls_before {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "The processor was not woken up"
+        );
+        enqueuer.enqueue_object(&"test-obj-1".to_string()).await?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    cancel_token.cancel();
+
+    Ok(())
+}
+
+/// Deferred write that sets the outcome column of a test object
+struct MarkOutcomeWriteOp {
+    object_id: String,
+}
+
+#[async_trait::async_trait]
+impl WriteOp for MarkOutcomeWriteOp {
+    async fn apply<'a, 't: 'a>(
+        self: Box<Self>,
+        txn: &'a mut sqlx::PgTransaction<'t>,
+    ) -> Result<(), StateHandlerError> {
+        sqlx::query("UPDATE test_objects SET controller_state_outcome = '{}' WHERE id = $1")
+            .bind(&self.object_id)
+            .execute(&mut **txn)
+            .await
+            .map_err(|e| DatabaseError::new("mark outcome", e))?;
+        Ok(())
+    }
+
+    fn describe(&self) -> String {
+        format!("mark outcome of {}", self.object_id)
+    }
+}
+
+/// A state handler with every kind of side effect: an external call, a write
+/// in the returned transaction, a deferred write and a transition
+#[derive(Debug)]
+struct SideEffectStateHandler {
+    pool: sqlx::PgPool,
+    recorder: SideEffectRecorder,
+}
+
+#[async_trait::async_trait]
+impl StateHandler for SideEffectStateHandler {
+    type State = TestObject;
+    type ControllerState = TestObjectControllerState;
+    type ObjectId = String;
+    type ContextObjects = TestStateControllerContextObjects;
+
+    async fn handle_object_state(
+        &self,
+        object_id: &String,
+        _state: &mut TestObject,
+        _controller_state: &Self::ControllerState,
+        ctx: &mut StateHandlerContext<Self::ContextObjects>,
+    ) -> Result<StateHandlerOutcome<Self::ControllerState>, StateHandlerError> {
+        self.recorder
+            .record(SideEffect::new("bmc", "power_cycle", object_id.clone()));
+        ctx.pending_db_writes.push(MarkOutcomeWriteOp {
+            object_id: object_id.clone(),
+        });
+        let mut txn = self.pool.begin().await?;
+        sqlx::query("UPDATE test_objects SET controller_state_outcome = '{}' WHERE id = $1")
+            .bind(object_id)
+            .execute(&mut *txn)
+            .await?;
+        Ok(StateHandlerOutcome::transition(TestObjectControllerState::B).with_txn(txn))
+    }
+}
+
+#[carbide_macros::sqlx_test]
+async fn test_explain_object_persists_nothing(pool: sqlx::PgPool) -> eyre::Result<()> {
+    create_test_state_controller_tables(&pool).await;
+
+    let mut txn = pool.begin().await?;
+    let object = create_test_object("test-obj-1".to_string(), &mut txn).await;
+    txn.commit().await?;
+
+    let recorder = SideEffectRecorder::new();
+    let handler = SideEffectStateHandler {
+        pool: pool.clone(),
+        recorder: recorder.clone(),
+    };
+    let explanation = explain_object(
+        &pool,
+        &TestStateControllerIO::default(),
+        &handler,
+        &object.id,
+        &mut (),
+        &recorder,
+    )
+    .await?;
+
+    assert_eq!(explanation.controller_state, TestObjectControllerState::A);
+    let outcome = explanation.outcome.expect("the handler succeeds");
+    assert!(matches!(
+        outcome,
+        ExplainedOutcome::Transition {
+            next_state: TestObjectControllerState::B,
+            ..
+        }
+    ));
+    assert_eq!(outcome.source_ref().file(), file!());
+    assert_eq!(
+        explanation
+            .side_effects
+            .iter()
+            .map(|effect| (effect.service, effect.operation.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("bmc", "power_cycle"),
+            ("database", "commit handler transaction"),
+            ("database", "mark outcome of test-obj-1"),
+            ("database", "persist controller state"),
+        ]
+    );
+
+    let mut txn = pool.begin().await?;
+    let stored = TestStateControllerIO::default()
+        .load_object_state(&mut txn, &object.id)
+        .await?
+        .expect("the object still exists");
+    assert_eq!(stored.controller_state.value, TestObjectControllerState::A);
+    assert_eq!(
+        stored.controller_state.version,
+        object.controller_state.version
+    );
+    let outcome: Option<serde_json::Value> =
+        sqlx::query_scalar("SELECT controller_state_outcome FROM test_objects WHERE id = $1")
+            .bind(&object.id)
+            .fetch_one(&mut *txn)
+            .await?;
+    assert_eq!(outcome, None);
+
+    Ok(())
+}
@@ -38,6 +38,12 @@ pub enum LoopManager {
     MachineValidationManager,
     /// The managed-host-state republisher's MQTT sweep (`nico-api`).
     ManagedHostStateRepublisher,
+    /// The NSG reference refresher's membership sweep (`nico-api`).
+    NetworkSecurityGroupReferenceRefresher,
+    /// The rack power budget manager's capping pass (`nico-api`).
+    RackPowerBudgetManager,
+    /// The idle host power manager's parking pass (`nico-api`).
+    IdleHostPowerManager,
     /// The BMC endpoint discovery pass (`nico-hardware-health`).
     HealthDiscovery,
 }